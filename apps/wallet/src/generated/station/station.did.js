@@ -1810,7 +1810,11 @@ export const idlFactory = ({ IDL }) => {
         [GetPermissionResult],
         ['query'],
       ),
-    'get_request' : IDL.Func([GetRequestInput], [GetRequestResult], ['query']),
+    'get_request' : IDL.Func(
+        [GetRequestInput],
+        [GetRequestResult],
+        ['composite_query'],
+      ),
     'get_request_policy' : IDL.Func(
         [GetRequestPolicyInput],
         [GetRequestPolicyResult],
@@ -1873,7 +1877,7 @@ export const idlFactory = ({ IDL }) => {
     'list_requests' : IDL.Func(
         [ListRequestsInput],
         [ListRequestsResult],
-        ['query'],
+        ['composite_query'],
       ),
     'list_user_groups' : IDL.Func(
         [ListUserGroupsInput],
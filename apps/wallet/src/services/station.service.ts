@@ -476,9 +476,7 @@ export class StationService {
       sortBy,
       onlyApprovable,
     }: ListRequestsArgs = {},
-    verifiedCall = false,
   ): Promise<ExtractOk<ListRequestsResult>> {
-    const actor = verifiedCall ? this.verified_actor : this.actor;
     const paginate: PaginationInput = {
       limit: limit ? [limit] : [],
       offset: offset ? [BigInt(offset)] : [],
@@ -499,7 +497,8 @@ export class StationService {
       ];
     }
 
-    const result = await actor.list_requests({
+    // composite queries can't be made as update calls, hence the requests are always queried
+    const result = await this.actor.list_requests({
       statuses: statuses ? [statuses] : [],
       created_from_dt: created_dt?.fromDt ? [created_dt.fromDt.toISOString()] : [],
       created_to_dt: created_dt?.toDt ? [created_dt.toDt.toISOString()] : [],
@@ -583,12 +582,9 @@ export class StationService {
     return result.Ok.request;
   }
 
-  async getRequest(
-    input: GetRequestInput,
-    verifiedCall = false,
-  ): Promise<ExtractOk<GetRequestResult>> {
-    const actor = verifiedCall ? this.verified_actor : this.actor;
-    const result = await actor.get_request(input);
+  async getRequest(input: GetRequestInput): Promise<ExtractOk<GetRequestResult>> {
+    // composite queries can't be made as update calls, hence the request is always queried
+    const result = await this.actor.get_request(input);
 
     if (variantIs(result, 'Err')) {
       throw result.Err;
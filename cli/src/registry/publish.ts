@@ -1,7 +1,7 @@
 import { execSync } from 'child_process';
 import { createCommand } from 'commander';
 import { rmSync } from 'fs';
-import { writeFile } from 'fs/promises';
+import { readFile, writeFile } from 'fs/promises';
 import { tmpdir } from 'os';
 import { join } from 'path';
 import { AddRegistryEntryResult, EditRegistryEntryResult } from '../generated/control_panel';
@@ -26,6 +26,7 @@ import {
 import { createHash } from 'crypto';
 
 const artifactsRootPath = join(__dirname, '../../../artifacts');
+const coreRootPath = join(__dirname, '../../../core');
 
 interface PackageDedependency {
   name: string;
@@ -128,6 +129,29 @@ command
     parseRegistryApplication,
   );
 
+// Escapes the given value to be used as a Candid text literal.
+const toCandidText = (value: string): string =>
+  value
+    .replace(/\\/g, '\\\\')
+    .replace(/"/g, '\\"')
+    .replace(/\n/g, '\\n')
+    .replace(/\r/g, '\\r')
+    .replace(/\t/g, '\\t');
+
+// Reads the Candid interface of the application and checks it with `didc`, the control panel only
+// checks that the uploaded interface declares a service to keep the Candid parser out of its wasm.
+const readCheckedCandidInterface = async (app: Application): Promise<string> => {
+  const candidPath = join(coreRootPath, app, 'api/spec.did');
+
+  try {
+    execSync(`didc check '${candidPath}'`, { stdio: 'pipe' });
+  } catch (e) {
+    throw new Error(`The Candid interface of ${app} is invalid: ${e}`);
+  }
+
+  return readFile(candidPath, { encoding: 'utf-8' });
+};
+
 // Saves the argument in a temporary file and returns the path to the file.
 const saveArgumentInTempFile = async (argument: string): Promise<string> => {
   const tempFilePath = join(tmpdir(), 'orbit-cli-argument-' + Math.random().toString(36).slice(2));
@@ -143,6 +167,7 @@ command.action(async options => {
   assertReplicaIsHealthy(options.network);
   assertCommandExists('dfx');
   assertCommandExists('icx-asset');
+  assertCommandExists('didc');
 
   const replicaUrl = await getReplicaUrl(options.network);
   const identityPemPath = await getIdentityPemFilePath(options.identity);
@@ -282,6 +307,62 @@ command.action(async options => {
 
       console.log(`Registry entry for ${entry.name} has been added with id ${result.Ok.entry.id}.`);
     }
+
+    // Publishes the Candid interface of the module, which is checked before since the control panel
+    // does not parse it.
+    const hasCandidInterface = foundEntries.some(
+      foundEntry =>
+        'CandidInterface' in foundEntry.value &&
+        foundEntry.value.CandidInterface.version === entry.version,
+    );
+
+    if (hasCandidInterface) {
+      console.log(`The Candid interface of ${entry.name} v${entry.version} is already published.`);
+      continue;
+    }
+
+    console.log(`Adding the Candid interface registry entry for ${entry.name}...`);
+    const candid = await readCheckedCandidInterface(registryEntryToApplicationMap[entry.name]);
+    const candidArgumentFile = await saveArgumentInTempFile(`
+      record {
+        entry = record {
+          name = "${entry.name}";
+          description = "The Candid interface of ${entry.name}.";
+          tags = vec { ${entry.tags.map(tag => `"${tag}"`).join('; ')} };
+          categories = vec { ${entry.categories.map(category => `"${category}"`).join('; ')} };
+          metadata = vec {
+            record {
+              key = "url";
+              value = "${entry.metadata.url}";
+            }
+          };
+          value = variant {
+            CandidInterface = record {
+              candid = "${toCandidText(candid)}";
+              version = "${entry.version}";
+              wasm_module = opt record { name = "${entry.name}"; version = "${entry.version}"; };
+            }
+          }
+        }
+      }
+    `);
+
+    const unparsedCandidResult = await execAsync(`
+      dfx canister call --identity '${options.identity}' --network '${options.network}' --output json control_panel add_registry_entry --argument-file '${candidArgumentFile}'
+    `);
+
+    rmSync(candidArgumentFile);
+
+    const candidResult: AddRegistryEntryResult = JSON.parse(unparsedCandidResult);
+    if ('Err' in candidResult) {
+      throw new Error(
+        `Failed to add the Candid interface registry entry: ${JSON.stringify(candidResult.Err)}`,
+      );
+    }
+
+    console.log(
+      `Candid interface registry entry for ${entry.name} has been added with id ${candidResult.Ok.entry.id}.`,
+    );
   }
 });
 
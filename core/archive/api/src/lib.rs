@@ -0,0 +1,66 @@
+use candid::{CandidType, Deserialize, Principal};
+
+/// The request id is the hyphenated UUID of the request in the station.
+pub type RequestIdDTO = String;
+
+#[derive(Clone, Debug, CandidType, serde::Serialize, Deserialize)]
+pub struct InitArg {
+    /// The station that owns the archive, only the station is allowed to append entries.
+    pub station_id: Principal,
+}
+
+#[derive(Clone, Debug, CandidType, serde::Serialize, Deserialize)]
+pub struct ArchivedRequestDTO {
+    /// The id of the request in the station.
+    pub request_id: RequestIdDTO,
+    /// The creation timestamp of the request in nanoseconds, used to support time range queries.
+    pub created_at: u64,
+    /// The timestamp in nanoseconds of when the request was archived.
+    pub archived_at: u64,
+    /// The request as encoded by the station.
+    #[serde(with = "serde_bytes")]
+    pub request: Vec<u8>,
+    /// The evaluation result of the request as encoded by the station, if any.
+    #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
+    pub evaluation_result: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Debug, CandidType, serde::Serialize, Deserialize)]
+pub struct AppendRequestsInput {
+    pub requests: Vec<ArchivedRequestDTO>,
+}
+
+#[derive(Clone, Debug, CandidType, serde::Serialize, Deserialize)]
+pub struct GetRequestsInput {
+    pub request_ids: Vec<RequestIdDTO>,
+}
+
+#[derive(Clone, Debug, CandidType, serde::Serialize, Deserialize)]
+pub struct GetRequestsResponse {
+    pub requests: Vec<ArchivedRequestDTO>,
+}
+
+#[derive(Clone, Debug, CandidType, serde::Serialize, Deserialize)]
+pub struct ListRequestsInput {
+    /// Lower bound (inclusive) of the request creation timestamp in nanoseconds.
+    pub created_from: Option<u64>,
+    /// Upper bound (inclusive) of the request creation timestamp in nanoseconds.
+    pub created_to: Option<u64>,
+    pub offset: Option<u64>,
+    pub limit: Option<u16>,
+}
+
+#[derive(Clone, Debug, CandidType, serde::Serialize, Deserialize)]
+pub struct ListRequestsResponse {
+    /// The archived requests ordered by their creation timestamp.
+    pub requests: Vec<ArchivedRequestDTO>,
+    pub next_offset: Option<u64>,
+    /// The number of archived requests, regardless of the creation time range.
+    pub total: u64,
+}
+
+#[derive(Clone, Debug, CandidType, serde::Serialize, Deserialize)]
+pub struct ArchiveStatsResponse {
+    pub station_id: Principal,
+    pub total_requests: u64,
+}
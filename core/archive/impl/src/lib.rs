@@ -0,0 +1,267 @@
+//! The archive canister stores requests that were moved out of the station.
+//!
+//! The archive is created and controlled by the station, it only accepts new entries from the station and
+//! exposes query methods that the station uses to transparently serve archived requests.
+use archive_api::{
+    AppendRequestsInput, ArchiveStatsResponse, ArchivedRequestDTO, GetRequestsInput,
+    GetRequestsResponse, InitArg, ListRequestsInput, ListRequestsResponse,
+};
+use candid::Principal;
+use ic_cdk::{init, post_upgrade, query, update};
+use ic_stable_structures::{
+    memory_manager::{MemoryId, MemoryManager, VirtualMemory},
+    DefaultMemoryImpl, StableBTreeMap,
+};
+use orbit_essentials::storable;
+use std::cell::RefCell;
+use uuid::Uuid;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+type StableMap<K, V> = StableBTreeMap<K, V, Memory>;
+type StableValue<T> = StableMap<(), T>;
+type RequestId = [u8; 16];
+
+/// Represents one mebibyte.
+pub const MIB: u32 = 1 << 20;
+
+/// Canisters use 64KiB pages for Wasm memory, more details in the PR that introduced this constant:
+/// - https://github.com/WebAssembly/design/pull/442#issuecomment-153203031
+pub const WASM_PAGE_SIZE: u32 = 65536;
+
+/// The size of the stable memory bucket in WASM pages.
+///
+/// Same bucket size as the other Orbit canisters, which limits the canister to 32GiB of stable memory.
+pub const STABLE_MEMORY_BUCKET_SIZE: u16 = (MIB / WASM_PAGE_SIZE) as u16;
+
+/// Current version of stable memory layout.
+pub const STABLE_MEMORY_VERSION: u32 = 1;
+
+/// The default number of requests returned by `list_requests`.
+const DEFAULT_LIST_LIMIT: u16 = 50;
+
+/// The maximum number of requests returned by `list_requests`, kept low since requests can be large.
+const MAX_LIST_LIMIT: u16 = 100;
+
+const MEMORY_ID_STATE: u8 = 0;
+const MEMORY_ID_REQUESTS: u8 = 1;
+const MEMORY_ID_REQUESTS_CREATED_AT_INDEX: u8 = 2;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init_with_bucket_size(DefaultMemoryImpl::default(), STABLE_MEMORY_BUCKET_SIZE));
+    static STATE: RefCell<StableValue<State>> = RefCell::new(
+        StableValue::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(MEMORY_ID_STATE))),
+        )
+    );
+    static REQUESTS: RefCell<StableMap<RequestId, ArchivedRequest>> = RefCell::new(
+        StableMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(MEMORY_ID_REQUESTS))),
+        )
+    );
+    static REQUESTS_CREATED_AT_INDEX: RefCell<StableMap<(u64, RequestId), ()>> = RefCell::new(
+        StableMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(MEMORY_ID_REQUESTS_CREATED_AT_INDEX))),
+        )
+    );
+}
+
+#[storable]
+struct State {
+    station_id: Principal,
+    stable_memory_version: u32,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            station_id: Principal::anonymous(),
+            stable_memory_version: STABLE_MEMORY_VERSION,
+        }
+    }
+}
+
+#[storable]
+#[derive(Clone, Debug)]
+struct ArchivedRequest {
+    created_at: u64,
+    archived_at: u64,
+    #[serde(with = "serde_bytes")]
+    request: Vec<u8>,
+    #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
+    evaluation_result: Option<Vec<u8>>,
+}
+
+impl ArchivedRequest {
+    fn into_dto(self, id: RequestId) -> ArchivedRequestDTO {
+        ArchivedRequestDTO {
+            request_id: Uuid::from_bytes(id).hyphenated().to_string(),
+            created_at: self.created_at,
+            archived_at: self.archived_at,
+            request: self.request,
+            evaluation_result: self.evaluation_result,
+        }
+    }
+}
+
+fn get_state() -> State {
+    STATE.with(|storage| storage.borrow().get(&()).unwrap_or_default())
+}
+
+fn set_state(state: State) {
+    STATE.with(|storage| storage.borrow_mut().insert((), state));
+}
+
+/// The number of archived requests, the stable map keeps its length in its header.
+fn total_requests() -> u64 {
+    REQUESTS.with(|db| db.borrow().len())
+}
+
+fn parse_request_id(id: &str) -> Result<RequestId, String> {
+    Uuid::parse_str(id)
+        .map(|uuid| *uuid.as_bytes())
+        .map_err(|e| format!("Invalid request id `{id}`: {e}"))
+}
+
+#[init]
+fn init_fn(InitArg { station_id }: InitArg) {
+    set_state(State {
+        station_id,
+        ..Default::default()
+    });
+}
+
+/// The archive is upgraded by the station, which is its only controller, through the `ManageSystemInfo`
+/// request with the `deploy` option of the request archive configuration.
+#[post_upgrade]
+fn post_upgrade() {
+    // basic health check
+    let _ = get_state();
+}
+
+#[update]
+fn append_requests(input: AppendRequestsInput) -> Result<(), String> {
+    let station_id = get_state().station_id;
+    if ic_cdk::caller() != station_id {
+        return Err(format!(
+            "Only the station {station_id} is authorized to call `append_requests`."
+        ));
+    }
+
+    // validates all the entries first to avoid partially appending the batch
+    let entries = input
+        .requests
+        .into_iter()
+        .map(|dto| {
+            parse_request_id(&dto.request_id).map(|id| {
+                (
+                    id,
+                    ArchivedRequest {
+                        created_at: dto.created_at,
+                        archived_at: dto.archived_at,
+                        request: dto.request,
+                        evaluation_result: dto.evaluation_result,
+                    },
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (id, entry) in entries {
+        let created_at = entry.created_at;
+        let prev = REQUESTS.with(|db| db.borrow_mut().insert(id, entry));
+
+        REQUESTS_CREATED_AT_INDEX.with(|index| {
+            let mut index = index.borrow_mut();
+            if let Some(prev) = prev {
+                index.remove(&(prev.created_at, id));
+            }
+
+            index.insert((created_at, id), ());
+        });
+    }
+
+    Ok(())
+}
+
+#[query]
+fn get_requests(input: GetRequestsInput) -> GetRequestsResponse {
+    let requests = input
+        .request_ids
+        .iter()
+        .filter_map(|id| parse_request_id(id).ok())
+        .filter_map(|id| {
+            REQUESTS
+                .with(|db| db.borrow().get(&id))
+                .map(|entry| entry.into_dto(id))
+        })
+        .collect();
+
+    GetRequestsResponse { requests }
+}
+
+#[query]
+fn list_requests(input: ListRequestsInput) -> ListRequestsResponse {
+    let from = (input.created_from.unwrap_or(u64::MIN), [u8::MIN; 16]);
+    let to = (input.created_to.unwrap_or(u64::MAX), [u8::MAX; 16]);
+    let offset = input.offset.unwrap_or(0);
+    let limit = input
+        .limit
+        .unwrap_or(DEFAULT_LIST_LIMIT)
+        .clamp(1, MAX_LIST_LIMIT) as u64;
+
+    // one more entry than the limit is taken to know if there is a next page without counting the range
+    let mut ids = REQUESTS_CREATED_AT_INDEX.with(|index| {
+        index
+            .borrow()
+            .range(from..=to)
+            .skip(offset as usize)
+            .take(limit as usize + 1)
+            .map(|((_, id), _)| id)
+            .collect::<Vec<_>>()
+    });
+    let has_next = ids.len() > limit as usize;
+    ids.truncate(limit as usize);
+
+    let requests = ids
+        .into_iter()
+        .filter_map(|id| {
+            REQUESTS
+                .with(|db| db.borrow().get(&id))
+                .map(|entry| entry.into_dto(id))
+        })
+        .collect::<Vec<_>>();
+
+    ListRequestsResponse {
+        requests,
+        next_offset: has_next.then_some(offset + limit),
+        total: total_requests(),
+    }
+}
+
+#[query]
+fn stats() -> ArchiveStatsResponse {
+    ArchiveStatsResponse {
+        station_id: get_state().station_id,
+        total_requests: total_requests(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_candid_interface() {
+        use candid_parser::utils::{service_equal, CandidSource};
+
+        candid::export_service!();
+        let new_interface = __export_service();
+
+        service_equal(
+            CandidSource::Text(&new_interface),
+            CandidSource::Text(include_str!("../../api/spec.did")),
+        )
+        .unwrap();
+    }
+}
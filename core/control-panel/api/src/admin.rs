@@ -0,0 +1,105 @@
+use crate::{PaginationInput, TimestampRfc3339, UserSubscriptionStatusDTO, UuidDTO};
+use candid::{CandidType, Deserialize, Principal};
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ManagedUserDTO {
+    pub id: UuidDTO,
+    pub identity: Principal,
+    pub subscription_status: UserSubscriptionStatusDTO,
+    /// The e-mail address of the user, only set while the user is on the waitlist.
+    pub email: Option<String>,
+    pub deployed_stations: Vec<Principal>,
+    pub deploy_quota: UserDeployQuotaDTO,
+    pub last_active: TimestampRfc3339,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct UserDeployQuotaDTO {
+    /// The maximum number of stations that can ever be deployed for the user, unlimited if not set.
+    pub max_deployed_stations: Option<u64>,
+    pub max_deployed_stations_per_day: u64,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ListUsersInput {
+    /// Only lists the users with the given subscription status, all users are listed if not set.
+    pub subscription_status: Option<UserSubscriptionStatusDTO>,
+    pub pagination: Option<PaginationInput>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ListUsersResponse {
+    pub users: Vec<ManagedUserDTO>,
+    pub total: u64,
+    pub next_offset: Option<u64>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SetUserSubscriptionStatusInput {
+    pub user_id: UuidDTO,
+    /// The new subscription status, users cannot be moved back to the waitlist.
+    pub subscription_status: UserSubscriptionStatusDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SetUserSubscriptionStatusResponse {
+    pub user: ManagedUserDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SetUserDeployQuotaInput {
+    pub user_id: UuidDTO,
+    pub quota: UserDeployQuotaDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SetUserDeployQuotaResponse {
+    pub user: ManagedUserDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct WaitlistEntryDTO {
+    pub user_id: UuidDTO,
+    pub identity: Principal,
+    pub email: String,
+    pub last_active: TimestampRfc3339,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ExportWaitlistInput {
+    pub pagination: Option<PaginationInput>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ExportWaitlistResponse {
+    pub entries: Vec<WaitlistEntryDTO>,
+    pub total: u64,
+    pub next_offset: Option<u64>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum AdminActionKindDTO {
+    SetUserSubscriptionStatus(SetUserSubscriptionStatusInput),
+    SetUserDeployQuota(SetUserDeployQuotaInput),
+    ExportWaitlist { num_users: u64 },
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct AdminActionDTO {
+    pub performed_by: Principal,
+    pub performed_at: TimestampRfc3339,
+    pub kind: AdminActionKindDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ListAdminActionsInput {
+    pub pagination: Option<PaginationInput>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ListAdminActionsResponse {
+    /// The logged actions, the most recent action first.
+    pub actions: Vec<AdminActionDTO>,
+    pub total: u64,
+    pub next_offset: Option<u64>,
+}
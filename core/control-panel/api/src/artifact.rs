@@ -20,3 +20,97 @@ pub struct ArtifactDTO {
 pub struct GetArtifactResponse {
     pub artifact: ArtifactDTO,
 }
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetArtifactChunkInput {
+    pub artifact_id: UuidDTO,
+    pub chunk_index: u64,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetArtifactChunkResponse {
+    #[serde(with = "serde_bytes")]
+    pub chunk: Vec<u8>,
+    pub chunk_index: u64,
+    pub chunk_count: u64,
+    pub size: u64,
+    pub hash: Sha256HashDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ArtifactUploadDTO {
+    pub id: UuidDTO,
+    pub hash: Sha256HashDTO,
+    pub size: u64,
+    pub chunk_size: u64,
+    pub missing_chunks: Vec<u64>,
+    pub created_at: TimestampRfc3339,
+    pub last_modification_timestamp: TimestampRfc3339,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct StartArtifactUploadInput {
+    pub hash: Sha256HashDTO,
+    pub size: u64,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct StartArtifactUploadResponse {
+    pub upload: ArtifactUploadDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetArtifactUploadInput {
+    pub upload_id: UuidDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetArtifactUploadResponse {
+    pub upload: ArtifactUploadDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct UploadArtifactChunkInput {
+    pub upload_id: UuidDTO,
+    pub chunk_index: u64,
+    #[serde(with = "serde_bytes")]
+    pub chunk: Vec<u8>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct UploadArtifactChunkResponse {
+    pub upload: ArtifactUploadDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct FinishArtifactUploadInput {
+    pub upload_id: UuidDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct FinishArtifactUploadResponse {
+    pub artifact_id: UuidDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ArtifactStorageStatsDTO {
+    pub artifacts: u64,
+    pub artifacts_size: u64,
+    pub chunks: u64,
+    pub stored_size: u64,
+    pub uploads: u64,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetArtifactStorageStatsResponse {
+    pub stats: ArtifactStorageStatsDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct CollectArtifactGarbageResponse {
+    pub removed_uploads: u64,
+    pub removed_artifacts: u64,
+    pub removed_chunks: u64,
+    pub freed_bytes: u64,
+    pub completed: bool,
+}
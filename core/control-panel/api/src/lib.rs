@@ -21,6 +21,10 @@ pub use user_station::*;
 mod manage_user;
 pub use manage_user::*;
 
+/// Admin DTOs.
+mod admin;
+pub use admin::*;
+
 /// Canister hooks DTOs.
 mod canister;
 pub use canister::*;
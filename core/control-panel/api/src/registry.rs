@@ -1,5 +1,5 @@
 use crate::{MetadataDTO, PaginationInput, SortDirection, TimestampRfc3339, UuidDTO};
-use candid::{CandidType, Deserialize};
+use candid::{CandidType, Deserialize, Principal};
 use orbit_essentials::types::WasmModuleExtraChunks;
 
 #[derive(CandidType, Deserialize, serde::Serialize, Debug, Clone, Eq, PartialEq)]
@@ -20,24 +20,73 @@ pub struct WasmModuleRegistryEntryValueDTO {
 pub struct WasmModuleRegistryEntryValueInput {
     #[serde(with = "serde_bytes")]
     pub wasm_module: Vec<u8>,
+    pub wasm_module_artifact_id: Option<UuidDTO>,
     pub version: String,
     pub dependencies: Vec<WasmModuleRegistryEntryDependencyDTO>,
     pub module_extra_chunks: Option<WasmModuleExtraChunks>,
 }
 
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct CandidInterfaceRegistryEntryValueDTO {
+    pub candid_artifact_id: UuidDTO,
+    pub version: String,
+    pub wasm_module: Option<WasmModuleRegistryEntryDependencyDTO>,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct CandidInterfaceRegistryEntryValueInput {
+    pub candid: String,
+    pub version: String,
+    pub wasm_module: Option<WasmModuleRegistryEntryDependencyDTO>,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct AssetBundleRegistryEntryValueDTO {
+    pub bundle_artifact_id: UuidDTO,
+    pub version: String,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct AssetBundleRegistryEntryValueInput {
+    #[serde(with = "serde_bytes")]
+    pub bundle: Vec<u8>,
+    pub version: String,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct StationConfigTemplateRegistryEntryValueDTO {
+    pub template_artifact_id: UuidDTO,
+    pub version: String,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct StationConfigTemplateRegistryEntryValueInput {
+    pub template: String,
+    pub version: String,
+}
+
 #[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
 pub enum RegistryEntryValueDTO {
     WasmModule(WasmModuleRegistryEntryValueDTO),
+    CandidInterface(CandidInterfaceRegistryEntryValueDTO),
+    AssetBundle(AssetBundleRegistryEntryValueDTO),
+    StationConfigTemplate(StationConfigTemplateRegistryEntryValueDTO),
 }
 
 #[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
 pub enum RegistryEntryValueInput {
     WasmModule(WasmModuleRegistryEntryValueInput),
+    CandidInterface(CandidInterfaceRegistryEntryValueInput),
+    AssetBundle(AssetBundleRegistryEntryValueInput),
+    StationConfigTemplate(StationConfigTemplateRegistryEntryValueInput),
 }
 
 #[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
 pub enum RegistryEntryValueKindDTO {
     WasmModule,
+    CandidInterface,
+    AssetBundle,
+    StationConfigTemplate,
 }
 
 #[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
@@ -150,3 +199,107 @@ pub struct NextWasmModuleVersionInput {
 pub struct NextWasmModuleVersionResponse {
     pub entry: Option<RegistryEntryDTO>,
 }
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct UpgradePathModuleInput {
+    pub name: String,
+    pub current_version: String,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct ResolveUpgradePathInput {
+    pub station: UpgradePathModuleInput,
+    pub upgrader: UpgradePathModuleInput,
+    pub target_version: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct UpgradePathStepDTO {
+    pub station: RegistryEntryDTO,
+    pub upgrader: Option<RegistryEntryDTO>,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct ResolveUpgradePathResponse {
+    pub steps: Vec<UpgradePathStepDTO>,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct RegistryNamespaceDTO {
+    pub name: String,
+    pub owners: Vec<Principal>,
+    pub publishers: Vec<Principal>,
+    pub created_at: TimestampRfc3339,
+    pub updated_at: Option<TimestampRfc3339>,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct AddRegistryNamespaceInput {
+    pub name: String,
+    pub owners: Vec<Principal>,
+    pub publishers: Vec<Principal>,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct AddRegistryNamespaceResponse {
+    pub namespace: RegistryNamespaceDTO,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct EditRegistryNamespaceInput {
+    pub name: String,
+    pub owners: Option<Vec<Principal>>,
+    pub publishers: Option<Vec<Principal>>,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct EditRegistryNamespaceResponse {
+    pub namespace: RegistryNamespaceDTO,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetRegistryNamespaceInput {
+    pub name: String,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetRegistryNamespaceResponse {
+    pub namespace: RegistryNamespaceDTO,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct RegistryEntryAttestationDTO {
+    pub attester: Principal,
+    pub build_hash: String,
+    pub source_repository: String,
+    pub source_commit: String,
+    pub attested_at: TimestampRfc3339,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct AttestRegistryEntryInput {
+    pub id: UuidDTO,
+    pub build_hash: String,
+    pub source_repository: String,
+    pub source_commit: String,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct AttestRegistryEntryResponse {
+    pub attestation: RegistryEntryAttestationDTO,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct VerifyRegistryEntryInput {
+    pub id: UuidDTO,
+    pub expected_hash: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct VerifyRegistryEntryResponse {
+    pub publisher: Option<Principal>,
+    pub publisher_authorized: bool,
+    pub content_hash: String,
+    pub attestations: Vec<RegistryEntryAttestationDTO>,
+    pub verified: bool,
+}
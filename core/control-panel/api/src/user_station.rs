@@ -19,6 +19,19 @@ pub struct DeployStationInput {
     pub admins: Vec<DeployStationAdminUserInput>,
     pub associate_with_caller: Option<AssociateWithCallerInput>,
     pub subnet_selection: Option<SubnetSelection>,
+    pub template: Option<DeployStationTemplateInput>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug)]
+pub enum DeployStationTemplateInput {
+    Registry(StationConfigTemplateReferenceInput),
+    Inline(String),
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug)]
+pub struct StationConfigTemplateReferenceInput {
+    pub name: String,
+    pub version: Option<String>,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -62,3 +75,9 @@ pub enum CanDeployStationResponse {
     Allowed(usize),
     QuotaExceeded,
 }
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SetStationUpgradeAnnouncementsInput {
+    pub station_id: Principal,
+    pub enabled: bool,
+}
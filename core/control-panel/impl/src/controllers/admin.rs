@@ -0,0 +1,146 @@
+//! Admin entrypoints, only available to the controllers of the control panel.
+use crate::core::middlewares::{call_context, logger, use_canister_call_metric};
+use crate::core::CallContext;
+use crate::services::{UserAdminService, USER_ADMIN_SERVICE};
+use control_panel_api::{
+    ExportWaitlistInput, ExportWaitlistResponse, ListAdminActionsInput, ListAdminActionsResponse,
+    ListUsersInput, ListUsersResponse, SetUserDeployQuotaInput, SetUserDeployQuotaResponse,
+    SetUserSubscriptionStatusInput, SetUserSubscriptionStatusResponse,
+};
+use ic_cdk_macros::{query, update};
+use lazy_static::lazy_static;
+use orbit_essentials::api::ApiResult;
+use orbit_essentials::with_middleware;
+use std::sync::Arc;
+
+// Canister entrypoints for the controller.
+
+#[query(name = "list_users")]
+async fn list_users(input: ListUsersInput) -> ApiResult<ListUsersResponse> {
+    CONTROLLER.list_users(input).await
+}
+
+#[update(name = "set_user_subscription_status")]
+async fn set_user_subscription_status(
+    input: SetUserSubscriptionStatusInput,
+) -> ApiResult<SetUserSubscriptionStatusResponse> {
+    CONTROLLER.set_user_subscription_status(input).await
+}
+
+#[update(name = "set_user_deploy_quota")]
+async fn set_user_deploy_quota(
+    input: SetUserDeployQuotaInput,
+) -> ApiResult<SetUserDeployQuotaResponse> {
+    CONTROLLER.set_user_deploy_quota(input).await
+}
+
+#[query(name = "export_waitlist")]
+async fn export_waitlist(input: ExportWaitlistInput) -> ApiResult<ExportWaitlistResponse> {
+    CONTROLLER.export_waitlist(input).await
+}
+
+#[query(name = "list_admin_actions")]
+async fn list_admin_actions(input: ListAdminActionsInput) -> ApiResult<ListAdminActionsResponse> {
+    CONTROLLER.list_admin_actions(input).await
+}
+
+// Controller initialization and implementation.
+lazy_static! {
+    static ref CONTROLLER: AdminController = AdminController::new(Arc::clone(&USER_ADMIN_SERVICE));
+}
+
+#[derive(Debug)]
+pub struct AdminController {
+    user_admin_service: Arc<UserAdminService>,
+}
+
+impl AdminController {
+    pub fn new(user_admin_service: Arc<UserAdminService>) -> Self {
+        Self { user_admin_service }
+    }
+
+    async fn list_users(&self, input: ListUsersInput) -> ApiResult<ListUsersResponse> {
+        let ctx = CallContext::get();
+        let paginated_result = self.user_admin_service.list_users(input, &ctx)?;
+
+        Ok(ListUsersResponse {
+            users: paginated_result
+                .items
+                .into_iter()
+                .map(|user| user.into())
+                .collect(),
+            total: paginated_result.total,
+            next_offset: paginated_result.next_offset,
+        })
+    }
+
+    #[with_middleware(
+        guard = logger::<()>(__target_fn, context, None),
+        tail = logger(__target_fn, context, Some(&result)),
+        context = &call_context()
+    )]
+    #[with_middleware(tail = use_canister_call_metric("set_user_subscription_status", &result))]
+    async fn set_user_subscription_status(
+        &self,
+        input: SetUserSubscriptionStatusInput,
+    ) -> ApiResult<SetUserSubscriptionStatusResponse> {
+        let ctx = CallContext::get();
+        let user = self
+            .user_admin_service
+            .set_user_subscription_status(input, &ctx)?;
+
+        Ok(SetUserSubscriptionStatusResponse { user: user.into() })
+    }
+
+    #[with_middleware(
+        guard = logger::<()>(__target_fn, context, None),
+        tail = logger(__target_fn, context, Some(&result)),
+        context = &call_context()
+    )]
+    #[with_middleware(tail = use_canister_call_metric("set_user_deploy_quota", &result))]
+    async fn set_user_deploy_quota(
+        &self,
+        input: SetUserDeployQuotaInput,
+    ) -> ApiResult<SetUserDeployQuotaResponse> {
+        let ctx = CallContext::get();
+        let user = self.user_admin_service.set_user_deploy_quota(input, &ctx)?;
+
+        Ok(SetUserDeployQuotaResponse { user: user.into() })
+    }
+
+    async fn export_waitlist(
+        &self,
+        input: ExportWaitlistInput,
+    ) -> ApiResult<ExportWaitlistResponse> {
+        let ctx = CallContext::get();
+        let paginated_result = self.user_admin_service.export_waitlist(input, &ctx)?;
+
+        Ok(ExportWaitlistResponse {
+            entries: paginated_result
+                .items
+                .into_iter()
+                .map(|user| user.into())
+                .collect(),
+            total: paginated_result.total,
+            next_offset: paginated_result.next_offset,
+        })
+    }
+
+    async fn list_admin_actions(
+        &self,
+        input: ListAdminActionsInput,
+    ) -> ApiResult<ListAdminActionsResponse> {
+        let ctx = CallContext::get();
+        let paginated_result = self.user_admin_service.list_admin_actions(input, &ctx)?;
+
+        Ok(ListAdminActionsResponse {
+            actions: paginated_result
+                .items
+                .into_iter()
+                .map(|action| action.into())
+                .collect(),
+            total: paginated_result.total,
+            next_offset: paginated_result.next_offset,
+        })
+    }
+}
@@ -1,12 +1,25 @@
 use crate::{
-    mappers::HelperMapper,
+    core::middlewares::{
+        call_context, use_can_upload_artifacts, use_canister_call_metric, use_is_authorized_admin,
+    },
+    errors::ArtifactError,
+    mappers::{ArtifactMapper, HelperMapper},
+    models::{ArtifactChunk, ArtifactGarbageCollection, ArtifactGarbageCollectionCursor},
     services::{ArtifactService, ARTIFACT_SERVICE},
 };
-use control_panel_api::{GetArtifactInput, GetArtifactResponse};
-use ic_cdk_macros::query;
+use control_panel_api::{
+    CollectArtifactGarbageResponse, FinishArtifactUploadInput, FinishArtifactUploadResponse,
+    GetArtifactChunkInput, GetArtifactChunkResponse, GetArtifactInput, GetArtifactResponse,
+    GetArtifactStorageStatsResponse, GetArtifactUploadInput, GetArtifactUploadResponse,
+    StartArtifactUploadInput, StartArtifactUploadResponse, UploadArtifactChunkInput,
+    UploadArtifactChunkResponse,
+};
+use ic_cdk_macros::{query, update};
+use ic_cdk_timers::set_timer;
 use lazy_static::lazy_static;
-use orbit_essentials::api::ApiResult;
-use std::sync::Arc;
+use orbit_essentials::{api::ApiResult, with_middleware};
+use std::{sync::Arc, time::Duration};
+use uuid::Uuid;
 
 // Canister entrypoints for the controller.
 #[query(name = "get_artifact")]
@@ -14,6 +27,49 @@ async fn get_artifact(input: GetArtifactInput) -> ApiResult<GetArtifactResponse>
     CONTROLLER.get_artifact(input).await
 }
 
+#[query(name = "get_artifact_chunk")]
+async fn get_artifact_chunk(input: GetArtifactChunkInput) -> ApiResult<GetArtifactChunkResponse> {
+    CONTROLLER.get_artifact_chunk(input).await
+}
+
+#[update(name = "start_artifact_upload")]
+async fn start_artifact_upload(
+    input: StartArtifactUploadInput,
+) -> ApiResult<StartArtifactUploadResponse> {
+    CONTROLLER.start_artifact_upload(input).await
+}
+
+#[query(name = "get_artifact_upload")]
+async fn get_artifact_upload(
+    input: GetArtifactUploadInput,
+) -> ApiResult<GetArtifactUploadResponse> {
+    CONTROLLER.get_artifact_upload(input).await
+}
+
+#[update(name = "upload_artifact_chunk")]
+async fn upload_artifact_chunk(
+    input: UploadArtifactChunkInput,
+) -> ApiResult<UploadArtifactChunkResponse> {
+    CONTROLLER.upload_artifact_chunk(input).await
+}
+
+#[update(name = "finish_artifact_upload")]
+async fn finish_artifact_upload(
+    input: FinishArtifactUploadInput,
+) -> ApiResult<FinishArtifactUploadResponse> {
+    CONTROLLER.finish_artifact_upload(input).await
+}
+
+#[query(name = "get_artifact_storage_stats")]
+async fn get_artifact_storage_stats() -> ApiResult<GetArtifactStorageStatsResponse> {
+    CONTROLLER.get_artifact_storage_stats().await
+}
+
+#[update(name = "collect_artifact_garbage")]
+async fn collect_artifact_garbage() -> ApiResult<CollectArtifactGarbageResponse> {
+    CONTROLLER.collect_artifact_garbage().await
+}
+
 // Controller initialization and implementation.
 lazy_static! {
     static ref CONTROLLER: ArtifactController =
@@ -37,11 +93,143 @@ impl ArtifactController {
                 .expect("Invalid artifact id")
                 .as_bytes(),
         )?;
+        let content = self.artifact_service.read_reply_content(&artifact)?;
 
         Ok(GetArtifactResponse {
-            artifact: artifact.into(),
+            artifact: ArtifactMapper::to_dto(artifact, content),
+        })
+    }
+
+    /// Returns the chunk of the artifact with the given index.
+    pub async fn get_artifact_chunk(
+        &self,
+        input: GetArtifactChunkInput,
+    ) -> ApiResult<GetArtifactChunkResponse> {
+        let artifact_id = HelperMapper::to_uuid(input.artifact_id)?;
+        let artifact = self.artifact_service.find_by_id(artifact_id.as_bytes())?;
+        let chunk = self
+            .artifact_service
+            .read_chunk(&artifact, input.chunk_index)?;
+
+        Ok(GetArtifactChunkResponse {
+            chunk,
+            chunk_index: input.chunk_index,
+            chunk_count: ArtifactChunk::count_for_size(artifact.size()),
+            size: artifact.size(),
+            hash: hex::encode(artifact.hash()),
+        })
+    }
+
+    /// Starts or resumes the upload of an artifact.
+    #[with_middleware(guard = use_can_upload_artifacts(&call_context()))]
+    #[with_middleware(tail = use_canister_call_metric("start_artifact_upload", &result))]
+    pub async fn start_artifact_upload(
+        &self,
+        input: StartArtifactUploadInput,
+    ) -> ApiResult<StartArtifactUploadResponse> {
+        let hash = hex::decode(&input.hash).map_err(|_| ArtifactError::ValidationError {
+            info: "The hash of the artifact must be hex encoded".to_string(),
+        })?;
+        let upload = self
+            .artifact_service
+            .start_upload(hash, input.size, &call_context())?;
+
+        Ok(StartArtifactUploadResponse {
+            upload: upload.into(),
+        })
+    }
+
+    /// Returns the upload of the caller with the given id.
+    pub async fn get_artifact_upload(
+        &self,
+        input: GetArtifactUploadInput,
+    ) -> ApiResult<GetArtifactUploadResponse> {
+        let upload_id = HelperMapper::to_uuid(input.upload_id)?;
+        let upload = self
+            .artifact_service
+            .get_upload(upload_id.as_bytes(), &call_context())?;
+
+        Ok(GetArtifactUploadResponse {
+            upload: upload.into(),
+        })
+    }
+
+    /// Uploads a chunk of an artifact.
+    #[with_middleware(guard = use_can_upload_artifacts(&call_context()))]
+    #[with_middleware(tail = use_canister_call_metric("upload_artifact_chunk", &result))]
+    pub async fn upload_artifact_chunk(
+        &self,
+        input: UploadArtifactChunkInput,
+    ) -> ApiResult<UploadArtifactChunkResponse> {
+        let upload_id = HelperMapper::to_uuid(input.upload_id)?;
+        let upload = self.artifact_service.upload_chunk(
+            upload_id.as_bytes(),
+            input.chunk_index,
+            input.chunk,
+            &call_context(),
+        )?;
+
+        Ok(UploadArtifactChunkResponse {
+            upload: upload.into(),
         })
     }
+
+    /// Finishes the upload of an artifact.
+    #[with_middleware(guard = use_can_upload_artifacts(&call_context()))]
+    #[with_middleware(tail = use_canister_call_metric("finish_artifact_upload", &result))]
+    pub async fn finish_artifact_upload(
+        &self,
+        input: FinishArtifactUploadInput,
+    ) -> ApiResult<FinishArtifactUploadResponse> {
+        let upload_id = HelperMapper::to_uuid(input.upload_id)?;
+        let artifact_id = self
+            .artifact_service
+            .finish_upload(upload_id.as_bytes(), &call_context())?;
+
+        Ok(FinishArtifactUploadResponse {
+            artifact_id: Uuid::from_bytes(artifact_id).to_string(),
+        })
+    }
+
+    /// Returns the statistics of the artifact storage.
+    #[with_middleware(guard = use_is_authorized_admin(&call_context()))]
+    pub async fn get_artifact_storage_stats(&self) -> ApiResult<GetArtifactStorageStatsResponse> {
+        Ok(GetArtifactStorageStatsResponse {
+            stats: self.artifact_service.storage_stats().into(),
+        })
+    }
+
+    /// Removes expired uploads and unreferenced artifacts and chunks.
+    ///
+    /// Only the first batch is collected in this call, the remaining ones continue in the background.
+    #[with_middleware(guard = use_is_authorized_admin(&call_context()))]
+    #[with_middleware(tail = use_canister_call_metric("collect_artifact_garbage", &result))]
+    pub async fn collect_artifact_garbage(&self) -> ApiResult<CollectArtifactGarbageResponse> {
+        let mut collection = ArtifactGarbageCollection::default();
+        let next = self
+            .artifact_service
+            .collect_garbage(ArtifactGarbageCollectionCursor::default(), &mut collection);
+        let completed = next.is_none();
+
+        if let Some(cursor) = next {
+            collect_artifact_garbage_in_batches(cursor);
+        }
+
+        Ok(ArtifactMapper::to_garbage_collection_response(
+            collection, completed,
+        ))
+    }
+}
+
+/// Collects the artifact garbage in batches, each in its own message so that every batch stays
+/// within the instruction limit.
+pub fn collect_artifact_garbage_in_batches(cursor: ArtifactGarbageCollectionCursor) {
+    set_timer(Duration::from_millis(0), move || {
+        let mut collection = ArtifactGarbageCollection::default();
+        if let Some(next) = ARTIFACT_SERVICE.collect_garbage(cursor, &mut collection) {
+            collect_artifact_garbage_in_batches(next);
+        }
+    });
 }
 
 #[cfg(test)]
@@ -80,4 +268,29 @@ mod tests {
 
         assert!(response.is_err());
     }
+
+    #[tokio::test]
+    async fn test_large_artifacts_are_only_returned_in_chunks() {
+        let artifact_data = vec![1; ArtifactService::MAX_REPLY_CONTENT_SIZE as usize + 1];
+        let artifact_id = ARTIFACT_SERVICE.create(artifact_data.clone()).unwrap();
+
+        let response = CONTROLLER
+            .get_artifact(GetArtifactInput {
+                artifact_id: Uuid::from_bytes(artifact_id).to_string(),
+            })
+            .await;
+
+        assert!(response.is_err());
+
+        let response = CONTROLLER
+            .get_artifact_chunk(GetArtifactChunkInput {
+                artifact_id: Uuid::from_bytes(artifact_id).to_string(),
+                chunk_index: 1,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.chunk_count, 2);
+        assert_eq!(response.chunk, vec![1]);
+    }
 }
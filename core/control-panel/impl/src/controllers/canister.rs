@@ -1,8 +1,9 @@
 //! Canister lifecycle hooks.
-use super::AVAILABLE_TOKENS_USER_REGISTRATION;
+use super::{collect_artifact_garbage_in_batches, AVAILABLE_TOKENS_USER_REGISTRATION};
 use crate::core::ic_cdk::{api::set_certified_data, spawn};
 use crate::core::metrics::recompute_all_metrics;
-use crate::services::{CANISTER_SERVICE, USER_SERVICE};
+use crate::models::{ArtifactGarbageCollectionCursor, ArtifactId};
+use crate::services::{ARTIFACT_SERVICE, CANISTER_SERVICE, STATION_CONFIG_TEMPLATE_SERVICE};
 use control_panel_api::UploadCanisterModulesInput;
 use ic_cdk_macros::{init, post_upgrade};
 use ic_cdk_timers::{set_timer, set_timer_interval};
@@ -35,6 +36,14 @@ fn init_timers_fn() {
             ic_cdk_timers::set_timer(std::time::Duration::from_secs(60), move || {
                 spawn(initialize_rng_timer())
             });
+            return;
+        }
+
+        // the registry entries of the built-in templates need random ids
+        if let Err(e) = STATION_CONFIG_TEMPLATE_SERVICE.publish_builtin_templates() {
+            ic_cdk::print(format!(
+                "publishing the built-in station templates failed: {e}"
+            ));
         }
     }
 
@@ -54,6 +63,20 @@ fn init_timers_fn() {
             });
         },
     );
+
+    set_timer_interval(Duration::from_secs(DAY), || {
+        collect_artifact_garbage_in_batches(ArtifactGarbageCollectionCursor::default());
+    });
+}
+
+/// Migrates the inline artifacts in batches, each in its own message so that the upgrade and every
+/// batch stay within the instruction limit.
+fn migrate_inline_artifacts(after: Option<ArtifactId>) {
+    if let Some(last_checked) = ARTIFACT_SERVICE.migrate_inline_artifacts(after) {
+        set_timer(Duration::from_millis(0), move || {
+            migrate_inline_artifacts(Some(last_checked))
+        });
+    }
 }
 
 #[init]
@@ -73,8 +96,8 @@ async fn post_upgrade() {
     recompute_all_metrics();
     init_timers_fn();
 
-    // approve all users
-    USER_SERVICE.approve_all_users();
+    // artifacts used to be stored inline, before they were split into content-addressed chunks
+    set_timer(Duration::from_millis(0), || migrate_inline_artifacts(None));
 
     CANISTER_SERVICE
         .init_canister()
@@ -1,5 +1,9 @@
 //! Canister controller entrypoints.
 
+/// Admin entrypoints.
+mod admin;
+pub use admin::*;
+
 /// Artifact entrypoints.
 mod artifact;
 pub use artifact::*;
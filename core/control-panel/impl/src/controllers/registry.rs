@@ -1,13 +1,24 @@
 use crate::{
-    core::middlewares::{call_context, use_canister_call_metric, use_is_authorized_admin},
+    core::middlewares::{
+        call_context, use_can_manage_registry_namespace, use_can_publish_registry_entry,
+        use_can_publish_to_namespace, use_canister_call_metric, use_is_authorized_admin,
+    },
+    errors::RegistryError,
     mappers::HelperMapper,
-    services::{RegistryService, REGISTRY_SERVICE},
+    services::{
+        RegistryNamespaceService, RegistryService, StationUpgradeAnnouncementService,
+        REGISTRY_NAMESPACE_SERVICE, REGISTRY_SERVICE, STATION_UPGRADE_ANNOUNCEMENT_SERVICE,
+    },
 };
 use control_panel_api::{
-    AddRegistryEntryInput, AddRegistryEntryResponse, DeleteRegistryEntryInput,
-    DeleteRegistryEntryResponse, EditRegistryEntryInput, EditRegistryEntryResponse,
-    GetRegistryEntryInput, GetRegistryEntryResponse, NextWasmModuleVersionInput,
-    NextWasmModuleVersionResponse, SearchRegistryInput, SearchRegistryResponse,
+    AddRegistryEntryInput, AddRegistryEntryResponse, AddRegistryNamespaceInput,
+    AddRegistryNamespaceResponse, AttestRegistryEntryInput, AttestRegistryEntryResponse,
+    DeleteRegistryEntryInput, DeleteRegistryEntryResponse, EditRegistryEntryInput,
+    EditRegistryEntryResponse, EditRegistryNamespaceInput, EditRegistryNamespaceResponse,
+    GetRegistryEntryInput, GetRegistryEntryResponse, GetRegistryNamespaceInput,
+    GetRegistryNamespaceResponse, NextWasmModuleVersionInput, NextWasmModuleVersionResponse,
+    ResolveUpgradePathInput, ResolveUpgradePathResponse, SearchRegistryInput,
+    SearchRegistryResponse, VerifyRegistryEntryInput, VerifyRegistryEntryResponse,
 };
 use ic_cdk_macros::{query, update};
 use lazy_static::lazy_static;
@@ -32,6 +43,13 @@ async fn next_wasm_module_version(
     CONTROLLER.next_wasm_module_version(input).await
 }
 
+#[query(name = "resolve_upgrade_path")]
+async fn resolve_upgrade_path(
+    input: ResolveUpgradePathInput,
+) -> ApiResult<ResolveUpgradePathResponse> {
+    CONTROLLER.resolve_upgrade_path(input).await
+}
+
 #[update(name = "add_registry_entry")]
 async fn add_registry_entry(input: AddRegistryEntryInput) -> ApiResult<AddRegistryEntryResponse> {
     CONTROLLER.add_registry_entry(input).await
@@ -51,20 +69,68 @@ async fn delete_registry_entry(
     CONTROLLER.delete_registry_entry(input).await
 }
 
+#[update(name = "add_registry_namespace")]
+async fn add_registry_namespace(
+    input: AddRegistryNamespaceInput,
+) -> ApiResult<AddRegistryNamespaceResponse> {
+    CONTROLLER.add_registry_namespace(input).await
+}
+
+#[update(name = "edit_registry_namespace")]
+async fn edit_registry_namespace(
+    input: EditRegistryNamespaceInput,
+) -> ApiResult<EditRegistryNamespaceResponse> {
+    CONTROLLER.edit_registry_namespace(input).await
+}
+
+#[query(name = "get_registry_namespace")]
+async fn get_registry_namespace(
+    input: GetRegistryNamespaceInput,
+) -> ApiResult<GetRegistryNamespaceResponse> {
+    CONTROLLER.get_registry_namespace(input).await
+}
+
+#[update(name = "attest_registry_entry")]
+async fn attest_registry_entry(
+    input: AttestRegistryEntryInput,
+) -> ApiResult<AttestRegistryEntryResponse> {
+    CONTROLLER.attest_registry_entry(input).await
+}
+
+#[query(name = "verify_registry_entry")]
+async fn verify_registry_entry(
+    input: VerifyRegistryEntryInput,
+) -> ApiResult<VerifyRegistryEntryResponse> {
+    CONTROLLER.verify_registry_entry(input).await
+}
+
 // Controller initialization and implementation.
 lazy_static! {
-    static ref CONTROLLER: RegistryController =
-        RegistryController::new(Arc::clone(&REGISTRY_SERVICE));
+    static ref CONTROLLER: RegistryController = RegistryController::new(
+        Arc::clone(&REGISTRY_SERVICE),
+        Arc::clone(&REGISTRY_NAMESPACE_SERVICE),
+        Arc::clone(&STATION_UPGRADE_ANNOUNCEMENT_SERVICE)
+    );
 }
 
 #[derive(Debug)]
 pub struct RegistryController {
     registry_service: Arc<RegistryService>,
+    registry_namespace_service: Arc<RegistryNamespaceService>,
+    station_upgrade_announcement_service: Arc<StationUpgradeAnnouncementService>,
 }
 
 impl RegistryController {
-    pub fn new(registry_service: Arc<RegistryService>) -> Self {
-        Self { registry_service }
+    pub fn new(
+        registry_service: Arc<RegistryService>,
+        registry_namespace_service: Arc<RegistryNamespaceService>,
+        station_upgrade_announcement_service: Arc<StationUpgradeAnnouncementService>,
+    ) -> Self {
+        Self {
+            registry_service,
+            registry_namespace_service,
+            station_upgrade_announcement_service,
+        }
     }
 
     /// Returns the registry entry by id.
@@ -97,6 +163,18 @@ impl RegistryController {
         })
     }
 
+    /// Returns the steps to upgrade a station and its upgrader to the target station version.
+    pub async fn resolve_upgrade_path(
+        &self,
+        input: ResolveUpgradePathInput,
+    ) -> ApiResult<ResolveUpgradePathResponse> {
+        let steps = self.registry_service.resolve_upgrade_path(input)?;
+
+        Ok(ResolveUpgradePathResponse {
+            steps: steps.into_iter().map(|step| step.into()).collect(),
+        })
+    }
+
     /// Searches the registry for entries.
     pub async fn search_registry(
         &self,
@@ -116,13 +194,16 @@ impl RegistryController {
     }
 
     /// Adds a new registry entry.
-    #[with_middleware(guard = use_is_authorized_admin(&call_context()))]
+    #[with_middleware(guard = use_can_publish_to_namespace(&call_context(), &input.entry.name))]
     #[with_middleware(tail = use_canister_call_metric("add_registry_entry", &result))]
     pub async fn add_registry_entry(
         &self,
         input: AddRegistryEntryInput,
     ) -> ApiResult<AddRegistryEntryResponse> {
-        let new_entry = self.registry_service.create(input.entry)?;
+        let new_entry = self.registry_service.create(input.entry, &call_context())?;
+
+        self.station_upgrade_announcement_service
+            .announce(&new_entry);
 
         Ok(AddRegistryEntryResponse {
             entry: new_entry.into(),
@@ -130,7 +211,7 @@ impl RegistryController {
     }
 
     /// Edits an existing registry entry.
-    #[with_middleware(guard = use_is_authorized_admin(&call_context()))]
+    #[with_middleware(guard = use_can_publish_registry_entry(&call_context(), &input.id))]
     #[with_middleware(tail = use_canister_call_metric("edit_registry_entry", &result))]
     pub async fn edit_registry_entry(
         &self,
@@ -147,7 +228,7 @@ impl RegistryController {
     }
 
     /// Deletes an existing registry entry.
-    #[with_middleware(guard = use_is_authorized_admin(&call_context()))]
+    #[with_middleware(guard = use_can_publish_registry_entry(&call_context(), &input.id))]
     #[with_middleware(tail = use_canister_call_metric("delete_registry_entry", &result))]
     pub async fn delete_registry_entry(
         &self,
@@ -160,14 +241,93 @@ impl RegistryController {
             entry: deleted_entry.into(),
         })
     }
+
+    /// Registers a new namespace.
+    #[with_middleware(guard = use_is_authorized_admin(&call_context()))]
+    #[with_middleware(tail = use_canister_call_metric("add_registry_namespace", &result))]
+    pub async fn add_registry_namespace(
+        &self,
+        input: AddRegistryNamespaceInput,
+    ) -> ApiResult<AddRegistryNamespaceResponse> {
+        let namespace = self.registry_namespace_service.create(input)?;
+
+        Ok(AddRegistryNamespaceResponse {
+            namespace: namespace.into(),
+        })
+    }
+
+    /// Edits the owners and publishers of a namespace.
+    #[with_middleware(guard = use_can_manage_registry_namespace(&call_context(), &input.name))]
+    #[with_middleware(tail = use_canister_call_metric("edit_registry_namespace", &result))]
+    pub async fn edit_registry_namespace(
+        &self,
+        input: EditRegistryNamespaceInput,
+    ) -> ApiResult<EditRegistryNamespaceResponse> {
+        let namespace = self.registry_namespace_service.edit(input)?;
+
+        Ok(EditRegistryNamespaceResponse {
+            namespace: namespace.into(),
+        })
+    }
+
+    /// Returns the namespace by name.
+    pub async fn get_registry_namespace(
+        &self,
+        input: GetRegistryNamespaceInput,
+    ) -> ApiResult<GetRegistryNamespaceResponse> {
+        let namespace = self.registry_namespace_service.get(&input.name)?;
+
+        Ok(GetRegistryNamespaceResponse {
+            namespace: namespace.into(),
+        })
+    }
+
+    /// Attests the content of an existing registry entry.
+    #[with_middleware(guard = use_can_publish_registry_entry(&call_context(), &input.id))]
+    #[with_middleware(tail = use_canister_call_metric("attest_registry_entry", &result))]
+    pub async fn attest_registry_entry(
+        &self,
+        input: AttestRegistryEntryInput,
+    ) -> ApiResult<AttestRegistryEntryResponse> {
+        let entry_id = HelperMapper::to_uuid(input.id.clone()).expect("Invalid registry entry id");
+        let attestation =
+            self.registry_service
+                .attest(entry_id.as_bytes(), input, &call_context())?;
+
+        Ok(AttestRegistryEntryResponse {
+            attestation: attestation.into(),
+        })
+    }
+
+    /// Verifies the provenance of a registry entry.
+    pub async fn verify_registry_entry(
+        &self,
+        input: VerifyRegistryEntryInput,
+    ) -> ApiResult<VerifyRegistryEntryResponse> {
+        let entry_id = HelperMapper::to_uuid(input.id)?;
+        let expected_hash = input
+            .expected_hash
+            .map(|hash| {
+                hex::decode(hash).map_err(|_| RegistryError::ValidationError {
+                    info: "The expected hash must be hex encoded".to_string(),
+                })
+            })
+            .transpose()?;
+
+        let verification = self
+            .registry_service
+            .verify(entry_id.as_bytes(), expected_hash)?;
+
+        Ok(verification.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::ic_cdk::{api::id as self_canister_id, set_caller};
-    use crate::errors::RegistryError;
-    use orbit_essentials::api::ApiError;
+    use candid::Principal;
+    use orbit_essentials::{api::ApiError, utils::sha256_hash};
     use uuid::Uuid;
 
     #[tokio::test]
@@ -201,6 +361,7 @@ mod tests {
                             version: "0.1.0".to_string(),
                             dependencies: vec![],
                             wasm_module: vec![],
+                            wasm_module_artifact_id: None,
                             module_extra_chunks: None,
                         },
                     ),
@@ -226,6 +387,7 @@ mod tests {
                             version: "0.1.0".to_string(),
                             dependencies: vec![],
                             wasm_module: vec![1, 2, 3],
+                            wasm_module_artifact_id: None,
                             module_extra_chunks: None,
                         },
                     ),
@@ -294,6 +456,7 @@ mod tests {
                             version: "0.1.0".to_string(),
                             dependencies: vec![],
                             wasm_module: vec![1, 2, 3],
+                            wasm_module_artifact_id: None,
                             module_extra_chunks: None,
                         },
                     ),
@@ -365,6 +528,7 @@ mod tests {
                             version: "0.1.0".to_string(),
                             dependencies: vec![],
                             wasm_module: vec![1, 2, 3],
+                            wasm_module_artifact_id: None,
                             module_extra_chunks: None,
                         },
                     ),
@@ -380,4 +544,131 @@ mod tests {
             .await
             .unwrap();
     }
+
+    fn namespaced_entry_input(name: &str) -> control_panel_api::AddRegistryEntryInput {
+        control_panel_api::AddRegistryEntryInput {
+            entry: control_panel_api::RegistryEntryInput {
+                name: name.to_string(),
+                description: "This is a demo description for the package.".to_string(),
+                categories: vec![],
+                tags: vec![],
+                metadata: vec![],
+                value: control_panel_api::RegistryEntryValueInput::WasmModule(
+                    control_panel_api::WasmModuleRegistryEntryValueInput {
+                        version: "0.1.0".to_string(),
+                        dependencies: vec![],
+                        wasm_module: vec![1, 2, 3],
+                        wasm_module_artifact_id: None,
+                        module_extra_chunks: None,
+                    },
+                ),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_namespace_publisher_can_publish_and_attest() {
+        let owner = Principal::from_slice(&[1; 29]);
+
+        set_caller(self_canister_id());
+        CONTROLLER
+            .add_registry_namespace(AddRegistryNamespaceInput {
+                name: "publisher".to_string(),
+                owners: vec![owner],
+                publishers: vec![],
+            })
+            .await
+            .unwrap();
+
+        set_caller(owner);
+        let entry = CONTROLLER
+            .add_registry_entry(namespaced_entry_input("@publisher/module"))
+            .await
+            .unwrap()
+            .entry;
+
+        let build_hash = hex::encode(sha256_hash(&[1, 2, 3]));
+        let verification = CONTROLLER
+            .verify_registry_entry(VerifyRegistryEntryInput {
+                id: entry.id.clone(),
+                expected_hash: Some(build_hash.clone()),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(verification.publisher, Some(owner));
+        assert!(verification.publisher_authorized);
+        assert!(!verification.verified);
+
+        CONTROLLER
+            .attest_registry_entry(AttestRegistryEntryInput {
+                id: entry.id.clone(),
+                build_hash: build_hash.clone(),
+                source_repository: "https://github.com/dfinity/orbit".to_string(),
+                source_commit: "a".repeat(40),
+            })
+            .await
+            .unwrap();
+
+        let verification = CONTROLLER
+            .verify_registry_entry(VerifyRegistryEntryInput {
+                id: entry.id.clone(),
+                expected_hash: Some(build_hash),
+            })
+            .await
+            .unwrap();
+
+        assert!(verification.verified);
+
+        let verification = CONTROLLER
+            .verify_registry_entry(VerifyRegistryEntryInput {
+                id: entry.id,
+                expected_hash: Some(hex::encode([0; 32])),
+            })
+            .await
+            .unwrap();
+
+        assert!(!verification.verified);
+    }
+
+    #[tokio::test]
+    async fn test_attestation_must_match_content() {
+        set_caller(self_canister_id());
+
+        let entry = CONTROLLER
+            .add_registry_entry(namespaced_entry_input("attested"))
+            .await
+            .unwrap()
+            .entry;
+
+        let result = CONTROLLER
+            .attest_registry_entry(AttestRegistryEntryInput {
+                id: entry.id,
+                build_hash: hex::encode([0; 32]),
+                source_repository: "https://github.com/dfinity/orbit".to_string(),
+                source_commit: "a".repeat(40),
+            })
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_cannot_publish_to_namespace_of_others() {
+        set_caller(self_canister_id());
+        CONTROLLER
+            .add_registry_namespace(AddRegistryNamespaceInput {
+                name: "others".to_string(),
+                owners: vec![Principal::from_slice(&[1; 29])],
+                publishers: vec![],
+            })
+            .await
+            .unwrap();
+
+        set_caller(Principal::from_slice(&[2; 29]));
+        let _ = CONTROLLER
+            .add_registry_entry(namespaced_entry_input("@others/module"))
+            .await;
+    }
 }
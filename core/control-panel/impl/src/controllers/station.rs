@@ -10,7 +10,8 @@ use crate::{core::CallContext, services::UserService};
 use candid::Principal;
 use control_panel_api::{
     CanDeployStationResponse, DeployStationInput, DeployStationResponse, ListUserStationsInput,
-    ListUserStationsResponse, ManageUserStationsInput, UserStationDTO,
+    ListUserStationsResponse, ManageUserStationsInput, SetStationUpgradeAnnouncementsInput,
+    UserStationDTO,
 };
 use ic_cdk_macros::{query, update};
 use lazy_static::lazy_static;
@@ -46,6 +47,13 @@ async fn can_deploy_station() -> ApiResult<CanDeployStationResponse> {
     CONTROLLER.can_deploy_station().await
 }
 
+#[update(name = "set_station_upgrade_announcements")]
+async fn set_station_upgrade_announcements(
+    input: SetStationUpgradeAnnouncementsInput,
+) -> ApiResult<()> {
+    CONTROLLER.set_station_upgrade_announcements(input).await
+}
+
 // Controller initialization and implementation.
 lazy_static! {
     static ref CONTROLLER: StationController = StationController::new(
@@ -161,4 +169,20 @@ impl StationController {
             .can_deploy_station(&ctx)
             .map(|can_deploy_station| can_deploy_station.into())
     }
+
+    /// Enables or disables the announcements of new station versions for a station deployed for the user.
+    #[with_middleware(tail = use_canister_call_metric("set_station_upgrade_announcements", &result))]
+    async fn set_station_upgrade_announcements(
+        &self,
+        input: SetStationUpgradeAnnouncementsInput,
+    ) -> ApiResult<()> {
+        let ctx = CallContext::get();
+        self.user_service.set_station_upgrade_announcements(
+            input.station_id,
+            input.enabled,
+            &ctx,
+        )?;
+
+        Ok(())
+    }
 }
@@ -17,6 +17,11 @@ pub const ARTIFACT_INDEX_MEMORY_ID: MemoryId = MemoryId::new(5);
 pub const REGISTRY_MEMORY_ID: MemoryId = MemoryId::new(6);
 pub const REGISTRY_INDEX_MEMORY_ID: MemoryId = MemoryId::new(7);
 pub const REGISTRY_SORT_INDEX_MEMORY_ID: MemoryId = MemoryId::new(8);
+pub const REGISTRY_NAMESPACE_MEMORY_ID: MemoryId = MemoryId::new(9);
+pub const ARTIFACT_CHUNK_MEMORY_ID: MemoryId = MemoryId::new(10);
+pub const ARTIFACT_UPLOAD_MEMORY_ID: MemoryId = MemoryId::new(11);
+pub const ADMIN_ACTION_MEMORY_ID: MemoryId = MemoryId::new(12);
+pub const ARTIFACT_CHUNK_SIZE_MEMORY_ID: MemoryId = MemoryId::new(13);
 
 thread_local! {
   /// Static configuration of the canister.
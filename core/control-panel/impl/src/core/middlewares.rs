@@ -1,8 +1,15 @@
 use super::CallContext;
-use crate::{core::ic_cdk, SERVICE_NAME};
+use crate::{
+    core::ic_cdk,
+    mappers::{HelperMapper, RegistryMapper},
+    repositories::REGISTRY_REPOSITORY,
+    services::REGISTRY_NAMESPACE_SERVICE,
+    SERVICE_NAME,
+};
 use orbit_essentials::{
     api::ApiResult,
     metrics::{labels, with_metrics_registry},
+    repository::Repository,
 };
 use serde::Serialize;
 
@@ -77,3 +84,46 @@ pub fn use_is_authorized_admin(ctx: &CallContext) {
         ic_cdk::api::trap("You are not authorized to perform this action");
     }
 }
+
+/// Trap the execution of the canister call if the caller cannot publish an entry with the given name.
+pub fn use_can_publish_to_namespace(ctx: &CallContext, entry_name: &str) {
+    let (namespace, _) = RegistryMapper::split_name(entry_name);
+
+    if !REGISTRY_NAMESPACE_SERVICE.can_publish(ctx, &namespace) {
+        ic_cdk::api::trap("You are not authorized to publish to this namespace");
+    }
+}
+
+/// Trap the execution of the canister call if the caller cannot publish to the namespace of the entry.
+///
+/// Admins are always allowed, so that they get a not found error for entries that do not exist.
+pub fn use_can_publish_registry_entry(ctx: &CallContext, entry_id: &str) {
+    if ctx.is_admin() {
+        return;
+    }
+
+    let namespace = HelperMapper::to_uuid(entry_id.to_string())
+        .ok()
+        .and_then(|id| REGISTRY_REPOSITORY.get(id.as_bytes()))
+        .map(|entry| entry.namespace);
+
+    match namespace {
+        Some(namespace) if REGISTRY_NAMESPACE_SERVICE.can_publish(ctx, &namespace) => {}
+        _ => ic_cdk::api::trap("You are not authorized to publish to this namespace"),
+    }
+}
+
+/// Trap the execution of the canister call if the caller cannot upload artifacts, which is reserved to
+/// the principals that can publish registry entries.
+pub fn use_can_upload_artifacts(ctx: &CallContext) {
+    if !REGISTRY_NAMESPACE_SERVICE.can_publish_to_any(ctx) {
+        ic_cdk::api::trap("You are not authorized to upload artifacts");
+    }
+}
+
+/// Trap the execution of the canister call if the caller cannot manage the namespace.
+pub fn use_can_manage_registry_namespace(ctx: &CallContext, namespace: &str) {
+    if !REGISTRY_NAMESPACE_SERVICE.can_manage(ctx, namespace) {
+        ic_cdk::api::trap("You are not authorized to manage this namespace");
+    }
+}
@@ -11,6 +11,21 @@ pub enum ArtifactError {
     /// The artifact was not found.
     #[error("The artifact with id {id} was not found.")]
     NotFound { id: String },
+    /// The artifact upload was not found.
+    #[error("The artifact upload with id {id} was not found.")]
+    UploadNotFound { id: String },
+    /// The artifact upload still has chunks that were not uploaded.
+    #[error("The artifact upload is missing {missing_chunks} chunks.")]
+    UploadIncomplete { missing_chunks: u64 },
+    /// The content of the artifact does not match the expected hash.
+    #[error("The artifact hash {actual} does not match the expected hash {expected}.")]
+    HashMismatch { expected: String, actual: String },
+    /// A chunk of the artifact was not found.
+    #[error("The artifact chunk with hash {hash} was not found.")]
+    ChunkNotFound { hash: String },
+    /// The artifact is too large to be returned in a single reply.
+    #[error("The artifact of {size} bytes exceeds the {max_size} bytes that can be returned at once, it needs to be fetched chunk by chunk.")]
+    TooLargeForReply { size: u64, max_size: u64 },
 }
 
 impl DetailableError for ArtifactError {
@@ -21,10 +36,28 @@ impl DetailableError for ArtifactError {
                 details.insert("info".to_string(), info.to_string());
                 Some(details)
             }
-            ArtifactError::NotFound { id } => {
+            ArtifactError::NotFound { id } | ArtifactError::UploadNotFound { id } => {
                 details.insert("id".to_string(), id.to_string());
                 Some(details)
             }
+            ArtifactError::UploadIncomplete { missing_chunks } => {
+                details.insert("missing_chunks".to_string(), missing_chunks.to_string());
+                Some(details)
+            }
+            ArtifactError::HashMismatch { expected, actual } => {
+                details.insert("expected".to_string(), expected.to_string());
+                details.insert("actual".to_string(), actual.to_string());
+                Some(details)
+            }
+            ArtifactError::ChunkNotFound { hash } => {
+                details.insert("hash".to_string(), hash.to_string());
+                Some(details)
+            }
+            ArtifactError::TooLargeForReply { size, max_size } => {
+                details.insert("size".to_string(), size.to_string());
+                details.insert("max_size".to_string(), max_size.to_string());
+                Some(details)
+            }
         }
     }
 }
@@ -8,13 +8,19 @@ pub enum DeployError {
     /// The deployment of the station canister failed.
     #[error(r#"The deployment of the station canister failed due to `{reason}`"#)]
     Failed { reason: String },
+    /// The station config template cannot be used for the deployment.
+    #[error(r#"The station config template is invalid: {reason}"#)]
+    InvalidTemplate { reason: String },
 }
 
 impl DetailableError for DeployError {
     fn details(&self) -> Option<HashMap<String, String>> {
         let mut details = HashMap::new();
-        let DeployError::Failed { reason } = self;
-        details.insert("reason".to_string(), reason.to_string());
+        match self {
+            DeployError::Failed { reason } | DeployError::InvalidTemplate { reason } => {
+                details.insert("reason".to_string(), reason.to_string());
+            }
+        }
         Some(details)
     }
 }
@@ -20,6 +20,21 @@ pub enum RegistryError {
     /// WasmModule with name not found.
     #[error("Wasm module with name {name} not found.")]
     WasmModuleNotFound { name: String },
+    /// Station config template with name not found.
+    #[error("Station config template with name {name} not found.")]
+    StationConfigTemplateNotFound { name: String },
+    /// The namespace is not registered.
+    #[error("The namespace {namespace} is not registered.")]
+    NamespaceNotFound { namespace: String },
+    /// The namespace is already registered.
+    #[error("The namespace {namespace} is already registered.")]
+    NamespaceAlreadyExists { namespace: String },
+    /// The attested build hash does not match the content of the entry.
+    #[error("The build hash {actual} does not match the content hash {expected}.")]
+    AttestationHashMismatch { expected: String, actual: String },
+    /// No consistent upgrade path was found.
+    #[error("No upgrade path was found: {reason}")]
+    UpgradePathNotFound { reason: String },
 }
 
 impl DetailableError for RegistryError {
@@ -38,10 +53,25 @@ impl DetailableError for RegistryError {
                 details.insert("kind".to_string(), kind.to_string());
                 Some(details)
             }
-            RegistryError::WasmModuleNotFound { name } => {
+            RegistryError::WasmModuleNotFound { name }
+            | RegistryError::StationConfigTemplateNotFound { name } => {
                 details.insert("name".to_string(), name.to_string());
                 Some(details)
             }
+            RegistryError::NamespaceNotFound { namespace }
+            | RegistryError::NamespaceAlreadyExists { namespace } => {
+                details.insert("namespace".to_string(), namespace.to_string());
+                Some(details)
+            }
+            RegistryError::UpgradePathNotFound { reason } => {
+                details.insert("reason".to_string(), reason.to_string());
+                Some(details)
+            }
+            RegistryError::AttestationHashMismatch { expected, actual } => {
+                details.insert("expected".to_string(), expected.to_string());
+                details.insert("actual".to_string(), actual.to_string());
+                Some(details)
+            }
         }
     }
 }
@@ -0,0 +1,87 @@
+use crate::models::{
+    indexes::user_status_index::UserIndexSubscriptionStatus, AdminAction, AdminActionKind, User,
+};
+use control_panel_api::{
+    AdminActionDTO, AdminActionKindDTO, ManagedUserDTO, SetUserDeployQuotaInput,
+    SetUserSubscriptionStatusInput, UserDeployQuotaDTO, UserSubscriptionStatusDTO,
+    WaitlistEntryDTO,
+};
+use orbit_essentials::utils::timestamp_to_rfc3339;
+use uuid::Uuid;
+
+impl From<User> for ManagedUserDTO {
+    fn from(user: User) -> Self {
+        ManagedUserDTO {
+            id: Uuid::from_bytes(user.id).hyphenated().to_string(),
+            identity: user.identity,
+            email: user.get_waitlist_email().map(|email| email.to_string()),
+            deployed_stations: user.get_deployed_stations(),
+            deploy_quota: UserDeployQuotaDTO {
+                max_deployed_stations: user.get_max_deployed_stations().map(|max| max as u64),
+                max_deployed_stations_per_day: user.get_max_deployed_stations_per_day() as u64,
+            },
+            last_active: timestamp_to_rfc3339(&user.last_active),
+            subscription_status: user.subscription_status.into(),
+        }
+    }
+}
+
+impl From<User> for WaitlistEntryDTO {
+    fn from(user: User) -> Self {
+        WaitlistEntryDTO {
+            user_id: Uuid::from_bytes(user.id).hyphenated().to_string(),
+            identity: user.identity,
+            email: user.get_waitlist_email().unwrap_or_default().to_string(),
+            last_active: timestamp_to_rfc3339(&user.last_active),
+        }
+    }
+}
+
+impl From<AdminAction> for AdminActionDTO {
+    fn from(action: AdminAction) -> Self {
+        AdminActionDTO {
+            performed_by: action.performed_by,
+            performed_at: timestamp_to_rfc3339(&action.performed_at),
+            kind: action.kind.into(),
+        }
+    }
+}
+
+impl From<AdminActionKind> for AdminActionKindDTO {
+    fn from(kind: AdminActionKind) -> Self {
+        match kind {
+            AdminActionKind::SetUserSubscriptionStatus {
+                user_id,
+                subscription_status,
+            } => AdminActionKindDTO::SetUserSubscriptionStatus(SetUserSubscriptionStatusInput {
+                user_id: Uuid::from_bytes(user_id).hyphenated().to_string(),
+                subscription_status: subscription_status.into(),
+            }),
+            AdminActionKind::SetUserDeployQuota {
+                user_id,
+                max_deployed_stations,
+                max_deployed_stations_per_day,
+            } => AdminActionKindDTO::SetUserDeployQuota(SetUserDeployQuotaInput {
+                user_id: Uuid::from_bytes(user_id).hyphenated().to_string(),
+                quota: UserDeployQuotaDTO {
+                    max_deployed_stations: max_deployed_stations.map(|max| max as u64),
+                    max_deployed_stations_per_day: max_deployed_stations_per_day as u64,
+                },
+            }),
+            AdminActionKind::ExportWaitlist { num_users } => AdminActionKindDTO::ExportWaitlist {
+                num_users: num_users as u64,
+            },
+        }
+    }
+}
+
+impl From<UserSubscriptionStatusDTO> for UserIndexSubscriptionStatus {
+    fn from(status: UserSubscriptionStatusDTO) -> Self {
+        match status {
+            UserSubscriptionStatusDTO::Unsubscribed => UserIndexSubscriptionStatus::Unsubscribed,
+            UserSubscriptionStatusDTO::Pending => UserIndexSubscriptionStatus::Pending,
+            UserSubscriptionStatusDTO::Approved => UserIndexSubscriptionStatus::Approved,
+            UserSubscriptionStatusDTO::Denylisted => UserIndexSubscriptionStatus::Denylisted,
+        }
+    }
+}
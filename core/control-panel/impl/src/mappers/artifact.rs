@@ -1,17 +1,63 @@
-use crate::models::Artifact;
+use crate::models::{
+    Artifact, ArtifactChunk, ArtifactGarbageCollection, ArtifactStorageStats, ArtifactUpload,
+};
 use orbit_essentials::utils::timestamp_to_rfc3339;
 use uuid::Uuid;
 
-impl From<Artifact> for control_panel_api::ArtifactDTO {
-    fn from(model: Artifact) -> Self {
+#[derive(Default, Clone, Debug)]
+pub struct ArtifactMapper {}
+
+impl ArtifactMapper {
+    /// Maps the artifact and its content, which is stored separately, to the transport type.
+    pub fn to_dto(model: Artifact, content: Vec<u8>) -> control_panel_api::ArtifactDTO {
         control_panel_api::ArtifactDTO {
             id: Uuid::from_bytes(*model.id()).to_string(),
-            size: model.artifact().len() as u64,
+            size: content.len() as u64,
             hash: hex::encode(model.hash()),
-            artifact: model.artifact().to_vec(),
+            artifact: content,
             created_at: timestamp_to_rfc3339(&model.created_at()),
         }
     }
+
+    /// Maps what a garbage collection call removed to the transport type.
+    pub fn to_garbage_collection_response(
+        collection: ArtifactGarbageCollection,
+        completed: bool,
+    ) -> control_panel_api::CollectArtifactGarbageResponse {
+        control_panel_api::CollectArtifactGarbageResponse {
+            removed_uploads: collection.removed_uploads,
+            removed_artifacts: collection.removed_artifacts,
+            removed_chunks: collection.removed_chunks,
+            freed_bytes: collection.freed_bytes,
+            completed,
+        }
+    }
+}
+
+impl From<ArtifactUpload> for control_panel_api::ArtifactUploadDTO {
+    fn from(upload: ArtifactUpload) -> Self {
+        control_panel_api::ArtifactUploadDTO {
+            id: Uuid::from_bytes(upload.id).to_string(),
+            hash: hex::encode(&upload.hash),
+            size: upload.size,
+            chunk_size: ArtifactChunk::MAX_SIZE as u64,
+            missing_chunks: upload.missing_chunks(),
+            created_at: timestamp_to_rfc3339(&upload.created_at),
+            last_modification_timestamp: timestamp_to_rfc3339(&upload.last_modification_timestamp),
+        }
+    }
+}
+
+impl From<ArtifactStorageStats> for control_panel_api::ArtifactStorageStatsDTO {
+    fn from(stats: ArtifactStorageStats) -> Self {
+        control_panel_api::ArtifactStorageStatsDTO {
+            artifacts: stats.artifacts,
+            artifacts_size: stats.artifacts_size,
+            chunks: stats.chunks,
+            stored_size: stats.stored_size,
+            uploads: stats.uploads,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -21,15 +67,15 @@ mod tests {
     #[test]
     fn valid_model_conversion() {
         let model = Artifact::new(b"hello world".to_vec());
-        let dto = control_panel_api::ArtifactDTO::from(model.clone());
+        let dto = ArtifactMapper::to_dto(model.clone(), b"hello world".to_vec());
 
         assert_eq!(dto.id, Uuid::from_bytes(*model.id()).to_string());
-        assert_eq!(dto.size, model.artifact().len() as u64);
+        assert_eq!(dto.size, model.size());
         assert_eq!(
             dto.hash,
             "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9".to_string()
         );
-        assert_eq!(dto.artifact, model.artifact());
+        assert_eq!(dto.artifact, b"hello world");
         assert_eq!(dto.created_at, timestamp_to_rfc3339(&model.created_at()));
     }
 }
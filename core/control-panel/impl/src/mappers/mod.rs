@@ -1,6 +1,9 @@
 //! Mappers are used to facilitate the conversion between transport types and internal types.
 
+mod admin;
+
 mod artifact;
+pub use artifact::*;
 
 mod user;
 
@@ -1,7 +1,9 @@
 use super::HelperMapper;
 use crate::models::{
-    RegistryEntry, RegistryValue, RegistryValueKind, WasmModuleRegistryEntryDependency,
-    WasmModuleRegistryValue,
+    ArtifactId, AssetBundleRegistryValue, CandidInterfaceRegistryValue, RegistryEntry,
+    RegistryEntryAttestation, RegistryEntryVerification, RegistryNamespace, RegistryValue,
+    RegistryValueKind, StationConfigTemplateRegistryValue, UpgradePathStep,
+    WasmModuleRegistryEntryDependency, WasmModuleRegistryValue,
 };
 use orbit_essentials::utils::timestamp_to_rfc3339;
 use uuid::Uuid;
@@ -14,25 +16,7 @@ impl RegistryMapper {
         entry: &mut RegistryEntry,
         input: &control_panel_api::RegistryEntryInput,
     ) {
-        // If the name starts with the namespace prefix and contains a slash, we assume that the
-        // namespace is part of the name and extract it.
-        //
-        // The validation of the format is done at a later point when entry.validate() is called before saving.
-        let mut name = input.name.clone();
-        let mut namespace = RegistryEntry::DEFAULT_NAMESPACE.to_string();
-        if name.starts_with(RegistryEntry::NAMESPACE_PREFIX) && name.contains('/') {
-            let cloned_name = name.clone();
-            let name_without_prefix =
-                cloned_name.trim_start_matches(RegistryEntry::NAMESPACE_PREFIX);
-            let mut parts = name_without_prefix.split('/');
-
-            if let Some(found_namespace) = parts.next() {
-                if let Some(found_name) = parts.next() {
-                    name = found_name.to_string();
-                    namespace = found_namespace.to_string();
-                }
-            }
-        }
+        let (namespace, name) = Self::split_name(&input.name);
 
         entry.namespace = namespace;
         entry.name = name;
@@ -41,11 +25,19 @@ impl RegistryMapper {
         entry.categories.clone_from(&input.categories);
         entry.metadata = HelperMapper::from_metadata(input.metadata.clone());
 
-        match &input.value {
+        // a placeholder uuid that will be replaced later
+        entry.value = Self::value_from_input(&input.value, [0; 16]);
+    }
+
+    /// Maps the value input to a registry value whose content is stored in the given artifact.
+    pub fn value_from_input(
+        input: &control_panel_api::RegistryEntryValueInput,
+        artifact_id: ArtifactId,
+    ) -> RegistryValue {
+        match input {
             control_panel_api::RegistryEntryValueInput::WasmModule(wasm_module) => {
-                entry.value = RegistryValue::WasmModule(WasmModuleRegistryValue {
-                    // a placeholder uuid that will be replaced later
-                    wasm_artifact_id: [0; 16],
+                RegistryValue::WasmModule(WasmModuleRegistryValue {
+                    wasm_artifact_id: artifact_id,
                     version: wasm_module.version.clone(),
                     dependencies: wasm_module
                         .dependencies
@@ -53,11 +45,62 @@ impl RegistryMapper {
                         .map(|dep| dep.clone().into())
                         .collect(),
                     module_extra_chunks: wasm_module.module_extra_chunks.clone(),
-                });
+                })
+            }
+            control_panel_api::RegistryEntryValueInput::CandidInterface(candid_interface) => {
+                RegistryValue::CandidInterface(CandidInterfaceRegistryValue {
+                    candid_artifact_id: artifact_id,
+                    version: candid_interface.version.clone(),
+                    wasm_module: candid_interface.wasm_module.clone().map(Into::into),
+                })
+            }
+            control_panel_api::RegistryEntryValueInput::AssetBundle(asset_bundle) => {
+                RegistryValue::AssetBundle(AssetBundleRegistryValue {
+                    bundle_artifact_id: artifact_id,
+                    version: asset_bundle.version.clone(),
+                })
+            }
+            control_panel_api::RegistryEntryValueInput::StationConfigTemplate(template) => {
+                RegistryValue::StationConfigTemplate(StationConfigTemplateRegistryValue {
+                    template_artifact_id: artifact_id,
+                    version: template.version.clone(),
+                })
             }
         }
     }
 
+    /// Splits the name of an entry into its namespace and name.
+    ///
+    /// If the name starts with the namespace prefix and contains a slash, we assume that the
+    /// namespace is part of the name and extract it, otherwise the default namespace is used.
+    ///
+    /// The validation of the format is done at a later point when entry.validate() is called before saving.
+    pub fn split_name(input: &str) -> (String, String) {
+        let mut name = input.to_string();
+        let mut namespace = RegistryEntry::DEFAULT_NAMESPACE.to_string();
+        if name.starts_with(RegistryEntry::NAMESPACE_PREFIX) && name.contains('/') {
+            let name_without_prefix = input.trim_start_matches(RegistryEntry::NAMESPACE_PREFIX);
+            let mut parts = name_without_prefix.split('/');
+
+            if let Some(found_namespace) = parts.next() {
+                if let Some(found_name) = parts.next() {
+                    name = found_name.to_string();
+                    namespace = found_namespace.to_string();
+                }
+            }
+        }
+
+        (namespace, name)
+    }
+
+    /// Returns the namespace name without the namespace prefix.
+    pub fn to_namespace_name(input: &str) -> String {
+        input
+            .strip_prefix(RegistryEntry::NAMESPACE_PREFIX)
+            .unwrap_or(input)
+            .to_string()
+    }
+
     pub fn fill_from_update_input(
         entry: &mut RegistryEntry,
         input: &control_panel_api::RegistryEntryUpdateInput,
@@ -84,6 +127,9 @@ impl From<&RegistryValue> for RegistryValueKind {
     fn from(value: &RegistryValue) -> Self {
         match value {
             RegistryValue::WasmModule(_) => RegistryValueKind::WasmModule,
+            RegistryValue::CandidInterface(_) => RegistryValueKind::CandidInterface,
+            RegistryValue::AssetBundle(_) => RegistryValueKind::AssetBundle,
+            RegistryValue::StationConfigTemplate(_) => RegistryValueKind::StationConfigTemplate,
         }
     }
 }
@@ -99,12 +145,53 @@ impl From<WasmModuleRegistryValue> for control_panel_api::WasmModuleRegistryEntr
     }
 }
 
+impl From<CandidInterfaceRegistryValue>
+    for control_panel_api::CandidInterfaceRegistryEntryValueDTO
+{
+    fn from(value: CandidInterfaceRegistryValue) -> Self {
+        Self {
+            candid_artifact_id: Uuid::from_bytes(value.candid_artifact_id).to_string(),
+            version: value.version,
+            wasm_module: value.wasm_module.map(Into::into),
+        }
+    }
+}
+
+impl From<AssetBundleRegistryValue> for control_panel_api::AssetBundleRegistryEntryValueDTO {
+    fn from(value: AssetBundleRegistryValue) -> Self {
+        Self {
+            bundle_artifact_id: Uuid::from_bytes(value.bundle_artifact_id).to_string(),
+            version: value.version,
+        }
+    }
+}
+
+impl From<StationConfigTemplateRegistryValue>
+    for control_panel_api::StationConfigTemplateRegistryEntryValueDTO
+{
+    fn from(value: StationConfigTemplateRegistryValue) -> Self {
+        Self {
+            template_artifact_id: Uuid::from_bytes(value.template_artifact_id).to_string(),
+            version: value.version,
+        }
+    }
+}
+
 impl From<RegistryValue> for control_panel_api::RegistryEntryValueDTO {
     fn from(value: RegistryValue) -> Self {
         match value {
             RegistryValue::WasmModule(wasm_module) => {
                 control_panel_api::RegistryEntryValueDTO::WasmModule(wasm_module.into())
             }
+            RegistryValue::CandidInterface(candid_interface) => {
+                control_panel_api::RegistryEntryValueDTO::CandidInterface(candid_interface.into())
+            }
+            RegistryValue::AssetBundle(asset_bundle) => {
+                control_panel_api::RegistryEntryValueDTO::AssetBundle(asset_bundle.into())
+            }
+            RegistryValue::StationConfigTemplate(template) => {
+                control_panel_api::RegistryEntryValueDTO::StationConfigTemplate(template.into())
+            }
         }
     }
 }
@@ -125,12 +212,70 @@ impl From<RegistryEntry> for control_panel_api::RegistryEntryDTO {
     }
 }
 
+impl From<RegistryNamespace> for control_panel_api::RegistryNamespaceDTO {
+    fn from(namespace: RegistryNamespace) -> Self {
+        Self {
+            name: namespace.name,
+            owners: namespace.owners,
+            publishers: namespace.publishers,
+            created_at: timestamp_to_rfc3339(&namespace.created_at),
+            updated_at: namespace.updated_at.map(|ts| timestamp_to_rfc3339(&ts)),
+        }
+    }
+}
+
+impl From<RegistryEntryAttestation> for control_panel_api::RegistryEntryAttestationDTO {
+    fn from(attestation: RegistryEntryAttestation) -> Self {
+        Self {
+            attester: attestation.attester,
+            build_hash: hex::encode(attestation.build_hash),
+            source_repository: attestation.source_repository,
+            source_commit: attestation.source_commit,
+            attested_at: timestamp_to_rfc3339(&attestation.attested_at),
+        }
+    }
+}
+
+impl From<RegistryEntryVerification> for control_panel_api::VerifyRegistryEntryResponse {
+    fn from(verification: RegistryEntryVerification) -> Self {
+        Self {
+            publisher: verification.publisher,
+            publisher_authorized: verification.publisher_authorized,
+            content_hash: hex::encode(verification.content_hash),
+            attestations: verification
+                .attestations
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            verified: verification.verified,
+        }
+    }
+}
+
+impl From<UpgradePathStep> for control_panel_api::UpgradePathStepDTO {
+    fn from(step: UpgradePathStep) -> Self {
+        Self {
+            station: step.station.into(),
+            upgrader: step.upgrader.map(Into::into),
+        }
+    }
+}
+
 impl From<control_panel_api::RegistryEntryValueKindDTO> for RegistryValueKind {
     fn from(kind: control_panel_api::RegistryEntryValueKindDTO) -> Self {
         match kind {
             control_panel_api::RegistryEntryValueKindDTO::WasmModule => {
                 RegistryValueKind::WasmModule
             }
+            control_panel_api::RegistryEntryValueKindDTO::CandidInterface => {
+                RegistryValueKind::CandidInterface
+            }
+            control_panel_api::RegistryEntryValueKindDTO::AssetBundle => {
+                RegistryValueKind::AssetBundle
+            }
+            control_panel_api::RegistryEntryValueKindDTO::StationConfigTemplate => {
+                RegistryValueKind::StationConfigTemplate
+            }
         }
     }
 }
@@ -141,6 +286,15 @@ impl From<control_panel_api::RegistryEntryValueInput> for RegistryValueKind {
             control_panel_api::RegistryEntryValueInput::WasmModule(_) => {
                 RegistryValueKind::WasmModule
             }
+            control_panel_api::RegistryEntryValueInput::CandidInterface(_) => {
+                RegistryValueKind::CandidInterface
+            }
+            control_panel_api::RegistryEntryValueInput::AssetBundle(_) => {
+                RegistryValueKind::AssetBundle
+            }
+            control_panel_api::RegistryEntryValueInput::StationConfigTemplate(_) => {
+                RegistryValueKind::StationConfigTemplate
+            }
         }
     }
 }
@@ -186,6 +340,7 @@ mod tests {
                     version: "1.0.0".to_string(),
                     dependencies: vec![],
                     wasm_module: vec![],
+                    wasm_module_artifact_id: None,
                     module_extra_chunks: None,
                 },
             ),
@@ -211,6 +366,7 @@ mod tests {
                     version: "1.0.0".to_string(),
                     dependencies: vec![],
                     wasm_module: vec![],
+                    wasm_module_artifact_id: None,
                     module_extra_chunks: None,
                 },
             ),
@@ -0,0 +1,77 @@
+use super::{UserId, UserSubscriptionStatus};
+use crate::core::ic_cdk::next_time;
+use candid::Principal;
+use orbit_essentials::model::ModelKey;
+use orbit_essentials::storable;
+use orbit_essentials::types::Timestamp;
+
+/// An action performed by a controller through the admin endpoints of the control panel.
+///
+/// The actions are only appended to the admin action log, they are never updated nor removed.
+#[storable]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct AdminAction {
+    /// The controller that performed the action.
+    pub performed_by: Principal,
+    /// The timestamp when the action was performed, which is unique and identifies the action.
+    pub performed_at: Timestamp,
+    pub kind: AdminActionKind,
+}
+
+#[storable]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum AdminActionKind {
+    SetUserSubscriptionStatus {
+        user_id: UserId,
+        subscription_status: UserSubscriptionStatus,
+    },
+    SetUserDeployQuota {
+        user_id: UserId,
+        max_deployed_stations: Option<usize>,
+        max_deployed_stations_per_day: usize,
+    },
+    /// Only recorded by earlier versions, the waitlist is now exported with a query.
+    ExportWaitlist {
+        /// The number of users that were in the exported waitlist.
+        num_users: usize,
+    },
+}
+
+impl ModelKey<Timestamp> for AdminAction {
+    fn key(&self) -> Timestamp {
+        self.performed_at
+    }
+}
+
+impl AdminAction {
+    pub fn new(performed_by: Principal, kind: AdminActionKind) -> Self {
+        Self {
+            performed_by,
+            performed_at: next_time(),
+            kind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_stable_structures::Storable;
+
+    #[test]
+    fn valid_model_serialization() {
+        let model = AdminAction::new(
+            Principal::from_slice(&[1; 29]),
+            AdminActionKind::SetUserDeployQuota {
+                user_id: [2; 16],
+                max_deployed_stations: Some(10),
+                max_deployed_stations_per_day: 1,
+            },
+        );
+
+        let serialized_model = model.to_bytes();
+        let deserialized_model = AdminAction::from_bytes(serialized_model);
+
+        assert_eq!(model, deserialized_model);
+    }
+}
@@ -1,3 +1,4 @@
+use super::{ArtifactChunk, ArtifactChunkHash, ArtifactUploadId};
 use crate::{core::ic_cdk::next_time, errors::ArtifactError, repositories::ARTIFACT_REPOSITORY};
 use orbit_essentials::{
     model::{ModelKey, ModelValidator, ModelValidatorResult},
@@ -18,13 +19,26 @@ pub struct Artifact {
     id: ArtifactId,
     /// The artifact sha256 hash.
     hash: Vec<u8>,
-    /// The artifact itself.
+    /// The content of artifacts that were stored inline, before artifacts were split into chunks.
+    ///
+    /// It is moved to the chunk repository when the canister is upgraded and is empty otherwise.
+    #[serde(default)]
     artifact: Vec<u8>,
+    /// The hashes of the chunks of the artifact, in order, which are stored in the chunk repository
+    /// and shared by all the artifacts that have the same chunk.
+    #[serde(default)]
+    chunks: Vec<ArtifactChunkHash>,
+    /// The size of the artifact in bytes.
+    #[serde(default)]
+    size: u64,
     /// Stores the references to the artifact. This is used as a reference counter since there can
     /// be multiple references to the same artifact if the hash is the same.
     ///
     /// The reference counter is incremented when a new reference is created and decremented when a
     /// reference is removed. When the reference counter reaches zero, the artifact is removed.
+    ///
+    /// Artifacts that are uploaded in chunks start without references and are garbage collected if they
+    /// are not referenced within the retention period.
     rc: u32,
     /// The date when the artifact was created.
     created_at: Timestamp,
@@ -52,24 +66,63 @@ impl Default for ArtifactCreateOpts {
 }
 
 impl Artifact {
-    /// Creates a new artifact.
+    /// The time during which artifacts without references are kept, so that they can be referenced
+    /// after their upload is finished.
+    pub const UNREFERENCED_RETENTION_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    /// Creates a new artifact for the given content.
+    ///
+    /// Only the hashes of the chunks are kept in the artifact, the chunks themselves must be stored
+    /// separately.
     pub fn new(artifact: Vec<u8>) -> Self {
         Self::new_with_opts(artifact, ArtifactCreateOpts::default())
     }
 
-    /// Creates a new artifact with the given options.
+    /// Creates a new artifact for the given content with the given options.
     pub fn new_with_opts(artifact: Vec<u8>, opts: ArtifactCreateOpts) -> Self {
-        let hash = sha256_hash(&artifact);
+        let chunks = ArtifactChunk::split(&artifact)
+            .map(ArtifactChunk::hash_of)
+            .collect();
 
         Self {
             id: opts.id,
             rc: 1,
-            artifact,
+            hash: sha256_hash(&artifact),
+            artifact: Vec::new(),
+            chunks,
+            size: artifact.len() as u64,
+            created_at: opts.created_at,
+        }
+    }
+
+    /// Creates a new artifact from chunks that were already stored, without references.
+    pub fn from_chunks(hash: Vec<u8>, size: u64, chunks: Vec<ArtifactChunkHash>) -> Self {
+        let opts = ArtifactCreateOpts::default();
+
+        Self {
+            id: opts.id,
+            rc: 0,
             hash,
+            artifact: Vec::new(),
+            chunks,
+            size,
             created_at: opts.created_at,
         }
     }
 
+    /// Creates an artifact with its content stored inline, as artifacts were stored before they were
+    /// split into chunks.
+    #[cfg(test)]
+    pub fn new_inline(artifact: Vec<u8>) -> Self {
+        Self {
+            size: 0,
+            chunks: Vec::new(),
+            hash: sha256_hash(&artifact),
+            artifact,
+            ..Self::new(Vec::new())
+        }
+    }
+
     /// Returns the artifact id.
     pub fn id(&self) -> &ArtifactId {
         &self.id
@@ -80,9 +133,44 @@ impl Artifact {
         &self.hash
     }
 
-    /// Returns the artifact.
-    pub fn artifact(&self) -> &[u8] {
-        &self.artifact
+    /// Returns the size of the artifact in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the hashes of the chunks of the artifact.
+    pub fn chunks(&self) -> &[ArtifactChunkHash] {
+        &self.chunks
+    }
+
+    /// Returns the content of the artifact if it is still stored inline.
+    pub fn inline_content(&self) -> Option<&[u8]> {
+        match self.chunks.is_empty() && !self.artifact.is_empty() {
+            true => Some(&self.artifact),
+            false => None,
+        }
+    }
+
+    /// Replaces the inline content of the artifact with the hashes of its chunks.
+    pub fn move_inline_content_to_chunks(&mut self) -> Option<Vec<u8>> {
+        self.inline_content()?;
+
+        let content = std::mem::take(&mut self.artifact);
+        self.size = content.len() as u64;
+        self.chunks = ArtifactChunk::split(&content)
+            .map(ArtifactChunk::hash_of)
+            .collect();
+
+        Some(content)
+    }
+
+    /// Returns `true` if the artifact has no references and its retention period is over.
+    pub fn is_collectable(&self, now: Timestamp) -> bool {
+        self.rc == 0
+            && self
+                .created_at
+                .saturating_add(Self::UNREFERENCED_RETENTION_NS)
+                < now
     }
 
     /// Returns the creation timestamp.
@@ -120,6 +208,50 @@ fn validate_unique_hash(self_id: &ArtifactId, hash: &[u8]) -> ModelValidatorResu
         })
 }
 
+/// The statistics of the artifact storage.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ArtifactStorageStats {
+    /// The number of artifacts.
+    pub artifacts: u64,
+    /// The sum of the sizes of all the artifacts.
+    pub artifacts_size: u64,
+    /// The number of stored chunks.
+    pub chunks: u64,
+    /// The number of bytes that are actually stored, which is lower than the size of the artifacts
+    /// when chunks are shared.
+    pub stored_size: u64,
+    /// The number of uploads in progress.
+    pub uploads: u64,
+}
+
+/// The result of a garbage collection pass over the artifact storage.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ArtifactGarbageCollection {
+    /// The number of expired uploads that were removed.
+    pub removed_uploads: u64,
+    /// The number of unreferenced artifacts that were removed.
+    pub removed_artifacts: u64,
+    /// The number of chunks that were removed.
+    pub removed_chunks: u64,
+    /// The number of bytes that were freed.
+    pub freed_bytes: u64,
+}
+
+/// The position of a garbage collection, which checks the uploads, the artifacts and the chunks in
+/// this order, each one after the last entry that was checked.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ArtifactGarbageCollectionCursor {
+    Uploads(Option<ArtifactUploadId>),
+    Artifacts(Option<ArtifactId>),
+    Chunks(Option<ArtifactChunkHash>),
+}
+
+impl Default for ArtifactGarbageCollectionCursor {
+    fn default() -> Self {
+        Self::Uploads(None)
+    }
+}
+
 impl ModelValidator<ArtifactError> for Artifact {
     fn validate(&self) -> ModelValidatorResult<ArtifactError> {
         validate_unique_hash(self.id(), &self.hash)?;
@@ -137,7 +269,8 @@ mod tests {
     fn test_empty_artifact_is_accepted() {
         let artifact = Artifact::new(Vec::new());
 
-        assert_eq!(artifact.artifact(), b"");
+        assert_eq!(artifact.size(), 0);
+        assert!(artifact.chunks().is_empty());
         assert_eq!(
             artifact.hash(),
             vec![
@@ -158,7 +291,20 @@ mod tests {
                 239, 227, 122, 83, 128, 238, 144, 136, 247, 172, 226, 239, 205, 233,
             ]
         );
-        assert_eq!(artifact.artifact(), b"hello world");
+        assert_eq!(artifact.size(), 11);
+        assert_eq!(artifact.chunks(), &[ArtifactChunk::hash_of(b"hello world")]);
+    }
+
+    #[test]
+    fn test_large_artifact_is_split_in_chunks() {
+        let mut content = vec![1; ArtifactChunk::MAX_SIZE * 2];
+        content.extend_from_slice(&[2; 10]);
+        let artifact = Artifact::new(content);
+
+        assert_eq!(artifact.size(), ArtifactChunk::MAX_SIZE as u64 * 2 + 10);
+        assert_eq!(artifact.chunks().len(), 3);
+        assert_eq!(artifact.chunks()[0], artifact.chunks()[1]);
+        assert_eq!(artifact.chunks()[2], ArtifactChunk::hash_of(&[2; 10]));
     }
 
     #[test]
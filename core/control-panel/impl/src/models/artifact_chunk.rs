@@ -0,0 +1,106 @@
+use orbit_essentials::{model::ModelKey, storable, utils::sha256_hash};
+
+/// The sha256 hash of a chunk, which identifies the chunk.
+pub type ArtifactChunkHash = [u8; 32];
+
+/// A chunk of the content of one or more artifacts.
+///
+/// Chunks are content-addressed, so the artifacts that share the same chunk only store it once.
+#[storable]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct ArtifactChunk {
+    /// The sha256 hash of the chunk data.
+    hash: ArtifactChunkHash,
+    /// The chunk data.
+    #[serde(with = "serde_bytes")]
+    data: Vec<u8>,
+    /// The number of references to the chunk from artifacts and uploads in progress.
+    ///
+    /// When the reference counter reaches zero, the chunk is removed.
+    rc: u32,
+}
+
+impl ModelKey<ArtifactChunkHash> for ArtifactChunk {
+    fn key(&self) -> ArtifactChunkHash {
+        self.hash
+    }
+}
+
+impl ArtifactChunk {
+    /// The maximum size of a chunk, which also fits in a single call to upload or download it.
+    pub const MAX_SIZE: usize = 1024 * 1024;
+
+    /// Creates a new chunk with a single reference.
+    pub fn new(data: Vec<u8>) -> Self {
+        Self {
+            hash: Self::hash_of(&data),
+            data,
+            rc: 1,
+        }
+    }
+
+    /// Returns the hash that identifies a chunk with the given data.
+    pub fn hash_of(data: &[u8]) -> ArtifactChunkHash {
+        sha256_hash(data)
+            .try_into()
+            .expect("sha256 hashes are 32 bytes long")
+    }
+
+    /// Splits the content in chunks of at most [`Self::MAX_SIZE`] bytes.
+    pub fn split(content: &[u8]) -> impl Iterator<Item = &[u8]> {
+        content.chunks(Self::MAX_SIZE)
+    }
+
+    /// Returns the number of chunks of a content of the given size.
+    pub fn count_for_size(size: u64) -> u64 {
+        size.div_ceil(Self::MAX_SIZE as u64)
+    }
+
+    /// Returns the chunk hash.
+    pub fn hash(&self) -> &ArtifactChunkHash {
+        &self.hash
+    }
+
+    /// Returns the chunk data.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the reference counter.
+    pub fn rc(&self) -> u32 {
+        self.rc
+    }
+
+    /// Increments the reference counter.
+    pub fn increment_rc(&mut self) {
+        self.rc = self.rc.saturating_add(1);
+    }
+
+    /// Decrements the reference counter.
+    pub fn decrement_rc(&mut self) {
+        self.rc = self.rc.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_is_identified_by_its_hash() {
+        let chunk = ArtifactChunk::new(b"hello world".to_vec());
+
+        assert_eq!(chunk.key(), ArtifactChunk::hash_of(b"hello world"));
+        assert_eq!(chunk.rc(), 1);
+    }
+
+    #[test]
+    fn counts_chunks_for_size() {
+        let max_size = ArtifactChunk::MAX_SIZE as u64;
+
+        assert_eq!(ArtifactChunk::count_for_size(0), 0);
+        assert_eq!(ArtifactChunk::count_for_size(1), 1);
+        assert_eq!(ArtifactChunk::count_for_size(max_size), 1);
+        assert_eq!(ArtifactChunk::count_for_size(max_size + 1), 2);
+    }
+}
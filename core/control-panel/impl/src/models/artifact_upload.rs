@@ -0,0 +1,149 @@
+use super::{ArtifactChunk, ArtifactChunkHash};
+use crate::{core::ic_cdk::next_time, errors::ArtifactError};
+use candid::Principal;
+use orbit_essentials::{
+    model::{ModelKey, ModelValidator, ModelValidatorResult},
+    storable,
+    types::{Timestamp, UUID},
+};
+use uuid::Uuid;
+
+/// The artifact upload id, which is a UUID.
+pub type ArtifactUploadId = UUID;
+
+/// An upload of an artifact in chunks.
+///
+/// The uploaded chunks are stored in the chunk repository right away, so an upload can be resumed by
+/// uploading only the chunks that are still missing.
+#[storable]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct ArtifactUpload {
+    /// The UUID that identifies the upload.
+    pub id: ArtifactUploadId,
+    /// The expected sha256 hash of the artifact.
+    pub hash: Vec<u8>,
+    /// The size of the artifact in bytes.
+    ///
+    /// Restrictions:
+    ///
+    /// - The size must be at most 100 MiB.
+    pub size: u64,
+    /// The hashes of the chunks that were uploaded, by chunk index.
+    pub chunks: Vec<Option<ArtifactChunkHash>>,
+    /// The principal that started the upload and is the only one that can continue it.
+    pub uploaded_by: Principal,
+    /// The timestamp when the upload was started.
+    pub created_at: Timestamp,
+    /// The timestamp when a chunk was last uploaded.
+    pub last_modification_timestamp: Timestamp,
+}
+
+impl ModelKey<ArtifactUploadId> for ArtifactUpload {
+    fn key(&self) -> ArtifactUploadId {
+        self.id
+    }
+}
+
+impl ArtifactUpload {
+    pub const MAX_SIZE: u64 = 100 * 1024 * 1024;
+
+    /// The time after the last uploaded chunk after which the upload is removed.
+    pub const EXPIRATION_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    pub fn new(hash: Vec<u8>, size: u64, uploaded_by: Principal) -> Self {
+        let now = next_time();
+
+        Self {
+            id: *Uuid::new_v4().as_bytes(),
+            hash,
+            size,
+            chunks: vec![None; ArtifactChunk::count_for_size(size) as usize],
+            uploaded_by,
+            created_at: now,
+            last_modification_timestamp: now,
+        }
+    }
+
+    /// Returns the size that the chunk with the given index must have.
+    pub fn expected_chunk_size(&self, index: u64) -> Option<usize> {
+        if index >= self.chunks.len() as u64 {
+            return None;
+        }
+
+        let offset = index * ArtifactChunk::MAX_SIZE as u64;
+
+        Some(std::cmp::min(ArtifactChunk::MAX_SIZE as u64, self.size - offset) as usize)
+    }
+
+    /// Returns the indexes of the chunks that were not uploaded yet.
+    pub fn missing_chunks(&self) -> Vec<u64> {
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| chunk.is_none())
+            .map(|(index, _)| index as u64)
+            .collect()
+    }
+
+    /// Returns `true` if no chunk was uploaded within the expiration period.
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        self.last_modification_timestamp
+            .saturating_add(Self::EXPIRATION_NS)
+            < now
+    }
+}
+
+impl ModelValidator<ArtifactError> for ArtifactUpload {
+    fn validate(&self) -> ModelValidatorResult<ArtifactError> {
+        if self.hash.len() != 32 {
+            return Err(ArtifactError::ValidationError {
+                info: "The hash of the artifact must be a 32 bytes sha256 hash".to_string(),
+            });
+        }
+
+        if self.size > Self::MAX_SIZE {
+            return Err(ArtifactError::ValidationError {
+                info: format!(
+                    "The artifact must be at most {} bytes, got {}",
+                    Self::MAX_SIZE,
+                    self.size
+                ),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_missing_chunks() {
+        let size = ArtifactChunk::MAX_SIZE as u64 * 2 + 5;
+        let mut upload = ArtifactUpload::new(vec![0; 32], size, Principal::anonymous());
+
+        assert_eq!(upload.missing_chunks(), vec![0, 1, 2]);
+        assert_eq!(upload.expected_chunk_size(1), Some(ArtifactChunk::MAX_SIZE));
+        assert_eq!(upload.expected_chunk_size(2), Some(5));
+        assert_eq!(upload.expected_chunk_size(3), None);
+
+        upload.chunks[1] = Some([1; 32]);
+
+        assert_eq!(upload.missing_chunks(), vec![0, 2]);
+    }
+
+    #[test]
+    fn fails_validation_for_invalid_uploads() {
+        let upload = ArtifactUpload::new(vec![0; 31], 10, Principal::anonymous());
+        assert!(upload.validate().is_err());
+
+        let upload = ArtifactUpload::new(
+            vec![0; 32],
+            ArtifactUpload::MAX_SIZE + 1,
+            Principal::anonymous(),
+        );
+        assert!(upload.validate().is_err());
+    }
+}
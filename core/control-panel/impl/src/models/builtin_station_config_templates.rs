@@ -0,0 +1,398 @@
+//! The station config templates that the control panel publishes in its registry.
+use super::StationConfigTemplate;
+use station_api::{
+    AccountResourceActionDTO, AllowDTO, AuthScopeDTO, DisasterRecoveryCommitteeDTO,
+    ExternalCanisterIdDTO, ExternalCanisterResourceActionDTO, InitAccountInput,
+    InitAccountPermissionsInput, InitAccountWithPermissionsInput, InitAssetInput,
+    InitNamedRuleInput, InitPermissionInput, InitRequestPolicyInput, InitUserGroupInput,
+    MetadataDTO, PermissionResourceActionDTO, QuorumDTO, QuorumPercentageDTO, RequestPolicyRuleDTO,
+    RequestResourceActionDTO, RequestSpecifierDTO, ResourceActionDTO, ResourceDTO, ResourceIdDTO,
+    ResourceIdsDTO, ResourceSpecifierDTO, SystemResourceActionDTO, UserResourceActionDTO,
+    UserSpecifierDTO,
+};
+
+/// The ids of the admin and operator groups of the station, which are the ids the station uses
+/// for its default groups.
+const ADMIN_GROUP_ID: &str = "00000000-0000-4000-8000-000000000000";
+const OPERATOR_GROUP_ID: &str = "00000000-0000-4000-8000-000000000001";
+
+/// The id of the ICP asset, which is the id the station uses for its default ICP asset.
+const ICP_ASSET_ID: &str = "7802cbab-221d-4e49-b764-a695ea6def1a";
+
+const TWO_ADMINS_RULE_ID: &str = "3f6c2a8e-5b1d-4c7e-9a2f-8d4e6b0c1a01";
+const ADMIN_MAJORITY_RULE_ID: &str = "3f6c2a8e-5b1d-4c7e-9a2f-8d4e6b0c1a02";
+const OPERATOR_APPROVAL_RULE_ID: &str = "3f6c2a8e-5b1d-4c7e-9a2f-8d4e6b0c1a03";
+
+const TREASURY_ACCOUNT_ID: &str = "3f6c2a8e-5b1d-4c7e-9a2f-8d4e6b0c1b01";
+const OPERATIONS_ACCOUNT_ID: &str = "3f6c2a8e-5b1d-4c7e-9a2f-8d4e6b0c1b02";
+
+/// A built-in template and the name of the registry entry it is published as.
+pub struct BuiltinStationConfigTemplate {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub template: StationConfigTemplate,
+}
+
+pub fn builtin_station_config_templates() -> Vec<BuiltinStationConfigTemplate> {
+    vec![
+        BuiltinStationConfigTemplate {
+            name: "@orbit/treasury-2-of-3",
+            version: "1.0.0",
+            template: treasury_2_of_3(),
+        },
+        BuiltinStationConfigTemplate {
+            name: "@orbit/dao-ops",
+            version: "1.0.0",
+            template: dao_ops(),
+        },
+    ]
+}
+
+/// A treasury held by three admins, two of which must approve every transfer and change.
+fn treasury_2_of_3() -> StationConfigTemplate {
+    let two_admins = named_rule(TWO_ADMINS_RULE_ID);
+
+    StationConfigTemplate {
+        description: Some(
+            "A treasury held by three admins, two of which must approve every transfer and change."
+                .to_string(),
+        ),
+        min_admins: 3,
+        user_groups: vec![user_group(ADMIN_GROUP_ID, "Admin")],
+        admin_groups: vec![ADMIN_GROUP_ID.to_string()],
+        named_rules: vec![InitNamedRuleInput {
+            id: Some(TWO_ADMINS_RULE_ID.to_string()),
+            name: "Two admins".to_string(),
+            description: Some("Two of the admins must approve.".to_string()),
+            rule: RequestPolicyRuleDTO::Quorum(QuorumDTO {
+                approvers: UserSpecifierDTO::Group(vec![ADMIN_GROUP_ID.to_string()]),
+                min_approved: 2,
+            }),
+        }],
+        permissions: default_permissions(),
+        request_policies: governance_policies(&two_admins)
+            .into_iter()
+            .chain(operation_policies(&two_admins))
+            .collect(),
+        accounts: vec![icp_account(
+            TREASURY_ACCOUNT_ID,
+            "Treasury",
+            InitAccountPermissionsInput {
+                read_permission: restricted_to(&[ADMIN_GROUP_ID]),
+                configs_permission: restricted_to(&[ADMIN_GROUP_ID]),
+                transfer_permission: restricted_to(&[ADMIN_GROUP_ID]),
+                configs_request_policy: Some(two_admins.clone()),
+                transfer_request_policy: Some(two_admins),
+            },
+        )],
+        assets: vec![icp_asset()],
+        disaster_recovery_committee: Some(DisasterRecoveryCommitteeDTO {
+            user_group_id: ADMIN_GROUP_ID.to_string(),
+            quorum: 2,
+        }),
+    }
+}
+
+/// A station governed by a majority of the admins, where operators run the daily operations.
+fn dao_ops() -> StationConfigTemplate {
+    let admin_majority = named_rule(ADMIN_MAJORITY_RULE_ID);
+    let operator_approval = named_rule(OPERATOR_APPROVAL_RULE_ID);
+
+    StationConfigTemplate {
+        description: Some(
+            "A station governed by a majority of the admins, where operators run the daily operations and transfers."
+                .to_string(),
+        ),
+        min_admins: 3,
+        user_groups: vec![
+            user_group(ADMIN_GROUP_ID, "Admin"),
+            user_group(OPERATOR_GROUP_ID, "Operator"),
+        ],
+        admin_groups: vec![ADMIN_GROUP_ID.to_string()],
+        named_rules: vec![
+            InitNamedRuleInput {
+                id: Some(ADMIN_MAJORITY_RULE_ID.to_string()),
+                name: "Admin majority".to_string(),
+                description: Some("The majority of the admins must approve.".to_string()),
+                rule: RequestPolicyRuleDTO::QuorumPercentage(QuorumPercentageDTO {
+                    approvers: UserSpecifierDTO::Group(vec![ADMIN_GROUP_ID.to_string()]),
+                    min_approved: 51,
+                }),
+            },
+            InitNamedRuleInput {
+                id: Some(OPERATOR_APPROVAL_RULE_ID.to_string()),
+                name: "Operator approval".to_string(),
+                description: Some("One operator or admin must approve.".to_string()),
+                rule: RequestPolicyRuleDTO::Quorum(QuorumDTO {
+                    approvers: UserSpecifierDTO::Group(vec![
+                        OPERATOR_GROUP_ID.to_string(),
+                        ADMIN_GROUP_ID.to_string(),
+                    ]),
+                    min_approved: 1,
+                }),
+            },
+        ],
+        permissions: default_permissions(),
+        request_policies: governance_policies(&admin_majority)
+            .into_iter()
+            .chain(operation_policies(&operator_approval))
+            .collect(),
+        accounts: vec![icp_account(
+            OPERATIONS_ACCOUNT_ID,
+            "Operations",
+            InitAccountPermissionsInput {
+                read_permission: restricted_to(&[ADMIN_GROUP_ID, OPERATOR_GROUP_ID]),
+                configs_permission: restricted_to(&[ADMIN_GROUP_ID]),
+                transfer_permission: restricted_to(&[ADMIN_GROUP_ID, OPERATOR_GROUP_ID]),
+                configs_request_policy: Some(admin_majority),
+                transfer_request_policy: Some(operator_approval),
+            },
+        )],
+        assets: vec![icp_asset()],
+        disaster_recovery_committee: Some(DisasterRecoveryCommitteeDTO {
+            user_group_id: ADMIN_GROUP_ID.to_string(),
+            quorum: 2,
+        }),
+    }
+}
+
+fn named_rule(id: &str) -> RequestPolicyRuleDTO {
+    RequestPolicyRuleDTO::NamedRule(id.to_string())
+}
+
+fn user_group(id: &str, name: &str) -> InitUserGroupInput {
+    InitUserGroupInput {
+        id: Some(id.to_string()),
+        name: name.to_string(),
+    }
+}
+
+fn authenticated() -> AllowDTO {
+    AllowDTO {
+        auth_scope: AuthScopeDTO::Authenticated,
+        users: Vec::new(),
+        user_groups: Vec::new(),
+    }
+}
+
+fn restricted_to(user_groups: &[&str]) -> AllowDTO {
+    AllowDTO {
+        auth_scope: AuthScopeDTO::Restricted,
+        users: Vec::new(),
+        user_groups: user_groups.iter().map(|id| id.to_string()).collect(),
+    }
+}
+
+/// The permissions that the station grants by default, the request policies decide who approves.
+fn default_permissions() -> Vec<InitPermissionInput> {
+    let crud = |resource: fn(ResourceActionDTO) -> ResourceDTO| {
+        vec![
+            resource(ResourceActionDTO::List),
+            resource(ResourceActionDTO::Create),
+            resource(ResourceActionDTO::Read(ResourceIdDTO::Any)),
+            resource(ResourceActionDTO::Update(ResourceIdDTO::Any)),
+            resource(ResourceActionDTO::Delete(ResourceIdDTO::Any)),
+        ]
+    };
+
+    let mut resources = vec![
+        ResourceDTO::System(SystemResourceActionDTO::Capabilities),
+        ResourceDTO::System(SystemResourceActionDTO::SystemInfo),
+        ResourceDTO::System(SystemResourceActionDTO::ManageSystemInfo),
+        ResourceDTO::System(SystemResourceActionDTO::Upgrade),
+        ResourceDTO::User(UserResourceActionDTO::List),
+        ResourceDTO::User(UserResourceActionDTO::Create),
+        ResourceDTO::User(UserResourceActionDTO::Read(ResourceIdDTO::Any)),
+        ResourceDTO::User(UserResourceActionDTO::Update(ResourceIdDTO::Any)),
+        ResourceDTO::Permission(PermissionResourceActionDTO::Read),
+        ResourceDTO::Permission(PermissionResourceActionDTO::Update),
+        ResourceDTO::Request(RequestResourceActionDTO::List),
+        ResourceDTO::Request(RequestResourceActionDTO::Read(ResourceIdDTO::Any)),
+        ResourceDTO::Account(AccountResourceActionDTO::Create),
+        ResourceDTO::Account(AccountResourceActionDTO::List),
+        ResourceDTO::Account(AccountResourceActionDTO::Read(ResourceIdDTO::Any)),
+        ResourceDTO::ExternalCanister(ExternalCanisterResourceActionDTO::List),
+        ResourceDTO::ExternalCanister(ExternalCanisterResourceActionDTO::Create),
+        ResourceDTO::ExternalCanister(ExternalCanisterResourceActionDTO::Read(
+            ExternalCanisterIdDTO::Any,
+        )),
+        ResourceDTO::ExternalCanister(ExternalCanisterResourceActionDTO::Change(
+            ExternalCanisterIdDTO::Any,
+        )),
+        ResourceDTO::ExternalCanister(ExternalCanisterResourceActionDTO::Fund(
+            ExternalCanisterIdDTO::Any,
+        )),
+        ResourceDTO::ExternalCanister(ExternalCanisterResourceActionDTO::ReleaseControl(
+            ExternalCanisterIdDTO::Any,
+        )),
+    ];
+    resources.extend(crud(ResourceDTO::UserGroup));
+    resources.extend(crud(ResourceDTO::RequestPolicy));
+    resources.extend(crud(ResourceDTO::AddressBook));
+    resources.extend(crud(ResourceDTO::Asset));
+    resources.extend(crud(ResourceDTO::NamedRule));
+
+    resources
+        .into_iter()
+        .map(|resource| InitPermissionInput {
+            resource,
+            allow: authenticated(),
+        })
+        .collect()
+}
+
+/// The policies of the requests that change who controls the station and how.
+///
+/// A single admin can freeze the station in an emergency, while unfreezing it needs the rule.
+fn governance_policies(rule: &RequestPolicyRuleDTO) -> Vec<InitRequestPolicyInput> {
+    let mut policies = policies(
+        rule,
+        vec![
+            RequestSpecifierDTO::SystemUpgrade,
+            RequestSpecifierDTO::ManageSystemInfo,
+            RequestSpecifierDTO::SetDisasterRecovery,
+            RequestSpecifierDTO::UnfreezeStation,
+            RequestSpecifierDTO::ReleaseExternalCanisterControl(ExternalCanisterIdDTO::Any),
+            RequestSpecifierDTO::AddUser,
+            RequestSpecifierDTO::EditUser(ResourceIdsDTO::Any),
+            RequestSpecifierDTO::EditPermission(ResourceSpecifierDTO::Any),
+            RequestSpecifierDTO::AddRequestPolicy,
+            RequestSpecifierDTO::EditRequestPolicy(ResourceIdsDTO::Any),
+            RequestSpecifierDTO::RemoveRequestPolicy(ResourceIdsDTO::Any),
+            RequestSpecifierDTO::AddUserGroup,
+            RequestSpecifierDTO::EditUserGroup(ResourceIdsDTO::Any),
+            RequestSpecifierDTO::RemoveUserGroup(ResourceIdsDTO::Any),
+            RequestSpecifierDTO::AddNamedRule,
+            RequestSpecifierDTO::EditNamedRule(ResourceIdsDTO::Any),
+            RequestSpecifierDTO::RemoveNamedRule(ResourceIdsDTO::Any),
+        ],
+    );
+
+    policies.push(InitRequestPolicyInput {
+        id: None,
+        specifier: RequestSpecifierDTO::FreezeStation,
+        rule: RequestPolicyRuleDTO::Quorum(QuorumDTO {
+            approvers: UserSpecifierDTO::Group(vec![ADMIN_GROUP_ID.to_string()]),
+            min_approved: 1,
+        }),
+    });
+
+    policies
+}
+
+/// The policies of the requests of the daily operations of the station.
+fn operation_policies(rule: &RequestPolicyRuleDTO) -> Vec<InitRequestPolicyInput> {
+    policies(
+        rule,
+        vec![
+            RequestSpecifierDTO::AddAccount,
+            RequestSpecifierDTO::AddAddressBookEntry,
+            RequestSpecifierDTO::EditAddressBookEntry(ResourceIdsDTO::Any),
+            RequestSpecifierDTO::RemoveAddressBookEntry(ResourceIdsDTO::Any),
+            RequestSpecifierDTO::CreateExternalCanister,
+            RequestSpecifierDTO::ChangeExternalCanister(ExternalCanisterIdDTO::Any),
+            RequestSpecifierDTO::FundExternalCanister(ExternalCanisterIdDTO::Any),
+            RequestSpecifierDTO::AddAsset,
+            RequestSpecifierDTO::EditAsset(ResourceIdsDTO::Any),
+            RequestSpecifierDTO::RemoveAsset(ResourceIdsDTO::Any),
+        ],
+    )
+}
+
+fn policies(
+    rule: &RequestPolicyRuleDTO,
+    specifiers: Vec<RequestSpecifierDTO>,
+) -> Vec<InitRequestPolicyInput> {
+    specifiers
+        .into_iter()
+        .map(|specifier| InitRequestPolicyInput {
+            id: None,
+            specifier,
+            rule: rule.clone(),
+        })
+        .collect()
+}
+
+fn icp_asset() -> InitAssetInput {
+    InitAssetInput {
+        id: Some(ICP_ASSET_ID.to_string()),
+        name: "Internet Computer".to_string(),
+        blockchain: "icp".to_string(),
+        standards: vec!["icp_native".to_string(), "icrc1".to_string()],
+        metadata: vec![
+            MetadataDTO {
+                key: "ledger_canister_id".to_string(),
+                value: "ryjl3-tyaaa-aaaaa-aaaba-cai".to_string(),
+            },
+            MetadataDTO {
+                key: "index_canister_id".to_string(),
+                value: "qhbym-qaaaa-aaaaa-aaafq-cai".to_string(),
+            },
+        ],
+        symbol: "ICP".to_string(),
+        decimals: 8,
+    }
+}
+
+fn icp_account(
+    id: &str,
+    name: &str,
+    permissions: InitAccountPermissionsInput,
+) -> InitAccountWithPermissionsInput {
+    let seed = *uuid::Uuid::parse_str(id)
+        .expect("built-in account ids are valid")
+        .as_bytes();
+
+    InitAccountWithPermissionsInput {
+        account_init: InitAccountInput {
+            id: Some(id.to_string()),
+            name: name.to_string(),
+            seed,
+            assets: vec![ICP_ASSET_ID.to_string()],
+            metadata: Vec::new(),
+        },
+        permissions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn builtin_templates_are_deployable() {
+        for builtin in builtin_station_config_templates() {
+            let template = StationConfigTemplate::from_json(builtin.template.to_json().as_bytes())
+                .unwrap_or_else(|e| panic!("{} is not valid: {e}", builtin.name));
+
+            template
+                .validate_for_deployment(template.min_admins as usize)
+                .unwrap_or_else(|e| panic!("{} is not deployable: {e}", builtin.name));
+
+            let named_rules = template
+                .named_rules
+                .iter()
+                .filter_map(|rule| rule.id.clone())
+                .collect::<BTreeSet<_>>();
+            let referenced_rules = template
+                .request_policies
+                .iter()
+                .map(|policy| &policy.rule)
+                .chain(template.accounts.iter().flat_map(|account| {
+                    account
+                        .permissions
+                        .configs_request_policy
+                        .iter()
+                        .chain(account.permissions.transfer_request_policy.iter())
+                }))
+                .filter_map(|rule| match rule {
+                    RequestPolicyRuleDTO::NamedRule(id) => Some(id.clone()),
+                    _ => None,
+                })
+                .collect::<BTreeSet<_>>();
+
+            assert!(referenced_rules.is_subset(&named_rules));
+        }
+    }
+}
@@ -34,7 +34,7 @@ impl Artifact {
 
     pub fn to_index_by_size(&self) -> ArtifactIndex {
         ArtifactIndex {
-            index: ArtifactIndexKind::Size(self.artifact().len() as u64),
+            index: ArtifactIndexKind::Size(self.size()),
             artifact_id: *self.id(),
         }
     }
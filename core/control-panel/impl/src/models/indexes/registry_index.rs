@@ -1,4 +1,4 @@
-use crate::models::{RegistryEntry, RegistryEntryId, RegistryValue, RegistryValueKind};
+use crate::models::{RegistryEntry, RegistryEntryId, RegistryValueKind};
 use orbit_essentials::storable;
 
 /// The main index for registry entries.
@@ -64,12 +64,10 @@ impl RegistryEntry {
     }
 
     pub fn to_index_by_version(&self) -> Option<RegistryIndex> {
-        match &self.value {
-            RegistryValue::WasmModule(module) => Some(RegistryIndex {
-                index: RegistryIndexKind::Version(module.version.to_string()),
-                registry_entry_id: self.id,
-            }),
-        }
+        Some(RegistryIndex {
+            index: RegistryIndexKind::Version(self.value.version().to_string()),
+            registry_entry_id: self.id,
+        })
     }
 
     pub fn to_index_by_value_kind(&self) -> RegistryIndex {
@@ -1,4 +1,4 @@
-use crate::models::{RegistryEntry, RegistryValue};
+use crate::models::RegistryEntry;
 use orbit_essentials::{storable, types::Timestamp};
 
 /// The main index for registry entries.
@@ -18,9 +18,7 @@ impl RegistryEntry {
         RegistrySortIndex {
             created_at: self.created_at,
             modified_at: self.updated_at,
-            version: match self.value.clone() {
-                RegistryValue::WasmModule(value) => Some(value.version),
-            },
+            version: Some(self.value.version().to_string()),
         }
     }
 }
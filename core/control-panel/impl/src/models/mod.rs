@@ -6,12 +6,30 @@ pub use user_station::*;
 mod user;
 pub use user::*;
 
+mod admin_action;
+pub use admin_action::*;
+
 mod registry_entry;
 pub use registry_entry::*;
 
+mod registry_namespace;
+pub use registry_namespace::*;
+
 mod artifact;
 pub use artifact::*;
 
+mod artifact_chunk;
+pub use artifact_chunk::*;
+
+mod artifact_upload;
+pub use artifact_upload::*;
+
+mod station_config_template;
+pub use station_config_template::*;
+
+mod builtin_station_config_templates;
+pub use builtin_station_config_templates::*;
+
 pub mod indexes;
 
 mod rate_limiter;
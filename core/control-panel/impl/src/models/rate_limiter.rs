@@ -30,6 +30,15 @@ impl RateLimiter {
         }
     }
 
+    pub fn max_deployed_stations(&self) -> usize {
+        self.max_deployed_stations
+    }
+
+    /// Changes the maximum number of stations that can be deployed per day, including today.
+    pub fn set_max_deployed_stations(&mut self, max_deployed_stations: usize) {
+        self.max_deployed_stations = max_deployed_stations;
+    }
+
     pub fn can_deploy_station(&self) -> CanDeployStation {
         match self.remaining_quota() {
             Some(remaining) => CanDeployStation::Allowed(remaining),
@@ -39,15 +48,16 @@ impl RateLimiter {
 
     pub fn remaining_quota(&self) -> Option<usize> {
         let current_unix_date = time() / 86_400_000_000_000;
-        if self.unix_date == current_unix_date {
-            if self.num_deployed_stations >= self.max_deployed_stations {
-                None
-            } else {
-                Some(self.max_deployed_stations - self.num_deployed_stations)
-            }
+        let num_deployed_stations = if self.unix_date == current_unix_date {
+            self.num_deployed_stations
         } else {
-            Some(self.max_deployed_stations)
-        }
+            0
+        };
+
+        // a limit of zero stations per day disables the deployments
+        self.max_deployed_stations
+            .checked_sub(num_deployed_stations)
+            .filter(|remaining| *remaining > 0)
     }
 
     pub fn add_deployed_station(&mut self) {
@@ -1,6 +1,8 @@
-use super::artifact::ArtifactId;
+use super::{artifact::ArtifactId, StationConfigTemplate};
 use crate::repositories::{RegistryWhere, REGISTRY_REPOSITORY};
 use crate::{core::ic_cdk::next_time, errors::RegistryError};
+use candid::Principal;
+use control_panel_api::{RegistryEntrySortBy, SortDirection};
 use orbit_essentials::model::{ModelKey, ModelValidator, ModelValidatorResult};
 use orbit_essentials::repository::Repository;
 use orbit_essentials::storable;
@@ -17,8 +19,8 @@ pub type RegistryEntryId = UUID;
 
 /// The registry is a record that is stored in the registry repository.
 ///
-/// It stores entries about wasm modules and can be extended to other entry types. When adding new entry types,
-/// the `RegistryValue` enum should be updated to include the new entry type.
+/// It stores entries about wasm modules, Candid interfaces, asset bundles and station configuration templates.
+/// When adding new entry types, the `RegistryValue` enum should be updated to include the new entry type.
 #[storable]
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct RegistryEntry {
@@ -64,7 +66,8 @@ pub struct RegistryEntry {
     /// - Categories must be between 2 and 32 characters long.
     /// - There can be up to 10 categories per entry.
     pub categories: Vec<String>,
-    /// The content of the entry in the registry, which can be a Wasm module.
+    /// The content of the entry in the registry, which can be a Wasm module, a Candid interface,
+    /// an asset bundle or a station configuration template.
     pub value: RegistryValue,
     /// The timestamp when the entry was created.
     pub created_at: Timestamp,
@@ -81,6 +84,32 @@ pub struct RegistryEntry {
     /// - The value must be between 1 and 512 characters long.
     /// - There can be up to 10 metadata entries per entry in the registry.
     pub metadata: BTreeMap<String, String>,
+    /// The principal that published the entry, entries published before namespaces had owners have no publisher.
+    #[serde(default)]
+    pub publisher: Option<Principal>,
+    /// The attestations of the content of the entry, which are cleared when the content changes.
+    ///
+    /// Restrictions:
+    ///
+    /// - There can be up to 10 attestations per entry, one per attester.
+    #[serde(default)]
+    pub attestations: Vec<RegistryEntryAttestation>,
+}
+
+/// An attestation that the content of an entry was reproducibly built from the given source.
+#[storable]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct RegistryEntryAttestation {
+    /// The principal that made the attestation, which must be able to publish to the namespace.
+    pub attester: Principal,
+    /// The sha256 hash of the reproducible build, which must match the content of the entry.
+    pub build_hash: Vec<u8>,
+    /// The repository of the source code (e.g. `https://github.com/dfinity/orbit`).
+    pub source_repository: String,
+    /// The commit of the source code that was built.
+    pub source_commit: String,
+    /// The timestamp when the attestation was made.
+    pub attested_at: Timestamp,
 }
 
 impl ModelKey<RegistryEntryId> for RegistryEntry {
@@ -97,6 +126,9 @@ impl ModelKey<RegistryEntryId> for RegistryEntry {
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum RegistryValue {
     WasmModule(WasmModuleRegistryValue),
+    CandidInterface(CandidInterfaceRegistryValue),
+    AssetBundle(AssetBundleRegistryValue),
+    StationConfigTemplate(StationConfigTemplateRegistryValue),
 }
 
 /// The registry type, which is the type of the registry.
@@ -104,6 +136,9 @@ pub enum RegistryValue {
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, Hash)]
 pub enum RegistryValueKind {
     WasmModule = 1,
+    CandidInterface = 2,
+    AssetBundle = 3,
+    StationConfigTemplate = 4,
 }
 
 /// The wasm module registry value, which is the content of the wasm module and its version.
@@ -131,13 +166,83 @@ pub struct WasmModuleRegistryValue {
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct WasmModuleRegistryEntryDependency {
     pub name: String,
+    /// The version of the dependency, which is either an exact version (e.g. `1.2.0`) or a semver
+    /// requirement (e.g. `^0.5` or `>=1.2 <2`).
+    pub version: String,
+}
+
+/// The Candid interface registry value, which is the `.did` file of a canister and its version.
+#[storable]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct CandidInterfaceRegistryValue {
+    /// The id of the Candid interface file that is stored in the artifact repository.
+    pub candid_artifact_id: ArtifactId,
+    /// The version of the Candid interface.
+    ///
+    /// Restrictions:
+    ///
+    /// - Versions must be between 1 and 32 characters long.
+    pub version: String,
+    /// The wasm module entry that exposes this interface, if any.
+    pub wasm_module: Option<WasmModuleRegistryEntryDependency>,
+}
+
+/// The asset bundle registry value, which is a tarball of the assets of an asset canister.
+#[storable]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct AssetBundleRegistryValue {
+    /// The id of the tarball that is stored in the artifact repository.
+    pub bundle_artifact_id: ArtifactId,
+    /// The version of the asset bundle.
+    ///
+    /// Restrictions:
+    ///
+    /// - Versions must be between 1 and 32 characters long.
     pub version: String,
 }
 
+/// The station configuration template registry value, which is a JSON document with the initial
+/// configuration of a station.
+#[storable]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct StationConfigTemplateRegistryValue {
+    /// The id of the JSON template that is stored in the artifact repository.
+    pub template_artifact_id: ArtifactId,
+    /// The version of the template.
+    ///
+    /// Restrictions:
+    ///
+    /// - Versions must be between 1 and 32 characters long.
+    pub version: String,
+}
+
+/// A step of an upgrade path, the upgrader of the step must be installed before its station.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradePathStep {
+    pub station: RegistryEntry,
+    /// The upgrader to install, if the installed upgrader does not satisfy the station.
+    pub upgrader: Option<RegistryEntry>,
+}
+
+/// The provenance of an entry, as returned by the verification of the entry.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistryEntryVerification {
+    pub publisher: Option<Principal>,
+    /// Whether the publisher can still publish to the namespace of the entry.
+    pub publisher_authorized: bool,
+    /// The sha256 hash of the content, for wasm modules with extra chunks it is the hash of the whole module.
+    pub content_hash: Vec<u8>,
+    pub attestations: Vec<RegistryEntryAttestation>,
+    pub verified: bool,
+}
+
 impl Display for RegistryValueKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             RegistryValueKind::WasmModule => write!(f, "WasmModule"),
+            RegistryValueKind::CandidInterface => write!(f, "CandidInterface"),
+            RegistryValueKind::AssetBundle => write!(f, "AssetBundle"),
+            RegistryValueKind::StationConfigTemplate => write!(f, "StationConfigTemplate"),
         }
     }
 }
@@ -175,6 +280,8 @@ impl RegistryEntry {
     pub const MIN_TAG_LENGTH: usize = 2;
     pub const MAX_TAG_LENGTH: usize = 32;
 
+    pub const MAX_ATTESTATIONS: usize = 10;
+
     /// Creates a new registry entry with a random id and default values.
     ///
     /// The value of the entry is a wasm module with a random id and default values.
@@ -195,6 +302,8 @@ impl RegistryEntry {
             metadata: Default::default(),
             created_at: next_time(),
             updated_at: None,
+            publisher: None,
+            attestations: Vec::new(),
         }
     }
 
@@ -204,12 +313,37 @@ impl RegistryEntry {
     }
 
     pub fn to_kind(&self) -> RegistryValueKind {
-        match &self.value {
-            RegistryValue::WasmModule(_) => RegistryValueKind::WasmModule,
+        RegistryValueKind::from(&self.value)
+    }
+}
+
+impl RegistryValue {
+    /// Returns the version of the value, all kinds of values are versioned.
+    pub fn version(&self) -> &str {
+        match self {
+            RegistryValue::WasmModule(value) => &value.version,
+            RegistryValue::CandidInterface(value) => &value.version,
+            RegistryValue::AssetBundle(value) => &value.version,
+            RegistryValue::StationConfigTemplate(value) => &value.version,
+        }
+    }
+
+    /// Returns the id of the artifact that holds the content of the value.
+    pub fn artifact_id(&self) -> &ArtifactId {
+        match self {
+            RegistryValue::WasmModule(value) => &value.wasm_artifact_id,
+            RegistryValue::CandidInterface(value) => &value.candid_artifact_id,
+            RegistryValue::AssetBundle(value) => &value.bundle_artifact_id,
+            RegistryValue::StationConfigTemplate(value) => &value.template_artifact_id,
         }
     }
 }
 
+impl RegistryEntryAttestation {
+    pub const BUILD_HASH_LENGTH: usize = 32;
+    pub const MAX_SOURCE_REPOSITORY_LENGTH: usize = 256;
+}
+
 impl WasmModuleRegistryValue {
     pub const MIN_VERSION_LENGTH: usize = 1;
     pub const MAX_VERSION_LENGTH: usize = 32;
@@ -217,7 +351,139 @@ impl WasmModuleRegistryValue {
     pub const MAX_DEPENDENCIES: usize = 25;
 }
 
-fn validate_wasm_module_version(version: &str) -> ModelValidatorResult<RegistryError> {
+impl WasmModuleRegistryEntryDependency {
+    pub const MAX_VERSION_REQUIREMENT_LENGTH: usize = 64;
+
+    /// Parses the version of the dependency as a semver requirement.
+    ///
+    /// An exact version only matches itself, unlike in `semver` where `1.2.0` means `^1.2.0`, and the
+    /// comparators of a requirement can be separated by whitespace or commas.
+    pub fn version_req(&self) -> Result<semver::VersionReq, RegistryError> {
+        let invalid = |info: String| RegistryError::ValidationError {
+            info: format!(
+                "Invalid version requirement {} of the dependency {}: {info}",
+                self.version, self.name
+            ),
+        };
+
+        if self.version.len() > Self::MAX_VERSION_REQUIREMENT_LENGTH {
+            return Err(invalid(format!(
+                "it must be at most {} characters long",
+                Self::MAX_VERSION_REQUIREMENT_LENGTH
+            )));
+        }
+
+        if let Ok(version) = semver::Version::parse(&self.version) {
+            return semver::VersionReq::parse(&format!("={version}"))
+                .map_err(|e| invalid(e.to_string()));
+        }
+
+        // Operators that are separated from their version by whitespace are joined back to it.
+        let mut comparators: Vec<String> = Vec::new();
+        let mut pending_operator = String::new();
+        for part in self
+            .version
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+        {
+            if part
+                .chars()
+                .all(|c| matches!(c, '<' | '>' | '=' | '~' | '^'))
+            {
+                pending_operator.push_str(part);
+            } else {
+                comparators.push(format!("{}{part}", std::mem::take(&mut pending_operator)));
+            }
+        }
+
+        if comparators.is_empty() || !pending_operator.is_empty() {
+            return Err(invalid("it must contain at least one version".to_string()));
+        }
+
+        semver::VersionReq::parse(&comparators.join(", ")).map_err(|e| invalid(e.to_string()))
+    }
+
+    /// Returns the newest wasm module of the registry that satisfies the dependency.
+    pub fn resolve(&self) -> Result<Option<RegistryEntry>, RegistryError> {
+        let version_req = self.version_req()?;
+        let candidates = REGISTRY_REPOSITORY.find_ids_where(
+            RegistryWhere::clause()
+                .and_fullname(&self.name)
+                .and_kind(RegistryValueKind::WasmModule),
+            Some(RegistryEntrySortBy::Version(SortDirection::Desc)),
+        );
+
+        Ok(candidates
+            .iter()
+            .filter_map(|id| REGISTRY_REPOSITORY.get(id))
+            .find(|entry| {
+                semver::Version::parse(entry.value.version())
+                    .is_ok_and(|version| version_req.matches(&version))
+            }))
+    }
+}
+
+impl CandidInterfaceRegistryValue {
+    /// Validates that the content is text that declares a service.
+    ///
+    /// The interface is not parsed to keep the Candid parser out of the control panel, publishers are
+    /// expected to check it before publishing (e.g. `orbit-cli registry publish` runs `didc check`) and
+    /// stations parse it again when it is registered for an external canister.
+    pub fn validate_content(candid: &[u8]) -> ModelValidatorResult<RegistryError> {
+        let candid = std::str::from_utf8(candid).map_err(|_| RegistryError::ValidationError {
+            info: "The Candid interface must be valid UTF-8".to_string(),
+        })?;
+
+        let declares_service = candid
+            .lines()
+            .map(|line| line.split("//").next().unwrap_or_default())
+            .flat_map(|line| line.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_')))
+            .any(|word| word == "service");
+
+        if !declares_service {
+            return Err(RegistryError::ValidationError {
+                info: "The Candid interface does not define a service".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl AssetBundleRegistryValue {
+    const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+    const TAR_MAGIC: &'static [u8] = b"ustar";
+    const TAR_MAGIC_OFFSET: usize = 257;
+
+    /// Validates that the content is a tarball, which can optionally be gzip compressed.
+    pub fn validate_content(bundle: &[u8]) -> ModelValidatorResult<RegistryError> {
+        let is_gzip = bundle.starts_with(&Self::GZIP_MAGIC);
+        let is_tar = bundle
+            .get(Self::TAR_MAGIC_OFFSET..Self::TAR_MAGIC_OFFSET + Self::TAR_MAGIC.len())
+            .is_some_and(|magic| magic == Self::TAR_MAGIC);
+
+        if !is_gzip && !is_tar {
+            return Err(RegistryError::ValidationError {
+                info: "The asset bundle must be a tar or tar.gz archive".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl StationConfigTemplateRegistryValue {
+    /// Validates that the content is a station config template.
+    pub fn validate_content(template: &[u8]) -> ModelValidatorResult<RegistryError> {
+        StationConfigTemplate::from_json(template).map_err(|e| RegistryError::ValidationError {
+            info: format!("Invalid station configuration template: {e}"),
+        })?;
+
+        Ok(())
+    }
+}
+
+fn validate_version(version: &str) -> ModelValidatorResult<RegistryError> {
     if (version.len() < WasmModuleRegistryValue::MIN_VERSION_LENGTH)
         || (version.len() > WasmModuleRegistryValue::MAX_VERSION_LENGTH)
     {
@@ -240,26 +506,22 @@ fn validate_wasm_module_version(version: &str) -> ModelValidatorResult<RegistryE
 }
 
 fn validate_uniqueness(entry: &RegistryEntry) -> ModelValidatorResult<RegistryError> {
-    match &entry.value {
-        RegistryValue::WasmModule(value) => {
-            let mut ids = REGISTRY_REPOSITORY.find_ids_where(
-                RegistryWhere::clause()
-                    .and_fullname(&entry.fullname())
-                    .and_version(&value.version),
-                None,
-            );
+    let mut ids = REGISTRY_REPOSITORY.find_ids_where(
+        RegistryWhere::clause()
+            .and_fullname(&entry.fullname())
+            .and_version(entry.value.version()),
+        None,
+    );
 
-            ids.retain(|id| id != &entry.id);
+    ids.retain(|id| id != &entry.id);
 
-            if !ids.is_empty() {
-                return Err(RegistryError::ValidationError {
-                    info: format!(
-                        "The entry is a duplicate of {}",
-                        Uuid::from_bytes(ids[0]).hyphenated()
-                    ),
-                });
-            }
-        }
+    if !ids.is_empty() {
+        return Err(RegistryError::ValidationError {
+            info: format!(
+                "The entry is a duplicate of {}",
+                Uuid::from_bytes(ids[0]).hyphenated()
+            ),
+        });
     }
 
     Ok(())
@@ -305,24 +567,16 @@ fn dfs_check_entry_dependencies(
     match &entry.value {
         RegistryValue::WasmModule(value) => {
             for dependency in value.dependencies.iter() {
-                let found = REGISTRY_REPOSITORY.find_ids_where(
-                    RegistryWhere::clause()
-                        .and_fullname(&dependency.name)
-                        .and_version(&dependency.version)
-                        .and_kind(RegistryValueKind::WasmModule),
-                    None,
-                );
-
-                if found.is_empty() {
-                    // The dependency is not found in the registry and can be ignored.
-                    continue;
-                }
-
-                if let Some(dependency_entry) = REGISTRY_REPOSITORY.get(&found[0]) {
+                // Dependencies that are not found in the registry can be ignored.
+                if let Some(dependency_entry) = dependency.resolve()? {
                     dfs_check_entry_dependencies(&dependency_entry, visited)?;
                 }
             }
         }
+        // Only wasm modules can depend on other entries.
+        RegistryValue::CandidInterface(_)
+        | RegistryValue::AssetBundle(_)
+        | RegistryValue::StationConfigTemplate(_) => {}
     }
 
     Ok(())
@@ -342,23 +596,36 @@ fn validate_dependencies(entry: &RegistryEntry) -> ModelValidatorResult<Registry
             }
 
             for dependency in value.dependencies.iter() {
-                validate_wasm_module_version(&dependency.version)?;
+                if dependency.resolve()?.is_none() {
+                    return Err(RegistryError::ValidationError {
+                        info: format!(
+                            "The dependency {} cannot be resolved, no wasm module satisfies {}",
+                            dependency.name, dependency.version
+                        ),
+                    });
+                }
+            }
+        }
+        RegistryValue::CandidInterface(value) => {
+            if let Some(wasm_module) = &value.wasm_module {
+                validate_version(&wasm_module.version)?;
 
                 let found = REGISTRY_REPOSITORY.find_ids_where(
                     RegistryWhere::clause()
-                        .and_fullname(&dependency.name)
-                        .and_version(&dependency.version)
+                        .and_fullname(&wasm_module.name)
+                        .and_version(&wasm_module.version)
                         .and_kind(RegistryValueKind::WasmModule),
                     None,
                 );
 
                 if found.is_empty() {
                     return Err(RegistryError::ValidationError {
-                        info: format!("The dependency {} is not valid", dependency.name),
+                        info: format!("The linked wasm module {} is not valid", wasm_module.name),
                     });
                 }
             }
         }
+        RegistryValue::AssetBundle(_) | RegistryValue::StationConfigTemplate(_) => {}
     }
 
     // Check for circular dependencies
@@ -369,7 +636,7 @@ fn validate_dependencies(entry: &RegistryEntry) -> ModelValidatorResult<Registry
 
 impl ModelValidator<RegistryError> for WasmModuleRegistryValue {
     fn validate(&self) -> ModelValidatorResult<RegistryError> {
-        validate_wasm_module_version(&self.version)?;
+        validate_version(&self.version)?;
 
         Ok(())
     }
@@ -418,7 +685,7 @@ fn validate_name(name: &str) -> ModelValidatorResult<RegistryError> {
     Ok(())
 }
 
-fn validate_namespace(namespace: &str) -> ModelValidatorResult<RegistryError> {
+pub(crate) fn validate_namespace(namespace: &str) -> ModelValidatorResult<RegistryError> {
     if namespace.is_empty() {
         return Err(RegistryError::ValidationError {
             info: "Namespace cannot be empty".to_string(),
@@ -584,6 +851,75 @@ fn validate_timestamps(
     Ok(())
 }
 
+impl ModelValidator<RegistryError> for RegistryEntryAttestation {
+    fn validate(&self) -> ModelValidatorResult<RegistryError> {
+        if self.build_hash.len() != Self::BUILD_HASH_LENGTH {
+            return Err(RegistryError::ValidationError {
+                info: format!(
+                    "The build hash must be a sha256 hash of {} bytes",
+                    Self::BUILD_HASH_LENGTH
+                ),
+            });
+        }
+
+        if self.source_repository.trim().is_empty()
+            || self.source_repository.len() > Self::MAX_SOURCE_REPOSITORY_LENGTH
+        {
+            return Err(RegistryError::ValidationError {
+                info: format!(
+                    "Source repository length must be between 1 and {}",
+                    Self::MAX_SOURCE_REPOSITORY_LENGTH
+                ),
+            });
+        }
+
+        // Accepts both sha1 and sha256 git object names.
+        if !matches!(self.source_commit.len(), 40 | 64)
+            || !self
+                .source_commit
+                .chars()
+                .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+        {
+            return Err(RegistryError::ValidationError {
+                info: "Source commit must be a full lowercase hex git commit hash".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_attestations(
+    attestations: &[RegistryEntryAttestation],
+) -> ModelValidatorResult<RegistryError> {
+    if attestations.len() > RegistryEntry::MAX_ATTESTATIONS {
+        return Err(RegistryError::ValidationError {
+            info: format!(
+                "Too many attestations, expected at most {} but got {}",
+                RegistryEntry::MAX_ATTESTATIONS,
+                attestations.len()
+            ),
+        });
+    }
+
+    let attesters = attestations
+        .iter()
+        .map(|attestation| attestation.attester)
+        .collect::<HashSet<_>>();
+
+    if attesters.len() != attestations.len() {
+        return Err(RegistryError::ValidationError {
+            info: "There can only be one attestation per attester".to_string(),
+        });
+    }
+
+    for attestation in attestations {
+        attestation.validate()?;
+    }
+
+    Ok(())
+}
+
 impl ModelValidator<RegistryError> for RegistryEntry {
     fn validate(&self) -> ModelValidatorResult<RegistryError> {
         validate_namespace(&self.namespace)?;
@@ -593,9 +929,13 @@ impl ModelValidator<RegistryError> for RegistryEntry {
         validate_tags(&self.tags)?;
         validate_metadata(&self.metadata)?;
         validate_timestamps(self.created_at, self.updated_at)?;
+        validate_attestations(&self.attestations)?;
 
         match &self.value {
             RegistryValue::WasmModule(value) => value.validate(),
+            RegistryValue::CandidInterface(_)
+            | RegistryValue::AssetBundle(_)
+            | RegistryValue::StationConfigTemplate(_) => validate_version(self.value.version()),
         }?;
 
         validate_uniqueness(self)?;
@@ -623,6 +963,8 @@ pub mod registry_entry_test_utils {
             created_at: 0,
             updated_at: None,
             metadata: BTreeMap::new(),
+            publisher: None,
+            attestations: Vec::new(),
         }
     }
 
@@ -917,6 +1259,74 @@ mod tests {
         entry.validate().unwrap();
     }
 
+    #[rstest]
+    #[case::exact("1.2.0", "1.2.0", true)]
+    #[case::exact_does_not_match_newer("1.2.0", "1.2.1", false)]
+    #[case::caret("^0.5", "0.5.3", true)]
+    #[case::caret_does_not_match_next_minor("^0.5", "0.6.0", false)]
+    #[case::range(">=1.2 <2", "1.9.0", true)]
+    #[case::range_upper_bound(">=1.2 <2", "2.0.0", false)]
+    #[case::range_with_spaces_and_commas(">= 1.2, < 2", "1.2.0", true)]
+    fn dependency_version_requirements(
+        #[case] requirement: &str,
+        #[case] version: &str,
+        #[case] matches: bool,
+    ) {
+        let dependency = WasmModuleRegistryEntryDependency {
+            name: "@orbit/upgrader".to_string(),
+            version: requirement.to_string(),
+        };
+
+        let version_req = dependency.version_req().unwrap();
+
+        assert_eq!(
+            version_req.matches(&semver::Version::parse(version).unwrap()),
+            matches
+        );
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::dangling_operator(">=1.2 <")]
+    #[case::not_a_version("latest")]
+    fn invalid_dependency_version_requirements(#[case] requirement: &str) {
+        let dependency = WasmModuleRegistryEntryDependency {
+            name: "@orbit/upgrader".to_string(),
+            version: requirement.to_string(),
+        };
+
+        assert!(dependency.version_req().is_err());
+    }
+
+    #[test]
+    fn resolves_dependency_to_newest_matching_version() {
+        for version in ["0.5.0", "0.5.2", "0.6.0"] {
+            let mut entry = create_registry_entry();
+            entry.name = "range-dependency".to_string();
+            entry.value = RegistryValue::WasmModule(WasmModuleRegistryValue {
+                wasm_artifact_id: *Uuid::new_v4().as_bytes(),
+                version: version.to_string(),
+                dependencies: Vec::new(),
+                module_extra_chunks: None,
+            });
+
+            REGISTRY_REPOSITORY.insert(entry.id, entry);
+        }
+
+        let mut dependency = WasmModuleRegistryEntryDependency {
+            name: format!("@{}/range-dependency", RegistryEntry::DEFAULT_NAMESPACE),
+            version: "^0.5".to_string(),
+        };
+
+        let resolved = dependency.resolve().unwrap().unwrap();
+
+        assert_eq!(resolved.value.version(), "0.5.2");
+
+        dependency.version = ">=1 <2".to_string();
+
+        assert!(dependency.resolve().unwrap().is_none());
+    }
+
     #[test]
     fn detects_circular_dependencies() {
         let mut sub_package = create_registry_entry();
@@ -997,4 +1407,35 @@ mod tests {
 
         validate_dependencies(&package).unwrap();
     }
+
+    #[rstest]
+    #[case::short_build_hash(vec![0; 16], "a".repeat(40))]
+    #[case::short_commit(vec![0; 32], "a".repeat(7))]
+    #[case::uppercase_commit(vec![0; 32], "A".repeat(40))]
+    fn invalid_attestation(#[case] build_hash: Vec<u8>, #[case] source_commit: String) {
+        let mut entry = create_registry_entry();
+        entry.attestations = vec![RegistryEntryAttestation {
+            attester: Principal::from_slice(&[1; 29]),
+            build_hash,
+            source_repository: "https://github.com/dfinity/orbit".to_string(),
+            source_commit,
+            attested_at: 0,
+        }];
+
+        assert!(validate_attestations(&entry.attestations).is_err());
+    }
+
+    #[test]
+    fn only_one_attestation_per_attester() {
+        let attestation = RegistryEntryAttestation {
+            attester: Principal::from_slice(&[1; 29]),
+            build_hash: vec![0; 32],
+            source_repository: "https://github.com/dfinity/orbit".to_string(),
+            source_commit: "a".repeat(64),
+            attested_at: 0,
+        };
+
+        assert!(validate_attestations(&[attestation.clone()]).is_ok());
+        assert!(validate_attestations(&[attestation.clone(), attestation]).is_err());
+    }
 }
@@ -0,0 +1,139 @@
+use super::registry_entry::validate_namespace;
+use crate::{core::ic_cdk::next_time, errors::RegistryError};
+use candid::Principal;
+use orbit_essentials::model::{ModelKey, ModelValidator, ModelValidatorResult};
+use orbit_essentials::storable;
+use orbit_essentials::types::Timestamp;
+use std::collections::BTreeSet;
+
+/// A namespace of the registry and the principals that are allowed to publish to it.
+///
+/// Entries of namespaces that are not registered can only be published by the admins of the control panel.
+#[storable]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct RegistryNamespace {
+    /// The name of the namespace, without the `@` prefix.
+    pub name: String,
+    /// The owners of the namespace, which manage its owners and publishers and can also publish entries.
+    ///
+    /// Restrictions:
+    ///
+    /// - There must be between 1 and 10 owners.
+    pub owners: Vec<Principal>,
+    /// The principals that can publish entries to the namespace.
+    ///
+    /// Restrictions:
+    ///
+    /// - There can be up to 50 publishers.
+    pub publishers: Vec<Principal>,
+    /// The timestamp when the namespace was registered.
+    pub created_at: Timestamp,
+    /// The timestamp when the namespace was last updated.
+    pub updated_at: Option<Timestamp>,
+}
+
+impl ModelKey<String> for RegistryNamespace {
+    fn key(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl RegistryNamespace {
+    pub const MAX_OWNERS: usize = 10;
+    pub const MAX_PUBLISHERS: usize = 50;
+
+    pub fn new(name: String, owners: Vec<Principal>, publishers: Vec<Principal>) -> Self {
+        Self {
+            name,
+            owners,
+            publishers,
+            created_at: next_time(),
+            updated_at: None,
+        }
+    }
+
+    /// Returns `true` if the principal is an owner of the namespace.
+    pub fn is_owner(&self, principal: &Principal) -> bool {
+        self.owners.contains(principal)
+    }
+
+    /// Returns `true` if the principal can publish entries to the namespace.
+    pub fn can_publish(&self, principal: &Principal) -> bool {
+        self.is_owner(principal) || self.publishers.contains(principal)
+    }
+}
+
+fn validate_principals(
+    field: &str,
+    principals: &[Principal],
+    min: usize,
+    max: usize,
+) -> ModelValidatorResult<RegistryError> {
+    if principals.len() < min || principals.len() > max {
+        return Err(RegistryError::ValidationError {
+            info: format!("The namespace must have between {min} and {max} {field}"),
+        });
+    }
+
+    if principals.contains(&Principal::anonymous()) {
+        return Err(RegistryError::ValidationError {
+            info: format!("The anonymous principal cannot be one of the {field}"),
+        });
+    }
+
+    if principals.iter().collect::<BTreeSet<_>>().len() != principals.len() {
+        return Err(RegistryError::ValidationError {
+            info: format!("The {field} of the namespace must be unique"),
+        });
+    }
+
+    Ok(())
+}
+
+impl ModelValidator<RegistryError> for RegistryNamespace {
+    fn validate(&self) -> ModelValidatorResult<RegistryError> {
+        validate_namespace(&self.name)?;
+        validate_principals("owners", &self.owners, 1, Self::MAX_OWNERS)?;
+        validate_principals("publishers", &self.publishers, 0, Self::MAX_PUBLISHERS)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn owners_and_publishers_can_publish() {
+        let owner = Principal::from_slice(&[1; 29]);
+        let publisher = Principal::from_slice(&[2; 29]);
+        let namespace = RegistryNamespace::new("orbit".to_string(), vec![owner], vec![publisher]);
+
+        assert!(namespace.validate().is_ok());
+        assert!(namespace.can_publish(&owner));
+        assert!(namespace.can_publish(&publisher));
+        assert!(!namespace.is_owner(&publisher));
+        assert!(!namespace.can_publish(&Principal::from_slice(&[3; 29])));
+    }
+
+    #[test]
+    fn namespace_requires_an_owner() {
+        let namespace = RegistryNamespace::new("orbit".to_string(), vec![], vec![]);
+
+        assert!(namespace.validate().is_err());
+    }
+
+    #[test]
+    fn namespace_rejects_duplicate_or_anonymous_principals() {
+        let owner = Principal::from_slice(&[1; 29]);
+        let mut namespace = RegistryNamespace::new("orbit".to_string(), vec![owner, owner], vec![]);
+
+        assert!(namespace.validate().is_err());
+
+        namespace.owners = vec![owner];
+        namespace.publishers = vec![Principal::anonymous()];
+
+        assert!(namespace.validate().is_err());
+    }
+}
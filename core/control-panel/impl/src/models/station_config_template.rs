@@ -0,0 +1,388 @@
+use crate::errors::DeployError;
+use serde::{Deserialize, Serialize};
+use station_api::{
+    AllowDTO, DisasterRecoveryCommitteeDTO, InitAccountWithPermissionsInput, InitAssetInput,
+    InitNamedRuleInput, InitPermissionInput, InitRequestPolicyInput, InitUserGroupInput,
+    InitUserInput, InitialConfig, RequestPolicyRuleDTO, UserSpecifierDTO, UuidDTO,
+};
+use std::{collections::BTreeSet, str::FromStr};
+use uuid::Uuid;
+
+/// A declarative configuration of the initial entries of a station, applied when it is deployed.
+///
+/// Templates are stored as JSON in station config template registry entries. The admins of the
+/// deployment are the only users of the station, they are added to the `admin_groups` of the template.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StationConfigTemplate {
+    /// A description of the template, shown when choosing a template.
+    pub description: Option<String>,
+    /// The minimum number of admins required to deploy a station with the template.
+    pub min_admins: u16,
+    pub user_groups: Vec<InitUserGroupInput>,
+    /// The ids of the user groups that the admins of the deployment are added to.
+    pub admin_groups: Vec<UuidDTO>,
+    pub named_rules: Vec<InitNamedRuleInput>,
+    pub permissions: Vec<InitPermissionInput>,
+    pub request_policies: Vec<InitRequestPolicyInput>,
+    pub accounts: Vec<InitAccountWithPermissionsInput>,
+    pub assets: Vec<InitAssetInput>,
+    pub disaster_recovery_committee: Option<DisasterRecoveryCommitteeDTO>,
+}
+
+impl StationConfigTemplate {
+    /// Parses the template from its JSON representation.
+    pub fn from_json(content: &[u8]) -> Result<Self, String> {
+        let value: serde_json::Value =
+            serde_json::from_slice(content).map_err(|e| e.to_string())?;
+
+        if !value.is_object() {
+            return Err("the template must be a JSON object".to_string());
+        }
+
+        serde_json::from_value(value).map_err(|e| e.to_string())
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("station config templates are serializable")
+    }
+
+    /// Validates that the template can be deployed with the given number of admins.
+    ///
+    /// Checks the ids of the entries and the references between them, besides the references that
+    /// depend on the admins of the deployment. The contents of the entries (e.g. the resources of the
+    /// permissions) are validated by the station when it is initialized.
+    pub fn validate_for_deployment(&self, admins: usize) -> Result<(), DeployError> {
+        self.validate_references()?;
+
+        let min_admins = std::cmp::max(self.min_admins, 1) as usize;
+        if admins < min_admins {
+            return Err(invalid_template(format!(
+                "at least {min_admins} admins are required, got {admins}"
+            )));
+        }
+
+        let user_groups = self
+            .user_groups
+            .iter()
+            .filter_map(|group| group.id.as_deref().and_then(parse_uuid))
+            .collect::<BTreeSet<_>>();
+
+        if self.admin_groups.is_empty() {
+            return Err(invalid_template(
+                "the admins must be added to at least one user group".to_string(),
+            ));
+        }
+
+        for group in self.admin_groups.iter() {
+            if !parse_uuid(group).is_some_and(|id| user_groups.contains(&id)) {
+                return Err(invalid_template(format!(
+                    "the admin group {group} is not a user group of the template"
+                )));
+            }
+        }
+
+        if let Some(committee) = &self.disaster_recovery_committee {
+            let is_admin_group = self
+                .admin_groups
+                .iter()
+                .any(|group| parse_uuid(group) == parse_uuid(&committee.user_group_id));
+
+            // The admins are the only users, so the committee must be one of their groups.
+            if !is_admin_group || committee.quorum == 0 || committee.quorum as usize > admins {
+                return Err(invalid_template(format!(
+                    "the disaster recovery committee cannot be satisfied by {admins} admins"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates that the ids of the entries are unique UUIDs and that the entries only refer to
+    /// entries of the template.
+    fn validate_references(&self) -> Result<(), DeployError> {
+        let user_groups = unique_ids(
+            "user group",
+            self.user_groups.iter().map(|group| group.id.as_ref()),
+        )?;
+        let named_rules = unique_ids(
+            "named rule",
+            self.named_rules.iter().map(|rule| rule.id.as_ref()),
+        )?;
+        let assets = unique_ids("asset", self.assets.iter().map(|asset| asset.id.as_ref()))?;
+        unique_ids(
+            "request policy",
+            self.request_policies
+                .iter()
+                .map(|policy| policy.id.as_ref()),
+        )?;
+        unique_ids(
+            "account",
+            self.accounts
+                .iter()
+                .map(|account| account.account_init.id.as_ref()),
+        )?;
+
+        let mut names = BTreeSet::new();
+        for group in self.user_groups.iter() {
+            if group.name.trim().is_empty() || !names.insert(group.name.as_str()) {
+                return Err(invalid_template(format!(
+                    "the user group name `{}` is empty or not unique",
+                    group.name
+                )));
+            }
+        }
+
+        let check_allow = |allow: &AllowDTO| -> Result<(), DeployError> {
+            // the admins are the only users and their ids are only known once the station is initialized
+            if !allow.users.is_empty() {
+                return Err(invalid_template(
+                    "permissions can only be granted to user groups".to_string(),
+                ));
+            }
+
+            check_refs("user group", &allow.user_groups, &user_groups)
+        };
+
+        let rules = self
+            .named_rules
+            .iter()
+            .map(|rule| &rule.rule)
+            .chain(self.request_policies.iter().map(|policy| &policy.rule))
+            .chain(self.accounts.iter().flat_map(|account| {
+                account
+                    .permissions
+                    .configs_request_policy
+                    .iter()
+                    .chain(account.permissions.transfer_request_policy.iter())
+            }));
+        for rule in rules {
+            check_rule(rule, &user_groups, &named_rules)?;
+        }
+
+        for permission in self.permissions.iter() {
+            check_allow(&permission.allow)?;
+        }
+
+        for account in self.accounts.iter() {
+            check_allow(&account.permissions.read_permission)?;
+            check_allow(&account.permissions.configs_permission)?;
+            check_allow(&account.permissions.transfer_permission)?;
+            check_refs("asset", &account.account_init.assets, &assets)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the initial configuration of the station with the given admins as its users.
+    pub fn into_initial_config(self, mut admins: Vec<InitUserInput>) -> InitialConfig {
+        for admin in admins.iter_mut() {
+            admin.groups = Some(self.admin_groups.clone());
+        }
+
+        InitialConfig::Complete {
+            users: admins,
+            user_groups: self.user_groups,
+            permissions: self.permissions,
+            request_policies: self.request_policies,
+            named_rules: self.named_rules,
+            accounts: self.accounts,
+            assets: self.assets,
+            disaster_recovery_committee: self.disaster_recovery_committee,
+        }
+    }
+}
+
+fn parse_uuid(id: &str) -> Option<Uuid> {
+    Uuid::from_str(id).ok()
+}
+
+/// Parses the ids of the entries of a kind, entries without an id get one assigned by the station.
+fn unique_ids<'a>(
+    kind: &str,
+    ids: impl Iterator<Item = Option<&'a UuidDTO>>,
+) -> Result<BTreeSet<Uuid>, DeployError> {
+    let mut unique = BTreeSet::new();
+    for id in ids.flatten() {
+        let Some(uuid) = parse_uuid(id) else {
+            return Err(invalid_template(format!(
+                "the {kind} id {id} is not a UUID"
+            )));
+        };
+
+        if !unique.insert(uuid) {
+            return Err(invalid_template(format!(
+                "the {kind} id {id} is not unique"
+            )));
+        }
+    }
+
+    Ok(unique)
+}
+
+fn check_refs(kind: &str, refs: &[UuidDTO], ids: &BTreeSet<Uuid>) -> Result<(), DeployError> {
+    match refs
+        .iter()
+        .find(|id| !parse_uuid(id).is_some_and(|uuid| ids.contains(&uuid)))
+    {
+        Some(id) => Err(invalid_template(format!(
+            "the {kind} {id} is not a {kind} of the template"
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn check_rule(
+    rule: &RequestPolicyRuleDTO,
+    user_groups: &BTreeSet<Uuid>,
+    named_rules: &BTreeSet<Uuid>,
+) -> Result<(), DeployError> {
+    let check_approvers = |approvers: &UserSpecifierDTO| match approvers {
+        UserSpecifierDTO::Any => Ok(()),
+        UserSpecifierDTO::Group(groups) => check_refs("user group", groups, user_groups),
+        UserSpecifierDTO::Id(_) => Err(invalid_template(
+            "request policies can only refer to user groups".to_string(),
+        )),
+    };
+
+    match rule {
+        RequestPolicyRuleDTO::AutoApproved
+        | RequestPolicyRuleDTO::AllowListed
+        | RequestPolicyRuleDTO::AllowListedByMetadata(_) => Ok(()),
+        RequestPolicyRuleDTO::Quorum(quorum) => check_approvers(&quorum.approvers),
+        RequestPolicyRuleDTO::QuorumPercentage(quorum) => check_approvers(&quorum.approvers),
+        RequestPolicyRuleDTO::AnyOf(rules) | RequestPolicyRuleDTO::AllOf(rules) => rules
+            .iter()
+            .try_for_each(|rule| check_rule(rule, user_groups, named_rules)),
+        RequestPolicyRuleDTO::Not(rule) => check_rule(rule, user_groups, named_rules),
+        RequestPolicyRuleDTO::NamedRule(id) => {
+            check_refs("named rule", std::slice::from_ref(id), named_rules)
+        }
+    }
+}
+
+fn invalid_template(reason: String) -> DeployError {
+    DeployError::InvalidTemplate { reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+    use station_api::{QuorumDTO, RequestSpecifierDTO, UserIdentityInput, UserStatusDTO};
+
+    const ADMIN_GROUP: &str = "00000000-0000-4000-8000-000000000000";
+
+    fn admin(id: u8) -> InitUserInput {
+        InitUserInput {
+            id: None,
+            name: format!("admin-{id}"),
+            identities: vec![UserIdentityInput {
+                identity: Principal::from_slice(&[id; 29]),
+            }],
+            groups: None,
+            status: UserStatusDTO::Active,
+        }
+    }
+
+    fn template() -> StationConfigTemplate {
+        StationConfigTemplate {
+            min_admins: 2,
+            user_groups: vec![InitUserGroupInput {
+                id: Some(ADMIN_GROUP.to_string()),
+                name: "Admin".to_string(),
+            }],
+            admin_groups: vec![ADMIN_GROUP.to_string()],
+            disaster_recovery_committee: Some(DisasterRecoveryCommitteeDTO {
+                user_group_id: ADMIN_GROUP.to_string(),
+                quorum: 2,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_templates_from_json() {
+        let template = StationConfigTemplate::from_json(template().to_json().as_bytes()).unwrap();
+        assert_eq!(template.admin_groups, vec![ADMIN_GROUP.to_string()]);
+
+        assert!(StationConfigTemplate::from_json(br#"{"description":"empty"}"#).is_ok());
+        assert!(StationConfigTemplate::from_json(b"[]").is_err());
+        assert!(StationConfigTemplate::from_json(br#"{"min_admins":"two"}"#).is_err());
+    }
+
+    #[test]
+    fn validates_template_against_admins() {
+        let template = template();
+
+        assert!(template.validate_for_deployment(1).is_err());
+        assert!(template.validate_for_deployment(2).is_ok());
+
+        let mut unknown_admin_group = template.clone();
+        unknown_admin_group.admin_groups = vec![Uuid::new_v4().to_string()];
+        assert!(unknown_admin_group.validate_for_deployment(2).is_err());
+
+        let mut unreachable_committee = template.clone();
+        unreachable_committee.min_admins = 1;
+        assert!(unreachable_committee.validate_for_deployment(1).is_err());
+    }
+
+    #[test]
+    fn validates_template_references() {
+        let named_rule = "00000000-0000-4000-8000-000000000001".to_string();
+        let mut template = template();
+        template.named_rules = vec![InitNamedRuleInput {
+            id: Some(named_rule.clone()),
+            name: "Admin approval".to_string(),
+            description: None,
+            rule: RequestPolicyRuleDTO::Quorum(QuorumDTO {
+                approvers: UserSpecifierDTO::Group(vec![ADMIN_GROUP.to_string()]),
+                min_approved: 1,
+            }),
+        }];
+        template.request_policies = vec![InitRequestPolicyInput {
+            id: None,
+            specifier: RequestSpecifierDTO::AddUser,
+            rule: RequestPolicyRuleDTO::NamedRule(named_rule),
+        }];
+        assert!(template.validate_for_deployment(2).is_ok());
+
+        let mut unknown_named_rule = template.clone();
+        unknown_named_rule.request_policies[0].rule = RequestPolicyRuleDTO::Not(Box::new(
+            RequestPolicyRuleDTO::NamedRule(Uuid::new_v4().to_string()),
+        ));
+        assert!(unknown_named_rule.validate_for_deployment(2).is_err());
+
+        let mut unknown_approvers = template.clone();
+        unknown_approvers.named_rules[0].rule = RequestPolicyRuleDTO::Quorum(QuorumDTO {
+            approvers: UserSpecifierDTO::Group(vec![Uuid::new_v4().to_string()]),
+            min_approved: 1,
+        });
+        assert!(unknown_approvers.validate_for_deployment(2).is_err());
+
+        let mut duplicate_group = template.clone();
+        duplicate_group.user_groups.push(InitUserGroupInput {
+            id: Some(ADMIN_GROUP.to_string()),
+            name: "Operator".to_string(),
+        });
+        assert!(duplicate_group.validate_for_deployment(2).is_err());
+
+        let mut invalid_id = template.clone();
+        invalid_id.request_policies[0].id = Some("not-a-uuid".to_string());
+        assert!(invalid_id.validate_for_deployment(2).is_err());
+    }
+
+    #[test]
+    fn adds_admins_to_admin_groups() {
+        let InitialConfig::Complete { users, .. } =
+            template().into_initial_config(vec![admin(1), admin(2)])
+        else {
+            panic!("expected a complete initial config");
+        };
+
+        assert!(users
+            .iter()
+            .all(|user| user.groups == Some(vec![ADMIN_GROUP.to_string()])));
+    }
+}
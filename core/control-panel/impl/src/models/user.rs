@@ -61,9 +61,15 @@ pub struct User {
     /// The stations that have ever been deployed for the user by the control panel.
     /// Used to bound the total number of stations a user could deploy via the control panel.
     deployed_stations: Vec<Principal>,
+    /// The deployed stations that should not be notified when a new station version is published.
+    #[serde(default)]
+    upgrade_announcement_opt_outs: Vec<Principal>,
     /// Used to rate limit the number of deployed stations per user per day by the control panel.
     #[serde(default = "RateLimiter::new_user")]
     user_rate_limiter: RateLimiter,
+    /// The maximum number of stations that can ever be deployed for the user, unlimited if not set.
+    #[serde(default)]
+    max_deployed_stations: Option<usize>,
     /// The timestamp of last time the user was active.
     pub last_active: Timestamp,
     /// Last time the identity was updated.
@@ -90,6 +96,14 @@ impl User {
         UserKey(self.id)
     }
 
+    /// Returns the e-mail address of the user while the user is on the waitlist.
+    pub fn get_waitlist_email(&self) -> Option<&str> {
+        match &self.subscription_status {
+            UserSubscriptionStatus::Pending(email) => Some(email),
+            _ => None,
+        }
+    }
+
     pub fn get_num_deployed_stations(&self) -> usize {
         self.deployed_stations.len()
     }
@@ -103,8 +117,74 @@ impl User {
         self.user_rate_limiter.add_deployed_station();
     }
 
+    /// Returns `true` if the deployed station should be notified when a new station version is published.
+    pub fn receives_upgrade_announcements(&self, station: &Principal) -> bool {
+        self.deployed_stations.contains(station)
+            && !self.upgrade_announcement_opt_outs.contains(station)
+    }
+
+    /// Enables or disables the announcements of new station versions for one of the deployed stations.
+    pub fn set_upgrade_announcements(
+        &mut self,
+        station: Principal,
+        enabled: bool,
+    ) -> Result<(), UserError> {
+        if !self.deployed_stations.contains(&station) {
+            return Err(UserError::ValidationError {
+                info: format!("The station {} was not deployed for the user", station),
+            });
+        }
+
+        self.upgrade_announcement_opt_outs
+            .retain(|opted_out| opted_out != &station);
+
+        if !enabled {
+            self.upgrade_announcement_opt_outs.push(station);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the maximum number of stations that can ever be deployed for the user, if limited.
+    pub fn get_max_deployed_stations(&self) -> Option<usize> {
+        self.max_deployed_stations
+    }
+
+    /// Returns the maximum number of stations that can be deployed for the user per day.
+    pub fn get_max_deployed_stations_per_day(&self) -> usize {
+        self.user_rate_limiter.max_deployed_stations()
+    }
+
+    /// Sets the quota of stations that can be deployed for the user, in total and per day.
+    pub fn set_deploy_quota(
+        &mut self,
+        max_deployed_stations: Option<usize>,
+        max_deployed_stations_per_day: usize,
+    ) {
+        self.max_deployed_stations = max_deployed_stations;
+        self.user_rate_limiter
+            .set_max_deployed_stations(max_deployed_stations_per_day);
+    }
+
+    /// Returns if the user can deploy a station, only approved users can deploy stations.
     pub fn can_deploy_station(&self) -> CanDeployStation {
-        self.user_rate_limiter.can_deploy_station()
+        if self.subscription_status != UserSubscriptionStatus::Approved {
+            return CanDeployStation::NotAllowed(self.subscription_status.clone());
+        }
+
+        let remaining_today = match self.user_rate_limiter.can_deploy_station() {
+            CanDeployStation::Allowed(remaining) => remaining,
+            not_allowed => return not_allowed,
+        };
+
+        match self.max_deployed_stations {
+            Some(max) if self.deployed_stations.len() >= max => CanDeployStation::QuotaExceeded,
+            Some(max) => CanDeployStation::Allowed(std::cmp::min(
+                remaining_today,
+                max - self.deployed_stations.len(),
+            )),
+            None => CanDeployStation::Allowed(remaining_today),
+        }
     }
 
     pub fn new_from_register_input(
@@ -124,7 +204,9 @@ impl User {
             subscription_status: UserSubscriptionStatus::Approved,
             stations: stations.into_iter().map(|station| station.into()).collect(),
             deployed_stations: vec![],
+            upgrade_announcement_opt_outs: vec![],
             user_rate_limiter: RateLimiter::new_user(),
+            max_deployed_stations: None,
             last_active: registration_time,
             last_update_timestamp: registration_time,
         }
@@ -244,6 +326,47 @@ mod tests {
         assert!(validate_stations(&user_with_too_many_stations.stations).is_err());
     }
 
+    #[test]
+    fn opts_out_of_upgrade_announcements() {
+        let mut user = mock_user();
+        let station = Principal::from_slice(&[1; 29]);
+
+        assert!(user.set_upgrade_announcements(station, false).is_err());
+
+        user.add_deployed_station(station);
+        assert!(user.receives_upgrade_announcements(&station));
+
+        user.set_upgrade_announcements(station, false).unwrap();
+        assert!(!user.receives_upgrade_announcements(&station));
+
+        user.set_upgrade_announcements(station, true).unwrap();
+        assert!(user.receives_upgrade_announcements(&station));
+    }
+
+    #[test]
+    fn deploy_quota_limits_stations() {
+        let mut user = mock_user();
+        assert_eq!(
+            user.can_deploy_station(),
+            CanDeployStation::Allowed(RateLimiter::new_user().max_deployed_stations())
+        );
+
+        user.set_deploy_quota(Some(1), 5);
+        assert_eq!(user.can_deploy_station(), CanDeployStation::Allowed(1));
+
+        user.add_deployed_station(Principal::from_slice(&[1; 29]));
+        assert_eq!(user.can_deploy_station(), CanDeployStation::QuotaExceeded);
+
+        user.set_deploy_quota(None, 5);
+        assert_eq!(user.can_deploy_station(), CanDeployStation::Allowed(4));
+
+        user.subscription_status = UserSubscriptionStatus::Denylisted;
+        assert_eq!(
+            user.can_deploy_station(),
+            CanDeployStation::NotAllowed(UserSubscriptionStatus::Denylisted)
+        );
+    }
+
     #[rstest]
     #[case::empty_name(&"")]
     #[case::invalid_email(&"john")]
@@ -267,7 +390,9 @@ pub mod user_model_utils {
             subscription_status: UserSubscriptionStatus::Approved,
             stations: vec![],
             deployed_stations: vec![],
+            upgrade_announcement_opt_outs: vec![],
             user_rate_limiter: RateLimiter::new_user(),
+            max_deployed_stations: None,
             last_active: 0,
             last_update_timestamp: 0,
         }
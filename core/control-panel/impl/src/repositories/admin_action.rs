@@ -0,0 +1,80 @@
+use crate::{
+    core::{with_memory_manager, Memory, ADMIN_ACTION_MEMORY_ID},
+    models::AdminAction,
+};
+use ic_stable_structures::{memory_manager::VirtualMemory, StableBTreeMap};
+use lazy_static::lazy_static;
+use orbit_essentials::repository::{Repository, StableDb};
+use orbit_essentials::types::Timestamp;
+use std::{cell::RefCell, sync::Arc};
+
+thread_local! {
+  static DB: RefCell<StableBTreeMap<Timestamp, AdminAction, VirtualMemory<Memory>>> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      StableBTreeMap::init(memory_manager.get(ADMIN_ACTION_MEMORY_ID))
+    )
+  })
+}
+
+lazy_static! {
+    pub static ref ADMIN_ACTION_REPOSITORY: Arc<AdminActionRepository> =
+        Arc::new(AdminActionRepository::default());
+}
+
+/// A repository that enables managing the admin action log in stable memory.
+#[derive(Default, Debug)]
+pub struct AdminActionRepository {}
+
+impl StableDb<Timestamp, AdminAction, VirtualMemory<Memory>> for AdminActionRepository {
+    fn with_db<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut StableBTreeMap<Timestamp, AdminAction, VirtualMemory<Memory>>) -> R,
+    {
+        DB.with(|m| f(&mut m.borrow_mut()))
+    }
+}
+
+impl Repository<Timestamp, AdminAction, VirtualMemory<Memory>> for AdminActionRepository {}
+
+impl AdminActionRepository {
+    /// Returns the keys of all the logged actions, the most recent action first.
+    pub fn find_keys_most_recent_first(&self) -> Vec<Timestamp> {
+        let mut keys = DB.with(|m| m.borrow().iter().map(|(key, _)| key).collect::<Vec<_>>());
+        keys.reverse();
+
+        keys
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AdminActionKind;
+    use candid::Principal;
+
+    #[test]
+    fn lists_most_recent_actions_first() {
+        let repository = AdminActionRepository::default();
+        let actions = (0..3)
+            .map(|_| {
+                AdminAction::new(
+                    Principal::from_slice(&[1; 29]),
+                    AdminActionKind::ExportWaitlist { num_users: 0 },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for action in actions.iter() {
+            repository.insert(action.performed_at, action.clone());
+        }
+
+        assert_eq!(
+            repository.find_keys_most_recent_first(),
+            actions
+                .iter()
+                .rev()
+                .map(|action| action.performed_at)
+                .collect::<Vec<_>>()
+        );
+    }
+}
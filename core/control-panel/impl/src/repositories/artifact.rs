@@ -10,7 +10,7 @@ use ic_stable_structures::{memory_manager::VirtualMemory, StableBTreeMap};
 use lazy_static::lazy_static;
 use orbit_essentials::repository::StableDb;
 use orbit_essentials::repository::{IndexRepository, IndexedRepository, Repository};
-use std::{cell::RefCell, sync::Arc};
+use std::{cell::RefCell, ops::Bound, sync::Arc};
 
 thread_local! {
   static DB: RefCell<StableBTreeMap<ArtifactId, Artifact, VirtualMemory<Memory>>> = with_memory_manager(|memory_manager| {
@@ -93,6 +93,22 @@ impl ArtifactRepository {
         artifacts.into_iter().next()
     }
 
+    /// Returns the artifact that comes after the given id in id order, or the first artifact if no
+    /// id is given.
+    pub fn find_next(&self, after: Option<&ArtifactId>) -> Option<Artifact> {
+        let start = match after {
+            Some(id) => Bound::Excluded(*id),
+            None => Bound::Unbounded,
+        };
+
+        DB.with(|m| {
+            m.borrow()
+                .range((start, Bound::Unbounded))
+                .next()
+                .map(|(_, artifact)| artifact)
+        })
+    }
+
     /// Finds an artifact by the given size.
     pub fn find_by_size_lte(&self, size: u64) -> Vec<ArtifactId> {
         let artifacts = self.indexes.find_by_criteria(ArtifactIndexCriteria {
@@ -128,6 +144,27 @@ mod tests {
         assert_eq!(repository.len(), 0);
     }
 
+    #[test]
+    fn test_find_next() {
+        let repository = ArtifactRepository::default();
+        let mut artifacts = vec![
+            Artifact::new(b"first".to_vec()),
+            Artifact::new(b"second".to_vec()),
+        ];
+        artifacts.sort_by_key(|artifact| *artifact.id());
+
+        for artifact in &artifacts {
+            repository.insert(*artifact.id(), artifact.clone());
+        }
+
+        assert_eq!(repository.find_next(None), Some(artifacts[0].clone()));
+        assert_eq!(
+            repository.find_next(Some(artifacts[0].id())),
+            Some(artifacts[1].clone())
+        );
+        assert_eq!(repository.find_next(Some(artifacts[1].id())), None);
+    }
+
     #[test]
     fn test_find_by_hash() {
         let repository = ArtifactRepository::default();
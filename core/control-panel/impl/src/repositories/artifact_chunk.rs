@@ -0,0 +1,162 @@
+use crate::{
+    core::{with_memory_manager, Memory, ARTIFACT_CHUNK_MEMORY_ID, ARTIFACT_CHUNK_SIZE_MEMORY_ID},
+    models::{ArtifactChunk, ArtifactChunkHash},
+};
+use ic_stable_structures::{memory_manager::VirtualMemory, Cell, StableBTreeMap};
+use lazy_static::lazy_static;
+use orbit_essentials::repository::{Repository, StableDb};
+use std::{cell::RefCell, ops::Bound, sync::Arc};
+
+thread_local! {
+  static DB: RefCell<StableBTreeMap<ArtifactChunkHash, ArtifactChunk, VirtualMemory<Memory>>> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      StableBTreeMap::init(memory_manager.get(ARTIFACT_CHUNK_MEMORY_ID))
+    )
+  });
+
+  /// The number of bytes stored by all the chunks, kept up to date as chunks are added and removed
+  /// so that it does not require reading every chunk.
+  static STORED_BYTES: RefCell<Cell<u64, VirtualMemory<Memory>>> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      Cell::init(memory_manager.get(ARTIFACT_CHUNK_SIZE_MEMORY_ID), 0)
+        .expect("failed to initialize the artifact chunk size cell")
+    )
+  })
+}
+
+lazy_static! {
+    pub static ref ARTIFACT_CHUNK_REPOSITORY: Arc<ArtifactChunkRepository> =
+        Arc::new(ArtifactChunkRepository::default());
+}
+
+/// A repository that enables managing the content-addressed chunks of artifacts in stable memory.
+#[derive(Default, Debug)]
+pub struct ArtifactChunkRepository {}
+
+impl StableDb<ArtifactChunkHash, ArtifactChunk, VirtualMemory<Memory>> for ArtifactChunkRepository {
+    fn with_db<F, R>(f: F) -> R
+    where
+        F: FnOnce(
+            &mut StableBTreeMap<ArtifactChunkHash, ArtifactChunk, VirtualMemory<Memory>>,
+        ) -> R,
+    {
+        DB.with(|m| f(&mut m.borrow_mut()))
+    }
+}
+
+impl Repository<ArtifactChunkHash, ArtifactChunk, VirtualMemory<Memory>>
+    for ArtifactChunkRepository
+{
+    fn insert(&self, key: ArtifactChunkHash, value: ArtifactChunk) -> Option<ArtifactChunk> {
+        let size = value.data().len() as u64;
+        let prev = Self::with_db(|db| db.insert(key, value));
+
+        update_stored_bytes(|bytes| {
+            bytes
+                .saturating_add(size)
+                .saturating_sub(prev.as_ref().map_or(0, |prev| prev.data().len() as u64))
+        });
+
+        prev
+    }
+
+    fn remove(&self, key: &ArtifactChunkHash) -> Option<ArtifactChunk> {
+        let prev = Self::with_db(|db| db.remove(key));
+
+        if let Some(prev) = &prev {
+            update_stored_bytes(|bytes| bytes.saturating_sub(prev.data().len() as u64));
+        }
+
+        prev
+    }
+
+    fn clear(&self) {
+        Self::with_db(|db| db.clear_new());
+        update_stored_bytes(|_| 0);
+    }
+}
+
+fn update_stored_bytes(f: impl FnOnce(u64) -> u64) {
+    STORED_BYTES.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let bytes = f(*cell.get());
+
+        cell.set(bytes)
+            .expect("failed to write the artifact chunk size");
+    });
+}
+
+impl ArtifactChunkRepository {
+    /// Returns the number of chunks and the number of bytes that they store.
+    pub fn stored_size(&self) -> (u64, u64) {
+        (
+            self.len() as u64,
+            STORED_BYTES.with(|cell| *cell.borrow().get()),
+        )
+    }
+
+    /// Returns the chunk that comes after the given hash in hash order, or the first chunk if no
+    /// hash is given.
+    pub fn find_next(&self, after: Option<&ArtifactChunkHash>) -> Option<ArtifactChunk> {
+        let start = match after {
+            Some(hash) => Bound::Excluded(*hash),
+            None => Bound::Unbounded,
+        };
+
+        Self::with_db(|db| {
+            db.range((start, Bound::Unbounded))
+                .next()
+                .map(|(_, chunk)| chunk)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_chunks_in_hash_order() {
+        let repository = ArtifactChunkRepository::default();
+        let referenced = ArtifactChunk::new(b"referenced".to_vec());
+        let mut unreferenced = ArtifactChunk::new(b"unreferenced".to_vec());
+        unreferenced.decrement_rc();
+
+        repository.insert(*referenced.hash(), referenced.clone());
+        repository.insert(*unreferenced.hash(), unreferenced.clone());
+
+        let mut chunks = vec![referenced, unreferenced];
+        chunks.sort_by_key(|chunk| *chunk.hash());
+
+        assert_eq!(repository.find_next(None), Some(chunks[0].clone()));
+        assert_eq!(
+            repository.find_next(Some(chunks[0].hash())),
+            Some(chunks[1].clone())
+        );
+        assert_eq!(repository.find_next(Some(chunks[1].hash())), None);
+        assert_eq!(repository.stored_size(), (2, 22));
+    }
+
+    #[test]
+    fn keeps_track_of_the_stored_size() {
+        let repository = ArtifactChunkRepository::default();
+        let chunk = ArtifactChunk::new(b"chunk".to_vec());
+        let other_chunk = ArtifactChunk::new(b"other chunk".to_vec());
+
+        repository.insert(*chunk.hash(), chunk.clone());
+        repository.insert(*other_chunk.hash(), other_chunk.clone());
+        assert_eq!(repository.stored_size(), (2, 16));
+
+        // updating the reference counter of a chunk does not change its size
+        let mut referenced_chunk = chunk.clone();
+        referenced_chunk.increment_rc();
+        repository.insert(*chunk.hash(), referenced_chunk);
+        assert_eq!(repository.stored_size(), (2, 16));
+
+        repository.remove(chunk.hash());
+        assert_eq!(repository.stored_size(), (1, 11));
+
+        repository.clear();
+        assert_eq!(repository.stored_size(), (0, 0));
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{
+    core::{with_memory_manager, Memory, ARTIFACT_UPLOAD_MEMORY_ID},
+    models::{ArtifactUpload, ArtifactUploadId},
+};
+use ic_stable_structures::{memory_manager::VirtualMemory, StableBTreeMap};
+use lazy_static::lazy_static;
+use orbit_essentials::repository::{Repository, StableDb};
+use std::{cell::RefCell, ops::Bound, sync::Arc};
+
+thread_local! {
+  static DB: RefCell<StableBTreeMap<ArtifactUploadId, ArtifactUpload, VirtualMemory<Memory>>> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      StableBTreeMap::init(memory_manager.get(ARTIFACT_UPLOAD_MEMORY_ID))
+    )
+  })
+}
+
+lazy_static! {
+    pub static ref ARTIFACT_UPLOAD_REPOSITORY: Arc<ArtifactUploadRepository> =
+        Arc::new(ArtifactUploadRepository::default());
+}
+
+/// A repository that enables managing the artifact uploads in progress in stable memory.
+#[derive(Default, Debug)]
+pub struct ArtifactUploadRepository {}
+
+impl StableDb<ArtifactUploadId, ArtifactUpload, VirtualMemory<Memory>>
+    for ArtifactUploadRepository
+{
+    fn with_db<F, R>(f: F) -> R
+    where
+        F: FnOnce(
+            &mut StableBTreeMap<ArtifactUploadId, ArtifactUpload, VirtualMemory<Memory>>,
+        ) -> R,
+    {
+        DB.with(|m| f(&mut m.borrow_mut()))
+    }
+}
+
+impl Repository<ArtifactUploadId, ArtifactUpload, VirtualMemory<Memory>>
+    for ArtifactUploadRepository
+{
+}
+
+impl ArtifactUploadRepository {
+    /// Returns the upload that comes after the given id in id order, or the first upload if no id
+    /// is given.
+    pub fn find_next(&self, after: Option<&ArtifactUploadId>) -> Option<ArtifactUpload> {
+        let start = match after {
+            Some(id) => Bound::Excluded(*id),
+            None => Bound::Unbounded,
+        };
+
+        Self::with_db(|db| {
+            db.range((start, Bound::Unbounded))
+                .next()
+                .map(|(_, upload)| upload)
+        })
+    }
+}
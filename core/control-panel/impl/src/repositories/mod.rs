@@ -1,12 +1,24 @@
 //! Repositories for models and related information, such as indexes.
 
+mod admin_action;
+pub use admin_action::*;
+
 mod artifact;
 pub use artifact::*;
 
+mod artifact_chunk;
+pub use artifact_chunk::*;
+
+mod artifact_upload;
+pub use artifact_upload::*;
+
 mod user;
 pub use user::*;
 
 mod registry;
 pub use registry::*;
 
+mod registry_namespace;
+pub use registry_namespace::*;
+
 pub mod indexes;
@@ -775,6 +775,7 @@ mod tests {
             RegistryValue::WasmModule(ref value) => {
                 assert_eq!(value.version, "1.0.2");
             }
+            _ => panic!("expected a wasm module"),
         }
 
         let result = repository.find_ids_where(
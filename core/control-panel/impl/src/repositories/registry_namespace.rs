@@ -0,0 +1,36 @@
+use crate::{
+    core::{with_memory_manager, Memory, REGISTRY_NAMESPACE_MEMORY_ID},
+    models::RegistryNamespace,
+};
+use ic_stable_structures::{memory_manager::VirtualMemory, StableBTreeMap};
+use lazy_static::lazy_static;
+use orbit_essentials::repository::{Repository, StableDb};
+use std::{cell::RefCell, sync::Arc};
+
+thread_local! {
+  static DB: RefCell<StableBTreeMap<String, RegistryNamespace, VirtualMemory<Memory>>> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      StableBTreeMap::init(memory_manager.get(REGISTRY_NAMESPACE_MEMORY_ID))
+    )
+  })
+}
+
+lazy_static! {
+    pub static ref REGISTRY_NAMESPACE_REPOSITORY: Arc<RegistryNamespaceRepository> =
+        Arc::new(RegistryNamespaceRepository::default());
+}
+
+/// A repository that enables managing the namespaces of the registry in stable memory.
+#[derive(Default, Debug)]
+pub struct RegistryNamespaceRepository {}
+
+impl StableDb<String, RegistryNamespace, VirtualMemory<Memory>> for RegistryNamespaceRepository {
+    fn with_db<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut StableBTreeMap<String, RegistryNamespace, VirtualMemory<Memory>>) -> R,
+    {
+        DB.with(|m| f(&mut m.borrow_mut()))
+    }
+}
+
+impl Repository<String, RegistryNamespace, VirtualMemory<Memory>> for RegistryNamespaceRepository {}
@@ -1,6 +1,12 @@
 use crate::{
     core::{metrics::USER_METRICS, with_memory_manager, Memory, USER_MEMORY_ID},
-    models::{indexes::user_identity_index::UserIdentityIndexCriteria, User, UserKey},
+    models::{
+        indexes::{
+            user_identity_index::UserIdentityIndexCriteria,
+            user_status_index::{UserIndexSubscriptionStatus, UserStatusIndexCriteria},
+        },
+        User, UserId, UserKey,
+    },
     repositories::indexes::{
         user_identity_index::UserIdentityIndexRepository,
         user_status_index::UserStatusIndexRepository,
@@ -106,12 +112,32 @@ impl UserRepository {
             .iter()
             .find_map(|id| self.get(&UserKey(*id)))
     }
+
+    /// Returns the ids of the users with the given subscription status, or of all users if no status is given.
+    ///
+    /// The ids are sorted so that the users can be paginated.
+    pub fn find_ids_by_subscription_status(
+        &self,
+        status: Option<UserIndexSubscriptionStatus>,
+    ) -> Vec<UserId> {
+        let mut ids = match status {
+            Some(status) => self
+                .status_index
+                .find_by_criteria(UserStatusIndexCriteria { status })
+                .into_iter()
+                .collect::<Vec<_>>(),
+            None => DB.with(|m| m.borrow().iter().map(|(key, _)| key.0).collect()),
+        };
+        ids.sort();
+
+        ids
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::user_model_utils::mock_user;
+    use crate::models::{user_model_utils::mock_user, UserSubscriptionStatus};
     use candid::Principal;
 
     #[test]
@@ -158,6 +184,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_find_ids_by_subscription_status() {
+        let approved_user = mock_user();
+        let mut pending_user = mock_user();
+        pending_user.subscription_status =
+            UserSubscriptionStatus::Pending("john@example.com".to_string());
+
+        USER_REPOSITORY.insert(approved_user.to_key(), approved_user.clone());
+        USER_REPOSITORY.insert(pending_user.to_key(), pending_user.clone());
+
+        assert_eq!(
+            USER_REPOSITORY
+                .find_ids_by_subscription_status(Some(UserIndexSubscriptionStatus::Pending)),
+            vec![pending_user.id]
+        );
+        assert_eq!(
+            USER_REPOSITORY.find_ids_by_subscription_status(None).len(),
+            2
+        );
+    }
+
     #[test]
     fn check_find_by_identity_gets_correct_user_from_many() {
         for _ in 0..10 {
@@ -1,29 +1,60 @@
 use crate::{
+    core::{ic_cdk::api::time, CallContext},
     errors::ArtifactError,
-    models::{Artifact, ArtifactId},
-    repositories::{ArtifactRepository, ARTIFACT_REPOSITORY},
+    models::{
+        Artifact, ArtifactChunk, ArtifactChunkHash, ArtifactGarbageCollection,
+        ArtifactGarbageCollectionCursor, ArtifactId, ArtifactStorageStats, ArtifactUpload,
+        ArtifactUploadId,
+    },
+    repositories::{
+        ArtifactChunkRepository, ArtifactRepository, ArtifactUploadRepository,
+        ARTIFACT_CHUNK_REPOSITORY, ARTIFACT_REPOSITORY, ARTIFACT_UPLOAD_REPOSITORY,
+    },
 };
 use lazy_static::lazy_static;
 use orbit_essentials::repository::Repository;
 use orbit_essentials::{api::ServiceResult, model::ModelValidator};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 use uuid::Uuid;
 
 lazy_static! {
-    pub static ref ARTIFACT_SERVICE: Arc<ArtifactService> =
-        Arc::new(ArtifactService::new(Arc::clone(&ARTIFACT_REPOSITORY)));
+    pub static ref ARTIFACT_SERVICE: Arc<ArtifactService> = Arc::new(ArtifactService::new(
+        Arc::clone(&ARTIFACT_REPOSITORY),
+        Arc::clone(&ARTIFACT_CHUNK_REPOSITORY),
+        Arc::clone(&ARTIFACT_UPLOAD_REPOSITORY),
+    ));
 }
 
 /// The artifact service provides methods to manage artifacts.
 #[derive(Default, Debug)]
 pub struct ArtifactService {
     artifact_repository: Arc<ArtifactRepository>,
+    artifact_chunk_repository: Arc<ArtifactChunkRepository>,
+    artifact_upload_repository: Arc<ArtifactUploadRepository>,
 }
 
 impl ArtifactService {
-    pub fn new(artifact_repository: Arc<ArtifactRepository>) -> Self {
+    /// The maximum number of artifacts that are checked for inline content in a single call.
+    const MIGRATION_BATCH_SIZE: usize = 100;
+
+    /// The maximum number of uploads, artifacts or chunks that are checked in a single garbage
+    /// collection call.
+    const GARBAGE_COLLECTION_BATCH_SIZE: usize = 100;
+
+    /// The maximum size of an artifact whose content is returned in a single reply, larger
+    /// artifacts need to be fetched chunk by chunk.
+    pub const MAX_REPLY_CONTENT_SIZE: u64 = ArtifactChunk::MAX_SIZE as u64;
+
+    pub fn new(
+        artifact_repository: Arc<ArtifactRepository>,
+        artifact_chunk_repository: Arc<ArtifactChunkRepository>,
+        artifact_upload_repository: Arc<ArtifactUploadRepository>,
+    ) -> Self {
         Self {
             artifact_repository,
+            artifact_chunk_repository,
+            artifact_upload_repository,
         }
     }
 
@@ -31,32 +62,42 @@ impl ArtifactService {
     ///
     /// If the artifact already exists, the existing artifact id is returned.
     pub fn create(&self, artifact: Vec<u8>) -> ServiceResult<ArtifactId> {
-        let artifact = Artifact::new(artifact);
+        let model = Artifact::new(artifact.clone());
 
-        match self.artifact_repository.find_by_hash(artifact.hash()) {
+        match self.artifact_repository.find_by_hash(model.hash()) {
             Some(artifact_id) => {
-                let mut artifact = self.find_by_id(&artifact_id)?;
-
-                artifact.increment_rc();
-
-                self.artifact_repository
-                    .insert(*artifact.id(), artifact.clone());
+                self.add_reference(&artifact_id)?;
 
                 Ok(artifact_id)
             }
             None => {
-                let artifact_id = artifact.id();
+                let artifact_id = model.id();
 
-                artifact.validate()?;
+                model.validate()?;
 
-                self.artifact_repository
-                    .insert(*artifact_id, artifact.clone());
+                for chunk in ArtifactChunk::split(&artifact) {
+                    self.store_chunk(chunk.to_vec());
+                }
+
+                self.artifact_repository.insert(*artifact_id, model.clone());
 
                 Ok(*artifact_id)
             }
         }
     }
 
+    /// Adds a reference to an existing artifact, such as the ones created from uploads.
+    pub fn add_reference(&self, artifact_id: &ArtifactId) -> ServiceResult<()> {
+        let mut artifact = self.find_by_id(artifact_id)?;
+
+        artifact.increment_rc();
+
+        self.artifact_repository
+            .insert(*artifact.id(), artifact.clone());
+
+        Ok(())
+    }
+
     /// Finds an artifact by its id.
     ///
     /// If the artifact does not exist, an error is returned.
@@ -71,6 +112,50 @@ impl ArtifactService {
         Ok(artifact)
     }
 
+    /// Returns the whole content of the artifact.
+    pub fn read_content(&self, artifact: &Artifact) -> ServiceResult<Vec<u8>> {
+        if let Some(content) = artifact.inline_content() {
+            return Ok(content.to_vec());
+        }
+
+        let mut content = Vec::with_capacity(artifact.size() as usize);
+        for hash in artifact.chunks() {
+            content.extend_from_slice(self.find_chunk(hash)?.data());
+        }
+
+        Ok(content)
+    }
+
+    /// Returns the content of an artifact that is small enough to be returned in a single reply.
+    pub fn read_reply_content(&self, artifact: &Artifact) -> ServiceResult<Vec<u8>> {
+        if artifact.size() > Self::MAX_REPLY_CONTENT_SIZE {
+            Err(ArtifactError::TooLargeForReply {
+                size: artifact.size(),
+                max_size: Self::MAX_REPLY_CONTENT_SIZE,
+            })?
+        }
+
+        self.read_content(artifact)
+    }
+
+    /// Returns the chunk of the artifact with the given index, so that large artifacts can be
+    /// downloaded in multiple calls.
+    pub fn read_chunk(&self, artifact: &Artifact, chunk_index: u64) -> ServiceResult<Vec<u8>> {
+        if let Some(content) = artifact.inline_content() {
+            return match ArtifactChunk::split(content).nth(chunk_index as usize) {
+                Some(chunk) => Ok(chunk.to_vec()),
+                None => Err(Self::chunk_out_of_range(chunk_index))?,
+            };
+        }
+
+        let hash = artifact
+            .chunks()
+            .get(chunk_index as usize)
+            .ok_or_else(|| Self::chunk_out_of_range(chunk_index))?;
+
+        Ok(self.find_chunk(hash)?.data().to_vec())
+    }
+
     /// Removes an artifact by its id.
     ///
     /// The artifact is only removed if all references to it are removed.
@@ -86,16 +171,349 @@ impl ArtifactService {
             return Ok(());
         }
 
-        self.artifact_repository.remove(artifact_id);
+        self.remove_artifact(&artifact);
 
         Ok(())
     }
+
+    /// Starts the upload of an artifact in chunks.
+    ///
+    /// If the caller already has an upload in progress for the same artifact, that upload is returned
+    /// so that it can be resumed.
+    pub fn start_upload(
+        &self,
+        hash: Vec<u8>,
+        size: u64,
+        ctx: &CallContext,
+    ) -> ServiceResult<ArtifactUpload> {
+        if let Some(upload) = self
+            .artifact_upload_repository
+            .list()
+            .into_iter()
+            .find(|upload| {
+                upload.uploaded_by == ctx.caller() && upload.hash == hash && upload.size == size
+            })
+        {
+            return Ok(upload);
+        }
+
+        if size > ArtifactUpload::MAX_SIZE {
+            Err(ArtifactError::ValidationError {
+                info: format!(
+                    "The artifact must be at most {} bytes, got {size}",
+                    ArtifactUpload::MAX_SIZE
+                ),
+            })?
+        }
+
+        let upload = ArtifactUpload::new(hash, size, ctx.caller());
+
+        upload.validate()?;
+
+        self.artifact_upload_repository
+            .insert(upload.id, upload.clone());
+
+        Ok(upload)
+    }
+
+    /// Returns the upload with the given id if it was started by the caller.
+    pub fn get_upload(
+        &self,
+        upload_id: &ArtifactUploadId,
+        ctx: &CallContext,
+    ) -> ServiceResult<ArtifactUpload> {
+        let upload = self
+            .artifact_upload_repository
+            .get(upload_id)
+            .filter(|upload| upload.uploaded_by == ctx.caller())
+            .ok_or(ArtifactError::UploadNotFound {
+                id: Uuid::from_bytes(*upload_id).to_string(),
+            })?;
+
+        Ok(upload)
+    }
+
+    /// Stores a chunk of the upload, uploading the same chunk again replaces the previous one.
+    pub fn upload_chunk(
+        &self,
+        upload_id: &ArtifactUploadId,
+        chunk_index: u64,
+        data: Vec<u8>,
+        ctx: &CallContext,
+    ) -> ServiceResult<ArtifactUpload> {
+        let mut upload = self.get_upload(upload_id, ctx)?;
+        let expected_size = upload
+            .expected_chunk_size(chunk_index)
+            .ok_or_else(|| Self::chunk_out_of_range(chunk_index))?;
+
+        if data.len() != expected_size {
+            Err(ArtifactError::ValidationError {
+                info: format!(
+                    "The chunk {chunk_index} must be {expected_size} bytes, got {}",
+                    data.len()
+                ),
+            })?
+        }
+
+        let hash = ArtifactChunk::hash_of(&data);
+        let slot = &mut upload.chunks[chunk_index as usize];
+        if *slot != Some(hash) {
+            let previous = slot.replace(hash);
+            self.store_chunk(data);
+
+            if let Some(previous) = previous {
+                self.release_chunk(&previous);
+            }
+        }
+
+        upload.last_modification_timestamp = time();
+
+        self.artifact_upload_repository
+            .insert(upload.id, upload.clone());
+
+        Ok(upload)
+    }
+
+    /// Finishes the upload and returns the id of the artifact.
+    ///
+    /// The artifact is created without references, so it must be referenced before the retention
+    /// period of unreferenced artifacts is over. If the content does not match the expected hash the
+    /// upload is discarded.
+    pub fn finish_upload(
+        &self,
+        upload_id: &ArtifactUploadId,
+        ctx: &CallContext,
+    ) -> ServiceResult<ArtifactId> {
+        let upload = self.get_upload(upload_id, ctx)?;
+        let missing_chunks = upload.missing_chunks().len() as u64;
+        if missing_chunks > 0 {
+            Err(ArtifactError::UploadIncomplete { missing_chunks })?
+        }
+
+        let chunks = upload.chunks.iter().flatten().copied().collect::<Vec<_>>();
+        let mut hasher = Sha256::new();
+        for hash in &chunks {
+            hasher.update(self.find_chunk(hash)?.data());
+        }
+        let hash = hasher.finalize().to_vec();
+
+        self.artifact_upload_repository.remove(upload_id);
+
+        if hash != upload.hash {
+            self.release_chunks(&chunks);
+
+            Err(ArtifactError::HashMismatch {
+                expected: hex::encode(&upload.hash),
+                actual: hex::encode(&hash),
+            })?
+        }
+
+        if let Some(artifact_id) = self.artifact_repository.find_by_hash(&hash) {
+            self.release_chunks(&chunks);
+
+            return Ok(artifact_id);
+        }
+
+        let artifact = Artifact::from_chunks(hash, upload.size, chunks);
+
+        artifact.validate()?;
+
+        self.artifact_repository
+            .insert(*artifact.id(), artifact.clone());
+
+        Ok(*artifact.id())
+    }
+
+    /// Removes the expired uploads, the unreferenced artifacts whose retention period is over and the
+    /// chunks that are no longer used, continuing from the given cursor.
+    ///
+    /// A single call checks a bounded number of entries and adds what it removed to the collection.
+    /// Returns the cursor to continue from in the next call, or `None` once everything is checked.
+    pub fn collect_garbage(
+        &self,
+        cursor: ArtifactGarbageCollectionCursor,
+        collection: &mut ArtifactGarbageCollection,
+    ) -> Option<ArtifactGarbageCollectionCursor> {
+        let now = time();
+        let mut cursor = cursor;
+
+        for _ in 0..Self::GARBAGE_COLLECTION_BATCH_SIZE {
+            cursor = match cursor {
+                ArtifactGarbageCollectionCursor::Uploads(after) => {
+                    match self.artifact_upload_repository.find_next(after.as_ref()) {
+                        Some(upload) => {
+                            if upload.is_expired(now) {
+                                let chunks =
+                                    upload.chunks.iter().flatten().copied().collect::<Vec<_>>();
+                                collection.freed_bytes += self.release_chunks(&chunks);
+                                self.artifact_upload_repository.remove(&upload.id);
+                                collection.removed_uploads += 1;
+                            }
+
+                            ArtifactGarbageCollectionCursor::Uploads(Some(upload.id))
+                        }
+                        None => ArtifactGarbageCollectionCursor::Artifacts(None),
+                    }
+                }
+                ArtifactGarbageCollectionCursor::Artifacts(after) => {
+                    match self.artifact_repository.find_next(after.as_ref()) {
+                        Some(artifact) => {
+                            if artifact.is_collectable(now) {
+                                collection.freed_bytes += self.remove_artifact(&artifact);
+                                collection.removed_artifacts += 1;
+                            }
+
+                            ArtifactGarbageCollectionCursor::Artifacts(Some(*artifact.id()))
+                        }
+                        None => ArtifactGarbageCollectionCursor::Chunks(None),
+                    }
+                }
+                ArtifactGarbageCollectionCursor::Chunks(after) => {
+                    // Chunks are removed when their last reference is released, this only catches
+                    // the ones that were left behind.
+                    let chunk = self.artifact_chunk_repository.find_next(after.as_ref())?;
+                    if chunk.rc() == 0 {
+                        self.artifact_chunk_repository.remove(chunk.hash());
+                        collection.removed_chunks += 1;
+                        collection.freed_bytes += chunk.data().len() as u64;
+                    }
+
+                    ArtifactGarbageCollectionCursor::Chunks(Some(*chunk.hash()))
+                }
+            };
+        }
+
+        Some(cursor)
+    }
+
+    /// Returns the statistics of the artifact storage.
+    pub fn storage_stats(&self) -> ArtifactStorageStats {
+        let mut stats = ArtifactStorageStats {
+            uploads: self.artifact_upload_repository.len() as u64,
+            ..Default::default()
+        };
+
+        for artifact in self.artifact_repository.list() {
+            stats.artifacts += 1;
+
+            match artifact.inline_content() {
+                Some(content) => {
+                    stats.artifacts_size += content.len() as u64;
+                    stats.stored_size += content.len() as u64;
+                }
+                None => stats.artifacts_size += artifact.size(),
+            }
+        }
+
+        let (chunks, stored_size) = self.artifact_chunk_repository.stored_size();
+        stats.chunks = chunks;
+        stats.stored_size += stored_size;
+
+        stats
+    }
+
+    /// Moves the content of the artifacts that are still stored inline to the chunk repository,
+    /// continuing after the given artifact.
+    ///
+    /// A single call checks a bounded number of artifacts and migrates at most one of them, since
+    /// the inline content can be large. Returns the id of the last artifact that was checked, to
+    /// continue from it in the next call, or `None` once all the artifacts are checked.
+    pub fn migrate_inline_artifacts(&self, after: Option<ArtifactId>) -> Option<ArtifactId> {
+        let mut last_checked = after;
+
+        for _ in 0..Self::MIGRATION_BATCH_SIZE {
+            let mut artifact = self.artifact_repository.find_next(last_checked.as_ref())?;
+            last_checked = Some(*artifact.id());
+
+            if let Some(content) = artifact.move_inline_content_to_chunks() {
+                for chunk in ArtifactChunk::split(&content) {
+                    self.store_chunk(chunk.to_vec());
+                }
+
+                self.artifact_repository
+                    .insert(*artifact.id(), artifact.clone());
+
+                break;
+            }
+        }
+
+        last_checked
+    }
+
+    fn find_chunk(&self, hash: &ArtifactChunkHash) -> ServiceResult<ArtifactChunk> {
+        let chunk =
+            self.artifact_chunk_repository
+                .get(hash)
+                .ok_or(ArtifactError::ChunkNotFound {
+                    hash: hex::encode(hash),
+                })?;
+
+        Ok(chunk)
+    }
+
+    /// Stores the chunk, or adds a reference to it if a chunk with the same hash is already stored.
+    fn store_chunk(&self, data: Vec<u8>) {
+        let chunk = match self
+            .artifact_chunk_repository
+            .get(&ArtifactChunk::hash_of(&data))
+        {
+            Some(mut chunk) => {
+                chunk.increment_rc();
+                chunk
+            }
+            None => ArtifactChunk::new(data),
+        };
+
+        self.artifact_chunk_repository
+            .insert(*chunk.hash(), chunk.clone());
+    }
+
+    /// Releases a reference to the chunk and removes it if it was the last one.
+    ///
+    /// Returns the number of bytes that were freed.
+    fn release_chunk(&self, hash: &ArtifactChunkHash) -> u64 {
+        let Some(mut chunk) = self.artifact_chunk_repository.get(hash) else {
+            return 0;
+        };
+
+        chunk.decrement_rc();
+
+        if chunk.rc() > 0 {
+            self.artifact_chunk_repository.insert(*hash, chunk);
+
+            return 0;
+        }
+
+        self.artifact_chunk_repository.remove(hash);
+
+        chunk.data().len() as u64
+    }
+
+    fn release_chunks(&self, hashes: &[ArtifactChunkHash]) -> u64 {
+        hashes.iter().map(|hash| self.release_chunk(hash)).sum()
+    }
+
+    fn remove_artifact(&self, artifact: &Artifact) -> u64 {
+        let freed_bytes = self.release_chunks(artifact.chunks());
+        self.artifact_repository.remove(artifact.id());
+
+        freed_bytes
+    }
+
+    fn chunk_out_of_range(chunk_index: u64) -> ArtifactError {
+        ArtifactError::ValidationError {
+            info: format!("The chunk index {chunk_index} is out of range"),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use orbit_essentials::api::ApiError;
+    use crate::core::ic_cdk::api::set_mock_ic_time;
+    use candid::Principal;
+    use orbit_essentials::{api::ApiError, utils::sha256_hash};
+    use std::time::{Duration, SystemTime};
 
     #[test]
     fn test_create() {
@@ -104,7 +522,10 @@ mod tests {
         let found_artifact = ARTIFACT_SERVICE.find_by_id(&artifact_id).unwrap();
 
         assert_eq!(found_artifact.id(), &artifact_id);
-        assert_eq!(found_artifact.artifact(), &artifact);
+        assert_eq!(
+            ARTIFACT_SERVICE.read_content(&found_artifact).unwrap(),
+            artifact
+        );
     }
 
     #[test]
@@ -162,4 +583,218 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_artifacts_share_chunks() {
+        let shared = vec![7; ArtifactChunk::MAX_SIZE];
+        let first = [shared.clone(), vec![1, 2, 3]].concat();
+        let second = [shared.clone(), vec![4, 5, 6]].concat();
+
+        let first_id = ARTIFACT_SERVICE.create(first.clone()).unwrap();
+        let second_id = ARTIFACT_SERVICE.create(second.clone()).unwrap();
+
+        let stats = ARTIFACT_SERVICE.storage_stats();
+        assert_eq!(stats.artifacts, 2);
+        assert_eq!(stats.chunks, 3);
+        assert_eq!(stats.artifacts_size, (first.len() + second.len()) as u64);
+        assert_eq!(stats.stored_size, (shared.len() + 6) as u64);
+
+        let first_artifact = ARTIFACT_SERVICE.find_by_id(&first_id).unwrap();
+        assert_eq!(
+            ARTIFACT_SERVICE.read_content(&first_artifact).unwrap(),
+            first
+        );
+        assert_eq!(
+            ARTIFACT_SERVICE.read_chunk(&first_artifact, 1).unwrap(),
+            vec![1, 2, 3]
+        );
+        assert!(ARTIFACT_SERVICE.read_chunk(&first_artifact, 2).is_err());
+
+        ARTIFACT_SERVICE.remove_by_id(&first_id).unwrap();
+
+        let second_artifact = ARTIFACT_SERVICE.find_by_id(&second_id).unwrap();
+        assert_eq!(
+            ARTIFACT_SERVICE.read_content(&second_artifact).unwrap(),
+            second
+        );
+        assert_eq!(ARTIFACT_SERVICE.storage_stats().chunks, 2);
+    }
+
+    #[test]
+    fn test_resumable_upload() {
+        let ctx = CallContext::new(Principal::from_slice(&[1; 29]));
+        let content = [vec![1; ArtifactChunk::MAX_SIZE], vec![2; 10]].concat();
+        let hash = sha256_hash(&content);
+
+        let upload = ARTIFACT_SERVICE
+            .start_upload(hash.clone(), content.len() as u64, &ctx)
+            .unwrap();
+        ARTIFACT_SERVICE
+            .upload_chunk(&upload.id, 1, vec![2; 10], &ctx)
+            .unwrap();
+
+        // other principals can neither see nor continue the upload
+        let other = CallContext::new(Principal::from_slice(&[2; 29]));
+        assert!(ARTIFACT_SERVICE.get_upload(&upload.id, &other).is_err());
+        assert!(ARTIFACT_SERVICE
+            .upload_chunk(&upload.id, 0, vec![1; ArtifactChunk::MAX_SIZE], &other)
+            .is_err());
+
+        let resumed = ARTIFACT_SERVICE
+            .start_upload(hash, content.len() as u64, &ctx)
+            .unwrap();
+        assert_eq!(resumed.id, upload.id);
+        assert_eq!(resumed.missing_chunks(), vec![0]);
+        assert!(ARTIFACT_SERVICE.finish_upload(&upload.id, &ctx).is_err());
+
+        assert!(ARTIFACT_SERVICE
+            .upload_chunk(&upload.id, 0, vec![1; 10], &ctx)
+            .is_err());
+        ARTIFACT_SERVICE
+            .upload_chunk(&upload.id, 0, vec![1; ArtifactChunk::MAX_SIZE], &ctx)
+            .unwrap();
+
+        let artifact_id = ARTIFACT_SERVICE.finish_upload(&upload.id, &ctx).unwrap();
+        let artifact = ARTIFACT_SERVICE.find_by_id(&artifact_id).unwrap();
+
+        assert_eq!(artifact.rc(), 0);
+        assert_eq!(ARTIFACT_SERVICE.read_content(&artifact).unwrap(), content);
+        assert!(ARTIFACT_SERVICE.get_upload(&upload.id, &ctx).is_err());
+
+        // uploading existing content resolves to the same artifact
+        assert_eq!(ARTIFACT_SERVICE.create(content).unwrap(), artifact_id);
+    }
+
+    #[test]
+    fn test_upload_with_wrong_hash_is_discarded() {
+        let ctx = CallContext::new(Principal::from_slice(&[1; 29]));
+        let upload = ARTIFACT_SERVICE.start_upload(vec![0; 32], 3, &ctx).unwrap();
+
+        ARTIFACT_SERVICE
+            .upload_chunk(&upload.id, 0, vec![1, 2, 3], &ctx)
+            .unwrap();
+
+        let result = ARTIFACT_SERVICE.finish_upload(&upload.id, &ctx);
+
+        assert!(result.is_err());
+        assert!(ARTIFACT_SERVICE.get_upload(&upload.id, &ctx).is_err());
+        assert_eq!(ARTIFACT_SERVICE.storage_stats().chunks, 0);
+    }
+
+    fn collect_all_garbage() -> ArtifactGarbageCollection {
+        let mut collection = ArtifactGarbageCollection::default();
+        let mut cursor = ArtifactGarbageCollectionCursor::default();
+        while let Some(next) = ARTIFACT_SERVICE.collect_garbage(cursor, &mut collection) {
+            cursor = next;
+        }
+
+        collection
+    }
+
+    #[test]
+    fn test_collect_garbage() {
+        let ctx = CallContext::new(Principal::from_slice(&[1; 29]));
+        let referenced_id = ARTIFACT_SERVICE.create(vec![1, 2, 3]).unwrap();
+
+        let content = vec![4, 5, 6];
+        let upload = ARTIFACT_SERVICE
+            .start_upload(sha256_hash(&content), 3, &ctx)
+            .unwrap();
+        ARTIFACT_SERVICE
+            .upload_chunk(&upload.id, 0, content, &ctx)
+            .unwrap();
+        let unreferenced_id = ARTIFACT_SERVICE.finish_upload(&upload.id, &ctx).unwrap();
+
+        let expired = ARTIFACT_SERVICE.start_upload(vec![0; 32], 2, &ctx).unwrap();
+        ARTIFACT_SERVICE
+            .upload_chunk(&expired.id, 0, vec![7, 8], &ctx)
+            .unwrap();
+
+        assert_eq!(collect_all_garbage(), ArtifactGarbageCollection::default());
+
+        set_mock_ic_time(SystemTime::UNIX_EPOCH + Duration::from_secs(2 * 24 * 60 * 60));
+
+        assert_eq!(
+            collect_all_garbage(),
+            ArtifactGarbageCollection {
+                removed_uploads: 1,
+                removed_artifacts: 1,
+                removed_chunks: 0,
+                freed_bytes: 5,
+            }
+        );
+        assert!(ARTIFACT_SERVICE.find_by_id(&referenced_id).is_ok());
+        assert!(ARTIFACT_SERVICE.find_by_id(&unreferenced_id).is_err());
+        assert_eq!(ARTIFACT_SERVICE.storage_stats().stored_size, 3);
+    }
+
+    #[test]
+    fn test_collect_garbage_in_batches() {
+        let artifact_ids = (0..ArtifactService::GARBAGE_COLLECTION_BATCH_SIZE + 1)
+            .map(|i| ARTIFACT_SERVICE.create(i.to_le_bytes().to_vec()).unwrap())
+            .collect::<Vec<_>>();
+        let mut collection = ArtifactGarbageCollection::default();
+
+        // the artifacts do not fit in a single batch
+        let mut cursor = ARTIFACT_SERVICE
+            .collect_garbage(ArtifactGarbageCollectionCursor::default(), &mut collection)
+            .unwrap();
+        assert!(matches!(
+            cursor,
+            ArtifactGarbageCollectionCursor::Artifacts(Some(_))
+        ));
+
+        while let Some(next) = ARTIFACT_SERVICE.collect_garbage(cursor, &mut collection) {
+            cursor = next;
+        }
+
+        assert_eq!(collection, ArtifactGarbageCollection::default());
+        for artifact_id in &artifact_ids {
+            assert!(ARTIFACT_SERVICE.find_by_id(artifact_id).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_migrate_inline_artifacts_one_at_a_time() {
+        let inline_artifacts = [vec![1, 2, 3], vec![4, 5, 6]].map(|content| {
+            let artifact = Artifact::new_inline(content);
+            ARTIFACT_REPOSITORY.insert(*artifact.id(), artifact.clone());
+            artifact
+        });
+        let chunked_id = ARTIFACT_SERVICE.create(vec![7, 8, 9]).unwrap();
+
+        let mut calls = 0;
+        let mut last_checked = None;
+        loop {
+            calls += 1;
+            last_checked = match ARTIFACT_SERVICE.migrate_inline_artifacts(last_checked) {
+                Some(id) => Some(id),
+                None => break,
+            };
+        }
+
+        // each call stops after migrating an artifact, and the last one finds nothing left
+        assert_eq!(calls, 3);
+        for artifact in &inline_artifacts {
+            let migrated = ARTIFACT_SERVICE.find_by_id(artifact.id()).unwrap();
+
+            assert!(migrated.inline_content().is_none());
+            assert_eq!(migrated.size(), 3);
+            assert_eq!(
+                ARTIFACT_SERVICE.read_content(&migrated).unwrap(),
+                ARTIFACT_SERVICE.read_content(artifact).unwrap()
+            );
+        }
+        assert!(ARTIFACT_SERVICE.find_by_id(&chunked_id).is_ok());
+        assert_eq!(
+            ARTIFACT_SERVICE.storage_stats(),
+            ArtifactStorageStats {
+                artifacts: 3,
+                artifacts_size: 9,
+                chunks: 3,
+                stored_size: 9,
+                uploads: 0,
+            }
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use super::{UserService, UserStationService};
+use super::{StationConfigTemplateService, UserService, UserStationService};
 use crate::{
     core::{
         canister_config, CallContext, CANISTER_CREATION_CYCLES, INITIAL_STATION_CYCLES,
@@ -6,7 +6,7 @@ use crate::{
     },
     errors::{DeployError, UserError},
     models::{CanDeployStation, UserStation},
-    services::{USER_SERVICE, USER_STATION_SERVICE},
+    services::{STATION_CONFIG_TEMPLATE_SERVICE, USER_SERVICE, USER_STATION_SERVICE},
 };
 use candid::{Encode, Principal};
 use control_panel_api::DeployStationInput;
@@ -22,7 +22,8 @@ use std::sync::Arc;
 lazy_static! {
     pub static ref DEPLOY_SERVICE: Arc<DeployService> = Arc::new(DeployService::new(
         Arc::clone(&USER_SERVICE),
-        Arc::clone(&USER_STATION_SERVICE)
+        Arc::clone(&USER_STATION_SERVICE),
+        Arc::clone(&STATION_CONFIG_TEMPLATE_SERVICE)
     ));
 }
 
@@ -30,16 +31,19 @@ lazy_static! {
 pub struct DeployService {
     user_service: Arc<UserService>,
     user_station_service: Arc<UserStationService>,
+    station_config_template_service: Arc<StationConfigTemplateService>,
 }
 
 impl DeployService {
     pub fn new(
         user_service: Arc<UserService>,
         user_station_service: Arc<UserStationService>,
+        station_config_template_service: Arc<StationConfigTemplateService>,
     ) -> Self {
         Self {
             user_service,
             user_station_service,
+            station_config_template_service,
         }
     }
 
@@ -70,6 +74,17 @@ impl DeployService {
             }
         }
 
+        // Resolves and validates the template before creating the canister, so that templates with invalid
+        // references cost no cycles, the contents of the entries are validated by the station on init
+        let template = input
+            .template
+            .as_ref()
+            .map(|template| self.station_config_template_service.resolve(template))
+            .transpose()?;
+        if let Some(template) = &template {
+            template.validate_for_deployment(input.admins.len())?;
+        }
+
         // Creates the station canister
         let station_canister = create_canister(input.subnet_selection, CANISTER_CREATION_CYCLES)
             .await
@@ -143,10 +158,13 @@ impl DeployService {
                     }
                 ),
                 fallback_controller: Some(NNS_ROOT_CANISTER_ID),
-                initial_config: station_api::InitialConfig::WithAllDefaults {
-                    users: intial_users,
-                    admin_quorum: 1,
-                    operator_quorum: 1,
+                initial_config: match template {
+                    Some(template) => template.into_initial_config(intial_users),
+                    None => station_api::InitialConfig::WithAllDefaults {
+                        users: intial_users,
+                        admin_quorum: 1,
+                        operator_quorum: 1,
+                    },
                 },
             }
         )))
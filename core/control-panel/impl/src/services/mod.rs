@@ -6,6 +6,9 @@ pub use artifact::*;
 mod user;
 pub use user::*;
 
+mod user_admin;
+pub use user_admin::*;
+
 mod user_station;
 pub use user_station::*;
 
@@ -17,3 +20,12 @@ pub use deploy::*;
 
 mod registry;
 pub use registry::*;
+
+mod registry_namespace;
+pub use registry_namespace::*;
+
+mod station_upgrade_announcement;
+pub use station_upgrade_announcement::*;
+
+mod station_config_template;
+pub use station_config_template::*;
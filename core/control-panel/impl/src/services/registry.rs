@@ -1,16 +1,20 @@
 use crate::{
+    core::{ic_cdk::next_time, CallContext},
     errors::RegistryError,
     mappers::{HelperMapper, RegistryMapper},
     models::{
-        RegistryEntry, RegistryEntryId, RegistryValue, RegistryValueKind, WasmModuleRegistryValue,
-        LATEST_TAG,
+        AssetBundleRegistryValue, CandidInterfaceRegistryValue, RegistryEntry,
+        RegistryEntryAttestation, RegistryEntryId, RegistryEntryVerification, RegistryValue,
+        RegistryValueKind, StationConfigTemplateRegistryValue, UpgradePathStep, LATEST_TAG,
     },
     repositories::{RegistryRepository, RegistryWhere, REGISTRY_REPOSITORY},
-    services::{ArtifactService, ARTIFACT_SERVICE},
+    services::{
+        ArtifactService, RegistryNamespaceService, ARTIFACT_SERVICE, REGISTRY_NAMESPACE_SERVICE,
+    },
 };
 use control_panel_api::{
-    RegistryEntryInput, RegistryEntrySortBy, RegistryEntryUpdateInput, SearchRegistryFilterKindDTO,
-    SearchRegistryInput, SortDirection,
+    AttestRegistryEntryInput, RegistryEntryInput, RegistryEntrySortBy, RegistryEntryUpdateInput,
+    ResolveUpgradePathInput, SearchRegistryFilterKindDTO, SearchRegistryInput, SortDirection,
 };
 use lazy_static::lazy_static;
 use orbit_essentials::{
@@ -25,7 +29,8 @@ use uuid::Uuid;
 lazy_static! {
     pub static ref REGISTRY_SERVICE: Arc<RegistryService> = Arc::new(RegistryService::new(
         Arc::clone(&REGISTRY_REPOSITORY),
-        Arc::clone(&ARTIFACT_SERVICE)
+        Arc::clone(&ARTIFACT_SERVICE),
+        Arc::clone(&REGISTRY_NAMESPACE_SERVICE)
     ));
 }
 
@@ -34,6 +39,7 @@ lazy_static! {
 pub struct RegistryService {
     registry_repository: Arc<RegistryRepository>,
     artifact_service: Arc<ArtifactService>,
+    registry_namespace_service: Arc<RegistryNamespaceService>,
 }
 
 impl RegistryService {
@@ -43,10 +49,12 @@ impl RegistryService {
     pub fn new(
         registry_repository: Arc<RegistryRepository>,
         artifact_service: Arc<ArtifactService>,
+        registry_namespace_service: Arc<RegistryNamespaceService>,
     ) -> Self {
         Self {
             registry_repository,
             artifact_service,
+            registry_namespace_service,
         }
     }
 
@@ -116,36 +124,22 @@ impl RegistryService {
         })
     }
 
-    /// Creates a new registry entry and returns it.
-    pub fn create(&self, input: RegistryEntryInput) -> ServiceResult<RegistryEntry> {
+    /// Creates a new registry entry and returns it, the caller is recorded as its publisher.
+    pub fn create(
+        &self,
+        input: RegistryEntryInput,
+        ctx: &CallContext,
+    ) -> ServiceResult<RegistryEntry> {
         let mut entry = RegistryEntry::new();
 
         RegistryMapper::fill_from_create_input(&mut entry, &input);
+        entry.publisher = Some(ctx.caller());
 
-        match &input.value {
-            control_panel_api::RegistryEntryValueInput::WasmModule(module) => {
-                let artifact_id = self.artifact_service.create(module.wasm_module.clone())?;
-
-                entry.value = RegistryValue::WasmModule(WasmModuleRegistryValue {
-                    wasm_artifact_id: artifact_id,
-                    version: module.version.clone(),
-                    dependencies: module
-                        .dependencies
-                        .iter()
-                        .map(|dep| dep.clone().into())
-                        .collect(),
-                    module_extra_chunks: module.module_extra_chunks.clone(),
-                });
-            }
-        };
+        entry.value = self.create_value(&input.value)?;
 
         if let Err(err) = entry.validate() {
-            match &entry.value {
-                RegistryValue::WasmModule(wasm_module) => {
-                    self.artifact_service
-                        .remove_by_id(&wasm_module.wasm_artifact_id)?;
-                }
-            };
+            self.artifact_service
+                .remove_by_id(entry.value.artifact_id())?;
 
             Err(err)?
         }
@@ -170,30 +164,21 @@ impl RegistryService {
         let mut previous_artifact_id = None;
         let mut new_artifact_id = None;
 
-        match (&input.value, &entry.value) {
-            (
-                Some(control_panel_api::RegistryEntryValueInput::WasmModule(module)),
-                RegistryValue::WasmModule(current_module),
-            ) => {
-                previous_artifact_id = Some(current_module.wasm_artifact_id);
+        if let Some(value) = &input.value {
+            if RegistryValueKind::from(value.clone()) != entry.to_kind() {
+                Err(RegistryError::UpdateKindMismatch {
+                    kind: entry.to_kind().to_string(),
+                })?
+            }
 
-                let artifact_id = self.artifact_service.create(module.wasm_module.clone())?;
+            previous_artifact_id = Some(*entry.value.artifact_id());
 
-                entry.value = RegistryValue::WasmModule(WasmModuleRegistryValue {
-                    wasm_artifact_id: artifact_id,
-                    version: module.version.clone(),
-                    dependencies: module
-                        .dependencies
-                        .iter()
-                        .map(|dep| dep.clone().into())
-                        .collect(),
-                    module_extra_chunks: module.module_extra_chunks.clone(),
-                });
+            entry.value = self.create_value(value)?;
+            // The attestations were made for the previous content.
+            entry.attestations.clear();
 
-                new_artifact_id = Some(artifact_id);
-            }
-            (None, _) => (),
-        };
+            new_artifact_id = Some(*entry.value.artifact_id());
+        }
 
         if let Err(err) = entry.validate() {
             // Makes sure to remove the new artifact if the entry is invalid to avoid orphaned artifacts.
@@ -216,6 +201,142 @@ impl RegistryService {
         Ok(entry)
     }
 
+    /// Attests that the content of the entry was reproducibly built from the given source.
+    ///
+    /// A previous attestation of the caller is replaced.
+    pub fn attest(
+        &self,
+        registry_id: &RegistryEntryId,
+        input: AttestRegistryEntryInput,
+        ctx: &CallContext,
+    ) -> ServiceResult<RegistryEntryAttestation> {
+        let mut entry = self.get(registry_id)?;
+        let content_hash = self.content_hash(&entry)?;
+        let build_hash =
+            hex::decode(&input.build_hash).map_err(|_| RegistryError::ValidationError {
+                info: "The build hash must be hex encoded".to_string(),
+            })?;
+
+        if build_hash != content_hash {
+            Err(RegistryError::AttestationHashMismatch {
+                expected: hex::encode(&content_hash),
+                actual: input.build_hash,
+            })?
+        }
+
+        let attestation = RegistryEntryAttestation {
+            attester: ctx.caller(),
+            build_hash,
+            source_repository: input.source_repository,
+            source_commit: input.source_commit,
+            attested_at: next_time(),
+        };
+
+        entry
+            .attestations
+            .retain(|existing| existing.attester != attestation.attester);
+        entry.attestations.push(attestation.clone());
+        entry.validate()?;
+
+        self.registry_repository.insert(entry.id, entry);
+
+        Ok(attestation)
+    }
+
+    /// Verifies the provenance of the entry.
+    ///
+    /// The entry is verified if its publisher can still publish to the namespace, its content matches
+    /// the expected hash if provided, and it has an attestation of its content from a principal that
+    /// can publish to the namespace.
+    pub fn verify(
+        &self,
+        registry_id: &RegistryEntryId,
+        expected_hash: Option<Vec<u8>>,
+    ) -> ServiceResult<RegistryEntryVerification> {
+        let entry = self.get(registry_id)?;
+        let content_hash = self.content_hash(&entry)?;
+        let can_publish = |principal| {
+            self.registry_namespace_service
+                .can_publish(&CallContext::new(principal), &entry.namespace)
+        };
+
+        let publisher_authorized = entry.publisher.is_some_and(can_publish);
+        let is_attested = entry.attestations.iter().any(|attestation| {
+            attestation.build_hash == content_hash && can_publish(attestation.attester)
+        });
+        let hash_matches = expected_hash.is_none_or(|expected| expected == content_hash);
+
+        Ok(RegistryEntryVerification {
+            publisher: entry.publisher,
+            publisher_authorized,
+            verified: publisher_authorized && is_attested && hash_matches,
+            content_hash,
+            attestations: entry.attestations,
+        })
+    }
+
+    /// Returns the sha256 hash of the content of the entry.
+    ///
+    /// For wasm modules with extra chunks the artifact only holds the first chunk, so the hash of the
+    /// whole module is used instead.
+    fn content_hash(&self, entry: &RegistryEntry) -> ServiceResult<Vec<u8>> {
+        if let RegistryValue::WasmModule(module) = &entry.value {
+            if let Some(module_extra_chunks) = &module.module_extra_chunks {
+                return Ok(module_extra_chunks.wasm_module_hash.clone());
+            }
+        }
+
+        let artifact = self
+            .artifact_service
+            .find_by_id(entry.value.artifact_id())?;
+
+        Ok(artifact.hash().to_vec())
+    }
+
+    /// Validates the content of the value input and stores it in the artifact repository.
+    fn create_value(
+        &self,
+        input: &control_panel_api::RegistryEntryValueInput,
+    ) -> ServiceResult<RegistryValue> {
+        let content = match input {
+            control_panel_api::RegistryEntryValueInput::WasmModule(module) => {
+                if let Some(artifact_id) = &module.wasm_module_artifact_id {
+                    if !module.wasm_module.is_empty() {
+                        Err(RegistryError::ValidationError {
+                            info: "The wasm module must be empty when an uploaded artifact is used"
+                                .to_string(),
+                        })?
+                    }
+
+                    let artifact_id = *HelperMapper::to_uuid(artifact_id.clone())?.as_bytes();
+                    self.artifact_service.add_reference(&artifact_id)?;
+
+                    return Ok(RegistryMapper::value_from_input(input, artifact_id));
+                }
+
+                module.wasm_module.clone()
+            }
+            control_panel_api::RegistryEntryValueInput::CandidInterface(candid_interface) => {
+                let candid = candid_interface.candid.as_bytes().to_vec();
+                CandidInterfaceRegistryValue::validate_content(&candid)?;
+                candid
+            }
+            control_panel_api::RegistryEntryValueInput::AssetBundle(asset_bundle) => {
+                AssetBundleRegistryValue::validate_content(&asset_bundle.bundle)?;
+                asset_bundle.bundle.clone()
+            }
+            control_panel_api::RegistryEntryValueInput::StationConfigTemplate(template) => {
+                let template = template.template.as_bytes().to_vec();
+                StationConfigTemplateRegistryValue::validate_content(&template)?;
+                template
+            }
+        };
+
+        let artifact_id = self.artifact_service.create(content)?;
+
+        Ok(RegistryMapper::value_from_input(input, artifact_id))
+    }
+
     /// Applies the latest tag to the entry and removes it from the previous latest entry if it exists.
     fn apply_single_latest_tag_across_entries(&self, entry: &RegistryEntry) {
         if entry.tags.contains(&LATEST_TAG.to_string()) {
@@ -242,18 +363,50 @@ impl RegistryService {
     pub fn delete(&self, registry_id: &RegistryEntryId) -> ServiceResult<RegistryEntry> {
         let registry = self.get(registry_id)?;
 
-        match &registry.value {
-            RegistryValue::WasmModule(wasm_module) => {
-                self.artifact_service
-                    .remove_by_id(&wasm_module.wasm_artifact_id)?;
-            }
-        };
+        self.artifact_service
+            .remove_by_id(registry.value.artifact_id())?;
 
         self.registry_repository.remove(registry_id);
 
         Ok(registry)
     }
 
+    /// Finds the station config template by name, using the newest version if none is given.
+    pub fn find_station_config_template(
+        &self,
+        name: &str,
+        version: Option<&str>,
+    ) -> ServiceResult<RegistryEntry> {
+        let fullname = Self::to_fullname(name);
+        let entries = self
+            .registry_repository
+            .find_ids_where(
+                RegistryWhere::clause()
+                    .and_fullname(&fullname)
+                    .and_kind(RegistryValueKind::StationConfigTemplate),
+                Some(RegistryEntrySortBy::Version(SortDirection::Asc)),
+            )
+            .iter()
+            .filter_map(|id| self.get(id).ok())
+            .collect::<Vec<_>>();
+
+        let entry = match version {
+            Some(version) => entries
+                .into_iter()
+                .find(|entry| entry.value.version() == version),
+            None => entries.into_iter().last(),
+        };
+
+        let entry = entry.ok_or(RegistryError::StationConfigTemplateNotFound {
+            name: match version {
+                Some(version) => format!("{fullname}@{version}"),
+                None => fullname,
+            },
+        })?;
+
+        Ok(entry)
+    }
+
     /// Finds the next version of the registry entry by name and the current version.
     ///
     /// If there is no next version, `None` is returned.
@@ -262,27 +415,8 @@ impl RegistryService {
         name: &str,
         current_version: &str,
     ) -> ServiceResult<Option<RegistryEntry>> {
-        let fullname = match name.starts_with(RegistryEntry::NAMESPACE_PREFIX) {
-            true => name.to_string(),
-            false => format!(
-                "{}{}/{}",
-                RegistryEntry::NAMESPACE_PREFIX,
-                RegistryEntry::DEFAULT_NAMESPACE,
-                name
-            ),
-        };
-
-        let results = self.registry_repository.find_ids_where(
-            RegistryWhere::clause()
-                .and_fullname(&fullname)
-                .and_kind(RegistryValueKind::WasmModule),
-            Some(RegistryEntrySortBy::Version(SortDirection::Asc)),
-        );
-
-        let mut entries = results
-            .iter()
-            .filter_map(|id| self.get(id).ok())
-            .collect::<Vec<RegistryEntry>>();
+        let fullname = Self::to_fullname(name);
+        let mut entries = self.find_wasm_modules_by_fullname(&fullname);
 
         if entries.is_empty() {
             return Err(RegistryError::WasmModuleNotFound {
@@ -290,32 +424,156 @@ impl RegistryService {
             })?;
         }
 
-        entries.retain(|entry| match &entry.value {
-            RegistryValue::WasmModule(wasm_module) => wasm_module.version != current_version,
-        });
+        entries.retain(|entry| entry.value.version() != current_version);
 
         let current_version = HelperMapper::to_semver(current_version);
 
         for entry in entries {
-            match &entry.value {
-                RegistryValue::WasmModule(wasm_module) => {
-                    let new_version = HelperMapper::to_semver(&wasm_module.version);
-                    if new_version > current_version {
-                        return Ok(Some(entry));
-                    }
-                }
+            let new_version = HelperMapper::to_semver(entry.value.version());
+            if new_version > current_version {
+                return Ok(Some(entry));
             }
         }
 
         Ok(None)
     }
+
+    /// Resolves the upgrade path of a station and its upgrader to the target station version.
+    ///
+    /// Every station version between the installed and the target one is a step of the path, and the
+    /// upgrader is upgraded along the way to the newest version that satisfies the requirement that the
+    /// station version of the step has on it.
+    pub fn resolve_upgrade_path(
+        &self,
+        input: ResolveUpgradePathInput,
+    ) -> ServiceResult<Vec<UpgradePathStep>> {
+        let parse_version = |version: &str| {
+            semver::Version::parse(version).map_err(|e| RegistryError::ValidationError {
+                info: format!("Invalid version {version}: {e}"),
+            })
+        };
+
+        let station_fullname = Self::to_fullname(&input.station.name);
+        let upgrader_fullname = Self::to_fullname(&input.upgrader.name);
+        let current_station_version = parse_version(&input.station.current_version)?;
+        let mut current_upgrader_version = parse_version(&input.upgrader.current_version)?;
+        let target_version = input
+            .target_version
+            .as_deref()
+            .map(parse_version)
+            .transpose()?;
+
+        let stations = self.find_wasm_modules_by_fullname(&station_fullname);
+        if stations.is_empty() {
+            Err(RegistryError::WasmModuleNotFound {
+                name: station_fullname.clone(),
+            })?;
+        }
+
+        let stations = stations
+            .into_iter()
+            .filter_map(|entry| {
+                let version = semver::Version::parse(entry.value.version()).ok()?;
+                Some((version, entry))
+            })
+            .filter(|(version, _)| {
+                *version > current_station_version
+                    && target_version
+                        .as_ref()
+                        .map_or(true, |target_version| version <= target_version)
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(target_version) = &target_version {
+            if target_version > &current_station_version
+                && !stations
+                    .iter()
+                    .any(|(version, _)| version == target_version)
+            {
+                Err(RegistryError::UpgradePathNotFound {
+                    reason: format!("{station_fullname} has no version {target_version}"),
+                })?;
+            }
+        }
+
+        let upgraders = self.find_wasm_modules_by_fullname(&upgrader_fullname);
+        let mut steps = Vec::with_capacity(stations.len());
+        for (station_version, station) in stations {
+            let RegistryValue::WasmModule(station_module) = &station.value else {
+                continue;
+            };
+
+            let requirement = station_module
+                .dependencies
+                .iter()
+                .find(|dependency| Self::to_fullname(&dependency.name) == upgrader_fullname);
+
+            let mut upgrader = None;
+            if let Some(requirement) = requirement {
+                let version_req = requirement.version_req()?;
+                if !version_req.matches(&current_upgrader_version) {
+                    let (version, entry) = upgraders
+                        .iter()
+                        .rev()
+                        .filter_map(|entry| {
+                            let version = semver::Version::parse(entry.value.version()).ok()?;
+                            Some((version, entry))
+                        })
+                        .find(|(version, _)| {
+                            *version > current_upgrader_version && version_req.matches(version)
+                        })
+                        .ok_or_else(|| RegistryError::UpgradePathNotFound {
+                            reason: format!(
+                                "no version of {upgrader_fullname} newer than {current_upgrader_version} satisfies {} required by {station_fullname} {station_version}",
+                                requirement.version
+                            ),
+                        })?;
+
+                    current_upgrader_version = version;
+                    upgrader = Some(entry.clone());
+                }
+            }
+
+            steps.push(UpgradePathStep { station, upgrader });
+        }
+
+        Ok(steps)
+    }
+
+    /// Returns the wasm modules with the given full name, sorted by ascending version.
+    fn find_wasm_modules_by_fullname(&self, fullname: &str) -> Vec<RegistryEntry> {
+        self.registry_repository
+            .find_ids_where(
+                RegistryWhere::clause()
+                    .and_fullname(fullname)
+                    .and_kind(RegistryValueKind::WasmModule),
+                Some(RegistryEntrySortBy::Version(SortDirection::Asc)),
+            )
+            .iter()
+            .filter_map(|id| self.get(id).ok())
+            .collect()
+    }
+
+    /// Returns the full name of the entry, using the default namespace if none is provided.
+    fn to_fullname(name: &str) -> String {
+        match name.starts_with(RegistryEntry::NAMESPACE_PREFIX) {
+            true => name.to_string(),
+            false => format!(
+                "{}{}/{}",
+                RegistryEntry::NAMESPACE_PREFIX,
+                RegistryEntry::DEFAULT_NAMESPACE,
+                name
+            ),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::{
-        registry_entry_test_utils::create_registry_entry, WasmModuleRegistryValue,
+        registry_entry_test_utils::create_registry_entry, WasmModuleRegistryEntryDependency,
+        WasmModuleRegistryValue,
     };
     use candid::Principal;
     use control_panel_api::RegistryEntryValueKindDTO;
@@ -461,17 +719,55 @@ mod tests {
                 control_panel_api::WasmModuleRegistryEntryValueInput {
                     version: "1.0.0".to_string(),
                     wasm_module: [0, 1, 3].to_vec(),
+                    wasm_module_artifact_id: None,
                     dependencies: Vec::new(),
                     module_extra_chunks: None,
                 },
             ),
         };
 
-        let result = REGISTRY_SERVICE.create(input);
+        let result = REGISTRY_SERVICE.create(input, &CallContext::default());
 
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_creates_wasm_module_from_uploaded_artifact() {
+        let ctx = CallContext::default();
+        let wasm_module = vec![0, 97, 115, 109];
+        let upload = ARTIFACT_SERVICE
+            .start_upload(orbit_essentials::utils::sha256_hash(&wasm_module), 4, &ctx)
+            .unwrap();
+        ARTIFACT_SERVICE
+            .upload_chunk(&upload.id, 0, wasm_module, &ctx)
+            .unwrap();
+        let artifact_id = ARTIFACT_SERVICE.finish_upload(&upload.id, &ctx).unwrap();
+
+        let input = |wasm_module: Vec<u8>| RegistryEntryInput {
+            name: "uploaded".to_string(),
+            description: "A wasm module that was uploaded in chunks.".to_string(),
+            tags: Vec::new(),
+            categories: Vec::new(),
+            metadata: Default::default(),
+            value: control_panel_api::RegistryEntryValueInput::WasmModule(
+                control_panel_api::WasmModuleRegistryEntryValueInput {
+                    version: "1.0.0".to_string(),
+                    wasm_module,
+                    wasm_module_artifact_id: Some(Uuid::from_bytes(artifact_id).to_string()),
+                    dependencies: Vec::new(),
+                    module_extra_chunks: None,
+                },
+            ),
+        };
+
+        assert!(REGISTRY_SERVICE.create(input(vec![1]), &ctx).is_err());
+
+        let entry = REGISTRY_SERVICE.create(input(Vec::new()), &ctx).unwrap();
+
+        assert_eq!(entry.value.artifact_id(), &artifact_id);
+        assert_eq!(ARTIFACT_SERVICE.find_by_id(&artifact_id).unwrap().rc(), 1);
+    }
+
     #[test]
     fn test_latest_tag() {
         let mut entry = create_registry_entry();
@@ -496,13 +792,16 @@ mod tests {
                 control_panel_api::WasmModuleRegistryEntryValueInput {
                     version: "1.0.1".to_string(),
                     wasm_module: [0, 1, 3].to_vec(),
+                    wasm_module_artifact_id: None,
                     dependencies: Vec::new(),
                     module_extra_chunks: None,
                 },
             ),
         };
 
-        let result = REGISTRY_SERVICE.create(input).unwrap();
+        let result = REGISTRY_SERVICE
+            .create(input, &CallContext::default())
+            .unwrap();
 
         assert_eq!(result.tags, vec![LATEST_TAG.to_string()]);
 
@@ -529,13 +828,16 @@ mod tests {
                 control_panel_api::WasmModuleRegistryEntryValueInput {
                     version: "1.0.0".to_string(),
                     wasm_module: [0, 1].to_vec(),
+                    wasm_module_artifact_id: None,
                     dependencies: Vec::new(),
                     module_extra_chunks: None,
                 },
             ),
         };
 
-        let entry = REGISTRY_SERVICE.create(create_input).unwrap();
+        let entry = REGISTRY_SERVICE
+            .create(create_input, &CallContext::default())
+            .unwrap();
 
         let module_extra_chunks = WasmModuleExtraChunks {
             store_canister: Principal::management_canister(),
@@ -551,6 +853,7 @@ mod tests {
                 control_panel_api::WasmModuleRegistryEntryValueInput {
                     version: "1.0.1".to_string(),
                     wasm_module: [0, 1, 3].to_vec(),
+                    wasm_module_artifact_id: None,
                     dependencies: Vec::new(),
                     module_extra_chunks: Some(module_extra_chunks.clone()),
                 },
@@ -573,6 +876,7 @@ mod tests {
                 assert_eq!(wasm_module.dependencies.len(), 0);
                 assert_eq!(wasm_module.module_extra_chunks, Some(module_extra_chunks));
             }
+            _ => panic!("expected a wasm module"),
         }
     }
 
@@ -601,6 +905,7 @@ mod tests {
             RegistryValue::WasmModule(wasm_module) => {
                 assert_eq!(wasm_module.version, "1.0.4");
             }
+            _ => panic!("expected a wasm module"),
         }
     }
 
@@ -632,4 +937,281 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    fn insert_wasm_module(name: &str, version: &str, dependencies: Vec<(&str, &str)>) {
+        let mut entry = create_registry_entry();
+        entry.name = name.to_string();
+        entry.value = RegistryValue::WasmModule(WasmModuleRegistryValue {
+            wasm_artifact_id: *Uuid::new_v4().as_bytes(),
+            version: version.to_string(),
+            dependencies: dependencies
+                .into_iter()
+                .map(|(name, version)| WasmModuleRegistryEntryDependency {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                })
+                .collect(),
+            module_extra_chunks: None,
+        });
+
+        REGISTRY_REPOSITORY.insert(entry.id, entry);
+    }
+
+    fn upgrade_path_input(
+        station_version: &str,
+        upgrader_version: &str,
+        target_version: Option<&str>,
+    ) -> ResolveUpgradePathInput {
+        ResolveUpgradePathInput {
+            station: control_panel_api::UpgradePathModuleInput {
+                name: "station".to_string(),
+                current_version: station_version.to_string(),
+            },
+            upgrader: control_panel_api::UpgradePathModuleInput {
+                name: "upgrader".to_string(),
+                current_version: upgrader_version.to_string(),
+            },
+            target_version: target_version.map(|version| version.to_string()),
+        }
+    }
+
+    #[test]
+    fn resolves_upgrade_path_through_intermediate_versions() {
+        for version in ["0.1.0", "0.2.0", "0.2.1", "0.3.0"] {
+            insert_wasm_module("upgrader", version, Vec::new());
+        }
+        insert_wasm_module("station", "1.0.0", vec![("upgrader", "^0.1")]);
+        insert_wasm_module("station", "1.1.0", vec![("upgrader", "^0.1")]);
+        insert_wasm_module("station", "1.2.0", vec![("upgrader", ">=0.2 <0.3")]);
+        insert_wasm_module("station", "2.0.0", vec![("upgrader", "0.3.0")]);
+
+        let steps = REGISTRY_SERVICE
+            .resolve_upgrade_path(upgrade_path_input("1.0.0", "0.1.0", None))
+            .unwrap();
+
+        let versions = steps
+            .iter()
+            .map(|step| {
+                (
+                    step.station.value.version().to_string(),
+                    step.upgrader
+                        .as_ref()
+                        .map(|upgrader| upgrader.value.version().to_string()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            versions,
+            vec![
+                ("1.1.0".to_string(), None),
+                ("1.2.0".to_string(), Some("0.2.1".to_string())),
+                ("2.0.0".to_string(), Some("0.3.0".to_string())),
+            ]
+        );
+
+        let steps = REGISTRY_SERVICE
+            .resolve_upgrade_path(upgrade_path_input("1.0.0", "0.1.0", Some("1.1.0")))
+            .unwrap();
+
+        assert_eq!(steps.len(), 1);
+        assert!(steps[0].upgrader.is_none());
+
+        let steps = REGISTRY_SERVICE
+            .resolve_upgrade_path(upgrade_path_input("2.0.0", "0.3.0", None))
+            .unwrap();
+
+        assert!(steps.is_empty());
+    }
+
+    #[test]
+    fn upgrade_path_fails_if_upgrader_cannot_be_satisfied() {
+        insert_wasm_module("upgrader", "0.1.0", Vec::new());
+        insert_wasm_module("station", "1.0.0", vec![("upgrader", "^0.1")]);
+        insert_wasm_module("station", "1.1.0", vec![("upgrader", "^0.2")]);
+
+        let result =
+            REGISTRY_SERVICE.resolve_upgrade_path(upgrade_path_input("1.0.0", "0.1.0", None));
+
+        assert!(result.is_err());
+
+        let result = REGISTRY_SERVICE.resolve_upgrade_path(upgrade_path_input(
+            "1.0.0",
+            "0.1.0",
+            Some("1.0.5"),
+        ));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_creates_candid_interface_linked_to_wasm_module() {
+        let module = REGISTRY_SERVICE
+            .create(
+                RegistryEntryInput {
+                    name: "@orbit/ledger".to_string(),
+                    description: "This is a test description for the module.".to_string(),
+                    tags: Vec::new(),
+                    categories: Vec::new(),
+                    metadata: Default::default(),
+                    value: control_panel_api::RegistryEntryValueInput::WasmModule(
+                        control_panel_api::WasmModuleRegistryEntryValueInput {
+                            version: "1.0.0".to_string(),
+                            wasm_module: [0, 1].to_vec(),
+                            wasm_module_artifact_id: None,
+                            dependencies: Vec::new(),
+                            module_extra_chunks: None,
+                        },
+                    ),
+                },
+                &CallContext::default(),
+            )
+            .unwrap();
+
+        let entry = REGISTRY_SERVICE
+            .create(
+                RegistryEntryInput {
+                    name: "@orbit/ledger-did".to_string(),
+                    description: "This is the Candid interface of the ledger.".to_string(),
+                    tags: Vec::new(),
+                    categories: Vec::new(),
+                    metadata: Default::default(),
+                    value: control_panel_api::RegistryEntryValueInput::CandidInterface(
+                        control_panel_api::CandidInterfaceRegistryEntryValueInput {
+                            candid: "service : { greet : (text) -> (text) query }".to_string(),
+                            version: "1.0.0".to_string(),
+                            wasm_module: Some(
+                                control_panel_api::WasmModuleRegistryEntryDependencyDTO {
+                                    name: module.fullname(),
+                                    version: "1.0.0".to_string(),
+                                },
+                            ),
+                        },
+                    ),
+                },
+                &CallContext::default(),
+            )
+            .unwrap();
+
+        let result = REGISTRY_SERVICE
+            .search(SearchRegistryInput {
+                filter_by: vec![SearchRegistryFilterKindDTO::Kind(
+                    RegistryEntryValueKindDTO::CandidInterface,
+                )],
+                sort_by: None,
+                pagination: None,
+            })
+            .unwrap();
+
+        assert_eq!(result.total, 1);
+        assert_eq!(result.items[0].id, entry.id);
+    }
+
+    #[test]
+    fn test_rejects_invalid_artifact_content() {
+        let input = |value| RegistryEntryInput {
+            name: "content".to_string(),
+            description: "This is a test description for the entry.".to_string(),
+            tags: Vec::new(),
+            categories: Vec::new(),
+            metadata: Default::default(),
+            value,
+        };
+
+        assert!(REGISTRY_SERVICE
+            .create(
+                input(control_panel_api::RegistryEntryValueInput::CandidInterface(
+                    control_panel_api::CandidInterfaceRegistryEntryValueInput {
+                        candid: "type A = text;".to_string(),
+                        version: "1.0.0".to_string(),
+                        wasm_module: None,
+                    },
+                )),
+                &CallContext::default()
+            )
+            .is_err());
+
+        assert!(REGISTRY_SERVICE
+            .create(
+                input(control_panel_api::RegistryEntryValueInput::AssetBundle(
+                    control_panel_api::AssetBundleRegistryEntryValueInput {
+                        bundle: b"not a tarball".to_vec(),
+                        version: "1.0.0".to_string(),
+                    },
+                )),
+                &CallContext::default()
+            )
+            .is_err());
+
+        assert!(REGISTRY_SERVICE
+            .create(
+                input(
+                    control_panel_api::RegistryEntryValueInput::StationConfigTemplate(
+                        control_panel_api::StationConfigTemplateRegistryEntryValueInput {
+                            template: "[]".to_string(),
+                            version: "1.0.0".to_string(),
+                        },
+                    )
+                ),
+                &CallContext::default()
+            )
+            .is_err());
+
+        let bundle = REGISTRY_SERVICE
+            .create(
+                input(control_panel_api::RegistryEntryValueInput::AssetBundle(
+                    control_panel_api::AssetBundleRegistryEntryValueInput {
+                        bundle: [0x1f, 0x8b, 0x08, 0x00].to_vec(),
+                        version: "1.0.0".to_string(),
+                    },
+                )),
+                &CallContext::default(),
+            )
+            .unwrap();
+
+        assert_eq!(bundle.to_kind(), RegistryValueKind::AssetBundle);
+    }
+
+    #[test]
+    fn test_edit_cannot_change_kind() {
+        let entry = REGISTRY_SERVICE
+            .create(
+                RegistryEntryInput {
+                    name: "template".to_string(),
+                    description: "This is a test description for the template.".to_string(),
+                    tags: Vec::new(),
+                    categories: Vec::new(),
+                    metadata: Default::default(),
+                    value: control_panel_api::RegistryEntryValueInput::StationConfigTemplate(
+                        control_panel_api::StationConfigTemplateRegistryEntryValueInput {
+                            template: r#"{"name":"treasury"}"#.to_string(),
+                            version: "1.0.0".to_string(),
+                        },
+                    ),
+                },
+                &CallContext::default(),
+            )
+            .unwrap();
+
+        let result = REGISTRY_SERVICE.edit(
+            &entry.id,
+            RegistryEntryUpdateInput {
+                description: None,
+                tags: None,
+                categories: None,
+                metadata: None,
+                value: Some(control_panel_api::RegistryEntryValueInput::WasmModule(
+                    control_panel_api::WasmModuleRegistryEntryValueInput {
+                        version: "1.0.1".to_string(),
+                        wasm_module: [0, 1].to_vec(),
+                        wasm_module_artifact_id: None,
+                        dependencies: Vec::new(),
+                        module_extra_chunks: None,
+                    },
+                )),
+            },
+        );
+
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,176 @@
+use crate::{
+    core::{ic_cdk::next_time, CallContext},
+    errors::RegistryError,
+    mappers::RegistryMapper,
+    models::{RegistryEntry, RegistryNamespace},
+    repositories::{RegistryNamespaceRepository, REGISTRY_NAMESPACE_REPOSITORY},
+};
+use control_panel_api::{AddRegistryNamespaceInput, EditRegistryNamespaceInput};
+use lazy_static::lazy_static;
+use orbit_essentials::{api::ServiceResult, model::ModelValidator, repository::Repository};
+use std::sync::Arc;
+
+lazy_static! {
+    pub static ref REGISTRY_NAMESPACE_SERVICE: Arc<RegistryNamespaceService> = Arc::new(
+        RegistryNamespaceService::new(Arc::clone(&REGISTRY_NAMESPACE_REPOSITORY))
+    );
+}
+
+/// The registry namespace service manages who can publish entries to each namespace of the registry.
+#[derive(Default, Debug)]
+pub struct RegistryNamespaceService {
+    registry_namespace_repository: Arc<RegistryNamespaceRepository>,
+}
+
+impl RegistryNamespaceService {
+    pub fn new(registry_namespace_repository: Arc<RegistryNamespaceRepository>) -> Self {
+        Self {
+            registry_namespace_repository,
+        }
+    }
+
+    /// Returns the namespace by name, the namespace prefix is optional.
+    pub fn get(&self, name: &str) -> ServiceResult<RegistryNamespace> {
+        let name = RegistryMapper::to_namespace_name(name);
+        let namespace = self
+            .registry_namespace_repository
+            .get(&name)
+            .ok_or(RegistryError::NamespaceNotFound { namespace: name })?;
+
+        Ok(namespace)
+    }
+
+    /// Registers a new namespace and returns it.
+    pub fn create(&self, input: AddRegistryNamespaceInput) -> ServiceResult<RegistryNamespace> {
+        let name = RegistryMapper::to_namespace_name(&input.name);
+
+        if name == RegistryEntry::DEFAULT_NAMESPACE {
+            Err(RegistryError::ValidationError {
+                info: "The default namespace cannot be registered".to_string(),
+            })?
+        }
+
+        if self.registry_namespace_repository.exists(&name) {
+            Err(RegistryError::NamespaceAlreadyExists { namespace: name })?
+        }
+
+        let namespace = RegistryNamespace::new(name, input.owners, input.publishers);
+
+        namespace.validate()?;
+
+        self.registry_namespace_repository
+            .insert(namespace.name.clone(), namespace.clone());
+
+        Ok(namespace)
+    }
+
+    /// Updates the owners and publishers of the namespace and returns it.
+    pub fn edit(&self, input: EditRegistryNamespaceInput) -> ServiceResult<RegistryNamespace> {
+        let mut namespace = self.get(&input.name)?;
+
+        if let Some(owners) = input.owners {
+            namespace.owners = owners;
+        }
+
+        if let Some(publishers) = input.publishers {
+            namespace.publishers = publishers;
+        }
+
+        namespace.updated_at = Some(next_time());
+        namespace.validate()?;
+
+        self.registry_namespace_repository
+            .insert(namespace.name.clone(), namespace.clone());
+
+        Ok(namespace)
+    }
+
+    /// Returns `true` if the caller can publish entries to the namespace.
+    ///
+    /// Admins can publish to all namespaces, including the ones that are not registered.
+    pub fn can_publish(&self, ctx: &CallContext, namespace: &str) -> bool {
+        ctx.is_admin()
+            || self
+                .registry_namespace_repository
+                .get(&RegistryMapper::to_namespace_name(namespace))
+                .is_some_and(|namespace| namespace.can_publish(&ctx.caller()))
+    }
+
+    /// Returns `true` if the caller can publish entries to at least one namespace.
+    pub fn can_publish_to_any(&self, ctx: &CallContext) -> bool {
+        ctx.is_admin()
+            || self
+                .registry_namespace_repository
+                .list()
+                .iter()
+                .any(|namespace| namespace.can_publish(&ctx.caller()))
+    }
+
+    /// Returns `true` if the caller can manage the owners and publishers of the namespace.
+    pub fn can_manage(&self, ctx: &CallContext, namespace: &str) -> bool {
+        ctx.is_admin()
+            || self
+                .registry_namespace_repository
+                .get(&RegistryMapper::to_namespace_name(namespace))
+                .is_some_and(|namespace| namespace.is_owner(&ctx.caller()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    #[test]
+    fn owners_manage_and_publishers_publish() {
+        let owner = Principal::from_slice(&[1; 29]);
+        let publisher = Principal::from_slice(&[2; 29]);
+
+        let namespace = REGISTRY_NAMESPACE_SERVICE
+            .create(AddRegistryNamespaceInput {
+                name: "@orbit".to_string(),
+                owners: vec![owner],
+                publishers: vec![],
+            })
+            .unwrap();
+
+        assert_eq!(namespace.name, "orbit");
+        assert!(!REGISTRY_NAMESPACE_SERVICE.can_publish(&CallContext::new(publisher), "orbit"));
+
+        REGISTRY_NAMESPACE_SERVICE
+            .edit(EditRegistryNamespaceInput {
+                name: "orbit".to_string(),
+                owners: None,
+                publishers: Some(vec![publisher]),
+            })
+            .unwrap();
+
+        assert!(REGISTRY_NAMESPACE_SERVICE.can_publish(&CallContext::new(publisher), "@orbit"));
+        assert!(REGISTRY_NAMESPACE_SERVICE.can_manage(&CallContext::new(owner), "orbit"));
+        assert!(!REGISTRY_NAMESPACE_SERVICE.can_manage(&CallContext::new(publisher), "orbit"));
+        assert!(!REGISTRY_NAMESPACE_SERVICE.can_publish(&CallContext::new(publisher), "other"));
+    }
+
+    #[test]
+    fn namespaces_cannot_be_registered_twice() {
+        let input = AddRegistryNamespaceInput {
+            name: "twice".to_string(),
+            owners: vec![Principal::from_slice(&[1; 29])],
+            publishers: vec![],
+        };
+
+        assert!(REGISTRY_NAMESPACE_SERVICE.create(input.clone()).is_ok());
+        assert!(REGISTRY_NAMESPACE_SERVICE.create(input).is_err());
+    }
+
+    #[test]
+    fn default_namespace_cannot_be_registered() {
+        let result = REGISTRY_NAMESPACE_SERVICE.create(AddRegistryNamespaceInput {
+            name: RegistryEntry::DEFAULT_NAMESPACE.to_string(),
+            owners: vec![Principal::from_slice(&[1; 29])],
+            publishers: vec![],
+        });
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use super::{ArtifactService, RegistryService, ARTIFACT_SERVICE, REGISTRY_SERVICE};
+use crate::{
+    core::{ic_cdk::api::id as self_canister_id, CallContext},
+    errors::DeployError,
+    models::{builtin_station_config_templates, StationConfigTemplate, LATEST_TAG},
+};
+use control_panel_api::{
+    DeployStationTemplateInput, RegistryEntryInput, RegistryEntryValueInput,
+    StationConfigTemplateRegistryEntryValueInput,
+};
+use lazy_static::lazy_static;
+use orbit_essentials::api::ServiceResult;
+use std::sync::Arc;
+
+lazy_static! {
+    pub static ref STATION_CONFIG_TEMPLATE_SERVICE: Arc<StationConfigTemplateService> =
+        Arc::new(StationConfigTemplateService::new(
+            Arc::clone(&REGISTRY_SERVICE),
+            Arc::clone(&ARTIFACT_SERVICE),
+        ));
+}
+
+/// Resolves the templates used to deploy stations and publishes the built-in ones.
+#[derive(Default, Debug)]
+pub struct StationConfigTemplateService {
+    registry_service: Arc<RegistryService>,
+    artifact_service: Arc<ArtifactService>,
+}
+
+impl StationConfigTemplateService {
+    pub fn new(
+        registry_service: Arc<RegistryService>,
+        artifact_service: Arc<ArtifactService>,
+    ) -> Self {
+        Self {
+            registry_service,
+            artifact_service,
+        }
+    }
+
+    /// Returns the template of the deployment, either inline or from the registry.
+    pub fn resolve(
+        &self,
+        input: &DeployStationTemplateInput,
+    ) -> ServiceResult<StationConfigTemplate> {
+        let content = match input {
+            DeployStationTemplateInput::Inline(template) => template.as_bytes().to_vec(),
+            DeployStationTemplateInput::Registry(reference) => {
+                let entry = self
+                    .registry_service
+                    .find_station_config_template(&reference.name, reference.version.as_deref())?;
+                let artifact = self
+                    .artifact_service
+                    .find_by_id(entry.value.artifact_id())?;
+
+                self.artifact_service.read_content(&artifact)?
+            }
+        };
+
+        let template = StationConfigTemplate::from_json(&content)
+            .map_err(|reason| DeployError::InvalidTemplate { reason })?;
+
+        Ok(template)
+    }
+
+    /// Publishes the built-in templates whose version is not in the registry yet.
+    ///
+    /// The control panel is recorded as the publisher of the built-in templates.
+    pub fn publish_builtin_templates(&self) -> ServiceResult<()> {
+        let ctx = CallContext::new(self_canister_id());
+
+        for builtin in builtin_station_config_templates() {
+            if self
+                .registry_service
+                .find_station_config_template(builtin.name, Some(builtin.version))
+                .is_ok()
+            {
+                continue;
+            }
+
+            self.registry_service.create(
+                RegistryEntryInput {
+                    name: builtin.name.to_string(),
+                    description: builtin.template.description.clone().unwrap_or_default(),
+                    tags: vec![LATEST_TAG.to_string()],
+                    categories: Vec::new(),
+                    metadata: Vec::new(),
+                    value: RegistryEntryValueInput::StationConfigTemplate(
+                        StationConfigTemplateRegistryEntryValueInput {
+                            template: builtin.template.to_json(),
+                            version: builtin.version.to_string(),
+                        },
+                    ),
+                },
+                &ctx,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use control_panel_api::StationConfigTemplateReferenceInput;
+
+    #[test]
+    fn publishes_builtin_templates_once() {
+        STATION_CONFIG_TEMPLATE_SERVICE
+            .publish_builtin_templates()
+            .unwrap();
+        STATION_CONFIG_TEMPLATE_SERVICE
+            .publish_builtin_templates()
+            .unwrap();
+
+        for builtin in builtin_station_config_templates() {
+            let template = STATION_CONFIG_TEMPLATE_SERVICE
+                .resolve(&DeployStationTemplateInput::Registry(
+                    StationConfigTemplateReferenceInput {
+                        name: builtin.name.to_string(),
+                        version: None,
+                    },
+                ))
+                .unwrap();
+
+            assert_eq!(template.min_admins, builtin.template.min_admins);
+        }
+
+        let entry = REGISTRY_SERVICE
+            .find_station_config_template("@orbit/treasury-2-of-3", None)
+            .unwrap();
+        assert_eq!(entry.publisher, Some(self_canister_id()));
+    }
+
+    #[test]
+    fn resolves_inline_templates() {
+        assert!(STATION_CONFIG_TEMPLATE_SERVICE
+            .resolve(&DeployStationTemplateInput::Inline(
+                r#"{"min_admins":2}"#.to_string()
+            ))
+            .is_ok());
+        assert!(STATION_CONFIG_TEMPLATE_SERVICE
+            .resolve(&DeployStationTemplateInput::Inline("[]".to_string()))
+            .is_err());
+        assert!(STATION_CONFIG_TEMPLATE_SERVICE
+            .resolve(&DeployStationTemplateInput::Registry(
+                StationConfigTemplateReferenceInput {
+                    name: "@orbit/unknown".to_string(),
+                    version: None,
+                }
+            ))
+            .is_err());
+    }
+}
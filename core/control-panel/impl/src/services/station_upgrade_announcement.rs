@@ -0,0 +1,178 @@
+use super::{RegistryService, UserService, REGISTRY_SERVICE, USER_SERVICE};
+use crate::{
+    core::ic_cdk::{api::print, spawn},
+    models::{RegistryEntry, RegistryValue},
+};
+use candid::Principal;
+use futures::future::join_all;
+use lazy_static::lazy_static;
+use orbit_essentials::api::ApiResult;
+use station_api::NotifyStationUpgradeAvailableInput;
+use std::sync::Arc;
+use uuid::Uuid;
+
+lazy_static! {
+    pub static ref STATION_UPGRADE_ANNOUNCEMENT_SERVICE: Arc<StationUpgradeAnnouncementService> =
+        Arc::new(StationUpgradeAnnouncementService::new(
+            Arc::clone(&REGISTRY_SERVICE),
+            Arc::clone(&USER_SERVICE),
+        ));
+}
+
+/// Announces the new station versions published in the registry to the deployed stations.
+#[derive(Default, Debug)]
+pub struct StationUpgradeAnnouncementService {
+    registry_service: Arc<RegistryService>,
+    user_service: Arc<UserService>,
+}
+
+impl StationUpgradeAnnouncementService {
+    /// The name of the registry entries of the station wasm module.
+    pub const STATION_ENTRY_NAME: &'static str = "@orbit/station";
+
+    /// The maximum number of stations that are notified concurrently, so that a slow station only
+    /// delays its own batch instead of every station after it.
+    const NOTIFICATION_BATCH_SIZE: usize = 50;
+
+    pub fn new(registry_service: Arc<RegistryService>, user_service: Arc<UserService>) -> Self {
+        Self {
+            registry_service,
+            user_service,
+        }
+    }
+
+    /// Notifies the deployed stations that did not opt out if the entry is the newest station version.
+    ///
+    /// The stations are notified in the background, so that publishing the entry does not depend on
+    /// the availability of the stations nor fails the publication. Returns the stations that are notified.
+    pub fn announce(&self, entry: &RegistryEntry) -> Vec<Principal> {
+        let RegistryValue::WasmModule(value) = &entry.value else {
+            return Vec::new();
+        };
+
+        if entry.fullname() != Self::STATION_ENTRY_NAME || !self.is_newest_version(&value.version) {
+            return Vec::new();
+        }
+
+        let stations: Vec<Principal> = self
+            .user_service
+            .get_stations_receiving_upgrade_announcements()
+            .into_iter()
+            .collect();
+
+        let input = NotifyStationUpgradeAvailableInput {
+            registry_entry_id: Uuid::from_bytes(entry.id).hyphenated().to_string(),
+            name: entry.fullname(),
+            version: value.version.clone(),
+        };
+
+        let targets = stations.clone();
+        spawn(async move {
+            for batch in targets.chunks(Self::NOTIFICATION_BATCH_SIZE) {
+                join_all(
+                    batch
+                        .iter()
+                        .map(|station| notify_station(*station, input.clone())),
+                )
+                .await;
+            }
+        });
+
+        stations
+    }
+
+    fn is_newest_version(&self, version: &str) -> bool {
+        match self
+            .registry_service
+            .find_next_wasm_module_version(Self::STATION_ENTRY_NAME, version)
+        {
+            Ok(next_version) => next_version.is_none(),
+            Err(err) => {
+                print(format!(
+                    "Failed to check if the station version {} is the newest: {}",
+                    version, err
+                ));
+
+                false
+            }
+        }
+    }
+}
+
+async fn notify_station(station: Principal, input: NotifyStationUpgradeAvailableInput) {
+    let result =
+        ic_cdk::call::<_, (ApiResult<()>,)>(station, "notify_station_upgrade_available", (input,))
+            .await;
+
+    match result {
+        Ok((Ok(()),)) => {}
+        Ok((Err(err),)) => print(format!(
+            "Failed to announce the station upgrade to {}: {}",
+            station,
+            err.message.unwrap_or(err.code)
+        )),
+        Err((_, err)) => print(format!(
+            "Failed to announce the station upgrade to {}: {}",
+            station, err
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::CallContext,
+        models::{
+            registry_entry_test_utils::create_registry_entry, user_model_utils::mock_user,
+            WasmModuleRegistryValue,
+        },
+        repositories::{REGISTRY_REPOSITORY, USER_REPOSITORY},
+    };
+    use orbit_essentials::repository::Repository;
+
+    fn insert_station_entry(version: &str) -> RegistryEntry {
+        let mut entry = create_registry_entry();
+        entry.namespace = "orbit".to_string();
+        entry.name = "station".to_string();
+        entry.value = RegistryValue::WasmModule(WasmModuleRegistryValue {
+            wasm_artifact_id: [0; 16],
+            version: version.to_string(),
+            dependencies: Vec::new(),
+            module_extra_chunks: None,
+        });
+
+        REGISTRY_REPOSITORY.insert(entry.id, entry.clone());
+
+        entry
+    }
+
+    #[test]
+    fn announces_newest_station_version_to_stations_that_did_not_opt_out() {
+        let first_station = Principal::from_slice(&[1; 29]);
+        let second_station = Principal::from_slice(&[2; 29]);
+        let mut user = mock_user();
+        user.add_deployed_station(first_station);
+        user.add_deployed_station(second_station);
+        USER_REPOSITORY.insert(user.to_key(), user.clone());
+
+        USER_SERVICE
+            .set_station_upgrade_announcements(
+                second_station,
+                false,
+                &CallContext::new(user.identity),
+            )
+            .unwrap();
+
+        let older = insert_station_entry("1.0.0");
+        let newest = insert_station_entry("1.1.0");
+
+        assert_eq!(
+            STATION_UPGRADE_ANNOUNCEMENT_SERVICE.announce(&newest),
+            vec![first_station]
+        );
+        assert!(STATION_UPGRADE_ANNOUNCEMENT_SERVICE
+            .announce(&older)
+            .is_empty());
+    }
+}
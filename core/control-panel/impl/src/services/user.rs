@@ -3,7 +3,7 @@ use crate::{
         canister_config, generate_uuid_v4, ic_cdk::next_time, write_canister_config, CallContext,
     },
     errors::{DeployError, UserError},
-    models::{CanDeployStation, User, UserId, UserKey, UserSubscriptionStatus},
+    models::{CanDeployStation, User, UserId, UserKey},
     repositories::{UserRepository, USER_REPOSITORY},
     services::canister::FUND_MANAGER,
 };
@@ -131,6 +131,36 @@ impl UserService {
             .collect()
     }
 
+    /// Returns the deployed stations that should be notified when a new station version is published.
+    pub fn get_stations_receiving_upgrade_announcements(&self) -> BTreeSet<Principal> {
+        self.user_repository
+            .list()
+            .into_iter()
+            .flat_map(|user| {
+                user.get_deployed_stations()
+                    .into_iter()
+                    .filter(|station| user.receives_upgrade_announcements(station))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Enables or disables the announcements of new station versions for a station deployed for the caller.
+    pub fn set_station_upgrade_announcements(
+        &self,
+        station_id: Principal,
+        enabled: bool,
+        ctx: &CallContext,
+    ) -> ServiceResult<User> {
+        let mut user = self.get_user_by_identity(&ctx.caller(), ctx)?;
+
+        user.set_upgrade_announcements(station_id, enabled)?;
+
+        self.user_repository.insert(user.to_key(), user.clone());
+
+        Ok(user)
+    }
+
     pub fn add_deployed_station(
         &self,
         user_id: &UserId,
@@ -218,14 +248,6 @@ impl UserService {
 
         Ok(())
     }
-
-    /// Set the subscription status of all users to `UserSubscriptionStatus::Approved`.
-    pub fn approve_all_users(&self) {
-        for mut user in self.user_repository.list() {
-            user.subscription_status = UserSubscriptionStatus::Approved;
-            self.user_repository.insert(user.to_key(), user);
-        }
-    }
 }
 
 #[cfg(test)]
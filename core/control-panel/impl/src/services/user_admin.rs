@@ -0,0 +1,392 @@
+use crate::{
+    core::CallContext,
+    errors::{CanisterError, UserError},
+    mappers::HelperMapper,
+    models::{
+        indexes::user_status_index::UserIndexSubscriptionStatus, AdminAction, AdminActionKind,
+        User, UserKey, UserSubscriptionStatus,
+    },
+    repositories::{
+        AdminActionRepository, UserRepository, ADMIN_ACTION_REPOSITORY, USER_REPOSITORY,
+    },
+};
+use control_panel_api::{
+    ExportWaitlistInput, ListAdminActionsInput, ListUsersInput, SetUserDeployQuotaInput,
+    SetUserSubscriptionStatusInput,
+};
+use lazy_static::lazy_static;
+use orbit_essentials::{
+    api::ServiceResult,
+    model::ModelValidator,
+    pagination::{paginated_items, PaginatedData, PaginatedItemsArgs},
+    repository::Repository,
+};
+use std::sync::Arc;
+
+lazy_static! {
+    pub static ref USER_ADMIN_SERVICE: Arc<UserAdminService> = Arc::new(UserAdminService::new(
+        Arc::clone(&USER_REPOSITORY),
+        Arc::clone(&ADMIN_ACTION_REPOSITORY),
+    ));
+}
+
+/// Manages the users of the control panel on behalf of its controllers.
+///
+/// Every change and export is recorded in the admin action log.
+#[derive(Default, Debug)]
+pub struct UserAdminService {
+    user_repository: Arc<UserRepository>,
+    admin_action_repository: Arc<AdminActionRepository>,
+}
+
+impl UserAdminService {
+    pub const DEFAULT_LIST_LIMIT: u16 = 50;
+    pub const MAX_LIST_LIMIT: u16 = 500;
+
+    pub fn new(
+        user_repository: Arc<UserRepository>,
+        admin_action_repository: Arc<AdminActionRepository>,
+    ) -> Self {
+        Self {
+            user_repository,
+            admin_action_repository,
+        }
+    }
+
+    /// Returns the users with the given subscription status, or all users if no status is given.
+    pub fn list_users(
+        &self,
+        input: ListUsersInput,
+        ctx: &CallContext,
+    ) -> ServiceResult<PaginatedData<User>> {
+        self.assert_controller(ctx, "list_users")?;
+
+        let user_ids = self
+            .user_repository
+            .find_ids_by_subscription_status(input.subscription_status.map(Into::into));
+        let paginated_ids = paginated_items(PaginatedItemsArgs {
+            offset: input.pagination.to_owned().and_then(|p| p.offset),
+            limit: input.pagination.and_then(|p| p.limit),
+            default_limit: Some(Self::DEFAULT_LIST_LIMIT),
+            max_limit: Some(Self::MAX_LIST_LIMIT),
+            items: &user_ids,
+        })?;
+
+        Ok(PaginatedData {
+            total: paginated_ids.total,
+            next_offset: paginated_ids.next_offset,
+            items: paginated_ids
+                .items
+                .into_iter()
+                .flat_map(|id| self.user_repository.get(&UserKey(id)))
+                .collect(),
+        })
+    }
+
+    /// Approves or denies the user, users cannot be moved back to the waitlist.
+    pub fn set_user_subscription_status(
+        &self,
+        input: SetUserSubscriptionStatusInput,
+        ctx: &CallContext,
+    ) -> ServiceResult<User> {
+        self.assert_controller(ctx, "set_user_subscription_status")?;
+
+        let mut user = self.get_user(input.user_id)?;
+        let subscription_status = UserSubscriptionStatus::try_from(input.subscription_status)?;
+
+        user.subscription_status = subscription_status.clone();
+        user.validate()?;
+
+        self.user_repository.insert(user.to_key(), user.clone());
+        self.log_action(
+            ctx,
+            AdminActionKind::SetUserSubscriptionStatus {
+                user_id: user.id,
+                subscription_status,
+            },
+        );
+
+        Ok(user)
+    }
+
+    /// Sets the quota of stations that can be deployed for the user.
+    pub fn set_user_deploy_quota(
+        &self,
+        input: SetUserDeployQuotaInput,
+        ctx: &CallContext,
+    ) -> ServiceResult<User> {
+        self.assert_controller(ctx, "set_user_deploy_quota")?;
+
+        let mut user = self.get_user(input.user_id)?;
+        let max_deployed_stations = input
+            .quota
+            .max_deployed_stations
+            .map(to_quota)
+            .transpose()?;
+        let max_deployed_stations_per_day = to_quota(input.quota.max_deployed_stations_per_day)?;
+
+        user.set_deploy_quota(max_deployed_stations, max_deployed_stations_per_day);
+
+        self.user_repository.insert(user.to_key(), user.clone());
+        self.log_action(
+            ctx,
+            AdminActionKind::SetUserDeployQuota {
+                user_id: user.id,
+                max_deployed_stations,
+                max_deployed_stations_per_day,
+            },
+        );
+
+        Ok(user)
+    }
+
+    /// Returns the users on the waitlist, including their e-mail addresses.
+    ///
+    /// The export is served by a query, so it is not recorded in the admin action log.
+    pub fn export_waitlist(
+        &self,
+        input: ExportWaitlistInput,
+        ctx: &CallContext,
+    ) -> ServiceResult<PaginatedData<User>> {
+        self.assert_controller(ctx, "export_waitlist")?;
+
+        let user_ids = self
+            .user_repository
+            .find_ids_by_subscription_status(Some(UserIndexSubscriptionStatus::Pending));
+        let paginated_ids = paginated_items(PaginatedItemsArgs {
+            offset: input.pagination.to_owned().and_then(|p| p.offset),
+            limit: input.pagination.and_then(|p| p.limit),
+            default_limit: Some(Self::DEFAULT_LIST_LIMIT),
+            max_limit: Some(Self::MAX_LIST_LIMIT),
+            items: &user_ids,
+        })?;
+
+        Ok(PaginatedData {
+            total: paginated_ids.total,
+            next_offset: paginated_ids.next_offset,
+            items: paginated_ids
+                .items
+                .into_iter()
+                .flat_map(|id| self.user_repository.get(&UserKey(id)))
+                .collect(),
+        })
+    }
+
+    /// Returns the logged admin actions, the most recent action first.
+    pub fn list_admin_actions(
+        &self,
+        input: ListAdminActionsInput,
+        ctx: &CallContext,
+    ) -> ServiceResult<PaginatedData<AdminAction>> {
+        self.assert_controller(ctx, "list_admin_actions")?;
+
+        let keys = self.admin_action_repository.find_keys_most_recent_first();
+        let paginated_keys = paginated_items(PaginatedItemsArgs {
+            offset: input.pagination.to_owned().and_then(|p| p.offset),
+            limit: input.pagination.and_then(|p| p.limit),
+            default_limit: Some(Self::DEFAULT_LIST_LIMIT),
+            max_limit: Some(Self::MAX_LIST_LIMIT),
+            items: &keys,
+        })?;
+
+        Ok(PaginatedData {
+            total: paginated_keys.total,
+            next_offset: paginated_keys.next_offset,
+            items: paginated_keys
+                .items
+                .into_iter()
+                .flat_map(|key| self.admin_action_repository.get(&key))
+                .collect(),
+        })
+    }
+
+    fn assert_controller(&self, ctx: &CallContext, method: &str) -> ServiceResult<()> {
+        if !ctx.is_controller() {
+            Err(CanisterError::Forbidden {
+                method: method.to_string(),
+            })?
+        }
+
+        Ok(())
+    }
+
+    fn get_user(&self, user_id: String) -> ServiceResult<User> {
+        let user_id = HelperMapper::to_uuid(user_id)?;
+
+        let user = self
+            .user_repository
+            .get(&UserKey(*user_id.as_bytes()))
+            .ok_or(UserError::NotFound {
+                user: user_id.hyphenated().to_string(),
+            })?;
+
+        Ok(user)
+    }
+
+    fn log_action(&self, ctx: &CallContext, kind: AdminActionKind) {
+        let action = AdminAction::new(ctx.caller(), kind);
+
+        self.admin_action_repository
+            .insert(action.performed_at, action);
+    }
+}
+
+fn to_quota(quota: u64) -> ServiceResult<usize> {
+    let quota = usize::try_from(quota).map_err(|_| UserError::ValidationError {
+        info: format!("The deploy quota {} is too large", quota),
+    })?;
+
+    Ok(quota)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ic_cdk::TEST_CONTROLLER_ID;
+    use crate::models::{user_model_utils::mock_user, CanDeployStation};
+    use candid::Principal;
+    use control_panel_api::{PaginationInput, UserDeployQuotaDTO, UserSubscriptionStatusDTO};
+    use uuid::Uuid;
+
+    fn controller() -> CallContext {
+        CallContext::new(TEST_CONTROLLER_ID)
+    }
+
+    fn insert_pending_user(email: &str) -> User {
+        let mut user = mock_user();
+        user.subscription_status = UserSubscriptionStatus::Pending(email.to_string());
+        USER_REPOSITORY.insert(user.to_key(), user.clone());
+
+        user
+    }
+
+    #[test]
+    fn only_controllers_can_manage_users() {
+        let ctx = CallContext::new(Principal::from_slice(&[1; 29]));
+
+        assert!(USER_ADMIN_SERVICE
+            .list_users(
+                ListUsersInput {
+                    subscription_status: None,
+                    pagination: None,
+                },
+                &ctx
+            )
+            .is_err());
+        assert!(USER_ADMIN_SERVICE
+            .export_waitlist(ExportWaitlistInput { pagination: None }, &ctx)
+            .is_err());
+        assert!(USER_ADMIN_SERVICE
+            .list_admin_actions(ListAdminActionsInput { pagination: None }, &ctx)
+            .is_err());
+    }
+
+    #[test]
+    fn approves_waitlisted_users_and_logs_the_actions() {
+        let approved_user = insert_pending_user("john@example.com");
+        let denied_user = insert_pending_user("jane@example.com");
+
+        let waitlist = USER_ADMIN_SERVICE
+            .export_waitlist(ExportWaitlistInput { pagination: None }, &controller())
+            .unwrap();
+        assert_eq!(waitlist.total, 2);
+        assert_eq!(waitlist.items.len(), 2);
+
+        let first_page = USER_ADMIN_SERVICE
+            .export_waitlist(
+                ExportWaitlistInput {
+                    pagination: Some(PaginationInput {
+                        offset: None,
+                        limit: Some(1),
+                    }),
+                },
+                &controller(),
+            )
+            .unwrap();
+        assert_eq!(first_page.items.len(), 1);
+        assert_eq!(first_page.next_offset, Some(1));
+
+        for (user, subscription_status) in [
+            (&approved_user, UserSubscriptionStatusDTO::Approved),
+            (&denied_user, UserSubscriptionStatusDTO::Denylisted),
+        ] {
+            USER_ADMIN_SERVICE
+                .set_user_subscription_status(
+                    SetUserSubscriptionStatusInput {
+                        user_id: Uuid::from_bytes(user.id).hyphenated().to_string(),
+                        subscription_status,
+                    },
+                    &controller(),
+                )
+                .unwrap();
+        }
+
+        let pending_users = USER_ADMIN_SERVICE
+            .list_users(
+                ListUsersInput {
+                    subscription_status: Some(UserSubscriptionStatusDTO::Pending),
+                    pagination: None,
+                },
+                &controller(),
+            )
+            .unwrap();
+        assert_eq!(pending_users.total, 0);
+
+        let denied_user = USER_REPOSITORY.get(&denied_user.to_key()).unwrap();
+        assert_eq!(
+            denied_user.can_deploy_station(),
+            CanDeployStation::NotAllowed(UserSubscriptionStatus::Denylisted)
+        );
+
+        let actions = USER_ADMIN_SERVICE
+            .list_admin_actions(ListAdminActionsInput { pagination: None }, &controller())
+            .unwrap();
+        assert_eq!(actions.total, 2);
+        assert!(matches!(
+            actions.items[0].kind,
+            AdminActionKind::SetUserSubscriptionStatus {
+                subscription_status: UserSubscriptionStatus::Denylisted,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn users_cannot_be_moved_back_to_the_waitlist() {
+        let user = mock_user();
+        USER_REPOSITORY.insert(user.to_key(), user.clone());
+
+        assert!(USER_ADMIN_SERVICE
+            .set_user_subscription_status(
+                SetUserSubscriptionStatusInput {
+                    user_id: Uuid::from_bytes(user.id).hyphenated().to_string(),
+                    subscription_status: UserSubscriptionStatusDTO::Pending,
+                },
+                &controller(),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn sets_user_deploy_quota() {
+        let user = mock_user();
+        USER_REPOSITORY.insert(user.to_key(), user.clone());
+
+        let user = USER_ADMIN_SERVICE
+            .set_user_deploy_quota(
+                SetUserDeployQuotaInput {
+                    user_id: Uuid::from_bytes(user.id).hyphenated().to_string(),
+                    quota: UserDeployQuotaDTO {
+                        max_deployed_stations: Some(0),
+                        max_deployed_stations_per_day: 5,
+                    },
+                },
+                &controller(),
+            )
+            .unwrap();
+
+        assert_eq!(user.get_max_deployed_stations(), Some(0));
+        assert_eq!(user.get_max_deployed_stations_per_day(), 5);
+        assert_eq!(user.can_deploy_station(), CanDeployStation::QuotaExceeded);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
     AllowDTO, CanisterInstallMode, ChangeMetadataDTO, CycleObtainStrategyInput, MetadataDTO,
-    PaginationInput, RequestPolicyRuleDTO, Sha256HashDTO, SortDirection, TimestampRfc3339, UuidDTO,
-    ValidationMethodResourceTargetDTO,
+    PaginationInput, RequestPolicyRuleDTO, ResolvedWasmModuleRegistryEntryDTO, Sha256HashDTO,
+    SortDirection, TimestampRfc3339, UuidDTO, ValidationMethodResourceTargetDTO,
+    WasmModuleRegistryReferenceDTO,
 };
 use candid::{CandidType, Deserialize, Nat, Principal};
 use orbit_essentials::cmc::SubnetSelection;
@@ -17,6 +18,52 @@ pub struct CanisterStatusInput {
     pub canister_id: Principal,
 }
 
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct GetExternalCanisterCyclesHistoryInput {
+    pub canister_id: Principal,
+    /// Only uses the samples taken in the given time range, bounds are inclusive.
+    ///
+    /// Defaults to the week before `to_dt`.
+    pub from_dt: Option<TimestampRfc3339>,
+    pub to_dt: Option<TimestampRfc3339>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct ExternalCanisterCyclesSampleDTO {
+    pub sampled_at: TimestampRfc3339,
+    pub cycles: u64,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct ExternalCanisterTopUpDTO {
+    pub detected_at: TimestampRfc3339,
+    pub cycles: u64,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct GetExternalCanisterCyclesHistoryResponse {
+    pub samples: Vec<ExternalCanisterCyclesSampleDTO>,
+    pub top_ups: Vec<ExternalCanisterTopUpDTO>,
+    pub burn_rate_cycles_per_day: Option<u64>,
+    pub projected_runway_secs: Option<u64>,
+    pub projected_depletion_dt: Option<TimestampRfc3339>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct QueryExternalCanisterInput {
+    pub canister_id: Principal,
+    pub method_name: String,
+    #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
+    pub arg: Option<Vec<u8>>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct QueryExternalCanisterResponse {
+    #[serde(with = "serde_bytes")]
+    pub reply: Vec<u8>,
+    pub reply_candid: Option<String>,
+}
+
 // Taken from https://internetcomputer.org/docs/current/references/ic-interface-spec/#ic-create_canister
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone, Default)]
 pub enum LogVisibility {
@@ -131,6 +178,14 @@ pub struct ChangeExternalCanisterOperationInput {
     pub module_extra_chunks: Option<WasmModuleExtraChunks>,
     #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
     pub arg: Option<Vec<u8>>,
+    pub rollback: Option<ExternalCanisterRollbackInput>,
+    pub module_registry: Option<WasmModuleRegistryReferenceDTO>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct ExternalCanisterRollbackInput {
+    pub replace_snapshot: Option<String>,
+    pub health_check_method: Option<String>,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -139,6 +194,59 @@ pub struct ChangeExternalCanisterOperationDTO {
     pub mode: CanisterInstallMode,
     pub module_checksum: Sha256HashDTO,
     pub arg_checksum: Option<Sha256HashDTO>,
+    pub rollback: Option<ExternalCanisterRollbackInput>,
+    pub rollback_snapshot_id: Option<String>,
+    pub module_registry_entry: Option<ResolvedWasmModuleRegistryEntryDTO>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub enum ExternalCanisterFleetTargetDTO {
+    Labels(Vec<String>),
+    CanisterIds(Vec<Principal>),
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct ExternalCanisterFleetArgInput {
+    pub canister_id: Principal,
+    #[serde(with = "serde_bytes")]
+    pub arg: Vec<u8>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct UpgradeExternalCanisterFleetOperationInput {
+    pub target: ExternalCanisterFleetTargetDTO,
+    pub mode: CanisterInstallMode,
+    #[serde(with = "serde_bytes")]
+    pub module: Vec<u8>,
+    pub module_extra_chunks: Option<WasmModuleExtraChunks>,
+    #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
+    pub arg: Option<Vec<u8>>,
+    pub canister_args: Vec<ExternalCanisterFleetArgInput>,
+    pub canary_canister_ids: Vec<Principal>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub enum ExternalCanisterUpgradeStatusDTO {
+    Pending,
+    Completed,
+    Failed { reason: String },
+    Skipped,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct ExternalCanisterUpgradeOutcomeDTO {
+    pub canister_id: Principal,
+    pub canary: bool,
+    pub arg_checksum: Option<Sha256HashDTO>,
+    pub status: ExternalCanisterUpgradeStatusDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct UpgradeExternalCanisterFleetOperationDTO {
+    pub target: ExternalCanisterFleetTargetDTO,
+    pub mode: CanisterInstallMode,
+    pub module_checksum: Sha256HashDTO,
+    pub outcomes: Vec<ExternalCanisterUpgradeOutcomeDTO>,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -166,6 +274,35 @@ pub enum ConfigureExternalCanisterOperationKindDTO {
     SoftDelete,
     Delete,
     NativeSettings(DefiniteCanisterSettingsInput),
+    BackupSchedule(Option<ExternalCanisterBackupScheduleInput>),
+    CandidInterface(Option<String>),
+    AddControllers(Vec<Principal>),
+    RemoveControllers(Vec<Principal>),
+    ReleaseControl(Vec<Principal>),
+    ReadOnlyMethods(Vec<String>),
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct ExternalCanisterBackupScheduleInput {
+    pub interval_secs: Option<u64>,
+    pub before_change: bool,
+    pub retention_count: u64,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct ExternalCanisterBackupFailureDTO {
+    pub failed_at: TimestampRfc3339,
+    pub reason: String,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct ExternalCanisterBackupDTO {
+    pub schedule: ExternalCanisterBackupScheduleInput,
+    /// The hex encoded ids of the backup snapshots, ordered from the oldest to the newest.
+    pub snapshot_ids: Vec<String>,
+    pub last_backup_at: Option<TimestampRfc3339>,
+    pub last_failure: Option<ExternalCanisterBackupFailureDTO>,
+    pub next_backup_at: Option<TimestampRfc3339>,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -189,8 +326,10 @@ pub struct CallExternalCanisterOperationDTO {
     pub execution_method: CanisterMethodDTO,
     pub arg_checksum: Option<Sha256HashDTO>,
     pub arg_rendering: Option<String>,
+    pub arg_candid: Option<String>,
     pub execution_method_cycles: Option<u64>,
     pub execution_method_reply: Option<Vec<u8>>,
+    pub execution_method_reply_candid: Option<String>,
     #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
     pub arg: Option<Vec<u8>>,
 }
@@ -298,6 +437,8 @@ pub struct ExternalCanisterDTO {
     pub created_at: TimestampRfc3339,
     pub modified_at: Option<TimestampRfc3339>,
     pub monitoring: Option<MonitorExternalCanisterStartInput>,
+    pub backup: Option<ExternalCanisterBackupDTO>,
+    pub read_only_methods: Vec<String>,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -323,6 +464,7 @@ pub struct ExternalCanisterCallerPrivilegesDTO {
     pub canister_id: Principal,
     pub can_change: bool,
     pub can_fund: bool,
+    pub can_read_logs: bool,
     pub can_call: Vec<ExternalCanisterCallerMethodPrivilegesDTO>,
 }
 
@@ -330,6 +472,7 @@ pub struct ExternalCanisterCallerPrivilegesDTO {
 pub struct GetExternalCanisterResponse {
     pub canister: ExternalCanisterDTO,
     pub privileges: ExternalCanisterCallerPrivilegesDTO,
+    pub candid_interface: Option<String>,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -344,6 +487,7 @@ pub struct ListExternalCanistersInput {
     pub states: Option<Vec<ExternalCanisterStateDTO>>,
     pub paginate: Option<PaginationInput>,
     pub sort_by: Option<ListExternalCanistersSortInput>,
+    pub search_term: Option<String>,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
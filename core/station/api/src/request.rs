@@ -16,7 +16,8 @@ use crate::{
     EditAssetOperationDTO, EditAssetOperationInput, EditNamedRuleOperationDTO,
     EditNamedRuleOperationInput, EditPermissionOperationDTO, EditPermissionOperationInput,
     EditUserGroupOperationDTO, EditUserGroupOperationInput, EditUserOperationDTO,
-    EditUserOperationInput, FundExternalCanisterOperationDTO, FundExternalCanisterOperationInput,
+    EditUserOperationInput, FreezeStationOperationDTO, FreezeStationOperationInput,
+    FundExternalCanisterOperationDTO, FundExternalCanisterOperationInput,
     ManageSystemInfoOperationDTO, ManageSystemInfoOperationInput, PaginationInput,
     PruneExternalCanisterOperationDTO, PruneExternalCanisterOperationInput,
     RemoveAddressBookEntryOperationDTO, RemoveAddressBookEntryOperationInput,
@@ -27,7 +28,8 @@ use crate::{
     SetDisasterRecoveryOperationDTO, SetDisasterRecoveryOperationInput,
     SnapshotExternalCanisterOperationDTO, SnapshotExternalCanisterOperationInput, SortDirection,
     SystemRestoreOperationDTO, SystemRestoreOperationInput, SystemUpgradeOperationDTO,
-    SystemUpgradeOperationInput, UuidDTO,
+    SystemUpgradeOperationInput, UnfreezeStationOperationDTO, UnfreezeStationOperationInput,
+    UpgradeExternalCanisterFleetOperationDTO, UpgradeExternalCanisterFleetOperationInput, UuidDTO,
 };
 use candid::{CandidType, Deserialize, Principal};
 
@@ -103,6 +105,9 @@ pub enum RequestOperationDTO {
     AddNamedRule(Box<AddNamedRuleOperationDTO>),
     EditNamedRule(Box<EditNamedRuleOperationDTO>),
     RemoveNamedRule(Box<RemoveNamedRuleOperationDTO>),
+    FreezeStation(Box<FreezeStationOperationDTO>),
+    UnfreezeStation(Box<UnfreezeStationOperationDTO>),
+    UpgradeExternalCanisterFleet(Box<UpgradeExternalCanisterFleetOperationDTO>),
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -141,6 +146,9 @@ pub enum RequestOperationInput {
     AddNamedRule(AddNamedRuleOperationInput),
     EditNamedRule(EditNamedRuleOperationInput),
     RemoveNamedRule(RemoveNamedRuleOperationInput),
+    FreezeStation(FreezeStationOperationInput),
+    UnfreezeStation(UnfreezeStationOperationInput),
+    UpgradeExternalCanisterFleet(UpgradeExternalCanisterFleetOperationInput),
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -179,6 +187,9 @@ pub enum RequestOperationTypeDTO {
     AddNamedRule,
     EditNamedRule,
     RemoveNamedRule,
+    FreezeStation,
+    UnfreezeStation,
+    UpgradeExternalCanisterFleet,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -217,6 +228,9 @@ pub enum ListRequestsOperationTypeDTO {
     AddNamedRule,
     EditNamedRule,
     RemoveNamedRule,
+    FreezeStation,
+    UnfreezeStation,
+    UpgradeExternalCanisterFleet,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -299,11 +313,28 @@ pub struct GetRequestInput {
     pub with_full_info: Option<bool>,
 }
 
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct RequestFieldDiffDTO {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub current: Option<String>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct RequestDiffDTO {
+    pub fields: Vec<RequestFieldDiffDTO>,
+    pub snapshot_taken_at: TimestampRfc3339,
+    pub executed_at: Option<TimestampRfc3339>,
+    pub is_stale: bool,
+}
+
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
 pub struct GetRequestResponse {
     pub request: RequestDTO,
     pub privileges: RequestCallerPrivilegesDTO,
     pub additional_info: RequestAdditionalInfoDTO,
+    pub diff: Option<RequestDiffDTO>,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -329,6 +360,17 @@ pub struct ListRequestsInput {
     pub with_evaluation_results: bool,
     pub deduplication_keys: Option<Vec<String>>,
     pub tags: Option<Vec<String>>,
+    pub search_term: Option<String>,
+    pub resources: Option<Vec<ListRequestsResourceFilterDTO>>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub enum ListRequestsResourceFilterDTO {
+    Account(UuidDTO),
+    User(UuidDTO),
+    ExternalCanister(Principal),
+    Asset(UuidDTO),
+    RequestPolicy(UuidDTO),
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -18,6 +18,7 @@ pub enum RequestSpecifierDTO {
     SetDisasterRecovery,
     ChangeExternalCanister(ExternalCanisterIdDTO),
     FundExternalCanister(ExternalCanisterIdDTO),
+    ReleaseExternalCanisterControl(ExternalCanisterIdDTO),
     CreateExternalCanister,
     CallExternalCanister(CallExternalCanisterResourceTargetDTO),
     EditPermission(ResourceSpecifierDTO),
@@ -34,6 +35,8 @@ pub enum RequestSpecifierDTO {
     AddNamedRule,
     EditNamedRule(ResourceIdsDTO),
     RemoveNamedRule(ResourceIdsDTO),
+    FreezeStation,
+    UnfreezeStation,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -83,6 +83,8 @@ pub enum ExternalCanisterResourceActionDTO {
     Fund(ExternalCanisterIdDTO),
     Change(ExternalCanisterIdDTO),
     Call(CallExternalCanisterResourceTargetDTO),
+    ReadLogs(ExternalCanisterIdDTO),
+    ReleaseControl(ExternalCanisterIdDTO),
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -19,6 +19,32 @@ pub struct SystemInfoDTO {
     pub cycle_obtain_strategy: CycleObtainStrategyDTO,
     pub max_station_backup_snapshots: u64,
     pub max_upgrader_backup_snapshots: u64,
+    pub request_archive: Option<RequestArchiveDTO>,
+    pub freeze: Option<StationFreezeDTO>,
+    pub available_upgrade: Option<AvailableStationUpgradeDTO>,
+    pub control_panel_id: Option<Principal>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug)]
+pub struct AvailableStationUpgradeDTO {
+    pub registry_canister_id: Principal,
+    pub registry_entry_id: UuidDTO,
+    pub name: String,
+    pub version: String,
+    pub announced_at: TimestampRfc3339,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug)]
+pub struct StationFreezeDTO {
+    pub request_id: UuidDTO,
+    pub reason: Option<String>,
+    pub frozen_at: TimestampRfc3339,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug)]
+pub struct RequestArchiveDTO {
+    pub canister_id: Principal,
+    pub min_age_secs: Option<u64>,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -58,6 +84,23 @@ pub struct ManageSystemInfoOperationInput {
     pub cycle_obtain_strategy: Option<CycleObtainStrategyInput>,
     pub max_station_backup_snapshots: Option<u64>,
     pub max_upgrader_backup_snapshots: Option<u64>,
+    pub request_archive: Option<RequestArchiveConfigInput>,
+    pub control_panel_id: Option<Principal>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct DeployRequestArchiveInput {
+    #[serde(with = "serde_bytes")]
+    pub wasm_module: Vec<u8>,
+    pub initial_cycles: Option<u128>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct RequestArchiveConfigInput {
+    /// Finalized requests older than this are moved to the archive, archival is paused if not set.
+    pub min_age_secs: Option<u64>,
+    /// Deploys the archive canister, or upgrades the existing archive canister to the provided wasm module.
+    pub deploy: Option<DeployRequestArchiveInput>,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Clone, Debug)]
@@ -243,6 +286,7 @@ pub struct SystemUpgradeOperationInput {
     #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
     pub arg: Option<Vec<u8>>,
     pub take_backup_snapshot: Option<bool>,
+    pub module_registry: Option<WasmModuleRegistryReferenceDTO>,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -251,6 +295,31 @@ pub struct SystemUpgradeOperationDTO {
     pub module_checksum: Sha256HashDTO,
     pub arg_checksum: Option<Sha256HashDTO>,
     pub take_backup_snapshot: Option<bool>,
+    pub module_registry_entry: Option<ResolvedWasmModuleRegistryEntryDTO>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum WasmModuleRegistryVersionDTO {
+    Version(String),
+    Tag(String),
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WasmModuleRegistryReferenceDTO {
+    pub registry_canister_id: Principal,
+    pub name: String,
+    pub version: WasmModuleRegistryVersionDTO,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedWasmModuleRegistryEntryDTO {
+    pub registry_canister_id: Principal,
+    pub name: String,
+    pub entry_id: UuidDTO,
+    pub version: String,
+    pub wasm_artifact_id: UuidDTO,
+    pub wasm_artifact_hash: Sha256HashDTO,
+    pub verified_publisher: Option<Principal>,
 }
 
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
@@ -258,6 +327,13 @@ pub struct NotifyFailedStationUpgradeInput {
     pub reason: String,
 }
 
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct NotifyStationUpgradeAvailableInput {
+    pub registry_entry_id: UuidDTO,
+    pub name: String,
+    pub version: String,
+}
+
 #[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
 pub enum SystemRestoreTargetDTO {
     RestoreStation,
@@ -274,3 +350,21 @@ pub struct SystemRestoreOperationInput {
 pub struct SystemRestoreOperationDTO {
     pub input: SystemRestoreOperationInput,
 }
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct FreezeStationOperationInput {
+    pub reason: Option<String>,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct FreezeStationOperationDTO {
+    pub input: FreezeStationOperationInput,
+}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct UnfreezeStationOperationInput {}
+
+#[derive(CandidType, serde::Serialize, Deserialize, Debug, Clone)]
+pub struct UnfreezeStationOperationDTO {
+    pub input: UnfreezeStationOperationInput,
+}
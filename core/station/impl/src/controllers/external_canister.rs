@@ -2,18 +2,23 @@ use crate::{
     core::ic_cdk::api::trap,
     core::middlewares::{authorize, call_context},
     models::resource::{ExternalCanisterId, ExternalCanisterResourceAction, Resource},
-    services::{ExternalCanisterService, EXTERNAL_CANISTER_SERVICE},
+    services::{
+        ExternalCanisterCyclesService, ExternalCanisterService, EXTERNAL_CANISTER_CYCLES_SERVICE,
+        EXTERNAL_CANISTER_SERVICE,
+    },
 };
 use ic_cdk::api::management_canister::main::CanisterStatusResponse;
 use ic_cdk_macros::{query, update};
 use lazy_static::lazy_static;
 use orbit_essentials::api::ApiResult;
+use orbit_essentials::utils::rfc3339_to_timestamp;
 use orbit_essentials::with_middleware;
 use station_api::{
     CanisterSnapshotsInput, CanisterStatusInput, ExternalCanisterCallerPrivilegesDTO,
+    GetExternalCanisterCyclesHistoryInput, GetExternalCanisterCyclesHistoryResponse,
     GetExternalCanisterFiltersInput, GetExternalCanisterFiltersResponse, GetExternalCanisterInput,
     GetExternalCanisterResponse, ListExternalCanistersInput, ListExternalCanistersResponse,
-    Snapshot,
+    QueryExternalCanisterInput, QueryExternalCanisterResponse, Snapshot,
 };
 use std::sync::Arc;
 
@@ -31,6 +36,13 @@ async fn canister_snapshots(input: CanisterSnapshotsInput) -> ApiResult<Vec<Snap
     CONTROLLER.canister_snapshots(input).await
 }
 
+#[update(name = "query_external_canister")]
+async fn query_external_canister(
+    input: QueryExternalCanisterInput,
+) -> ApiResult<QueryExternalCanisterResponse> {
+    CONTROLLER.query_external_canister(input).await
+}
+
 #[query(name = "get_external_canister")]
 async fn get_external_canister(
     input: GetExternalCanisterInput,
@@ -45,6 +57,13 @@ async fn list_external_canisters(
     CONTROLLER.list_external_canisters(input).await
 }
 
+#[query(name = "get_external_canister_cycles_history")]
+async fn get_external_canister_cycles_history(
+    input: GetExternalCanisterCyclesHistoryInput,
+) -> ApiResult<GetExternalCanisterCyclesHistoryResponse> {
+    CONTROLLER.get_external_canister_cycles_history(input).await
+}
+
 #[query(name = "get_external_canister_filters")]
 async fn get_external_canister_filters(
     input: GetExternalCanisterFiltersInput,
@@ -54,18 +73,27 @@ async fn get_external_canister_filters(
 
 // Controller initialization and implementation.
 lazy_static! {
-    static ref CONTROLLER: ExternalCanisterController =
-        ExternalCanisterController::new(Arc::clone(&EXTERNAL_CANISTER_SERVICE));
+    static ref CONTROLLER: ExternalCanisterController = ExternalCanisterController::new(
+        Arc::clone(&EXTERNAL_CANISTER_SERVICE),
+        Arc::clone(&EXTERNAL_CANISTER_CYCLES_SERVICE),
+    );
 }
 
 #[derive(Debug, Default)]
 pub struct ExternalCanisterController {
     canister_service: Arc<ExternalCanisterService>,
+    cycles_service: Arc<ExternalCanisterCyclesService>,
 }
 
 impl ExternalCanisterController {
-    fn new(canister_service: Arc<ExternalCanisterService>) -> Self {
-        Self { canister_service }
+    fn new(
+        canister_service: Arc<ExternalCanisterService>,
+        cycles_service: Arc<ExternalCanisterCyclesService>,
+    ) -> Self {
+        Self {
+            canister_service,
+            cycles_service,
+        }
     }
 
     #[with_middleware(guard = authorize(&call_context(), &[Resource::from(&input)]))]
@@ -86,6 +114,40 @@ impl ExternalCanisterController {
             .map(|snapshots| snapshots.into_iter().map(|s| s.into()).collect())
     }
 
+    #[with_middleware(guard = authorize(&call_context(), &[Resource::from(&input)]))]
+    async fn query_external_canister(
+        &self,
+        input: QueryExternalCanisterInput,
+    ) -> ApiResult<QueryExternalCanisterResponse> {
+        let reply = self
+            .canister_service
+            .query_external_canister(input.canister_id, input.method_name.clone(), input.arg)
+            .await?;
+        let reply_candid = self
+            .canister_service
+            .get_candid_service(&input.canister_id)
+            .and_then(|service| service.render_reply(&input.method_name, &reply).ok());
+
+        Ok(QueryExternalCanisterResponse {
+            reply,
+            reply_candid,
+        })
+    }
+
+    #[with_middleware(guard = authorize(&call_context(), &[Resource::ExternalCanister(ExternalCanisterResourceAction::Read(ExternalCanisterId::Canister(input.canister_id)))]))]
+    async fn get_external_canister_cycles_history(
+        &self,
+        input: GetExternalCanisterCyclesHistoryInput,
+    ) -> ApiResult<GetExternalCanisterCyclesHistoryResponse> {
+        let forecast = self.cycles_service.get_cycles_forecast(
+            &input.canister_id,
+            input.from_dt.as_deref().map(rfc3339_to_timestamp),
+            input.to_dt.as_deref().map(rfc3339_to_timestamp),
+        )?;
+
+        Ok(forecast.into())
+    }
+
     #[with_middleware(guard = authorize(&call_context(), &[Resource::ExternalCanister(ExternalCanisterResourceAction::Read(ExternalCanisterId::Canister(input.canister_id)))]))]
     async fn get_external_canister(
         &self,
@@ -108,11 +170,16 @@ impl ExternalCanisterController {
                 &external_canister.canister_id,
                 &ctx,
             );
+        let candid_interface = self
+            .canister_service
+            .get_candid_interface(&external_canister.canister_id)
+            .map(|interface| interface.candid);
 
         Ok(GetExternalCanisterResponse {
             canister: external_canister
                 .into_dto(external_canister_permissions, external_canister_policies),
             privileges: caller_privileges.into(),
+            candid_interface,
         })
     }
 
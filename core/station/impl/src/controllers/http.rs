@@ -1,9 +1,11 @@
-use crate::{core::ic_cdk::api::canister_balance, SERVICE_NAME};
+use crate::{
+    core::ic_cdk::api::canister_balance, services::EXTERNAL_CANISTER_CYCLES_SERVICE, SERVICE_NAME,
+};
 use ic_cdk_macros::query;
 use lazy_static::lazy_static;
 use orbit_essentials::api::{HeaderField, HttpRequest, HttpResponse};
 use orbit_essentials::http::{add_skip_certification_headers, not_found, parse_path};
-use orbit_essentials::metrics::with_metrics_registry;
+use orbit_essentials::metrics::{labels, with_metrics_registry};
 
 // Canister entrypoints for the controller.
 #[query(name = "http_request", decoding_quota = 10000)]
@@ -55,6 +57,58 @@ impl HttpController {
                 .set(canister_balance() as f64);
         });
 
+        for (external_canister, forecast) in
+            EXTERNAL_CANISTER_CYCLES_SERVICE.list_cycles_forecasts()
+        {
+            let canister_id = external_canister.canister_id.to_text();
+            let labels = labels! {
+                "canister_id" => canister_id.as_str(),
+                "name" => external_canister.name.as_str()
+            };
+            let label_names = &["canister_id", "name"];
+
+            with_metrics_registry(SERVICE_NAME, |registry| {
+                if let Some((_, cycles)) = forecast.samples.last() {
+                    registry
+                        .gauge_vec_mut(
+                            "external_canister_cycles_balance",
+                            "latest cycles balance sampled for the external canister",
+                            label_names,
+                        )
+                        .with(&labels)
+                        .set(*cycles as f64);
+                }
+                if let Some(burn_rate) = forecast.burn_rate_cycles_per_day {
+                    registry
+                        .gauge_vec_mut(
+                            "external_canister_cycles_burn_rate_per_day",
+                            "average cycles burned per day by the external canister over the last week",
+                            label_names,
+                        )
+                        .with(&labels)
+                        .set(burn_rate as f64);
+                }
+                if let Some(runway_secs) = forecast.projected_runway_secs {
+                    registry
+                        .gauge_vec_mut(
+                            "external_canister_cycles_runway_seconds",
+                            "seconds left until the external canister runs out of cycles at its burn rate",
+                            label_names,
+                        )
+                        .with(&labels)
+                        .set(runway_secs as f64);
+                }
+                registry
+                    .gauge_vec_mut(
+                        "external_canister_cycles_top_ups",
+                        "number of top-ups of the external canister over the last week",
+                        label_names,
+                    )
+                    .with(&labels)
+                    .set(forecast.top_ups.len() as f64);
+            });
+        }
+
         with_metrics_registry(SERVICE_NAME, |registry| {
             registry.export_metrics_as_http_response()
         })
@@ -1,13 +1,15 @@
 use crate::{
+    core::authorization::Authorization,
     core::ic_cdk::api::call::arg_data_raw_size,
     core::ic_cdk::api::{time, trap},
     core::limiter::Limiter,
     core::middlewares::{authorize, call_context, use_canister_call_metric},
     core::CallContext,
-    errors::{RequestError, RequestExecuteError},
+    errors::{AuthorizationError, RequestError, RequestExecuteError},
     mappers::HelperMapper,
     models::rate_limiter::RequestRateLimiterKey,
-    models::resource::{RequestResourceAction, Resource},
+    models::resource::{RequestResourceAction, Resource, ResourceId},
+    models::RequestCallerPrivileges,
     services::{RequestService, REQUEST_SERVICE},
 };
 use ic_cdk_macros::{query, update};
@@ -25,14 +27,16 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, UNIX_EPOCH};
+use uuid::Uuid;
 
 // Canister entrypoints for the controller.
-#[query(name = "list_requests")]
+// Both are composite queries, since the requests that were moved to the request archive are fetched from it.
+#[query(name = "list_requests", composite = true)]
 async fn list_requests(input: ListRequestsInput) -> ApiResult<ListRequestsResponse> {
     CONTROLLER.list_requests(input).await
 }
 
-#[query(name = "get_request")]
+#[query(name = "get_request", composite = true)]
 async fn get_request(input: GetRequestInput) -> ApiResult<GetRequestResponse> {
     CONTROLLER.get_request(input).await
 }
@@ -183,12 +187,19 @@ impl RequestController {
         })
     }
 
-    #[with_middleware(guard = authorize(&call_context(), &[Resource::from(&input)]))]
+    // The read access to archived requests is checked after the request is fetched from the archive, since
+    // the local indexes that grant the default access to the requester and approvers do not have them.
     async fn get_request(&self, input: GetRequestInput) -> ApiResult<GetRequestResponse> {
         let ctx = &call_context();
-        let request = self
-            .request_service
-            .get_request(HelperMapper::to_uuid(input.request_id)?.as_bytes())?;
+        let request_id = *HelperMapper::to_uuid(input.request_id.clone())?.as_bytes();
+        let Ok(request) = self.request_service.get_request(&request_id) else {
+            return self
+                .get_archived_request(ctx, request_id, input.with_full_info)
+                .await;
+        };
+
+        authorize(ctx, &[Resource::from(&input)]);
+
         let privileges = self
             .request_service
             .get_caller_privileges_for_request(&request.id, ctx)
@@ -196,6 +207,7 @@ impl RequestController {
         let additional_info = self
             .request_service
             .get_request_additional_info(&request, true)?;
+        let diff = self.request_service.get_request_diff(&request);
 
         Ok(GetRequestResponse {
             request: match input.with_full_info {
@@ -204,6 +216,52 @@ impl RequestController {
             },
             privileges: privileges.into(),
             additional_info: additional_info.into(),
+            diff: diff.map(Into::into),
+        })
+    }
+
+    async fn get_archived_request(
+        &self,
+        ctx: &CallContext,
+        request_id: UUID,
+        with_full_info: Option<bool>,
+    ) -> ApiResult<GetRequestResponse> {
+        let archived_request = self
+            .request_service
+            .find_archived_request(&request_id)
+            .await?
+            .ok_or(RequestError::NotFound {
+                request_id: Uuid::from_bytes(request_id).hyphenated().to_string(),
+            })?;
+
+        if !Authorization::is_allowed_to_read_archived_request(ctx, &archived_request.request) {
+            return Err(AuthorizationError::Unauthorized {
+                resource: Resource::Request(RequestResourceAction::Read(ResourceId::Id(
+                    request_id,
+                )))
+                .to_string(),
+            }
+            .into());
+        }
+
+        let additional_info = self
+            .request_service
+            .get_archived_request_additional_info(&archived_request, true)?;
+
+        Ok(GetRequestResponse {
+            request: match with_full_info {
+                None | Some(false) => archived_request.request.to_dto(),
+                Some(true) => archived_request.request.to_dto_with_full_info(),
+            },
+            // archived requests are finalized, hence they can't be approved anymore
+            privileges: RequestCallerPrivileges {
+                id: request_id,
+                can_approve: false,
+            }
+            .into(),
+            additional_info: additional_info.into(),
+            // snapshots are not kept for archived requests
+            diff: None,
         })
     }
 
@@ -230,8 +288,32 @@ impl RequestController {
             additionals.push(RequestAdditionalInfoDTO::from(additional_info));
         }
 
+        for archived_request in &result.archived_items {
+            // archived requests are finalized, hence they can't be approved anymore
+            privileges.push(RequestCallerPrivilegesDTO::from(RequestCallerPrivileges {
+                id: archived_request.request.id,
+                can_approve: false,
+            }));
+            additionals.push(RequestAdditionalInfoDTO::from(
+                self.request_service.get_archived_request_additional_info(
+                    archived_request,
+                    with_evaluation_results,
+                )?,
+            ));
+        }
+
         Ok(ListRequestsResponse {
-            requests: result.items.into_iter().map(|p| p.to_dto()).collect(),
+            requests: result
+                .items
+                .into_iter()
+                .map(|p| p.to_dto())
+                .chain(
+                    result
+                        .archived_items
+                        .into_iter()
+                        .map(|archived_request| archived_request.request.to_dto()),
+                )
+                .collect(),
             next_offset: result.next_offset,
             total: result.total,
             privileges,
@@ -259,11 +341,13 @@ impl RequestController {
             let additional_info = self
                 .request_service
                 .get_request_additional_info(&request, true)?;
+            let diff = self.request_service.get_request_diff(&request);
 
             Ok(Some(GetRequestResponse {
                 request: request.to_dto(),
                 privileges: privileges.into(),
                 additional_info: additional_info.into(),
+                diff: diff.map(Into::into),
             }))
         } else {
             Ok(None)
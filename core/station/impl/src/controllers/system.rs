@@ -15,7 +15,8 @@ use orbit_essentials::api::ApiResult;
 use orbit_essentials::http::certified_data_for_skip_certification;
 use orbit_essentials::with_middleware;
 use station_api::{
-    HealthStatus, NotifyFailedStationUpgradeInput, SystemInfoResponse, SystemInstall, SystemUpgrade,
+    HealthStatus, NotifyFailedStationUpgradeInput, NotifyStationUpgradeAvailableInput,
+    SystemInfoResponse, SystemInstall, SystemUpgrade,
 };
 use std::sync::Arc;
 
@@ -95,6 +96,13 @@ async fn notify_failed_station_upgrade(input: NotifyFailedStationUpgradeInput) -
     CONTROLLER.notify_failed_station_upgrade(input).await
 }
 
+#[update(name = "notify_station_upgrade_available")]
+async fn notify_station_upgrade_available(
+    input: NotifyStationUpgradeAvailableInput,
+) -> ApiResult<()> {
+    CONTROLLER.notify_station_upgrade_available(input).await
+}
+
 // Controller initialization and implementation.
 lazy_static! {
     static ref CONTROLLER: SystemController = SystemController::new(Arc::clone(&SYSTEM_SERVICE));
@@ -170,12 +178,31 @@ impl SystemController {
             .notify_failed_station_upgrade(input.reason)
             .await
     }
+
+    // No authorization middleware as the caller is checked to be the control panel trusted by the
+    // station, which is the control panel that deployed it unless configured otherwise.
+    async fn notify_station_upgrade_available(
+        &self,
+        input: NotifyStationUpgradeAvailableInput,
+    ) -> ApiResult<()> {
+        let ctx = call_context();
+        if self.system_service.get_system_info().get_control_panel_id() != Some(&ctx.caller()) {
+            let err = AuthorizationError::Unauthorized {
+                resource: "notify_station_upgrade_available".to_string(),
+            };
+            return Err(err.into());
+        }
+
+        self.system_service
+            .notify_station_upgrade_available(ctx.caller(), input)
+            .await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::ic_cdk::next_time;
+    use crate::core::ic_cdk::{next_time, set_caller, TEST_CONTROLLER_ID};
     use crate::core::{read_system_info, write_system_info};
     use crate::models::request_test_utils::mock_request;
     use crate::models::{RequestStatus, SystemInfo};
@@ -183,6 +210,7 @@ mod tests {
     use crate::STABLE_MEMORY_VERSION;
     use candid::Principal;
     use orbit_essentials::repository::Repository;
+    use uuid::Uuid;
 
     #[tokio::test]
     async fn apply_migration_should_migrate_stable_memory_version() {
@@ -225,4 +253,68 @@ mod tests {
             STABLE_MEMORY_VERSION
         );
     }
+
+    #[tokio::test]
+    async fn only_the_trusted_control_panel_can_announce_station_upgrades() {
+        let control_panel = Principal::from_slice(&[7; 10]);
+        let mut system_info = SystemInfo::new(Principal::management_canister(), Vec::new());
+        system_info.set_control_panel_id(control_panel);
+        write_system_info(system_info);
+
+        let input = NotifyStationUpgradeAvailableInput {
+            registry_entry_id: Uuid::new_v4().hyphenated().to_string(),
+            name: "@orbit/station".to_string(),
+            version: "999.0.0".to_string(),
+        };
+
+        // the controllers of the station, such as the upgrader, can't announce upgrades
+        set_caller(TEST_CONTROLLER_ID);
+        assert!(CONTROLLER
+            .notify_station_upgrade_available(input.clone())
+            .await
+            .is_err());
+        assert!(read_system_info().get_available_upgrade().is_none());
+
+        set_caller(control_panel);
+        CONTROLLER
+            .notify_station_upgrade_available(input.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            read_system_info()
+                .get_available_upgrade()
+                .map(|upgrade| upgrade.version.clone()),
+            Some("999.0.0".to_string())
+        );
+
+        // stations can trust another control panel, e.g. if they were deployed before it was recorded
+        let other_control_panel = Principal::from_slice(&[8; 10]);
+        let mut system_info = read_system_info();
+        SystemService::do_update_system_info(
+            &mut system_info,
+            crate::models::ManageSystemInfoOperationInput {
+                name: None,
+                cycle_obtain_strategy: None,
+                max_station_backup_snapshots: None,
+                max_upgrader_backup_snapshots: None,
+                request_archive: None,
+                control_panel_id: Some(other_control_panel),
+            },
+        )
+        .await
+        .unwrap();
+        write_system_info(system_info);
+
+        assert!(CONTROLLER
+            .notify_station_upgrade_available(input.clone())
+            .await
+            .is_err());
+
+        set_caller(other_control_panel);
+        CONTROLLER
+            .notify_station_upgrade_available(input)
+            .await
+            .unwrap();
+    }
 }
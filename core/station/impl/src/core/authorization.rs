@@ -10,7 +10,7 @@ use crate::{
             NotificationResourceAction, RequestResourceAction, Resource, ResourceId,
             UserResourceAction,
         },
-        NotificationKey, User,
+        NotificationKey, Request, User,
     },
     repositories::{NOTIFICATION_REPOSITORY, REQUEST_REPOSITORY},
     services::permission::PERMISSION_SERVICE,
@@ -62,6 +62,29 @@ impl Authorization {
             false
         })
     }
+
+    /// Checks if the caller can read a request that was moved to the request archive.
+    ///
+    /// Archived requests are not in the local indexes that grant the default read access to their
+    /// requester and approvers, hence the participants are checked against the request itself.
+    pub fn is_allowed_to_read_archived_request(ctx: &CallContext, request: &Request) -> bool {
+        if let Some(user) = ctx.user() {
+            if user.is_active()
+                && (request.requested_by == user.id
+                    || request
+                        .approvals
+                        .iter()
+                        .any(|approval| approval.approver_id == user.id))
+            {
+                return true;
+            }
+        }
+
+        Self::is_allowed(
+            ctx,
+            &Resource::Request(RequestResourceAction::Read(ResourceId::Id(request.id))),
+        )
+    }
 }
 
 /// Checks if the user had access to the resource based on default rules (non-permission based).
@@ -141,6 +164,7 @@ mod tests {
         models::{
             account_test_utils,
             permission::{Allow, Permission},
+            request_test_utils,
             resource::{AccountResourceAction, ResourceAction},
             user_group_test_utils,
             user_test_utils::{self, mock_user},
@@ -398,4 +422,36 @@ mod tests {
             &Resource::User(UserResourceAction::Read(ResourceId::Any))
         ));
     }
+
+    #[tokio::test]
+    async fn participants_can_read_archived_requests() {
+        let test_context = setup();
+        let ctx = CallContext::new(test_context.finance_user.identities[0]);
+
+        // the archived requests are not stored in the request repository
+        let mut request = request_test_utils::mock_request();
+        request.requested_by = test_context.finance_user.id;
+        request.approvals = vec![];
+
+        assert!(Authorization::is_allowed_to_read_archived_request(
+            &ctx, &request
+        ));
+
+        request.requested_by = [3; 16];
+
+        assert!(!Authorization::is_allowed_to_read_archived_request(
+            &ctx, &request
+        ));
+
+        let permission = Permission::new(
+            Allow::users(vec![test_context.finance_user.id]),
+            Resource::Request(RequestResourceAction::Read(ResourceId::Any)),
+        );
+
+        PERMISSION_REPOSITORY.insert(permission.key(), permission.to_owned());
+
+        assert!(Authorization::is_allowed_to_read_archived_request(
+            &ctx, &request
+        ));
+    }
 }
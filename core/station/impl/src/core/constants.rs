@@ -42,3 +42,24 @@ pub const ACCOUNT_BALANCE_FRESHNESS_IN_MS: u64 = 3000;
 
 /// The default initial cycles balance to use when creating and deploying the upgrader canister.
 pub const DEFAULT_INITIAL_UPGRADER_CYCLES: u128 = 2_500_000_000_000;
+
+/// The default initial cycles balance to use when creating and deploying the request archive canister.
+pub const DEFAULT_INITIAL_REQUEST_ARCHIVE_CYCLES: u128 = 1_000_000_000_000;
+
+/// The minimum age a finalized request must have to be moved to the request archive, set to one day.
+pub const MIN_REQUEST_ARCHIVE_AGE_SECS: u64 = 24 * 60 * 60;
+
+/// The maximum number of requests moved to the archive canister in a single call.
+pub const REQUEST_ARCHIVE_BATCH_SIZE: usize = 100;
+
+/// The interval between runs of the request archival job.
+pub const REQUEST_ARCHIVE_JOB_INTERVAL_NS: u64 = 6 * 60 * 60 * 1_000_000_000;
+
+/// The interval between the cycles balance samplings of the monitored external canisters,
+/// a new sample is only recorded if the cycle monitor fetched a new balance in between.
+pub const EXTERNAL_CANISTER_CYCLES_SAMPLING_INTERVAL_NS: u64 = 60 * 60 * 1_000_000_000;
+
+/// The interval between the checks of the controllers of the external canisters for changes made
+/// outside of the station.
+pub const EXTERNAL_CANISTER_CONTROLLERS_RECONCILIATION_INTERVAL_NS: u64 =
+    6 * 60 * 60 * 1_000_000_000;
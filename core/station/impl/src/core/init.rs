@@ -165,6 +165,12 @@ lazy_static! {
             Allow::authenticated(),
             Resource::ExternalCanister(ExternalCanisterResourceAction::Fund(ExternalCanisterId::Any)),
         ),
+        (
+            Allow::authenticated(),
+            Resource::ExternalCanister(ExternalCanisterResourceAction::ReleaseControl(
+                ExternalCanisterId::Any,
+            )),
+        ),
         // assets
         (
             Allow::authenticated(),
@@ -245,6 +251,15 @@ pub fn default_policies(
             RequestSpecifier::ManageSystemInfo,
             RequestPolicyRule::NamedRule(admin_named_rule_id),
         ),
+        // a single admin can freeze the station in an emergency, unfreezing needs the admin quorum
+        (
+            RequestSpecifier::FreezeStation,
+            RequestPolicyRule::Quorum(UserSpecifier::Group(vec![*ADMIN_GROUP_ID]), 1),
+        ),
+        (
+            RequestSpecifier::UnfreezeStation,
+            RequestPolicyRule::NamedRule(admin_named_rule_id),
+        ),
         // accounts
         (
             RequestSpecifier::AddAccount,
@@ -316,6 +331,11 @@ pub fn default_policies(
             RequestSpecifier::FundExternalCanister(ExternalCanisterId::Any),
             RequestPolicyRule::NamedRule(regular_named_rule_id),
         ),
+        // the station can't regain the control of a canister once released
+        (
+            RequestSpecifier::ReleaseExternalCanisterControl(ExternalCanisterId::Any),
+            RequestPolicyRule::NamedRule(admin_named_rule_id),
+        ),
         // create, edit, and remove assets
         (
             RequestSpecifier::AddAsset,
@@ -31,6 +31,12 @@ pub const POLICY_RESOURCE_INDEX_MEMORY_ID: MemoryId = MemoryId::new(31);
 pub const REQUEST_EVALUATION_RESULT_MEMORY_ID: MemoryId = MemoryId::new(32);
 pub const EXTERNAL_CANISTER_MEMORY_ID: MemoryId = MemoryId::new(33);
 pub const NAMED_RULE_MEMORY_ID: MemoryId = MemoryId::new(34);
+pub const SEARCH_INDEX_MEMORY_ID: MemoryId = MemoryId::new(35);
+pub const REQUEST_SNAPSHOT_MEMORY_ID: MemoryId = MemoryId::new(36);
+pub const EXTERNAL_CANISTER_INTERFACE_MEMORY_ID: MemoryId = MemoryId::new(37);
+pub const EXTERNAL_CANISTER_CYCLES_MEMORY_ID: MemoryId = MemoryId::new(38);
+pub const EXTERNAL_CANISTER_CONTROLLERS_MEMORY_ID: MemoryId = MemoryId::new(39);
+pub const REQUEST_RESOURCE_TARGET_INDEX_MEMORY_ID: MemoryId = MemoryId::new(40);
 
 thread_local! {
   /// Static configuration of the canister.
@@ -1,5 +1,6 @@
 use candid::Principal;
 use orbit_essentials::types::UUID;
+use std::collections::BTreeSet;
 
 use super::authorization::Authorization;
 use super::CallContext;
@@ -116,6 +117,21 @@ pub(crate) fn format_unique_string(text: &str) -> String {
     deunicode::deunicode(text).to_lowercase().replace(' ', "")
 }
 
+/// The maximum number of characters of a search term, longer words are truncated.
+pub const MAX_SEARCH_TERM_LEN: usize = 32;
+
+/// Splits the text into the normalized terms used by the search index.
+///
+/// The text is transliterated to ASCII and lowercased, terms are split on any non alphanumeric character.
+pub(crate) fn to_search_terms(text: &str) -> BTreeSet<String> {
+    deunicode::deunicode(text)
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| term.chars().take(MAX_SEARCH_TERM_LEN).collect())
+        .collect()
+}
+
 /// The minimum principal value that can be used.
 pub const MIN_PRINCIPAL: Principal = Principal::from_slice(&[0; 29]);
 
@@ -142,6 +158,24 @@ pub fn max_string_of_size(size: &usize) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn search_terms_are_normalized() {
+        let terms = to_search_terms("Pay  the Café-bill, ÜBER fast!");
+
+        assert_eq!(
+            terms.into_iter().collect::<Vec<_>>(),
+            vec!["bill", "cafe", "fast", "pay", "the", "uber"]
+        );
+    }
+
+    #[test]
+    fn search_terms_are_truncated() {
+        let terms = to_search_terms(&"a".repeat(MAX_SEARCH_TERM_LEN + 10));
+
+        assert_eq!(terms.len(), 1);
+        assert_eq!(terms.first().unwrap().len(), MAX_SEARCH_TERM_LEN);
+    }
+
     #[test]
     fn correctly_calculates_min_threshold() {
         assert_eq!(calculate_minimum_threshold(&Percentage(1), &1), 1);
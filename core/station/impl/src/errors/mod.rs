@@ -62,6 +62,9 @@ pub use change_canister::*;
 mod external_canister;
 pub use external_canister::*;
 
+mod wasm_module_registry;
+pub use wasm_module_registry::*;
+
 mod request_policy;
 pub use request_policy::*;
 
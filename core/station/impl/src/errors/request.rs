@@ -41,6 +41,9 @@ pub enum RequestError {
     /// Request cancellation not allowed.
     #[error(r#"Request cancellation not allowed."#)]
     CancellationNotAllowed { reason: String },
+    /// The request archive could not be reached.
+    #[error(r#"The request archive is unavailable due to `{reason}`."#)]
+    ArchiveUnavailable { reason: String },
 }
 
 impl DetailableError for RequestError {
@@ -79,6 +82,10 @@ impl DetailableError for RequestError {
                 details.insert("reason".to_string(), reason.to_string());
                 Some(details)
             }
+            RequestError::ArchiveUnavailable { reason } => {
+                details.insert("reason".to_string(), reason.to_string());
+                Some(details)
+            }
             _ => None,
         }
     }
@@ -108,12 +108,17 @@ pub enum SystemInfoValidationError {
         r#"The provided maximum number of backup snapshots {provided} exceeds the limit {limit}."#
     )]
     InvalidMaxBackupSnapshots { provided: u64, limit: u64 },
+    #[error(
+        r#"The provided request archive minimum age {provided} is lower than the minimum {min} seconds."#
+    )]
+    InvalidRequestArchiveMinAge { provided: u64, min: u64 },
 }
 
 impl DetailableError for SystemInfoValidationError {
     fn details(&self) -> Option<std::collections::HashMap<String, String>> {
         match self {
             SystemInfoValidationError::InvalidMaxBackupSnapshots { .. } => None,
+            SystemInfoValidationError::InvalidRequestArchiveMinAge { .. } => None,
         }
     }
 }
@@ -0,0 +1,61 @@
+use orbit_essentials::api::DetailableError;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Container for wasm module registry errors.
+#[derive(Error, Debug, Eq, PartialEq, Clone)]
+pub enum WasmModuleRegistryError {
+    /// The module source of the request is invalid.
+    #[error(r#"The module source is invalid: {info}"#)]
+    InvalidModuleSource { info: String },
+    /// The referenced registry is not the control panel trusted by the station.
+    #[error(
+        r#"The registry {registry_canister_id} is not the control panel trusted by the station."#
+    )]
+    UntrustedRegistry { registry_canister_id: String },
+    /// No wasm module registry entry matches the reference.
+    #[error(r#"No wasm module registry entry matches {reference}."#)]
+    EntryNotFound { reference: String },
+    /// The registry could not be reached or returned an error.
+    #[error(r#"The registry is unavailable: {reason}"#)]
+    RegistryUnavailable { reason: String },
+    /// The fetched module does not match the hash recorded when the request was created.
+    #[error(
+        r#"The fetched wasm module hash {actual} does not match the expected hash {expected}."#
+    )]
+    HashMismatch { expected: String, actual: String },
+}
+
+impl DetailableError for WasmModuleRegistryError {
+    fn details(&self) -> Option<HashMap<String, String>> {
+        let mut details = HashMap::new();
+        match self {
+            WasmModuleRegistryError::InvalidModuleSource { info } => {
+                details.insert("info".to_string(), info.to_string());
+                Some(details)
+            }
+            WasmModuleRegistryError::UntrustedRegistry {
+                registry_canister_id,
+            } => {
+                details.insert(
+                    "registry_canister_id".to_string(),
+                    registry_canister_id.to_string(),
+                );
+                Some(details)
+            }
+            WasmModuleRegistryError::EntryNotFound { reference } => {
+                details.insert("reference".to_string(), reference.to_string());
+                Some(details)
+            }
+            WasmModuleRegistryError::RegistryUnavailable { reason } => {
+                details.insert("reason".to_string(), reason.to_string());
+                Some(details)
+            }
+            WasmModuleRegistryError::HashMismatch { expected, actual } => {
+                details.insert("expected".to_string(), expected.to_string());
+                details.insert("actual".to_string(), actual.to_string());
+                Some(details)
+            }
+        }
+    }
+}
@@ -1,11 +1,12 @@
 use super::{Create, Execute, RequestExecuteStage};
 use crate::{
+    core::ic_cdk::api::print,
     errors::{RequestError, RequestExecuteError},
     models::{CallExternalCanisterOperation, Request, RequestOperation},
     services::ExternalCanisterService,
 };
 use async_trait::async_trait;
-use candid::Decode;
+use candid::{Decode, Encode};
 use orbit_essentials::model::ModelValidator;
 use orbit_essentials::types::UUID;
 use sha2::{Digest, Sha256};
@@ -32,12 +33,38 @@ impl Create<CallExternalCanisterOperationInput> for CallExternalCanisterRequestC
                 hasher.finalize().to_vec()
             }),
             arg_rendering: None,
+            arg_candid: None,
             execution_method_reply: None,
+            execution_method_reply_candid: None,
             input: operation_input.into(),
         };
 
         op.input.validate()?;
 
+        if let Some(service) = self
+            .external_canister_service
+            .get_candid_service(&op.input.execution_method.canister_id)
+        {
+            // a call without argument is sent with the empty argument list
+            let arg = match op.input.arg {
+                Some(ref arg) => arg.clone(),
+                None => Encode!(&()).map_err(|err| RequestError::ValidationError {
+                    info: format!("failed to encode the empty argument: {err}"),
+                })?,
+            };
+
+            let arg_candid = service
+                .render_args(&op.input.execution_method.method_name, &arg)
+                .map_err(|err| RequestError::ValidationError {
+                    info: format!(
+                        "the argument does not match the Candid interface of canister {}: {}",
+                        op.input.execution_method.canister_id, err
+                    ),
+                })?;
+
+            op.arg_candid = Some(arg_candid);
+        }
+
         let arg_rendering = match op.input.validation_method {
             Some(ref validation_method) => {
                 let rendering_bytes = self
@@ -124,6 +151,27 @@ impl Execute for CallExternalCanisterRequestExecute<'_, '_> {
                 ),
             })?;
         let mut call_external_canister_operation = self.operation.clone();
+
+        if let Some(service) = self
+            .external_canister_service
+            .get_candid_service(&self.operation.input.execution_method.canister_id)
+        {
+            // the call already succeeded, a reply that cannot be decoded is only kept as a blob
+            match service.render_reply(
+                &self.operation.input.execution_method.method_name,
+                &execution_method_reply,
+            ) {
+                Ok(reply_candid) => {
+                    call_external_canister_operation.execution_method_reply_candid =
+                        Some(reply_candid);
+                }
+                Err(err) => print(format!(
+                    "Failed to decode the reply of external canister {}: {}",
+                    self.operation.input.execution_method.canister_id, err
+                )),
+            }
+        }
+
         call_external_canister_operation.execution_method_reply = Some(execution_method_reply);
 
         Ok(RequestExecuteStage::Completed(
@@ -1,38 +1,68 @@
-use super::{Create, Execute, RequestExecuteStage};
+use super::{
+    system_upgrade::{fetch_module, resolve_module_registry_entry},
+    Create, Execute, RequestExecuteStage,
+};
 use crate::{
-    errors::{RequestError, RequestExecuteError},
-    models::{ChangeExternalCanisterOperation, Request, RequestOperation},
-    services::ChangeCanisterService,
+    core::ic_cdk::api::print,
+    errors::{ChangeCanisterError, RequestError, RequestExecuteError},
+    models::{
+        CanisterInstallMode, ChangeExternalCanisterOperation, ChangeExternalCanisterOperationInput,
+        ExternalCanisterRollbackInput, PruneExternalCanisterResource, Request, RequestOperation,
+        WasmModuleExtraChunks,
+    },
+    services::{
+        ChangeCanisterService, ExternalCanisterBackupService, ExternalCanisterService,
+        WasmModuleRegistryService,
+    },
 };
 use async_trait::async_trait;
-use orbit_essentials::types::UUID;
+use candid::Principal;
+use orbit_essentials::{api::ServiceResult, types::UUID};
 use sha2::{Digest, Sha256};
-use station_api::{ChangeExternalCanisterOperationInput, CreateRequestInput};
+use station_api::CreateRequestInput;
 use std::sync::Arc;
 
-pub struct ChangeExternalCanisterRequestCreate;
+pub struct ChangeExternalCanisterRequestCreate {
+    pub wasm_module_registry_service: Arc<WasmModuleRegistryService>,
+}
 
 #[async_trait]
-impl Create<ChangeExternalCanisterOperationInput> for ChangeExternalCanisterRequestCreate {
+impl Create<station_api::ChangeExternalCanisterOperationInput>
+    for ChangeExternalCanisterRequestCreate
+{
     async fn create(
         &self,
         request_id: UUID,
         requested_by_user: UUID,
         input: CreateRequestInput,
-        operation_input: ChangeExternalCanisterOperationInput,
+        operation_input: station_api::ChangeExternalCanisterOperationInput,
     ) -> Result<Request, RequestError> {
+        let operation_input: ChangeExternalCanisterOperationInput = operation_input.into();
+        let module_registry_entry = resolve_module_registry_entry(
+            &self.wasm_module_registry_service,
+            &operation_input.module,
+            &operation_input.module_extra_chunks,
+            &operation_input.module_registry,
+        )
+        .await?;
+
         let request = Request::from_request_creation_input(
             request_id,
             requested_by_user,
             input,
             RequestOperation::ChangeExternalCanister(ChangeExternalCanisterOperation {
+                rollback_snapshot_id: None,
                 arg_checksum: operation_input.arg.as_ref().map(|arg| {
                     let mut hasher = Sha256::new();
                     hasher.update(arg);
                     hasher.finalize().to_vec()
                 }),
                 module_checksum: {
-                    if let Some(ref module_extra_chunks) = operation_input.module_extra_chunks {
+                    if let Some(ref module_registry_entry) = module_registry_entry {
+                        module_registry_entry.module_checksum()
+                    } else if let Some(ref module_extra_chunks) =
+                        operation_input.module_extra_chunks
+                    {
                         module_extra_chunks.wasm_module_hash.clone()
                     } else {
                         let mut hasher = Sha256::new();
@@ -40,7 +70,8 @@ impl Create<ChangeExternalCanisterOperationInput> for ChangeExternalCanisterRequ
                         hasher.finalize().to_vec()
                     }
                 },
-                input: operation_input.into(),
+                module_registry_entry,
+                input: operation_input,
             }),
             "Change canister".to_string(),
         );
@@ -53,6 +84,9 @@ pub struct ChangeExternalCanisterRequestExecute<'p, 'o> {
     request: &'p Request,
     operation: &'o ChangeExternalCanisterOperation,
     change_canister_service: Arc<ChangeCanisterService>,
+    external_canister_backup_service: Arc<ExternalCanisterBackupService>,
+    external_canister_service: Arc<ExternalCanisterService>,
+    wasm_module_registry_service: Arc<WasmModuleRegistryService>,
 }
 
 impl<'p, 'o> ChangeExternalCanisterRequestExecute<'p, 'o> {
@@ -60,40 +94,181 @@ impl<'p, 'o> ChangeExternalCanisterRequestExecute<'p, 'o> {
         request: &'p Request,
         operation: &'o ChangeExternalCanisterOperation,
         change_canister_service: Arc<ChangeCanisterService>,
+        external_canister_backup_service: Arc<ExternalCanisterBackupService>,
+        external_canister_service: Arc<ExternalCanisterService>,
+        wasm_module_registry_service: Arc<WasmModuleRegistryService>,
     ) -> Self {
         Self {
             request,
             operation,
             change_canister_service,
+            external_canister_backup_service,
+            external_canister_service,
+            wasm_module_registry_service,
+        }
+    }
+
+    async fn install(&self) -> ServiceResult<(), ChangeCanisterError> {
+        let (module, module_extra_chunks) = fetch_module(
+            &self.wasm_module_registry_service,
+            &self.operation.input.module,
+            &self.operation.input.module_extra_chunks,
+            &self.operation.input.module_registry,
+            &self.operation.module_registry_entry,
+        )
+        .await
+        .map_err(|err| ChangeCanisterError::Failed {
+            reason: err.to_string(),
+        })?;
+
+        install_external_canister(
+            &self.change_canister_service,
+            &self.external_canister_backup_service,
+            self.operation.input.canister_id,
+            self.operation.input.mode.clone(),
+            &module,
+            module_extra_chunks,
+            self.operation.input.arg.clone(),
+        )
+        .await
+    }
+
+    /// Installs the canister and calls the health check method, the first failure is returned.
+    async fn install_and_check_health(
+        &self,
+        rollback: &ExternalCanisterRollbackInput,
+    ) -> Result<(), String> {
+        self.install()
+            .await
+            .map_err(|err| format!("failed to install: {}", err))?;
+
+        if let Some(health_check_method) = &rollback.health_check_method {
+            self.external_canister_service
+                .call_external_canister(
+                    self.operation.input.canister_id,
+                    health_check_method.clone(),
+                    None,
+                    None,
+                )
+                .await
+                .map_err(|err| format!("health check `{}` failed: {}", health_check_method, err))?;
         }
+
+        Ok(())
     }
 }
 
 #[async_trait]
 impl Execute for ChangeExternalCanisterRequestExecute<'_, '_> {
     async fn execute(&self) -> Result<RequestExecuteStage, RequestExecuteError> {
-        let (_backup_snapshot_id, result) = self
+        let canister_id = self.operation.input.canister_id;
+
+        let Some(rollback) = &self.operation.input.rollback else {
+            self.install()
+                .await
+                .map_err(|err| RequestExecuteError::Failed {
+                    reason: format!(
+                        "failed to install external canister {}: {}",
+                        canister_id, err
+                    ),
+                })?;
+
+            return Ok(RequestExecuteStage::Completed(
+                self.request.operation.clone(),
+            ));
+        };
+
+        let snapshot_id = self
             .change_canister_service
-            .install_canister(
-                self.operation.input.canister_id,
-                self.operation.input.mode.clone(),
-                &self.operation.input.module,
-                &self.operation.input.module_extra_chunks,
-                self.operation.input.arg.clone(),
-                false,
-                None,
-            )
-            .await;
+            .snapshot_canister(canister_id, rollback.replace_snapshot.clone(), false)
+            .await
+            .map_err(|err| RequestExecuteError::Failed {
+                reason: format!(
+                    "failed to take the rollback snapshot of external canister {}: {}",
+                    canister_id, err
+                ),
+            })?;
 
-        result.map_err(|err| RequestExecuteError::Failed {
-            reason: format!(
-                "failed to install external canister {}: {}",
-                self.operation.input.canister_id, err
-            ),
-        })?;
+        if let Err(reason) = self.install_and_check_health(rollback).await {
+            let rollback_outcome = match self
+                .change_canister_service
+                .restore_canister(canister_id, snapshot_id.clone())
+                .await
+            {
+                Ok(()) => format!("restored from snapshot {}", hex::encode(&snapshot_id)),
+                Err(err) => format!(
+                    "failed to restore from snapshot {}: {}",
+                    hex::encode(&snapshot_id),
+                    err
+                ),
+            };
+
+            return Err(RequestExecuteError::Failed {
+                reason: format!(
+                    "external canister {} {}, {}",
+                    canister_id, reason, rollback_outcome
+                ),
+            });
+        }
+
+        // the rollback snapshot is no longer needed once the change succeeded, it is only kept on the
+        // operation if it could not be deleted so that it can be pruned later
+        let mut operation = self.operation.clone();
+        if let Err(err) = self
+            .change_canister_service
+            .prune_canister(
+                canister_id,
+                PruneExternalCanisterResource::Snapshot(snapshot_id.clone()),
+            )
+            .await
+        {
+            print(format!(
+                "Failed to delete the rollback snapshot {} of external canister {}: {}",
+                hex::encode(&snapshot_id),
+                canister_id,
+                err
+            ));
+            operation.rollback_snapshot_id = Some(snapshot_id);
+        }
 
         Ok(RequestExecuteStage::Completed(
-            self.request.operation.clone(),
+            RequestOperation::ChangeExternalCanister(operation),
         ))
     }
 }
+
+/// Installs the module on the external canister, taking a backup snapshot first if the
+/// backup schedule of the canister requires one before every change.
+pub(super) async fn install_external_canister(
+    change_canister_service: &ChangeCanisterService,
+    external_canister_backup_service: &ExternalCanisterBackupService,
+    canister_id: Principal,
+    mode: CanisterInstallMode,
+    module: &[u8],
+    module_extra_chunks: &Option<WasmModuleExtraChunks>,
+    arg: Option<Vec<u8>>,
+) -> ServiceResult<(), ChangeCanisterError> {
+    let before_change_backup =
+        external_canister_backup_service.get_before_change_backup(&canister_id);
+
+    let (backup_snapshot_id, result) = change_canister_service
+        .install_canister(
+            canister_id,
+            mode,
+            module,
+            module_extra_chunks,
+            arg,
+            before_change_backup.is_some(),
+            before_change_backup.clone().flatten(),
+        )
+        .await;
+
+    // the backup snapshot is taken before the canister is changed, so it is recorded even if the change failed
+    if let Some(snapshot_id) = backup_snapshot_id {
+        external_canister_backup_service
+            .record_before_change_backup(canister_id, snapshot_id, before_change_backup.flatten())
+            .await;
+    }
+
+    result
+}
@@ -5,7 +5,9 @@ use crate::{
         ConfigureExternalCanisterOperation, ConfigureExternalCanisterOperationKind,
         ExternalCanister, Request, RequestOperation,
     },
-    services::ExternalCanisterService,
+    services::{
+        ExternalCanisterBackupService, ExternalCanisterControllersService, ExternalCanisterService,
+    },
 };
 use async_trait::async_trait;
 use orbit_essentials::types::UUID;
@@ -39,6 +41,8 @@ pub struct ConfigureExternalCanisterRequestExecute<'p, 'o> {
     request: &'p Request,
     operation: &'o ConfigureExternalCanisterOperation,
     external_canister_service: Arc<ExternalCanisterService>,
+    external_canister_backup_service: Arc<ExternalCanisterBackupService>,
+    external_canister_controllers_service: Arc<ExternalCanisterControllersService>,
 }
 
 impl<'p, 'o> ConfigureExternalCanisterRequestExecute<'p, 'o> {
@@ -46,11 +50,15 @@ impl<'p, 'o> ConfigureExternalCanisterRequestExecute<'p, 'o> {
         request: &'p Request,
         operation: &'o ConfigureExternalCanisterOperation,
         external_canister_service: Arc<ExternalCanisterService>,
+        external_canister_backup_service: Arc<ExternalCanisterBackupService>,
+        external_canister_controllers_service: Arc<ExternalCanisterControllersService>,
     ) -> Self {
         Self {
             request,
             operation,
             external_canister_service,
+            external_canister_backup_service,
+            external_canister_controllers_service,
         }
     }
 
@@ -99,6 +107,30 @@ impl Execute for ConfigureExternalCanisterRequestExecute<'_, '_> {
                         reason: format!("Failed to configure settings: {e}"),
                     })?;
             }
+            ConfigureExternalCanisterOperationKind::BackupSchedule(schedule) => {
+                self.external_canister_backup_service
+                    .set_backup_schedule(self.operation.canister_id, schedule.clone())
+                    .await
+                    .map_err(|e| RequestExecuteError::Failed {
+                        reason: format!("Failed to configure backup schedule: {e}"),
+                    })?;
+            }
+            ConfigureExternalCanisterOperationKind::ReadOnlyMethods(methods) => {
+                let external_canister = self.lookup_operation_external_canister()?;
+
+                self.external_canister_service
+                    .set_read_only_methods(&external_canister.id, methods.clone())
+                    .map_err(|e| RequestExecuteError::Failed {
+                        reason: format!("Failed to configure read-only methods: {e}"),
+                    })?;
+            }
+            ConfigureExternalCanisterOperationKind::CandidInterface(candid) => {
+                self.external_canister_service
+                    .set_candid_interface(&self.operation.canister_id, candid.clone())
+                    .map_err(|e| RequestExecuteError::Failed {
+                        reason: format!("Failed to register Candid interface: {e}"),
+                    })?;
+            }
             // these operations do not require an external canister entry
             ConfigureExternalCanisterOperationKind::NativeSettings(settings) => {
                 self.external_canister_service
@@ -107,6 +139,35 @@ impl Execute for ConfigureExternalCanisterRequestExecute<'_, '_> {
                     .map_err(|e| RequestExecuteError::Failed {
                         reason: format!("Failed to configure native settings: {e}"),
                     })?;
+
+                if let Some(controllers) = &settings.controllers {
+                    self.external_canister_controllers_service
+                        .record_controllers(&self.operation.canister_id, controllers.clone());
+                }
+            }
+            ConfigureExternalCanisterOperationKind::AddControllers(controllers) => {
+                self.external_canister_controllers_service
+                    .add_controllers(self.operation.canister_id, controllers.clone())
+                    .await
+                    .map_err(|e| RequestExecuteError::Failed {
+                        reason: format!("Failed to add controllers: {e}"),
+                    })?;
+            }
+            ConfigureExternalCanisterOperationKind::RemoveControllers(controllers) => {
+                self.external_canister_controllers_service
+                    .remove_controllers(self.operation.canister_id, controllers.clone())
+                    .await
+                    .map_err(|e| RequestExecuteError::Failed {
+                        reason: format!("Failed to remove controllers: {e}"),
+                    })?;
+            }
+            ConfigureExternalCanisterOperationKind::ReleaseControl(controllers) => {
+                self.external_canister_controllers_service
+                    .release_control(self.operation.canister_id, controllers.clone())
+                    .await
+                    .map_err(|e| RequestExecuteError::Failed {
+                        reason: format!("Failed to release control: {e}"),
+                    })?;
             }
         }
 
@@ -0,0 +1,197 @@
+use super::{Create, Execute, RequestExecuteStage};
+use crate::{
+    errors::{RequestError, RequestExecuteError},
+    models::{FreezeStationOperation, Request, RequestOperation, UnfreezeStationOperation},
+    services::SystemService,
+};
+use async_trait::async_trait;
+use orbit_essentials::types::UUID;
+use station_api::{CreateRequestInput, FreezeStationOperationInput, UnfreezeStationOperationInput};
+use std::sync::Arc;
+
+pub struct FreezeStationRequestCreate;
+
+#[async_trait]
+impl Create<FreezeStationOperationInput> for FreezeStationRequestCreate {
+    async fn create(
+        &self,
+        request_id: UUID,
+        requested_by_user: UUID,
+        input: CreateRequestInput,
+        operation_input: FreezeStationOperationInput,
+    ) -> Result<Request, RequestError> {
+        let request = Request::from_request_creation_input(
+            request_id,
+            requested_by_user,
+            input,
+            RequestOperation::FreezeStation(FreezeStationOperation {
+                input: operation_input.into(),
+            }),
+            "Freeze station".to_string(),
+        );
+
+        Ok(request)
+    }
+}
+
+pub struct FreezeStationRequestExecute<'p, 'o> {
+    request: &'p Request,
+    operation: &'o FreezeStationOperation,
+    system_service: Arc<SystemService>,
+}
+
+impl<'p, 'o> FreezeStationRequestExecute<'p, 'o> {
+    pub fn new(
+        request: &'p Request,
+        operation: &'o FreezeStationOperation,
+        system_service: Arc<SystemService>,
+    ) -> Self {
+        Self {
+            request,
+            operation,
+            system_service,
+        }
+    }
+}
+
+#[async_trait]
+impl Execute for FreezeStationRequestExecute<'_, '_> {
+    async fn execute(&self) -> Result<RequestExecuteStage, RequestExecuteError> {
+        self.system_service
+            .freeze_station(self.request.id, self.operation.input.reason.clone());
+
+        Ok(RequestExecuteStage::Completed(
+            self.request.operation.clone(),
+        ))
+    }
+}
+
+pub struct UnfreezeStationRequestCreate;
+
+#[async_trait]
+impl Create<UnfreezeStationOperationInput> for UnfreezeStationRequestCreate {
+    async fn create(
+        &self,
+        request_id: UUID,
+        requested_by_user: UUID,
+        input: CreateRequestInput,
+        operation_input: UnfreezeStationOperationInput,
+    ) -> Result<Request, RequestError> {
+        let request = Request::from_request_creation_input(
+            request_id,
+            requested_by_user,
+            input,
+            RequestOperation::UnfreezeStation(UnfreezeStationOperation {
+                input: operation_input.into(),
+            }),
+            "Unfreeze station".to_string(),
+        );
+
+        Ok(request)
+    }
+}
+
+pub struct UnfreezeStationRequestExecute<'p> {
+    request: &'p Request,
+    system_service: Arc<SystemService>,
+}
+
+impl<'p> UnfreezeStationRequestExecute<'p> {
+    pub fn new(request: &'p Request, system_service: Arc<SystemService>) -> Self {
+        Self {
+            request,
+            system_service,
+        }
+    }
+}
+
+#[async_trait]
+impl Execute for UnfreezeStationRequestExecute<'_> {
+    async fn execute(&self) -> Result<RequestExecuteStage, RequestExecuteError> {
+        self.system_service.unfreeze_station();
+
+        Ok(RequestExecuteStage::Completed(
+            self.request.operation.clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::{read_system_info, test_utils},
+        services::SYSTEM_SERVICE,
+    };
+    use station_api::HealthStatus;
+    use uuid::Uuid;
+
+    fn mock_create_request_input(
+        operation: station_api::RequestOperationInput,
+    ) -> CreateRequestInput {
+        CreateRequestInput {
+            title: None,
+            summary: None,
+            execution_plan: Some(station_api::RequestExecutionScheduleDTO::Immediate),
+            expiration_dt: None,
+            operation,
+            deduplication_key: None,
+            tags: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn freeze_and_unfreeze_station() {
+        test_utils::init_canister_system();
+
+        let freeze_input = FreezeStationOperationInput {
+            reason: Some("compromised key".to_string()),
+        };
+        let request = FreezeStationRequestCreate
+            .create(
+                *Uuid::new_v4().as_bytes(),
+                *Uuid::new_v4().as_bytes(),
+                mock_create_request_input(station_api::RequestOperationInput::FreezeStation(
+                    freeze_input.clone(),
+                )),
+                freeze_input,
+            )
+            .await
+            .unwrap();
+
+        let RequestOperation::FreezeStation(operation) = &request.operation else {
+            panic!("Invalid operation");
+        };
+
+        FreezeStationRequestExecute::new(&request, operation, Arc::clone(&SYSTEM_SERVICE))
+            .execute()
+            .await
+            .unwrap();
+
+        let freeze = read_system_info().get_freeze().cloned().unwrap();
+        assert_eq!(freeze.request_id, request.id);
+        assert_eq!(freeze.reason, Some("compromised key".to_string()));
+        // the freeze is exposed through the system info, a frozen station keeps reporting as healthy
+        assert_eq!(SYSTEM_SERVICE.health_status(), HealthStatus::Healthy);
+
+        let request = UnfreezeStationRequestCreate
+            .create(
+                *Uuid::new_v4().as_bytes(),
+                *Uuid::new_v4().as_bytes(),
+                mock_create_request_input(station_api::RequestOperationInput::UnfreezeStation(
+                    UnfreezeStationOperationInput {},
+                )),
+                UnfreezeStationOperationInput {},
+            )
+            .await
+            .unwrap();
+
+        UnfreezeStationRequestExecute::new(&request, Arc::clone(&SYSTEM_SERVICE))
+            .execute()
+            .await
+            .unwrap();
+
+        assert!(!read_system_info().is_frozen());
+        assert_eq!(SYSTEM_SERVICE.health_status(), HealthStatus::Healthy);
+    }
+}
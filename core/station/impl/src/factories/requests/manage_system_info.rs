@@ -51,8 +51,20 @@ impl Execute for ManageSystemInfoRequestExecute<'_, '_> {
             .await
             .map_err(|err| RequestExecuteError::Failed { reason: err })?;
 
+        let mut operation = self.operation.clone();
+
+        // the archive wasm module is no longer needed once the archive canister is deployed or upgraded
+        if let Some(deploy) = operation
+            .input
+            .request_archive
+            .as_mut()
+            .and_then(|config| config.deploy.as_mut())
+        {
+            deploy.wasm_module = Vec::new();
+        }
+
         Ok(RequestExecuteStage::Completed(
-            self.request.operation.clone(),
+            RequestOperation::ManageSystemInfo(operation),
         ))
     }
 }
@@ -94,6 +106,8 @@ mod tests {
                     cycle_obtain_strategy: None,
                     max_station_backup_snapshots: None,
                     max_upgrader_backup_snapshots: None,
+                    request_archive: None,
+                    control_panel_id: None,
                 },
             })
         );
@@ -147,6 +161,8 @@ mod mnanage_system_info_test_utils {
             cycle_obtain_strategy: None,
             max_station_backup_snapshots: None,
             max_upgrader_backup_snapshots: None,
+            request_archive: None,
+            control_panel_id: None,
         }
     }
 
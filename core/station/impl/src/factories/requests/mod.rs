@@ -2,9 +2,11 @@ use crate::{
     core::generate_uuid_v4,
     errors::{RequestError, RequestExecuteError},
     models::{Request, RequestOperation},
+    repositories::REQUEST_REPOSITORY,
     services::{
-        permission::PERMISSION_SERVICE, CHANGE_CANISTER_SERVICE, EXTERNAL_CANISTER_SERVICE,
-        REQUEST_POLICY_SERVICE, SYSTEM_SERVICE,
+        permission::PERMISSION_SERVICE, CHANGE_CANISTER_SERVICE, EXTERNAL_CANISTER_BACKUP_SERVICE,
+        EXTERNAL_CANISTER_CONTROLLERS_SERVICE, EXTERNAL_CANISTER_SERVICE, REQUEST_POLICY_SERVICE,
+        SYSTEM_SERVICE, WASM_MODULE_REGISTRY_SERVICE,
     },
 };
 use async_trait::async_trait;
@@ -38,6 +40,7 @@ mod edit_permission;
 mod edit_request_policy;
 mod edit_user;
 mod edit_user_group;
+mod freeze_station;
 mod fund_external_canister;
 mod manage_system_info;
 mod monitor_external_canister;
@@ -53,6 +56,7 @@ mod snapshot_external_canister;
 mod system_restore;
 mod system_upgrade;
 mod transfer;
+mod upgrade_external_canister_fleet;
 
 use self::{
     add_account::{AddAccountRequestCreate, AddAccountRequestExecute},
@@ -195,7 +199,9 @@ impl RequestFactory {
                     .await
             }
             RequestOperationInput::SystemUpgrade(operation) => {
-                let creator = Box::new(SystemUpgradeRequestCreate {});
+                let creator = Box::new(SystemUpgradeRequestCreate {
+                    wasm_module_registry_service: Arc::clone(&WASM_MODULE_REGISTRY_SERVICE),
+                });
                 creator
                     .create(id, requested_by_user, input.clone(), operation.clone())
                     .await
@@ -213,7 +219,9 @@ impl RequestFactory {
                     .await
             }
             RequestOperationInput::ChangeExternalCanister(operation) => {
-                let creator = Box::new(ChangeExternalCanisterRequestCreate {});
+                let creator = Box::new(ChangeExternalCanisterRequestCreate {
+                    wasm_module_registry_service: Arc::clone(&WASM_MODULE_REGISTRY_SERVICE),
+                });
                 creator
                     .create(id, requested_by_user, input.clone(), operation.clone())
                     .await
@@ -336,6 +344,28 @@ impl RequestFactory {
                     .create(id, requested_by_user, input.clone(), operation.clone())
                     .await
             }
+            RequestOperationInput::FreezeStation(operation) => {
+                let creator = Box::new(freeze_station::FreezeStationRequestCreate {});
+                creator
+                    .create(id, requested_by_user, input.clone(), operation.clone())
+                    .await
+            }
+            RequestOperationInput::UnfreezeStation(operation) => {
+                let creator = Box::new(freeze_station::UnfreezeStationRequestCreate {});
+                creator
+                    .create(id, requested_by_user, input.clone(), operation.clone())
+                    .await
+            }
+            RequestOperationInput::UpgradeExternalCanisterFleet(operation) => {
+                let creator = Box::new(
+                    upgrade_external_canister_fleet::UpgradeExternalCanisterFleetRequestCreate {
+                        external_canister_service: Arc::clone(&EXTERNAL_CANISTER_SERVICE),
+                    },
+                );
+                creator
+                    .create(id, requested_by_user, input.clone(), operation.clone())
+                    .await
+            }
         }
     }
 
@@ -377,9 +407,14 @@ impl RequestFactory {
             RequestOperation::SetDisasterRecovery(operation) => Box::new(
                 set_disaster_recovery::SetDisasterRecoveryRequestExecute::new(request, operation),
             ),
-            RequestOperation::SystemUpgrade(operation) => Box::new(
-                SystemUpgradeRequestExecute::new(request, operation, Arc::clone(&SYSTEM_SERVICE)),
-            ),
+            RequestOperation::SystemUpgrade(operation) => {
+                Box::new(SystemUpgradeRequestExecute::new(
+                    request,
+                    operation,
+                    Arc::clone(&SYSTEM_SERVICE),
+                    Arc::clone(&WASM_MODULE_REGISTRY_SERVICE),
+                ))
+            }
             RequestOperation::SystemRestore(operation) => Box::new(
                 SystemRestoreRequestExecute::new(request, operation, Arc::clone(&SYSTEM_SERVICE)),
             ),
@@ -388,6 +423,9 @@ impl RequestFactory {
                     request,
                     operation,
                     Arc::clone(&CHANGE_CANISTER_SERVICE),
+                    Arc::clone(&EXTERNAL_CANISTER_BACKUP_SERVICE),
+                    Arc::clone(&EXTERNAL_CANISTER_SERVICE),
+                    Arc::clone(&WASM_MODULE_REGISTRY_SERVICE),
                 ))
             }
             RequestOperation::CreateExternalCanister(operation) => {
@@ -409,6 +447,8 @@ impl RequestFactory {
                     request,
                     operation,
                     Arc::clone(&EXTERNAL_CANISTER_SERVICE),
+                    Arc::clone(&EXTERNAL_CANISTER_BACKUP_SERVICE),
+                    Arc::clone(&EXTERNAL_CANISTER_CONTROLLERS_SERVICE),
                 ))
             }
             RequestOperation::FundExternalCanister(operation) => {
@@ -492,6 +532,28 @@ impl RequestFactory {
             RequestOperation::RemoveNamedRule(operation) => Box::new(
                 remove_named_rule::RemoveNamedRuleRequestExecute::new(request, operation),
             ),
+            RequestOperation::FreezeStation(operation) => {
+                Box::new(freeze_station::FreezeStationRequestExecute::new(
+                    request,
+                    operation,
+                    Arc::clone(&SYSTEM_SERVICE),
+                ))
+            }
+            RequestOperation::UnfreezeStation(_) => {
+                Box::new(freeze_station::UnfreezeStationRequestExecute::new(
+                    request,
+                    Arc::clone(&SYSTEM_SERVICE),
+                ))
+            }
+            RequestOperation::UpgradeExternalCanisterFleet(operation) => Box::new(
+                upgrade_external_canister_fleet::UpgradeExternalCanisterFleetRequestExecute::new(
+                    request,
+                    operation,
+                    Arc::clone(&CHANGE_CANISTER_SERVICE),
+                    Arc::clone(&EXTERNAL_CANISTER_BACKUP_SERVICE),
+                    Arc::clone(&REQUEST_REPOSITORY),
+                ),
+            ),
         }
     }
 }
@@ -1,27 +1,42 @@
 use super::{Create, Execute, RequestExecuteStage};
 use crate::{
     errors::{RequestError, RequestExecuteError},
-    models::{Request, RequestOperation, SystemUpgradeOperation, SystemUpgradeTarget},
-    services::SystemService,
+    models::{
+        Request, RequestOperation, ResolvedWasmModuleRegistryEntry, SystemUpgradeOperation,
+        SystemUpgradeOperationInput, SystemUpgradeTarget, WasmModuleExtraChunks,
+        WasmModuleRegistryReference,
+    },
+    services::{SystemService, WasmModuleRegistryService},
 };
 use async_trait::async_trait;
 use candid::Encode;
 use orbit_essentials::types::UUID;
 use sha2::{Digest, Sha256};
-use station_api::{CreateRequestInput, SystemUpgradeOperationInput};
-use std::sync::Arc;
+use station_api::CreateRequestInput;
+use std::{borrow::Cow, sync::Arc};
 
-pub struct SystemUpgradeRequestCreate;
+pub struct SystemUpgradeRequestCreate {
+    pub wasm_module_registry_service: Arc<WasmModuleRegistryService>,
+}
 
 #[async_trait]
-impl Create<SystemUpgradeOperationInput> for SystemUpgradeRequestCreate {
+impl Create<station_api::SystemUpgradeOperationInput> for SystemUpgradeRequestCreate {
     async fn create(
         &self,
         request_id: UUID,
         requested_by_user: UUID,
         input: CreateRequestInput,
-        operation_input: SystemUpgradeOperationInput,
+        operation_input: station_api::SystemUpgradeOperationInput,
     ) -> Result<Request, RequestError> {
+        let operation_input: SystemUpgradeOperationInput = operation_input.into();
+        let module_registry_entry = resolve_module_registry_entry(
+            &self.wasm_module_registry_service,
+            &operation_input.module,
+            &operation_input.module_extra_chunks,
+            &operation_input.module_registry,
+        )
+        .await?;
+
         let request = Request::from_request_creation_input(
             request_id,
             requested_by_user,
@@ -33,7 +48,11 @@ impl Create<SystemUpgradeOperationInput> for SystemUpgradeRequestCreate {
                     hasher.finalize().to_vec()
                 }),
                 module_checksum: {
-                    if let Some(ref module_extra_chunks) = operation_input.module_extra_chunks {
+                    if let Some(ref module_registry_entry) = module_registry_entry {
+                        module_registry_entry.module_checksum()
+                    } else if let Some(ref module_extra_chunks) =
+                        operation_input.module_extra_chunks
+                    {
                         module_extra_chunks.wasm_module_hash.clone()
                     } else {
                         let mut hasher = Sha256::new();
@@ -42,7 +61,8 @@ impl Create<SystemUpgradeOperationInput> for SystemUpgradeRequestCreate {
                     }
                 },
                 take_backup_snapshot: operation_input.take_backup_snapshot,
-                input: operation_input.into(),
+                module_registry_entry,
+                input: operation_input,
             }),
             "Upgrade System".to_string(),
         );
@@ -51,10 +71,73 @@ impl Create<SystemUpgradeOperationInput> for SystemUpgradeRequestCreate {
     }
 }
 
+/// Resolves the registry entry referenced by the request, if any, so that approvers can see the
+/// version that gets installed.
+pub(super) async fn resolve_module_registry_entry(
+    wasm_module_registry_service: &WasmModuleRegistryService,
+    module: &[u8],
+    module_extra_chunks: &Option<WasmModuleExtraChunks>,
+    module_registry: &Option<WasmModuleRegistryReference>,
+) -> Result<Option<ResolvedWasmModuleRegistryEntry>, RequestError> {
+    WasmModuleRegistryService::validate_module_source(module, module_extra_chunks, module_registry)
+        .map_err(|err| RequestError::ValidationError {
+            info: err.to_string(),
+        })?;
+
+    let Some(reference) = module_registry else {
+        return Ok(None);
+    };
+
+    let resolved = wasm_module_registry_service
+        .resolve(reference)
+        .await
+        .map_err(|err| RequestError::ValidationError {
+            info: format!(
+                "failed to resolve the registry entry {}: {}",
+                reference, err
+            ),
+        })?;
+
+    Ok(Some(resolved))
+}
+
+/// Returns the module and extra chunks to install, fetching them from the registry if the
+/// request references a registry entry.
+pub(super) async fn fetch_module<'a>(
+    wasm_module_registry_service: &WasmModuleRegistryService,
+    module: &'a [u8],
+    module_extra_chunks: &'a Option<WasmModuleExtraChunks>,
+    module_registry: &Option<WasmModuleRegistryReference>,
+    module_registry_entry: &'a Option<ResolvedWasmModuleRegistryEntry>,
+) -> Result<(Cow<'a, [u8]>, &'a Option<WasmModuleExtraChunks>), RequestExecuteError> {
+    let Some(reference) = module_registry else {
+        return Ok((Cow::Borrowed(module), module_extra_chunks));
+    };
+
+    let Some(resolved) = module_registry_entry else {
+        return Err(RequestExecuteError::Failed {
+            reason: format!("the registry entry {} was not resolved", reference),
+        });
+    };
+
+    let module = wasm_module_registry_service
+        .fetch_module(reference.registry_canister_id, resolved)
+        .await
+        .map_err(|err| RequestExecuteError::Failed {
+            reason: format!(
+                "failed to fetch the module of {} version {} from the registry: {}",
+                reference.name, resolved.version, err
+            ),
+        })?;
+
+    Ok((Cow::Owned(module), &resolved.module_extra_chunks))
+}
+
 pub struct SystemUpgradeRequestExecute<'p, 'o> {
     request: &'p Request,
     operation: &'o SystemUpgradeOperation,
     system_service: Arc<SystemService>,
+    wasm_module_registry_service: Arc<WasmModuleRegistryService>,
 }
 
 impl<'p, 'o> SystemUpgradeRequestExecute<'p, 'o> {
@@ -62,11 +145,13 @@ impl<'p, 'o> SystemUpgradeRequestExecute<'p, 'o> {
         request: &'p Request,
         operation: &'o SystemUpgradeOperation,
         system_service: Arc<SystemService>,
+        wasm_module_registry_service: Arc<WasmModuleRegistryService>,
     ) -> Self {
         Self {
             request,
             operation,
             system_service,
+            wasm_module_registry_service,
         }
     }
 }
@@ -74,6 +159,15 @@ impl<'p, 'o> SystemUpgradeRequestExecute<'p, 'o> {
 #[async_trait]
 impl Execute for SystemUpgradeRequestExecute<'_, '_> {
     async fn execute(&self) -> Result<RequestExecuteStage, RequestExecuteError> {
+        let (module, module_extra_chunks) = fetch_module(
+            &self.wasm_module_registry_service,
+            &self.operation.input.module,
+            &self.operation.input.module_extra_chunks,
+            &self.operation.input.module_registry,
+            &self.operation.module_registry_entry,
+        )
+        .await?;
+
         match self.operation.input.target {
             SystemUpgradeTarget::UpgradeStation => {
                 self.system_service
@@ -84,8 +178,8 @@ impl Execute for SystemUpgradeRequestExecute<'_, '_> {
                 let out = self
                     .system_service
                     .upgrade_station(
-                        &self.operation.input.module,
-                        &self.operation.input.module_extra_chunks,
+                        &module,
+                        module_extra_chunks,
                         arg,
                         self.operation.input.take_backup_snapshot,
                     )
@@ -108,8 +202,8 @@ impl Execute for SystemUpgradeRequestExecute<'_, '_> {
             SystemUpgradeTarget::UpgradeUpgrader => {
                 self.system_service
                     .upgrade_upgrader(
-                        &self.operation.input.module,
-                        &self.operation.input.module_extra_chunks,
+                        &module,
+                        module_extra_chunks,
                         self.operation.input.arg.clone(),
                         self.operation
                             .input
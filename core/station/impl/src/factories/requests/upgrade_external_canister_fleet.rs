@@ -0,0 +1,334 @@
+use super::{
+    change_external_canister::install_external_canister, Create, Execute, RequestExecuteStage,
+};
+use crate::{
+    core::ic_cdk::next_time,
+    errors::{RequestError, RequestExecuteError},
+    models::{
+        ExternalCanisterUpgradeOutcome, ExternalCanisterUpgradeStatus, Request, RequestOperation,
+        UpgradeExternalCanisterFleetOperation, UpgradeExternalCanisterFleetOperationInput,
+    },
+    repositories::RequestRepository,
+    services::{ChangeCanisterService, ExternalCanisterBackupService, ExternalCanisterService},
+};
+use async_trait::async_trait;
+use orbit_essentials::{model::ModelValidator, repository::Repository, types::UUID};
+use sha2::{Digest, Sha256};
+use station_api::CreateRequestInput;
+use std::sync::Arc;
+
+pub struct UpgradeExternalCanisterFleetRequestCreate {
+    pub external_canister_service: Arc<ExternalCanisterService>,
+}
+
+#[async_trait]
+impl Create<station_api::UpgradeExternalCanisterFleetOperationInput>
+    for UpgradeExternalCanisterFleetRequestCreate
+{
+    async fn create(
+        &self,
+        request_id: UUID,
+        requested_by_user: UUID,
+        input: CreateRequestInput,
+        operation_input: station_api::UpgradeExternalCanisterFleetOperationInput,
+    ) -> Result<Request, RequestError> {
+        let operation_input: UpgradeExternalCanisterFleetOperationInput = operation_input.into();
+
+        let mut canister_ids = self
+            .external_canister_service
+            .resolve_fleet_target(&operation_input.target)
+            .map_err(|err| RequestError::ValidationError {
+                info: format!("failed to resolve the fleet target: {}", err),
+            })?;
+
+        // the canaries are installed first, the sort is stable so the target order is kept otherwise
+        canister_ids.sort_by_key(|canister_id| !operation_input.is_canary(canister_id));
+
+        let outcomes = canister_ids
+            .into_iter()
+            .map(|canister_id| ExternalCanisterUpgradeOutcome {
+                canister_id,
+                arg_checksum: operation_input.arg_for(&canister_id).map(|arg| {
+                    let mut hasher = Sha256::new();
+                    hasher.update(arg);
+                    hasher.finalize().to_vec()
+                }),
+                status: ExternalCanisterUpgradeStatus::Pending,
+            })
+            .collect();
+
+        let operation = UpgradeExternalCanisterFleetOperation {
+            module_checksum: {
+                if let Some(ref module_extra_chunks) = operation_input.module_extra_chunks {
+                    module_extra_chunks.wasm_module_hash.clone()
+                } else {
+                    let mut hasher = Sha256::new();
+                    hasher.update(&operation_input.module);
+                    hasher.finalize().to_vec()
+                }
+            },
+            outcomes,
+            input: operation_input,
+        };
+
+        operation.validate()?;
+
+        let request = Request::from_request_creation_input(
+            request_id,
+            requested_by_user,
+            input,
+            RequestOperation::UpgradeExternalCanisterFleet(operation),
+            "Upgrade canister fleet".to_string(),
+        );
+
+        Ok(request)
+    }
+}
+
+pub struct UpgradeExternalCanisterFleetRequestExecute<'p, 'o> {
+    request: &'p Request,
+    operation: &'o UpgradeExternalCanisterFleetOperation,
+    change_canister_service: Arc<ChangeCanisterService>,
+    external_canister_backup_service: Arc<ExternalCanisterBackupService>,
+    request_repository: Arc<RequestRepository>,
+}
+
+impl<'p, 'o> UpgradeExternalCanisterFleetRequestExecute<'p, 'o> {
+    pub fn new(
+        request: &'p Request,
+        operation: &'o UpgradeExternalCanisterFleetOperation,
+        change_canister_service: Arc<ChangeCanisterService>,
+        external_canister_backup_service: Arc<ExternalCanisterBackupService>,
+        request_repository: Arc<RequestRepository>,
+    ) -> Self {
+        Self {
+            request,
+            operation,
+            change_canister_service,
+            external_canister_backup_service,
+            request_repository,
+        }
+    }
+
+    /// Stores the outcomes recorded so far, so that they are visible while the fleet is being
+    /// installed and are kept if the request fails.
+    fn save_progress(&self, operation: &UpgradeExternalCanisterFleetOperation) {
+        if let Some(mut request) = self.request_repository.get(&self.request.to_key()) {
+            request.operation = RequestOperation::UpgradeExternalCanisterFleet(operation.clone());
+            self.request_repository
+                .save_modified(&mut request, next_time());
+        }
+    }
+}
+
+#[async_trait]
+impl Execute for UpgradeExternalCanisterFleetRequestExecute<'_, '_> {
+    async fn execute(&self) -> Result<RequestExecuteStage, RequestExecuteError> {
+        let mut operation = self.operation.clone();
+        let mut failure = None;
+
+        for idx in 0..operation.outcomes.len() {
+            if failure.is_some() {
+                operation.outcomes[idx].status = ExternalCanisterUpgradeStatus::Skipped;
+                continue;
+            }
+
+            let canister_id = operation.outcomes[idx].canister_id;
+            let result = install_external_canister(
+                &self.change_canister_service,
+                &self.external_canister_backup_service,
+                canister_id,
+                operation.input.mode.clone(),
+                &operation.input.module,
+                &operation.input.module_extra_chunks,
+                operation.input.arg_for(&canister_id),
+            )
+            .await;
+
+            operation.outcomes[idx].status = match result {
+                Ok(()) => ExternalCanisterUpgradeStatus::Completed,
+                Err(err) => {
+                    failure = Some(format!(
+                        "failed to install external canister {}: {}",
+                        canister_id, err
+                    ));
+
+                    ExternalCanisterUpgradeStatus::Failed {
+                        reason: err.to_string(),
+                    }
+                }
+            };
+
+            if failure.is_none() {
+                self.save_progress(&operation);
+            }
+        }
+
+        // the module is no longer needed once the operation is finalized
+        operation.input.module = Vec::new();
+
+        match failure {
+            Some(reason) => {
+                let skipped = operation
+                    .outcomes
+                    .iter()
+                    .filter(|outcome| outcome.status == ExternalCanisterUpgradeStatus::Skipped)
+                    .count();
+
+                self.save_progress(&operation);
+
+                Err(RequestExecuteError::Failed {
+                    reason: format!("{}, {} remaining canisters were skipped", reason, skipped),
+                })
+            }
+            None => Ok(RequestExecuteStage::Completed(
+                RequestOperation::UpgradeExternalCanisterFleet(operation),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::test_utils, models::external_canister_test_utils::mock_external_canister,
+        repositories::EXTERNAL_CANISTER_REPOSITORY, services::EXTERNAL_CANISTER_SERVICE,
+    };
+    use candid::Principal;
+    use orbit_essentials::model::ModelKey;
+    use station_api::{
+        CanisterInstallMode, ExternalCanisterFleetArgInput, ExternalCanisterFleetTargetDTO,
+    };
+    use uuid::Uuid;
+
+    fn mock_create_request_input(
+        operation: station_api::RequestOperationInput,
+    ) -> CreateRequestInput {
+        CreateRequestInput {
+            title: None,
+            summary: None,
+            execution_plan: Some(station_api::RequestExecutionScheduleDTO::Immediate),
+            expiration_dt: None,
+            operation,
+            deduplication_key: None,
+            tags: None,
+        }
+    }
+
+    async fn create_fleet_request(
+        operation_input: station_api::UpgradeExternalCanisterFleetOperationInput,
+    ) -> Result<Request, RequestError> {
+        UpgradeExternalCanisterFleetRequestCreate {
+            external_canister_service: Arc::clone(&EXTERNAL_CANISTER_SERVICE),
+        }
+        .create(
+            *Uuid::new_v4().as_bytes(),
+            *Uuid::new_v4().as_bytes(),
+            mock_create_request_input(
+                station_api::RequestOperationInput::UpgradeExternalCanisterFleet(
+                    operation_input.clone(),
+                ),
+            ),
+            operation_input,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn create_resolves_labeled_fleet_with_canaries_first() {
+        test_utils::init_canister_system();
+
+        let mut fleet = Vec::new();
+        for name in ["tenant-a", "tenant-b", "tenant-c"] {
+            let mut external_canister = mock_external_canister();
+            external_canister.name = name.to_string();
+            external_canister.labels = vec!["tenant".to_string()];
+            EXTERNAL_CANISTER_REPOSITORY.insert(external_canister.key(), external_canister.clone());
+            fleet.push(external_canister.canister_id);
+        }
+
+        let mut unlabeled = mock_external_canister();
+        unlabeled.labels = vec!["other".to_string()];
+        EXTERNAL_CANISTER_REPOSITORY.insert(unlabeled.key(), unlabeled);
+
+        let request =
+            create_fleet_request(station_api::UpgradeExternalCanisterFleetOperationInput {
+                target: ExternalCanisterFleetTargetDTO::Labels(vec!["tenant".to_string()]),
+                mode: CanisterInstallMode::Upgrade(None),
+                module: vec![1, 2, 3],
+                module_extra_chunks: None,
+                arg: Some(vec![0]),
+                canister_args: vec![ExternalCanisterFleetArgInput {
+                    canister_id: fleet[0],
+                    arg: vec![1],
+                }],
+                canary_canister_ids: vec![fleet[2]],
+            })
+            .await
+            .unwrap();
+
+        let RequestOperation::UpgradeExternalCanisterFleet(operation) = &request.operation else {
+            panic!("Invalid operation");
+        };
+
+        assert_eq!(
+            operation
+                .outcomes
+                .iter()
+                .map(|outcome| outcome.canister_id)
+                .collect::<Vec<_>>(),
+            vec![fleet[2], fleet[0], fleet[1]]
+        );
+        assert!(operation
+            .outcomes
+            .iter()
+            .all(|outcome| outcome.status == ExternalCanisterUpgradeStatus::Pending));
+        assert_ne!(
+            operation.outcomes[1].arg_checksum,
+            operation.outcomes[2].arg_checksum
+        );
+        assert_eq!(
+            operation.outcomes[0].arg_checksum,
+            operation.outcomes[2].arg_checksum
+        );
+    }
+
+    #[tokio::test]
+    async fn create_fails_for_canary_outside_of_fleet() {
+        test_utils::init_canister_system();
+
+        let external_canister = mock_external_canister();
+        EXTERNAL_CANISTER_REPOSITORY.insert(external_canister.key(), external_canister.clone());
+
+        let result =
+            create_fleet_request(station_api::UpgradeExternalCanisterFleetOperationInput {
+                target: ExternalCanisterFleetTargetDTO::CanisterIds(vec![
+                    external_canister.canister_id,
+                ]),
+                mode: CanisterInstallMode::Upgrade(None),
+                module: vec![1, 2, 3],
+                module_extra_chunks: None,
+                arg: None,
+                canister_args: vec![],
+                canary_canister_ids: vec![Principal::management_canister()],
+            })
+            .await;
+
+        assert!(matches!(result, Err(RequestError::ValidationError { .. })));
+
+        let result =
+            create_fleet_request(station_api::UpgradeExternalCanisterFleetOperationInput {
+                target: ExternalCanisterFleetTargetDTO::Labels(vec!["unknown".to_string()]),
+                mode: CanisterInstallMode::Upgrade(None),
+                module: vec![1, 2, 3],
+                module_extra_chunks: None,
+                arg: None,
+                canister_args: vec![],
+                canary_canister_ids: vec![],
+            })
+            .await;
+
+        assert!(matches!(result, Err(RequestError::ValidationError { .. })));
+    }
+}
@@ -0,0 +1,65 @@
+use super::{scheduler::Scheduler, JobType, ScheduledJob};
+use crate::{
+    core::{
+        ic_cdk::{api::print, next_time},
+        read_system_state, REQUEST_ARCHIVE_JOB_INTERVAL_NS,
+    },
+    models::SystemState,
+    services::RequestArchiveService,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, Default)]
+pub struct Job {
+    request_archive_service: RequestArchiveService,
+}
+
+#[async_trait]
+impl ScheduledJob for Job {
+    const JOB_TYPE: JobType = JobType::ArchiveRequests;
+    async fn run() -> bool {
+        Self::default().archive_requests().await
+    }
+}
+
+/// This job is responsible for moving finalized requests older than the configured minimum age
+/// to the request archive canister, it reschedules itself while archival is enabled.
+impl Job {
+    async fn archive_requests(&self) -> bool {
+        match self.request_archive_service.archive_requests().await {
+            // more requests are pending archival, the scheduler runs the job again shortly
+            Ok(false) => return false,
+            Ok(true) => {}
+            Err(err) => {
+                print(format!("Failed to archive requests: {err}"));
+            }
+        }
+
+        if is_request_archival_enabled() {
+            schedule_next_run();
+        }
+
+        true
+    }
+}
+
+fn is_request_archival_enabled() -> bool {
+    match read_system_state() {
+        SystemState::Initialized(system_info) => system_info
+            .get_request_archive()
+            .is_some_and(|archive| archive.min_age_secs.is_some()),
+        SystemState::Uninitialized => false,
+    }
+}
+
+fn schedule_next_run() {
+    Scheduler::schedule::<Job>(next_time().saturating_add(REQUEST_ARCHIVE_JOB_INTERVAL_NS));
+}
+
+/// Schedules the request archival if it is enabled, safe to call multiple times since
+/// runs scheduled within the same job tolerance window share the same timer.
+pub fn schedule_archival() {
+    if is_request_archival_enabled() {
+        schedule_next_run();
+    }
+}
@@ -0,0 +1,43 @@
+use super::{scheduler::Scheduler, JobType, ScheduledJob};
+use crate::services::{ExternalCanisterBackupService, EXTERNAL_CANISTER_BACKUP_SERVICE};
+use async_trait::async_trait;
+
+#[derive(Debug, Default)]
+pub struct Job {
+    external_canister_backup_service: ExternalCanisterBackupService,
+}
+
+#[async_trait]
+impl ScheduledJob for Job {
+    const JOB_TYPE: JobType = JobType::BackupExternalCanisters;
+    async fn run() -> bool {
+        Self::default().backup_external_canisters().await
+    }
+}
+
+/// This job is responsible for taking the periodic backup snapshots of external canisters,
+/// it reschedules itself for the next backup that is due.
+impl Job {
+    async fn backup_external_canisters(&self) -> bool {
+        if let Some(next_backup_at) = self
+            .external_canister_backup_service
+            .run_due_backups()
+            .await
+        {
+            schedule_backups(next_backup_at);
+        }
+
+        true
+    }
+}
+
+pub fn schedule_backups(at_ns: u64) {
+    Scheduler::schedule::<Job>(at_ns);
+}
+
+/// Schedules the job for the next backup that is due, if any.
+pub fn schedule_next_backups() {
+    if let Some(next_backup_at) = EXTERNAL_CANISTER_BACKUP_SERVICE.next_backup_at() {
+        schedule_backups(next_backup_at);
+    }
+}
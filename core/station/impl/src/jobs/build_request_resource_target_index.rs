@@ -0,0 +1,58 @@
+use super::{scheduler::Scheduler, JobType, ScheduledJob};
+use crate::{
+    core::{ic_cdk::next_time, read_system_info, write_system_info},
+    models::{DataMigration, RequestId},
+    repositories::REQUEST_REPOSITORY,
+};
+use async_trait::async_trait;
+use std::cell::RefCell;
+
+/// The maximum number of requests that are added to the resource target index per run.
+const BATCH_SIZE: usize = 500;
+
+thread_local! {
+    /// The id of the next request to add to the resource target index, the build starts over after an
+    /// upgrade since adding a request that is already indexed has no effect.
+    static NEXT_REQUEST_ID: RefCell<Option<RequestId>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug)]
+pub struct Job;
+
+#[async_trait]
+impl ScheduledJob for Job {
+    const JOB_TYPE: JobType = JobType::BuildRequestResourceTargetIndex;
+    async fn run() -> bool {
+        Self::build_request_resource_target_index()
+    }
+}
+
+/// This job is responsible for adding the requests that were created before the resource target index
+/// existed to the index, it runs until all of them are indexed and then marks the data migration as applied.
+impl Job {
+    fn build_request_resource_target_index() -> bool {
+        let from = NEXT_REQUEST_ID.with(|next_request_id| *next_request_id.borrow());
+
+        match REQUEST_REPOSITORY.build_resource_target_index(from, BATCH_SIZE) {
+            Some(next_request_id) => {
+                NEXT_REQUEST_ID.with(|next| *next.borrow_mut() = Some(next_request_id));
+
+                // more requests are pending, the scheduler runs the job again shortly
+                false
+            }
+            None => {
+                let mut system_info = read_system_info();
+                system_info.set_data_migration_applied(DataMigration::RequestResourceTargetIndex);
+                write_system_info(system_info);
+
+                true
+            }
+        }
+    }
+}
+
+/// Schedules the build of the resource target index, safe to call multiple times since runs scheduled
+/// within the same job tolerance window share the same timer.
+pub fn schedule_build() {
+    Scheduler::schedule::<Job>(next_time());
+}
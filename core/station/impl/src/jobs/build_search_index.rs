@@ -0,0 +1,71 @@
+use super::{scheduler::Scheduler, JobType, ScheduledJob};
+use crate::{
+    core::{ic_cdk::next_time, read_system_info, write_system_info},
+    models::{indexes::search_index::SearchIndexKind, DataMigration},
+    repositories::{ADDRESS_BOOK_REPOSITORY, EXTERNAL_CANISTER_REPOSITORY, REQUEST_REPOSITORY},
+};
+use async_trait::async_trait;
+use orbit_essentials::types::UUID;
+use std::cell::RefCell;
+
+/// The maximum number of entries that are added to the search index per run.
+const BATCH_SIZE: usize = 500;
+
+thread_local! {
+    /// The kind and id of the next entry to add to the search index, the build starts over after an upgrade
+    /// since adding an entry that is already indexed has no effect.
+    static NEXT_ENTRY: RefCell<(SearchIndexKind, Option<UUID>)> = const { RefCell::new((SearchIndexKind::Request, None)) };
+}
+
+#[derive(Debug)]
+pub struct Job;
+
+#[async_trait]
+impl ScheduledJob for Job {
+    const JOB_TYPE: JobType = JobType::BuildSearchIndex;
+    async fn run() -> bool {
+        Self::build_search_index()
+    }
+}
+
+/// This job is responsible for adding the entries that were created before the search index existed
+/// to the index, it runs until all of them are indexed and then marks the data migration as applied.
+impl Job {
+    fn build_search_index() -> bool {
+        let (kind, from) = NEXT_ENTRY.with(|next_entry| *next_entry.borrow());
+
+        let next_id = match kind {
+            SearchIndexKind::Request => REQUEST_REPOSITORY.build_search_index(from, BATCH_SIZE),
+            SearchIndexKind::AddressBookEntry => {
+                ADDRESS_BOOK_REPOSITORY.build_search_index(from, BATCH_SIZE)
+            }
+            SearchIndexKind::ExternalCanister => {
+                EXTERNAL_CANISTER_REPOSITORY.build_search_index(from, BATCH_SIZE)
+            }
+        };
+
+        let next_entry = match (kind, next_id) {
+            (_, Some(id)) => (kind, Some(id)),
+            (SearchIndexKind::Request, None) => (SearchIndexKind::AddressBookEntry, None),
+            (SearchIndexKind::AddressBookEntry, None) => (SearchIndexKind::ExternalCanister, None),
+            (SearchIndexKind::ExternalCanister, None) => {
+                let mut system_info = read_system_info();
+                system_info.set_data_migration_applied(DataMigration::SearchIndex);
+                write_system_info(system_info);
+
+                return true;
+            }
+        };
+
+        NEXT_ENTRY.with(|next| *next.borrow_mut() = next_entry);
+
+        // more entries are pending, the scheduler runs the job again shortly
+        false
+    }
+}
+
+/// Schedules the build of the search index, safe to call multiple times since runs scheduled within
+/// the same job tolerance window share the same timer.
+pub fn schedule_build() {
+    Scheduler::schedule::<Job>(next_time());
+}
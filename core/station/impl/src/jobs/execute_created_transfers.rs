@@ -1,6 +1,9 @@
 use super::{scheduler::Scheduler, JobType, ScheduledJob};
 use crate::{
-    core::ic_cdk::{api::print, next_time},
+    core::{
+        ic_cdk::{api::print, next_time},
+        read_system_info,
+    },
     errors::TransferError,
     factories::blockchains::{
         BlockchainApiFactory, BlockchainTransactionSubmitted,
@@ -46,7 +49,13 @@ impl Job {
     /// Executes all the transfers that have been created but are not yet submitted to the blockchain.
     ///
     /// This function will process a maximum of `MAX_BATCH_SIZE` transfers at once.
+    ///
+    /// No transfers are submitted while the station is frozen, they are resumed once it is unfrozen.
     async fn execute_created_transfers(&self) -> bool {
+        if read_system_info().is_frozen() {
+            return true;
+        }
+
         let current_time = next_time();
         let mut transfers = self.transfer_repository.find_by_status(
             TransferStatus::Created.to_string(),
@@ -1,6 +1,6 @@
 use super::{scheduler::Scheduler, JobType, ScheduledJob};
 use crate::{
-    core::ic_cdk::next_time,
+    core::{ic_cdk::next_time, read_system_info},
     errors::RequestExecuteError,
     models::{Request, RequestStatus},
     repositories::RequestRepository,
@@ -9,6 +9,7 @@ use crate::{
 use async_trait::async_trait;
 use futures::future;
 use orbit_essentials::cdk::{call, id};
+use orbit_essentials::repository::Repository;
 
 #[derive(Debug, Default)]
 pub struct Job {
@@ -38,12 +39,19 @@ impl Job {
     /// This function will process a maximum of `MAX_BATCH_SIZE` requests at once.
     ///
     /// At any point in time, at most `MAX_PROCESSING_REQUESTS` requests can be processing at the same time.
+    ///
+    /// While the station is frozen, the requests with operations that are blocked by the freeze are left
+    /// scheduled and are picked up again once the station is unfrozen.
     async fn execute_scheduled_requests(&self) -> bool {
         let current_time = next_time();
         let mut requests = self
             .request_repository
             .find_scheduled(None, Some(current_time));
 
+        if read_system_info().is_frozen() {
+            requests.retain(|request| !request.operation.is_blocked_by_freeze());
+        }
+
         let num_processing_requests = self.request_repository.get_num_processing();
         let batch_size = std::cmp::min(
             Self::MAX_PROCESSING_REQUESTS.saturating_sub(num_processing_requests),
@@ -79,6 +87,11 @@ impl Job {
             match result {
                 Ok(()) => (),
                 Err((request, e)) => {
+                    // the operation might have stored its progress while executing, which must be kept
+                    let request = self
+                        .request_repository
+                        .get(&request.to_key())
+                        .unwrap_or(request);
                     let request_failed_time = next_time();
                     self.request_service
                         .fail_request(request, e.to_string(), request_failed_time)
@@ -15,9 +15,15 @@ use crate::{
 };
 use async_trait::async_trait;
 
+mod archive_requests;
+mod backup_external_canisters;
+mod build_request_resource_target_index;
+mod build_search_index;
 mod cancel_expired_requests;
 mod execute_created_transfers;
 mod execute_scheduled_requests;
+mod reconcile_external_canister_controllers;
+mod sample_external_canister_cycles;
 mod scheduler;
 
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
@@ -25,6 +31,12 @@ pub enum JobType {
     CancelExpiredRequests,
     ExecuteScheduledRequests,
     ExecuteCreatedTransfers,
+    ArchiveRequests,
+    BackupExternalCanisters,
+    SampleExternalCanisterCycles,
+    ReconcileExternalCanisterControllers,
+    BuildSearchIndex,
+    BuildRequestResourceTargetIndex,
 }
 
 #[async_trait]
@@ -257,6 +269,60 @@ pub fn jobs_observe_insert_transfer(observer: &mut Observer<(Transfer, Option<Tr
     }));
 }
 
+/// Schedules the next run of the request archival, only has an effect if the archival is enabled.
+pub fn schedule_request_archival() {
+    archive_requests::schedule_archival();
+}
+
+/// Schedules a run of the external canister backups at the given time, the job reschedules itself
+/// for the next backup that is due.
+pub fn schedule_external_canister_backups(at_ns: u64) {
+    backup_external_canisters::schedule_backups(at_ns);
+}
+
+/// Schedules the cycles balance sampling of the monitored external canisters, the job reschedules
+/// itself for as long as any external canister is monitored.
+pub fn schedule_external_canister_cycles_sampling() {
+    sample_external_canister_cycles::schedule_sampling();
+}
+
+/// Schedules the reconciliation of the external canister controllers, the job reschedules itself
+/// for as long as any external canister is active.
+pub fn schedule_external_canister_controllers_reconciliation() {
+    reconcile_external_canister_controllers::schedule_reconciliation();
+}
+
+/// Schedules the build of the search index for the entries that were created before it existed, the job
+/// reschedules itself until all entries are indexed.
+pub fn schedule_search_index_build() {
+    build_search_index::schedule_build();
+}
+
+/// Schedules the build of the request resource target index for the requests that were created before it
+/// existed, the job reschedules itself until all requests are indexed.
+pub fn schedule_request_resource_target_index_build() {
+    build_request_resource_target_index::schedule_build();
+}
+
+/// Restarts the execution of the requests and transfers that were held back while the station was frozen.
+pub fn resume_frozen_jobs() {
+    let current_time = next_time();
+
+    if !REQUEST_REPOSITORY
+        .find_scheduled(None, Some(current_time))
+        .is_empty()
+    {
+        execute_scheduled_requests::schedule_request_execution(current_time);
+    }
+
+    if !TRANSFER_REPOSITORY
+        .find_by_status(TransferStatus::Created.to_string(), None, None)
+        .is_empty()
+    {
+        execute_created_transfers::schedule_process_transfers(current_time);
+    }
+}
+
 pub fn initialize_job_timers() {
     // start the expiration timer for each request that is in Created state
     for request in REQUEST_REPOSITORY.find_by_status(RequestStatusCode::Created, None, None) {
@@ -282,6 +348,18 @@ pub fn initialize_job_timers() {
         // kick off execution timer for Transfers, once is enough
         execute_created_transfers::schedule_process_transfers(next_time());
     }
+
+    // the archival job reschedules itself for as long as it is enabled
+    archive_requests::schedule_archival();
+
+    // the backup job reschedules itself for the next backup that is due
+    backup_external_canisters::schedule_next_backups();
+
+    // the sampling job reschedules itself while external canisters are monitored
+    sample_external_canister_cycles::schedule_sampling();
+
+    // the reconciliation job reschedules itself while external canisters are active
+    reconcile_external_canister_controllers::schedule_reconciliation();
 }
 
 #[cfg(test)]
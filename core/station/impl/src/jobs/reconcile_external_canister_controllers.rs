@@ -0,0 +1,55 @@
+use super::{scheduler::Scheduler, JobType, ScheduledJob};
+use crate::{
+    core::{ic_cdk::next_time, EXTERNAL_CANISTER_CONTROLLERS_RECONCILIATION_INTERVAL_NS},
+    services::{ExternalCanisterControllersService, EXTERNAL_CANISTER_CONTROLLERS_SERVICE},
+};
+use async_trait::async_trait;
+
+#[derive(Debug, Default)]
+pub struct Job {
+    external_canister_controllers_service: ExternalCanisterControllersService,
+}
+
+#[async_trait]
+impl ScheduledJob for Job {
+    const JOB_TYPE: JobType = JobType::ReconcileExternalCanisterControllers;
+    // runs scheduled within the same interval share the same timer
+    const JOB_TOLERANCE_NS: u64 = EXTERNAL_CANISTER_CONTROLLERS_RECONCILIATION_INTERVAL_NS;
+    async fn run() -> bool {
+        Self::default()
+            .reconcile_external_canister_controllers()
+            .await
+    }
+}
+
+/// This job is responsible for detecting the controller changes of external canisters made outside
+/// of the station, it reschedules itself for as long as any external canister is active.
+impl Job {
+    async fn reconcile_external_canister_controllers(&self) -> bool {
+        self.external_canister_controllers_service
+            .reconcile_controllers()
+            .await;
+
+        if self
+            .external_canister_controllers_service
+            .has_active_canisters()
+        {
+            schedule_next_run();
+        }
+
+        true
+    }
+}
+
+fn schedule_next_run() {
+    Scheduler::schedule::<Job>(
+        next_time().saturating_add(EXTERNAL_CANISTER_CONTROLLERS_RECONCILIATION_INTERVAL_NS),
+    );
+}
+
+/// Schedules the reconciliation if any external canister is active, safe to call multiple times.
+pub fn schedule_reconciliation() {
+    if EXTERNAL_CANISTER_CONTROLLERS_SERVICE.has_active_canisters() {
+        schedule_next_run();
+    }
+}
@@ -0,0 +1,51 @@
+use super::{scheduler::Scheduler, JobType, ScheduledJob};
+use crate::{
+    core::{ic_cdk::next_time, EXTERNAL_CANISTER_CYCLES_SAMPLING_INTERVAL_NS},
+    services::{ExternalCanisterCyclesService, EXTERNAL_CANISTER_CYCLES_SERVICE},
+};
+use async_trait::async_trait;
+
+#[derive(Debug, Default)]
+pub struct Job {
+    external_canister_cycles_service: ExternalCanisterCyclesService,
+}
+
+#[async_trait]
+impl ScheduledJob for Job {
+    const JOB_TYPE: JobType = JobType::SampleExternalCanisterCycles;
+    // runs scheduled within the same interval share the same timer
+    const JOB_TOLERANCE_NS: u64 = EXTERNAL_CANISTER_CYCLES_SAMPLING_INTERVAL_NS;
+    async fn run() -> bool {
+        Self::default().sample_external_canister_cycles()
+    }
+}
+
+/// This job is responsible for recording the cycles balances of the monitored external canisters,
+/// it reschedules itself for as long as any canister is monitored.
+impl Job {
+    fn sample_external_canister_cycles(&self) -> bool {
+        self.external_canister_cycles_service.record_samples();
+
+        if self
+            .external_canister_cycles_service
+            .has_monitored_canisters()
+        {
+            schedule_next_run();
+        }
+
+        true
+    }
+}
+
+fn schedule_next_run() {
+    Scheduler::schedule::<Job>(
+        next_time().saturating_add(EXTERNAL_CANISTER_CYCLES_SAMPLING_INTERVAL_NS),
+    );
+}
+
+/// Schedules the sampling if any external canister is monitored, safe to call multiple times.
+pub fn schedule_sampling() {
+    if EXTERNAL_CANISTER_CYCLES_SERVICE.has_monitored_canisters() {
+        schedule_next_run();
+    }
+}
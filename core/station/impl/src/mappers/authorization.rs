@@ -16,7 +16,8 @@ use crate::{
 use orbit_essentials::repository::Repository;
 use orbit_essentials::types::UUID;
 use station_api::{
-    CanisterSnapshotsInput, CanisterStatusInput, RequestOperationInput, UserPrivilege,
+    CanisterSnapshotsInput, CanisterStatusInput, QueryExternalCanisterInput, RequestOperationInput,
+    UserPrivilege,
 };
 
 pub const USER_PRIVILEGES: [UserPrivilege; 23] = [
@@ -280,6 +281,21 @@ impl From<&station_api::CreateRequestInput> for Resource {
                     ExternalCanisterId::Canister(input.canister_id),
                 ))
             }
+            // A fleet upgrade can target any external canister, so it requires the privilege to change all of them
+            RequestOperationInput::UpgradeExternalCanisterFleet(_) => Resource::ExternalCanister(
+                ExternalCanisterResourceAction::Change(ExternalCanisterId::Any),
+            ),
+            // Releasing the control of external canisters requires its own privilege since it can't be reverted
+            RequestOperationInput::ConfigureExternalCanister(input)
+                if matches!(
+                    input.kind,
+                    station_api::ConfigureExternalCanisterOperationKindDTO::ReleaseControl(_)
+                ) =>
+            {
+                Resource::ExternalCanister(ExternalCanisterResourceAction::ReleaseControl(
+                    ExternalCanisterId::Canister(input.canister_id),
+                ))
+            }
             // Configuration of external canisters share the same `Change` action privilege
             RequestOperationInput::ConfigureExternalCanister(input) => {
                 Resource::ExternalCanister(ExternalCanisterResourceAction::Change(
@@ -346,7 +362,9 @@ impl From<&station_api::CreateRequestInput> for Resource {
                         .as_bytes(),
                 )))
             }
-            RequestOperationInput::ManageSystemInfo(_) => {
+            RequestOperationInput::ManageSystemInfo(_)
+            | RequestOperationInput::FreezeStation(_)
+            | RequestOperationInput::UnfreezeStation(_) => {
                 Resource::System(SystemResourceAction::ManageSystemInfo)
             }
             RequestOperationInput::AddAsset(_) => Resource::Asset(ResourceAction::Create),
@@ -396,6 +414,23 @@ impl From<&CanisterSnapshotsInput> for Resource {
     }
 }
 
+// Read-only calls share the privilege to request calls of the method without a validation method
+impl From<&QueryExternalCanisterInput> for Resource {
+    fn from(input: &QueryExternalCanisterInput) -> Self {
+        let execution_method = CanisterMethod {
+            canister_id: input.canister_id,
+            method_name: input.method_name.clone(),
+        };
+
+        Resource::ExternalCanister(ExternalCanisterResourceAction::Call(
+            CallExternalCanisterResourceTarget {
+                validation_method: ValidationMethodResourceTarget::No,
+                execution_method: execution_method.into(),
+            },
+        ))
+    }
+}
+
 impl From<&CanisterStatusInput> for Resource {
     fn from(input: &CanisterStatusInput) -> Self {
         let canister_id = input.canister_id;
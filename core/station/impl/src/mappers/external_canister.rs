@@ -3,14 +3,16 @@ use crate::{
     models::{
         ConfigureExternalCanisterOperationInput, ConfigureExternalCanisterOperationKind,
         ConfigureExternalCanisterSettingsInput, CreateExternalCanisterOperationInput,
-        DefiniteCanisterSettingsInput, ExternalCanister, ExternalCanisterCallRequestPolicyRule,
+        DefiniteCanisterSettingsInput, ExternalCanister, ExternalCanisterBackup,
+        ExternalCanisterBackupSchedule, ExternalCanisterCallRequestPolicyRule,
         ExternalCanisterCallerMethodsPrivileges, ExternalCanisterCallerPrivileges,
-        ExternalCanisterChangeRequestPolicyRule, ExternalCanisterPermissions,
-        ExternalCanisterRequestPolicies, ExternalCanisterState, FundExternalCanisterOperation,
-        FundExternalCanisterOperationInput, FundExternalCanisterOperationKind,
-        FundExternalCanisterSendCyclesInput, LogVisibility, MonitorExternalCanisterOperationInput,
-        MonitorExternalCanisterOperationKind, MonitorExternalCanisterStartInput,
-        MonitorExternalCanisterStrategy, MonitoringExternalCanisterCyclesThresholdInput,
+        ExternalCanisterChangeRequestPolicyRule, ExternalCanisterCyclesForecast,
+        ExternalCanisterPermissions, ExternalCanisterRequestPolicies, ExternalCanisterState,
+        FundExternalCanisterOperation, FundExternalCanisterOperationInput,
+        FundExternalCanisterOperationKind, FundExternalCanisterSendCyclesInput, LogVisibility,
+        MonitorExternalCanisterOperationInput, MonitorExternalCanisterOperationKind,
+        MonitorExternalCanisterStartInput, MonitorExternalCanisterStrategy,
+        MonitoringExternalCanisterCyclesThresholdInput,
         MonitoringExternalCanisterEstimatedRuntimeInput,
     },
     repositories::ExternalCanisterWhereClauseSort,
@@ -41,6 +43,8 @@ impl ExternalCanisterMapper {
             created_at: next_time(),
             modified_at: None,
             monitoring: None,
+            backup: None,
+            read_only_methods: Vec::new(),
         }
     }
 }
@@ -64,6 +68,8 @@ impl ExternalCanister {
             created_at: timestamp_to_rfc3339(&self.created_at),
             modified_at: self.modified_at.map(|ts| timestamp_to_rfc3339(&ts)),
             monitoring: self.monitoring.map(Into::into),
+            backup: self.backup.map(Into::into),
+            read_only_methods: self.read_only_methods,
         }
     }
 }
@@ -120,6 +126,7 @@ impl From<ExternalCanisterCallerPrivileges> for station_api::ExternalCanisterCal
             canister_id: privileges.canister_id,
             can_change: privileges.can_change,
             can_fund: privileges.can_fund,
+            can_read_logs: privileges.can_read_logs,
             can_call: privileges.can_call.into_iter().map(Into::into).collect(),
         }
     }
@@ -205,6 +212,61 @@ impl From<station_api::ConfigureExternalCanisterOperationKindDTO>
             station_api::ConfigureExternalCanisterOperationKindDTO::Settings(settings) => {
                 ConfigureExternalCanisterOperationKind::Settings(settings.into())
             }
+            station_api::ConfigureExternalCanisterOperationKindDTO::BackupSchedule(schedule) => {
+                ConfigureExternalCanisterOperationKind::BackupSchedule(schedule.map(Into::into))
+            }
+            station_api::ConfigureExternalCanisterOperationKindDTO::CandidInterface(candid) => {
+                ConfigureExternalCanisterOperationKind::CandidInterface(candid)
+            }
+            station_api::ConfigureExternalCanisterOperationKindDTO::AddControllers(controllers) => {
+                ConfigureExternalCanisterOperationKind::AddControllers(controllers)
+            }
+            station_api::ConfigureExternalCanisterOperationKindDTO::RemoveControllers(
+                controllers,
+            ) => ConfigureExternalCanisterOperationKind::RemoveControllers(controllers),
+            station_api::ConfigureExternalCanisterOperationKindDTO::ReleaseControl(controllers) => {
+                ConfigureExternalCanisterOperationKind::ReleaseControl(controllers)
+            }
+            station_api::ConfigureExternalCanisterOperationKindDTO::ReadOnlyMethods(methods) => {
+                ConfigureExternalCanisterOperationKind::ReadOnlyMethods(methods)
+            }
+        }
+    }
+}
+
+impl From<station_api::ExternalCanisterBackupScheduleInput> for ExternalCanisterBackupSchedule {
+    fn from(input: station_api::ExternalCanisterBackupScheduleInput) -> Self {
+        ExternalCanisterBackupSchedule {
+            interval_secs: input.interval_secs,
+            before_change: input.before_change,
+            retention_count: input.retention_count,
+        }
+    }
+}
+
+impl From<ExternalCanisterBackupSchedule> for station_api::ExternalCanisterBackupScheduleInput {
+    fn from(schedule: ExternalCanisterBackupSchedule) -> Self {
+        station_api::ExternalCanisterBackupScheduleInput {
+            interval_secs: schedule.interval_secs,
+            before_change: schedule.before_change,
+            retention_count: schedule.retention_count,
+        }
+    }
+}
+
+impl From<ExternalCanisterBackup> for station_api::ExternalCanisterBackupDTO {
+    fn from(backup: ExternalCanisterBackup) -> Self {
+        station_api::ExternalCanisterBackupDTO {
+            schedule: backup.schedule.into(),
+            snapshot_ids: backup.snapshot_ids.iter().map(hex::encode).collect(),
+            last_backup_at: backup.last_backup_at.map(|ts| timestamp_to_rfc3339(&ts)),
+            last_failure: backup.last_failure.map(|failure| {
+                station_api::ExternalCanisterBackupFailureDTO {
+                    failed_at: timestamp_to_rfc3339(&failure.failed_at),
+                    reason: failure.reason,
+                }
+            }),
+            next_backup_at: backup.next_backup_at.map(|ts| timestamp_to_rfc3339(&ts)),
         }
     }
 }
@@ -517,3 +579,41 @@ impl From<MonitoringExternalCanisterEstimatedRuntimeInput> for EstimatedRuntime
             .with_fallback_min_cycles(input.fallback_min_cycles)
     }
 }
+
+impl From<ExternalCanisterCyclesForecast>
+    for station_api::GetExternalCanisterCyclesHistoryResponse
+{
+    fn from(forecast: ExternalCanisterCyclesForecast) -> Self {
+        let projected_depletion_dt = match (forecast.samples.last(), forecast.projected_runway_secs)
+        {
+            (Some((sampled_at, _)), Some(runway_secs)) => Some(timestamp_to_rfc3339(
+                &sampled_at.saturating_add(runway_secs.saturating_mul(1_000_000_000)),
+            )),
+            _ => None,
+        };
+
+        station_api::GetExternalCanisterCyclesHistoryResponse {
+            samples: forecast
+                .samples
+                .into_iter()
+                .map(
+                    |(sampled_at, cycles)| station_api::ExternalCanisterCyclesSampleDTO {
+                        sampled_at: timestamp_to_rfc3339(&sampled_at),
+                        cycles,
+                    },
+                )
+                .collect(),
+            top_ups: forecast
+                .top_ups
+                .into_iter()
+                .map(|top_up| station_api::ExternalCanisterTopUpDTO {
+                    detected_at: timestamp_to_rfc3339(&top_up.detected_at),
+                    cycles: top_up.cycles,
+                })
+                .collect(),
+            burn_rate_cycles_per_day: forecast.burn_rate_cycles_per_day,
+            projected_runway_secs: forecast.projected_runway_secs,
+            projected_depletion_dt,
+        }
+    }
+}
@@ -104,7 +104,10 @@ impl TryFrom<NotificationType> for NotificationTypeDTO {
                     | RequestOperation::RemoveAsset(_)
                     | RequestOperation::AddNamedRule(_)
                     | RequestOperation::EditNamedRule(_)
-                    | RequestOperation::RemoveNamedRule(_) => None,
+                    | RequestOperation::RemoveNamedRule(_)
+                    | RequestOperation::FreezeStation(_)
+                    | RequestOperation::UnfreezeStation(_)
+                    | RequestOperation::UpgradeExternalCanisterFleet(_) => None,
                 };
 
                 let user_id: Option<[u8; 16]> = match &request.operation {
@@ -141,7 +144,10 @@ impl TryFrom<NotificationType> for NotificationTypeDTO {
                     | RequestOperation::RemoveAsset(_)
                     | RequestOperation::AddNamedRule(_)
                     | RequestOperation::EditNamedRule(_)
-                    | RequestOperation::RemoveNamedRule(_) => None,
+                    | RequestOperation::RemoveNamedRule(_)
+                    | RequestOperation::FreezeStation(_)
+                    | RequestOperation::UnfreezeStation(_)
+                    | RequestOperation::UpgradeExternalCanisterFleet(_) => None,
                 };
 
                 NotificationTypeDTO::RequestCreated(RequestCreatedNotificationDTO {
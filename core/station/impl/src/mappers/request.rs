@@ -1,7 +1,10 @@
 use crate::{
     core::ic_cdk::next_time,
+    errors::MapperError,
+    mappers::HelperMapper,
     models::{
-        Request, RequestAdditionalInfo, RequestCallerPrivileges, RequestExecutionPlan,
+        indexes::request_resource_index::RequestResourceFilter, Request, RequestAdditionalInfo,
+        RequestCallerPrivileges, RequestDiff, RequestExecutionPlan, RequestFieldDiff,
         RequestOperation, RequestStatus, UserId,
     },
 };
@@ -10,7 +13,8 @@ use orbit_essentials::{
     utils::{rfc3339_to_timestamp, timestamp_to_rfc3339},
 };
 use station_api::{
-    CallExternalCanisterOperationDTO, RequestDTO, RequestExecutionScheduleDTO, RequestOperationDTO,
+    CallExternalCanisterOperationDTO, ListRequestsResourceFilterDTO, RequestDTO, RequestDiffDTO,
+    RequestExecutionScheduleDTO, RequestFieldDiffDTO, RequestOperationDTO,
 };
 use uuid::Uuid;
 
@@ -170,3 +174,49 @@ impl From<RequestAdditionalInfo> for station_api::RequestAdditionalInfoDTO {
         }
     }
 }
+
+impl From<RequestFieldDiff> for RequestFieldDiffDTO {
+    fn from(diff: RequestFieldDiff) -> Self {
+        Self {
+            field: diff.field,
+            before: diff.before,
+            after: diff.after,
+            current: diff.current,
+        }
+    }
+}
+
+impl From<RequestDiff> for RequestDiffDTO {
+    fn from(diff: RequestDiff) -> Self {
+        Self {
+            fields: diff.fields.into_iter().map(Into::into).collect(),
+            snapshot_taken_at: timestamp_to_rfc3339(&diff.snapshot_taken_at),
+            executed_at: diff
+                .executed_at
+                .map(|executed_at| timestamp_to_rfc3339(&executed_at)),
+            is_stale: diff.is_stale,
+        }
+    }
+}
+
+impl TryFrom<ListRequestsResourceFilterDTO> for RequestResourceFilter {
+    type Error = MapperError;
+
+    fn try_from(filter: ListRequestsResourceFilterDTO) -> Result<Self, Self::Error> {
+        let to_id = |id: String| HelperMapper::to_uuid(id).map(|uuid| *uuid.as_bytes());
+
+        Ok(match filter {
+            ListRequestsResourceFilterDTO::Account(id) => {
+                RequestResourceFilter::Account(to_id(id)?)
+            }
+            ListRequestsResourceFilterDTO::User(id) => RequestResourceFilter::User(to_id(id)?),
+            ListRequestsResourceFilterDTO::ExternalCanister(canister_id) => {
+                RequestResourceFilter::ExternalCanister(canister_id)
+            }
+            ListRequestsResourceFilterDTO::Asset(id) => RequestResourceFilter::Asset(to_id(id)?),
+            ListRequestsResourceFilterDTO::RequestPolicy(id) => {
+                RequestResourceFilter::RequestPolicy(to_id(id)?)
+            }
+        })
+    }
+}
@@ -23,12 +23,12 @@ use crate::{
         CreateExternalCanisterOperation, CreateExternalCanisterOperationInput,
         CreateExternalCanisterOperationKind, CreateExternalCanisterOperationKindAddExisting,
         CreateExternalCanisterOperationKindCreateNew, CycleObtainStrategy,
-        DefiniteCanisterSettingsInput, DisasterRecoveryCommittee, EditAccountOperation,
-        EditAccountOperationInput, EditAddressBookEntryOperation, EditAssetOperation,
-        EditAssetOperationInput, EditNamedRuleOperation, EditNamedRuleOperationInput,
-        EditPermissionOperation, EditPermissionOperationInput, EditRequestPolicyOperation,
-        EditRequestPolicyOperationInput, EditUserGroupOperation, EditUserOperation,
-        EditUserOperationInput, ExternalCanisterCallPermission,
+        DefiniteCanisterSettingsInput, DeployRequestArchiveInput, DisasterRecoveryCommittee,
+        EditAccountOperation, EditAccountOperationInput, EditAddressBookEntryOperation,
+        EditAssetOperation, EditAssetOperationInput, EditNamedRuleOperation,
+        EditNamedRuleOperationInput, EditPermissionOperation, EditPermissionOperationInput,
+        EditRequestPolicyOperation, EditRequestPolicyOperationInput, EditUserGroupOperation,
+        EditUserOperation, EditUserOperationInput, ExternalCanisterCallPermission,
         ExternalCanisterCallPermissionExecMethodEntryInput,
         ExternalCanisterCallPermissionMethodPairInput,
         ExternalCanisterCallPermissionsExecMethodInput,
@@ -37,21 +37,27 @@ use crate::{
         ExternalCanisterCallRequestPolicyRuleInput,
         ExternalCanisterCallRequestPolicyRuleValidationInput,
         ExternalCanisterChangeCallPermissionsInput, ExternalCanisterChangeCallRequestPoliciesInput,
-        ExternalCanisterChangeRequestPolicyRuleInput, ExternalCanisterPermissionsCreateInput,
+        ExternalCanisterChangeRequestPolicyRuleInput, ExternalCanisterFleetArgInput,
+        ExternalCanisterFleetTarget, ExternalCanisterPermissionsCreateInput,
         ExternalCanisterPermissionsUpdateInput, ExternalCanisterRequestPoliciesCreateInput,
-        ExternalCanisterRequestPoliciesUpdateInput, FundExternalCanisterOperation, LogVisibility,
-        ManageSystemInfoOperation, ManageSystemInfoOperationInput,
-        MonitorExternalCanisterOperation, NamedRule, NamedRuleKey, PruneExternalCanisterOperation,
-        PruneExternalCanisterOperationInput, PruneExternalCanisterResource,
-        RemoveAddressBookEntryOperation, RemoveAssetOperation, RemoveAssetOperationInput,
-        RemoveNamedRuleOperation, RemoveNamedRuleOperationInput, RemoveRequestPolicyOperation,
-        RemoveRequestPolicyOperationInput, RemoveUserGroupOperation, RequestOperation,
+        ExternalCanisterRequestPoliciesUpdateInput, ExternalCanisterRollbackInput,
+        ExternalCanisterUpgradeStatus, FreezeStationOperation, FreezeStationOperationInput,
+        FundExternalCanisterOperation, LogVisibility, ManageSystemInfoOperation,
+        ManageSystemInfoOperationInput, MonitorExternalCanisterOperation, NamedRule, NamedRuleKey,
+        PruneExternalCanisterOperation, PruneExternalCanisterOperationInput,
+        PruneExternalCanisterResource, RemoveAddressBookEntryOperation, RemoveAssetOperation,
+        RemoveAssetOperationInput, RemoveNamedRuleOperation, RemoveNamedRuleOperationInput,
+        RemoveRequestPolicyOperation, RemoveRequestPolicyOperationInput, RemoveUserGroupOperation,
+        RequestArchiveConfigInput, RequestOperation, ResolvedWasmModuleRegistryEntry,
         RestoreExternalCanisterOperation, RestoreExternalCanisterOperationInput,
         SetDisasterRecoveryOperation, SetDisasterRecoveryOperationInput,
         SnapshotExternalCanisterOperation, SnapshotExternalCanisterOperationInput,
         SystemRestoreOperation, SystemRestoreOperationInput, SystemRestoreTarget,
         SystemUpgradeOperation, SystemUpgradeOperationInput, SystemUpgradeTarget,
-        TransferOperation, User, WasmMemoryPersistence, WasmModuleExtraChunks,
+        TransferOperation, UnfreezeStationOperation, UnfreezeStationOperationInput,
+        UpgradeExternalCanisterFleetOperation, UpgradeExternalCanisterFleetOperationInput, User,
+        WasmMemoryPersistence, WasmModuleExtraChunks, WasmModuleRegistryReference,
+        WasmModuleRegistryVersion,
     },
     repositories::{
         AccountRepository, AddressBookRepository, AssetRepository, NamedRuleRepository,
@@ -413,6 +419,71 @@ impl From<WasmModuleExtraChunks> for orbit_essentials::types::WasmModuleExtraChu
     }
 }
 
+impl From<WasmModuleRegistryVersion> for station_api::WasmModuleRegistryVersionDTO {
+    fn from(version: WasmModuleRegistryVersion) -> station_api::WasmModuleRegistryVersionDTO {
+        match version {
+            WasmModuleRegistryVersion::Version(version) => {
+                station_api::WasmModuleRegistryVersionDTO::Version(version)
+            }
+            WasmModuleRegistryVersion::Tag(tag) => {
+                station_api::WasmModuleRegistryVersionDTO::Tag(tag)
+            }
+        }
+    }
+}
+
+impl From<station_api::WasmModuleRegistryVersionDTO> for WasmModuleRegistryVersion {
+    fn from(version: station_api::WasmModuleRegistryVersionDTO) -> WasmModuleRegistryVersion {
+        match version {
+            station_api::WasmModuleRegistryVersionDTO::Version(version) => {
+                WasmModuleRegistryVersion::Version(version)
+            }
+            station_api::WasmModuleRegistryVersionDTO::Tag(tag) => {
+                WasmModuleRegistryVersion::Tag(tag)
+            }
+        }
+    }
+}
+
+impl From<WasmModuleRegistryReference> for station_api::WasmModuleRegistryReferenceDTO {
+    fn from(reference: WasmModuleRegistryReference) -> station_api::WasmModuleRegistryReferenceDTO {
+        station_api::WasmModuleRegistryReferenceDTO {
+            registry_canister_id: reference.registry_canister_id,
+            name: reference.name,
+            version: reference.version.into(),
+        }
+    }
+}
+
+impl From<station_api::WasmModuleRegistryReferenceDTO> for WasmModuleRegistryReference {
+    fn from(reference: station_api::WasmModuleRegistryReferenceDTO) -> WasmModuleRegistryReference {
+        WasmModuleRegistryReference {
+            registry_canister_id: reference.registry_canister_id,
+            name: reference.name,
+            version: reference.version.into(),
+        }
+    }
+}
+
+impl ResolvedWasmModuleRegistryEntry {
+    pub fn to_dto(
+        self,
+        reference: &WasmModuleRegistryReference,
+    ) -> station_api::ResolvedWasmModuleRegistryEntryDTO {
+        station_api::ResolvedWasmModuleRegistryEntryDTO {
+            registry_canister_id: reference.registry_canister_id,
+            name: reference.name.clone(),
+            entry_id: Uuid::from_bytes(self.entry_id).hyphenated().to_string(),
+            version: self.version,
+            wasm_artifact_id: Uuid::from_bytes(self.wasm_artifact_id)
+                .hyphenated()
+                .to_string(),
+            wasm_artifact_hash: hex::encode(self.wasm_artifact_hash),
+            verified_publisher: self.verified_publisher,
+        }
+    }
+}
+
 impl From<SystemUpgradeOperationInput> for station_api::SystemUpgradeOperationInput {
     fn from(input: SystemUpgradeOperationInput) -> station_api::SystemUpgradeOperationInput {
         station_api::SystemUpgradeOperationInput {
@@ -421,6 +492,7 @@ impl From<SystemUpgradeOperationInput> for station_api::SystemUpgradeOperationIn
             module_extra_chunks: input.module_extra_chunks.map(|c| c.into()),
             arg: input.arg,
             take_backup_snapshot: input.take_backup_snapshot,
+            module_registry: input.module_registry.map(|reference| reference.into()),
         }
     }
 }
@@ -433,6 +505,7 @@ impl From<station_api::SystemUpgradeOperationInput> for SystemUpgradeOperationIn
             module_extra_chunks: input.module_extra_chunks.map(|c| c.into()),
             arg: input.arg,
             take_backup_snapshot: input.take_backup_snapshot,
+            module_registry: input.module_registry.map(|reference| reference.into()),
         }
     }
 }
@@ -444,6 +517,10 @@ impl From<SystemUpgradeOperation> for station_api::SystemUpgradeOperationDTO {
             module_checksum: hex::encode(operation.module_checksum),
             arg_checksum: operation.arg_checksum.map(hex::encode),
             take_backup_snapshot: operation.take_backup_snapshot,
+            module_registry_entry: operation
+                .module_registry_entry
+                .zip(operation.input.module_registry.as_ref())
+                .map(|(entry, reference)| entry.to_dto(reference)),
         }
     }
 }
@@ -614,6 +691,38 @@ impl From<SetDisasterRecoveryOperation> for station_api::SetDisasterRecoveryOper
     }
 }
 
+impl From<station_api::FreezeStationOperationInput> for FreezeStationOperationInput {
+    fn from(input: station_api::FreezeStationOperationInput) -> FreezeStationOperationInput {
+        FreezeStationOperationInput {
+            reason: input.reason,
+        }
+    }
+}
+
+impl From<FreezeStationOperation> for station_api::FreezeStationOperationDTO {
+    fn from(operation: FreezeStationOperation) -> station_api::FreezeStationOperationDTO {
+        station_api::FreezeStationOperationDTO {
+            input: station_api::FreezeStationOperationInput {
+                reason: operation.input.reason,
+            },
+        }
+    }
+}
+
+impl From<station_api::UnfreezeStationOperationInput> for UnfreezeStationOperationInput {
+    fn from(_: station_api::UnfreezeStationOperationInput) -> UnfreezeStationOperationInput {
+        UnfreezeStationOperationInput {}
+    }
+}
+
+impl From<UnfreezeStationOperation> for station_api::UnfreezeStationOperationDTO {
+    fn from(_: UnfreezeStationOperation) -> station_api::UnfreezeStationOperationDTO {
+        station_api::UnfreezeStationOperationDTO {
+            input: station_api::UnfreezeStationOperationInput {},
+        }
+    }
+}
+
 // ---
 
 impl From<ChangeExternalCanisterOperationInput>
@@ -628,6 +737,8 @@ impl From<ChangeExternalCanisterOperationInput>
             module: input.module,
             module_extra_chunks: input.module_extra_chunks.map(|c| c.into()),
             arg: input.arg,
+            rollback: input.rollback.map(|rollback| rollback.into()),
+            module_registry: input.module_registry.map(|reference| reference.into()),
         }
     }
 }
@@ -644,6 +755,32 @@ impl From<station_api::ChangeExternalCanisterOperationInput>
             module: input.module,
             module_extra_chunks: input.module_extra_chunks.map(|c| c.into()),
             arg: input.arg,
+            rollback: input.rollback.map(|rollback| rollback.into()),
+            module_registry: input.module_registry.map(|reference| reference.into()),
+        }
+    }
+}
+
+impl From<ExternalCanisterRollbackInput> for station_api::ExternalCanisterRollbackInput {
+    fn from(input: ExternalCanisterRollbackInput) -> station_api::ExternalCanisterRollbackInput {
+        station_api::ExternalCanisterRollbackInput {
+            replace_snapshot: input.replace_snapshot.map(hex::encode),
+            health_check_method: input.health_check_method,
+        }
+    }
+}
+
+impl From<station_api::ExternalCanisterRollbackInput> for ExternalCanisterRollbackInput {
+    fn from(input: station_api::ExternalCanisterRollbackInput) -> ExternalCanisterRollbackInput {
+        ExternalCanisterRollbackInput {
+            replace_snapshot: input.replace_snapshot.map(|snapshot_id| {
+                hex::decode(&snapshot_id).unwrap_or_else(|err| {
+                    ic_cdk::trap(&format!(
+                        "Failed to decode snapshot id {snapshot_id} to hex: {err}"
+                    ))
+                })
+            }),
+            health_check_method: input.health_check_method,
         }
     }
 }
@@ -655,6 +792,110 @@ impl From<ChangeExternalCanisterOperation> for ChangeExternalCanisterOperationDT
             mode: operation.input.mode.into(),
             module_checksum: hex::encode(operation.module_checksum),
             arg_checksum: operation.arg_checksum.map(hex::encode),
+            rollback: operation.input.rollback.map(|rollback| rollback.into()),
+            rollback_snapshot_id: operation.rollback_snapshot_id.map(hex::encode),
+            module_registry_entry: operation
+                .module_registry_entry
+                .zip(operation.input.module_registry.as_ref())
+                .map(|(entry, reference)| entry.to_dto(reference)),
+        }
+    }
+}
+
+impl From<ExternalCanisterFleetTarget> for station_api::ExternalCanisterFleetTargetDTO {
+    fn from(target: ExternalCanisterFleetTarget) -> station_api::ExternalCanisterFleetTargetDTO {
+        match target {
+            ExternalCanisterFleetTarget::Labels(labels) => {
+                station_api::ExternalCanisterFleetTargetDTO::Labels(labels)
+            }
+            ExternalCanisterFleetTarget::CanisterIds(canister_ids) => {
+                station_api::ExternalCanisterFleetTargetDTO::CanisterIds(canister_ids)
+            }
+        }
+    }
+}
+
+impl From<station_api::ExternalCanisterFleetTargetDTO> for ExternalCanisterFleetTarget {
+    fn from(target: station_api::ExternalCanisterFleetTargetDTO) -> ExternalCanisterFleetTarget {
+        match target {
+            station_api::ExternalCanisterFleetTargetDTO::Labels(labels) => {
+                ExternalCanisterFleetTarget::Labels(labels)
+            }
+            station_api::ExternalCanisterFleetTargetDTO::CanisterIds(canister_ids) => {
+                ExternalCanisterFleetTarget::CanisterIds(canister_ids)
+            }
+        }
+    }
+}
+
+impl From<station_api::UpgradeExternalCanisterFleetOperationInput>
+    for UpgradeExternalCanisterFleetOperationInput
+{
+    fn from(
+        input: station_api::UpgradeExternalCanisterFleetOperationInput,
+    ) -> UpgradeExternalCanisterFleetOperationInput {
+        UpgradeExternalCanisterFleetOperationInput {
+            target: input.target.into(),
+            mode: input.mode.into(),
+            module: input.module,
+            module_extra_chunks: input.module_extra_chunks.map(|c| c.into()),
+            arg: input.arg,
+            canister_args: input
+                .canister_args
+                .into_iter()
+                .map(|canister_arg| ExternalCanisterFleetArgInput {
+                    canister_id: canister_arg.canister_id,
+                    arg: canister_arg.arg,
+                })
+                .collect(),
+            canary_canister_ids: input.canary_canister_ids,
+        }
+    }
+}
+
+impl From<ExternalCanisterUpgradeStatus> for station_api::ExternalCanisterUpgradeStatusDTO {
+    fn from(
+        status: ExternalCanisterUpgradeStatus,
+    ) -> station_api::ExternalCanisterUpgradeStatusDTO {
+        match status {
+            ExternalCanisterUpgradeStatus::Pending => {
+                station_api::ExternalCanisterUpgradeStatusDTO::Pending
+            }
+            ExternalCanisterUpgradeStatus::Completed => {
+                station_api::ExternalCanisterUpgradeStatusDTO::Completed
+            }
+            ExternalCanisterUpgradeStatus::Failed { reason } => {
+                station_api::ExternalCanisterUpgradeStatusDTO::Failed { reason }
+            }
+            ExternalCanisterUpgradeStatus::Skipped => {
+                station_api::ExternalCanisterUpgradeStatusDTO::Skipped
+            }
+        }
+    }
+}
+
+impl From<UpgradeExternalCanisterFleetOperation>
+    for station_api::UpgradeExternalCanisterFleetOperationDTO
+{
+    fn from(
+        operation: UpgradeExternalCanisterFleetOperation,
+    ) -> station_api::UpgradeExternalCanisterFleetOperationDTO {
+        let outcomes = operation
+            .outcomes
+            .into_iter()
+            .map(|outcome| station_api::ExternalCanisterUpgradeOutcomeDTO {
+                canister_id: outcome.canister_id,
+                canary: operation.input.is_canary(&outcome.canister_id),
+                arg_checksum: outcome.arg_checksum.map(hex::encode),
+                status: outcome.status.into(),
+            })
+            .collect();
+
+        station_api::UpgradeExternalCanisterFleetOperationDTO {
+            target: operation.input.target.into(),
+            mode: operation.input.mode.into(),
+            module_checksum: hex::encode(operation.module_checksum),
+            outcomes,
         }
     }
 }
@@ -691,6 +932,28 @@ impl From<ConfigureExternalCanisterOperationKind>
             ConfigureExternalCanisterOperationKind::NativeSettings(input) => {
                 station_api::ConfigureExternalCanisterOperationKindDTO::NativeSettings(input.into())
             }
+            ConfigureExternalCanisterOperationKind::BackupSchedule(schedule) => {
+                station_api::ConfigureExternalCanisterOperationKindDTO::BackupSchedule(
+                    schedule.map(Into::into),
+                )
+            }
+            ConfigureExternalCanisterOperationKind::CandidInterface(candid) => {
+                station_api::ConfigureExternalCanisterOperationKindDTO::CandidInterface(candid)
+            }
+            ConfigureExternalCanisterOperationKind::AddControllers(controllers) => {
+                station_api::ConfigureExternalCanisterOperationKindDTO::AddControllers(controllers)
+            }
+            ConfigureExternalCanisterOperationKind::RemoveControllers(controllers) => {
+                station_api::ConfigureExternalCanisterOperationKindDTO::RemoveControllers(
+                    controllers,
+                )
+            }
+            ConfigureExternalCanisterOperationKind::ReleaseControl(controllers) => {
+                station_api::ConfigureExternalCanisterOperationKindDTO::ReleaseControl(controllers)
+            }
+            ConfigureExternalCanisterOperationKind::ReadOnlyMethods(methods) => {
+                station_api::ConfigureExternalCanisterOperationKindDTO::ReadOnlyMethods(methods)
+            }
         }
     }
 }
@@ -1413,8 +1676,10 @@ impl From<CallExternalCanisterOperation> for CallExternalCanisterOperationDTO {
             execution_method: operation.input.execution_method.into(),
             arg_checksum: operation.arg_checksum.map(hex::encode),
             arg_rendering: operation.arg_rendering,
+            arg_candid: operation.arg_candid,
             execution_method_cycles: operation.input.execution_method_cycles,
             execution_method_reply: operation.execution_method_reply,
+            execution_method_reply_candid: operation.execution_method_reply_candid,
             // By default this field is not set to avoid having responses that could be too large
             arg: None,
         }
@@ -1813,6 +2078,8 @@ impl From<ManageSystemInfoOperationInput> for station_api::ManageSystemInfoOpera
             cycle_obtain_strategy: input.cycle_obtain_strategy.map(|strategy| strategy.into()),
             max_station_backup_snapshots: input.max_station_backup_snapshots,
             max_upgrader_backup_snapshots: input.max_upgrader_backup_snapshots,
+            request_archive: input.request_archive.map(|config| config.into()),
+            control_panel_id: input.control_panel_id,
         }
     }
 }
@@ -1824,6 +2091,34 @@ impl From<station_api::ManageSystemInfoOperationInput> for ManageSystemInfoOpera
             cycle_obtain_strategy: input.cycle_obtain_strategy.map(|strategy| strategy.into()),
             max_station_backup_snapshots: input.max_station_backup_snapshots,
             max_upgrader_backup_snapshots: input.max_upgrader_backup_snapshots,
+            request_archive: input.request_archive.map(|config| config.into()),
+            control_panel_id: input.control_panel_id,
+        }
+    }
+}
+
+impl From<RequestArchiveConfigInput> for station_api::RequestArchiveConfigInput {
+    fn from(input: RequestArchiveConfigInput) -> station_api::RequestArchiveConfigInput {
+        station_api::RequestArchiveConfigInput {
+            min_age_secs: input.min_age_secs,
+            deploy: input
+                .deploy
+                .map(|deploy| station_api::DeployRequestArchiveInput {
+                    wasm_module: deploy.wasm_module,
+                    initial_cycles: deploy.initial_cycles,
+                }),
+        }
+    }
+}
+
+impl From<station_api::RequestArchiveConfigInput> for RequestArchiveConfigInput {
+    fn from(input: station_api::RequestArchiveConfigInput) -> RequestArchiveConfigInput {
+        RequestArchiveConfigInput {
+            min_age_secs: input.min_age_secs,
+            deploy: input.deploy.map(|deploy| DeployRequestArchiveInput {
+                wasm_module: deploy.wasm_module,
+                initial_cycles: deploy.initial_cycles,
+            }),
         }
     }
 }
@@ -2175,6 +2470,15 @@ impl From<RequestOperation> for RequestOperationDTO {
             RequestOperation::RemoveNamedRule(operation) => {
                 RequestOperationDTO::RemoveNamedRule(Box::new(operation.into()))
             }
+            RequestOperation::FreezeStation(operation) => {
+                RequestOperationDTO::FreezeStation(Box::new(operation.into()))
+            }
+            RequestOperation::UnfreezeStation(operation) => {
+                RequestOperationDTO::UnfreezeStation(Box::new(operation.into()))
+            }
+            RequestOperation::UpgradeExternalCanisterFleet(operation) => {
+                RequestOperationDTO::UpgradeExternalCanisterFleet(Box::new(operation.into()))
+            }
         }
     }
 }
@@ -2273,6 +2577,19 @@ impl RequestOperation {
                     )),
                 ]
             }
+            RequestOperation::ConfigureExternalCanister(ConfigureExternalCanisterOperation {
+                canister_id,
+                kind: ConfigureExternalCanisterOperationKind::ReleaseControl(_),
+            }) => {
+                vec![
+                    Resource::ExternalCanister(ExternalCanisterResourceAction::ReleaseControl(
+                        ExternalCanisterId::Any,
+                    )),
+                    Resource::ExternalCanister(ExternalCanisterResourceAction::ReleaseControl(
+                        ExternalCanisterId::Canister(*canister_id),
+                    )),
+                ]
+            }
             RequestOperation::ConfigureExternalCanister(ConfigureExternalCanisterOperation {
                 canister_id,
                 ..
@@ -2312,6 +2629,13 @@ impl RequestOperation {
                     )),
                 ]
             }
+            // Only the policies that cover all external canisters apply to fleet upgrades, since
+            // the policy of a single targeted canister must not be able to approve the whole fleet.
+            RequestOperation::UpgradeExternalCanisterFleet(_) => {
+                vec![Resource::ExternalCanister(
+                    ExternalCanisterResourceAction::Change(ExternalCanisterId::Any),
+                )]
+            }
             RequestOperation::CreateExternalCanister(CreateExternalCanisterOperation {
                 ..
             }) => {
@@ -2406,7 +2730,9 @@ impl RequestOperation {
                     Resource::RequestPolicy(ResourceAction::Delete(ResourceId::Any)),
                 ]
             }
-            RequestOperation::ManageSystemInfo(_) => {
+            RequestOperation::ManageSystemInfo(_)
+            | RequestOperation::FreezeStation(_)
+            | RequestOperation::UnfreezeStation(_) => {
                 vec![Resource::System(SystemResourceAction::ManageSystemInfo)]
             }
             RequestOperation::AddAsset(_) => {
@@ -2463,6 +2789,8 @@ mod tests {
             module: vec![1, 2, 3],
             module_extra_chunks: None,
             arg: None,
+            rollback: None,
+            module_registry: None,
         }
     }
 
@@ -2549,6 +2877,8 @@ mod tests {
                 module: vec![],
                 module_extra_chunks: None,
                 arg: None,
+                rollback: None,
+                module_registry: None,
             };
             let internal: ChangeExternalCanisterOperationInput = api_input.into();
             let api_back: station_api::ChangeExternalCanisterOperationInput = internal.into();
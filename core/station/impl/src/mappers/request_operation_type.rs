@@ -111,6 +111,15 @@ impl From<station_api::ListRequestsOperationTypeDTO> for ListRequestsOperationTy
             station_api::ListRequestsOperationTypeDTO::RemoveNamedRule => {
                 ListRequestsOperationType::RemoveNamedRule
             }
+            station_api::ListRequestsOperationTypeDTO::FreezeStation => {
+                ListRequestsOperationType::FreezeStation
+            }
+            station_api::ListRequestsOperationTypeDTO::UnfreezeStation => {
+                ListRequestsOperationType::UnfreezeStation
+            }
+            station_api::ListRequestsOperationTypeDTO::UpgradeExternalCanisterFleet => {
+                ListRequestsOperationType::UpgradeExternalCanisterFleet
+            }
         }
     }
 }
@@ -180,6 +189,11 @@ impl From<RequestOperationTypeDTO> for RequestOperationType {
             RequestOperationTypeDTO::AddNamedRule => RequestOperationType::AddNamedRule,
             RequestOperationTypeDTO::EditNamedRule => RequestOperationType::EditNamedRule,
             RequestOperationTypeDTO::RemoveNamedRule => RequestOperationType::RemoveNamedRule,
+            RequestOperationTypeDTO::FreezeStation => RequestOperationType::FreezeStation,
+            RequestOperationTypeDTO::UnfreezeStation => RequestOperationType::UnfreezeStation,
+            RequestOperationTypeDTO::UpgradeExternalCanisterFleet => {
+                RequestOperationType::UpgradeExternalCanisterFleet
+            }
         }
     }
 }
@@ -250,6 +264,12 @@ impl From<RequestOperationType> for RequestOperationTypeDTO {
             RequestOperationType::AddNamedRule => RequestOperationTypeDTO::AddNamedRule,
             RequestOperationType::EditNamedRule => RequestOperationTypeDTO::EditNamedRule,
             RequestOperationType::RemoveNamedRule => RequestOperationTypeDTO::RemoveNamedRule,
+
+            RequestOperationType::FreezeStation => RequestOperationTypeDTO::FreezeStation,
+            RequestOperationType::UnfreezeStation => RequestOperationTypeDTO::UnfreezeStation,
+            RequestOperationType::UpgradeExternalCanisterFleet => {
+                RequestOperationTypeDTO::UpgradeExternalCanisterFleet
+            }
         }
     }
 }
@@ -307,6 +327,11 @@ impl From<RequestOperation> for RequestOperationType {
             RequestOperation::AddNamedRule(_) => RequestOperationType::AddNamedRule,
             RequestOperation::EditNamedRule(_) => RequestOperationType::EditNamedRule,
             RequestOperation::RemoveNamedRule(_) => RequestOperationType::RemoveNamedRule,
+            RequestOperation::FreezeStation(_) => RequestOperationType::FreezeStation,
+            RequestOperation::UnfreezeStation(_) => RequestOperationType::UnfreezeStation,
+            RequestOperation::UpgradeExternalCanisterFleet(_) => {
+                RequestOperationType::UpgradeExternalCanisterFleet
+            }
         }
     }
 }
@@ -261,6 +261,9 @@ impl From<RequestSpecifier> for station_api::RequestSpecifierDTO {
             RequestSpecifier::FundExternalCanister(target) => {
                 station_api::RequestSpecifierDTO::FundExternalCanister(target.into())
             }
+            RequestSpecifier::ReleaseExternalCanisterControl(target) => {
+                station_api::RequestSpecifierDTO::ReleaseExternalCanisterControl(target.into())
+            }
             RequestSpecifier::CreateExternalCanister => {
                 station_api::RequestSpecifierDTO::CreateExternalCanister
             }
@@ -304,6 +307,9 @@ impl From<RequestSpecifier> for station_api::RequestSpecifierDTO {
             RequestSpecifier::RemoveNamedRule(resource_ids) => {
                 station_api::RequestSpecifierDTO::RemoveNamedRule(resource_ids.into())
             }
+
+            RequestSpecifier::FreezeStation => station_api::RequestSpecifierDTO::FreezeStation,
+            RequestSpecifier::UnfreezeStation => station_api::RequestSpecifierDTO::UnfreezeStation,
         }
     }
 }
@@ -341,6 +347,9 @@ impl From<station_api::RequestSpecifierDTO> for RequestSpecifier {
             station_api::RequestSpecifierDTO::FundExternalCanister(target) => {
                 RequestSpecifier::FundExternalCanister(target.into())
             }
+            station_api::RequestSpecifierDTO::ReleaseExternalCanisterControl(target) => {
+                RequestSpecifier::ReleaseExternalCanisterControl(target.into())
+            }
             station_api::RequestSpecifierDTO::CreateExternalCanister => {
                 RequestSpecifier::CreateExternalCanister
             }
@@ -384,6 +393,9 @@ impl From<station_api::RequestSpecifierDTO> for RequestSpecifier {
             station_api::RequestSpecifierDTO::RemoveNamedRule(resource_ids) => {
                 RequestSpecifier::RemoveNamedRule(resource_ids.into())
             }
+
+            station_api::RequestSpecifierDTO::FreezeStation => RequestSpecifier::FreezeStation,
+            station_api::RequestSpecifierDTO::UnfreezeStation => RequestSpecifier::UnfreezeStation,
         }
     }
 }
@@ -403,7 +415,9 @@ impl RequestSpecifier {
         match self {
             RequestSpecifier::AddAccount => vec![Resource::Account(AccountResourceAction::Create)],
             RequestSpecifier::AddUser => vec![Resource::User(UserResourceAction::Create)],
-            RequestSpecifier::ManageSystemInfo => {
+            RequestSpecifier::ManageSystemInfo
+            | RequestSpecifier::FreezeStation
+            | RequestSpecifier::UnfreezeStation => {
                 vec![Resource::System(SystemResourceAction::ManageSystemInfo)]
             }
 
@@ -475,6 +489,11 @@ impl RequestSpecifier {
                     ExternalCanisterResourceAction::Fund(target.clone()),
                 )]
             }
+            RequestSpecifier::ReleaseExternalCanisterControl(target) => {
+                vec![Resource::ExternalCanister(
+                    ExternalCanisterResourceAction::ReleaseControl(target.clone()),
+                )]
+            }
             RequestSpecifier::CreateExternalCanister => {
                 vec![Resource::ExternalCanister(
                     ExternalCanisterResourceAction::Create,
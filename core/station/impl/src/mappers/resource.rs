@@ -254,6 +254,12 @@ impl From<station_api::ExternalCanisterResourceActionDTO> for ExternalCanisterRe
             station_api::ExternalCanisterResourceActionDTO::Read(target) => {
                 ExternalCanisterResourceAction::Read(target.into())
             }
+            station_api::ExternalCanisterResourceActionDTO::ReadLogs(target) => {
+                ExternalCanisterResourceAction::ReadLogs(target.into())
+            }
+            station_api::ExternalCanisterResourceActionDTO::ReleaseControl(target) => {
+                ExternalCanisterResourceAction::ReleaseControl(target.into())
+            }
         }
     }
 }
@@ -279,6 +285,12 @@ impl From<ExternalCanisterResourceAction> for station_api::ExternalCanisterResou
             ExternalCanisterResourceAction::Read(target) => {
                 station_api::ExternalCanisterResourceActionDTO::Read(target.into())
             }
+            ExternalCanisterResourceAction::ReadLogs(target) => {
+                station_api::ExternalCanisterResourceActionDTO::ReadLogs(target.into())
+            }
+            ExternalCanisterResourceAction::ReleaseControl(target) => {
+                station_api::ExternalCanisterResourceActionDTO::ReleaseControl(target.into())
+            }
         }
     }
 }
@@ -4,6 +4,7 @@ use orbit_essentials::{
     utils::{raw_rand_successful, timestamp_to_rfc3339},
 };
 use station_api::DisasterRecoveryDTO;
+use uuid::Uuid;
 
 impl SystemInfo {
     pub fn to_dto(
@@ -31,6 +32,31 @@ impl SystemInfo {
             upgrader_cycles,
             max_station_backup_snapshots: self.get_max_station_backup_snapshots(),
             max_upgrader_backup_snapshots: self.get_max_upgrader_backup_snapshots(),
+            request_archive: self.get_request_archive().map(|archive| {
+                station_api::RequestArchiveDTO {
+                    canister_id: archive.canister_id,
+                    min_age_secs: archive.min_age_secs,
+                }
+            }),
+            freeze: self
+                .get_freeze()
+                .map(|freeze| station_api::StationFreezeDTO {
+                    request_id: Uuid::from_bytes(freeze.request_id).hyphenated().to_string(),
+                    reason: freeze.reason.clone(),
+                    frozen_at: timestamp_to_rfc3339(&freeze.frozen_at),
+                }),
+            available_upgrade: self.get_available_upgrade().map(|upgrade| {
+                station_api::AvailableStationUpgradeDTO {
+                    registry_canister_id: upgrade.registry_canister_id,
+                    registry_entry_id: Uuid::from_bytes(upgrade.registry_entry_id)
+                        .hyphenated()
+                        .to_string(),
+                    name: upgrade.name.clone(),
+                    version: upgrade.version.clone(),
+                    announced_at: timestamp_to_rfc3339(&upgrade.announced_at),
+                }
+            }),
+            control_panel_id: self.get_control_panel_id().copied(),
         }
     }
 }
@@ -1,6 +1,18 @@
 use crate::core::ic_cdk::api::trap;
 use crate::core::{read_system_info, write_system_info};
+use crate::jobs::{schedule_request_resource_target_index_build, schedule_search_index_build};
+use crate::models::request_specifier::{RequestSpecifier, UserSpecifier};
+use crate::models::resource::{ExternalCanisterId, ExternalCanisterResourceAction, Resource};
+use crate::models::{
+    AddRequestPolicyOperationInput, DataMigration, EditPermissionOperationInput, RequestPolicyRule,
+    ADMIN_GROUP_ID,
+};
+use crate::repositories::permission::PERMISSION_REPOSITORY;
+use crate::repositories::request_policy::REQUEST_POLICY_REPOSITORY;
+use crate::services::permission::PERMISSION_SERVICE;
+use crate::services::REQUEST_POLICY_SERVICE;
 use crate::STABLE_MEMORY_VERSION;
+use orbit_essentials::repository::Repository;
 
 /// Handles stable memory schema migrations for the station canister.
 ///
@@ -50,9 +62,236 @@ impl MigrationHandler {
 
 /// If there is a check that needs to be run on every upgrade, regardless if the memory version has changed,
 /// it should be added here.
-fn post_run() {}
+fn post_run() {
+    apply_data_migrations();
+}
+
+/// Applies the data migrations that were not yet applied to the station, each of them only runs once.
+fn apply_data_migrations() {
+    let mut system_info = read_system_info();
+
+    for migration in DataMigration::ALL {
+        if system_info.is_data_migration_applied(&migration) {
+            continue;
+        }
+
+        match migration {
+            DataMigration::FreezeStationPolicies => add_freeze_station_policies(),
+            DataMigration::ReleaseExternalCanisterControlPolicies => {
+                add_release_external_canister_control_policies()
+            }
+            // the job marks the migration as applied once all entries are indexed
+            DataMigration::SearchIndex => {
+                schedule_search_index_build();
+                continue;
+            }
+            DataMigration::RequestResourceTargetIndex => {
+                schedule_request_resource_target_index_build();
+                continue;
+            }
+        }
+
+        system_info.set_data_migration_applied(migration);
+    }
+
+    write_system_info(system_info);
+}
+
+/// Adds the freeze and unfreeze policies to stations created before the station freeze was introduced,
+/// since requests without a matching policy are rejected.
+///
+/// As for new stations, a single admin can freeze the station, while unfreezing it requires the same
+/// approval as managing the system info.
+fn add_freeze_station_policies() {
+    let policies = REQUEST_POLICY_REPOSITORY.list();
+    let has_policy =
+        |specifier: &RequestSpecifier| policies.iter().any(|policy| policy.specifier == *specifier);
+    let admin_rule = policies
+        .iter()
+        .find(|policy| policy.specifier == RequestSpecifier::ManageSystemInfo)
+        .map(|policy| policy.rule.clone());
+
+    let freeze_policies = [
+        (
+            RequestSpecifier::FreezeStation,
+            RequestPolicyRule::Quorum(UserSpecifier::Group(vec![*ADMIN_GROUP_ID]), 1),
+        ),
+        (
+            RequestSpecifier::UnfreezeStation,
+            admin_rule.unwrap_or(RequestPolicyRule::Quorum(
+                UserSpecifier::Group(vec![*ADMIN_GROUP_ID]),
+                1,
+            )),
+        ),
+    ];
+
+    for (specifier, rule) in freeze_policies {
+        if has_policy(&specifier) {
+            continue;
+        }
+
+        if let Err(err) = REQUEST_POLICY_SERVICE
+            .add_request_policy(AddRequestPolicyOperationInput { specifier, rule })
+        {
+            trap(&format!("Failed to add the station freeze policies: {err}"));
+        }
+    }
+}
+
+/// Adds the request policy and permission of releasing the control of external canisters to stations
+/// created before it had its own, such requests used to require the `Change` privilege and policies.
+///
+/// The permission is copied from the one of changing any external canister so that the same users can
+/// still request it, while the approval requires the same rule as managing the system info.
+fn add_release_external_canister_control_policies() {
+    let policies = REQUEST_POLICY_REPOSITORY.list();
+    let specifier = RequestSpecifier::ReleaseExternalCanisterControl(ExternalCanisterId::Any);
+
+    if !policies.iter().any(|policy| policy.specifier == specifier) {
+        let rule = policies
+            .iter()
+            .find(|policy| policy.specifier == RequestSpecifier::ManageSystemInfo)
+            .map(|policy| policy.rule.clone())
+            .unwrap_or(RequestPolicyRule::Quorum(
+                UserSpecifier::Group(vec![*ADMIN_GROUP_ID]),
+                1,
+            ));
+
+        if let Err(err) = REQUEST_POLICY_SERVICE
+            .add_request_policy(AddRequestPolicyOperationInput { specifier, rule })
+        {
+            trap(&format!(
+                "Failed to add the release external canister control policy: {err}"
+            ));
+        }
+    }
+
+    let resource = Resource::ExternalCanister(ExternalCanisterResourceAction::ReleaseControl(
+        ExternalCanisterId::Any,
+    ));
+
+    if PERMISSION_REPOSITORY.get(&resource).is_none() {
+        let allow = PERMISSION_SERVICE
+            .get_permission(&Resource::ExternalCanister(
+                ExternalCanisterResourceAction::Change(ExternalCanisterId::Any),
+            ))
+            .allow;
+
+        if let Err(err) = PERMISSION_SERVICE.edit_permission(EditPermissionOperationInput {
+            resource,
+            auth_scope: Some(allow.auth_scope),
+            users: Some(allow.users),
+            user_groups: Some(allow.user_groups),
+        }) {
+            trap(&format!(
+                "Failed to add the release external canister control permission: {err}"
+            ));
+        }
+    }
+}
 
 /// The migration to apply to the station canister stable memory.
 ///
 /// Please include the migration steps in the `apply_migration` function.
 fn apply_migration() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::permission::{Allow, Permission};
+    use crate::models::{request_policy_test_utils::mock_request_policy, UserGroup};
+    use crate::repositories::USER_GROUP_REPOSITORY;
+
+    #[test]
+    fn adds_freeze_station_policies_with_the_system_info_rule() {
+        USER_GROUP_REPOSITORY.insert(
+            *ADMIN_GROUP_ID,
+            UserGroup {
+                id: *ADMIN_GROUP_ID,
+                name: "Admin".to_string(),
+                last_modification_timestamp: 0,
+            },
+        );
+
+        let mut policy = mock_request_policy();
+        policy.specifier = RequestSpecifier::ManageSystemInfo;
+        policy.rule = RequestPolicyRule::AutoApproved;
+        REQUEST_POLICY_REPOSITORY.insert(policy.id, policy);
+
+        add_freeze_station_policies();
+
+        let policies = REQUEST_POLICY_REPOSITORY.list();
+        let rule_of = |specifier: RequestSpecifier| {
+            policies
+                .iter()
+                .find(|policy| policy.specifier == specifier)
+                .map(|policy| policy.rule.clone())
+        };
+
+        assert_eq!(
+            rule_of(RequestSpecifier::FreezeStation),
+            Some(RequestPolicyRule::Quorum(
+                UserSpecifier::Group(vec![*ADMIN_GROUP_ID]),
+                1
+            ))
+        );
+        assert_eq!(
+            rule_of(RequestSpecifier::UnfreezeStation),
+            Some(RequestPolicyRule::AutoApproved)
+        );
+
+        // the policies are not duplicated
+        add_freeze_station_policies();
+
+        assert_eq!(REQUEST_POLICY_REPOSITORY.len(), 3);
+    }
+
+    #[test]
+    fn adds_release_external_canister_control_policies_with_the_change_permission() {
+        USER_GROUP_REPOSITORY.insert(
+            *ADMIN_GROUP_ID,
+            UserGroup {
+                id: *ADMIN_GROUP_ID,
+                name: "Admin".to_string(),
+                last_modification_timestamp: 0,
+            },
+        );
+
+        let mut policy = mock_request_policy();
+        policy.specifier = RequestSpecifier::ManageSystemInfo;
+        policy.rule = RequestPolicyRule::AutoApproved;
+        REQUEST_POLICY_REPOSITORY.insert(policy.id, policy);
+
+        let change_permission = Permission::new(
+            Allow::user_groups(vec![*ADMIN_GROUP_ID]),
+            Resource::ExternalCanister(ExternalCanisterResourceAction::Change(
+                ExternalCanisterId::Any,
+            )),
+        );
+        PERMISSION_REPOSITORY.insert(change_permission.resource.clone(), change_permission);
+
+        add_release_external_canister_control_policies();
+
+        let policy = REQUEST_POLICY_REPOSITORY
+            .list()
+            .into_iter()
+            .find(|policy| {
+                policy.specifier
+                    == RequestSpecifier::ReleaseExternalCanisterControl(ExternalCanisterId::Any)
+            })
+            .expect("the release control policy should be added");
+        assert_eq!(policy.rule, RequestPolicyRule::AutoApproved);
+
+        let permission = PERMISSION_REPOSITORY
+            .get(&Resource::ExternalCanister(
+                ExternalCanisterResourceAction::ReleaseControl(ExternalCanisterId::Any),
+            ))
+            .expect("the release control permission should be added");
+        assert_eq!(permission.allow.user_groups, vec![*ADMIN_GROUP_ID]);
+
+        // the policy is not duplicated
+        add_release_external_canister_control_policies();
+
+        assert_eq!(REQUEST_POLICY_REPOSITORY.len(), 2);
+    }
+}
@@ -6,9 +6,10 @@ use super::resource::{
 };
 use super::{
     CanisterMethod, ConfigureExternalCanisterSettingsInput, CreateExternalCanisterOperationInput,
-    CreateExternalCanisterOperationKind, ExternalCanisterChangeCallRequestPoliciesInput,
-    ExternalCanisterRequestPoliciesCreateInput, ExternalCanisterRequestPoliciesUpdateInput,
-    Metadata, MonitorExternalCanisterStartInput, RequestPolicy, RequestPolicyRule,
+    CreateExternalCanisterOperationKind, ExternalCanisterBackupScheduleInput,
+    ExternalCanisterChangeCallRequestPoliciesInput, ExternalCanisterRequestPoliciesCreateInput,
+    ExternalCanisterRequestPoliciesUpdateInput, Metadata, MonitorExternalCanisterStartInput,
+    RequestPolicy, RequestPolicyRule,
 };
 use crate::core::validation::EnsureExternalCanister;
 use crate::errors::{ExternalCanisterError, ExternalCanisterValidationError};
@@ -60,6 +61,15 @@ pub struct ExternalCanister {
     pub modified_at: Option<Timestamp>,
     /// Is the canister monitored by the station.
     pub monitoring: Option<ExternalCanisterMonitoring>,
+    /// The automatic backup configuration and status of the canister.
+    #[serde(default)]
+    pub backup: Option<ExternalCanisterBackup>,
+    /// The methods that users with the call permission can call through the station without a request.
+    ///
+    /// The station cannot verify that a method does not change the state of the canister, so only
+    /// query methods, or update methods without side effects, should be allowed.
+    #[serde(default)]
+    pub read_only_methods: Vec<String>,
 }
 
 #[storable]
@@ -127,6 +137,7 @@ pub struct ExternalCanisterCallerPrivileges {
     pub canister_id: Principal,
     pub can_change: bool,
     pub can_fund: bool,
+    pub can_read_logs: bool,
     pub can_call: Vec<ExternalCanisterCallerMethodsPrivileges>,
 }
 
@@ -134,11 +145,111 @@ pub type ExternalCanisterAvailableFilters = GetExternalCanisterFiltersResponse;
 
 pub type ExternalCanisterMonitoring = MonitorExternalCanisterStartInput;
 
+pub type ExternalCanisterBackupSchedule = ExternalCanisterBackupScheduleInput;
+
+/// The automatic backups of an external canister, which are snapshots taken by the station.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExternalCanisterBackup {
+    pub schedule: ExternalCanisterBackupSchedule,
+    /// The backup snapshots taken by the station, ordered from the oldest to the newest.
+    ///
+    /// Only these snapshots are pruned by the retention policy, snapshots taken
+    /// through requests are left untouched.
+    pub snapshot_ids: Vec<Vec<u8>>,
+    /// The time of the last successful backup.
+    pub last_backup_at: Option<Timestamp>,
+    /// The last failed backup attempt, cleared by the next successful backup.
+    pub last_failure: Option<ExternalCanisterBackupFailure>,
+    /// The time at which the next periodic backup is due.
+    pub next_backup_at: Option<Timestamp>,
+}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExternalCanisterBackupFailure {
+    pub failed_at: Timestamp,
+    pub reason: String,
+}
+
+impl ExternalCanisterBackup {
+    pub fn new(schedule: ExternalCanisterBackupSchedule, now: Timestamp) -> Self {
+        let mut backup = Self {
+            schedule,
+            snapshot_ids: Vec::new(),
+            last_backup_at: None,
+            last_failure: None,
+            next_backup_at: None,
+        };
+
+        backup.schedule_next_backup(now);
+
+        backup
+    }
+
+    /// Sets the time of the next periodic backup based on the configured interval.
+    pub fn schedule_next_backup(&mut self, now: Timestamp) {
+        self.next_backup_at = self
+            .schedule
+            .interval_secs
+            .map(|interval_secs| now.saturating_add(interval_secs.saturating_mul(1_000_000_000)));
+    }
+
+    /// Returns the backup snapshot that should be replaced by the next backup, if the retention
+    /// count has been reached.
+    pub fn snapshot_to_replace(&self) -> Option<Vec<u8>> {
+        if self.snapshot_ids.len() as u64 >= self.schedule.retention_count {
+            self.snapshot_ids.first().cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Records a successful backup, the replaced snapshot no longer exists on the canister.
+    pub fn record_success(
+        &mut self,
+        snapshot_id: Vec<u8>,
+        replaced_snapshot_id: Option<Vec<u8>>,
+        now: Timestamp,
+    ) {
+        if let Some(replaced_snapshot_id) = replaced_snapshot_id {
+            self.snapshot_ids.retain(|id| *id != replaced_snapshot_id);
+        }
+
+        self.snapshot_ids.push(snapshot_id);
+        self.last_backup_at = Some(now);
+        self.last_failure = None;
+    }
+
+    pub fn record_failure(&mut self, reason: String, now: Timestamp) {
+        self.last_failure = Some(ExternalCanisterBackupFailure {
+            failed_at: now,
+            reason,
+        });
+    }
+
+    /// Returns the oldest backup snapshots that exceed the retention count.
+    pub fn snapshots_to_prune(&self) -> Vec<Vec<u8>> {
+        let excess = self
+            .snapshot_ids
+            .len()
+            .saturating_sub(self.schedule.retention_count as usize);
+
+        self.snapshot_ids.iter().take(excess).cloned().collect()
+    }
+
+    pub fn last_snapshot_id(&self) -> Option<&Vec<u8>> {
+        self.snapshot_ids.last()
+    }
+}
+
 impl ExternalCanister {
     pub const MAX_NAME_LENGTH: usize = 100;
     pub const MAX_LABEL_LENGTH: usize = 50;
     pub const MAX_LABELS: usize = 10;
     pub const MAX_DESCRIPTION_LENGTH: usize = 1000;
+    pub const MAX_READ_ONLY_METHODS: usize = 50;
+    pub const MAX_METHOD_NAME_LENGTH: usize = 255;
 
     /// Checks if the external canister is archived.
     pub fn is_archived(&self) -> bool {
@@ -236,6 +347,37 @@ fn validate_labels(labels: &[String]) -> ModelValidatorResult<ExternalCanisterEr
     Ok(())
 }
 
+/// Validates the methods allowed to be called through the station without a request.
+pub fn validate_read_only_methods(
+    methods: &[String],
+) -> ModelValidatorResult<ExternalCanisterValidationError> {
+    if methods.len() > ExternalCanister::MAX_READ_ONLY_METHODS {
+        return Err(ExternalCanisterValidationError::ValidationError {
+            info: format!(
+                "The external canister cannot have more than {} read-only methods.",
+                ExternalCanister::MAX_READ_ONLY_METHODS
+            ),
+        });
+    }
+
+    for method in methods {
+        if method.is_empty()
+            || method == CanisterMethod::WILDCARD
+            || method.len() > ExternalCanister::MAX_METHOD_NAME_LENGTH
+        {
+            return Err(ExternalCanisterValidationError::ValidationError {
+                info: format!(
+                    "The read-only method '{}' must be a method name of at most {} characters.",
+                    method,
+                    ExternalCanister::MAX_METHOD_NAME_LENGTH
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
 impl ModelValidator<ExternalCanisterError> for ExternalCanister {
     fn validate(&self) -> ModelValidatorResult<ExternalCanisterError> {
         validate_name(&self.name)?;
@@ -455,6 +597,8 @@ pub mod external_canister_test_utils {
             created_at: next_time(),
             modified_at: None,
             monitoring: None,
+            backup: None,
+            read_only_methods: Vec::new(),
         }
     }
 }
@@ -505,6 +649,18 @@ mod tests {
         assert!(external_canister.validate().is_ok());
     }
 
+    #[test]
+    fn read_only_methods_validation() {
+        assert!(validate_read_only_methods(&["get_config".to_string()]).is_ok());
+        assert!(validate_read_only_methods(&["".to_string()]).is_err());
+        assert!(validate_read_only_methods(&[CanisterMethod::WILDCARD.to_string()]).is_err());
+        assert!(validate_read_only_methods(&vec![
+            "get_config".to_string();
+            ExternalCanister::MAX_READ_ONLY_METHODS + 1
+        ])
+        .is_err());
+    }
+
     #[test]
     fn invalid_external_canister_validation() {
         let mut external_canister = mock_external_canister();
@@ -806,4 +962,37 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn backup_retention_replaces_and_prunes_oldest_snapshots() {
+        let mut backup = ExternalCanisterBackup::new(
+            ExternalCanisterBackupSchedule {
+                interval_secs: Some(3_600),
+                before_change: false,
+                retention_count: 2,
+            },
+            0,
+        );
+
+        assert_eq!(backup.next_backup_at, Some(3_600_000_000_000));
+        assert_eq!(backup.snapshot_to_replace(), None);
+
+        backup.record_failure("canister is not stopping".to_string(), 1);
+        backup.record_success(vec![1], None, 2);
+        backup.record_success(vec![2], None, 3);
+
+        assert!(backup.last_failure.is_none());
+        assert_eq!(backup.last_backup_at, Some(3));
+        assert_eq!(backup.snapshot_to_replace(), Some(vec![1]));
+
+        backup.record_success(vec![3], Some(vec![1]), 4);
+
+        assert_eq!(backup.snapshot_ids, vec![vec![2], vec![3]]);
+        assert_eq!(backup.last_snapshot_id(), Some(&vec![3]));
+        assert!(backup.snapshots_to_prune().is_empty());
+
+        backup.schedule.retention_count = 1;
+
+        assert_eq!(backup.snapshots_to_prune(), vec![vec![2]]);
+    }
 }
@@ -0,0 +1,175 @@
+use super::ExternalCanisterEntryId;
+use crate::errors::ExternalCanisterValidationError;
+use candid::Principal;
+use orbit_essentials::{model::ModelValidatorResult, storable, types::Timestamp};
+
+/// The controllers of an external canister as last set or observed by the station, used to detect
+/// controller changes made outside of the station.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalCanisterControllers {
+    pub external_canister_id: ExternalCanisterEntryId,
+    /// The controllers sorted and without duplicates.
+    pub controllers: Vec<Principal>,
+    pub updated_at: Timestamp,
+}
+
+/// The controllers that were added and removed outside of the station.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ExternalCanisterControllersDrift {
+    pub added: Vec<Principal>,
+    pub removed: Vec<Principal>,
+}
+
+impl ExternalCanisterControllers {
+    /// The maximum number of controllers that a canister can have on the IC.
+    pub const MAX_CONTROLLERS: usize = 10;
+
+    pub fn new(
+        external_canister_id: ExternalCanisterEntryId,
+        mut controllers: Vec<Principal>,
+        updated_at: Timestamp,
+    ) -> Self {
+        controllers.sort();
+        controllers.dedup();
+
+        Self {
+            external_canister_id,
+            controllers,
+            updated_at,
+        }
+    }
+
+    /// Returns the differences between the observed controllers and the known ones, if any.
+    pub fn drift(&self, observed: &[Principal]) -> Option<ExternalCanisterControllersDrift> {
+        let drift = ExternalCanisterControllersDrift {
+            added: observed
+                .iter()
+                .filter(|controller| !self.controllers.contains(controller))
+                .copied()
+                .collect(),
+            removed: self
+                .controllers
+                .iter()
+                .filter(|controller| !observed.contains(controller))
+                .copied()
+                .collect(),
+        };
+
+        (!drift.added.is_empty() || !drift.removed.is_empty()).then_some(drift)
+    }
+}
+
+/// Validates the controllers that a request sets on an external canister.
+///
+/// Unless the request releases the control of the canister, the station must stay one of its
+/// controllers, otherwise the canister could no longer be managed by the station.
+pub fn validate_canister_controllers(
+    controllers: &[Principal],
+    releases_control: bool,
+) -> ModelValidatorResult<ExternalCanisterValidationError> {
+    if controllers.len() > ExternalCanisterControllers::MAX_CONTROLLERS {
+        return Err(ExternalCanisterValidationError::ValidationError {
+            info: format!(
+                "A canister can have at most {} controllers, got {}.",
+                ExternalCanisterControllers::MAX_CONTROLLERS,
+                controllers.len()
+            ),
+        });
+    }
+
+    let station_id = crate::core::ic_cdk::api::id();
+    let includes_station = controllers.contains(&station_id);
+
+    if releases_control && includes_station {
+        return Err(ExternalCanisterValidationError::ValidationError {
+            info: "The station cannot be a controller of a canister it releases the control of."
+                .to_string(),
+        });
+    }
+
+    if !releases_control && !includes_station {
+        return Err(ExternalCanisterValidationError::ValidationError {
+            info: format!(
+                "The station {station_id} must stay a controller of the canister, use the release control operation to hand over the canister."
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Validates the controllers that a request adds to or removes from an external canister.
+pub fn validate_controllers_change(
+    controllers: &[Principal],
+    removes: bool,
+) -> ModelValidatorResult<ExternalCanisterValidationError> {
+    if controllers.is_empty() {
+        return Err(ExternalCanisterValidationError::ValidationError {
+            info: "The list of controllers to change cannot be empty.".to_string(),
+        });
+    }
+
+    if controllers.len() > ExternalCanisterControllers::MAX_CONTROLLERS {
+        return Err(ExternalCanisterValidationError::ValidationError {
+            info: format!(
+                "A canister can have at most {} controllers, got {}.",
+                ExternalCanisterControllers::MAX_CONTROLLERS,
+                controllers.len()
+            ),
+        });
+    }
+
+    if removes && controllers.contains(&crate::core::ic_cdk::api::id()) {
+        return Err(ExternalCanisterValidationError::ValidationError {
+            info: "The station cannot be removed as a controller, use the release control operation to hand over the canister.".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn principal(id: u8) -> Principal {
+        Principal::from_slice(&[id; 29])
+    }
+
+    #[test]
+    fn detects_added_and_removed_controllers() {
+        let known = ExternalCanisterControllers::new([0; 16], vec![principal(2), principal(1)], 0);
+
+        assert_eq!(known.controllers, vec![principal(1), principal(2)]);
+        assert_eq!(known.drift(&[principal(2), principal(1)]), None);
+        assert_eq!(
+            known.drift(&[principal(1), principal(3)]),
+            Some(ExternalCanisterControllersDrift {
+                added: vec![principal(3)],
+                removed: vec![principal(2)],
+            })
+        );
+    }
+
+    #[test]
+    fn station_must_stay_controller_unless_released() {
+        let station_id = crate::core::ic_cdk::api::id();
+
+        assert!(validate_canister_controllers(&[station_id, principal(1)], false).is_ok());
+        assert!(validate_canister_controllers(&[principal(1)], false).is_err());
+        assert!(validate_canister_controllers(&[principal(1)], true).is_ok());
+        assert!(validate_canister_controllers(&[station_id], true).is_err());
+        assert!(validate_canister_controllers(&[station_id; 11], false).is_err());
+    }
+
+    #[test]
+    fn station_cannot_be_removed_as_controller() {
+        let station_id = crate::core::ic_cdk::api::id();
+
+        assert!(validate_controllers_change(&[principal(1)], true).is_ok());
+        assert!(validate_controllers_change(&[station_id], false).is_ok());
+        assert!(validate_controllers_change(&[station_id], true).is_err());
+        assert!(validate_controllers_change(&[], false).is_err());
+    }
+}
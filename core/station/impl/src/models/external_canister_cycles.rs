@@ -0,0 +1,169 @@
+use super::ExternalCanisterEntryId;
+use orbit_essentials::{storable, types::Timestamp};
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+const SECS_PER_DAY: u128 = 24 * 60 * 60;
+
+/// The key of a cycles balance sample, samples of the same canister are ordered by time.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExternalCanisterCyclesSampleKey {
+    pub external_canister_id: ExternalCanisterEntryId,
+    /// The time the balance was fetched by the cycle monitor.
+    pub sampled_at: Timestamp,
+}
+
+/// A cycles balance sample of an external canister monitored by the station.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalCanisterCyclesSample {
+    pub cycles: u64,
+    /// The total cycles deposited by the cycle monitor since it started monitoring the canister.
+    ///
+    /// The total restarts from zero when the monitoring restarts, e.g. after a station upgrade.
+    pub deposited_cycles: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalCanisterTopUp {
+    /// The time of the first sample that includes the top-up.
+    pub detected_at: Timestamp,
+    pub cycles: u64,
+}
+
+/// The cycles consumption of an external canister derived from its balance samples.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalCanisterCyclesForecast {
+    pub samples: Vec<(Timestamp, u64)>,
+    pub top_ups: Vec<ExternalCanisterTopUp>,
+    /// The average cycles burned per day, `None` if the samples don't span any time.
+    pub burn_rate_cycles_per_day: Option<u64>,
+    /// The seconds left until the canister runs out of cycles at the current burn rate, `None` if
+    /// the canister doesn't burn cycles or the burn rate is unknown.
+    pub projected_runway_secs: Option<u64>,
+}
+
+impl ExternalCanisterCyclesSample {
+    /// Returns the cycles deposited between the previous sample and this one.
+    fn deposited_since(&self, previous: &ExternalCanisterCyclesSample) -> u64 {
+        if self.deposited_cycles >= previous.deposited_cycles {
+            self.deposited_cycles - previous.deposited_cycles
+        } else {
+            // the monitoring restarted in between, so the total only counts the new deposits
+            self.deposited_cycles
+        }
+    }
+}
+
+impl ExternalCanisterCyclesForecast {
+    /// The default time window used to compute the burn rate.
+    pub const DEFAULT_WINDOW_NS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+    /// Computes the forecast from the samples of a single canister, sorted by time.
+    pub fn from_samples(samples: &[(Timestamp, ExternalCanisterCyclesSample)]) -> Self {
+        let mut top_ups = Vec::new();
+        let mut burned_cycles: u128 = 0;
+
+        for pair in samples.windows(2) {
+            let (_, previous) = &pair[0];
+            let (sampled_at, current) = &pair[1];
+            let deposited = current.deposited_since(previous);
+
+            if deposited > 0 {
+                top_ups.push(ExternalCanisterTopUp {
+                    detected_at: *sampled_at,
+                    cycles: deposited,
+                });
+            }
+
+            burned_cycles += (previous.cycles as u128 + deposited as u128)
+                .saturating_sub(current.cycles as u128);
+        }
+
+        let elapsed_ns = match (samples.first(), samples.last()) {
+            (Some((first, _)), Some((last, _))) => last.saturating_sub(*first) as u128,
+            _ => 0,
+        };
+
+        let burn_rate_cycles_per_day = (elapsed_ns > 0).then(|| {
+            (burned_cycles * SECS_PER_DAY * NANOS_PER_SEC / elapsed_ns).min(u64::MAX as u128) as u64
+        });
+
+        let projected_runway_secs = match samples.last() {
+            Some((_, last)) if elapsed_ns > 0 && burned_cycles > 0 => Some(
+                (last.cycles as u128 * elapsed_ns / burned_cycles / NANOS_PER_SEC)
+                    .min(u64::MAX as u128) as u64,
+            ),
+            _ => None,
+        };
+
+        Self {
+            samples: samples
+                .iter()
+                .map(|(sampled_at, sample)| (*sampled_at, sample.cycles))
+                .collect(),
+            top_ups,
+            burn_rate_cycles_per_day,
+            projected_runway_secs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    fn sample(cycles: u64, deposited_cycles: u64) -> ExternalCanisterCyclesSample {
+        ExternalCanisterCyclesSample {
+            cycles,
+            deposited_cycles,
+        }
+    }
+
+    #[test]
+    fn burn_rate_accounts_for_top_ups() {
+        let forecast = ExternalCanisterCyclesForecast::from_samples(&[
+            (0, sample(1_000, 0)),
+            (DAY_NS, sample(900, 0)),
+            // topped up with 500 cycles, while burning 100
+            (2 * DAY_NS, sample(1_300, 500)),
+        ]);
+
+        assert_eq!(forecast.burn_rate_cycles_per_day, Some(100));
+        assert_eq!(forecast.projected_runway_secs, Some(13 * 24 * 60 * 60));
+        assert_eq!(
+            forecast.top_ups,
+            vec![ExternalCanisterTopUp {
+                detected_at: 2 * DAY_NS,
+                cycles: 500,
+            }]
+        );
+    }
+
+    #[test]
+    fn deposited_total_restarts_after_monitoring_restart() {
+        let forecast = ExternalCanisterCyclesForecast::from_samples(&[
+            (0, sample(1_000, 700)),
+            (DAY_NS, sample(1_100, 200)),
+        ]);
+
+        assert_eq!(forecast.top_ups[0].cycles, 200);
+        assert_eq!(forecast.burn_rate_cycles_per_day, Some(100));
+    }
+
+    #[test]
+    fn no_forecast_without_consumption() {
+        let forecast = ExternalCanisterCyclesForecast::from_samples(&[(0, sample(1_000, 0))]);
+        assert_eq!(forecast.burn_rate_cycles_per_day, None);
+        assert_eq!(forecast.projected_runway_secs, None);
+
+        let forecast = ExternalCanisterCyclesForecast::from_samples(&[
+            (0, sample(1_000, 0)),
+            (DAY_NS, sample(1_000, 0)),
+        ]);
+        assert_eq!(forecast.burn_rate_cycles_per_day, Some(0));
+        assert_eq!(forecast.projected_runway_secs, None);
+    }
+}
@@ -0,0 +1,193 @@
+use super::ExternalCanisterEntryId;
+use crate::errors::ExternalCanisterValidationError;
+use candid::types::{Function, Type, TypeEnv};
+use candid_parser::{utils::CandidSource, IDLArgs};
+use orbit_essentials::{
+    model::{ModelValidator, ModelValidatorResult},
+    storable,
+    types::Timestamp,
+};
+
+/// The Candid interface registered for an external canister, used to type-check the arguments
+/// of calls to the canister and to render them, and their replies, for display.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalCanisterInterface {
+    pub external_canister_id: ExternalCanisterEntryId,
+    /// The service description in the Candid language, as found in a `.did` file.
+    pub candid: String,
+    pub updated_at: Timestamp,
+}
+
+impl ExternalCanisterInterface {
+    pub const MAX_CANDID_LEN: usize = 256 * 1024;
+}
+
+/// The parsed service description of a Candid interface, which is kept in a cache since parsing
+/// the interface is far more expensive than decoding the arguments and replies of a call.
+#[derive(Clone, Debug)]
+pub struct CandidService {
+    env: TypeEnv,
+    actor: Type,
+}
+
+impl CandidService {
+    /// The maximum length of the rendered arguments and replies, longer renderings are truncated
+    /// since they are stored with the request.
+    pub const MAX_RENDERING_LEN: usize = 16 * 1024;
+
+    /// Parses the service description, fails if it does not define a service.
+    pub fn parse(candid: &str) -> Result<Self, String> {
+        let (env, actor) = CandidSource::Text(candid)
+            .load()
+            .map_err(|err| err.to_string())?;
+        let actor = actor.ok_or("the Candid interface does not define a service".to_string())?;
+
+        Ok(Self { env, actor })
+    }
+
+    fn method(&self, method_name: &str) -> Result<&Function, String> {
+        self.env
+            .get_method(&self.actor, method_name)
+            .map_err(|_| format!("the method `{method_name}` is not part of the Candid interface"))
+    }
+
+    /// Decodes the argument of a call to the method and renders it as Candid text.
+    ///
+    /// Fails if the argument does not type-check against the method arguments.
+    pub fn render_args(&self, method_name: &str, arg: &[u8]) -> Result<String, String> {
+        let method = self.method(method_name)?;
+        let args = IDLArgs::from_bytes_with_types(arg, &self.env, &method.args)
+            .map_err(|err| err.to_string())?;
+
+        Ok(truncate_rendering(args.to_string()))
+    }
+
+    /// Decodes the reply of a call to the method and renders it as Candid text.
+    pub fn render_reply(&self, method_name: &str, reply: &[u8]) -> Result<String, String> {
+        let method = self.method(method_name)?;
+        let rets = IDLArgs::from_bytes_with_types(reply, &self.env, &method.rets)
+            .map_err(|err| err.to_string())?;
+
+        Ok(truncate_rendering(rets.to_string()))
+    }
+}
+
+fn truncate_rendering(mut rendering: String) -> String {
+    if rendering.len() > CandidService::MAX_RENDERING_LEN {
+        let mut end = CandidService::MAX_RENDERING_LEN;
+        while !rendering.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        rendering.truncate(end);
+        rendering.push_str("...");
+    }
+
+    rendering
+}
+
+/// Validates a service description before it is registered for an external canister.
+pub fn validate_candid_interface(
+    candid: &str,
+) -> ModelValidatorResult<ExternalCanisterValidationError> {
+    if candid.len() > ExternalCanisterInterface::MAX_CANDID_LEN {
+        return Err(ExternalCanisterValidationError::ValidationError {
+            info: format!(
+                "The Candid interface exceeds the maximum size of {} bytes.",
+                ExternalCanisterInterface::MAX_CANDID_LEN
+            ),
+        });
+    }
+
+    CandidService::parse(candid).map_err(|err| {
+        ExternalCanisterValidationError::ValidationError {
+            info: format!("The Candid interface is invalid: {err}"),
+        }
+    })?;
+
+    Ok(())
+}
+
+impl ModelValidator<ExternalCanisterValidationError> for ExternalCanisterInterface {
+    fn validate(&self) -> ModelValidatorResult<ExternalCanisterValidationError> {
+        validate_candid_interface(&self.candid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Encode;
+
+    const CANDID: &str = r#"
+        type Tenant = record { name : text; seats : nat32 };
+        service : {
+            add_tenant : (Tenant) -> (nat64);
+            list_tenants : () -> (vec Tenant) query;
+        }
+    "#;
+
+    fn mock_service() -> CandidService {
+        CandidService::parse(CANDID).unwrap()
+    }
+
+    #[derive(candid::CandidType)]
+    struct Tenant {
+        name: String,
+        seats: u32,
+    }
+
+    #[test]
+    fn renders_arguments_and_replies() {
+        let service = mock_service();
+        let arg = Encode!(&Tenant {
+            name: "acme".to_string(),
+            seats: 3,
+        })
+        .unwrap();
+
+        let rendering = service.render_args("add_tenant", &arg).unwrap();
+        assert!(rendering.contains("\"acme\""));
+        assert!(rendering.contains("seats = 3"));
+
+        let reply = Encode!(&42u64).unwrap();
+        assert!(service
+            .render_reply("add_tenant", &reply)
+            .unwrap()
+            .contains("42"));
+    }
+
+    #[test]
+    fn fails_for_arguments_that_do_not_type_check() {
+        let service = mock_service();
+
+        assert!(service
+            .render_args("add_tenant", &Encode!(&"acme").unwrap())
+            .is_err());
+        assert!(service
+            .render_args("remove_tenant", &Encode!(&()).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn fails_for_invalid_interface() {
+        assert!(validate_candid_interface(CANDID).is_ok());
+        assert!(validate_candid_interface("service : { foo : (unknown) -> () }").is_err());
+        assert!(validate_candid_interface("type A = nat;").is_err());
+    }
+
+    #[test]
+    fn truncates_long_renderings() {
+        let service = mock_service();
+        let arg = Encode!(&Tenant {
+            name: "é".repeat(CandidService::MAX_RENDERING_LEN),
+            seats: 3,
+        })
+        .unwrap();
+
+        let rendering = service.render_args("add_tenant", &arg).unwrap();
+        assert!(rendering.len() <= CandidService::MAX_RENDERING_LEN + 3);
+        assert!(rendering.ends_with("..."));
+    }
+}
@@ -2,6 +2,7 @@ pub mod notification_user_index;
 pub mod request_index;
 pub mod request_policy_resource_index;
 pub mod request_resource_index;
+pub mod search_index;
 pub mod transfer_account_index;
 pub mod transfer_status_index;
 pub mod unique_index;
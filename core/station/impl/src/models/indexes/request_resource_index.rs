@@ -1,6 +1,15 @@
-use crate::models::{resource::Resource, Request};
+use crate::models::{
+    resource::{
+        AccountResourceAction, ExecutionMethodResourceTarget, ExternalCanisterId,
+        ExternalCanisterResourceAction, Resource, ResourceAction, ResourceId, UserResourceAction,
+        ValidationMethodResourceTarget,
+    },
+    Request,
+};
+use candid::Principal;
 use orbit_essentials::storable;
 use orbit_essentials::types::UUID;
+use std::collections::BTreeSet;
 
 /// Index of requests by the resource derived from the request operation.
 #[storable]
@@ -17,6 +26,124 @@ pub struct RequestResourceIndexCriteria {
     pub request_id: UUID,
 }
 
+/// A resource that requests are filtered by, matched against the resources derived from the request operation.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RequestResourceFilter {
+    Account(UUID),
+    User(UUID),
+    ExternalCanister(Principal),
+    Asset(UUID),
+    RequestPolicy(UUID),
+}
+
+/// Index of requests by the entries that the resources derived from the request operation refer to,
+/// used to find the requests of a resource filter through a range lookup.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RequestResourceTargetIndex {
+    /// The entry that the resource refers to.
+    pub target: RequestResourceFilter,
+    /// The request id, which is a UUID.
+    pub request_id: UUID,
+}
+
+#[derive(Clone, Debug)]
+pub struct RequestResourceTargetIndexCriteria {
+    pub target: RequestResourceFilter,
+}
+
+impl RequestResourceFilter {
+    /// Returns the entries that the resource refers to, resources of any entry (e.g. `ResourceId::Any`)
+    /// refer to none.
+    pub fn from_resource(resource: &Resource) -> Vec<RequestResourceFilter> {
+        fn id(resource_id: &ResourceId) -> Option<UUID> {
+            match resource_id {
+                ResourceId::Id(id) => Some(*id),
+                ResourceId::Any => None,
+            }
+        }
+
+        fn action_id(action: &ResourceAction) -> Option<UUID> {
+            match action {
+                ResourceAction::List | ResourceAction::Create => None,
+                ResourceAction::Read(resource_id)
+                | ResourceAction::Update(resource_id)
+                | ResourceAction::Delete(resource_id) => id(resource_id),
+            }
+        }
+
+        match resource {
+            Resource::Account(action) => match action {
+                AccountResourceAction::List | AccountResourceAction::Create => None,
+                AccountResourceAction::Transfer(resource_id)
+                | AccountResourceAction::Read(resource_id)
+                | AccountResourceAction::Update(resource_id) => id(resource_id),
+            }
+            .map(RequestResourceFilter::Account)
+            .into_iter()
+            .collect(),
+            Resource::User(action) => match action {
+                UserResourceAction::List | UserResourceAction::Create => None,
+                UserResourceAction::Read(resource_id) | UserResourceAction::Update(resource_id) => {
+                    id(resource_id)
+                }
+            }
+            .map(RequestResourceFilter::User)
+            .into_iter()
+            .collect(),
+            Resource::ExternalCanister(action) => match action {
+                ExternalCanisterResourceAction::List | ExternalCanisterResourceAction::Create => {
+                    vec![]
+                }
+                ExternalCanisterResourceAction::Change(target)
+                | ExternalCanisterResourceAction::Read(target)
+                | ExternalCanisterResourceAction::ReadLogs(target)
+                | ExternalCanisterResourceAction::ReleaseControl(target)
+                | ExternalCanisterResourceAction::Fund(target) => match target {
+                    ExternalCanisterId::Canister(canister_id) => {
+                        vec![RequestResourceFilter::ExternalCanister(*canister_id)]
+                    }
+                    ExternalCanisterId::Any => vec![],
+                },
+                ExternalCanisterResourceAction::Call(target) => {
+                    let mut canister_ids = Vec::new();
+                    if let ExecutionMethodResourceTarget::ExecutionMethod(method) =
+                        &target.execution_method
+                    {
+                        canister_ids.push(method.canister_id);
+                    }
+                    if let ValidationMethodResourceTarget::ValidationMethod(method) =
+                        &target.validation_method
+                    {
+                        canister_ids.push(method.canister_id);
+                    }
+                    canister_ids.dedup();
+
+                    canister_ids
+                        .into_iter()
+                        .map(RequestResourceFilter::ExternalCanister)
+                        .collect()
+                }
+            },
+            Resource::Asset(action) => action_id(action)
+                .map(RequestResourceFilter::Asset)
+                .into_iter()
+                .collect(),
+            Resource::RequestPolicy(action) => action_id(action)
+                .map(RequestResourceFilter::RequestPolicy)
+                .into_iter()
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Checks if the resource refers to the filtered entry.
+    pub fn matches(&self, resource: &Resource) -> bool {
+        Self::from_resource(resource).contains(self)
+    }
+}
+
 impl Request {
     pub fn to_index_for_resource(&self) -> Vec<RequestResourceIndex> {
         self.operation
@@ -28,6 +155,23 @@ impl Request {
             })
             .collect()
     }
+
+    pub fn to_index_for_resource_target(&self) -> Vec<RequestResourceTargetIndex> {
+        let targets = self
+            .operation
+            .to_resources()
+            .iter()
+            .flat_map(RequestResourceFilter::from_resource)
+            .collect::<BTreeSet<_>>();
+
+        targets
+            .into_iter()
+            .map(|target| RequestResourceTargetIndex {
+                target,
+                request_id: self.id,
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -57,6 +201,53 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn resource_filter_matches_resources_of_the_entry() {
+        let account_id = [1; 16];
+        let canister_id = Principal::from_slice(&[2; 29]);
+
+        assert!(
+            RequestResourceFilter::Account(account_id).matches(&Resource::Account(
+                AccountResourceAction::Transfer(ResourceId::Id(account_id))
+            ))
+        );
+        assert!(
+            !RequestResourceFilter::Account(account_id).matches(&Resource::Account(
+                AccountResourceAction::Transfer(ResourceId::Any)
+            ))
+        );
+        assert!(
+            !RequestResourceFilter::Account(account_id).matches(&Resource::User(
+                UserResourceAction::Update(ResourceId::Id(account_id))
+            ))
+        );
+        assert!(
+            RequestResourceFilter::ExternalCanister(canister_id).matches(
+                &Resource::ExternalCanister(ExternalCanisterResourceAction::Call(
+                    crate::models::resource::CallExternalCanisterResourceTarget {
+                        execution_method: ExecutionMethodResourceTarget::ExecutionMethod(
+                            crate::models::CanisterMethod {
+                                canister_id,
+                                method_name: "greet".to_string(),
+                            }
+                        ),
+                        validation_method: ValidationMethodResourceTarget::No,
+                    }
+                ))
+            )
+        );
+        assert!(
+            !RequestResourceFilter::ExternalCanister(canister_id).matches(
+                &Resource::ExternalCanister(ExternalCanisterResourceAction::Change(
+                    ExternalCanisterId::Any
+                ))
+            )
+        );
+        assert!(RequestResourceFilter::RequestPolicy(account_id).matches(
+            &Resource::RequestPolicy(ResourceAction::Delete(ResourceId::Id(account_id)))
+        ));
+    }
+
     #[test]
     fn valid_request_resource_indexes() {
         let mut request = mock_request();
@@ -80,5 +271,33 @@ mod tests {
             index_entries[0].resource,
             Resource::User(UserResourceAction::Create)
         ));
+
+        // creating a user refers to no existing entry
+        assert!(request.to_index_for_resource_target().is_empty());
+    }
+
+    #[test]
+    fn resource_targets_are_deduplicated() {
+        let canister_id = Principal::from_slice(&[2; 29]);
+        let method = crate::models::CanisterMethod {
+            canister_id,
+            method_name: "greet".to_string(),
+        };
+
+        let targets = RequestResourceFilter::from_resource(&Resource::ExternalCanister(
+            ExternalCanisterResourceAction::Call(
+                crate::models::resource::CallExternalCanisterResourceTarget {
+                    execution_method: ExecutionMethodResourceTarget::ExecutionMethod(
+                        method.clone(),
+                    ),
+                    validation_method: ValidationMethodResourceTarget::ValidationMethod(method),
+                },
+            ),
+        ));
+
+        assert_eq!(
+            targets,
+            vec![RequestResourceFilter::ExternalCanister(canister_id)]
+        );
     }
 }
@@ -0,0 +1,142 @@
+use crate::{
+    core::utils::to_search_terms,
+    models::{AddressBookEntry, ExternalCanister, Request},
+};
+use orbit_essentials::{storable, types::UUID};
+use std::collections::BTreeSet;
+
+/// The kind of entry that is indexed for full-text search.
+#[storable]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum SearchIndexKind {
+    Request,
+    AddressBookEntry,
+    ExternalCanister,
+}
+
+/// Inverted index of the normalized terms found in the searchable text of an entry.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct SearchIndexKey {
+    /// The kind of the indexed entry.
+    pub kind: SearchIndexKind,
+    /// The normalized term, see `to_search_terms`.
+    pub term: String,
+    /// The id of the indexed entry.
+    pub entry_id: UUID,
+}
+
+#[derive(Clone, Debug)]
+pub struct SearchIndexCriteria {
+    pub kind: SearchIndexKind,
+    /// Each term of the search text must be the prefix of a term of the entry.
+    pub search_term: String,
+}
+
+impl SearchIndexCriteria {
+    /// Creates the criteria if the search text has any searchable term, otherwise there is nothing to filter by.
+    pub fn new(kind: SearchIndexKind, search_term: Option<&str>) -> Option<Self> {
+        search_term
+            .filter(|search_term| !to_search_terms(search_term).is_empty())
+            .map(|search_term| Self {
+                kind,
+                search_term: search_term.to_string(),
+            })
+    }
+}
+
+fn to_search_index<'a>(
+    kind: SearchIndexKind,
+    entry_id: UUID,
+    texts: impl IntoIterator<Item = &'a str>,
+) -> Vec<SearchIndexKey> {
+    texts
+        .into_iter()
+        .flat_map(to_search_terms)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|term| SearchIndexKey {
+            kind,
+            term,
+            entry_id,
+        })
+        .collect()
+}
+
+/// Checks if the search index entries of a single entry match the search text, for entries that are
+/// not stored in the search index repository (e.g. archived requests).
+pub fn matches_search_term(index: &[SearchIndexKey], search_term: &str) -> bool {
+    to_search_terms(search_term)
+        .iter()
+        .all(|term| index.iter().any(|key| key.term.starts_with(term.as_str())))
+}
+
+impl Request {
+    /// Converts the request title and summary to their search index entries.
+    pub fn to_search_index(&self) -> Vec<SearchIndexKey> {
+        to_search_index(
+            SearchIndexKind::Request,
+            self.id,
+            std::iter::once(self.title.as_str()).chain(self.summary.as_deref()),
+        )
+    }
+}
+
+impl AddressBookEntry {
+    /// Converts the address owner and labels to their search index entries.
+    pub fn to_search_index(&self) -> Vec<SearchIndexKey> {
+        to_search_index(
+            SearchIndexKind::AddressBookEntry,
+            self.id,
+            std::iter::once(self.address_owner.as_str())
+                .chain(self.labels.iter().map(String::as_str)),
+        )
+    }
+}
+
+impl ExternalCanister {
+    /// Converts the canister name and description to their search index entries.
+    pub fn to_search_index(&self) -> Vec<SearchIndexKey> {
+        to_search_index(
+            SearchIndexKind::ExternalCanister,
+            self.id,
+            std::iter::once(self.name.as_str()).chain(self.description.as_deref()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::request_test_utils::mock_request;
+
+    #[test]
+    fn request_search_index_includes_title_and_summary() {
+        let mut request = mock_request();
+        request.title = "Transfer to Zoë".to_string();
+        request.summary = Some("Monthly transfer".to_string());
+
+        let terms = request
+            .to_search_index()
+            .into_iter()
+            .map(|key| {
+                assert_eq!(key.kind, SearchIndexKind::Request);
+                assert_eq!(key.entry_id, request.id);
+                key.term
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(terms, vec!["monthly", "to", "transfer", "zoe"]);
+    }
+
+    #[test]
+    fn search_term_matches_term_prefixes() {
+        let mut request = mock_request();
+        request.title = "Transfer to Zoë".to_string();
+        request.summary = None;
+        let index = request.to_search_index();
+
+        assert!(matches_search_term(&index, "zoe trans"));
+        assert!(!matches_search_term(&index, "zoe monthly"));
+    }
+}
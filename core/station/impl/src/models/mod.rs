@@ -23,6 +23,18 @@ pub use user::*;
 pub mod external_canister;
 pub use external_canister::*;
 
+pub mod external_canister_controllers;
+pub use external_canister_controllers::*;
+
+pub mod external_canister_cycles;
+pub use external_canister_cycles::*;
+
+pub mod wasm_module_registry;
+pub use wasm_module_registry::*;
+
+pub mod external_canister_interface;
+pub use external_canister_interface::*;
+
 pub mod user_group;
 pub use user_group::*;
 
@@ -73,6 +85,9 @@ pub use request_policy_rule::*;
 pub mod request;
 pub use request::*;
 
+pub mod request_snapshot;
+pub use request_snapshot::*;
+
 pub mod asset;
 pub use asset::*;
 
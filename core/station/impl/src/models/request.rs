@@ -94,6 +94,32 @@ pub struct RequestAdditionalInfo {
     pub evaluation_result: Option<RequestEvaluationResult>,
 }
 
+/// A request that was moved to the request archive canister, together with its evaluation result.
+#[derive(Debug, Clone)]
+pub struct ArchivedRequest {
+    pub request: Request,
+    pub evaluation_result: Option<RequestEvaluationResult>,
+}
+
+/// A page of archived requests, the cursor is the position in the archive to continue listing from.
+#[derive(Debug, Clone)]
+pub struct ArchivedRequestsPage {
+    pub items: Vec<ArchivedRequest>,
+    pub next_cursor: Option<u64>,
+}
+
+/// A page of requests, the requests kept in the station are followed by the archived requests.
+///
+/// The total only counts the requests kept in the station, since the archive has no indexes to count
+/// the archived requests that match the filters.
+#[derive(Debug, Clone)]
+pub struct RequestsPage {
+    pub items: Vec<Request>,
+    pub archived_items: Vec<ArchivedRequest>,
+    pub next_offset: Option<u64>,
+    pub total: u64,
+}
+
 fn validate_title(title: &str) -> ModelValidatorResult<RequestError> {
     if title.len() > Request::MAX_TITLE_LEN as usize {
         return Err(RequestError::ValidationError {
@@ -3,15 +3,18 @@ use super::{
     request_policy_rule::{RequestPolicyRule, RequestPolicyRuleInput},
     request_specifier::RequestSpecifier,
     resource::{Resource, ValidationMethodResourceTarget},
-    AccountAsset, AccountId, AddressBookEntryId, AddressFormat, Asset, AssetId, Blockchain,
-    ChangeMetadata, CycleObtainStrategy, DisasterRecoveryCommittee, ExternalCanisterCallPermission,
-    ExternalCanisterState, MetadataItem, NamedRuleId, TokenStandard, UserGroupId, UserId,
-    UserStatus,
+    validate_candid_interface, validate_canister_controllers, validate_controllers_change,
+    validate_read_only_methods, AccountAsset, AccountId, AddressBookEntryId, AddressFormat, Asset,
+    AssetId, Blockchain, ChangeMetadata, CycleObtainStrategy, DisasterRecoveryCommittee,
+    ExternalCanisterCallPermission, ExternalCanisterState, MetadataItem, NamedRuleId,
+    ResolvedWasmModuleRegistryEntry, TokenStandard, UserGroupId, UserId, UserStatus,
+    WasmModuleRegistryReference,
 };
 use crate::core::validation::{
     EnsureAccount, EnsureAddressBookEntry, EnsureAsset, EnsureExternalCanister, EnsureIdExists,
     EnsureNamedRule, EnsureRequestPolicy, EnsureUser, EnsureUserGroup,
 };
+use crate::core::MIN_REQUEST_ARCHIVE_AGE_SECS;
 use crate::errors::{ExternalCanisterValidationError, SystemInfoValidationError, ValidationError};
 use crate::models::resource::ExecutionMethodResourceTarget;
 use crate::models::Metadata;
@@ -61,6 +64,9 @@ pub enum RequestOperation {
     AddNamedRule(AddNamedRuleOperation),
     EditNamedRule(EditNamedRuleOperation),
     RemoveNamedRule(RemoveNamedRuleOperation),
+    FreezeStation(FreezeStationOperation),
+    UnfreezeStation(UnfreezeStationOperation),
+    UpgradeExternalCanisterFleet(UpgradeExternalCanisterFleetOperation),
 }
 
 impl Display for RequestOperation {
@@ -108,10 +114,27 @@ impl Display for RequestOperation {
             RequestOperation::AddNamedRule(_) => write!(f, "add_named_rule"),
             RequestOperation::EditNamedRule(_) => write!(f, "edit_named_rule"),
             RequestOperation::RemoveNamedRule(_) => write!(f, "remove_named_rule"),
+            RequestOperation::FreezeStation(_) => write!(f, "freeze_station"),
+            RequestOperation::UnfreezeStation(_) => write!(f, "unfreeze_station"),
+            RequestOperation::UpgradeExternalCanisterFleet(_) => {
+                write!(f, "upgrade_external_canister_fleet")
+            }
         }
     }
 }
 
+impl RequestOperation {
+    /// Whether the execution of the operation is held back while the station is frozen.
+    pub fn is_blocked_by_freeze(&self) -> bool {
+        matches!(
+            self,
+            RequestOperation::Transfer(_)
+                | RequestOperation::CallExternalCanister(_)
+                | RequestOperation::FundExternalCanister(_)
+        )
+    }
+}
+
 #[storable]
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct AddAssetOperation {
@@ -411,6 +434,9 @@ pub struct SystemUpgradeOperationInput {
     #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
     pub arg: Option<Vec<u8>>,
     pub take_backup_snapshot: Option<bool>,
+    /// The registry entry to install instead of the uploaded module.
+    #[serde(default)]
+    pub module_registry: Option<WasmModuleRegistryReference>,
 }
 
 #[storable]
@@ -421,6 +447,8 @@ pub struct SystemUpgradeOperation {
     #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
     pub arg_checksum: Option<Vec<u8>>,
     pub take_backup_snapshot: Option<bool>,
+    #[serde(default)]
+    pub module_registry_entry: Option<ResolvedWasmModuleRegistryEntry>,
     pub input: SystemUpgradeOperationInput,
 }
 
@@ -445,6 +473,28 @@ pub struct SystemRestoreOperation {
     pub input: SystemRestoreOperationInput,
 }
 
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FreezeStationOperationInput {
+    pub reason: Option<String>,
+}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FreezeStationOperation {
+    pub input: FreezeStationOperationInput,
+}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UnfreezeStationOperationInput {}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UnfreezeStationOperation {
+    pub input: UnfreezeStationOperationInput,
+}
+
 #[storable]
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct SetDisasterRecoveryOperation {
@@ -529,6 +579,39 @@ pub struct ChangeExternalCanisterOperationInput {
     pub module_extra_chunks: Option<WasmModuleExtraChunks>,
     #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
     pub arg: Option<Vec<u8>>,
+    #[serde(default)]
+    pub rollback: Option<ExternalCanisterRollbackInput>,
+    /// The registry entry to install instead of the uploaded module.
+    #[serde(default)]
+    pub module_registry: Option<WasmModuleRegistryReference>,
+}
+
+/// Restores the canister from a snapshot taken right before the install if the install,
+/// or the health check that follows it, fails.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExternalCanisterRollbackInput {
+    #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
+    pub replace_snapshot: Option<Vec<u8>>,
+    /// The method of the canister called with an empty argument after the install.
+    pub health_check_method: Option<String>,
+}
+
+impl ModelValidator<ValidationError> for ExternalCanisterRollbackInput {
+    fn validate(&self) -> ModelValidatorResult<ValidationError> {
+        if self
+            .health_check_method
+            .as_ref()
+            .is_some_and(|method_name| method_name.trim().is_empty())
+        {
+            return Err(ExternalCanisterValidationError::ValidationError {
+                info: "The health check method name cannot be empty.".to_string(),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
 }
 
 #[storable]
@@ -538,9 +621,139 @@ pub struct ChangeExternalCanisterOperation {
     pub module_checksum: Vec<u8>,
     #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
     pub arg_checksum: Option<Vec<u8>>,
+    /// The snapshot taken right before the install if the rollback is enabled, only kept if it could
+    /// not be deleted once the change succeeded.
+    #[serde(
+        default,
+        deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob"
+    )]
+    pub rollback_snapshot_id: Option<Vec<u8>>,
+    #[serde(default)]
+    pub module_registry_entry: Option<ResolvedWasmModuleRegistryEntry>,
     pub input: ChangeExternalCanisterOperationInput,
 }
 
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ExternalCanisterFleetTarget {
+    /// All active external canisters that have any of the given labels.
+    Labels(Vec<String>),
+    CanisterIds(Vec<Principal>),
+}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExternalCanisterFleetArgInput {
+    pub canister_id: Principal,
+    #[serde(with = "serde_bytes")]
+    pub arg: Vec<u8>,
+}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UpgradeExternalCanisterFleetOperationInput {
+    pub target: ExternalCanisterFleetTarget,
+    pub mode: CanisterInstallMode,
+    /// The module is only available while the operation is not finalized.
+    #[serde(with = "serde_bytes")]
+    pub module: Vec<u8>,
+    pub module_extra_chunks: Option<WasmModuleExtraChunks>,
+    /// The argument of the canisters that are not listed in `canister_args`.
+    #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
+    pub arg: Option<Vec<u8>>,
+    pub canister_args: Vec<ExternalCanisterFleetArgInput>,
+    /// The canisters that are installed before the rest of the fleet.
+    pub canary_canister_ids: Vec<Principal>,
+}
+
+impl UpgradeExternalCanisterFleetOperationInput {
+    /// Returns the argument that is passed to the new wasm module of the given canister.
+    pub fn arg_for(&self, canister_id: &Principal) -> Option<Vec<u8>> {
+        self.canister_args
+            .iter()
+            .find(|canister_arg| canister_arg.canister_id == *canister_id)
+            .map(|canister_arg| canister_arg.arg.clone())
+            .or_else(|| self.arg.clone())
+    }
+
+    pub fn is_canary(&self, canister_id: &Principal) -> bool {
+        self.canary_canister_ids.contains(canister_id)
+    }
+}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ExternalCanisterUpgradeStatus {
+    Pending,
+    Completed,
+    Failed {
+        reason: String,
+    },
+    /// The canister was not installed because an earlier canister of the fleet failed.
+    Skipped,
+}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExternalCanisterUpgradeOutcome {
+    pub canister_id: Principal,
+    #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
+    pub arg_checksum: Option<Vec<u8>>,
+    pub status: ExternalCanisterUpgradeStatus,
+}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UpgradeExternalCanisterFleetOperation {
+    #[serde(with = "serde_bytes")]
+    pub module_checksum: Vec<u8>,
+    /// The targeted canisters in installation order, the canaries come first.
+    ///
+    /// The target is resolved when the request is created, so canisters labeled afterwards are not included.
+    pub outcomes: Vec<ExternalCanisterUpgradeOutcome>,
+    pub input: UpgradeExternalCanisterFleetOperationInput,
+}
+
+impl ModelValidator<ValidationError> for UpgradeExternalCanisterFleetOperation {
+    fn validate(&self) -> ModelValidatorResult<ValidationError> {
+        if self.outcomes.is_empty() {
+            return Err(ExternalCanisterValidationError::ValidationError {
+                info: "The fleet upgrade does not target any external canister.".to_string(),
+            }
+            .into());
+        }
+
+        for outcome in &self.outcomes {
+            EnsureExternalCanister::ensure_external_canister(outcome.canister_id)?;
+        }
+
+        let targets = self
+            .outcomes
+            .iter()
+            .map(|outcome| outcome.canister_id)
+            .collect::<HashSet<_>>();
+
+        for canister_id in self.input.canary_canister_ids.iter().chain(
+            self.input
+                .canister_args
+                .iter()
+                .map(|canister_arg| &canister_arg.canister_id),
+        ) {
+            if !targets.contains(canister_id) {
+                return Err(ExternalCanisterValidationError::ValidationError {
+                    info: format!(
+                        "The canister {} is not targeted by the fleet upgrade.",
+                        canister_id
+                    ),
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[storable]
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ExternalCanisterPermissionsCreateInput {
@@ -782,6 +995,70 @@ pub enum ConfigureExternalCanisterOperationKind {
     SoftDelete,
     Delete,
     NativeSettings(DefiniteCanisterSettingsInput),
+    /// Sets the automatic backup schedule of the canister, `None` disables automatic backups.
+    BackupSchedule(Option<ExternalCanisterBackupScheduleInput>),
+    /// Registers the Candid interface of the canister, `None` removes it.
+    CandidInterface(Option<String>),
+    /// Adds controllers to the canister.
+    AddControllers(Vec<Principal>),
+    /// Removes controllers from the canister, the station itself cannot be removed.
+    RemoveControllers(Vec<Principal>),
+    /// Hands over the canister to the given controllers, the station is no longer a controller afterwards.
+    ReleaseControl(Vec<Principal>),
+    /// Sets the methods that can be called through the station without a request.
+    ReadOnlyMethods(Vec<String>),
+}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExternalCanisterBackupScheduleInput {
+    /// The interval between periodic snapshots, no periodic snapshots are taken if not set.
+    pub interval_secs: Option<u64>,
+    /// Whether a snapshot is taken before every `ChangeExternalCanister` request is executed.
+    pub before_change: bool,
+    /// The number of backup snapshots to keep, older backup snapshots are pruned.
+    pub retention_count: u64,
+}
+
+impl ExternalCanisterBackupScheduleInput {
+    /// The minimum interval between periodic snapshots, since taking a snapshot stops the canister.
+    pub const MIN_INTERVAL_SECS: u64 = 60 * 60;
+}
+
+impl ModelValidator<ValidationError> for ExternalCanisterBackupScheduleInput {
+    fn validate(&self) -> ModelValidatorResult<ValidationError> {
+        if self.retention_count == 0 || self.retention_count > ICP_MAX_CANISTER_SNAPSHOTS {
+            return Err(ExternalCanisterValidationError::ValidationError {
+                info: format!(
+                    "The backup retention count must be between 1 and {}, got {}.",
+                    ICP_MAX_CANISTER_SNAPSHOTS, self.retention_count
+                ),
+            }
+            .into());
+        }
+
+        if self
+            .interval_secs
+            .is_some_and(|interval_secs| interval_secs < Self::MIN_INTERVAL_SECS)
+        {
+            return Err(ExternalCanisterValidationError::ValidationError {
+                info: format!(
+                    "The backup interval must be at least {} seconds.",
+                    Self::MIN_INTERVAL_SECS
+                ),
+            }
+            .into());
+        }
+
+        if self.interval_secs.is_none() && !self.before_change {
+            return Err(ExternalCanisterValidationError::ValidationError {
+                info: "The backup schedule must either set an interval or take snapshots before changes.".to_string(),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
 }
 
 #[storable]
@@ -875,8 +1152,14 @@ pub struct CallExternalCanisterOperation {
     #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
     pub arg_checksum: Option<Vec<u8>>,
     pub arg_rendering: Option<String>,
+    /// The argument decoded against the registered Candid interface of the canister.
+    #[serde(default)]
+    pub arg_candid: Option<String>,
     #[serde(deserialize_with = "orbit_essentials::deserialize::deserialize_option_blob")]
     pub execution_method_reply: Option<Vec<u8>>,
+    /// The reply decoded against the registered Candid interface of the canister.
+    #[serde(default)]
+    pub execution_method_reply_candid: Option<String>,
 }
 
 #[storable]
@@ -1006,6 +1289,26 @@ pub struct ManageSystemInfoOperationInput {
     pub cycle_obtain_strategy: Option<CycleObtainStrategy>,
     pub max_station_backup_snapshots: Option<u64>,
     pub max_upgrader_backup_snapshots: Option<u64>,
+    #[serde(default)]
+    pub request_archive: Option<RequestArchiveConfigInput>,
+    #[serde(default)]
+    pub control_panel_id: Option<Principal>,
+}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DeployRequestArchiveInput {
+    /// The module is only available while the operation is not finalized.
+    #[serde(with = "serde_bytes")]
+    pub wasm_module: Vec<u8>,
+    pub initial_cycles: Option<u128>,
+}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RequestArchiveConfigInput {
+    pub min_age_secs: Option<u64>,
+    pub deploy: Option<DeployRequestArchiveInput>,
 }
 
 impl ModelValidator<ValidationError> for ManageSystemInfoOperationInput {
@@ -1031,6 +1334,20 @@ impl ModelValidator<ValidationError> for ManageSystemInfoOperationInput {
             check_max_backup_snapshots(max_backup_snapshots)?;
         }
 
+        if let Some(min_age_secs) = self
+            .request_archive
+            .as_ref()
+            .and_then(|config| config.min_age_secs)
+        {
+            if min_age_secs < MIN_REQUEST_ARCHIVE_AGE_SECS {
+                let err = SystemInfoValidationError::InvalidRequestArchiveMinAge {
+                    provided: min_age_secs,
+                    min: MIN_REQUEST_ARCHIVE_AGE_SECS,
+                };
+                return Err(err.into());
+            }
+        }
+
         Ok(())
     }
 }
@@ -1186,15 +1503,47 @@ impl ModelValidator<ValidationError> for RequestOperation {
             RequestOperation::ChangeExternalCanister(op) => {
                 let canister_id = op.input.canister_id;
                 EnsureExternalCanister::ensure_external_canister(canister_id)?;
+                if let Some(rollback) = &op.input.rollback {
+                    rollback.validate()?;
+                }
             }
             RequestOperation::ConfigureExternalCanister(op) => {
                 let canister_id = op.canister_id;
                 EnsureExternalCanister::ensure_external_canister(canister_id)?;
-                if let ConfigureExternalCanisterOperationKind::Settings(settings) = &op.kind {
-                    if let Some(updated_request_policies) = &settings.request_policies {
-                        ContextualModel::new(updated_request_policies.clone(), canister_id)
-                            .validate()?;
+                match &op.kind {
+                    ConfigureExternalCanisterOperationKind::Settings(settings) => {
+                        if let Some(updated_request_policies) = &settings.request_policies {
+                            ContextualModel::new(updated_request_policies.clone(), canister_id)
+                                .validate()?;
+                        }
+                    }
+                    ConfigureExternalCanisterOperationKind::BackupSchedule(Some(schedule)) => {
+                        schedule.validate()?;
+                    }
+                    ConfigureExternalCanisterOperationKind::CandidInterface(Some(candid)) => {
+                        validate_candid_interface(candid)?;
+                    }
+                    ConfigureExternalCanisterOperationKind::NativeSettings(
+                        DefiniteCanisterSettingsInput {
+                            controllers: Some(controllers),
+                            ..
+                        },
+                    ) => {
+                        validate_canister_controllers(controllers, false)?;
+                    }
+                    ConfigureExternalCanisterOperationKind::AddControllers(controllers) => {
+                        validate_controllers_change(controllers, false)?;
                     }
+                    ConfigureExternalCanisterOperationKind::RemoveControllers(controllers) => {
+                        validate_controllers_change(controllers, true)?;
+                    }
+                    ConfigureExternalCanisterOperationKind::ReleaseControl(controllers) => {
+                        validate_canister_controllers(controllers, true)?;
+                    }
+                    ConfigureExternalCanisterOperationKind::ReadOnlyMethods(methods) => {
+                        validate_read_only_methods(methods)?;
+                    }
+                    _ => (),
                 }
             }
             RequestOperation::FundExternalCanister(op) => {
@@ -1260,6 +1609,11 @@ impl ModelValidator<ValidationError> for RequestOperation {
             RequestOperation::RemoveNamedRule(op) => {
                 EnsureNamedRule::id_exists(&op.input.named_rule_id)?;
             }
+            RequestOperation::FreezeStation(_) => (),
+            RequestOperation::UnfreezeStation(_) => (),
+            RequestOperation::UpgradeExternalCanisterFleet(op) => {
+                op.validate()?;
+            }
         }
         Ok(())
     }
@@ -1626,9 +1980,13 @@ mod test {
                     module: vec![],
                     module_extra_chunks: None,
                     arg: None,
+                    rollback: None,
+                    module_registry: None,
                 },
                 module_checksum: vec![],
                 arg_checksum: None,
+                rollback_snapshot_id: None,
+                module_registry_entry: None,
             },
         )
         .validate()
@@ -1754,7 +2112,9 @@ mod test {
                 },
                 arg_checksum: None,
                 arg_rendering: None,
+                arg_candid: None,
                 execution_method_reply: None,
+                execution_method_reply_candid: None,
             })
             .validate()
             .unwrap_err();
@@ -1781,7 +2141,9 @@ mod test {
                 },
                 arg_checksum: None,
                 arg_rendering: None,
+                arg_candid: None,
                 execution_method_reply: None,
+                execution_method_reply_candid: None,
             })
             .validate()
             .unwrap_err();
@@ -1809,6 +2171,8 @@ mod test {
                 name: None,
                 max_station_backup_snapshots: Some(max_backup_snapshots),
                 max_upgrader_backup_snapshots: None,
+                request_archive: None,
+                control_panel_id: None,
             },
         })
         .validate()
@@ -1820,6 +2184,8 @@ mod test {
                 name: None,
                 max_station_backup_snapshots: None,
                 max_upgrader_backup_snapshots: Some(max_backup_snapshots),
+                request_archive: None,
+                control_panel_id: None,
             },
         })
         .validate()
@@ -1852,6 +2218,8 @@ mod test {
                 name: None,
                 max_station_backup_snapshots: Some(max_backup_snapshots),
                 max_upgrader_backup_snapshots: None,
+                request_archive: None,
+                control_panel_id: None,
             },
         })
         .validate()
@@ -1864,10 +2232,102 @@ mod test {
                 name: None,
                 max_station_backup_snapshots: None,
                 max_upgrader_backup_snapshots: Some(max_backup_snapshots),
+                request_archive: None,
+                control_panel_id: None,
             },
         })
         .validate()
         .unwrap_err();
         check_err(err);
     }
+
+    #[tokio::test]
+    async fn fail_request_operation_with_too_low_request_archive_min_age() {
+        let min_age_secs = MIN_REQUEST_ARCHIVE_AGE_SECS - 1;
+
+        let err = RequestOperation::ManageSystemInfo(crate::models::ManageSystemInfoOperation {
+            input: crate::models::ManageSystemInfoOperationInput {
+                cycle_obtain_strategy: None,
+                name: None,
+                max_station_backup_snapshots: None,
+                max_upgrader_backup_snapshots: None,
+                request_archive: Some(crate::models::RequestArchiveConfigInput {
+                    min_age_secs: Some(min_age_secs),
+                    deploy: None,
+                }),
+                control_panel_id: None,
+            },
+        })
+        .validate()
+        .unwrap_err();
+
+        match err {
+            ValidationError::SystemInfoValidationError(
+                SystemInfoValidationError::InvalidRequestArchiveMinAge { provided, min },
+            ) => {
+                assert_eq!(provided, min_age_secs);
+                assert_eq!(min, MIN_REQUEST_ARCHIVE_AGE_SECS);
+            }
+            _ => panic!("Unexpected error: {err:?}"),
+        };
+    }
+
+    #[test]
+    fn fail_backup_schedule_with_invalid_retention_or_interval() {
+        let schedule = ExternalCanisterBackupScheduleInput {
+            interval_secs: Some(ExternalCanisterBackupScheduleInput::MIN_INTERVAL_SECS),
+            before_change: false,
+            retention_count: ICP_MAX_CANISTER_SNAPSHOTS,
+        };
+
+        assert!(schedule.validate().is_ok());
+
+        for schedule in [
+            ExternalCanisterBackupScheduleInput {
+                retention_count: 0,
+                ..schedule.clone()
+            },
+            ExternalCanisterBackupScheduleInput {
+                retention_count: ICP_MAX_CANISTER_SNAPSHOTS + 1,
+                ..schedule.clone()
+            },
+            ExternalCanisterBackupScheduleInput {
+                interval_secs: Some(ExternalCanisterBackupScheduleInput::MIN_INTERVAL_SECS - 1),
+                ..schedule.clone()
+            },
+            ExternalCanisterBackupScheduleInput {
+                interval_secs: None,
+                ..schedule.clone()
+            },
+        ] {
+            assert!(matches!(
+                schedule.validate().unwrap_err(),
+                ValidationError::ExternalCanisterValidationError(
+                    ExternalCanisterValidationError::ValidationError { .. }
+                )
+            ));
+        }
+    }
+
+    #[test]
+    fn fail_rollback_with_empty_health_check_method() {
+        let rollback = ExternalCanisterRollbackInput {
+            replace_snapshot: None,
+            health_check_method: Some("health".to_string()),
+        };
+
+        assert!(rollback.validate().is_ok());
+        assert!(ExternalCanisterRollbackInput {
+            health_check_method: None,
+            ..rollback.clone()
+        }
+        .validate()
+        .is_ok());
+        assert!(ExternalCanisterRollbackInput {
+            health_check_method: Some(" ".to_string()),
+            ..rollback
+        }
+        .validate()
+        .is_err());
+    }
 }
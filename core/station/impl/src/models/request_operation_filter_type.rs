@@ -39,6 +39,9 @@ pub enum RequestOperationFilterType {
     AddNamedRule,
     EditNamedRule,
     RemoveNamedRule,
+    FreezeStation,
+    UnfreezeStation,
+    UpgradeExternalCanisterFleet,
 }
 
 impl From<RequestOperation> for RequestOperationFilterType {
@@ -110,6 +113,11 @@ impl From<RequestOperation> for RequestOperationFilterType {
             RequestOperation::AddNamedRule(_) => RequestOperationFilterType::AddNamedRule,
             RequestOperation::EditNamedRule(_) => RequestOperationFilterType::EditNamedRule,
             RequestOperation::RemoveNamedRule(_) => RequestOperationFilterType::RemoveNamedRule,
+            RequestOperation::FreezeStation(_) => RequestOperationFilterType::FreezeStation,
+            RequestOperation::UnfreezeStation(_) => RequestOperationFilterType::UnfreezeStation,
+            RequestOperation::UpgradeExternalCanisterFleet(_) => {
+                RequestOperationFilterType::UpgradeExternalCanisterFleet
+            }
         }
     }
 }
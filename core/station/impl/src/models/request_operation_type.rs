@@ -44,6 +44,9 @@ pub enum RequestOperationType {
     EditNamedRule = 34,
     RemoveNamedRule = 35,
     SystemRestore = 36,
+    FreezeStation = 37,
+    UnfreezeStation = 38,
+    UpgradeExternalCanisterFleet = 39,
 }
 
 /// A helper enum to filter the requests based on the operation type and
@@ -84,6 +87,9 @@ pub enum ListRequestsOperationType {
     AddNamedRule,
     EditNamedRule,
     RemoveNamedRule,
+    FreezeStation,
+    UnfreezeStation,
+    UpgradeExternalCanisterFleet,
 }
 
 impl PartialEq<ListRequestsOperationType> for RequestOperationFilterType {
@@ -246,6 +252,18 @@ impl PartialEq<ListRequestsOperationType> for RequestOperationFilterType {
             ListRequestsOperationType::RemoveNamedRule => {
                 matches!(self, RequestOperationFilterType::RemoveNamedRule)
             }
+            ListRequestsOperationType::FreezeStation => {
+                matches!(self, RequestOperationFilterType::FreezeStation)
+            }
+            ListRequestsOperationType::UnfreezeStation => {
+                matches!(self, RequestOperationFilterType::UnfreezeStation)
+            }
+            ListRequestsOperationType::UpgradeExternalCanisterFleet => {
+                matches!(
+                    self,
+                    RequestOperationFilterType::UpgradeExternalCanisterFleet
+                )
+            }
         }
     }
 }
@@ -280,6 +298,11 @@ impl FromStr for RequestOperationType {
             "configure_external_canister" => Ok(RequestOperationType::ConfigureExternalCanister),
             "fund_external_canister" => Ok(RequestOperationType::FundExternalCanister),
             "monitor_external_canister" => Ok(RequestOperationType::MonitorExternalCanister),
+            "freeze_station" => Ok(RequestOperationType::FreezeStation),
+            "unfreeze_station" => Ok(RequestOperationType::UnfreezeStation),
+            "upgrade_external_canister_fleet" => {
+                Ok(RequestOperationType::UpgradeExternalCanisterFleet)
+            }
             _ => Err(()),
         }
     }
@@ -332,6 +355,11 @@ impl Display for RequestOperationType {
             RequestOperationType::AddNamedRule => write!(f, "add_named_rule"),
             RequestOperationType::EditNamedRule => write!(f, "edit_named_rule"),
             RequestOperationType::RemoveNamedRule => write!(f, "remove_named_rule"),
+            RequestOperationType::FreezeStation => write!(f, "freeze_station"),
+            RequestOperationType::UnfreezeStation => write!(f, "unfreeze_station"),
+            RequestOperationType::UpgradeExternalCanisterFleet => {
+                write!(f, "upgrade_external_canister_fleet")
+            }
         }
     }
 }
@@ -470,5 +498,17 @@ mod tests {
             RequestOperationType::from_str("fund_external_canister").unwrap(),
             RequestOperationType::FundExternalCanister
         );
+        assert_eq!(
+            RequestOperationType::from_str("freeze_station").unwrap(),
+            RequestOperationType::FreezeStation
+        );
+        assert_eq!(
+            RequestOperationType::from_str("unfreeze_station").unwrap(),
+            RequestOperationType::UnfreezeStation
+        );
+        assert_eq!(
+            RequestOperationType::from_str("upgrade_external_canister_fleet").unwrap(),
+            RequestOperationType::UpgradeExternalCanisterFleet
+        );
     }
 }
@@ -93,6 +93,7 @@ fn _validate_rule_for_specifier(
                 | RequestSpecifier::ChangeExternalCanister(..)
                 | RequestSpecifier::CallExternalCanister(..)
                 | RequestSpecifier::FundExternalCanister(..)
+                | RequestSpecifier::ReleaseExternalCanisterControl(..)
                 | RequestSpecifier::EditPermission(..)
                 | RequestSpecifier::AddRequestPolicy
                 | RequestSpecifier::EditRequestPolicy(..)
@@ -107,7 +108,9 @@ fn _validate_rule_for_specifier(
                 | RequestSpecifier::RemoveAsset(..)
                 | RequestSpecifier::AddNamedRule
                 | RequestSpecifier::EditNamedRule(..)
-                | RequestSpecifier::RemoveNamedRule(..) => {
+                | RequestSpecifier::RemoveNamedRule(..)
+                | RequestSpecifier::FreezeStation
+                | RequestSpecifier::UnfreezeStation => {
                     Err(RequestPolicyError::InvalidRuleForSpecifier {
                         invalid_rule: rule.to_string(),
                         specifier: specifier.to_string(),
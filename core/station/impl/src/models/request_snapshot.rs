@@ -0,0 +1,200 @@
+use super::RequestId;
+use orbit_essentials::{storable, types::Timestamp};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The field values of an entity at a given time, rendered as text so that they can be compared.
+///
+/// Fields that are not set (e.g. an account without a transfer policy) are not included.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntitySnapshot {
+    pub fields: BTreeMap<String, String>,
+    pub taken_at: Timestamp,
+}
+
+/// The snapshots of the entity that is edited by a request.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestSnapshot {
+    pub request_id: RequestId,
+    /// The entity as it was when the request was created.
+    pub at_creation: EntitySnapshot,
+    /// The entity as it would be after the request is executed, based on the snapshot at creation.
+    pub requested: BTreeMap<String, String>,
+    /// The entity right after the request was executed.
+    pub after_execution: Option<EntitySnapshot>,
+}
+
+/// The change of a single field of the edited entity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestFieldDiff {
+    pub field: String,
+    /// The value when the request was created.
+    pub before: Option<String>,
+    /// The requested value, or the actual value once the request was executed.
+    pub after: Option<String>,
+    /// The current value, only set if the field changed since the request was created.
+    pub current: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestDiff {
+    pub fields: Vec<RequestFieldDiff>,
+    pub snapshot_taken_at: Timestamp,
+    pub executed_at: Option<Timestamp>,
+    /// Whether the entity changed since the request was created, which is only checked while the
+    /// request is not yet executed.
+    pub is_stale: bool,
+}
+
+/// The entity edited by a request as it is now, to detect requests that became stale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurrentEntity<'a> {
+    /// The entity is not compared, e.g. because the request can no longer be executed.
+    NotChecked,
+    /// The entity no longer exists.
+    Removed,
+    /// The current fields of the entity.
+    Fields(&'a BTreeMap<String, String>),
+}
+
+impl CurrentEntity<'_> {
+    fn field(&self, field: &str) -> Option<&String> {
+        match self {
+            CurrentEntity::Fields(fields) => fields.get(field),
+            CurrentEntity::NotChecked | CurrentEntity::Removed => None,
+        }
+    }
+}
+
+impl RequestSnapshot {
+    /// Computes the field level diff of the request.
+    ///
+    /// The current entity is only used to detect stale requests if the request was not yet executed.
+    pub fn diff(&self, current: CurrentEntity) -> RequestDiff {
+        let after = self
+            .after_execution
+            .as_ref()
+            .map(|snapshot| &snapshot.fields)
+            .unwrap_or(&self.requested);
+        let current = match &self.after_execution {
+            Some(_) => CurrentEntity::NotChecked,
+            None => current,
+        };
+
+        let fields = self
+            .at_creation
+            .fields
+            .keys()
+            .chain(after.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|field| {
+                let before = self.at_creation.fields.get(field);
+                let after = after.get(field);
+                let changed_since_creation =
+                    current != CurrentEntity::NotChecked && current.field(field) != before;
+
+                if before == after && !changed_since_creation {
+                    return None;
+                }
+
+                Some(RequestFieldDiff {
+                    field: field.to_owned(),
+                    before: before.cloned(),
+                    after: after.cloned(),
+                    current: match changed_since_creation {
+                        true => current.field(field).cloned(),
+                        false => None,
+                    },
+                })
+            })
+            .collect();
+
+        RequestDiff {
+            fields,
+            snapshot_taken_at: self.at_creation.taken_at,
+            executed_at: self
+                .after_execution
+                .as_ref()
+                .map(|snapshot| snapshot.taken_at),
+            is_stale: match current {
+                CurrentEntity::NotChecked => false,
+                CurrentEntity::Removed => true,
+                CurrentEntity::Fields(fields) => *fields != self.at_creation.fields,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(fields: &[(&str, &str)], taken_at: Timestamp) -> EntitySnapshot {
+        EntitySnapshot {
+            fields: fields
+                .iter()
+                .map(|(field, value)| (field.to_string(), value.to_string()))
+                .collect(),
+            taken_at,
+        }
+    }
+
+    fn request_snapshot() -> RequestSnapshot {
+        RequestSnapshot {
+            request_id: [0; 16],
+            at_creation: snapshot(&[("name", "Main"), ("status", "Active")], 1),
+            requested: snapshot(&[("name", "Treasury"), ("status", "Active")], 1).fields,
+            after_execution: None,
+        }
+    }
+
+    #[test]
+    fn diff_only_includes_changed_fields() {
+        let request_snapshot = request_snapshot();
+        let current = request_snapshot.at_creation.clone();
+
+        let diff = request_snapshot.diff(CurrentEntity::Fields(&current.fields));
+
+        assert!(!diff.is_stale);
+        assert_eq!(
+            diff.fields,
+            vec![RequestFieldDiff {
+                field: "name".to_string(),
+                before: Some("Main".to_string()),
+                after: Some("Treasury".to_string()),
+                current: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_flags_entity_changed_after_creation() {
+        let request_snapshot = request_snapshot();
+        let current = snapshot(&[("name", "Main"), ("status", "Inactive")], 2);
+
+        let diff = request_snapshot.diff(CurrentEntity::Fields(&current.fields));
+
+        assert!(diff.is_stale);
+        assert_eq!(diff.fields.len(), 2);
+        assert_eq!(diff.fields[1].field, "status");
+        assert_eq!(diff.fields[1].current, Some("Inactive".to_string()));
+
+        assert!(request_snapshot.diff(CurrentEntity::Removed).is_stale);
+    }
+
+    #[test]
+    fn diff_uses_execution_snapshot() {
+        let mut request_snapshot = request_snapshot();
+        request_snapshot.after_execution =
+            Some(snapshot(&[("name", "Treasury"), ("status", "Inactive")], 3));
+
+        let diff = request_snapshot.diff(CurrentEntity::Fields(&request_snapshot.requested));
+
+        assert!(!diff.is_stale);
+        assert_eq!(diff.executed_at, Some(3));
+        assert_eq!(diff.fields.len(), 2);
+        assert_eq!(diff.fields[1].after, Some("Inactive".to_string()));
+    }
+}
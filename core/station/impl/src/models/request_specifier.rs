@@ -70,6 +70,7 @@ pub enum RequestSpecifier {
     ChangeExternalCanister(ExternalCanisterId),
     CallExternalCanister(CallExternalCanisterResourceTarget),
     FundExternalCanister(ExternalCanisterId),
+    ReleaseExternalCanisterControl(ExternalCanisterId),
     EditPermission(ResourceSpecifier),
     AddRequestPolicy,
     EditRequestPolicy(ResourceIds),
@@ -86,6 +87,9 @@ pub enum RequestSpecifier {
     AddNamedRule,
     EditNamedRule(ResourceIds),
     RemoveNamedRule(ResourceIds),
+
+    FreezeStation,
+    UnfreezeStation,
 }
 
 impl fmt::Display for RequestSpecifier {
@@ -104,6 +108,9 @@ impl fmt::Display for RequestSpecifier {
             RequestSpecifier::ChangeExternalCanister(_) => write!(f, "ChangeExternalCanister"),
             RequestSpecifier::CallExternalCanister(_) => write!(f, "CallExternalCanister"),
             RequestSpecifier::FundExternalCanister(_) => write!(f, "FundExternalCanister"),
+            RequestSpecifier::ReleaseExternalCanisterControl(_) => {
+                write!(f, "ReleaseExternalCanisterControl")
+            }
             RequestSpecifier::EditPermission(_) => write!(f, "EditPermission"),
             RequestSpecifier::AddRequestPolicy => write!(f, "AddRequestPolicy"),
             RequestSpecifier::EditRequestPolicy(_) => write!(f, "EditRequestPolicy"),
@@ -119,6 +126,8 @@ impl fmt::Display for RequestSpecifier {
             RequestSpecifier::AddNamedRule => write!(f, "AddNamedRule"),
             RequestSpecifier::EditNamedRule(_) => write!(f, "EditNamedRule"),
             RequestSpecifier::RemoveNamedRule(_) => write!(f, "RemoveNamedRule"),
+            RequestSpecifier::FreezeStation => write!(f, "FreezeStation"),
+            RequestSpecifier::UnfreezeStation => write!(f, "UnfreezeStation"),
         }
     }
 }
@@ -132,13 +141,16 @@ impl ModelValidator<ValidationError> for RequestSpecifier {
             | RequestSpecifier::SystemUpgrade
             | RequestSpecifier::ChangeExternalCanister(_)
             | RequestSpecifier::FundExternalCanister(_)
+            | RequestSpecifier::ReleaseExternalCanisterControl(_)
             | RequestSpecifier::CreateExternalCanister
             | RequestSpecifier::AddRequestPolicy
             | RequestSpecifier::ManageSystemInfo
             | RequestSpecifier::SetDisasterRecovery
             | RequestSpecifier::AddUserGroup
             | RequestSpecifier::AddAsset
-            | RequestSpecifier::AddNamedRule => (),
+            | RequestSpecifier::AddNamedRule
+            | RequestSpecifier::FreezeStation
+            | RequestSpecifier::UnfreezeStation => (),
 
             RequestSpecifier::CallExternalCanister(target) => {
                 target.validate()?;
@@ -206,6 +218,9 @@ impl From<&RequestSpecifier> for RequestOperationType {
             }
             RequestSpecifier::CallExternalCanister(_) => RequestOperationType::CallExternalCanister,
             RequestSpecifier::FundExternalCanister(_) => RequestOperationType::FundExternalCanister,
+            RequestSpecifier::ReleaseExternalCanisterControl(_) => {
+                RequestOperationType::ConfigureExternalCanister
+            }
             RequestSpecifier::AddRequestPolicy => RequestOperationType::AddRequestPolicy,
             RequestSpecifier::EditRequestPolicy(_) => RequestOperationType::EditRequestPolicy,
             RequestSpecifier::RemoveRequestPolicy(_) => RequestOperationType::RemoveRequestPolicy,
@@ -222,6 +237,9 @@ impl From<&RequestSpecifier> for RequestOperationType {
             RequestSpecifier::AddNamedRule => RequestOperationType::AddNamedRule,
             RequestSpecifier::EditNamedRule(_) => RequestOperationType::EditNamedRule,
             RequestSpecifier::RemoveNamedRule(_) => RequestOperationType::RemoveNamedRule,
+
+            RequestSpecifier::FreezeStation => RequestOperationType::FreezeStation,
+            RequestSpecifier::UnfreezeStation => RequestOperationType::UnfreezeStation,
         }
     }
 }
@@ -66,7 +66,9 @@ impl ModelValidator<ValidationError> for Resource {
                 | ExternalCanisterResourceAction::Create
                 | ExternalCanisterResourceAction::Change(_)
                 | ExternalCanisterResourceAction::Fund(_)
-                | ExternalCanisterResourceAction::Read(_) => (),
+                | ExternalCanisterResourceAction::Read(_)
+                | ExternalCanisterResourceAction::ReadLogs(_)
+                | ExternalCanisterResourceAction::ReleaseControl(_) => (),
                 ExternalCanisterResourceAction::Call(target) => target.validate()?,
             },
             Resource::Notification(action) => match action {
@@ -192,6 +194,8 @@ pub enum ExternalCanisterResourceAction {
     Read(ExternalCanisterId),
     Fund(ExternalCanisterId),
     Call(CallExternalCanisterResourceTarget),
+    ReadLogs(ExternalCanisterId),
+    ReleaseControl(ExternalCanisterId),
 }
 
 #[storable]
@@ -495,6 +499,49 @@ impl Resource {
                         )),
                     ]
                 }
+                ExternalCanisterResourceAction::ReadLogs(ExternalCanisterId::Any) => {
+                    vec![
+                        Resource::ExternalCanister(ExternalCanisterResourceAction::ReadLogs(
+                            ExternalCanisterId::Any,
+                        )),
+                        // Users that can change the canister can also install code that exposes its logs.
+                        Resource::ExternalCanister(ExternalCanisterResourceAction::Change(
+                            ExternalCanisterId::Any,
+                        )),
+                    ]
+                }
+                ExternalCanisterResourceAction::ReadLogs(ExternalCanisterId::Canister(id)) => {
+                    let mut associated_resources = Resource::ExternalCanister(
+                        ExternalCanisterResourceAction::ReadLogs(ExternalCanisterId::Any),
+                    )
+                    .to_expanded_list();
+
+                    associated_resources.push(Resource::ExternalCanister(
+                        ExternalCanisterResourceAction::ReadLogs(ExternalCanisterId::Canister(*id)),
+                    ));
+                    associated_resources.push(Resource::ExternalCanister(
+                        ExternalCanisterResourceAction::Change(ExternalCanisterId::Canister(*id)),
+                    ));
+
+                    associated_resources
+                }
+                ExternalCanisterResourceAction::ReleaseControl(ExternalCanisterId::Any) => {
+                    vec![Resource::ExternalCanister(
+                        ExternalCanisterResourceAction::ReleaseControl(ExternalCanisterId::Any),
+                    )]
+                }
+                ExternalCanisterResourceAction::ReleaseControl(ExternalCanisterId::Canister(
+                    id,
+                )) => {
+                    vec![
+                        Resource::ExternalCanister(ExternalCanisterResourceAction::ReleaseControl(
+                            ExternalCanisterId::Any,
+                        )),
+                        Resource::ExternalCanister(ExternalCanisterResourceAction::ReleaseControl(
+                            ExternalCanisterId::Canister(*id),
+                        )),
+                    ]
+                }
             },
             Resource::Notification(action) => match action {
                 NotificationResourceAction::List => {
@@ -815,6 +862,12 @@ impl Display for ExternalCanisterResourceAction {
             ExternalCanisterResourceAction::Read(target) => {
                 write!(f, "Read({target})")
             }
+            ExternalCanisterResourceAction::ReadLogs(target) => {
+                write!(f, "ReadLogs({target})")
+            }
+            ExternalCanisterResourceAction::ReleaseControl(target) => {
+                write!(f, "ReleaseControl({target})")
+            }
         }
     }
 }
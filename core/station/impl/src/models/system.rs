@@ -42,6 +42,80 @@ pub enum CycleObtainStrategy {
     },
 }
 
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RequestArchive {
+    /// The archive canister id, created and controlled by the station.
+    pub canister_id: Principal,
+    /// Finalized requests older than this are moved to the archive, archival is paused if not set.
+    pub min_age_secs: Option<u64>,
+}
+
+/// The station-wide emergency freeze, while active no outgoing value is moved by the station.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StationFreeze {
+    /// The request that froze the station.
+    pub request_id: UUID,
+    pub reason: Option<String>,
+    pub frozen_at: Timestamp,
+}
+
+/// A newer station version announced by the control panel that deployed the station.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AvailableStationUpgrade {
+    /// The control panel that hosts the registry where the version was published.
+    pub registry_canister_id: Principal,
+    pub registry_entry_id: UUID,
+    /// The name of the registry entry, including its namespace (e.g. `@orbit/station`).
+    pub name: String,
+    pub version: String,
+    pub announced_at: Timestamp,
+}
+
+impl AvailableStationUpgrade {
+    /// Returns `true` if the announced version is newer than the given version.
+    ///
+    /// Versions that are not valid semver are never considered newer.
+    pub fn is_newer_than(&self, version: &str) -> bool {
+        match (
+            semver::Version::parse(&self.version),
+            semver::Version::parse(version),
+        ) {
+            (Ok(announced), Ok(current)) => announced > current,
+            _ => false,
+        }
+    }
+}
+
+/// Data changes that are applied once, on the first upgrade to the version that introduced them, without
+/// a new stable memory layout (e.g. adding the default policies of a new request operation).
+#[storable]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DataMigration {
+    /// Adds the request policies of the station freeze and unfreeze operations.
+    FreezeStationPolicies,
+    /// Adds the requests, address book entries and external canisters to the search index, it is applied
+    /// in batches by a background job since it can exceed the instruction limit of an upgrade.
+    SearchIndex,
+    /// Adds the requests to the index of the entries that their resources refer to, it is applied in
+    /// batches by a background job since it can exceed the instruction limit of an upgrade.
+    RequestResourceTargetIndex,
+    /// Adds the request policy and permission of releasing the control of external canisters, which
+    /// used to share the ones of changing external canisters.
+    ReleaseExternalCanisterControlPolicies,
+}
+
+impl DataMigration {
+    pub const ALL: [DataMigration; 4] = [
+        DataMigration::FreezeStationPolicies,
+        DataMigration::SearchIndex,
+        DataMigration::RequestResourceTargetIndex,
+        DataMigration::ReleaseExternalCanisterControlPolicies,
+    ];
+}
+
 #[storable(size = SYSTEM_RESERVED_MEMORY_BYTES)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SystemInfo {
@@ -71,6 +145,23 @@ pub struct SystemInfo {
     /// The maximum number of station backup snapshots to keep.
     #[serde(default = "default_max_backup_snapshots")]
     max_station_backup_snapshots: u64,
+    /// The request archive configuration, if the station has an archive canister.
+    #[serde(default)]
+    request_archive: Option<RequestArchive>,
+    /// The emergency freeze, if the station is frozen.
+    #[serde(default)]
+    freeze: Option<StationFreeze>,
+    /// The newest station version announced by the control panel, if any.
+    #[serde(default)]
+    available_upgrade: Option<AvailableStationUpgrade>,
+    /// The control panel trusted by the station, which is the only one that can announce new station
+    /// versions. It is set to the control panel that deployed the station, and stations that were
+    /// deployed before it was recorded can configure it with a system info request.
+    #[serde(default, alias = "deployed_by")]
+    control_panel_id: Option<Principal>,
+    /// The data migrations that were already applied, new stations start with all of them applied.
+    #[serde(default)]
+    applied_data_migrations: Vec<DataMigration>,
 }
 
 impl Default for SystemInfo {
@@ -87,6 +178,11 @@ impl Default for SystemInfo {
             stable_memory_version: Some(STABLE_MEMORY_VERSION),
             cycle_obtain_strategy: CycleObtainStrategy::default(),
             max_station_backup_snapshots: default_max_backup_snapshots(),
+            request_archive: None,
+            freeze: None,
+            available_upgrade: None,
+            control_panel_id: None,
+            applied_data_migrations: DataMigration::ALL.to_vec(),
         }
     }
 }
@@ -110,6 +206,16 @@ impl SystemInfo {
         self.stable_memory_version = Some(version);
     }
 
+    pub fn is_data_migration_applied(&self, migration: &DataMigration) -> bool {
+        self.applied_data_migrations.contains(migration)
+    }
+
+    pub fn set_data_migration_applied(&mut self, migration: DataMigration) {
+        if !self.is_data_migration_applied(&migration) {
+            self.applied_data_migrations.push(migration);
+        }
+    }
+
     pub fn get_cycle_obtain_strategy(&self) -> &CycleObtainStrategy {
         &self.cycle_obtain_strategy
     }
@@ -202,6 +308,45 @@ impl SystemInfo {
         self.disaster_recovery_committee.as_ref()
     }
 
+    pub fn get_request_archive(&self) -> Option<&RequestArchive> {
+        self.request_archive.as_ref()
+    }
+
+    pub fn set_request_archive(&mut self, request_archive: Option<RequestArchive>) {
+        self.request_archive = request_archive;
+    }
+
+    pub fn get_freeze(&self) -> Option<&StationFreeze> {
+        self.freeze.as_ref()
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.freeze.is_some()
+    }
+
+    pub fn set_freeze(&mut self, freeze: Option<StationFreeze>) {
+        self.freeze = freeze;
+    }
+
+    /// Returns the announced station upgrade, unless the station already runs that version.
+    pub fn get_available_upgrade(&self) -> Option<&AvailableStationUpgrade> {
+        self.available_upgrade
+            .as_ref()
+            .filter(|upgrade| upgrade.is_newer_than(SYSTEM_VERSION))
+    }
+
+    pub fn set_available_upgrade(&mut self, available_upgrade: Option<AvailableStationUpgrade>) {
+        self.available_upgrade = available_upgrade;
+    }
+
+    pub fn get_control_panel_id(&self) -> Option<&Principal> {
+        self.control_panel_id.as_ref()
+    }
+
+    pub fn set_control_panel_id(&mut self, control_panel_id: Principal) {
+        self.control_panel_id = Some(control_panel_id);
+    }
+
     pub fn get_max_station_backup_snapshots(&self) -> u64 {
         self.max_station_backup_snapshots
     }
@@ -300,4 +445,26 @@ mod tests {
         info.set_name("  test".to_string());
         assert_eq!(info.name, "test");
     }
+
+    #[test]
+    fn test_available_upgrade_must_be_newer() {
+        let mut info = SystemInfo::default();
+        let mut upgrade = AvailableStationUpgrade {
+            registry_canister_id: Principal::anonymous(),
+            registry_entry_id: [1; 16],
+            name: "@orbit/station".to_string(),
+            version: SYSTEM_VERSION.to_string(),
+            announced_at: 0,
+        };
+
+        info.set_available_upgrade(Some(upgrade.clone()));
+        assert!(info.get_available_upgrade().is_none());
+
+        upgrade.version = "999.0.0".to_string();
+        info.set_available_upgrade(Some(upgrade.clone()));
+        assert_eq!(info.get_available_upgrade(), Some(&upgrade));
+
+        upgrade.version = "latest".to_string();
+        assert!(!upgrade.is_newer_than(SYSTEM_VERSION));
+    }
 }
@@ -0,0 +1,95 @@
+use super::WasmModuleExtraChunks;
+use candid::Principal;
+use orbit_essentials::{storable, types::UUID};
+
+/// A wasm module published in the registry of a control panel, used instead of uploading the
+/// module with the request.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WasmModuleRegistryReference {
+    /// The control panel that hosts the registry.
+    pub registry_canister_id: Principal,
+    /// The name of the registry entry, including its namespace (e.g. `@orbit/station`).
+    pub name: String,
+    pub version: WasmModuleRegistryVersion,
+}
+
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum WasmModuleRegistryVersion {
+    /// The exact version of the wasm module.
+    Version(String),
+    /// A tag of the entry, e.g. `latest`, the newest entry with the tag is used.
+    Tag(String),
+}
+
+/// The registry entry that a reference resolved to when the request was created.
+///
+/// The module is fetched again when the request is executed and must match the recorded hash,
+/// so that approvers approve the exact module that gets installed.
+#[storable]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ResolvedWasmModuleRegistryEntry {
+    pub entry_id: UUID,
+    pub version: String,
+    pub wasm_artifact_id: UUID,
+    /// The sha256 hash of the artifact, which only holds the first chunk if the module has extra chunks.
+    #[serde(with = "serde_bytes")]
+    pub wasm_artifact_hash: Vec<u8>,
+    pub module_extra_chunks: Option<WasmModuleExtraChunks>,
+    /// The publisher of the entry if its provenance was verified by the registry.
+    #[serde(default)]
+    pub verified_publisher: Option<Principal>,
+}
+
+impl WasmModuleRegistryReference {
+    /// Returns `true` if a registry entry with the given version and tags is referenced.
+    pub fn matches(&self, version: &str, tags: &[String]) -> bool {
+        match &self.version {
+            WasmModuleRegistryVersion::Version(expected) => expected == version,
+            WasmModuleRegistryVersion::Tag(tag) => tags.contains(tag),
+        }
+    }
+}
+
+impl ResolvedWasmModuleRegistryEntry {
+    /// Returns the checksum of the whole module, including its extra chunks.
+    pub fn module_checksum(&self) -> Vec<u8> {
+        match &self.module_extra_chunks {
+            Some(module_extra_chunks) => module_extra_chunks.wasm_module_hash.clone(),
+            None => self.wasm_artifact_hash.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for WasmModuleRegistryReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.version {
+            WasmModuleRegistryVersion::Version(version) => write!(f, "{}@{}", self.name, version),
+            WasmModuleRegistryVersion::Tag(tag) => write!(f, "{}#{}", self.name, tag),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_version_or_tag() {
+        let mut reference = WasmModuleRegistryReference {
+            registry_canister_id: Principal::anonymous(),
+            name: "@orbit/station".to_string(),
+            version: WasmModuleRegistryVersion::Version("1.0.0".to_string()),
+        };
+
+        assert!(reference.matches("1.0.0", &[]));
+        assert!(!reference.matches("1.0.1", &["1.0.0".to_string()]));
+
+        reference.version = WasmModuleRegistryVersion::Tag("latest".to_string());
+
+        assert!(reference.matches("1.0.1", &["latest".to_string()]));
+        assert!(!reference.matches("latest", &[]));
+        assert_eq!(reference.to_string(), "@orbit/station#latest");
+    }
+}
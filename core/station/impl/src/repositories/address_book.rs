@@ -1,18 +1,21 @@
-use super::indexes::unique_index::UniqueIndexRepository;
+use super::indexes::{search_index::SearchIndexRepository, unique_index::UniqueIndexRepository};
 use crate::{
     core::{
         metrics::ADDRESS_BOOK_METRICS, utils::max_string_of_size, with_memory_manager, Memory,
         ADDRESS_BOOK_MEMORY_ID,
     },
     models::{
-        indexes::unique_index::UniqueIndexKey, AddressBookEntry, AddressBookEntryId,
-        AddressBookEntryKey, AddressFormat, Blockchain,
+        indexes::{
+            search_index::{SearchIndexCriteria, SearchIndexKind},
+            unique_index::UniqueIndexKey,
+        },
+        AddressBookEntry, AddressBookEntryId, AddressBookEntryKey, AddressFormat, Blockchain,
     },
 };
 use ic_stable_structures::{memory_manager::VirtualMemory, StableBTreeMap};
 use lazy_static::lazy_static;
 use orbit_essentials::{
-    repository::{IndexedRepository, Repository, StableDb},
+    repository::{IndexRepository, IndexedRepository, Repository, StableDb},
     types::UUID,
 };
 use std::{cell::RefCell, collections::HashSet, sync::Arc};
@@ -35,6 +38,7 @@ lazy_static! {
 #[derive(Default, Debug)]
 pub struct AddressBookRepository {
     unique_index: UniqueIndexRepository,
+    search_index: SearchIndexRepository,
 }
 
 impl StableDb<AddressBookEntryKey, AddressBookEntry, VirtualMemory<Memory>>
@@ -57,6 +61,10 @@ impl IndexedRepository<AddressBookEntryKey, AddressBookEntry, VirtualMemory<Memo
         value.to_unique_indexes().iter().for_each(|(index, _)| {
             self.unique_index.remove(index);
         });
+
+        value.to_search_index().iter().for_each(|index| {
+            self.search_index.remove(index);
+        });
     }
 
     fn add_entry_indexes(&self, value: &AddressBookEntry) {
@@ -66,12 +74,18 @@ impl IndexedRepository<AddressBookEntryKey, AddressBookEntry, VirtualMemory<Memo
             .for_each(|(index, entry_id)| {
                 self.unique_index.insert(index, entry_id);
             });
+
+        value.to_search_index().into_iter().for_each(|index| {
+            self.search_index.insert(index);
+        });
     }
 
     /// Clears all the indexes for the repository.
     fn clear_indexes(&self) {
         self.unique_index
             .clear_when(|key| matches!(key, UniqueIndexKey::AddressBookBlockchainAddress(_, _)));
+
+        self.search_index.clear(SearchIndexKind::AddressBookEntry);
     }
 }
 
@@ -122,6 +136,43 @@ impl Repository<AddressBookEntryKey, AddressBookEntry, VirtualMemory<Memory>>
 }
 
 impl AddressBookRepository {
+    /// Adds up to `limit` address book entries to the search index, starting from the given id, and returns the
+    /// id of the next entry to index if there are more left.
+    ///
+    /// Used to populate the search index with the address book entries that were added before the index existed.
+    pub fn build_search_index(
+        &self,
+        from: Option<AddressBookEntryId>,
+        limit: usize,
+    ) -> Option<AddressBookEntryId> {
+        DB.with(|m| {
+            let db = m.borrow();
+            let mut entries = match from {
+                Some(id) => db.range(AddressBookEntryKey { id }..),
+                None => db.range(..),
+            };
+
+            entries.by_ref().take(limit).for_each(|(_, entry)| {
+                entry.to_search_index().into_iter().for_each(|index| {
+                    self.search_index.insert(index);
+                });
+            });
+
+            entries.next().map(|(key, _)| key.id)
+        })
+    }
+
+    /// Finds the ids of the entries whose address owner or labels match the search term.
+    ///
+    /// Returns `None` if the search term has no searchable words.
+    pub fn find_ids_by_search_term(
+        &self,
+        search_term: &str,
+    ) -> Option<HashSet<AddressBookEntryId>> {
+        SearchIndexCriteria::new(SearchIndexKind::AddressBookEntry, Some(search_term))
+            .map(|criteria| self.search_index.find_by_criteria(criteria))
+    }
+
     /// Get the address book entry by it's unique key.
     pub fn find_by_address(
         &self,
@@ -286,6 +337,37 @@ mod tests {
         assert!(result.contains(&address_book_entry_1));
     }
 
+    #[test]
+    fn test_find_ids_by_search_term() {
+        let repository = AddressBookRepository::default();
+        let mut address_book_entry_0 = address_book_entry_test_utils::mock_address_book_entry();
+        let mut address_book_entry_1 = address_book_entry_test_utils::mock_address_book_entry();
+        address_book_entry_0.id = [1; 16];
+        address_book_entry_0.address_owner = "Renée".to_string();
+        address_book_entry_0.labels = vec!["exchange".to_string()];
+        address_book_entry_1.id = [2; 16];
+        address_book_entry_1.address = "0x5678".to_string();
+        address_book_entry_1.address_owner = "René Payroll".to_string();
+        address_book_entry_1.labels = vec![];
+
+        repository.insert(address_book_entry_0.to_key(), address_book_entry_0.clone());
+        repository.insert(address_book_entry_1.to_key(), address_book_entry_1.clone());
+
+        assert_eq!(
+            repository.find_ids_by_search_term("rene"),
+            Some(HashSet::from([[1; 16], [2; 16]]))
+        );
+        assert_eq!(
+            repository.find_ids_by_search_term("exch"),
+            Some(HashSet::from([[1; 16]]))
+        );
+        assert_eq!(
+            repository.find_ids_by_search_term("payroll rene"),
+            Some(HashSet::from([[2; 16]]))
+        );
+        assert_eq!(repository.find_ids_by_search_term(" - "), None);
+    }
+
     #[test]
     fn test_find_by_address_formats() {
         let repository = AddressBookRepository::default();
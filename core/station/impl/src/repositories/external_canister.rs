@@ -1,15 +1,18 @@
-use super::indexes::unique_index::UniqueIndexRepository;
+use super::indexes::{search_index::SearchIndexRepository, unique_index::UniqueIndexRepository};
 use crate::{
     core::{utils::format_unique_string, with_memory_manager, Memory, EXTERNAL_CANISTER_MEMORY_ID},
     models::{
-        indexes::unique_index::UniqueIndexKey, ExternalCanister, ExternalCanisterEntryId,
-        ExternalCanisterKey, ExternalCanisterState,
+        indexes::{
+            search_index::{SearchIndexCriteria, SearchIndexKind},
+            unique_index::UniqueIndexKey,
+        },
+        ExternalCanister, ExternalCanisterEntryId, ExternalCanisterKey, ExternalCanisterState,
     },
 };
 use candid::Principal;
 use ic_stable_structures::{memory_manager::VirtualMemory, StableBTreeMap};
 use lazy_static::lazy_static;
-use orbit_essentials::repository::{IndexedRepository, SortDirection};
+use orbit_essentials::repository::{IndexRepository, IndexedRepository, SortDirection};
 use orbit_essentials::repository::{Repository, StableDb};
 use std::{cell::RefCell, collections::HashSet, sync::Arc};
 
@@ -31,6 +34,7 @@ lazy_static! {
 #[derive(Debug, Default)]
 pub struct ExternalCanisterRepository {
     unique_index: UniqueIndexRepository,
+    search_index: SearchIndexRepository,
 }
 
 impl StableDb<ExternalCanisterKey, ExternalCanister, VirtualMemory<Memory>>
@@ -56,6 +60,10 @@ impl IndexedRepository<ExternalCanisterKey, ExternalCanister, VirtualMemory<Memo
             .for_each(|(index, _)| {
                 self.unique_index.remove(&index);
             });
+
+        value.to_search_index().iter().for_each(|index| {
+            self.search_index.remove(index);
+        });
     }
 
     fn add_entry_indexes(&self, value: &ExternalCanister) {
@@ -65,6 +73,10 @@ impl IndexedRepository<ExternalCanisterKey, ExternalCanister, VirtualMemory<Memo
             .for_each(|(index, id)| {
                 self.unique_index.insert(index, id);
             });
+
+        value.to_search_index().into_iter().for_each(|index| {
+            self.search_index.insert(index);
+        });
     }
 
     /// Clears all the indexes of the repository.
@@ -73,6 +85,8 @@ impl IndexedRepository<ExternalCanisterKey, ExternalCanister, VirtualMemory<Memo
             matches!(key, UniqueIndexKey::ExternalCanisterId(_))
                 || matches!(key, UniqueIndexKey::ExternalCanisterName(_))
         });
+
+        self.search_index.clear(SearchIndexKind::ExternalCanister);
     }
 }
 
@@ -107,6 +121,32 @@ impl Repository<ExternalCanisterKey, ExternalCanister, VirtualMemory<Memory>>
 }
 
 impl ExternalCanisterRepository {
+    /// Adds up to `limit` external canisters to the search index, starting from the given id, and returns the
+    /// id of the next canister to index if there are more left.
+    ///
+    /// Used to populate the search index with the external canisters that were added before the index existed.
+    pub fn build_search_index(
+        &self,
+        from: Option<ExternalCanisterEntryId>,
+        limit: usize,
+    ) -> Option<ExternalCanisterEntryId> {
+        DB.with(|m| {
+            let db = m.borrow();
+            let mut entries = match from {
+                Some(id) => db.range(ExternalCanisterKey { id }..),
+                None => db.range(..),
+            };
+
+            entries.by_ref().take(limit).for_each(|(_, entry)| {
+                entry.to_search_index().into_iter().for_each(|index| {
+                    self.search_index.insert(index);
+                });
+            });
+
+            entries.next().map(|(key, _)| key.id)
+        })
+    }
+
     /// Returns an external canister by its name if it exists.
     pub fn find_by_name(&self, name: &str) -> Option<ExternalCanisterEntryId> {
         let name = format_unique_string(name);
@@ -182,11 +222,22 @@ impl ExternalCanisterRepository {
             where_clause.canister_ids.into_iter().collect();
         let filter_by_states: HashSet<ExternalCanisterState> =
             where_clause.states.into_iter().collect();
+        let filter_by_search = SearchIndexCriteria::new(
+            SearchIndexKind::ExternalCanister,
+            where_clause.search_term.as_deref(),
+        )
+        .map(|criteria| self.search_index.find_by_criteria(criteria));
 
         let mut found_ids = self
             .list()
             .into_iter()
             .filter_map(|entry| {
+                if let Some(found_ids) = &filter_by_search {
+                    if !found_ids.contains(&entry.id) {
+                        return None;
+                    }
+                }
+
                 if !filter_by_labels.is_empty()
                     && !entry
                         .labels
@@ -247,6 +298,7 @@ pub struct ExternalCanisterWhereClause {
     pub labels: Vec<String>,
     pub states: Vec<ExternalCanisterState>,
     pub sort_by: Option<ExternalCanisterWhereClauseSort>,
+    pub search_term: Option<String>,
 }
 
 #[cfg(test)]
@@ -328,4 +380,37 @@ mod tests {
         assert!(!repository.is_unique_canister_id(&entry.canister_id, None));
         assert!(repository.is_unique_canister_id(&entry.canister_id, Some(entry.id)));
     }
+
+    #[test]
+    fn test_find_canister_ids_by_search_term() {
+        let repository = ExternalCanisterRepository::default();
+        let mut ledger = mock_external_canister();
+        ledger.name = "Token ledger".to_string();
+        ledger.description = Some("Keeps the balances of the DAO token".to_string());
+        let mut frontend = mock_external_canister();
+        frontend.name = "DAO frontend".to_string();
+        frontend.description = None;
+
+        repository.insert(ledger.key(), ledger.clone());
+        repository.insert(frontend.key(), frontend.clone());
+
+        let find = |search_term: &str| {
+            repository.find_canister_ids_where(ExternalCanisterWhereClause {
+                canister_ids: vec![],
+                labels: vec![],
+                states: vec![],
+                sort_by: None,
+                search_term: Some(search_term.to_string()),
+            })
+        };
+
+        assert_eq!(find("dao").len(), 2);
+        assert_eq!(find("balance"), vec![ledger.canister_id]);
+        assert_eq!(find("front dao"), vec![frontend.canister_id]);
+        assert!(find("ledger frontend").is_empty());
+
+        repository.remove(&frontend.key());
+
+        assert_eq!(find("dao"), vec![ledger.canister_id]);
+    }
 }
@@ -0,0 +1,47 @@
+use crate::{
+    core::{with_memory_manager, Memory, EXTERNAL_CANISTER_CONTROLLERS_MEMORY_ID},
+    models::{ExternalCanisterControllers, ExternalCanisterEntryId},
+};
+use ic_stable_structures::{memory_manager::VirtualMemory, StableBTreeMap};
+use lazy_static::lazy_static;
+use orbit_essentials::repository::{Repository, StableDb};
+use std::{cell::RefCell, sync::Arc};
+
+thread_local! {
+  static DB: RefCell<StableBTreeMap<ExternalCanisterEntryId, ExternalCanisterControllers, VirtualMemory<Memory>>> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      StableBTreeMap::init(memory_manager.get(EXTERNAL_CANISTER_CONTROLLERS_MEMORY_ID))
+    )
+  })
+}
+
+lazy_static! {
+    pub static ref EXTERNAL_CANISTER_CONTROLLERS_REPOSITORY: Arc<ExternalCanisterControllersRepository> =
+        Arc::new(ExternalCanisterControllersRepository::default());
+}
+
+/// A repository that stores the known controllers of the external canisters in stable memory.
+#[derive(Default, Debug)]
+pub struct ExternalCanisterControllersRepository {}
+
+impl StableDb<ExternalCanisterEntryId, ExternalCanisterControllers, VirtualMemory<Memory>>
+    for ExternalCanisterControllersRepository
+{
+    fn with_db<F, R>(f: F) -> R
+    where
+        F: FnOnce(
+            &mut StableBTreeMap<
+                ExternalCanisterEntryId,
+                ExternalCanisterControllers,
+                VirtualMemory<Memory>,
+            >,
+        ) -> R,
+    {
+        DB.with(|m| f(&mut m.borrow_mut()))
+    }
+}
+
+impl Repository<ExternalCanisterEntryId, ExternalCanisterControllers, VirtualMemory<Memory>>
+    for ExternalCanisterControllersRepository
+{
+}
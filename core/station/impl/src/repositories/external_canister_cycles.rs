@@ -0,0 +1,185 @@
+use crate::{
+    core::{with_memory_manager, Memory, EXTERNAL_CANISTER_CYCLES_MEMORY_ID},
+    models::{
+        ExternalCanisterCyclesSample, ExternalCanisterCyclesSampleKey, ExternalCanisterEntryId,
+    },
+};
+use ic_stable_structures::{memory_manager::VirtualMemory, StableBTreeMap};
+use lazy_static::lazy_static;
+use orbit_essentials::{
+    repository::{Repository, StableDb},
+    types::Timestamp,
+};
+use std::{cell::RefCell, sync::Arc};
+
+thread_local! {
+  static DB: RefCell<StableBTreeMap<ExternalCanisterCyclesSampleKey, ExternalCanisterCyclesSample, VirtualMemory<Memory>>> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      StableBTreeMap::init(memory_manager.get(EXTERNAL_CANISTER_CYCLES_MEMORY_ID))
+    )
+  })
+}
+
+lazy_static! {
+    pub static ref EXTERNAL_CANISTER_CYCLES_REPOSITORY: Arc<ExternalCanisterCyclesRepository> =
+        Arc::new(ExternalCanisterCyclesRepository::default());
+}
+
+/// A repository that stores the cycles balance samples of the monitored external canisters.
+///
+/// The number of samples kept per canister is bounded, the oldest samples are removed first.
+#[derive(Default, Debug)]
+pub struct ExternalCanisterCyclesRepository {}
+
+impl StableDb<ExternalCanisterCyclesSampleKey, ExternalCanisterCyclesSample, VirtualMemory<Memory>>
+    for ExternalCanisterCyclesRepository
+{
+    fn with_db<F, R>(f: F) -> R
+    where
+        F: FnOnce(
+            &mut StableBTreeMap<
+                ExternalCanisterCyclesSampleKey,
+                ExternalCanisterCyclesSample,
+                VirtualMemory<Memory>,
+            >,
+        ) -> R,
+    {
+        DB.with(|m| f(&mut m.borrow_mut()))
+    }
+}
+
+impl
+    Repository<ExternalCanisterCyclesSampleKey, ExternalCanisterCyclesSample, VirtualMemory<Memory>>
+    for ExternalCanisterCyclesRepository
+{
+}
+
+impl ExternalCanisterCyclesRepository {
+    /// The maximum number of samples kept per canister, about 180 days at the monitoring interval.
+    pub const MAX_SAMPLES_PER_CANISTER: usize = 720;
+
+    /// Returns the samples of the canister taken within the given time range, sorted by time.
+    pub fn find_by_external_canister_id(
+        &self,
+        external_canister_id: &ExternalCanisterEntryId,
+        from_dt: Option<Timestamp>,
+        to_dt: Option<Timestamp>,
+    ) -> Vec<(Timestamp, ExternalCanisterCyclesSample)> {
+        let start_key = ExternalCanisterCyclesSampleKey {
+            external_canister_id: *external_canister_id,
+            sampled_at: from_dt.unwrap_or(Timestamp::MIN),
+        };
+        let end_key = ExternalCanisterCyclesSampleKey {
+            external_canister_id: *external_canister_id,
+            sampled_at: to_dt.unwrap_or(Timestamp::MAX),
+        };
+
+        if start_key > end_key {
+            return Vec::new();
+        }
+
+        DB.with(|db| {
+            db.borrow()
+                .range(start_key..=end_key)
+                .map(|(key, sample)| (key.sampled_at, sample))
+                .collect()
+        })
+    }
+
+    /// Returns the latest sample of the canister, if any.
+    pub fn find_latest(
+        &self,
+        external_canister_id: &ExternalCanisterEntryId,
+    ) -> Option<(Timestamp, ExternalCanisterCyclesSample)> {
+        self.find_by_external_canister_id(external_canister_id, None, None)
+            .pop()
+    }
+
+    /// Adds a sample and removes the oldest samples of the canister beyond the retention limit.
+    pub fn insert_sample(
+        &self,
+        key: ExternalCanisterCyclesSampleKey,
+        sample: ExternalCanisterCyclesSample,
+    ) {
+        let external_canister_id = key.external_canister_id;
+        self.insert(key, sample);
+
+        let samples = self.find_by_external_canister_id(&external_canister_id, None, None);
+        let excess = samples.len().saturating_sub(Self::MAX_SAMPLES_PER_CANISTER);
+
+        for (sampled_at, _) in samples.into_iter().take(excess) {
+            self.remove(&ExternalCanisterCyclesSampleKey {
+                external_canister_id,
+                sampled_at,
+            });
+        }
+    }
+
+    /// Removes all the samples of the canister.
+    pub fn remove_by_external_canister_id(&self, external_canister_id: &ExternalCanisterEntryId) {
+        for (sampled_at, _) in self.find_by_external_canister_id(external_canister_id, None, None) {
+            self.remove(&ExternalCanisterCyclesSampleKey {
+                external_canister_id: *external_canister_id,
+                sampled_at,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(cycles: u64) -> ExternalCanisterCyclesSample {
+        ExternalCanisterCyclesSample {
+            cycles,
+            deposited_cycles: 0,
+        }
+    }
+
+    #[test]
+    fn keeps_bounded_samples_per_canister() {
+        let repository = ExternalCanisterCyclesRepository::default();
+        let limit = ExternalCanisterCyclesRepository::MAX_SAMPLES_PER_CANISTER as u64;
+
+        for sampled_at in 0..limit + 5 {
+            repository.insert_sample(
+                ExternalCanisterCyclesSampleKey {
+                    external_canister_id: [1; 16],
+                    sampled_at,
+                },
+                sample(sampled_at),
+            );
+        }
+        repository.insert_sample(
+            ExternalCanisterCyclesSampleKey {
+                external_canister_id: [2; 16],
+                sampled_at: 0,
+            },
+            sample(0),
+        );
+
+        let samples = repository.find_by_external_canister_id(&[1; 16], None, None);
+        assert_eq!(samples.len() as u64, limit);
+        assert_eq!(samples[0].0, 5);
+        assert_eq!(repository.find_latest(&[1; 16]).unwrap().0, limit + 4);
+        assert_eq!(
+            repository
+                .find_by_external_canister_id(&[1; 16], Some(10), Some(19))
+                .len(),
+            10
+        );
+
+        repository.remove_by_external_canister_id(&[1; 16]);
+
+        assert!(repository
+            .find_by_external_canister_id(&[1; 16], None, None)
+            .is_empty());
+        assert_eq!(
+            repository
+                .find_by_external_canister_id(&[2; 16], None, None)
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{
+    core::{cache::Cache, with_memory_manager, Memory, EXTERNAL_CANISTER_INTERFACE_MEMORY_ID},
+    models::{CandidService, ExternalCanisterEntryId, ExternalCanisterInterface},
+};
+use ic_stable_structures::{memory_manager::VirtualMemory, StableBTreeMap};
+use lazy_static::lazy_static;
+use orbit_essentials::repository::{Repository, StableDb};
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+thread_local! {
+  static DB: RefCell<StableBTreeMap<ExternalCanisterEntryId, ExternalCanisterInterface, VirtualMemory<Memory>>> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      StableBTreeMap::init(memory_manager.get(EXTERNAL_CANISTER_INTERFACE_MEMORY_ID))
+    )
+  });
+
+  static SERVICE_CACHE: RefCell<Cache<ExternalCanisterEntryId, Rc<CandidService>>> = RefCell::new(Cache::new(ExternalCanisterInterfaceRepository::MAX_SERVICE_CACHE_SIZE));
+}
+
+lazy_static! {
+    pub static ref EXTERNAL_CANISTER_INTERFACE_REPOSITORY: Arc<ExternalCanisterInterfaceRepository> =
+        Arc::new(ExternalCanisterInterfaceRepository::default());
+}
+
+/// A repository that stores the Candid interfaces registered for external canisters in stable memory.
+///
+/// The interfaces are stored apart from the external canisters since they can be large.
+#[derive(Default, Debug)]
+pub struct ExternalCanisterInterfaceRepository {}
+
+impl StableDb<ExternalCanisterEntryId, ExternalCanisterInterface, VirtualMemory<Memory>>
+    for ExternalCanisterInterfaceRepository
+{
+    fn with_db<F, R>(f: F) -> R
+    where
+        F: FnOnce(
+            &mut StableBTreeMap<
+                ExternalCanisterEntryId,
+                ExternalCanisterInterface,
+                VirtualMemory<Memory>,
+            >,
+        ) -> R,
+    {
+        DB.with(|m| f(&mut m.borrow_mut()))
+    }
+}
+
+impl Repository<ExternalCanisterEntryId, ExternalCanisterInterface, VirtualMemory<Memory>>
+    for ExternalCanisterInterfaceRepository
+{
+    fn insert(
+        &self,
+        key: ExternalCanisterEntryId,
+        value: ExternalCanisterInterface,
+    ) -> Option<ExternalCanisterInterface> {
+        SERVICE_CACHE.with(|cache| cache.borrow_mut().remove(&key));
+
+        Self::with_db(|db| db.insert(key, value))
+    }
+
+    fn remove(&self, key: &ExternalCanisterEntryId) -> Option<ExternalCanisterInterface> {
+        SERVICE_CACHE.with(|cache| cache.borrow_mut().remove(key));
+
+        Self::with_db(|db| db.remove(key))
+    }
+
+    fn clear(&self) {
+        SERVICE_CACHE.with(|cache| cache.borrow_mut().clear());
+
+        Self::with_db(|db| db.clear_new());
+    }
+}
+
+impl ExternalCanisterInterfaceRepository {
+    /// The parsed services are kept in the heap, an interface can take up to 256KiB once parsed,
+    /// so the cache is limited to the interfaces of the most used canisters.
+    pub const MAX_SERVICE_CACHE_SIZE: usize = 64;
+
+    /// Returns the parsed service of the interface registered for the external canister, the
+    /// interface is only parsed the first time it is used after it was registered.
+    pub fn get_service(&self, key: &ExternalCanisterEntryId) -> Option<Rc<CandidService>> {
+        if let Some(service) = SERVICE_CACHE.with(|cache| cache.borrow().get(key).cloned()) {
+            return Some(service);
+        }
+
+        // the interface was validated when it was registered
+        let service = Rc::new(CandidService::parse(&self.get(key)?.candid).ok()?);
+        SERVICE_CACHE.with(|cache| cache.borrow_mut().insert(*key, service.clone()));
+
+        Some(service)
+    }
+}
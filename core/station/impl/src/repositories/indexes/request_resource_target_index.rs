@@ -0,0 +1,116 @@
+use crate::{
+    core::{with_memory_manager, Memory, REQUEST_RESOURCE_TARGET_INDEX_MEMORY_ID},
+    models::indexes::request_resource_index::{
+        RequestResourceTargetIndex, RequestResourceTargetIndexCriteria,
+    },
+};
+use ic_stable_structures::{memory_manager::VirtualMemory, StableBTreeMap};
+use orbit_essentials::{repository::IndexRepository, types::UUID};
+use std::{cell::RefCell, collections::HashSet};
+
+thread_local! {
+  static DB: RefCell<StableBTreeMap<RequestResourceTargetIndex, (), VirtualMemory<Memory>>> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      StableBTreeMap::init(memory_manager.get(REQUEST_RESOURCE_TARGET_INDEX_MEMORY_ID))
+    )
+  })
+}
+
+/// A repository that enables finding requests based on the entries their resources refer to in stable memory.
+#[derive(Default, Debug)]
+pub struct RequestResourceTargetIndexRepository {}
+
+impl RequestResourceTargetIndexRepository {
+    /// Clears the repository by removing all the entries.
+    pub fn clear(&self) {
+        DB.with(|m| m.borrow_mut().clear_new());
+    }
+}
+
+impl IndexRepository<RequestResourceTargetIndex, UUID> for RequestResourceTargetIndexRepository {
+    type FindByCriteria = RequestResourceTargetIndexCriteria;
+
+    fn exists(&self, index: &RequestResourceTargetIndex) -> bool {
+        DB.with(|m| m.borrow().get(index).is_some())
+    }
+
+    fn insert(&self, index: RequestResourceTargetIndex) {
+        DB.with(|m| m.borrow_mut().insert(index, ()));
+    }
+
+    fn remove(&self, index: &RequestResourceTargetIndex) -> bool {
+        DB.with(|m| m.borrow_mut().remove(index).is_some())
+    }
+
+    fn find_by_criteria(&self, criteria: Self::FindByCriteria) -> HashSet<UUID> {
+        DB.with(|db| {
+            let start_key = RequestResourceTargetIndex {
+                target: criteria.target.clone(),
+                request_id: [u8::MIN; 16],
+            };
+            let end_key = RequestResourceTargetIndex {
+                target: criteria.target,
+                request_id: [u8::MAX; 16],
+            };
+
+            db.borrow()
+                .range(start_key..=end_key)
+                .map(|(index, _)| index.request_id)
+                .collect::<HashSet<UUID>>()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::indexes::request_resource_index::RequestResourceFilter;
+
+    #[test]
+    fn test_repository_crud() {
+        let repository = RequestResourceTargetIndexRepository::default();
+        let index = RequestResourceTargetIndex {
+            target: RequestResourceFilter::User([1; 16]),
+            request_id: [0; 16],
+        };
+
+        assert!(!repository.exists(&index));
+
+        repository.insert(index.clone());
+
+        assert!(repository.exists(&index));
+        assert!(repository.remove(&index));
+        assert!(!repository.exists(&index));
+    }
+
+    #[test]
+    fn test_find_by_criteria() {
+        let repository = RequestResourceTargetIndexRepository::default();
+        for (request_id, target) in [
+            ([0; 16], RequestResourceFilter::User([1; 16])),
+            ([1; 16], RequestResourceFilter::User([1; 16])),
+            ([2; 16], RequestResourceFilter::User([2; 16])),
+            ([3; 16], RequestResourceFilter::Account([1; 16])),
+        ] {
+            repository.insert(RequestResourceTargetIndex { target, request_id });
+        }
+
+        assert_eq!(
+            repository.find_by_criteria(RequestResourceTargetIndexCriteria {
+                target: RequestResourceFilter::User([1; 16]),
+            }),
+            HashSet::from([[0; 16], [1; 16]])
+        );
+        assert_eq!(
+            repository.find_by_criteria(RequestResourceTargetIndexCriteria {
+                target: RequestResourceFilter::Account([1; 16]),
+            }),
+            HashSet::from([[3; 16]])
+        );
+        assert!(repository
+            .find_by_criteria(RequestResourceTargetIndexCriteria {
+                target: RequestResourceFilter::Asset([1; 16]),
+            })
+            .is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    core::{utils::to_search_terms, with_memory_manager, Memory, SEARCH_INDEX_MEMORY_ID},
+    models::indexes::search_index::{SearchIndexCriteria, SearchIndexKey, SearchIndexKind},
+};
+use ic_stable_structures::{memory_manager::VirtualMemory, StableBTreeMap};
+use orbit_essentials::{repository::IndexRepository, types::UUID};
+use std::{cell::RefCell, collections::HashSet};
+
+thread_local! {
+  static DB: RefCell<StableBTreeMap<SearchIndexKey, (), VirtualMemory<Memory>>> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      StableBTreeMap::init(memory_manager.get(SEARCH_INDEX_MEMORY_ID))
+    )
+  })
+}
+
+/// A repository that enables full-text search of entries by the terms of their searchable text.
+#[derive(Default, Debug)]
+pub struct SearchIndexRepository {}
+
+impl SearchIndexRepository {
+    /// Removes all the entries of the given kind.
+    pub fn clear(&self, kind: SearchIndexKind) {
+        DB.with(|db| {
+            let keys = db
+                .borrow()
+                .range(Self::term_start_key(kind, "")..)
+                .take_while(|(index, _)| index.kind == kind)
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>();
+
+            let mut db = db.borrow_mut();
+            for key in keys {
+                db.remove(&key);
+            }
+        });
+    }
+
+    fn term_start_key(kind: SearchIndexKind, term: &str) -> SearchIndexKey {
+        SearchIndexKey {
+            kind,
+            term: term.to_string(),
+            entry_id: [u8::MIN; 16],
+        }
+    }
+
+    /// Finds the entries that have a term starting with the given prefix.
+    fn find_by_term_prefix(&self, kind: SearchIndexKind, prefix: &str) -> HashSet<UUID> {
+        DB.with(|db| {
+            db.borrow()
+                .range(Self::term_start_key(kind, prefix)..)
+                .take_while(|(index, _)| index.kind == kind && index.term.starts_with(prefix))
+                .map(|(index, _)| index.entry_id)
+                .collect()
+        })
+    }
+}
+
+impl IndexRepository<SearchIndexKey, UUID> for SearchIndexRepository {
+    type FindByCriteria = SearchIndexCriteria;
+
+    fn exists(&self, index: &SearchIndexKey) -> bool {
+        DB.with(|m| m.borrow().get(index).is_some())
+    }
+
+    fn insert(&self, index: SearchIndexKey) {
+        DB.with(|m| m.borrow_mut().insert(index, ()));
+    }
+
+    fn remove(&self, index: &SearchIndexKey) -> bool {
+        DB.with(|m| m.borrow_mut().remove(index).is_some())
+    }
+
+    /// Entries are matched if each term of the search text is the prefix of one of their terms.
+    fn find_by_criteria(&self, criteria: Self::FindByCriteria) -> HashSet<UUID> {
+        let mut found: Option<HashSet<UUID>> = None;
+
+        for term in to_search_terms(&criteria.search_term) {
+            let ids = self.find_by_term_prefix(criteria.kind, &term);
+
+            let ids = match found {
+                Some(found) => found.intersection(&ids).copied().collect(),
+                None => ids,
+            };
+
+            if ids.is_empty() {
+                return ids;
+            }
+
+            found = Some(ids);
+        }
+
+        found.unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert_entry(repository: &SearchIndexRepository, kind: SearchIndexKind, id: u8, text: &str) {
+        for term in to_search_terms(text) {
+            repository.insert(SearchIndexKey {
+                kind,
+                term,
+                entry_id: [id; 16],
+            });
+        }
+    }
+
+    #[test]
+    fn test_repository_crud() {
+        let repository = SearchIndexRepository::default();
+        let index = SearchIndexKey {
+            kind: SearchIndexKind::Request,
+            term: "transfer".to_string(),
+            entry_id: [0; 16],
+        };
+
+        assert!(!repository.exists(&index));
+
+        repository.insert(index.clone());
+
+        assert!(repository.exists(&index));
+        assert!(repository.remove(&index));
+        assert!(!repository.exists(&index));
+    }
+
+    #[test]
+    fn test_find_by_criteria_matches_all_terms() {
+        let repository = SearchIndexRepository::default();
+        insert_entry(&repository, SearchIndexKind::Request, 1, "Monthly payroll");
+        insert_entry(&repository, SearchIndexKind::Request, 2, "Payroll bonus");
+        insert_entry(&repository, SearchIndexKind::ExternalCanister, 3, "Payroll");
+
+        let find = |search_term: &str| {
+            repository.find_by_criteria(SearchIndexCriteria {
+                kind: SearchIndexKind::Request,
+                search_term: search_term.to_string(),
+            })
+        };
+
+        assert_eq!(find("payroll").len(), 2);
+        assert_eq!(find("PAY"), HashSet::from([[1; 16], [2; 16]]));
+        assert_eq!(find("payroll month"), HashSet::from([[1; 16]]));
+        assert!(find("payroll yearly").is_empty());
+        assert!(find("  ").is_empty());
+    }
+
+    #[test]
+    fn test_clear_only_removes_kind() {
+        let repository = SearchIndexRepository::default();
+        insert_entry(&repository, SearchIndexKind::Request, 1, "payroll");
+        insert_entry(&repository, SearchIndexKind::ExternalCanister, 2, "payroll");
+
+        repository.clear(SearchIndexKind::Request);
+
+        let find = |kind: SearchIndexKind| {
+            repository.find_by_criteria(SearchIndexCriteria {
+                kind,
+                search_term: "payroll".to_string(),
+            })
+        };
+
+        assert!(find(SearchIndexKind::Request).is_empty());
+        assert_eq!(
+            find(SearchIndexKind::ExternalCanister),
+            HashSet::from([[2; 16]])
+        );
+    }
+}
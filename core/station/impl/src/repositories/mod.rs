@@ -15,6 +15,15 @@ pub use account::*;
 pub mod external_canister;
 pub use external_canister::*;
 
+pub mod external_canister_controllers;
+pub use external_canister_controllers::*;
+
+pub mod external_canister_cycles;
+pub use external_canister_cycles::*;
+
+pub mod external_canister_interface;
+pub use external_canister_interface::*;
+
 pub mod transfer;
 pub use transfer::*;
 
@@ -30,6 +39,9 @@ pub use request_policy::*;
 pub mod request_evaluation_result;
 pub use request_evaluation_result::*;
 
+pub mod request_snapshot;
+pub use request_snapshot::*;
+
 pub mod named_rule;
 pub use named_rule::*;
 
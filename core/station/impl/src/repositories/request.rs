@@ -1,5 +1,7 @@
 use super::indexes::{
     request_index::RequestIndexRepository, request_resource_index::RequestResourceIndexRepository,
+    request_resource_target_index::RequestResourceTargetIndexRepository,
+    search_index::SearchIndexRepository,
 };
 use crate::{
     core::{
@@ -12,7 +14,12 @@ use crate::{
     jobs::{jobs_observe_insert_request, jobs_observe_remove_request},
     models::{
         indexes::{
-            request_index::RequestIndexFields, request_resource_index::RequestResourceIndexCriteria,
+            request_index::RequestIndexFields,
+            request_resource_index::{
+                RequestResourceFilter, RequestResourceIndexCriteria,
+                RequestResourceTargetIndexCriteria,
+            },
+            search_index::{SearchIndexCriteria, SearchIndexKind},
         },
         resource::Resource,
         ListRequestsOperationType, Request, RequestId, RequestKey, RequestStatus,
@@ -49,6 +56,8 @@ lazy_static! {
 pub struct RequestRepository {
     index: RequestIndexRepository,
     resource_index: RequestResourceIndexRepository,
+    resource_target_index: RequestResourceTargetIndexRepository,
+    search_index: SearchIndexRepository,
     change_observer: Observer<(Request, Option<Request>)>,
     remove_observer: Observer<Request>,
 }
@@ -68,6 +77,8 @@ impl Default for RequestRepository {
             remove_observer,
             index: RequestIndexRepository::default(),
             resource_index: Default::default(),
+            resource_target_index: Default::default(),
+            search_index: Default::default(),
         }
     }
 }
@@ -92,6 +103,17 @@ impl IndexedRepository<RequestKey, Request, VirtualMemory<Memory>> for RequestRe
             self.resource_index.remove(index);
         });
 
+        entry
+            .to_index_for_resource_target()
+            .iter()
+            .for_each(|index| {
+                self.resource_target_index.remove(index);
+            });
+
+        entry.to_search_index().iter().for_each(|index| {
+            self.search_index.remove(index);
+        });
+
         entry.to_indexes().iter().for_each(|(index_key, _)| {
             self.index.remove(index_key);
         });
@@ -112,6 +134,17 @@ impl IndexedRepository<RequestKey, Request, VirtualMemory<Memory>> for RequestRe
             self.resource_index.insert(index);
         });
 
+        entry
+            .to_index_for_resource_target()
+            .into_iter()
+            .for_each(|index| {
+                self.resource_target_index.insert(index);
+            });
+
+        entry.to_search_index().into_iter().for_each(|index| {
+            self.search_index.insert(index);
+        });
+
         entry
             .to_indexes()
             .into_iter()
@@ -126,6 +159,8 @@ impl IndexedRepository<RequestKey, Request, VirtualMemory<Memory>> for RequestRe
 
         self.index.clear();
         self.resource_index.clear();
+        self.resource_target_index.clear();
+        self.search_index.clear(SearchIndexKind::Request);
     }
 }
 
@@ -163,6 +198,57 @@ impl RequestRepository {
     /// so the max cache storage size is around 300 MiB.
     const MAX_INDEXED_FIELDS_CACHE_SIZE: usize = 500_000;
 
+    /// Adds up to `limit` requests to the search index, starting from the given id, and returns the
+    /// id of the next request to index if there are more left.
+    ///
+    /// Used to populate the search index with the requests that were added before the index existed.
+    pub fn build_search_index(&self, from: Option<RequestId>, limit: usize) -> Option<RequestId> {
+        DB.with(|m| {
+            let db = m.borrow();
+            let mut entries = match from {
+                Some(id) => db.range(RequestKey { id }..),
+                None => db.range(..),
+            };
+
+            entries.by_ref().take(limit).for_each(|(_, request)| {
+                request.to_search_index().into_iter().for_each(|index| {
+                    self.search_index.insert(index);
+                });
+            });
+
+            entries.next().map(|(key, _)| key.id)
+        })
+    }
+
+    /// Adds up to `limit` requests to the resource target index, starting from the given id, and returns
+    /// the id of the next request to index if there are more left.
+    ///
+    /// Used to populate the resource target index with the requests that were added before the index existed.
+    pub fn build_resource_target_index(
+        &self,
+        from: Option<RequestId>,
+        limit: usize,
+    ) -> Option<RequestId> {
+        DB.with(|m| {
+            let db = m.borrow();
+            let mut entries = match from {
+                Some(id) => db.range(RequestKey { id }..),
+                None => db.range(..),
+            };
+
+            entries.by_ref().take(limit).for_each(|(_, request)| {
+                request
+                    .to_index_for_resource_target()
+                    .into_iter()
+                    .for_each(|index| {
+                        self.resource_target_index.insert(index);
+                    });
+            });
+
+            entries.next().map(|(key, _)| key.id)
+        })
+    }
+
     /// Find requests that have the provided status and would be expired between the provided timestamps.
     pub fn find_by_status_and_expiration_dt(
         &self,
@@ -214,6 +300,26 @@ impl RequestRepository {
             .collect::<Vec<Request>>()
     }
 
+    /// Find finalized requests that were last modified before the provided timestamp.
+    pub fn find_finalized_before(
+        &self,
+        last_modified_before: Timestamp,
+        take_limit: usize,
+    ) -> Vec<Request> {
+        [
+            RequestStatusCode::Completed,
+            RequestStatusCode::Rejected,
+            RequestStatusCode::Cancelled,
+            RequestStatusCode::Failed,
+        ]
+        .into_iter()
+        .flat_map(|status| self.index.find_by_status(status, None))
+        .filter(|(_, fields)| fields.last_modified_at <= last_modified_before)
+        .take(take_limit)
+        .filter_map(|(request_id, _)| self.get(&RequestKey { id: request_id }))
+        .collect::<Vec<Request>>()
+    }
+
     /// Find requests that are scheduled between the provided timestamps.
     pub fn find_scheduled(
         &self,
@@ -284,15 +390,64 @@ impl RequestRepository {
         condition: RequestWhereClause,
         sort_by: Option<ListRequestsSortBy>,
     ) -> Result<Vec<UUID>, RepositoryError> {
-        let mut entries = Vec::<(RequestId, RequestIndexFields)>::new();
-
         // first find the initial result set that would narrow down the search space
-        entries.extend(self.index.find_by_created_at_between(
-            condition.created_dt_from.unwrap_or(0),
-            condition.created_dt_to.unwrap_or(u64::MAX),
-            None,
-        ));
+        let mut entries = self
+            .index
+            .find_by_created_at_between(
+                condition.created_dt_from.unwrap_or(0),
+                condition.created_dt_to.unwrap_or(u64::MAX),
+                None,
+            )
+            .into_iter()
+            .collect::<Vec<(RequestId, RequestIndexFields)>>();
+
+        if let Some(criteria) =
+            SearchIndexCriteria::new(SearchIndexKind::Request, condition.search_term.as_deref())
+        {
+            let found_ids = self.search_index.find_by_criteria(criteria);
+
+            entries.retain(|(id, _)| found_ids.contains(id));
+        }
+
+        if !condition.resources.is_empty() {
+            let found_ids = condition
+                .resources
+                .iter()
+                .flat_map(|filter| {
+                    self.resource_target_index.find_by_criteria(
+                        RequestResourceTargetIndexCriteria {
+                            target: filter.clone(),
+                        },
+                    )
+                })
+                .collect::<HashSet<UUID>>();
+
+            entries.retain(|(id, _)| found_ids.contains(id));
+        }
+
+        let entries = Self::filter_and_sort_entries(entries, &condition, &sort_by);
+
+        INDEXED_FIELDS_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            for (id, fields) in &entries {
+                cache.insert(*id, fields.clone());
+            }
+        });
+
+        Ok(entries.into_iter().map(|(id, _)| id).collect())
+    }
 
+    /// Filters the provided entries based on the condition and sorts them based on the provided sort strategy.
+    ///
+    /// The search term and resources of the condition are resolved through their indexes beforehand, hence
+    /// they are not evaluated here.
+    ///
+    /// Also used for requests that are not stored in the repository anymore (e.g. archived requests).
+    pub fn filter_and_sort_entries(
+        entries: Vec<(RequestId, RequestIndexFields)>,
+        condition: &RequestWhereClause,
+        sort_by: &Option<ListRequestsSortBy>,
+    ) -> Vec<(RequestId, RequestIndexFields)> {
         // transform lists to constant lookup time
         let where_approvals: HashSet<_> = condition.approvers.iter().cloned().collect();
         let where_not_approvals: HashSet<_> = condition.not_approvers.iter().cloned().collect();
@@ -304,13 +459,19 @@ impl RequestRepository {
         let where_tags: HashSet<_> = condition.tags.iter().collect();
 
         // filter the result set based on the condition
-        entries = entries
+        let mut entries = entries
             .into_iter()
             .filter(|(id, fields)| {
                 if !where_not_ids.is_empty() && where_not_ids.contains(id) {
                     return false;
                 }
 
+                if fields.created_at < condition.created_dt_from.unwrap_or(u64::MIN)
+                    || fields.created_at > condition.created_dt_to.unwrap_or(u64::MAX)
+                {
+                    return false;
+                }
+
                 if !where_status.is_empty() && !where_status.contains(&fields.status) {
                     return false;
                 }
@@ -375,10 +536,6 @@ impl RequestRepository {
                     return false;
                 }
 
-                INDEXED_FIELDS_CACHE.with(|cache| {
-                    cache.borrow_mut().insert(*id, fields.clone());
-                });
-
                 true
             })
             .collect::<Vec<(RequestId, RequestIndexFields)>>();
@@ -389,7 +546,7 @@ impl RequestRepository {
             let mut ord = a.created_at.cmp(&b.created_at);
             let mut dir = station_api::SortDirection::Desc;
 
-            if let Some(sort_by) = &sort_by {
+            if let Some(sort_by) = sort_by {
                 match sort_by {
                     ListRequestsSortBy::CreatedAt(direction) => {
                         ord = a.created_at.cmp(&b.created_at);
@@ -418,7 +575,7 @@ impl RequestRepository {
             }
         });
 
-        Ok(entries.into_iter().map(|(id, _)| id).collect())
+        entries
     }
 
     pub fn cancel_request(
@@ -482,6 +639,8 @@ pub struct RequestWhereClause {
     pub excluded_ids: Vec<UUID>,
     pub deduplication_keys: Vec<String>,
     pub tags: Vec<String>,
+    pub search_term: Option<String>,
+    pub resources: Vec<RequestResourceFilter>,
 }
 
 #[cfg(test)]
@@ -583,6 +742,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec![],
+            search_term: None,
+            resources: vec![],
         };
 
         let requests = REQUEST_REPOSITORY
@@ -634,6 +795,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec![],
+            search_term: None,
+            resources: vec![],
         };
 
         let requests = REQUEST_REPOSITORY
@@ -688,6 +851,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec![],
+            search_term: None,
+            resources: vec![],
         };
 
         let requests = REQUEST_REPOSITORY
@@ -710,6 +875,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec![],
+            search_term: None,
+            resources: vec![],
         };
 
         let requests = REQUEST_REPOSITORY
@@ -732,6 +899,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec![],
+            search_term: None,
+            resources: vec![],
         };
 
         let requests = REQUEST_REPOSITORY
@@ -754,6 +923,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec![],
+            search_term: None,
+            resources: vec![],
         };
 
         let requests = REQUEST_REPOSITORY
@@ -781,6 +952,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec![],
+            search_term: None,
+            resources: vec![],
         };
 
         let requests = REQUEST_REPOSITORY
@@ -863,6 +1036,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec![],
+            search_term: None,
+            resources: vec![],
         };
 
         let requests = REQUEST_REPOSITORY
@@ -912,6 +1087,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec!["common".to_string()],
+            search_term: None,
+            resources: vec![],
         };
         let requests = REQUEST_REPOSITORY.find_ids_where(condition, None).unwrap();
         assert_eq!(requests.len(), 5);
@@ -930,6 +1107,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec!["common".to_string(), "1".to_string()],
+            search_term: None,
+            resources: vec![],
         };
         let requests = REQUEST_REPOSITORY.find_ids_where(condition, None).unwrap();
         assert_eq!(requests.len(), 5);
@@ -948,6 +1127,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec!["1".to_string()],
+            search_term: None,
+            resources: vec![],
         };
         let requests = REQUEST_REPOSITORY.find_ids_where(condition, None).unwrap();
         assert_eq!(requests.len(), 1);
@@ -966,6 +1147,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec!["1".to_string(), "2".to_string()],
+            search_term: None,
+            resources: vec![],
         };
         let requests = REQUEST_REPOSITORY.find_ids_where(condition, None).unwrap();
         assert_eq!(requests.len(), 2);
@@ -984,6 +1167,8 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec!["non-existent".to_string()],
+            search_term: None,
+            resources: vec![],
         };
         let requests = REQUEST_REPOSITORY.find_ids_where(condition, None).unwrap();
         assert_eq!(requests.len(), 0);
@@ -1002,10 +1187,165 @@ mod tests {
             excluded_ids: vec![],
             deduplication_keys: vec![],
             tags: vec!["1".to_string(), "non-existent".to_string()],
+            search_term: None,
+            resources: vec![],
         };
         let requests = REQUEST_REPOSITORY.find_ids_where(condition, None).unwrap();
         assert_eq!(requests.len(), 1);
     }
+
+    #[test]
+    fn find_requests_by_resource() {
+        for i in 0..3u8 {
+            let mut request = mock_request();
+            request.id = [i; 16];
+            if let RequestOperation::Transfer(operation) = &mut request.operation {
+                operation.input.from_account_id = [i % 2; 16];
+            }
+            REQUEST_REPOSITORY.insert(request.to_key(), request);
+        }
+
+        let find = |resources: Vec<RequestResourceFilter>| {
+            let mut ids = REQUEST_REPOSITORY
+                .find_ids_where(
+                    RequestWhereClause {
+                        resources,
+                        ..Default::default()
+                    },
+                    None,
+                )
+                .unwrap();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(
+            find(vec![RequestResourceFilter::Account([0; 16])]),
+            vec![[0; 16], [2; 16]]
+        );
+        assert_eq!(
+            find(vec![
+                RequestResourceFilter::Account([1; 16]),
+                RequestResourceFilter::User([0; 16])
+            ]),
+            vec![[1; 16]]
+        );
+        assert!(find(vec![RequestResourceFilter::Asset([0; 16])]).is_empty());
+    }
+
+    #[test]
+    fn find_requests_by_search_term() {
+        for (i, title) in ["Pay the café bill", "Upgrade the wallet", "Pay the rent"]
+            .iter()
+            .enumerate()
+        {
+            let mut request = mock_request();
+            request.id = [i as u8; 16];
+            request.title = title.to_string();
+            request.summary = None;
+            REQUEST_REPOSITORY.insert(request.to_key(), request);
+        }
+
+        let find = |search_term: &str| {
+            REQUEST_REPOSITORY
+                .find_ids_where(
+                    RequestWhereClause {
+                        search_term: Some(search_term.to_string()),
+                        ..Default::default()
+                    },
+                    None,
+                )
+                .unwrap()
+        };
+
+        assert_eq!(find("pay").len(), 2);
+        assert_eq!(find("CAFE"), vec![[0; 16]]);
+        assert_eq!(find("the wal"), vec![[1; 16]]);
+        assert!(find("pay wallet").is_empty());
+        assert_eq!(find("  ").len(), 3);
+
+        let mut request = REQUEST_REPOSITORY.get(&RequestKey { id: [2; 16] }).unwrap();
+        request.title = "Monthly rent".to_string();
+        REQUEST_REPOSITORY.insert(request.to_key(), request);
+
+        assert_eq!(find("pay").len(), 1);
+        assert_eq!(find("monthly"), vec![[2; 16]]);
+    }
+
+    #[test]
+    fn build_search_index_in_batches() {
+        for i in 0..3 {
+            let mut request = mock_request();
+            request.id = [i; 16];
+            request.title = "Pay the rent".to_string();
+            REQUEST_REPOSITORY.insert(request.to_key(), request);
+        }
+
+        let find = || {
+            REQUEST_REPOSITORY
+                .find_ids_where(
+                    RequestWhereClause {
+                        search_term: Some("rent".to_string()),
+                        ..Default::default()
+                    },
+                    None,
+                )
+                .unwrap()
+        };
+
+        REQUEST_REPOSITORY
+            .search_index
+            .clear(SearchIndexKind::Request);
+
+        assert!(find().is_empty());
+
+        let next_id = REQUEST_REPOSITORY.build_search_index(None, 2);
+
+        assert_eq!(next_id, Some([2; 16]));
+        assert_eq!(find().len(), 2);
+        assert_eq!(REQUEST_REPOSITORY.build_search_index(next_id, 2), None);
+        assert_eq!(find().len(), 3);
+    }
+
+    #[test]
+    fn build_resource_target_index_in_batches() {
+        for i in 0..3 {
+            let mut request = mock_request();
+            request.id = [i; 16];
+            if let RequestOperation::Transfer(operation) = &mut request.operation {
+                operation.input.from_account_id = [9; 16];
+            }
+            REQUEST_REPOSITORY.insert(request.to_key(), request);
+        }
+
+        let find = || {
+            REQUEST_REPOSITORY
+                .find_ids_where(
+                    RequestWhereClause {
+                        resources: vec![RequestResourceFilter::Account([9; 16])],
+                        ..Default::default()
+                    },
+                    None,
+                )
+                .unwrap()
+        };
+
+        assert_eq!(find().len(), 3);
+
+        REQUEST_REPOSITORY.resource_target_index.clear();
+
+        assert!(find().is_empty());
+
+        let next_id = REQUEST_REPOSITORY.build_resource_target_index(None, 2);
+
+        assert_eq!(next_id, Some([2; 16]));
+        assert_eq!(find().len(), 2);
+        assert_eq!(
+            REQUEST_REPOSITORY.build_resource_target_index(next_id, 2),
+            None
+        );
+        assert_eq!(find().len(), 3);
+    }
 }
 
 #[cfg(feature = "canbench")]
@@ -1101,6 +1441,8 @@ mod benchs {
                     not_requesters: vec![],
                     deduplication_keys: vec![],
                     tags: vec![],
+                    search_term: None,
+                    resources: vec![],
                 },
                 None,
             );
@@ -0,0 +1,64 @@
+use crate::{
+    core::{with_memory_manager, Memory, REQUEST_SNAPSHOT_MEMORY_ID},
+    models::{RequestId, RequestSnapshot},
+};
+use ic_stable_structures::{memory_manager::VirtualMemory, StableBTreeMap};
+use lazy_static::lazy_static;
+use orbit_essentials::repository::{Repository, StableDb};
+use std::{cell::RefCell, sync::Arc};
+
+thread_local! {
+  static DB: RefCell<StableBTreeMap<RequestId, RequestSnapshot, VirtualMemory<Memory>>> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      StableBTreeMap::init(memory_manager.get(REQUEST_SNAPSHOT_MEMORY_ID))
+    )
+  })
+}
+
+lazy_static! {
+    pub static ref REQUEST_SNAPSHOT_REPOSITORY: Arc<RequestSnapshotRepository> =
+        Arc::new(RequestSnapshotRepository::default());
+}
+
+/// A repository that stores the snapshots of the entities edited by requests in stable memory.
+#[derive(Default, Debug)]
+pub struct RequestSnapshotRepository {}
+
+impl StableDb<RequestId, RequestSnapshot, VirtualMemory<Memory>> for RequestSnapshotRepository {
+    fn with_db<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut StableBTreeMap<RequestId, RequestSnapshot, VirtualMemory<Memory>>) -> R,
+    {
+        DB.with(|m| f(&mut m.borrow_mut()))
+    }
+}
+
+impl Repository<RequestId, RequestSnapshot, VirtualMemory<Memory>> for RequestSnapshotRepository {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EntitySnapshot;
+
+    #[test]
+    fn test_crud() {
+        let repository = RequestSnapshotRepository::default();
+        let snapshot = RequestSnapshot {
+            request_id: [1; 16],
+            at_creation: EntitySnapshot {
+                fields: [("name".to_string(), "Main".to_string())].into(),
+                taken_at: 0,
+            },
+            requested: [("name".to_string(), "Treasury".to_string())].into(),
+            after_execution: None,
+        };
+
+        assert!(repository.get(&snapshot.request_id).is_none());
+
+        repository.insert(snapshot.request_id, snapshot.clone());
+
+        assert_eq!(repository.get(&snapshot.request_id), Some(snapshot.clone()));
+        assert!(repository.remove(&snapshot.request_id).is_some());
+        assert!(repository.get(&snapshot.request_id).is_none());
+    }
+}
@@ -91,10 +91,16 @@ impl AddressBookService {
             });
 
         if let Some(search_term) = input.search_term {
+            let found_ids = self
+                .address_book_repository
+                .find_ids_by_search_term(&search_term);
             let search_term = search_term.to_lowercase();
             entries.retain(|entry| {
                 entry.address_owner.to_lowercase().contains(&search_term)
                     || entry.address.to_lowercase().contains(&search_term)
+                    || found_ids
+                        .as_ref()
+                        .is_some_and(|found_ids| found_ids.contains(&entry.id))
             });
         }
 
@@ -342,4 +348,52 @@ mod tests {
             .get_entry_by_id(&address_book_entry.id)
             .unwrap_err();
     }
+
+    #[test]
+    fn search_entries_matches_substrings_and_labels() {
+        let ctx = setup();
+        let mut address_book_entry_0 = mock_address_book_entry();
+        address_book_entry_0.id = [1; 16];
+        address_book_entry_0.address = "0x1234".to_string();
+        address_book_entry_0.address_owner = "Treasury".to_string();
+        address_book_entry_0.labels = vec!["exchange".to_string()];
+        let mut address_book_entry_1 = mock_address_book_entry();
+        address_book_entry_1.id = [2; 16];
+        address_book_entry_1.address = "0x5678".to_string();
+        address_book_entry_1.address_owner = "Payroll".to_string();
+        address_book_entry_1.labels = vec![];
+
+        ctx.repository
+            .insert(address_book_entry_0.to_key(), address_book_entry_0.clone());
+        ctx.repository
+            .insert(address_book_entry_1.to_key(), address_book_entry_1.clone());
+
+        let search = |search_term: &str| {
+            ctx.service
+                .search_entries(
+                    ListAddressBookEntriesInput {
+                        ids: None,
+                        addresses: None,
+                        blockchain: None,
+                        labels: None,
+                        address_formats: None,
+                        search_term: Some(search_term.to_string()),
+                    },
+                    None,
+                )
+                .unwrap()
+                .items
+                .into_iter()
+                .map(|entry| entry.id)
+                .collect::<Vec<_>>()
+        };
+
+        // the address owner and address are matched anywhere in the text
+        assert_eq!(search("asur"), vec![[1; 16]]);
+        assert_eq!(search("ROLL"), vec![[2; 16]]);
+        assert_eq!(search("567"), vec![[2; 16]]);
+        // the labels are matched by the search index
+        assert_eq!(search("exch"), vec![[1; 16]]);
+        assert!(search("unknown").is_empty());
+    }
 }
@@ -1,5 +1,9 @@
+use crate::core::read_system_info;
 use crate::factories::blockchains::InternetComputer;
-use crate::models::{AccountKey, CycleObtainStrategy, MonitorExternalCanisterStrategy};
+use crate::models::{
+    AccountKey, CycleObtainStrategy, MonitorExternalCanisterStrategy,
+    MonitoringExternalCanisterEstimatedRuntimeInput,
+};
 use crate::repositories::ACCOUNT_REPOSITORY;
 use candid::Principal;
 use canfund::api::cmc::IcCyclesMintingCanister;
@@ -105,7 +109,25 @@ impl CycleManager {
     }
 }
 
+/// The funding strategy used for the canisters deployed by the station for its own operation (e.g. the upgrader).
+pub fn system_canister_fund_strategy() -> MonitorExternalCanisterStrategy {
+    MonitorExternalCanisterStrategy::BelowEstimatedRuntime(
+        MonitoringExternalCanisterEstimatedRuntimeInput {
+            min_runtime_secs: 60 * 24 * 60 * 60,  // 60 days
+            fund_runtime_secs: 30 * 24 * 60 * 60, // 30 days
+            max_runtime_cycles_fund: 2_000_000_000_000,
+            fallback_min_cycles: 600_000_000_000,
+            fallback_fund_cycles: 300_000_000_000,
+        },
+    )
+}
+
+/// Cycles are not obtained while the station is frozen, since that withdraws funds from its accounts.
 fn get_obtain_cycle_config(strategy: &CycleObtainStrategy) -> Option<ObtainCyclesOptions> {
+    if read_system_info().is_frozen() {
+        return None;
+    }
+
     match strategy {
         CycleObtainStrategy::Disabled => None,
         CycleObtainStrategy::MintFromNativeToken { account_id } => {
@@ -2,9 +2,10 @@ use super::permission::{PermissionService, PERMISSION_SERVICE};
 use super::request_policy::{RequestPolicyService, REQUEST_POLICY_SERVICE};
 use crate::core::authorization::Authorization;
 use crate::core::ic_cdk::api::print;
+use crate::core::ic_cdk::next_time;
 use crate::core::utils::{retain_accessible_resources, PaginatedData};
 use crate::core::validation::EnsureExternalCanister;
-use crate::core::CallContext;
+use crate::core::{read_system_info, CallContext};
 use crate::errors::ExternalCanisterError;
 use crate::mappers::ExternalCanisterMapper;
 use crate::models::permission::Permission;
@@ -14,22 +15,26 @@ use crate::models::resource::{
     ExternalCanisterResourceAction, Resource, ValidationMethodResourceTarget,
 };
 use crate::models::{
-    AddRequestPolicyOperationInput, CanisterExecutionAndValidationMethodPairInput, CanisterMethod,
-    ConfigureExternalCanisterSettingsInput, CreateExternalCanisterOperationInput,
+    AddRequestPolicyOperationInput, CandidService, CanisterExecutionAndValidationMethodPairInput,
+    CanisterMethod, ConfigureExternalCanisterSettingsInput, CreateExternalCanisterOperationInput,
     CreateExternalCanisterOperationKind, CycleObtainStrategy, DefiniteCanisterSettingsInput,
     EditPermissionOperationInput, EditRequestPolicyOperationInput, ExternalCanister,
     ExternalCanisterAvailableFilters, ExternalCanisterCallPermission,
     ExternalCanisterCallRequestPolicyRule, ExternalCanisterCallRequestPolicyRuleInput,
     ExternalCanisterCallerMethodsPrivileges, ExternalCanisterCallerPrivileges,
     ExternalCanisterChangeCallPermissionsInput, ExternalCanisterChangeCallRequestPoliciesInput,
-    ExternalCanisterChangeRequestPolicyRule, ExternalCanisterEntryId, ExternalCanisterKey,
-    ExternalCanisterMonitoring, ExternalCanisterPermissions,
-    ExternalCanisterPermissionsUpdateInput, ExternalCanisterRequestPolicies,
-    ExternalCanisterRequestPoliciesUpdateInput, MonitorExternalCanisterStrategy, RequestPolicy,
+    ExternalCanisterChangeRequestPolicyRule, ExternalCanisterEntryId, ExternalCanisterFleetTarget,
+    ExternalCanisterInterface, ExternalCanisterKey, ExternalCanisterMonitoring,
+    ExternalCanisterPermissions, ExternalCanisterPermissionsUpdateInput,
+    ExternalCanisterRequestPolicies, ExternalCanisterRequestPoliciesUpdateInput,
+    ExternalCanisterState, MonitorExternalCanisterStrategy, RequestPolicy,
 };
 use crate::repositories::permission::{PermissionRepository, PERMISSION_REPOSITORY};
 use crate::repositories::{
-    ExternalCanisterRepository, ExternalCanisterWhereClause, RequestPolicyRepository,
+    ExternalCanisterControllersRepository, ExternalCanisterCyclesRepository,
+    ExternalCanisterInterfaceRepository, ExternalCanisterRepository, ExternalCanisterWhereClause,
+    RequestPolicyRepository, EXTERNAL_CANISTER_CONTROLLERS_REPOSITORY,
+    EXTERNAL_CANISTER_CYCLES_REPOSITORY, EXTERNAL_CANISTER_INTERFACE_REPOSITORY,
     EXTERNAL_CANISTER_REPOSITORY, REQUEST_POLICY_REPOSITORY,
 };
 use crate::services::cycle_manager::{CycleManager, CYCLE_MANAGER};
@@ -37,7 +42,8 @@ use candid::{Encode, Principal};
 use ic_cdk::api::call::call_raw;
 use ic_cdk::api::management_canister::main::{
     self as mgmt, delete_canister, deposit_cycles, stop_canister, update_settings,
-    CanisterIdRecord, CanisterStatusResponse, Snapshot, UpdateSettingsArgument,
+    CanisterIdRecord, CanisterInfoRequest, CanisterStatusResponse, Snapshot,
+    UpdateSettingsArgument,
 };
 use lazy_static::lazy_static;
 use orbit_essentials::api::ServiceResult;
@@ -51,6 +57,7 @@ use station_api::{
     ListExternalCanistersInput,
 };
 use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -63,6 +70,9 @@ lazy_static! {
             Arc::clone(&PERMISSION_REPOSITORY),
             Arc::clone(&REQUEST_POLICY_SERVICE),
             Arc::clone(&REQUEST_POLICY_REPOSITORY),
+            Arc::clone(&EXTERNAL_CANISTER_INTERFACE_REPOSITORY),
+            Arc::clone(&EXTERNAL_CANISTER_CYCLES_REPOSITORY),
+            Arc::clone(&EXTERNAL_CANISTER_CONTROLLERS_REPOSITORY),
         ));
 }
 
@@ -76,6 +86,9 @@ pub struct ExternalCanisterService {
     permission_repository: Arc<PermissionRepository>,
     request_policy_service: Arc<RequestPolicyService>,
     request_policy_repository: Arc<RequestPolicyRepository>,
+    external_canister_interface_repository: Arc<ExternalCanisterInterfaceRepository>,
+    external_canister_cycles_repository: Arc<ExternalCanisterCyclesRepository>,
+    external_canister_controllers_repository: Arc<ExternalCanisterControllersRepository>,
 }
 
 impl ExternalCanisterService {
@@ -89,6 +102,9 @@ impl ExternalCanisterService {
         permission_repository: Arc<PermissionRepository>,
         request_policy_service: Arc<RequestPolicyService>,
         request_policy_repository: Arc<RequestPolicyRepository>,
+        external_canister_interface_repository: Arc<ExternalCanisterInterfaceRepository>,
+        external_canister_cycles_repository: Arc<ExternalCanisterCyclesRepository>,
+        external_canister_controllers_repository: Arc<ExternalCanisterControllersRepository>,
     ) -> Self {
         Self {
             cycle_manager,
@@ -97,6 +113,9 @@ impl ExternalCanisterService {
             permission_repository,
             request_policy_service,
             request_policy_repository,
+            external_canister_interface_repository,
+            external_canister_cycles_repository,
+            external_canister_controllers_repository,
         }
     }
 
@@ -280,6 +299,12 @@ impl ExternalCanisterService {
                     ExternalCanisterId::Canister(*canister_id),
                 )),
             ),
+            can_read_logs: Authorization::is_allowed(
+                ctx,
+                &Resource::ExternalCanister(ExternalCanisterResourceAction::ReadLogs(
+                    ExternalCanisterId::Canister(*canister_id),
+                )),
+            ),
             can_call: self
                 .find_external_canister_call_permissions(canister_id)
                 .iter()
@@ -333,6 +358,7 @@ impl ExternalCanisterService {
                     .map(|states| states.into_iter().map(Into::into).collect())
                     .unwrap_or_default(),
                 sort_by: input.sort_by.clone().map(Into::into),
+                search_term: input.search_term.clone(),
             },
         );
 
@@ -415,6 +441,157 @@ impl ExternalCanisterService {
         ExternalCanisterAvailableFilters { names, labels }
     }
 
+    /// Resolves the external canisters targeted by a fleet upgrade.
+    ///
+    /// Labels resolve to the active external canisters that have any of the labels, sorted by name,
+    /// whereas explicitly listed canisters keep the given order.
+    pub fn resolve_fleet_target(
+        &self,
+        target: &ExternalCanisterFleetTarget,
+    ) -> ServiceResult<Vec<Principal>> {
+        let canister_ids = match target {
+            ExternalCanisterFleetTarget::Labels(labels) => {
+                if labels.is_empty() {
+                    Err(ExternalCanisterError::ValidationError {
+                        info: "At least one label is required to target a fleet.".to_string(),
+                    })?;
+                }
+
+                self.external_canister_repository.find_canister_ids_where(
+                    ExternalCanisterWhereClause {
+                        canister_ids: Vec::new(),
+                        labels: labels.clone(),
+                        states: vec![ExternalCanisterState::Active],
+                        sort_by: None,
+                        search_term: None,
+                    },
+                )
+            }
+            ExternalCanisterFleetTarget::CanisterIds(canister_ids) => {
+                let mut seen = HashSet::new();
+                let mut unique_ids = Vec::new();
+                for canister_id in canister_ids {
+                    self.get_external_canister_by_canister_id(canister_id)?;
+
+                    if seen.insert(*canister_id) {
+                        unique_ids.push(*canister_id);
+                    }
+                }
+
+                unique_ids
+            }
+        };
+
+        if canister_ids.is_empty() {
+            Err(ExternalCanisterError::ValidationError {
+                info: "The fleet target does not match any external canister.".to_string(),
+            })?;
+        }
+
+        Ok(canister_ids)
+    }
+
+    /// Returns the Candid interface registered for the external canister, if any.
+    pub fn get_candid_interface(
+        &self,
+        canister_id: &Principal,
+    ) -> Option<ExternalCanisterInterface> {
+        let id = self
+            .external_canister_repository
+            .find_by_canister_id(canister_id)?;
+
+        self.external_canister_interface_repository.get(&id)
+    }
+
+    /// Returns the parsed service of the Candid interface registered for the external canister, if any.
+    pub fn get_candid_service(&self, canister_id: &Principal) -> Option<Rc<CandidService>> {
+        let id = self
+            .external_canister_repository
+            .find_by_canister_id(canister_id)?;
+
+        self.external_canister_interface_repository.get_service(&id)
+    }
+
+    /// Registers the Candid interface of the external canister, `None` removes the registered interface.
+    pub fn set_candid_interface(
+        &self,
+        canister_id: &Principal,
+        candid: Option<String>,
+    ) -> ServiceResult<()> {
+        let external_canister = self.get_external_canister_by_canister_id(canister_id)?;
+
+        match candid {
+            Some(candid) => {
+                let interface = ExternalCanisterInterface {
+                    external_canister_id: external_canister.id,
+                    candid,
+                    updated_at: next_time(),
+                };
+
+                interface.validate()?;
+
+                self.external_canister_interface_repository
+                    .insert(external_canister.id, interface);
+            }
+            None => {
+                self.external_canister_interface_repository
+                    .remove(&external_canister.id);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the methods that can be called through the station without a request.
+    pub fn set_read_only_methods(
+        &self,
+        id: &ExternalCanisterEntryId,
+        mut methods: Vec<String>,
+    ) -> ServiceResult<ExternalCanister> {
+        let mut external_canister = self.get_external_canister(id)?;
+
+        methods.sort();
+        methods.dedup();
+        external_canister.read_only_methods = methods;
+
+        self.external_canister_repository
+            .insert(external_canister.key(), external_canister.clone());
+
+        Ok(external_canister)
+    }
+
+    /// Calls a read-only method of the external canister without a request.
+    ///
+    /// Only the methods allowed by the read-only methods of the external canister can be called.
+    pub async fn query_external_canister(
+        &self,
+        canister_id: Principal,
+        method_name: String,
+        arg: Option<Vec<u8>>,
+    ) -> ServiceResult<Vec<u8>> {
+        let external_canister = self.get_external_canister_by_canister_id(&canister_id)?;
+
+        if external_canister.is_archived() {
+            Err(ExternalCanisterError::ValidationError {
+                info: format!("The external canister {canister_id} is archived."),
+            })?;
+        }
+
+        if !external_canister.read_only_methods.contains(&method_name) {
+            Err(ExternalCanisterError::ValidationError {
+                info: format!(
+                    "The method `{method_name}` is not a read-only method of the external canister {canister_id}."
+                ),
+            })?;
+        }
+
+        let reply = self
+            .call_external_canister(canister_id, method_name, arg, None)
+            .await?;
+
+        Ok(reply)
+    }
+
     /// Calls the management canister to get the status of the canister with the given id.
     ///
     /// The station needs to be a controller of the target canister.
@@ -434,6 +611,27 @@ impl ExternalCanisterService {
         Ok(canister_status_response)
     }
 
+    /// Calls the management canister to get the current controllers of the canister with the given id.
+    ///
+    /// Unlike the canister status, the controllers can be fetched even if the station is no longer
+    /// a controller of the target canister.
+    pub async fn canister_controllers(
+        &self,
+        canister_id: Principal,
+    ) -> ServiceResult<Vec<Principal>> {
+        let canister_info_response = mgmt::canister_info(CanisterInfoRequest {
+            canister_id,
+            num_requested_changes: None,
+        })
+        .await
+        .map_err(|(_, err)| ExternalCanisterError::Failed {
+            reason: err.to_string(),
+        })?
+        .0;
+
+        Ok(canister_info_response.controllers)
+    }
+
     /// Calls the management canister to get the snapshots of the canister with the given id.
     ///
     /// The station needs to be a controller of the target canister.
@@ -553,6 +751,8 @@ impl ExternalCanisterService {
                 .remove(&external_canister.key());
         })?;
 
+        crate::jobs::schedule_external_canister_controllers_reconciliation();
+
         Ok(external_canister)
     }
 
@@ -1035,14 +1235,23 @@ impl ExternalCanisterService {
     }
 
     /// Restarts monitoring for all external canisters after upgrade
+    ///
+    /// The monitored canisters are not topped up while the station is frozen, since that moves cycles
+    /// out of the station, they are added back once the station is unfrozen.
     pub fn canister_monitor_restart(&self) {
+        let is_frozen = read_system_info().is_frozen();
+
         for canister in self.external_canister_repository.find_all() {
             if let Some(monitoring) = &canister.monitoring {
-                self.cycle_manager.add_canister(
-                    canister.canister_id,
-                    monitoring.funding_strategy.clone(),
-                    monitoring.cycle_obtain_strategy,
-                );
+                if is_frozen {
+                    self.cycle_manager.remove_canister(canister.canister_id);
+                } else {
+                    self.cycle_manager.add_canister(
+                        canister.canister_id,
+                        monitoring.funding_strategy.clone(),
+                        monitoring.cycle_obtain_strategy,
+                    );
+                }
             }
         }
     }
@@ -1072,8 +1281,11 @@ impl ExternalCanisterService {
             })?;
         }
 
-        self.cycle_manager
-            .add_canister(canister_id, strategy.clone(), cycle_obtain_strategy);
+        // the monitoring of a frozen station is picked up by the cycle manager once it's unfrozen
+        if !read_system_info().is_frozen() {
+            self.cycle_manager
+                .add_canister(canister_id, strategy.clone(), cycle_obtain_strategy);
+        }
 
         external_canister.monitoring = Some(ExternalCanisterMonitoring {
             funding_strategy: strategy,
@@ -1083,6 +1295,8 @@ impl ExternalCanisterService {
         self.external_canister_repository
             .insert(external_canister.key(), external_canister.clone());
 
+        crate::jobs::schedule_external_canister_cycles_sampling();
+
         Ok(())
     }
 
@@ -1107,6 +1321,12 @@ impl ExternalCanisterService {
         let external_canister = self.get_external_canister(id)?;
         self.external_canister_repository
             .remove(&external_canister.key());
+        self.external_canister_interface_repository
+            .remove(&external_canister.id);
+        self.external_canister_cycles_repository
+            .remove_by_external_canister_id(&external_canister.id);
+        self.external_canister_controllers_repository
+            .remove(&external_canister.id);
 
         // Removes the read, change & fund permissions.
         self.permission_service
@@ -1296,7 +1516,7 @@ mod tests {
             ExternalCanisterChangeCallPermissionsInput,
             ExternalCanisterChangeCallRequestPoliciesInput,
             ExternalCanisterChangeRequestPolicyRuleInput, ExternalCanisterPermissionsCreateInput,
-            ExternalCanisterRequestPoliciesCreateInput, Metadata, RequestPolicyRule,
+            ExternalCanisterRequestPoliciesCreateInput, Metadata, RequestPolicyRule, StationFreeze,
         },
     };
     use orbit_essentials::api::ApiError;
@@ -1749,6 +1969,82 @@ mod tests {
             .is_err());
     }
 
+    #[tokio::test]
+    async fn test_candid_interface_is_registered_and_removed() {
+        setup();
+        let canister = EXTERNAL_CANISTER_SERVICE
+            .add_external_canister(CreateExternalCanisterOperationInput {
+                name: "test".to_string(),
+                description: None,
+                labels: None,
+                metadata: None,
+                permissions: ExternalCanisterPermissionsCreateInput {
+                    read: Allow::authenticated(),
+                    change: Allow::authenticated(),
+                    calls: Vec::new(),
+                },
+                request_policies: ExternalCanisterRequestPoliciesCreateInput {
+                    change: Vec::new(),
+                    calls: Vec::new(),
+                },
+                kind: CreateExternalCanisterOperationKind::AddExisting(
+                    CreateExternalCanisterOperationKindAddExisting {
+                        canister_id: Principal::from_slice(&[11; 29]),
+                    },
+                ),
+            })
+            .await
+            .unwrap();
+
+        assert!(EXTERNAL_CANISTER_SERVICE
+            .set_candid_interface(&canister.canister_id, Some("service : {".to_string()))
+            .is_err());
+
+        let candid = "service : { greet : (text) -> (text) }".to_string();
+        EXTERNAL_CANISTER_SERVICE
+            .set_candid_interface(&canister.canister_id, Some(candid.clone()))
+            .unwrap();
+
+        let interface = EXTERNAL_CANISTER_SERVICE
+            .get_candid_interface(&canister.canister_id)
+            .unwrap();
+        assert_eq!(interface.candid, candid);
+
+        let arg = Encode!(&"world").unwrap();
+        assert!(EXTERNAL_CANISTER_SERVICE
+            .get_candid_service(&canister.canister_id)
+            .unwrap()
+            .render_args("greet", &arg)
+            .is_ok());
+
+        // the cached service is replaced once another interface is registered
+        EXTERNAL_CANISTER_SERVICE
+            .set_candid_interface(
+                &canister.canister_id,
+                Some("service : { hello : (text) -> (text) }".to_string()),
+            )
+            .unwrap();
+        let service = EXTERNAL_CANISTER_SERVICE
+            .get_candid_service(&canister.canister_id)
+            .unwrap();
+        assert!(service.render_args("greet", &arg).is_err());
+        assert!(service.render_args("hello", &arg).is_ok());
+
+        EXTERNAL_CANISTER_SERVICE
+            .soft_delete_external_canister(&canister.id)
+            .unwrap();
+
+        assert!(EXTERNAL_CANISTER_SERVICE
+            .get_candid_interface(&canister.canister_id)
+            .is_none());
+        assert!(EXTERNAL_CANISTER_INTERFACE_REPOSITORY
+            .get(&canister.id)
+            .is_none());
+        assert!(EXTERNAL_CANISTER_INTERFACE_REPOSITORY
+            .get_service(&canister.id)
+            .is_none());
+    }
+
     #[tokio::test]
     async fn test_edit_external_canister() {
         setup();
@@ -2360,6 +2656,87 @@ mod tests {
             })
         );
     }
+
+    #[tokio::test]
+    async fn query_external_canister_requires_read_only_method() {
+        setup();
+        let mut external_canister =
+            crate::models::external_canister_test_utils::mock_external_canister();
+        external_canister.read_only_methods = vec!["get_config".to_string()];
+        EXTERNAL_CANISTER_REPOSITORY.insert(external_canister.key(), external_canister.clone());
+
+        let result = EXTERNAL_CANISTER_SERVICE
+            .query_external_canister(
+                external_canister.canister_id,
+                "set_config".to_string(),
+                None,
+            )
+            .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            ApiError::from(ExternalCanisterError::ValidationError {
+                info: format!(
+                    "The method `set_config` is not a read-only method of the external canister {}.",
+                    external_canister.canister_id
+                )
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn frozen_station_does_not_top_up_monitored_canisters() {
+        setup();
+        let external_canister =
+            crate::models::external_canister_test_utils::mock_external_canister();
+        EXTERNAL_CANISTER_REPOSITORY.insert(external_canister.key(), external_canister.clone());
+
+        let mut system_info = read_system_info();
+        system_info.set_freeze(Some(StationFreeze {
+            request_id: [1; 16],
+            reason: None,
+            frozen_at: 0,
+        }));
+        crate::core::write_system_info(system_info.clone());
+
+        EXTERNAL_CANISTER_SERVICE
+            .canister_monitor_start(
+                external_canister.canister_id,
+                MonitorExternalCanisterStrategy::Always(1_000_000_000_000),
+                None,
+            )
+            .unwrap();
+
+        // the monitoring is stored but the canister is not funded while the station is frozen
+        assert!(EXTERNAL_CANISTER_SERVICE
+            .get_external_canister(&external_canister.id)
+            .unwrap()
+            .monitoring
+            .is_some());
+        assert!(CYCLE_MANAGER
+            .get_canister(&external_canister.canister_id)
+            .is_none());
+
+        system_info.set_freeze(None);
+        crate::core::write_system_info(system_info.clone());
+        EXTERNAL_CANISTER_SERVICE.canister_monitor_restart();
+
+        assert!(CYCLE_MANAGER
+            .get_canister(&external_canister.canister_id)
+            .is_some());
+
+        system_info.set_freeze(Some(StationFreeze {
+            request_id: [2; 16],
+            reason: None,
+            frozen_at: 0,
+        }));
+        crate::core::write_system_info(system_info);
+        EXTERNAL_CANISTER_SERVICE.canister_monitor_restart();
+
+        assert!(CYCLE_MANAGER
+            .get_canister(&external_canister.canister_id)
+            .is_none());
+    }
 }
 
 #[cfg(feature = "canbench")]
@@ -2426,6 +2803,7 @@ mod benchs {
                         sort_by: Some(station_api::ListExternalCanistersSortInput::Name(
                             station_api::SortDirection::Asc,
                         )),
+                        search_term: None,
                     },
                     &CallContext::new(caller_identity),
                 )
@@ -0,0 +1,304 @@
+use crate::{
+    core::ic_cdk::{api::print, next_time},
+    errors::ExternalCanisterError,
+    models::{
+        ExternalCanister, ExternalCanisterBackup, ExternalCanisterBackupSchedule,
+        ExternalCanisterKey, ExternalCanisterState, PruneExternalCanisterResource,
+    },
+    repositories::{ExternalCanisterRepository, EXTERNAL_CANISTER_REPOSITORY},
+    services::{ChangeCanisterService, CHANGE_CANISTER_SERVICE},
+};
+use candid::Principal;
+use lazy_static::lazy_static;
+use orbit_essentials::{
+    api::ServiceResult, model::ModelKey, repository::Repository, types::Timestamp,
+};
+use std::sync::Arc;
+
+lazy_static! {
+    pub static ref EXTERNAL_CANISTER_BACKUP_SERVICE: Arc<ExternalCanisterBackupService> =
+        Arc::new(ExternalCanisterBackupService::new(
+            Arc::clone(&EXTERNAL_CANISTER_REPOSITORY),
+            Arc::clone(&CHANGE_CANISTER_SERVICE),
+        ));
+}
+
+/// Takes the automatic backup snapshots of external canisters and applies their retention policy.
+#[derive(Default, Debug)]
+pub struct ExternalCanisterBackupService {
+    external_canister_repository: Arc<ExternalCanisterRepository>,
+    change_canister_service: Arc<ChangeCanisterService>,
+}
+
+impl ExternalCanisterBackupService {
+    pub fn new(
+        external_canister_repository: Arc<ExternalCanisterRepository>,
+        change_canister_service: Arc<ChangeCanisterService>,
+    ) -> Self {
+        Self {
+            external_canister_repository,
+            change_canister_service,
+        }
+    }
+
+    fn get_external_canister(&self, canister_id: &Principal) -> ServiceResult<ExternalCanister> {
+        let external_canister = self
+            .external_canister_repository
+            .find_by_canister_id(canister_id)
+            .and_then(|id| {
+                self.external_canister_repository
+                    .get(&ExternalCanisterKey { id })
+            })
+            .ok_or(ExternalCanisterError::InvalidExternalCanister {
+                principal: *canister_id,
+            })?;
+
+        Ok(external_canister)
+    }
+
+    /// Applies the given change to the backup configuration of the canister, if it is configured.
+    ///
+    /// The canister is read again from the repository since it might have changed while awaiting.
+    fn update_backup<F>(&self, canister_id: &Principal, update: F)
+    where
+        F: FnOnce(&mut ExternalCanisterBackup),
+    {
+        if let Ok(mut external_canister) = self.get_external_canister(canister_id) {
+            if let Some(backup) = external_canister.backup.as_mut() {
+                update(backup);
+
+                self.external_canister_repository
+                    .insert(external_canister.key(), external_canister);
+            }
+        }
+    }
+
+    /// Sets the automatic backup schedule of the canister, `None` disables automatic backups.
+    ///
+    /// Disabling the backups does not delete the backup snapshots that were already taken.
+    pub async fn set_backup_schedule(
+        &self,
+        canister_id: Principal,
+        schedule: Option<ExternalCanisterBackupSchedule>,
+    ) -> ServiceResult<()> {
+        let mut external_canister = self.get_external_canister(&canister_id)?;
+        let now = next_time();
+
+        external_canister.backup = match (external_canister.backup.take(), schedule) {
+            (_, None) => None,
+            (Some(mut backup), Some(schedule)) => {
+                backup.schedule = schedule;
+                backup.schedule_next_backup(now);
+
+                Some(backup)
+            }
+            (None, Some(schedule)) => Some(ExternalCanisterBackup::new(schedule, now)),
+        };
+
+        let next_backup_at = external_canister
+            .backup
+            .as_ref()
+            .and_then(|backup| backup.next_backup_at);
+
+        self.external_canister_repository
+            .insert(external_canister.key(), external_canister);
+
+        if let Some(next_backup_at) = next_backup_at {
+            crate::jobs::schedule_external_canister_backups(next_backup_at);
+        }
+
+        // the retention count might have been lowered
+        self.prune_backup_snapshots(canister_id).await;
+
+        Ok(())
+    }
+
+    /// Takes a backup snapshot of the canister and prunes the backup snapshots that exceed
+    /// the retention count, the outcome is recorded in the backup status of the canister.
+    pub async fn backup_canister(&self, canister_id: Principal) -> ServiceResult<Vec<u8>> {
+        let replace_snapshot = self
+            .get_external_canister(&canister_id)?
+            .backup
+            .ok_or(ExternalCanisterError::Failed {
+                reason: format!("automatic backups are not configured for {canister_id}"),
+            })?
+            .snapshot_to_replace();
+
+        let result = self
+            .change_canister_service
+            .snapshot_canister(canister_id, replace_snapshot.clone(), false)
+            .await;
+
+        let now = next_time();
+        self.update_backup(&canister_id, |backup| match &result {
+            Ok(snapshot_id) => backup.record_success(snapshot_id.clone(), replace_snapshot, now),
+            Err(err) => backup.record_failure(err.to_string(), now),
+        });
+
+        let snapshot_id = result.map_err(|err| ExternalCanisterError::Failed {
+            reason: format!("failed to backup canister {canister_id}: {err}"),
+        })?;
+
+        self.prune_backup_snapshots(canister_id).await;
+
+        Ok(snapshot_id)
+    }
+
+    /// Returns `Some` with the snapshot that should be replaced if a backup snapshot must be
+    /// taken before the canister is changed, otherwise `None`.
+    pub fn get_before_change_backup(&self, canister_id: &Principal) -> Option<Option<Vec<u8>>> {
+        self.get_external_canister(canister_id)
+            .ok()?
+            .backup
+            .filter(|backup| backup.schedule.before_change)
+            .map(|backup| backup.snapshot_to_replace())
+    }
+
+    /// Records the backup snapshot that was taken before the canister was changed.
+    pub async fn record_before_change_backup(
+        &self,
+        canister_id: Principal,
+        snapshot_id: Vec<u8>,
+        replaced_snapshot_id: Option<Vec<u8>>,
+    ) {
+        let now = next_time();
+        self.update_backup(&canister_id, |backup| {
+            backup.record_success(snapshot_id, replaced_snapshot_id, now)
+        });
+
+        self.prune_backup_snapshots(canister_id).await;
+    }
+
+    /// Deletes the oldest backup snapshots that exceed the retention count of the canister.
+    ///
+    /// Snapshots that fail to be deleted are kept in the backup status and retried on the next backup.
+    async fn prune_backup_snapshots(&self, canister_id: Principal) {
+        let Some(backup) = self
+            .get_external_canister(&canister_id)
+            .ok()
+            .and_then(|external_canister| external_canister.backup)
+        else {
+            return;
+        };
+
+        for snapshot_id in backup.snapshots_to_prune() {
+            match self
+                .change_canister_service
+                .prune_canister(
+                    canister_id,
+                    PruneExternalCanisterResource::Snapshot(snapshot_id.clone()),
+                )
+                .await
+            {
+                Ok(()) => self.update_backup(&canister_id, |backup| {
+                    backup.snapshot_ids.retain(|id| *id != snapshot_id)
+                }),
+                Err(err) => print(format!(
+                    "Failed to prune backup snapshot {} of canister {}: {}",
+                    hex::encode(&snapshot_id),
+                    canister_id,
+                    err
+                )),
+            }
+        }
+    }
+
+    /// Takes the periodic backups that are due and returns the time at which the next one is due.
+    pub async fn run_due_backups(&self) -> Option<Timestamp> {
+        let now = next_time();
+        let due_backups = self
+            .external_canister_repository
+            .find_all()
+            .into_iter()
+            .filter(|external_canister| {
+                external_canister
+                    .backup
+                    .as_ref()
+                    .and_then(|backup| backup.next_backup_at)
+                    .is_some_and(|next_backup_at| next_backup_at <= now)
+            })
+            .collect::<Vec<_>>();
+
+        for external_canister in due_backups {
+            let canister_id = external_canister.canister_id;
+
+            // archived canisters keep their schedule but are not backed up
+            if external_canister.state == ExternalCanisterState::Active {
+                if let Err(err) = self.backup_canister(canister_id).await {
+                    print(format!("Failed to backup canister {canister_id}: {err}"));
+                }
+            }
+
+            self.update_backup(&canister_id, |backup| {
+                backup.schedule_next_backup(next_time())
+            });
+        }
+
+        self.next_backup_at()
+    }
+
+    /// Returns the earliest time at which a periodic backup is due.
+    pub fn next_backup_at(&self) -> Option<Timestamp> {
+        self.external_canister_repository
+            .find_all()
+            .into_iter()
+            .filter_map(|external_canister| external_canister.backup?.next_backup_at)
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        external_canister_test_utils::mock_external_canister, ExternalCanisterBackupScheduleInput,
+    };
+
+    #[tokio::test]
+    async fn set_and_disable_backup_schedule() {
+        let external_canister = mock_external_canister();
+        EXTERNAL_CANISTER_REPOSITORY.insert(external_canister.key(), external_canister.clone());
+
+        let service = EXTERNAL_CANISTER_BACKUP_SERVICE.clone();
+        service
+            .set_backup_schedule(
+                external_canister.canister_id,
+                Some(ExternalCanisterBackupScheduleInput {
+                    interval_secs: Some(24 * 60 * 60),
+                    before_change: true,
+                    retention_count: 3,
+                }),
+            )
+            .await
+            .unwrap();
+
+        let backup = service
+            .get_external_canister(&external_canister.canister_id)
+            .unwrap()
+            .backup
+            .unwrap();
+
+        assert_eq!(backup.schedule.retention_count, 3);
+        assert!(backup.next_backup_at.is_some());
+        assert_eq!(service.next_backup_at(), backup.next_backup_at);
+        assert_eq!(
+            service.get_before_change_backup(&external_canister.canister_id),
+            Some(None)
+        );
+
+        service
+            .set_backup_schedule(external_canister.canister_id, None)
+            .await
+            .unwrap();
+
+        assert!(service
+            .get_external_canister(&external_canister.canister_id)
+            .unwrap()
+            .backup
+            .is_none());
+        assert_eq!(
+            service.get_before_change_backup(&external_canister.canister_id),
+            None
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{
+    core::ic_cdk::{api::print, next_time},
+    errors::ExternalCanisterError,
+    models::{
+        validate_canister_controllers, ConfigureExternalCanisterSettingsInput,
+        DefiniteCanisterSettingsInput, ExternalCanister, ExternalCanisterControllers,
+        ExternalCanisterControllersDrift, ExternalCanisterState, NotificationType, UserStatus,
+        ADMIN_GROUP_ID,
+    },
+    repositories::{
+        ExternalCanisterControllersRepository, ExternalCanisterRepository, UserRepository,
+        EXTERNAL_CANISTER_CONTROLLERS_REPOSITORY, EXTERNAL_CANISTER_REPOSITORY, USER_REPOSITORY,
+    },
+    services::{
+        ExternalCanisterService, NotificationService, EXTERNAL_CANISTER_SERVICE,
+        NOTIFICATION_SERVICE,
+    },
+};
+use candid::Principal;
+use lazy_static::lazy_static;
+use orbit_essentials::{api::ServiceResult, repository::Repository};
+use std::sync::Arc;
+
+lazy_static! {
+    pub static ref EXTERNAL_CANISTER_CONTROLLERS_SERVICE: Arc<ExternalCanisterControllersService> =
+        Arc::new(ExternalCanisterControllersService::new(
+            Arc::clone(&EXTERNAL_CANISTER_SERVICE),
+            Arc::clone(&EXTERNAL_CANISTER_REPOSITORY),
+            Arc::clone(&EXTERNAL_CANISTER_CONTROLLERS_REPOSITORY),
+            Arc::clone(&NOTIFICATION_SERVICE),
+            Arc::clone(&USER_REPOSITORY),
+        ));
+}
+
+/// Changes the controllers of external canisters and detects the changes made outside of the station.
+#[derive(Default, Debug)]
+pub struct ExternalCanisterControllersService {
+    external_canister_service: Arc<ExternalCanisterService>,
+    external_canister_repository: Arc<ExternalCanisterRepository>,
+    external_canister_controllers_repository: Arc<ExternalCanisterControllersRepository>,
+    notification_service: Arc<NotificationService>,
+    user_repository: Arc<UserRepository>,
+}
+
+impl ExternalCanisterControllersService {
+    pub fn new(
+        external_canister_service: Arc<ExternalCanisterService>,
+        external_canister_repository: Arc<ExternalCanisterRepository>,
+        external_canister_controllers_repository: Arc<ExternalCanisterControllersRepository>,
+        notification_service: Arc<NotificationService>,
+        user_repository: Arc<UserRepository>,
+    ) -> Self {
+        Self {
+            external_canister_service,
+            external_canister_repository,
+            external_canister_controllers_repository,
+            notification_service,
+            user_repository,
+        }
+    }
+
+    fn find_active_canisters(&self) -> Vec<ExternalCanister> {
+        self.external_canister_repository
+            .find_all()
+            .into_iter()
+            .filter(|external_canister| external_canister.state == ExternalCanisterState::Active)
+            .collect()
+    }
+
+    /// Returns `true` if the controllers of any external canister are reconciled.
+    pub fn has_active_canisters(&self) -> bool {
+        !self.find_active_canisters().is_empty()
+    }
+
+    /// Records the controllers that the station set on the canister, so that they are not reported
+    /// as changed outside of the station.
+    pub fn record_controllers(&self, canister_id: &Principal, controllers: Vec<Principal>) {
+        if let Some(external_canister_id) = self
+            .external_canister_repository
+            .find_by_canister_id(canister_id)
+        {
+            self.external_canister_controllers_repository.insert(
+                external_canister_id,
+                ExternalCanisterControllers::new(external_canister_id, controllers, next_time()),
+            );
+        }
+    }
+
+    /// Adds the controllers to the current controllers of the canister.
+    pub async fn add_controllers(
+        &self,
+        canister_id: Principal,
+        controllers: Vec<Principal>,
+    ) -> ServiceResult<()> {
+        let mut updated_controllers = self
+            .external_canister_service
+            .canister_controllers(canister_id)
+            .await?;
+        updated_controllers.extend(controllers);
+        updated_controllers.sort();
+        updated_controllers.dedup();
+
+        self.set_controllers(canister_id, updated_controllers).await
+    }
+
+    /// Removes the controllers from the current controllers of the canister.
+    pub async fn remove_controllers(
+        &self,
+        canister_id: Principal,
+        controllers: Vec<Principal>,
+    ) -> ServiceResult<()> {
+        let mut updated_controllers = self
+            .external_canister_service
+            .canister_controllers(canister_id)
+            .await?;
+        updated_controllers.retain(|controller| !controllers.contains(controller));
+
+        self.set_controllers(canister_id, updated_controllers).await
+    }
+
+    /// Sets the controllers of the canister, the station must stay one of them.
+    async fn set_controllers(
+        &self,
+        canister_id: Principal,
+        controllers: Vec<Principal>,
+    ) -> ServiceResult<()> {
+        // the current controllers are only known at execution, so the lockout check is repeated
+        validate_canister_controllers(&controllers, false).map_err(|err| {
+            ExternalCanisterError::ValidationError {
+                info: err.to_string(),
+            }
+        })?;
+
+        self.external_canister_service
+            .change_canister_ic_settings(canister_id, controllers_settings(controllers.clone()))
+            .await?;
+
+        self.record_controllers(&canister_id, controllers);
+
+        Ok(())
+    }
+
+    /// Hands over the canister to the given controllers, the station is no longer a controller afterwards.
+    ///
+    /// Since the station can no longer manage the canister, its cycles are no longer monitored
+    /// and its external canister entry is archived.
+    pub async fn release_control(
+        &self,
+        canister_id: Principal,
+        controllers: Vec<Principal>,
+    ) -> ServiceResult<()> {
+        self.external_canister_service
+            .change_canister_ic_settings(canister_id, controllers_settings(controllers))
+            .await?;
+
+        if let Ok(external_canister) = self
+            .external_canister_service
+            .get_external_canister_by_canister_id(&canister_id)
+        {
+            if external_canister.monitoring.is_some() {
+                self.external_canister_service
+                    .canister_monitor_stop(canister_id)?;
+            }
+
+            self.external_canister_service.edit_external_canister(
+                &external_canister.id,
+                ConfigureExternalCanisterSettingsInput {
+                    state: Some(ExternalCanisterState::Archived),
+                    ..Default::default()
+                },
+            )?;
+
+            self.external_canister_controllers_repository
+                .remove(&external_canister.id);
+        }
+
+        Ok(())
+    }
+
+    /// Compares the controllers of the active external canisters with the known ones and notifies
+    /// the admins about the changes made outside of the station.
+    ///
+    /// The first time the controllers of a canister are fetched they are recorded as the known ones.
+    pub async fn reconcile_controllers(&self) {
+        for external_canister in self.find_active_canisters() {
+            let fetch_started_at = next_time();
+            let controllers = match self
+                .external_canister_service
+                .canister_controllers(external_canister.canister_id)
+                .await
+            {
+                Ok(controllers) => controllers,
+                Err(err) => {
+                    print(format!(
+                        "Failed to fetch the controllers of canister {}: {}",
+                        external_canister.canister_id, err
+                    ));
+
+                    continue;
+                }
+            };
+
+            // the canister might have been removed while awaiting
+            if self
+                .external_canister_repository
+                .find_by_canister_id(&external_canister.canister_id)
+                .is_none()
+            {
+                continue;
+            }
+
+            let known = self
+                .external_canister_controllers_repository
+                .get(&external_canister.id);
+
+            // the station changed the controllers while awaiting, so the fetched ones are outdated
+            if known
+                .as_ref()
+                .is_some_and(|known| known.updated_at >= fetch_started_at)
+            {
+                continue;
+            }
+
+            let observed =
+                ExternalCanisterControllers::new(external_canister.id, controllers, next_time());
+            let drift = known.and_then(|known| known.drift(&observed.controllers));
+
+            self.external_canister_controllers_repository
+                .insert(external_canister.id, observed);
+
+            if let Some(drift) = drift {
+                self.notify_drift(&external_canister, &drift).await;
+            }
+        }
+    }
+
+    async fn notify_drift(
+        &self,
+        external_canister: &ExternalCanister,
+        drift: &ExternalCanisterControllersDrift,
+    ) {
+        let format_principals = |principals: &[Principal]| {
+            principals
+                .iter()
+                .map(|principal| principal.to_text())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let mut message = Vec::new();
+        if drift.removed.contains(&crate::core::ic_cdk::api::id()) {
+            message.push("The station is no longer a controller of the canister.".to_string());
+        }
+        if !drift.added.is_empty() {
+            message.push(format!("Added: {}.", format_principals(&drift.added)));
+        }
+        if !drift.removed.is_empty() {
+            message.push(format!("Removed: {}.", format_principals(&drift.removed)));
+        }
+
+        let title = format!(
+            "Controllers of {} ({}) changed outside of the station",
+            external_canister.name, external_canister.canister_id
+        );
+
+        for admin in self
+            .user_repository
+            .find_by_group_and_status(ADMIN_GROUP_ID, &UserStatus::Active)
+        {
+            self.notification_service
+                .send_notification(
+                    admin.id,
+                    NotificationType::SystemMessage,
+                    title.clone(),
+                    Some(message.join(" ")),
+                )
+                .await;
+        }
+    }
+}
+
+fn controllers_settings(controllers: Vec<Principal>) -> DefiniteCanisterSettingsInput {
+    DefiniteCanisterSettingsInput {
+        controllers: Some(controllers),
+        compute_allocation: None,
+        memory_allocation: None,
+        freezing_threshold: None,
+        reserved_cycles_limit: None,
+        log_visibility: None,
+        wasm_memory_limit: None,
+    }
+}
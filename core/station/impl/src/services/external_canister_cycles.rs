@@ -0,0 +1,147 @@
+use crate::{
+    core::ic_cdk::next_time,
+    errors::ExternalCanisterError,
+    models::{
+        ExternalCanister, ExternalCanisterCyclesForecast, ExternalCanisterCyclesSample,
+        ExternalCanisterCyclesSampleKey,
+    },
+    repositories::{
+        ExternalCanisterCyclesRepository, ExternalCanisterRepository,
+        EXTERNAL_CANISTER_CYCLES_REPOSITORY, EXTERNAL_CANISTER_REPOSITORY,
+    },
+    services::{CycleManager, CYCLE_MANAGER},
+};
+use candid::Principal;
+use lazy_static::lazy_static;
+use orbit_essentials::{api::ServiceResult, types::Timestamp};
+use std::sync::Arc;
+
+lazy_static! {
+    pub static ref EXTERNAL_CANISTER_CYCLES_SERVICE: Arc<ExternalCanisterCyclesService> =
+        Arc::new(ExternalCanisterCyclesService::new(
+            Arc::clone(&EXTERNAL_CANISTER_REPOSITORY),
+            Arc::clone(&EXTERNAL_CANISTER_CYCLES_REPOSITORY),
+            Arc::clone(&CYCLE_MANAGER),
+        ));
+}
+
+/// Keeps the cycles balance history of the monitored external canisters and forecasts their runway.
+#[derive(Default, Debug)]
+pub struct ExternalCanisterCyclesService {
+    external_canister_repository: Arc<ExternalCanisterRepository>,
+    external_canister_cycles_repository: Arc<ExternalCanisterCyclesRepository>,
+    cycle_manager: Arc<CycleManager>,
+}
+
+impl ExternalCanisterCyclesService {
+    pub fn new(
+        external_canister_repository: Arc<ExternalCanisterRepository>,
+        external_canister_cycles_repository: Arc<ExternalCanisterCyclesRepository>,
+        cycle_manager: Arc<CycleManager>,
+    ) -> Self {
+        Self {
+            external_canister_repository,
+            external_canister_cycles_repository,
+            cycle_manager,
+        }
+    }
+
+    fn find_monitored_canisters(&self) -> Vec<ExternalCanister> {
+        self.external_canister_repository
+            .find_all()
+            .into_iter()
+            .filter(|external_canister| external_canister.monitoring.is_some())
+            .collect()
+    }
+
+    /// Returns `true` if the cycles of any external canister are monitored.
+    pub fn has_monitored_canisters(&self) -> bool {
+        !self.find_monitored_canisters().is_empty()
+    }
+
+    /// Records the balances fetched by the cycle monitor since the latest samples of the monitored canisters.
+    pub fn record_samples(&self) {
+        for external_canister in self.find_monitored_canisters() {
+            let Some(record) = self
+                .cycle_manager
+                .get_canister(&external_canister.canister_id)
+            else {
+                continue;
+            };
+            let Some(balance) = record.get_cycles() else {
+                continue;
+            };
+
+            let is_new_balance = self
+                .external_canister_cycles_repository
+                .find_latest(&external_canister.id)
+                .map_or(true, |(sampled_at, _)| balance.timestamp > sampled_at);
+
+            if is_new_balance {
+                self.external_canister_cycles_repository.insert_sample(
+                    ExternalCanisterCyclesSampleKey {
+                        external_canister_id: external_canister.id,
+                        sampled_at: balance.timestamp,
+                    },
+                    ExternalCanisterCyclesSample {
+                        cycles: saturating_u64(balance.amount),
+                        deposited_cycles: record
+                            .get_deposited_cycles()
+                            .as_ref()
+                            .map_or(0, |deposited| saturating_u64(deposited.amount)),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Returns the cycles forecast of the canister computed from its samples within the given time range,
+    /// by default the samples of the last week are used.
+    pub fn get_cycles_forecast(
+        &self,
+        canister_id: &Principal,
+        from_dt: Option<Timestamp>,
+        to_dt: Option<Timestamp>,
+    ) -> ServiceResult<ExternalCanisterCyclesForecast> {
+        let external_canister_id = self
+            .external_canister_repository
+            .find_by_canister_id(canister_id)
+            .ok_or(ExternalCanisterError::InvalidExternalCanister {
+                principal: *canister_id,
+            })?;
+
+        let from_dt = from_dt.unwrap_or_else(|| {
+            to_dt
+                .unwrap_or_else(next_time)
+                .saturating_sub(ExternalCanisterCyclesForecast::DEFAULT_WINDOW_NS)
+        });
+        let samples = self
+            .external_canister_cycles_repository
+            .find_by_external_canister_id(&external_canister_id, Some(from_dt), to_dt);
+
+        Ok(ExternalCanisterCyclesForecast::from_samples(&samples))
+    }
+
+    /// Returns the cycles forecast of every monitored canister over the default time window.
+    pub fn list_cycles_forecasts(&self) -> Vec<(ExternalCanister, ExternalCanisterCyclesForecast)> {
+        let from_dt = next_time().saturating_sub(ExternalCanisterCyclesForecast::DEFAULT_WINDOW_NS);
+
+        self.find_monitored_canisters()
+            .into_iter()
+            .map(|external_canister| {
+                let samples = self
+                    .external_canister_cycles_repository
+                    .find_by_external_canister_id(&external_canister.id, Some(from_dt), None);
+
+                (
+                    external_canister,
+                    ExternalCanisterCyclesForecast::from_samples(&samples),
+                )
+            })
+            .collect()
+    }
+}
+
+fn saturating_u64(amount: u128) -> u64 {
+    amount.min(u64::MAX as u128) as u64
+}
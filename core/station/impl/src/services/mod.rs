@@ -21,6 +21,12 @@ pub use user_group::*;
 mod request;
 pub use request::*;
 
+mod request_archive;
+pub use request_archive::*;
+
+mod request_snapshot;
+pub use request_snapshot::*;
+
 mod system;
 pub use system::*;
 
@@ -33,6 +39,18 @@ pub use change_canister::*;
 mod external_canister;
 pub use external_canister::*;
 
+mod external_canister_backup;
+pub use external_canister_backup::*;
+
+mod external_canister_controllers;
+pub use external_canister_controllers::*;
+
+mod external_canister_cycles;
+pub use external_canister_cycles::*;
+
+mod wasm_module_registry;
+pub use wasm_module_registry::*;
+
 pub mod permission;
 
 mod cycle_manager;
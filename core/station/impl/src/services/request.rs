@@ -2,23 +2,27 @@ use crate::{
     core::{
         authorization::Authorization,
         ic_cdk::next_time,
-        utils::{paginated_items, retain_accessible_resources, PaginatedData, PaginatedItemsArgs},
+        utils::{paginated_items, retain_accessible_resources, PaginatedItemsArgs},
         CallContext,
     },
     errors::{RequestError, RequestExecuteError},
     factories::requests::{RequestExecuteStage, RequestFactory},
     mappers::HelperMapper,
     models::{
+        indexes::request_resource_index::RequestResourceFilter,
         resource::{RequestResourceAction, Resource, ResourceId},
-        DisplayUser, NotificationType, Request, RequestAdditionalInfo, RequestApprovalStatus,
-        RequestCallerPrivileges, RequestCreatedNotification, RequestRejectedNotification,
-        RequestStatus, RequestStatusCode,
+        ArchivedRequest, DisplayUser, NotificationType, Request, RequestAdditionalInfo,
+        RequestApprovalStatus, RequestCallerPrivileges, RequestCreatedNotification, RequestDiff,
+        RequestRejectedNotification, RequestStatus, RequestStatusCode, RequestsPage,
     },
     repositories::{
         EvaluationResultRepository, RequestRepository, RequestWhereClause,
         REQUEST_EVALUATION_RESULT_REPOSITORY, REQUEST_REPOSITORY,
     },
-    services::{NotificationService, UserService, NOTIFICATION_SERVICE, USER_SERVICE},
+    services::{
+        NotificationService, RequestArchiveService, RequestSnapshotService, UserService,
+        NOTIFICATION_SERVICE, REQUEST_ARCHIVE_SERVICE, REQUEST_SNAPSHOT_SERVICE, USER_SERVICE,
+    },
 };
 use ic_cdk::print;
 use lazy_static::lazy_static;
@@ -38,6 +42,8 @@ lazy_static! {
         Arc::clone(&REQUEST_REPOSITORY),
         Arc::clone(&NOTIFICATION_SERVICE),
         Arc::clone(&REQUEST_EVALUATION_RESULT_REPOSITORY),
+        Arc::clone(&REQUEST_ARCHIVE_SERVICE),
+        Arc::clone(&REQUEST_SNAPSHOT_SERVICE),
     ));
 }
 
@@ -47,6 +53,8 @@ pub struct RequestService {
     request_repository: Arc<RequestRepository>,
     evaluation_result_repository: Arc<EvaluationResultRepository>,
     notification_service: Arc<NotificationService>,
+    request_archive_service: Arc<RequestArchiveService>,
+    request_snapshot_service: Arc<RequestSnapshotService>,
 }
 
 #[derive(Debug)]
@@ -64,12 +72,16 @@ impl RequestService {
         request_repository: Arc<RequestRepository>,
         notification_service: Arc<NotificationService>,
         evaluation_result_repository: Arc<EvaluationResultRepository>,
+        request_archive_service: Arc<RequestArchiveService>,
+        request_snapshot_service: Arc<RequestSnapshotService>,
     ) -> Self {
         Self {
             user_service,
             request_repository,
             notification_service,
             evaluation_result_repository,
+            request_archive_service,
+            request_snapshot_service,
         }
     }
 
@@ -84,6 +96,11 @@ impl RequestService {
         Ok(request)
     }
 
+    /// Returns the field level diff of the entity that the request edits, if any.
+    pub fn get_request_diff(&self, request: &Request) -> Option<RequestDiff> {
+        self.request_snapshot_service.get_request_diff(request)
+    }
+
     pub async fn get_caller_privileges_for_request(
         &self,
         request_id: &UUID,
@@ -146,7 +163,7 @@ impl RequestService {
         &self,
         input: ListRequestsInput,
         ctx: &CallContext,
-    ) -> ServiceResult<PaginatedData<Request>> {
+    ) -> ServiceResult<RequestsPage> {
         let filter_by_requesters = input
             .requester_ids
             .map(|ids| {
@@ -167,6 +184,16 @@ impl RequestService {
             })
             .transpose()?;
 
+        let filter_by_resources = input
+            .resources
+            .map(|resources| {
+                resources
+                    .into_iter()
+                    .map(RequestResourceFilter::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
         let filter_by_votable = if input.only_approvable {
             let user = self.user_service.get_user_by_identity(&ctx.caller())?;
             vec![user.id]
@@ -183,40 +210,43 @@ impl RequestService {
             statuses.push(RequestStatusCode::Created);
         }
 
-        let mut request_ids = self.request_repository.find_ids_where(
-            RequestWhereClause {
-                created_dt_from: input
-                    .created_from_dt
-                    .map(|dt| rfc3339_to_timestamp(dt.as_str())),
-                created_dt_to: input
-                    .created_to_dt
-                    .map(|dt| rfc3339_to_timestamp(dt.as_str())),
-                expiration_dt_from: input
-                    .expiration_from_dt
-                    .map(|dt| rfc3339_to_timestamp(dt.as_str())),
-                expiration_dt_to: input
-                    .expiration_to_dt
-                    .map(|dt| rfc3339_to_timestamp(dt.as_str())),
-                operation_types: input
-                    .operation_types
-                    .map(|types| {
-                        types
-                            .into_iter()
-                            .map(|operation_type| operation_type.into())
-                            .collect::<_>()
-                    })
-                    .unwrap_or_default(),
-                statuses,
-                requesters: filter_by_requesters.unwrap_or_default(),
-                approvers: filter_by_approvers.unwrap_or_default(),
-                not_approvers: filter_by_votable.clone(),
-                not_requesters: filter_by_votable,
-                excluded_ids: vec![],
-                deduplication_keys: input.deduplication_keys.unwrap_or_default(),
-                tags: input.tags.unwrap_or_default(),
-            },
-            input.sort_by,
-        )?;
+        let condition = RequestWhereClause {
+            created_dt_from: input
+                .created_from_dt
+                .map(|dt| rfc3339_to_timestamp(dt.as_str())),
+            created_dt_to: input
+                .created_to_dt
+                .map(|dt| rfc3339_to_timestamp(dt.as_str())),
+            expiration_dt_from: input
+                .expiration_from_dt
+                .map(|dt| rfc3339_to_timestamp(dt.as_str())),
+            expiration_dt_to: input
+                .expiration_to_dt
+                .map(|dt| rfc3339_to_timestamp(dt.as_str())),
+            operation_types: input
+                .operation_types
+                .map(|types| {
+                    types
+                        .into_iter()
+                        .map(|operation_type| operation_type.into())
+                        .collect::<_>()
+                })
+                .unwrap_or_default(),
+            statuses,
+            requesters: filter_by_requesters.unwrap_or_default(),
+            approvers: filter_by_approvers.unwrap_or_default(),
+            not_approvers: filter_by_votable.clone(),
+            not_requesters: filter_by_votable,
+            excluded_ids: vec![],
+            deduplication_keys: input.deduplication_keys.unwrap_or_default(),
+            tags: input.tags.unwrap_or_default(),
+            search_term: input.search_term,
+            resources: filter_by_resources.unwrap_or_default(),
+        };
+
+        let mut request_ids = self
+            .request_repository
+            .find_ids_where(condition.clone(), input.sort_by)?;
 
         // filter out requests that the caller does not have access to read
         retain_accessible_resources(ctx, &mut request_ids, |id| {
@@ -226,35 +256,108 @@ impl RequestService {
         // users have access to a request if they can approve it, or have already send their approval to it,
         // to see if a user can approve a request no further filtering is necessary
 
+        let offset = input.paginate.as_ref().and_then(|p| p.offset).unwrap_or(0);
+        let limit = input.paginate.and_then(|p| p.limit);
         let paginated_ids = paginated_items(PaginatedItemsArgs {
-            offset: input.paginate.to_owned().and_then(|p| p.offset),
-            limit: input.paginate.and_then(|p| p.limit),
+            offset: Some(offset),
+            limit,
             default_limit: Some(Self::DEFAULT_REQUEST_LIST_LIMIT),
             max_limit: Some(Self::MAX_REQUEST_LIST_LIMIT),
             items: &request_ids,
         })?;
+        let page_len = paginated_ids.items.len();
 
-        Ok(PaginatedData {
-            total: paginated_ids.total,
-            next_offset: paginated_ids.next_offset,
-            items: paginated_ids
-                .items
-                .into_iter()
-                .flat_map(|id| match self.get_request(&id) {
-                    Ok(request) => Some(request),
-                    Err(error) => {
-                        print(format!(
-                            "Failed to get request {}: {:?}",
-                            Uuid::from_bytes(id.to_owned()).hyphenated(),
-                            error
-                        ));
-                        None
-                    }
-                })
-                .collect::<Vec<Request>>(),
+        let items = paginated_ids
+            .items
+            .into_iter()
+            .flat_map(|id| match self.get_request(&id) {
+                Ok(request) => Some(request),
+                Err(error) => {
+                    print(format!(
+                        "Failed to get request {}: {:?}",
+                        Uuid::from_bytes(id.to_owned()).hyphenated(),
+                        error
+                    ));
+                    None
+                }
+            })
+            .collect::<Vec<Request>>();
+
+        // archived requests are finalized, hence they can't be approved anymore
+        let lists_archive = !input.only_approvable
+            && self
+                .request_archive_service
+                .get_archive_canister_id()
+                .is_some();
+
+        if paginated_ids.next_offset.is_some() || !lists_archive {
+            return Ok(RequestsPage {
+                items,
+                archived_items: Vec::new(),
+                next_offset: paginated_ids.next_offset,
+                total: paginated_ids.total,
+            });
+        }
+
+        // once the requests kept in the station are exhausted the page continues with the archived requests,
+        // the offsets past the total are positions in the archive
+        let total = paginated_ids.total;
+        let remaining =
+            (limit.unwrap_or(Self::DEFAULT_REQUEST_LIST_LIMIT) as usize).saturating_sub(page_len);
+
+        if remaining == 0 {
+            return Ok(RequestsPage {
+                items,
+                archived_items: Vec::new(),
+                next_offset: Some(total),
+                total,
+            });
+        }
+
+        let archived_page = self
+            .request_archive_service
+            .list_archived_requests(
+                &condition,
+                Some(offset.saturating_sub(total)),
+                remaining,
+                |request| Authorization::is_allowed_to_read_archived_request(ctx, request),
+            )
+            .await?;
+
+        Ok(RequestsPage {
+            items,
+            archived_items: archived_page.items,
+            next_offset: archived_page.next_cursor.map(|cursor| total + cursor),
+            total,
         })
     }
 
+    /// Gets an archived request by its id, returns `None` if the request is not in the archive.
+    pub async fn find_archived_request(
+        &self,
+        request_id: &UUID,
+    ) -> ServiceResult<Option<ArchivedRequest>> {
+        self.request_archive_service
+            .find_archived_request(request_id)
+            .await
+    }
+
+    /// Gets the additional info of an archived request, the evaluation result is the one stored with it.
+    pub fn get_archived_request_additional_info(
+        &self,
+        archived_request: &ArchivedRequest,
+        with_evaluation_results: bool,
+    ) -> ServiceResult<RequestAdditionalInfo> {
+        let mut additional_info =
+            self.get_request_additional_info(&archived_request.request, false)?;
+
+        if with_evaluation_results {
+            additional_info.evaluation_result = archived_request.evaluation_result.clone();
+        }
+
+        Ok(additional_info)
+    }
+
     pub async fn get_next_approvable_request(
         &self,
         input: GetNextApprovableRequestInput,
@@ -297,6 +400,8 @@ impl RequestService {
                 excluded_ids: exclude_request_ids,
                 deduplication_keys: vec![],
                 tags: vec![],
+                search_term: None,
+                resources: vec![],
             },
             input.sort_by,
         )?;
@@ -337,6 +442,10 @@ impl RequestService {
         self.request_repository
             .insert(request.to_key(), request.to_owned());
 
+        // Captures the edited entity as it is now, so that approvers can review what the request changes.
+        self.request_snapshot_service
+            .capture_creation_snapshot(&request);
+
         if request.can_approve(&requester.id) {
             request.add_approval(requester.id, RequestApprovalStatus::Approved, None)?;
         }
@@ -559,6 +668,11 @@ impl RequestService {
         self.request_repository
             .save_modified(&mut request, request_execution_time);
 
+        if let RequestStatus::Completed { .. } = request.status {
+            self.request_snapshot_service
+                .capture_execution_snapshot(&request);
+        }
+
         Ok(())
     }
 }
@@ -1032,6 +1146,8 @@ mod tests {
             statuses: None,
             deduplication_keys: None,
             tags: None,
+            search_term: None,
+            resources: None,
         };
 
         let users = vec![requester, approver, another_user];
@@ -1104,6 +1220,8 @@ mod tests {
                     with_evaluation_results: false,
                     deduplication_keys: None,
                     tags: None,
+                    search_term: None,
+                    resources: None,
                 },
                 &ctx.call_context,
             )
@@ -1241,6 +1359,8 @@ mod tests {
                     with_evaluation_results: false,
                     deduplication_keys: None,
                     tags: None,
+                    search_term: None,
+                    resources: None,
                 },
                 &ctx.call_context,
             )
@@ -1268,6 +1388,8 @@ mod tests {
                     with_evaluation_results: false,
                     deduplication_keys: None,
                     tags: None,
+                    search_term: None,
+                    resources: None,
                 },
                 &CallContext::new(transfer_requester_user.identities[0]),
             )
@@ -1294,6 +1416,8 @@ mod tests {
                     with_evaluation_results: false,
                     deduplication_keys: None,
                     tags: None,
+                    search_term: None,
+                    resources: None,
                 },
                 &CallContext::new(no_access_user.identities[0]),
             )
@@ -1337,6 +1461,8 @@ mod tests {
                     with_evaluation_results: false,
                     deduplication_keys: None,
                     tags: None,
+                    search_term: None,
+                    resources: None,
                 },
                 &ctx.call_context,
             )
@@ -1483,6 +1609,8 @@ mod tests {
                     with_evaluation_results: false,
                     deduplication_keys: None,
                     tags: None,
+                    search_term: None,
+                    resources: None,
                 },
                 &CallContext::new(user_2.identities[0]),
             )
@@ -1608,6 +1736,8 @@ mod tests {
                     only_approvable: false,
                     with_evaluation_results: false,
                     tags: None,
+                    search_term: None,
+                    resources: None,
                 },
                 &CallContext::new(user_2.identities[0]),
             )
@@ -1718,6 +1848,8 @@ mod benchs {
                             with_evaluation_results: false,
                             deduplication_keys: None,
                             tags: None,
+                            search_term: None,
+                            resources: None,
                         },
                         &CallContext::new(Principal::from_slice(&[5; 29])),
                     )
@@ -1765,6 +1897,8 @@ mod benchs {
                             with_evaluation_results: false,
                             deduplication_keys: None,
                             tags: None,
+                            search_term: None,
+                            resources: None,
                         },
                         &CallContext::new(Principal::from_slice(&[5; 29])),
                     )
@@ -0,0 +1,438 @@
+use crate::{
+    core::{
+        ic_cdk::{api::id as self_canister_id, next_time},
+        read_system_info, DEFAULT_INITIAL_REQUEST_ARCHIVE_CYCLES, REQUEST_ARCHIVE_BATCH_SIZE,
+    },
+    errors::RequestError,
+    models::{
+        indexes::search_index::matches_search_term, ArchivedRequest, ArchivedRequestsPage,
+        DeployRequestArchiveInput, Request, RequestEvaluationResult, RequestId,
+    },
+    repositories::{
+        EvaluationResultRepository, RequestRepository, RequestSnapshotRepository,
+        RequestWhereClause, REQUEST_EVALUATION_RESULT_REPOSITORY, REQUEST_REPOSITORY,
+        REQUEST_SNAPSHOT_REPOSITORY,
+    },
+    services::{system_canister_fund_strategy, CYCLE_MANAGER},
+};
+use archive_api::{
+    AppendRequestsInput, ArchivedRequestDTO, GetRequestsInput, GetRequestsResponse,
+    ListRequestsInput as ListArchivedRequestsInput,
+    ListRequestsResponse as ListArchivedRequestsResponse,
+};
+use candid::{Encode, Principal};
+use ic_cdk::api::management_canister::main::{self as mgmt};
+use ic_stable_structures::Storable;
+use lazy_static::lazy_static;
+use orbit_essentials::{api::ServiceResult, repository::Repository};
+use std::{borrow::Cow, collections::HashSet, sync::Arc};
+use uuid::Uuid;
+
+lazy_static! {
+    pub static ref REQUEST_ARCHIVE_SERVICE: Arc<RequestArchiveService> =
+        Arc::new(RequestArchiveService::new(
+            Arc::clone(&REQUEST_REPOSITORY),
+            Arc::clone(&REQUEST_EVALUATION_RESULT_REPOSITORY),
+            Arc::clone(&REQUEST_SNAPSHOT_REPOSITORY),
+        ));
+}
+
+/// Moves finalized requests to the request archive canister and serves them back once archived.
+#[derive(Default, Debug)]
+pub struct RequestArchiveService {
+    request_repository: Arc<RequestRepository>,
+    evaluation_result_repository: Arc<EvaluationResultRepository>,
+    request_snapshot_repository: Arc<RequestSnapshotRepository>,
+}
+
+impl RequestArchiveService {
+    /// The maximum number of archived requests that are scanned when listing requests, this
+    /// bounds the number of calls made to the archive canister within a single query.
+    const MAX_LIST_SCAN: u64 = 1_000;
+
+    /// The page size used when listing requests from the archive canister.
+    const LIST_PAGE_SIZE: u16 = 100;
+
+    pub fn new(
+        request_repository: Arc<RequestRepository>,
+        evaluation_result_repository: Arc<EvaluationResultRepository>,
+        request_snapshot_repository: Arc<RequestSnapshotRepository>,
+    ) -> Self {
+        Self {
+            request_repository,
+            evaluation_result_repository,
+            request_snapshot_repository,
+        }
+    }
+
+    /// Returns the archive canister id if the station has a request archive.
+    pub fn get_archive_canister_id(&self) -> Option<Principal> {
+        read_system_info()
+            .get_request_archive()
+            .map(|archive| archive.canister_id)
+    }
+
+    /// Creates the archive canister with the station as its only controller and installs the archive wasm module.
+    pub async fn deploy_archive(
+        &self,
+        input: &DeployRequestArchiveInput,
+    ) -> Result<Principal, String> {
+        let (archive_canister,) = mgmt::create_canister(
+            mgmt::CreateCanisterArgument {
+                settings: Some(mgmt::CanisterSettings {
+                    controllers: Some(vec![self_canister_id()]),
+                    ..Default::default()
+                }),
+            },
+            input
+                .initial_cycles
+                .unwrap_or(DEFAULT_INITIAL_REQUEST_ARCHIVE_CYCLES),
+        )
+        .await
+        .map_err(|e| format!("Failed to create request archive canister: {e:?}"))?;
+
+        mgmt::install_code(mgmt::InstallCodeArgument {
+            mode: mgmt::CanisterInstallMode::Install,
+            canister_id: archive_canister.canister_id,
+            wasm_module: input.wasm_module.clone(),
+            arg: Encode!(&archive_api::InitArg {
+                station_id: self_canister_id(),
+            })
+            .map_err(|e| format!("Failed to encode request archive init arg: {e}"))?,
+        })
+        .await
+        .map_err(|e| format!("Failed to install request archive canister: {e:?}"))?;
+
+        CYCLE_MANAGER.add_canister(
+            archive_canister.canister_id,
+            system_canister_fund_strategy(),
+            None,
+        );
+
+        Ok(archive_canister.canister_id)
+    }
+
+    /// Upgrades the archive canister to the provided wasm module, the archived requests are kept in its stable memory.
+    pub async fn upgrade_archive(
+        &self,
+        archive_canister_id: Principal,
+        input: &DeployRequestArchiveInput,
+    ) -> Result<(), String> {
+        mgmt::install_code(mgmt::InstallCodeArgument {
+            mode: mgmt::CanisterInstallMode::Upgrade(None),
+            canister_id: archive_canister_id,
+            wasm_module: input.wasm_module.clone(),
+            arg: Encode!(&())
+                .map_err(|e| format!("Failed to encode request archive upgrade arg: {e}"))?,
+        })
+        .await
+        .map_err(|e| format!("Failed to upgrade request archive canister: {e:?}"))
+    }
+
+    /// Gets an archived request by its id, returns `None` if the station has no archive or the request is not archived.
+    pub async fn find_archived_request(
+        &self,
+        request_id: &RequestId,
+    ) -> ServiceResult<Option<ArchivedRequest>> {
+        let Some(archive_id) = self.get_archive_canister_id() else {
+            return Ok(None);
+        };
+
+        let (response,) = ic_cdk::call::<_, (GetRequestsResponse,)>(
+            archive_id,
+            "get_requests",
+            (GetRequestsInput {
+                request_ids: vec![Uuid::from_bytes(*request_id).hyphenated().to_string()],
+            },),
+        )
+        .await
+        .map_err(|(_, err)| RequestError::ArchiveUnavailable { reason: err })?;
+
+        Ok(response.requests.into_iter().next().map(Self::from_dto))
+    }
+
+    /// Lists the archived requests that match the provided condition and are visible to the caller,
+    /// in the order they were created, starting from the provided position in the archive.
+    ///
+    /// At most `MAX_LIST_SCAN` archived requests are scanned per call to bound the number of calls made
+    /// to the archive canister, hence fewer requests than the limit can be returned while there is still
+    /// a `next_cursor` to continue from.
+    pub async fn list_archived_requests(
+        &self,
+        condition: &RequestWhereClause,
+        cursor: Option<u64>,
+        limit: usize,
+        is_visible: impl Fn(&Request) -> bool,
+    ) -> ServiceResult<ArchivedRequestsPage> {
+        let Some(archive_id) = self.get_archive_canister_id() else {
+            return Ok(ArchivedRequestsPage {
+                items: Vec::new(),
+                next_cursor: None,
+            });
+        };
+
+        let mut position = cursor.unwrap_or(0);
+        let mut scanned = 0;
+        let mut items = Vec::new();
+        loop {
+            let (response,) = ic_cdk::call::<_, (ListArchivedRequestsResponse,)>(
+                archive_id,
+                "list_requests",
+                (ListArchivedRequestsInput {
+                    created_from: condition.created_dt_from,
+                    created_to: condition.created_dt_to,
+                    offset: Some(position),
+                    limit: Some(Self::LIST_PAGE_SIZE),
+                },),
+            )
+            .await
+            .map_err(|(_, err)| RequestError::ArchiveUnavailable { reason: err })?;
+
+            let page_len = response.requests.len() as u64;
+            let (matching, consumed) = Self::take_matching(
+                response.requests.into_iter().map(Self::from_dto).collect(),
+                condition,
+                limit - items.len(),
+                &is_visible,
+            );
+
+            items.extend(matching);
+            position += consumed;
+            scanned += consumed;
+
+            if consumed == page_len && response.next_offset.is_none() {
+                return Ok(ArchivedRequestsPage {
+                    items,
+                    next_cursor: None,
+                });
+            }
+
+            if items.len() >= limit || scanned >= Self::MAX_LIST_SCAN {
+                return Ok(ArchivedRequestsPage {
+                    items,
+                    next_cursor: Some(position),
+                });
+            }
+        }
+    }
+
+    /// Takes the archived requests that match the condition and are visible to the caller, up to the limit.
+    ///
+    /// Returns the matching requests together with the number of requests that were consumed from the page,
+    /// the requests after the one that reached the limit are left for the next page.
+    fn take_matching(
+        requests: Vec<ArchivedRequest>,
+        condition: &RequestWhereClause,
+        limit: usize,
+        is_visible: &impl Fn(&Request) -> bool,
+    ) -> (Vec<ArchivedRequest>, u64) {
+        // the archive has no indexes, hence the condition is evaluated against each request
+        let entries = requests
+            .iter()
+            .filter(|entry| match &condition.search_term {
+                Some(search_term) => {
+                    matches_search_term(&entry.request.to_search_index(), search_term)
+                }
+                None => true,
+            })
+            .filter(|entry| {
+                condition.resources.is_empty()
+                    || entry
+                        .request
+                        .operation
+                        .to_resources()
+                        .iter()
+                        .any(|resource| {
+                            condition
+                                .resources
+                                .iter()
+                                .any(|filter| filter.matches(resource))
+                        })
+            })
+            .map(|entry| (entry.request.id, entry.request.index_fields()))
+            .collect::<Vec<_>>();
+
+        let matching_ids = RequestRepository::filter_and_sort_entries(entries, condition, &None)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<HashSet<_>>();
+
+        let mut matching = Vec::new();
+        let mut consumed = 0;
+        for entry in requests {
+            if matching.len() >= limit {
+                break;
+            }
+
+            consumed += 1;
+            if matching_ids.contains(&entry.request.id) && is_visible(&entry.request) {
+                matching.push(entry);
+            }
+        }
+
+        (matching, consumed)
+    }
+
+    /// Moves a batch of finalized requests older than the configured minimum age to the archive canister.
+    ///
+    /// Returns `true` if there are no more requests to archive at this time.
+    pub async fn archive_requests(&self) -> Result<bool, String> {
+        let Some(archive) = read_system_info().get_request_archive().cloned() else {
+            return Ok(true);
+        };
+
+        let Some(min_age_secs) = archive.min_age_secs else {
+            return Ok(true);
+        };
+
+        let archived_at = next_time();
+        let last_modified_before =
+            archived_at.saturating_sub(min_age_secs.saturating_mul(1_000_000_000));
+        let requests = self
+            .request_repository
+            .find_finalized_before(last_modified_before, REQUEST_ARCHIVE_BATCH_SIZE);
+
+        if requests.is_empty() {
+            return Ok(true);
+        }
+
+        let is_last_batch = requests.len() < REQUEST_ARCHIVE_BATCH_SIZE;
+        let entries = requests
+            .iter()
+            .map(|request| ArchivedRequestDTO {
+                request_id: Uuid::from_bytes(request.id).hyphenated().to_string(),
+                created_at: request.created_timestamp,
+                archived_at,
+                request: request.to_bytes().into_owned(),
+                evaluation_result: self
+                    .evaluation_result_repository
+                    .get(&request.id)
+                    .map(|evaluation| evaluation.to_bytes().into_owned()),
+            })
+            .collect::<Vec<_>>();
+
+        let (result,) = ic_cdk::call::<_, (Result<(), String>,)>(
+            archive.canister_id,
+            "append_requests",
+            (AppendRequestsInput { requests: entries },),
+        )
+        .await
+        .map_err(|(_, err)| format!("Failed to append requests to the archive: {err}"))?;
+
+        result?;
+
+        // the requests are only removed after the archive confirmed that they were stored,
+        // removing them from the repository also prunes the local indexes
+        for request in requests {
+            self.evaluation_result_repository.remove(&request.id);
+            self.request_snapshot_repository.remove(&request.id);
+            self.request_repository.remove(&request.to_key());
+        }
+
+        Ok(is_last_batch)
+    }
+
+    fn from_dto(dto: ArchivedRequestDTO) -> ArchivedRequest {
+        ArchivedRequest {
+            request: Request::from_bytes(Cow::Owned(dto.request)),
+            evaluation_result: dto
+                .evaluation_result
+                .map(|bytes| RequestEvaluationResult::from_bytes(Cow::Owned(bytes))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{request_test_utils::mock_request, RequestStatus, RequestStatusCode};
+
+    fn mock_archived_request(
+        title: &str,
+        requested_by: u8,
+        status: RequestStatus,
+    ) -> ArchivedRequest {
+        let mut request = mock_request();
+        request.title = title.to_string();
+        request.summary = None;
+        request.requested_by = [requested_by; 16];
+        request.status = status;
+
+        ArchivedRequest {
+            request,
+            evaluation_result: None,
+        }
+    }
+
+    #[test]
+    fn take_matching_filters_by_condition_and_visibility() {
+        let requests = vec![
+            mock_archived_request(
+                "upgrade frontend",
+                1,
+                RequestStatus::Completed { completed_at: 1 },
+            ),
+            mock_archived_request(
+                "upgrade backend",
+                2,
+                RequestStatus::Completed { completed_at: 1 },
+            ),
+            mock_archived_request("upgrade station", 1, RequestStatus::Rejected),
+            mock_archived_request(
+                "transfer funds",
+                1,
+                RequestStatus::Completed { completed_at: 1 },
+            ),
+        ];
+        let expected_id = requests[0].request.id;
+
+        let (matching, consumed) = RequestArchiveService::take_matching(
+            requests,
+            &RequestWhereClause {
+                statuses: vec![RequestStatusCode::Completed],
+                search_term: Some("upgr".to_string()),
+                ..Default::default()
+            },
+            10,
+            &|request: &Request| request.requested_by == [1; 16],
+        );
+
+        assert_eq!(consumed, 4);
+        assert_eq!(matching.len(), 1);
+        assert_eq!(matching[0].request.id, expected_id);
+    }
+
+    #[test]
+    fn take_matching_leaves_the_requests_after_the_limit_for_the_next_page() {
+        let requests = vec![
+            mock_archived_request("first", 1, RequestStatus::Rejected),
+            mock_archived_request("second", 1, RequestStatus::Completed { completed_at: 1 }),
+            mock_archived_request("third", 1, RequestStatus::Completed { completed_at: 1 }),
+            mock_archived_request("fourth", 1, RequestStatus::Completed { completed_at: 1 }),
+        ];
+        let ids = requests
+            .iter()
+            .map(|entry| entry.request.id)
+            .collect::<Vec<_>>();
+
+        let (matching, consumed) = RequestArchiveService::take_matching(
+            requests,
+            &RequestWhereClause {
+                statuses: vec![RequestStatusCode::Completed],
+                ..Default::default()
+            },
+            2,
+            &|_: &Request| true,
+        );
+
+        // the archive order is kept and the position is right after the last returned request
+        assert_eq!(consumed, 3);
+        assert_eq!(
+            matching
+                .iter()
+                .map(|entry| entry.request.id)
+                .collect::<Vec<_>>(),
+            vec![ids[1], ids[2]]
+        );
+    }
+}
@@ -0,0 +1,615 @@
+use crate::{
+    core::ic_cdk::next_time,
+    models::{
+        permission::{Allow, AuthScope},
+        request_policy_rule::{RequestPolicyRule, RequestPolicyRuleInput},
+        request_specifier::{RequestSpecifier, ResourceSpecifier, UserSpecifier},
+        resource::{AccountResourceAction, Resource, ResourceId, ResourceIds},
+        Account, CurrentEntity, EntitySnapshot, NamedRule, NamedRuleKey, Request, RequestDiff,
+        RequestOperation, RequestSnapshot, RequestStatus, User,
+    },
+    repositories::{
+        AccountRepository, NamedRuleRepository, RequestPolicyRepository, RequestSnapshotRepository,
+        UserRepository, ACCOUNT_REPOSITORY, NAMED_RULE_REPOSITORY, REQUEST_POLICY_REPOSITORY,
+        REQUEST_SNAPSHOT_REPOSITORY, USER_REPOSITORY,
+    },
+    services::permission::{PermissionService, PERMISSION_SERVICE},
+};
+use lazy_static::lazy_static;
+use orbit_essentials::{repository::Repository, types::UUID};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+use uuid::Uuid;
+
+lazy_static! {
+    pub static ref REQUEST_SNAPSHOT_SERVICE: Arc<RequestSnapshotService> =
+        Arc::new(RequestSnapshotService::new(
+            Arc::clone(&REQUEST_SNAPSHOT_REPOSITORY),
+            Arc::clone(&ACCOUNT_REPOSITORY),
+            Arc::clone(&USER_REPOSITORY),
+            Arc::clone(&REQUEST_POLICY_REPOSITORY),
+            Arc::clone(&NAMED_RULE_REPOSITORY),
+            Arc::clone(&PERMISSION_SERVICE),
+        ));
+}
+
+type EntityFields = BTreeMap<String, String>;
+
+/// Keeps track of the entities edited by requests, so that approvers can review what a request changes.
+#[derive(Default, Debug)]
+pub struct RequestSnapshotService {
+    request_snapshot_repository: Arc<RequestSnapshotRepository>,
+    account_repository: Arc<AccountRepository>,
+    user_repository: Arc<UserRepository>,
+    request_policy_repository: Arc<RequestPolicyRepository>,
+    named_rule_repository: Arc<NamedRuleRepository>,
+    permission_service: Arc<PermissionService>,
+}
+
+impl RequestSnapshotService {
+    pub fn new(
+        request_snapshot_repository: Arc<RequestSnapshotRepository>,
+        account_repository: Arc<AccountRepository>,
+        user_repository: Arc<UserRepository>,
+        request_policy_repository: Arc<RequestPolicyRepository>,
+        named_rule_repository: Arc<NamedRuleRepository>,
+        permission_service: Arc<PermissionService>,
+    ) -> Self {
+        Self {
+            request_snapshot_repository,
+            account_repository,
+            user_repository,
+            request_policy_repository,
+            named_rule_repository,
+            permission_service,
+        }
+    }
+
+    /// Takes the snapshot of the entity edited by the request together with the requested changes.
+    ///
+    /// Requests that don't edit an entity are ignored.
+    pub fn capture_creation_snapshot(&self, request: &Request) {
+        let Some(fields) = self.entity_fields(&request.operation) else {
+            return;
+        };
+
+        let requested = self.requested_fields(&request.operation, fields.clone());
+
+        self.request_snapshot_repository.insert(
+            request.id,
+            RequestSnapshot {
+                request_id: request.id,
+                at_creation: EntitySnapshot {
+                    fields,
+                    taken_at: next_time(),
+                },
+                requested,
+                after_execution: None,
+            },
+        );
+    }
+
+    /// Takes the snapshot of the edited entity after the request was executed.
+    pub fn capture_execution_snapshot(&self, request: &Request) {
+        let Some(mut snapshot) = self.request_snapshot_repository.get(&request.id) else {
+            return;
+        };
+
+        snapshot.after_execution = Some(EntitySnapshot {
+            fields: self.entity_fields(&request.operation).unwrap_or_default(),
+            taken_at: next_time(),
+        });
+
+        self.request_snapshot_repository
+            .insert(request.id, snapshot);
+    }
+
+    /// Returns the field level diff of the entity edited by the request, if a snapshot was taken.
+    pub fn get_request_diff(&self, request: &Request) -> Option<RequestDiff> {
+        let snapshot = self.request_snapshot_repository.get(&request.id)?;
+
+        // only requests that can still be executed can become stale
+        let current = match request.status {
+            RequestStatus::Created
+            | RequestStatus::Approved
+            | RequestStatus::Scheduled { .. }
+            | RequestStatus::Processing { .. } => self.entity_fields(&request.operation),
+            RequestStatus::Rejected
+            | RequestStatus::Cancelled { .. }
+            | RequestStatus::Completed { .. }
+            | RequestStatus::Failed { .. } => {
+                return Some(snapshot.diff(CurrentEntity::NotChecked))
+            }
+        };
+
+        Some(snapshot.diff(match &current {
+            Some(fields) => CurrentEntity::Fields(fields),
+            None => CurrentEntity::Removed,
+        }))
+    }
+
+    /// Returns the current fields of the entity edited by the operation, or `None` if the operation
+    /// doesn't edit an entity or the entity no longer exists.
+    fn entity_fields(&self, operation: &RequestOperation) -> Option<EntityFields> {
+        match operation {
+            RequestOperation::EditAccount(operation) => self
+                .account_repository
+                .get(&Account::key(operation.input.account_id))
+                .map(|account| self.account_fields(&account)),
+            RequestOperation::EditUser(operation) => self
+                .user_repository
+                .get(&User::key(operation.input.user_id))
+                .map(|user| user_fields(&user)),
+            RequestOperation::EditPermission(operation) => Some(allow_fields(
+                &self
+                    .permission_service
+                    .get_permission(&operation.input.resource)
+                    .allow,
+            )),
+            RequestOperation::EditRequestPolicy(operation) => self
+                .request_policy_repository
+                .get(&operation.input.policy_id)
+                .map(|policy| {
+                    EntityFields::from([
+                        ("specifier".to_string(), render_specifier(&policy.specifier)),
+                        ("rule".to_string(), render_rule(&policy.rule)),
+                    ])
+                }),
+            RequestOperation::EditNamedRule(operation) => self
+                .named_rule_repository
+                .get(&NamedRuleKey {
+                    id: operation.input.named_rule_id,
+                })
+                .map(|named_rule| named_rule_fields(&named_rule)),
+            _ => None,
+        }
+    }
+
+    /// Applies the changes of the operation to the fields of the entity at request creation.
+    fn requested_fields(
+        &self,
+        operation: &RequestOperation,
+        mut fields: EntityFields,
+    ) -> EntityFields {
+        match operation {
+            RequestOperation::EditAccount(operation) => {
+                let input = &operation.input;
+
+                if let Some(name) = &input.name {
+                    set_field(&mut fields, "name", Some(name.to_owned()));
+                }
+
+                if let (Some(change_assets), Some(mut account)) = (
+                    &input.change_assets,
+                    self.account_repository.get(&Account::key(input.account_id)),
+                ) {
+                    change_assets.apply(&mut account.assets);
+                    set_field(
+                        &mut fields,
+                        "assets",
+                        Some(render_ids(
+                            account.assets.iter().map(|asset| &asset.asset_id),
+                        )),
+                    );
+                }
+
+                for (field, permission) in [
+                    ("read_permission", &input.read_permission),
+                    ("configs_permission", &input.configs_permission),
+                    ("transfer_permission", &input.transfer_permission),
+                ] {
+                    if let Some(allow) = permission {
+                        set_field(&mut fields, field, Some(render_allow(allow)));
+                    }
+                }
+
+                for (field, policy) in [
+                    ("configs_request_policy", &input.configs_request_policy),
+                    ("transfer_request_policy", &input.transfer_request_policy),
+                ] {
+                    match policy {
+                        Some(RequestPolicyRuleInput::Set(rule)) => {
+                            set_field(&mut fields, field, Some(render_rule(rule)))
+                        }
+                        Some(RequestPolicyRuleInput::Remove) => set_field(&mut fields, field, None),
+                        None => {}
+                    }
+                }
+            }
+            RequestOperation::EditUser(operation) => {
+                let input = &operation.input;
+
+                if let Some(name) = &input.name {
+                    set_field(&mut fields, "name", Some(name.to_owned()));
+                }
+                if let Some(status) = &input.status {
+                    set_field(&mut fields, "status", Some(status.to_string()));
+                }
+                if let Some(identities) = &input.identities {
+                    set_field(
+                        &mut fields,
+                        "identities",
+                        Some(render_list(
+                            identities.iter().map(|identity| identity.to_text()),
+                        )),
+                    );
+                }
+                if let Some(groups) = &input.groups {
+                    set_field(&mut fields, "groups", Some(render_ids(groups)));
+                }
+            }
+            RequestOperation::EditPermission(operation) => {
+                let input = &operation.input;
+
+                if let Some(auth_scope) = &input.auth_scope {
+                    set_field(
+                        &mut fields,
+                        "auth_scope",
+                        Some(render_auth_scope(auth_scope).to_string()),
+                    );
+                }
+                if let Some(users) = &input.users {
+                    set_field(&mut fields, "users", Some(render_ids(users)));
+                }
+                if let Some(user_groups) = &input.user_groups {
+                    set_field(&mut fields, "user_groups", Some(render_ids(user_groups)));
+                }
+            }
+            RequestOperation::EditRequestPolicy(operation) => {
+                let input = &operation.input;
+
+                if let Some(specifier) = &input.specifier {
+                    set_field(&mut fields, "specifier", Some(render_specifier(specifier)));
+                }
+                if let Some(rule) = &input.rule {
+                    set_field(&mut fields, "rule", Some(render_rule(rule)));
+                }
+            }
+            RequestOperation::EditNamedRule(operation) => {
+                let input = &operation.input;
+
+                if let Some(name) = &input.name {
+                    set_field(&mut fields, "name", Some(name.to_owned()));
+                }
+                if let Some(description) = &input.description {
+                    set_field(&mut fields, "description", description.to_owned());
+                }
+                if let Some(rule) = &input.rule {
+                    set_field(&mut fields, "rule", Some(render_rule(rule)));
+                }
+            }
+            _ => {}
+        }
+
+        fields
+    }
+
+    fn account_fields(&self, account: &Account) -> EntityFields {
+        let mut fields = EntityFields::from([
+            ("name".to_string(), account.name.to_owned()),
+            (
+                "assets".to_string(),
+                render_ids(account.assets.iter().map(|asset| &asset.asset_id)),
+            ),
+        ]);
+
+        for (field, action) in [
+            (
+                "read_permission",
+                AccountResourceAction::Read(ResourceId::Id(account.id)),
+            ),
+            (
+                "configs_permission",
+                AccountResourceAction::Update(ResourceId::Id(account.id)),
+            ),
+            (
+                "transfer_permission",
+                AccountResourceAction::Transfer(ResourceId::Id(account.id)),
+            ),
+        ] {
+            let permission = self
+                .permission_service
+                .get_permission(&Resource::Account(action));
+
+            set_field(&mut fields, field, Some(render_allow(&permission.allow)));
+        }
+
+        for (field, policy_id) in [
+            ("configs_request_policy", account.configs_request_policy_id),
+            (
+                "transfer_request_policy",
+                account.transfer_request_policy_id,
+            ),
+        ] {
+            let rule = policy_id
+                .and_then(|policy_id| self.request_policy_repository.get(&policy_id))
+                .map(|policy| render_rule(&policy.rule));
+
+            set_field(&mut fields, field, rule);
+        }
+
+        fields
+    }
+}
+
+fn user_fields(user: &User) -> EntityFields {
+    EntityFields::from([
+        ("name".to_string(), user.name.to_owned()),
+        ("status".to_string(), user.status.to_string()),
+        (
+            "identities".to_string(),
+            render_list(user.identities.iter().map(|identity| identity.to_text())),
+        ),
+        ("groups".to_string(), render_ids(&user.groups)),
+    ])
+}
+
+fn allow_fields(allow: &Allow) -> EntityFields {
+    EntityFields::from([
+        (
+            "auth_scope".to_string(),
+            render_auth_scope(&allow.auth_scope).to_string(),
+        ),
+        ("users".to_string(), render_ids(&allow.users)),
+        ("user_groups".to_string(), render_ids(&allow.user_groups)),
+    ])
+}
+
+fn named_rule_fields(named_rule: &NamedRule) -> EntityFields {
+    let mut fields = EntityFields::from([
+        ("name".to_string(), named_rule.name.to_owned()),
+        ("rule".to_string(), render_rule(&named_rule.rule)),
+    ]);
+
+    set_field(
+        &mut fields,
+        "description",
+        named_rule.description.to_owned(),
+    );
+
+    fields
+}
+
+fn set_field(fields: &mut EntityFields, field: &str, value: Option<String>) {
+    match value {
+        Some(value) => {
+            fields.insert(field.to_string(), value);
+        }
+        None => {
+            fields.remove(field);
+        }
+    }
+}
+
+/// Renders the values sorted, so that reordering a list is not reported as a change.
+fn render_list(values: impl IntoIterator<Item = String>) -> String {
+    values
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_ids<'a>(ids: impl IntoIterator<Item = &'a UUID>) -> String {
+    render_list(
+        ids.into_iter()
+            .map(|id| Uuid::from_bytes(*id).hyphenated().to_string()),
+    )
+}
+
+fn render_resource_ids(ids: &ResourceIds) -> String {
+    match ids {
+        ResourceIds::Any => "any".to_string(),
+        ResourceIds::Ids(ids) => render_ids(ids),
+    }
+}
+
+fn render_auth_scope(auth_scope: &AuthScope) -> &'static str {
+    match auth_scope {
+        AuthScope::Public => "public",
+        AuthScope::Authenticated => "authenticated",
+        AuthScope::Restricted => "restricted",
+    }
+}
+
+fn render_allow(allow: &Allow) -> String {
+    format!(
+        "{}; users: [{}]; user_groups: [{}]",
+        render_auth_scope(&allow.auth_scope),
+        render_ids(&allow.users),
+        render_ids(&allow.user_groups)
+    )
+}
+
+fn render_user_specifier(approvers: &UserSpecifier) -> String {
+    match approvers {
+        UserSpecifier::Any => "any".to_string(),
+        UserSpecifier::Group(group_ids) => format!("groups: [{}]", render_ids(group_ids)),
+        UserSpecifier::Id(user_ids) => format!("users: [{}]", render_ids(user_ids)),
+    }
+}
+
+/// Renders the rule with all its parameters, named rules are referenced by id so that editing a named
+/// rule is not reported as a change of the rules that use it.
+fn render_rule(rule: &RequestPolicyRule) -> String {
+    let render_rules =
+        |rules: &[RequestPolicyRule]| rules.iter().map(render_rule).collect::<Vec<_>>().join(", ");
+
+    match rule {
+        RequestPolicyRule::AutoApproved => "auto_approved".to_string(),
+        RequestPolicyRule::AllowListed => "allowlisted".to_string(),
+        RequestPolicyRule::AllowListedByMetadata(metadata) => format!(
+            "allowlisted_by_metadata({}: {})",
+            metadata.key, metadata.value
+        ),
+        RequestPolicyRule::Quorum(approvers, min_approved) => format!(
+            "quorum({}; min_approved: {min_approved})",
+            render_user_specifier(approvers)
+        ),
+        RequestPolicyRule::QuorumPercentage(approvers, percentage) => format!(
+            "quorum_percentage({}; min_approved: {}%)",
+            render_user_specifier(approvers),
+            percentage.0
+        ),
+        RequestPolicyRule::And(rules) => format!("all_of({})", render_rules(rules)),
+        RequestPolicyRule::Or(rules) => format!("any_of({})", render_rules(rules)),
+        RequestPolicyRule::Not(rule) => format!("not({})", render_rule(rule)),
+        RequestPolicyRule::NamedRule(named_rule_id) => format!(
+            "named_rule({})",
+            Uuid::from_bytes(*named_rule_id).hyphenated()
+        ),
+    }
+}
+
+/// Renders the specifier as its operation followed by the targeted resources, if any.
+fn render_specifier(specifier: &RequestSpecifier) -> String {
+    match specifier {
+        RequestSpecifier::AddAccount
+        | RequestSpecifier::AddUser
+        | RequestSpecifier::AddAddressBookEntry
+        | RequestSpecifier::SetDisasterRecovery
+        | RequestSpecifier::CreateExternalCanister
+        | RequestSpecifier::AddRequestPolicy
+        | RequestSpecifier::AddUserGroup
+        | RequestSpecifier::ManageSystemInfo
+        | RequestSpecifier::SystemUpgrade
+        | RequestSpecifier::AddAsset
+        | RequestSpecifier::AddNamedRule
+        | RequestSpecifier::FreezeStation
+        | RequestSpecifier::UnfreezeStation => specifier.to_string(),
+        RequestSpecifier::EditAccount(ids)
+        | RequestSpecifier::EditUser(ids)
+        | RequestSpecifier::EditAddressBookEntry(ids)
+        | RequestSpecifier::RemoveAddressBookEntry(ids)
+        | RequestSpecifier::Transfer(ids)
+        | RequestSpecifier::EditRequestPolicy(ids)
+        | RequestSpecifier::RemoveRequestPolicy(ids)
+        | RequestSpecifier::EditUserGroup(ids)
+        | RequestSpecifier::RemoveUserGroup(ids)
+        | RequestSpecifier::EditAsset(ids)
+        | RequestSpecifier::RemoveAsset(ids)
+        | RequestSpecifier::EditNamedRule(ids)
+        | RequestSpecifier::RemoveNamedRule(ids) => {
+            format!("{specifier}({})", render_resource_ids(ids))
+        }
+        RequestSpecifier::ChangeExternalCanister(canister)
+        | RequestSpecifier::FundExternalCanister(canister)
+        | RequestSpecifier::ReleaseExternalCanisterControl(canister) => {
+            format!("{specifier}({canister})")
+        }
+        // the target already renders as `CallExternalCanister(<validation method>,<execution method>)`
+        RequestSpecifier::CallExternalCanister(target) => target.to_string(),
+        RequestSpecifier::EditPermission(ResourceSpecifier::Any) => format!("{specifier}(Any)"),
+        RequestSpecifier::EditPermission(ResourceSpecifier::Resource(resource)) => {
+            format!("{specifier}({resource})")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        request_test_utils::mock_request, user_test_utils::mock_user, EditUserOperation,
+        EditUserOperationInput, UserStatus,
+    };
+
+    #[test]
+    fn edit_user_request_diff_tracks_changes() {
+        let service = RequestSnapshotService::default();
+        let mut user = mock_user();
+        user.name = "Alice".to_string();
+        user.status = UserStatus::Active;
+        USER_REPOSITORY.insert(user.to_key(), user.clone());
+
+        let mut request = mock_request();
+        request.status = RequestStatus::Created;
+        request.operation = RequestOperation::EditUser(EditUserOperation {
+            input: EditUserOperationInput {
+                user_id: user.id,
+                name: Some("Bob".to_string()),
+                identities: None,
+                groups: None,
+                status: None,
+                cancel_pending_requests: None,
+            },
+        });
+
+        service.capture_creation_snapshot(&request);
+
+        let diff = service.get_request_diff(&request).unwrap();
+
+        assert!(!diff.is_stale);
+        assert_eq!(diff.fields.len(), 1);
+        assert_eq!(diff.fields[0].field, "name");
+        assert_eq!(diff.fields[0].before, Some("Alice".to_string()));
+        assert_eq!(diff.fields[0].after, Some("Bob".to_string()));
+
+        user.status = UserStatus::Inactive;
+        USER_REPOSITORY.insert(user.to_key(), user.clone());
+
+        let diff = service.get_request_diff(&request).unwrap();
+
+        assert!(diff.is_stale);
+        assert_eq!(diff.fields[1].field, "status");
+        assert_eq!(diff.fields[1].current, Some("inactive".to_string()));
+
+        user.name = "Bob".to_string();
+        USER_REPOSITORY.insert(user.to_key(), user.clone());
+        request.status = RequestStatus::Completed { completed_at: 0 };
+        service.capture_execution_snapshot(&request);
+
+        let diff = service.get_request_diff(&request).unwrap();
+
+        assert!(!diff.is_stale);
+        assert!(diff.executed_at.is_some());
+        assert_eq!(diff.fields[0].after, Some("Bob".to_string()));
+        assert_eq!(diff.fields[1].after, Some("inactive".to_string()));
+    }
+
+    #[test]
+    fn rules_and_specifiers_are_rendered_with_their_parameters() {
+        let group_id = [1; 16];
+        let rule = RequestPolicyRule::Or(vec![
+            RequestPolicyRule::Quorum(UserSpecifier::Group(vec![group_id]), 2),
+            RequestPolicyRule::Not(Box::new(RequestPolicyRule::AllowListed)),
+        ]);
+
+        assert_eq!(
+            render_rule(&rule),
+            "any_of(quorum(groups: [01010101-0101-0101-0101-010101010101]; min_approved: 2), not(allowlisted))"
+        );
+        assert_eq!(
+            render_specifier(&RequestSpecifier::EditUserGroup(ResourceIds::Ids(vec![
+                group_id
+            ]))),
+            "EditUserGroup(01010101-0101-0101-0101-010101010101)"
+        );
+        assert_eq!(
+            render_specifier(&RequestSpecifier::Transfer(ResourceIds::Any)),
+            "Transfer(any)"
+        );
+        assert_eq!(
+            render_allow(&Allow {
+                auth_scope: AuthScope::Authenticated,
+                users: vec![],
+                user_groups: vec![group_id],
+            }),
+            "authenticated; users: []; user_groups: [01010101-0101-0101-0101-010101010101]"
+        );
+    }
+
+    #[test]
+    fn requests_without_edited_entity_have_no_diff() {
+        let request = mock_request();
+
+        let service = RequestSnapshotService::default();
+
+        service.capture_creation_snapshot(&request);
+
+        assert!(service.get_request_diff(&request).is_none());
+    }
+}
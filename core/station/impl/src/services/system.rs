@@ -2,18 +2,22 @@ use crate::{
     core::{
         ic_cdk::{
             api::{print, trap},
-            next_time,
+            caller, next_time,
         },
         metrics::recompute_metrics,
         read_system_info, read_system_state, write_system_info,
     },
     errors::SystemError,
+    mappers::HelperMapper,
     models::{
-        system::{DisasterRecoveryCommittee, SystemInfo, SystemState},
+        system::{
+            AvailableStationUpgrade, DisasterRecoveryCommittee, RequestArchive, StationFreeze,
+            SystemInfo, SystemState,
+        },
         Asset, Blockchain, CanisterInstallMode, ManageSystemInfoOperationInput, Metadata,
-        RequestId, RequestKey, RequestOperation, RequestStatus, SystemRestoreTarget,
-        SystemUpgradeTarget, TokenStandard, WasmModuleExtraChunks, ADMIN_GROUP_ID,
-        OPERATOR_GROUP_ID,
+        NotificationType, RequestId, RequestKey, RequestOperation, RequestStatus,
+        SystemRestoreTarget, SystemUpgradeTarget, TokenStandard, UserStatus, WasmModuleExtraChunks,
+        ADMIN_GROUP_ID, OPERATOR_GROUP_ID,
     },
     repositories::{
         permission::PERMISSION_REPOSITORY, RequestRepository, ASSET_REPOSITORY,
@@ -22,7 +26,9 @@ use crate::{
     services::{
         change_canister::{ChangeCanisterService, CHANGE_CANISTER_SERVICE},
         disaster_recovery::DISASTER_RECOVERY_SERVICE,
+        notification::NOTIFICATION_SERVICE,
         request::{RequestService, REQUEST_SERVICE},
+        request_archive::REQUEST_ARCHIVE_SERVICE,
     },
     SYSTEM_VERSION,
 };
@@ -30,7 +36,10 @@ use candid::Principal;
 use lazy_static::lazy_static;
 use orbit_essentials::repository::Repository;
 use orbit_essentials::{api::ServiceResult, types::UUID};
-use station_api::{HealthStatus, InitialConfig, SystemInit, SystemInstall, SystemUpgrade};
+use station_api::{
+    HealthStatus, InitialConfig, NotifyStationUpgradeAvailableInput, SystemInit, SystemInstall,
+    SystemUpgrade,
+};
 use std::{
     collections::{BTreeMap, BTreeSet},
     sync::Arc,
@@ -155,6 +164,10 @@ impl SystemService {
             system_info.set_cycle_obtain_strategy(strategy);
         }
 
+        if let Some(control_panel_id) = input.control_panel_id {
+            system_info.set_control_panel_id(control_panel_id);
+        }
+
         if let Some(max_backup_snapshots) = input.max_station_backup_snapshots {
             system_info
                 .set_max_station_backup_snapshots(max_backup_snapshots)
@@ -167,6 +180,31 @@ impl SystemService {
                 .await?;
         }
 
+        if let Some(config) = input.request_archive {
+            let canister_id = match (system_info.get_request_archive(), config.deploy) {
+                (Some(archive), Some(deploy)) => {
+                    REQUEST_ARCHIVE_SERVICE
+                        .upgrade_archive(archive.canister_id, &deploy)
+                        .await?;
+
+                    archive.canister_id
+                }
+                (Some(archive), None) => archive.canister_id,
+                (None, Some(deploy)) => REQUEST_ARCHIVE_SERVICE.deploy_archive(&deploy).await?,
+                (None, None) => {
+                    return Err(
+                        "The request archive canister must be deployed before it can be configured."
+                            .to_string(),
+                    );
+                }
+            };
+
+            system_info.set_request_archive(Some(RequestArchive {
+                canister_id,
+                min_age_secs: config.min_age_secs,
+            }));
+        }
+
         Ok(())
     }
 
@@ -180,6 +218,9 @@ impl SystemService {
 
         write_system_info(system_info);
 
+        // the archival job reads the stored system info, hence it's only scheduled once it's written
+        crate::jobs::schedule_request_archival();
+
         res
     }
 
@@ -194,6 +235,47 @@ impl SystemService {
         });
     }
 
+    /// Freezes the station, which blocks the execution of transfers, external canister calls and
+    /// funding, as well as cycle withdrawals, until the station is unfrozen.
+    ///
+    /// Freezing an already frozen station only updates the freeze details.
+    pub fn freeze_station(&self, request_id: RequestId, reason: Option<String>) {
+        let mut system_info = self.get_system_info();
+        system_info.set_freeze(Some(StationFreeze {
+            request_id,
+            reason,
+            frozen_at: next_time(),
+        }));
+        write_system_info(system_info);
+
+        Self::refresh_cycle_obtain_strategy();
+    }
+
+    /// Unfreezes the station and resumes the operations that were blocked while it was frozen.
+    pub fn unfreeze_station(&self) {
+        let mut system_info = self.get_system_info();
+        if !system_info.is_frozen() {
+            return;
+        }
+
+        system_info.set_freeze(None);
+        write_system_info(system_info);
+
+        Self::refresh_cycle_obtain_strategy();
+        crate::jobs::resume_frozen_jobs();
+    }
+
+    /// Reapplies the cycle obtain strategy and the external canister monitoring so that the cycle
+    /// manager picks up the freeze state.
+    fn refresh_cycle_obtain_strategy() {
+        #[cfg(target_arch = "wasm32")]
+        {
+            crate::services::cycle_manager::CYCLE_MANAGER
+                .set_global_cycle_obtain_strategy(read_system_info().get_cycle_obtain_strategy());
+            crate::services::EXTERNAL_CANISTER_SERVICE.canister_monitor_restart();
+        }
+    }
+
     /// Execute an upgrade of the station by requesting the upgrader to perform it on our behalf.
     pub async fn upgrade_station(
         &self,
@@ -323,6 +405,9 @@ impl SystemService {
 
             install_canister_handlers::init_cycle_monitor(
                 *system_info.get_upgrader_canister_id(),
+                system_info
+                    .get_request_archive()
+                    .map(|archive| archive.canister_id),
                 system_info.get_cycle_obtain_strategy(),
             );
 
@@ -588,6 +673,9 @@ impl SystemService {
         // sets the name of the canister
         system_info.set_name(input.name.clone());
 
+        // the control panel that deploys the station installs it, and later announces new versions
+        system_info.set_control_panel_id(caller());
+
         // initializes the cache of the canister data, must happen during the same call as the init
         self.init_cache();
 
@@ -714,6 +802,63 @@ impl SystemService {
 
         Ok(())
     }
+
+    /// Records a newer station version announced by the control panel and notifies the admins.
+    ///
+    /// The station is not upgraded automatically, the admins are asked to create a system upgrade
+    /// request that references the announced registry entry. Announcements of versions that are not
+    /// newer than the current or the already announced version are ignored.
+    pub async fn notify_station_upgrade_available(
+        &self,
+        registry_canister_id: Principal,
+        input: NotifyStationUpgradeAvailableInput,
+    ) -> ServiceResult<()> {
+        let mut system_info = self.get_system_info();
+        let upgrade = AvailableStationUpgrade {
+            registry_canister_id,
+            registry_entry_id: *HelperMapper::to_uuid(input.registry_entry_id)?.as_bytes(),
+            name: input.name,
+            version: input.version,
+            announced_at: next_time(),
+        };
+
+        if !upgrade.is_newer_than(SYSTEM_VERSION) {
+            return Ok(());
+        }
+
+        if let Some(announced) = system_info.get_available_upgrade() {
+            if !upgrade.is_newer_than(&announced.version) {
+                return Ok(());
+            }
+        }
+
+        let title = format!("Station version {} is available", upgrade.version);
+        let message = format!(
+            "Version {} of the station was published as {} in the registry of {}. Create a system upgrade request that references {}@{} to upgrade the station from version {}.",
+            upgrade.version,
+            upgrade.name,
+            upgrade.registry_canister_id,
+            upgrade.name,
+            upgrade.version,
+            SYSTEM_VERSION,
+        );
+
+        system_info.set_available_upgrade(Some(upgrade));
+        write_system_info(system_info);
+
+        for admin in USER_REPOSITORY.find_by_group_and_status(ADMIN_GROUP_ID, &UserStatus::Active) {
+            NOTIFICATION_SERVICE
+                .send_notification(
+                    admin.id,
+                    NotificationType::SystemMessage,
+                    title.clone(),
+                    Some(message.clone()),
+                )
+                .await;
+        }
+
+        Ok(())
+    }
 }
 
 mod init_canister_sync_handlers {
@@ -906,6 +1051,7 @@ mod init_canister_sync_handlers {
             | RequestSpecifier::ChangeExternalCanister(..)
             | RequestSpecifier::CallExternalCanister(..)
             | RequestSpecifier::FundExternalCanister(..)
+            | RequestSpecifier::ReleaseExternalCanisterControl(..)
             | RequestSpecifier::EditPermission(..)
             | RequestSpecifier::AddRequestPolicy
             | RequestSpecifier::AddUserGroup
@@ -918,7 +1064,9 @@ mod init_canister_sync_handlers {
             | RequestSpecifier::RemoveAsset(..)
             | RequestSpecifier::AddNamedRule
             | RequestSpecifier::EditNamedRule(..)
-            | RequestSpecifier::RemoveNamedRule(..) => false,
+            | RequestSpecifier::RemoveNamedRule(..)
+            | RequestSpecifier::FreezeStation
+            | RequestSpecifier::UnfreezeStation => false,
         }
     }
 
@@ -1171,11 +1319,8 @@ mod init_canister_sync_handlers {
 mod install_canister_handlers {
     use crate::core::ic_cdk::api::id as self_canister_id;
     use crate::core::DEFAULT_INITIAL_UPGRADER_CYCLES;
-    use crate::models::{
-        CycleObtainStrategy, MonitorExternalCanisterStrategy,
-        MonitoringExternalCanisterEstimatedRuntimeInput,
-    };
-    use crate::services::cycle_manager::CYCLE_MANAGER;
+    use crate::models::CycleObtainStrategy;
+    use crate::services::cycle_manager::{system_canister_fund_strategy, CYCLE_MANAGER};
     use crate::services::EXTERNAL_CANISTER_SERVICE;
     use candid::{Encode, Principal};
     use ic_cdk::api::management_canister::main::{self as mgmt};
@@ -1260,21 +1405,23 @@ mod install_canister_handlers {
     }
 
     /// Starts the fund manager service setting it up to monitor the upgrader canister cycles and top it up if needed.
-    pub fn init_cycle_monitor(upgrader_id: Principal, cycle_obtain_strategy: &CycleObtainStrategy) {
-        let fund_strategy = MonitorExternalCanisterStrategy::BelowEstimatedRuntime(
-            MonitoringExternalCanisterEstimatedRuntimeInput {
-                min_runtime_secs: 60 * 24 * 60 * 60,  // 60 days
-                fund_runtime_secs: 30 * 24 * 60 * 60, // 30 days
-                max_runtime_cycles_fund: 2_000_000_000_000,
-                fallback_min_cycles: 600_000_000_000,
-                fallback_fund_cycles: 300_000_000_000,
-            },
-        );
+    ///
+    /// The request archive canister is monitored as well if the station has one.
+    pub fn init_cycle_monitor(
+        upgrader_id: Principal,
+        request_archive_id: Option<Principal>,
+        cycle_obtain_strategy: &CycleObtainStrategy,
+    ) {
+        let fund_strategy = system_canister_fund_strategy();
 
         CYCLE_MANAGER.set_global_cycle_obtain_strategy(cycle_obtain_strategy);
         CYCLE_MANAGER.add_canister(id(), fund_strategy.clone(), None);
         CYCLE_MANAGER.add_canister(upgrader_id, fund_strategy.clone(), None);
 
+        if let Some(request_archive_id) = request_archive_id {
+            CYCLE_MANAGER.add_canister(request_archive_id, fund_strategy.clone(), None);
+        }
+
         EXTERNAL_CANISTER_SERVICE.canister_monitor_restart();
 
         CYCLE_MANAGER.start();
@@ -1286,7 +1433,8 @@ mod tests {
     use super::*;
     use crate::{
         core::validation::disable_mock_resource_validation,
-        models::request_test_utils::mock_request,
+        models::{request_test_utils::mock_request, user_test_utils::mock_user},
+        repositories::NOTIFICATION_REPOSITORY,
         services::system::init_canister_sync_handlers::{
             set_initial_accounts, set_initial_assets, set_initial_named_rules,
             set_initial_request_policies, set_initial_user_groups, set_initial_users,
@@ -1358,6 +1506,52 @@ mod tests {
         assert!(system_info.get_change_canister_request().is_none());
     }
 
+    #[tokio::test]
+    async fn station_upgrade_announcement_notifies_admins_once() {
+        let mut admin = mock_user();
+        admin.groups = vec![*ADMIN_GROUP_ID];
+        admin.status = UserStatus::Active;
+        USER_REPOSITORY.insert(admin.to_key(), admin.clone());
+
+        write_system_info(SystemInfo::new(
+            Principal::management_canister(),
+            Vec::new(),
+        ));
+
+        let input = NotifyStationUpgradeAvailableInput {
+            registry_entry_id: Uuid::new_v4().hyphenated().to_string(),
+            name: "@orbit/station".to_string(),
+            version: "999.0.0".to_string(),
+        };
+
+        for _ in 0..2 {
+            SYSTEM_SERVICE
+                .notify_station_upgrade_available(Principal::anonymous(), input.clone())
+                .await
+                .unwrap();
+        }
+
+        let upgrade = read_system_info().get_available_upgrade().cloned().unwrap();
+        assert_eq!(upgrade.version, "999.0.0");
+        assert_eq!(NOTIFICATION_REPOSITORY.find_by_user_id(admin.id).len(), 1);
+
+        SYSTEM_SERVICE
+            .notify_station_upgrade_available(
+                Principal::anonymous(),
+                NotifyStationUpgradeAvailableInput {
+                    version: SYSTEM_VERSION.to_string(),
+                    ..input
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            read_system_info().get_available_upgrade().cloned(),
+            Some(upgrade)
+        );
+    }
+
     #[tokio::test]
     async fn test_initial_named_rules_with_correct_dependencies() {
         let id_1 = Uuid::new_v4().hyphenated().to_string();
@@ -0,0 +1,338 @@
+use crate::{
+    core::read_system_info,
+    errors::WasmModuleRegistryError,
+    models::{ResolvedWasmModuleRegistryEntry, WasmModuleExtraChunks, WasmModuleRegistryReference},
+};
+use candid::Principal;
+use control_panel_api::{
+    GetArtifactChunkInput, GetArtifactChunkResponse, PaginationInput, RegistryEntryDTO,
+    RegistryEntrySortBy, RegistryEntryValueDTO, RegistryEntryValueKindDTO,
+    SearchRegistryFilterKindDTO, SearchRegistryInput, SearchRegistryResponse, SortDirection,
+    VerifyRegistryEntryInput, VerifyRegistryEntryResponse,
+};
+use lazy_static::lazy_static;
+use orbit_essentials::api::{ApiResult, ServiceResult};
+use sha2::{Digest, Sha256};
+use std::{str::FromStr, sync::Arc};
+use uuid::Uuid;
+
+lazy_static! {
+    pub static ref WASM_MODULE_REGISTRY_SERVICE: Arc<WasmModuleRegistryService> =
+        Arc::new(WasmModuleRegistryService::default());
+}
+
+/// Resolves and fetches the wasm modules published in the registry of a control panel.
+#[derive(Default, Debug)]
+pub struct WasmModuleRegistryService {}
+
+impl WasmModuleRegistryService {
+    /// The page size used when searching the registry.
+    const SEARCH_PAGE_SIZE: u16 = 100;
+
+    /// The maximum number of pages searched to resolve a reference, this bounds the number
+    /// of calls made to the registry when a request is created.
+    const MAX_SEARCH_PAGES: usize = 10;
+
+    /// Validates that the module is either uploaded with the request or referenced in the registry
+    /// of the control panel trusted by the station, so that requests can't make the station call
+    /// arbitrary canisters.
+    pub fn validate_module_source(
+        module: &[u8],
+        module_extra_chunks: &Option<WasmModuleExtraChunks>,
+        module_registry: &Option<WasmModuleRegistryReference>,
+    ) -> ServiceResult<(), WasmModuleRegistryError> {
+        let Some(reference) = module_registry else {
+            return Ok(());
+        };
+
+        if !module.is_empty() || module_extra_chunks.is_some() {
+            return Err(WasmModuleRegistryError::InvalidModuleSource {
+                info: "the module cannot be uploaded when it is referenced in the registry"
+                    .to_string(),
+            });
+        }
+
+        if read_system_info().get_control_panel_id() != Some(&reference.registry_canister_id) {
+            return Err(WasmModuleRegistryError::UntrustedRegistry {
+                registry_canister_id: reference.registry_canister_id.to_text(),
+            });
+        }
+
+        if reference.name.trim().is_empty() {
+            return Err(WasmModuleRegistryError::InvalidModuleSource {
+                info: "the registry entry name cannot be empty".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Resolves the reference to the newest matching wasm module entry of the registry and
+    /// records the hash of its artifact.
+    pub async fn resolve(
+        &self,
+        reference: &WasmModuleRegistryReference,
+    ) -> ServiceResult<ResolvedWasmModuleRegistryEntry, WasmModuleRegistryError> {
+        let entry = self.find_entry(reference).await?;
+        let RegistryEntryValueDTO::WasmModule(value) = entry.value else {
+            return Err(WasmModuleRegistryError::EntryNotFound {
+                reference: reference.to_string(),
+            });
+        };
+
+        let artifact = self
+            .get_artifact(
+                reference.registry_canister_id,
+                value.wasm_artifact_id.clone(),
+            )
+            .await?;
+
+        let mut resolved = ResolvedWasmModuleRegistryEntry {
+            entry_id: parse_uuid(&entry.id)?,
+            version: value.version,
+            wasm_artifact_id: parse_uuid(&value.wasm_artifact_id)?,
+            wasm_artifact_hash: Sha256::digest(&artifact).to_vec(),
+            module_extra_chunks: value.module_extra_chunks.map(Into::into),
+            verified_publisher: None,
+        };
+
+        resolved.verified_publisher = self
+            .verify_publisher(reference.registry_canister_id, entry.id, &resolved)
+            .await;
+
+        Ok(resolved)
+    }
+
+    /// Returns the publisher of the entry if the registry verifies the provenance of the module.
+    ///
+    /// Registries that do not support the verification are treated as unverified, so that the
+    /// approvers of the request can decide whether to trust the module.
+    async fn verify_publisher(
+        &self,
+        registry_canister_id: Principal,
+        entry_id: String,
+        resolved: &ResolvedWasmModuleRegistryEntry,
+    ) -> Option<Principal> {
+        let (result,) = ic_cdk::call::<_, (ApiResult<VerifyRegistryEntryResponse>,)>(
+            registry_canister_id,
+            "verify_registry_entry",
+            (VerifyRegistryEntryInput {
+                id: entry_id,
+                expected_hash: Some(hex::encode(resolved.module_checksum())),
+            },),
+        )
+        .await
+        .ok()?;
+
+        result
+            .ok()
+            .filter(|verification| verification.verified)
+            .and_then(|verification| verification.publisher)
+    }
+
+    /// Fetches the module of the resolved entry, which must match the hash recorded when the
+    /// request was created.
+    pub async fn fetch_module(
+        &self,
+        registry_canister_id: Principal,
+        resolved: &ResolvedWasmModuleRegistryEntry,
+    ) -> ServiceResult<Vec<u8>, WasmModuleRegistryError> {
+        let module = self
+            .get_artifact(
+                registry_canister_id,
+                Uuid::from_bytes(resolved.wasm_artifact_id)
+                    .hyphenated()
+                    .to_string(),
+            )
+            .await?;
+
+        let hash = Sha256::digest(&module).to_vec();
+        if hash != resolved.wasm_artifact_hash {
+            return Err(WasmModuleRegistryError::HashMismatch {
+                expected: hex::encode(&resolved.wasm_artifact_hash),
+                actual: hex::encode(hash),
+            });
+        }
+
+        Ok(module)
+    }
+
+    async fn find_entry(
+        &self,
+        reference: &WasmModuleRegistryReference,
+    ) -> ServiceResult<RegistryEntryDTO, WasmModuleRegistryError> {
+        let mut offset = 0;
+
+        for _ in 0..Self::MAX_SEARCH_PAGES {
+            let (result,) = ic_cdk::call::<_, (ApiResult<SearchRegistryResponse>,)>(
+                reference.registry_canister_id,
+                "search_registry",
+                (SearchRegistryInput {
+                    filter_by: vec![
+                        SearchRegistryFilterKindDTO::Name(reference.name.clone()),
+                        SearchRegistryFilterKindDTO::Kind(RegistryEntryValueKindDTO::WasmModule),
+                    ],
+                    sort_by: Some(RegistryEntrySortBy::CreatedAt(SortDirection::Desc)),
+                    pagination: Some(PaginationInput {
+                        offset: Some(offset),
+                        limit: Some(Self::SEARCH_PAGE_SIZE),
+                    }),
+                },),
+            )
+            .await
+            .map_err(|(_, err)| WasmModuleRegistryError::RegistryUnavailable { reason: err })?;
+
+            let response = result.map_err(|err| WasmModuleRegistryError::RegistryUnavailable {
+                reason: err.message.unwrap_or(err.code),
+            })?;
+
+            let entry = response
+                .entries
+                .into_iter()
+                .find(|entry| match &entry.value {
+                    RegistryEntryValueDTO::WasmModule(value) => {
+                        reference.matches(&value.version, &entry.tags)
+                    }
+                    _ => false,
+                });
+
+            if let Some(entry) = entry {
+                return Ok(entry);
+            }
+
+            match response.next_offset {
+                Some(next_offset) => offset = next_offset,
+                None => break,
+            }
+        }
+
+        Err(WasmModuleRegistryError::EntryNotFound {
+            reference: reference.to_string(),
+        })
+    }
+
+    /// Fetches the artifact chunk by chunk, since the registry only returns small artifacts in a
+    /// single reply.
+    async fn get_artifact(
+        &self,
+        registry_canister_id: Principal,
+        artifact_id: String,
+    ) -> ServiceResult<Vec<u8>, WasmModuleRegistryError> {
+        let first_chunk = self
+            .get_artifact_chunk(registry_canister_id, artifact_id.clone(), 0)
+            .await?;
+        let mut artifact = Vec::with_capacity(first_chunk.size as usize);
+        artifact.extend(first_chunk.chunk);
+
+        for chunk_index in 1..first_chunk.chunk_count {
+            let chunk = self
+                .get_artifact_chunk(registry_canister_id, artifact_id.clone(), chunk_index)
+                .await?;
+            artifact.extend(chunk.chunk);
+        }
+
+        if artifact.len() as u64 != first_chunk.size {
+            return Err(WasmModuleRegistryError::RegistryUnavailable {
+                reason: format!(
+                    "the registry returned {} bytes for an artifact of {} bytes",
+                    artifact.len(),
+                    first_chunk.size
+                ),
+            });
+        }
+
+        Ok(artifact)
+    }
+
+    async fn get_artifact_chunk(
+        &self,
+        registry_canister_id: Principal,
+        artifact_id: String,
+        chunk_index: u64,
+    ) -> ServiceResult<GetArtifactChunkResponse, WasmModuleRegistryError> {
+        let (result,) = ic_cdk::call::<_, (ApiResult<GetArtifactChunkResponse>,)>(
+            registry_canister_id,
+            "get_artifact_chunk",
+            (GetArtifactChunkInput {
+                artifact_id,
+                chunk_index,
+            },),
+        )
+        .await
+        .map_err(|(_, err)| WasmModuleRegistryError::RegistryUnavailable { reason: err })?;
+
+        result.map_err(|err| WasmModuleRegistryError::RegistryUnavailable {
+            reason: err.message.unwrap_or(err.code),
+        })
+    }
+}
+
+fn parse_uuid(id: &str) -> ServiceResult<[u8; 16], WasmModuleRegistryError> {
+    Uuid::from_str(id)
+        .map(|uuid| *uuid.as_bytes())
+        .map_err(|_| WasmModuleRegistryError::RegistryUnavailable {
+            reason: format!("the registry returned the malformed id {id}"),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::write_system_info;
+    use crate::models::{SystemInfo, WasmModuleRegistryVersion};
+
+    fn trust_control_panel(control_panel_id: Principal) {
+        let mut system_info = SystemInfo::default();
+        system_info.set_control_panel_id(control_panel_id);
+        write_system_info(system_info);
+    }
+
+    #[test]
+    fn module_cannot_be_uploaded_and_referenced() {
+        trust_control_panel(Principal::anonymous());
+        let reference = Some(WasmModuleRegistryReference {
+            registry_canister_id: Principal::anonymous(),
+            name: "@orbit/station".to_string(),
+            version: WasmModuleRegistryVersion::Tag("latest".to_string()),
+        });
+
+        assert!(WasmModuleRegistryService::validate_module_source(&[], &None, &reference).is_ok());
+        assert!(WasmModuleRegistryService::validate_module_source(
+            &[0, 97, 115, 109],
+            &None,
+            &None
+        )
+        .is_ok());
+        assert!(WasmModuleRegistryService::validate_module_source(
+            &[0, 97, 115, 109],
+            &None,
+            &reference
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn only_the_trusted_control_panel_is_accepted_as_registry() {
+        let reference = Some(WasmModuleRegistryReference {
+            registry_canister_id: Principal::from_slice(&[1; 10]),
+            name: "@orbit/station".to_string(),
+            version: WasmModuleRegistryVersion::Tag("latest".to_string()),
+        });
+
+        // stations that do not trust any control panel can't use registry references
+        write_system_info(SystemInfo::default());
+        assert!(matches!(
+            WasmModuleRegistryService::validate_module_source(&[], &None, &reference),
+            Err(WasmModuleRegistryError::UntrustedRegistry { .. })
+        ));
+
+        trust_control_panel(Principal::from_slice(&[2; 10]));
+        assert!(matches!(
+            WasmModuleRegistryService::validate_module_source(&[], &None, &reference),
+            Err(WasmModuleRegistryError::UntrustedRegistry { .. })
+        ));
+
+        trust_control_panel(Principal::from_slice(&[1; 10]));
+        assert!(WasmModuleRegistryService::validate_module_source(&[], &None, &reference).is_ok());
+    }
+}
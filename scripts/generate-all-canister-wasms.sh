@@ -8,5 +8,6 @@ cd $SCRIPT_DIR/..
 
 ./scripts/generate-wasm.sh control-panel
 ./scripts/generate-wasm.sh upgrader
+./scripts/generate-wasm.sh archive
 ./scripts/generate-wasm.sh station
 ./scripts/generate-wasm.sh test_canister
@@ -165,6 +165,7 @@ fn deploy_user_station() {
         }],
         associate_with_caller: Some(AssociateWithCallerInput { labels: vec![] }),
         subnet_selection: None,
+        template: None,
     };
     let res: (ApiResult<DeployStationResponse>,) = update_candid_as(
         &env,
@@ -404,6 +405,7 @@ fn no_upload_canister_modules() {
         }],
         associate_with_caller: Some(AssociateWithCallerInput { labels: vec![] }),
         subnet_selection: None,
+        template: None,
     };
     let res: (ApiResult<DeployStationResponse>,) = update_candid_as(
         &env,
@@ -431,6 +433,7 @@ fn no_upload_canister_modules() {
         }],
         associate_with_caller: Some(AssociateWithCallerInput { labels: vec![] }),
         subnet_selection: None,
+        template: None,
     };
     let res: (ApiResult<DeployStationResponse>,) = update_candid_as(
         &env,
@@ -510,6 +513,7 @@ fn deploy_user_station_to_different_subnet() {
         subnet_selection: Some(SubnetSelection::Filter(SubnetFilter {
             subnet_type: Some("fiduciary".to_string()),
         })),
+        template: None,
     };
     let res: (ApiResult<DeployStationResponse>,) = update_candid_as(
         &env,
@@ -572,6 +576,7 @@ fn insufficient_control_panel_cycles() {
             }],
             associate_with_caller: Some(AssociateWithCallerInput { labels: vec![] }),
             subnet_selection: None,
+            template: None,
         };
 
         let res: (ApiResult<DeployStationResponse>,) = update_candid_as(
@@ -760,7 +765,7 @@ fn control_panel_upgrade() {
         assert_eq!(user_stations[1].canister_id, stations[i]);
     }
 
-    // upgrade control panel: post-upgrade hook iterates over all users and sets their subscription status to `UserSubscriptionStatusDTO::Approved`
+    // upgrade control panel: the users, including their subscription status, and their stations are kept
     let control_panel_wasm = get_canister_wasm("control_panel").to_vec();
     env.upgrade_canister(
         canister_ids.control_panel,
@@ -839,6 +844,7 @@ fn deploy_station(
         }],
         associate_with_caller: Some(AssociateWithCallerInput { labels: vec![] }),
         subnet_selection: None,
+        template: None,
     };
     update_candid_as::<_, (ApiResult<DeployStationResponse>,)>(
         env,
@@ -100,6 +100,7 @@ fn successful_monitors_stations_and_tops_up() {
         }],
         associate_with_caller: Some(AssociateWithCallerInput { labels: Vec::new() }),
         subnet_selection: None,
+        template: None,
     };
     let res: (ApiResult<DeployStationResponse>,) = update_candid_as(
         &env,
@@ -92,6 +92,8 @@ fn review() {
                 with_evaluation_results: false,
                 deduplication_keys: None,
                 tags: None,
+                search_term: None,
+                resources: None,
             })
             .await
             .unwrap();
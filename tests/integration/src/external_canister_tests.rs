@@ -3,7 +3,8 @@ use crate::utils::{
     add_user, bump_time_to_avoid_ratelimit, canister_status, deploy_test_canister, execute_request,
     get_core_canister_health_status, get_request, get_system_info, hash, submit_request,
     submit_request_approval, submit_request_raw, submit_request_with_expected_trap, update_raw,
-    upload_canister_chunks_to_asset_canister, user_test_id, wait_for_request, COUNTER_WAT,
+    upload_canister_chunks_to_asset_canister, user_test_id, wait_for_request, ADMIN_GROUP_ID,
+    COUNTER_WAT,
 };
 use crate::TestEnv;
 use candid::{Encode, Principal};
@@ -12,16 +13,20 @@ use ic_management_canister_types::{CanisterIdRecord, CanisterStatusType};
 use orbit_essentials::api::ApiResult;
 use orbit_essentials::cmc::{SubnetFilter, SubnetSelection};
 use orbit_essentials::utils::timestamp_to_rfc3339;
-use pocket_ic::update_candid_as;
+use pocket_ic::{query_candid_as, update_candid_as, PocketIc};
 use sha2::{Digest, Sha256};
 use station_api::{
     AddRequestPolicyOperationInput, AllowDTO, CallExternalCanisterOperationInput,
     CallExternalCanisterResourceTargetDTO, CanisterInstallMode, CanisterMethodDTO,
-    ChangeExternalCanisterOperationInput, CreateExternalCanisterOperationInput,
+    ChangeExternalCanisterOperationInput, ConfigureExternalCanisterOperationInput,
+    ConfigureExternalCanisterOperationKindDTO, CreateExternalCanisterOperationInput,
     CreateExternalCanisterOperationKindCreateNewDTO, CreateExternalCanisterOperationKindDTO,
-    EditPermissionOperationInput, ExecutionMethodResourceTargetDTO, ExternalCanisterIdDTO,
-    ExternalCanisterPermissionsCreateInput, ExternalCanisterRequestPoliciesCreateInput,
-    HealthStatus, ListRequestsInput, ListRequestsOperationTypeDTO, ListRequestsResponse,
+    DefiniteCanisterSettingsInput, EditPermissionOperationInput, ExecutionMethodResourceTargetDTO,
+    ExternalCanisterIdDTO, ExternalCanisterPermissionsCreateInput,
+    ExternalCanisterRequestPoliciesCreateInput, ExternalCanisterRollbackInput,
+    ExternalCanisterStateDTO, GetExternalCanisterInput, GetExternalCanisterResponse, HealthStatus,
+    ListNotificationsInput, ListNotificationsResponse, ListRequestsInput,
+    ListRequestsOperationTypeDTO, ListRequestsResponse, LogVisibility,
     PruneExternalCanisterOperationInput, PruneExternalCanisterResourceDTO, QuorumDTO,
     RequestApprovalStatusDTO, RequestOperationDTO, RequestOperationInput, RequestPolicyRuleDTO,
     RequestSpecifierDTO, RequestStatusDTO, RestoreExternalCanisterOperationInput, Snapshot,
@@ -77,6 +82,8 @@ fn successful_four_eyes_upgrade() {
             module: base_chunk,
             module_extra_chunks: Some(module_extra_chunks),
             arg: None,
+            rollback: None,
+            module_registry: None,
         });
 
     let change_canister_operation_request = submit_request(
@@ -222,6 +229,8 @@ fn upgrade_reinstall_list_test() {
             module: base_chunk.clone(),
             module_extra_chunks: Some(module_extra_chunks.clone()),
             arg: None,
+            rollback: None,
+            module_registry: None,
         });
     execute_request(
         &env,
@@ -247,6 +256,8 @@ fn upgrade_reinstall_list_test() {
             module: base_chunk,
             module_extra_chunks: Some(module_extra_chunks),
             arg: None,
+            rollback: None,
+            module_registry: None,
         });
     execute_request(
         &env,
@@ -282,8 +293,10 @@ fn upgrade_reinstall_list_test() {
         with_evaluation_results: false,
         deduplication_keys: None,
         tags: None,
+        search_term: None,
+        resources: None,
     };
-    let res: (ApiResult<ListRequestsResponse>,) = update_candid_as(
+    let res: (ApiResult<ListRequestsResponse>,) = query_candid_as(
         &env,
         canister_ids.station,
         WALLET_ADMIN_USER,
@@ -312,8 +325,10 @@ fn upgrade_reinstall_list_test() {
         with_evaluation_results: false,
         deduplication_keys: None,
         tags: None,
+        search_term: None,
+        resources: None,
     };
-    let res: (ApiResult<ListRequestsResponse>,) = update_candid_as(
+    let res: (ApiResult<ListRequestsResponse>,) = query_candid_as(
         &env,
         canister_ids.station,
         WALLET_ADMIN_USER,
@@ -340,8 +355,10 @@ fn upgrade_reinstall_list_test() {
         with_evaluation_results: false,
         deduplication_keys: None,
         tags: None,
+        search_term: None,
+        resources: None,
     };
-    let res: (ApiResult<ListRequestsResponse>,) = update_candid_as(
+    let res: (ApiResult<ListRequestsResponse>,) = query_candid_as(
         &env,
         canister_ids.station,
         WALLET_ADMIN_USER,
@@ -352,6 +369,121 @@ fn upgrade_reinstall_list_test() {
     assert_eq!(res.0.unwrap().total, 2);
 }
 
+#[test]
+fn failed_health_check_restores_the_snapshot() {
+    let TestEnv {
+        env, canister_ids, ..
+    } = setup_new_env();
+
+    // create and install the counter canister
+    let canister_id = create_canister(&env, canister_ids.station);
+    let module_bytes = wat::parse_str(COUNTER_WAT).unwrap();
+    env.install_canister(
+        canister_id,
+        module_bytes.clone(),
+        vec![],
+        Some(canister_ids.station),
+    );
+
+    // increment the counter in stable memory
+    update_raw(&env, canister_id, Principal::anonymous(), "inc", vec![]).unwrap();
+    let ctr = update_raw(&env, canister_id, Principal::anonymous(), "read", vec![]).unwrap();
+    assert_eq!(ctr, 2_u32.to_le_bytes());
+
+    // submit canister upgrade request with a health check method that the canister does not expose
+    let change_canister_operation =
+        RequestOperationInput::ChangeExternalCanister(ChangeExternalCanisterOperationInput {
+            canister_id,
+            mode: CanisterInstallMode::Upgrade(None),
+            module: module_bytes,
+            module_extra_chunks: None,
+            arg: None,
+            rollback: Some(ExternalCanisterRollbackInput {
+                replace_snapshot: None,
+                health_check_method: Some("missing_method".to_string()),
+            }),
+            module_registry: None,
+        });
+    let failed_request_status = execute_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        change_canister_operation,
+    )
+    .unwrap_err()
+    .unwrap();
+    match failed_request_status {
+        RequestStatusDTO::Failed { reason } => {
+            let reason = reason.unwrap();
+            assert!(reason.contains("health check `missing_method` failed"));
+            assert!(reason.contains("restored from snapshot"));
+        }
+        _ => panic!("Unexpected request status: {failed_request_status:?}"),
+    };
+
+    // the counter is restored from the snapshot, i.e., the increment in the post-upgrade hook is rolled back
+    let ctr = update_raw(&env, canister_id, Principal::anonymous(), "read", vec![]).unwrap();
+    assert_eq!(ctr, 2_u32.to_le_bytes());
+
+    // the canister keeps running after the restore
+    let status = canister_status(&env, Some(canister_ids.station), canister_id);
+    assert_eq!(status.status, CanisterStatusType::Running);
+}
+
+#[test]
+fn successful_change_deletes_the_rollback_snapshot() {
+    let TestEnv {
+        env, canister_ids, ..
+    } = setup_new_env();
+
+    // create and install the counter canister
+    let canister_id = create_canister(&env, canister_ids.station);
+    let module_bytes = wat::parse_str(COUNTER_WAT).unwrap();
+    env.install_canister(
+        canister_id,
+        module_bytes.clone(),
+        vec![],
+        Some(canister_ids.station),
+    );
+
+    let change_canister_operation =
+        RequestOperationInput::ChangeExternalCanister(ChangeExternalCanisterOperationInput {
+            canister_id,
+            mode: CanisterInstallMode::Upgrade(None),
+            module: module_bytes,
+            module_extra_chunks: None,
+            arg: None,
+            rollback: Some(ExternalCanisterRollbackInput {
+                replace_snapshot: None,
+                health_check_method: None,
+            }),
+            module_registry: None,
+        });
+    let completed_request = execute_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        change_canister_operation,
+    )
+    .unwrap();
+
+    match completed_request.operation {
+        RequestOperationDTO::ChangeExternalCanister(operation) => {
+            assert_eq!(operation.rollback_snapshot_id, None);
+        }
+        _ => panic!(
+            "Unexpected request operation type: {:?}",
+            completed_request.operation
+        ),
+    };
+
+    // the rollback snapshot does not count towards the snapshots of the canister once the change succeeded
+    let snapshots: Vec<_> = env
+        .list_canister_snapshots(canister_id, Some(canister_ids.station))
+        .unwrap();
+    assert!(snapshots.is_empty());
+}
+
 #[test]
 fn create_external_canister_and_check_status() {
     let TestEnv {
@@ -1758,3 +1890,456 @@ fn read_system_canister_info() {
     .unwrap_err();
     assert!(err.reject_message.contains("System(SystemInfo)"));
 }
+
+#[test]
+fn fetch_external_canister_logs_with_log_visibility() {
+    let TestEnv {
+        env, canister_ids, ..
+    } = setup_new_env();
+
+    // create an external canister and install a module that logs a message
+    let create_canister_operation =
+        RequestOperationInput::CreateExternalCanister(CreateExternalCanisterOperationInput {
+            kind: CreateExternalCanisterOperationKindDTO::CreateNew(
+                CreateExternalCanisterOperationKindCreateNewDTO {
+                    initial_cycles: None,
+                    subnet_selection: None,
+                },
+            ),
+            name: "logger".to_string(),
+            description: None,
+            labels: None,
+            metadata: None,
+            permissions: ExternalCanisterPermissionsCreateInput {
+                calls: vec![],
+                read: AllowDTO {
+                    auth_scope: station_api::AuthScopeDTO::Restricted,
+                    user_groups: vec![],
+                    users: vec![],
+                },
+                change: AllowDTO {
+                    auth_scope: station_api::AuthScopeDTO::Restricted,
+                    user_groups: vec![],
+                    users: vec![],
+                },
+            },
+            request_policies: ExternalCanisterRequestPoliciesCreateInput {
+                change: vec![],
+                calls: vec![],
+            },
+        });
+    let create_canister_request = execute_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        create_canister_operation,
+    )
+    .unwrap();
+    let canister_id = match create_canister_request.operation {
+        RequestOperationDTO::CreateExternalCanister(operation) => operation.canister_id.unwrap(),
+        _ => panic!(
+            "Unexpected request operation type: {:?}",
+            create_canister_request.operation
+        ),
+    };
+    let module_bytes = wat::parse_str(
+        r#"(module
+            (import "ic0" "debug_print" (func $debug_print (param i32 i32)))
+            (import "ic0" "msg_reply" (func $msg_reply))
+            (func $log
+                (call $debug_print (i32.const 0) (i32.const 5))
+                (call $msg_reply))
+            (memory $memory 1)
+            (data (i32.const 0) "hello")
+            (export "canister_update log" (func $log)))"#,
+    )
+    .unwrap();
+    env.install_canister(
+        canister_id,
+        module_bytes,
+        vec![],
+        Some(canister_ids.station),
+    );
+    update_raw(&env, canister_id, Principal::anonymous(), "log", vec![]).unwrap();
+
+    // the admin can read the logs according to the station permissions
+    let res: (ApiResult<GetExternalCanisterResponse>,) = update_candid_as(
+        &env,
+        canister_ids.station,
+        WALLET_ADMIN_USER,
+        "get_external_canister",
+        (GetExternalCanisterInput { canister_id },),
+    )
+    .unwrap();
+    assert!(res.0.unwrap().privileges.can_read_logs);
+
+    // the logs are only served to non-replicated queries, hence the admin fetches them directly
+    // and only once the station has added the admin to the log visibility of the canister
+    assert!(env
+        .fetch_canister_logs(canister_id, WALLET_ADMIN_USER)
+        .is_err());
+
+    let configure_canister_operation =
+        RequestOperationInput::ConfigureExternalCanister(ConfigureExternalCanisterOperationInput {
+            canister_id,
+            kind: ConfigureExternalCanisterOperationKindDTO::NativeSettings(
+                DefiniteCanisterSettingsInput {
+                    log_visibility: Some(LogVisibility::AllowedViewers(vec![WALLET_ADMIN_USER])),
+                    ..Default::default()
+                },
+            ),
+        });
+    execute_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        configure_canister_operation,
+    )
+    .unwrap();
+
+    let records = env
+        .fetch_canister_logs(canister_id, WALLET_ADMIN_USER)
+        .unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].content, b"hello".to_vec());
+
+    // other users are still not allowed to fetch the logs
+    assert!(env
+        .fetch_canister_logs(canister_id, user_test_id(0))
+        .is_err());
+}
+
+fn create_external_canister(env: &PocketIc, station_id: Principal, name: &str) -> Principal {
+    let create_canister_operation =
+        RequestOperationInput::CreateExternalCanister(CreateExternalCanisterOperationInput {
+            kind: CreateExternalCanisterOperationKindDTO::CreateNew(
+                CreateExternalCanisterOperationKindCreateNewDTO {
+                    initial_cycles: None,
+                    subnet_selection: None,
+                },
+            ),
+            name: name.to_string(),
+            description: None,
+            labels: None,
+            metadata: None,
+            permissions: ExternalCanisterPermissionsCreateInput {
+                calls: vec![],
+                read: AllowDTO {
+                    auth_scope: station_api::AuthScopeDTO::Restricted,
+                    user_groups: vec![],
+                    users: vec![],
+                },
+                change: AllowDTO {
+                    auth_scope: station_api::AuthScopeDTO::Restricted,
+                    user_groups: vec![],
+                    users: vec![],
+                },
+            },
+            request_policies: ExternalCanisterRequestPoliciesCreateInput {
+                change: vec![],
+                calls: vec![],
+            },
+        });
+    let create_canister_request = execute_request(
+        env,
+        WALLET_ADMIN_USER,
+        station_id,
+        create_canister_operation,
+    )
+    .unwrap();
+
+    match create_canister_request.operation {
+        RequestOperationDTO::CreateExternalCanister(operation) => operation.canister_id.unwrap(),
+        _ => panic!(
+            "Unexpected request operation type: {:?}",
+            create_canister_request.operation
+        ),
+    }
+}
+
+fn canister_controllers(
+    env: &PocketIc,
+    station_id: Principal,
+    canister_id: Principal,
+) -> Vec<Principal> {
+    let mut controllers = env
+        .canister_status(canister_id, Some(station_id))
+        .unwrap()
+        .settings
+        .controllers;
+    controllers.sort();
+
+    controllers
+}
+
+#[test]
+fn add_and_remove_external_canister_controllers() {
+    let TestEnv {
+        env, canister_ids, ..
+    } = setup_new_env();
+
+    let canister_id = create_external_canister(&env, canister_ids.station, "controlled");
+    let controller = user_test_id(0);
+
+    let add_controllers_operation =
+        RequestOperationInput::ConfigureExternalCanister(ConfigureExternalCanisterOperationInput {
+            canister_id,
+            kind: ConfigureExternalCanisterOperationKindDTO::AddControllers(vec![controller]),
+        });
+    execute_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        add_controllers_operation,
+    )
+    .unwrap();
+
+    let mut expected_controllers = vec![canister_ids.station, controller];
+    expected_controllers.sort();
+    assert_eq!(
+        canister_controllers(&env, canister_ids.station, canister_id),
+        expected_controllers
+    );
+
+    // the station cannot remove itself from the controllers
+    let remove_station_operation =
+        RequestOperationInput::ConfigureExternalCanister(ConfigureExternalCanisterOperationInput {
+            canister_id,
+            kind: ConfigureExternalCanisterOperationKindDTO::RemoveControllers(vec![
+                canister_ids.station,
+            ]),
+        });
+    let res = submit_request_raw(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        remove_station_operation,
+    )
+    .unwrap();
+    assert!(res.0.is_err());
+
+    let remove_controllers_operation =
+        RequestOperationInput::ConfigureExternalCanister(ConfigureExternalCanisterOperationInput {
+            canister_id,
+            kind: ConfigureExternalCanisterOperationKindDTO::RemoveControllers(vec![controller]),
+        });
+    execute_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        remove_controllers_operation,
+    )
+    .unwrap();
+
+    assert_eq!(
+        canister_controllers(&env, canister_ids.station, canister_id),
+        vec![canister_ids.station]
+    );
+}
+
+#[test]
+fn release_external_canister_control() {
+    let TestEnv {
+        env, canister_ids, ..
+    } = setup_new_env();
+
+    let canister_id = create_external_canister(&env, canister_ids.station, "released");
+    let new_owner = user_test_id(1);
+    let user = user_test_id(0);
+    add_user(&env, user, vec![], canister_ids.station);
+
+    // changes of the canister are auto-approved, which must not apply to releasing its control
+    let add_change_policy =
+        RequestOperationInput::AddRequestPolicy(AddRequestPolicyOperationInput {
+            specifier: RequestSpecifierDTO::ChangeExternalCanister(
+                ExternalCanisterIdDTO::Canister(canister_id),
+            ),
+            rule: RequestPolicyRuleDTO::AutoApproved,
+        });
+    execute_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        add_change_policy,
+    )
+    .unwrap();
+
+    let release_control_operation =
+        RequestOperationInput::ConfigureExternalCanister(ConfigureExternalCanisterOperationInput {
+            canister_id,
+            kind: ConfigureExternalCanisterOperationKindDTO::ReleaseControl(vec![new_owner]),
+        });
+    let release_control_request = submit_request(
+        &env,
+        user,
+        canister_ids.station,
+        release_control_operation.clone(),
+    );
+    env.advance_time(Duration::from_secs(5));
+    env.tick();
+    let release_control_request = get_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        release_control_request,
+    );
+    assert!(matches!(
+        release_control_request.status,
+        RequestStatusDTO::Created
+    ));
+
+    // releasing the control has its own permission, separate from the one of changing the canister
+    let restrict_release_control =
+        RequestOperationInput::EditPermission(EditPermissionOperationInput {
+            resource: station_api::ResourceDTO::ExternalCanister(
+                station_api::ExternalCanisterResourceActionDTO::ReleaseControl(
+                    ExternalCanisterIdDTO::Any,
+                ),
+            ),
+            auth_scope: Some(station_api::AuthScopeDTO::Restricted),
+            user_groups: Some(vec![ADMIN_GROUP_ID.hyphenated().to_string()]),
+            users: Some(vec![]),
+        });
+    execute_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        restrict_release_control,
+    )
+    .unwrap();
+
+    let trap_message = submit_request_with_expected_trap(
+        &env,
+        user,
+        canister_ids.station,
+        release_control_operation,
+    );
+    assert!(
+        trap_message.contains("Unauthorized access to resources: ExternalCanister(ReleaseControl(")
+    );
+
+    // the user can still change the canister
+    execute_request(
+        &env,
+        user,
+        canister_ids.station,
+        RequestOperationInput::ConfigureExternalCanister(ConfigureExternalCanisterOperationInput {
+            canister_id,
+            kind: ConfigureExternalCanisterOperationKindDTO::AddControllers(vec![new_owner]),
+        }),
+    )
+    .unwrap();
+
+    // the admin approves the pending release of the control
+    submit_request_approval(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        release_control_request.clone(),
+        RequestApprovalStatusDTO::Approved,
+    );
+    wait_for_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        release_control_request,
+    )
+    .unwrap();
+
+    assert_eq!(
+        canister_controllers(&env, new_owner, canister_id),
+        vec![new_owner]
+    );
+
+    let res: (ApiResult<GetExternalCanisterResponse>,) = update_candid_as(
+        &env,
+        canister_ids.station,
+        WALLET_ADMIN_USER,
+        "get_external_canister",
+        (GetExternalCanisterInput { canister_id },),
+    )
+    .unwrap();
+    assert!(matches!(
+        res.0.unwrap().canister.state,
+        ExternalCanisterStateDTO::Archived
+    ));
+}
+
+#[test]
+fn controllers_changed_outside_of_the_station_are_reported() {
+    let TestEnv {
+        env, canister_ids, ..
+    } = setup_new_env();
+
+    let canister_id = create_external_canister(&env, canister_ids.station, "drifting");
+    let reconcile_controllers = |env: &PocketIc| {
+        env.advance_time(Duration::from_secs(6 * 60 * 60 + 1));
+        for _ in 0..10 {
+            env.tick();
+        }
+    };
+    let list_drift_notifications = |env: &PocketIc| {
+        let res: (ApiResult<ListNotificationsResponse>,) = update_candid_as(
+            env,
+            canister_ids.station,
+            WALLET_ADMIN_USER,
+            "list_notifications",
+            (ListNotificationsInput {
+                status: None,
+                notification_type: None,
+                from_dt: None,
+                to_dt: None,
+            },),
+        )
+        .unwrap();
+
+        res.0
+            .unwrap()
+            .notifications
+            .into_iter()
+            .filter(|notification| {
+                notification
+                    .title
+                    .contains("changed outside of the station")
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // the first reconciliation records the controllers that the canister was created with
+    reconcile_controllers(&env);
+    assert!(list_drift_notifications(&env).is_empty());
+
+    // a controller is added without going through the station
+    let controller = user_test_id(0);
+    env.set_controllers(
+        canister_id,
+        Some(canister_ids.station),
+        vec![canister_ids.station, controller],
+    )
+    .unwrap();
+
+    reconcile_controllers(&env);
+    let notifications = list_drift_notifications(&env);
+    assert_eq!(notifications.len(), 1);
+    assert!(notifications[0].title.contains(&canister_id.to_text()));
+    assert!(notifications[0]
+        .message
+        .as_ref()
+        .unwrap()
+        .contains(&format!("Added: {controller}.")));
+
+    // the controllers changed by the station are not reported
+    execute_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        RequestOperationInput::ConfigureExternalCanister(ConfigureExternalCanisterOperationInput {
+            canister_id,
+            kind: ConfigureExternalCanisterOperationKindDTO::RemoveControllers(vec![controller]),
+        }),
+    )
+    .unwrap();
+
+    reconcile_controllers(&env);
+    assert_eq!(list_drift_notifications(&env).len(), 1);
+}
@@ -26,6 +26,7 @@ fn notification_authorization() {
         module_extra_chunks: None,
         arg: None,
         take_backup_snapshot: None,
+        module_registry: None,
     };
     let request_status = execute_request_with_extra_ticks(
         &env,
@@ -1,7 +1,7 @@
 use crate::setup::{setup_new_env, WALLET_ADMIN_USER};
 use crate::utils::user_test_id;
 use crate::TestEnv;
-use pocket_ic::update_candid_as;
+use pocket_ic::{query_candid_as, update_candid_as};
 use station_api::{
     AddUserOperationInput, ApiErrorDTO, CreateRequestInput, CreateRequestResponse, GetRequestInput,
     GetRequestResponse, RequestExecutionScheduleDTO, RequestOperationDTO, RequestOperationInput,
@@ -56,7 +56,7 @@ fn register_user_successful() {
         request_id: request_dto.id,
         with_full_info: Some(false),
     };
-    let res: (Result<GetRequestResponse, ApiErrorDTO>,) = update_candid_as(
+    let res: (Result<GetRequestResponse, ApiErrorDTO>,) = query_candid_as(
         &env,
         canister_ids.station,
         WALLET_ADMIN_USER,
@@ -0,0 +1,181 @@
+use crate::setup::{get_canister_wasm, setup_new_env, WALLET_ADMIN_USER};
+use crate::utils::{add_user, execute_request, get_request, try_get_request, user_test_id};
+use crate::TestEnv;
+use candid::Principal;
+use orbit_essentials::api::ApiResult;
+use pocket_ic::{query_candid_as, PocketIc};
+use station_api::{
+    DeployRequestArchiveInput, ListRequestsInput, ListRequestsResponse,
+    ManageSystemInfoOperationInput, PaginationInput, RequestArchiveConfigInput,
+    RequestOperationInput,
+};
+use std::time::Duration;
+
+const MIN_ARCHIVE_AGE_SECS: u64 = 24 * 60 * 60;
+
+fn list_requests(
+    env: &PocketIc,
+    station_id: Principal,
+    caller: Principal,
+    offset: Option<u64>,
+) -> ListRequestsResponse {
+    let (response,) = query_candid_as::<_, (ApiResult<ListRequestsResponse>,)>(
+        env,
+        station_id,
+        caller,
+        "list_requests",
+        (ListRequestsInput {
+            requester_ids: None,
+            approver_ids: None,
+            statuses: None,
+            operation_types: None,
+            expiration_from_dt: None,
+            expiration_to_dt: None,
+            created_from_dt: None,
+            created_to_dt: None,
+            paginate: Some(PaginationInput {
+                offset,
+                limit: None,
+            }),
+            sort_by: None,
+            only_approvable: false,
+            with_evaluation_results: false,
+            deduplication_keys: None,
+            tags: None,
+            search_term: None,
+            resources: None,
+        },),
+    )
+    .unwrap();
+
+    response.unwrap()
+}
+
+#[test]
+fn finalized_requests_are_moved_to_the_archive() {
+    let TestEnv {
+        env, canister_ids, ..
+    } = setup_new_env();
+
+    let archived_request = execute_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        RequestOperationInput::ManageSystemInfo(ManageSystemInfoOperationInput {
+            name: None,
+            cycle_obtain_strategy: None,
+            max_station_backup_snapshots: None,
+            max_upgrader_backup_snapshots: None,
+            request_archive: Some(RequestArchiveConfigInput {
+                min_age_secs: Some(MIN_ARCHIVE_AGE_SECS),
+                deploy: Some(DeployRequestArchiveInput {
+                    wasm_module: get_canister_wasm("archive"),
+                    initial_cycles: None,
+                }),
+            }),
+            control_panel_id: None,
+        }),
+    )
+    .unwrap();
+
+    // the archival job runs every 6 hours and only moves the requests older than the minimum age
+    for _ in 0..6 {
+        env.advance_time(Duration::from_secs(6 * 60 * 60));
+        for _ in 0..5 {
+            env.tick();
+        }
+    }
+
+    let local_request = execute_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        RequestOperationInput::ManageSystemInfo(ManageSystemInfoOperationInput {
+            name: Some("Archived Station".to_string()),
+            cycle_obtain_strategy: None,
+            max_station_backup_snapshots: None,
+            max_upgrader_backup_snapshots: None,
+            request_archive: None,
+            control_panel_id: None,
+        }),
+    )
+    .unwrap();
+
+    // the archived request is served from the archive
+    let request = try_get_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        archived_request.clone(),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(request.id, archived_request.id);
+
+    let request = get_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        local_request.clone(),
+    );
+    assert_eq!(request.id, local_request.id);
+
+    // the archived requests follow the requests kept in the station
+    let response = list_requests(&env, canister_ids.station, WALLET_ADMIN_USER, None);
+    let local_position = response
+        .requests
+        .iter()
+        .position(|request| request.id == local_request.id)
+        .unwrap();
+    let archived_position = response
+        .requests
+        .iter()
+        .position(|request| request.id == archived_request.id)
+        .unwrap();
+    assert!((local_position as u64) < response.total);
+    assert!((archived_position as u64) >= response.total);
+    assert!(!response.privileges[archived_position].can_approve);
+    assert_eq!(response.requests.len(), response.privileges.len());
+    assert_eq!(response.requests.len(), response.additional_info.len());
+    assert_eq!(response.next_offset, None);
+
+    // offsets past the total only list archived requests
+    let archived_response = list_requests(
+        &env,
+        canister_ids.station,
+        WALLET_ADMIN_USER,
+        Some(response.total),
+    );
+    assert_eq!(
+        archived_response
+            .requests
+            .iter()
+            .map(|request| request.id.clone())
+            .collect::<Vec<_>>(),
+        response.requests[response.total as usize..]
+            .iter()
+            .map(|request| request.id.clone())
+            .collect::<Vec<_>>()
+    );
+
+    // the read access is checked once the request is fetched from the archive
+    let err = try_get_request(
+        &env,
+        Principal::from_slice(&[42; 29]),
+        canister_ids.station,
+        archived_request.clone(),
+    )
+    .unwrap()
+    .unwrap_err();
+    assert_eq!(err.code, "UNAUTHORIZED");
+
+    // authenticated users can read all requests by default, including the archived ones
+    let other_user = user_test_id(0);
+    add_user(&env, other_user, vec![], canister_ids.station);
+
+    let response = list_requests(&env, canister_ids.station, other_user, None);
+    assert!(response
+        .requests
+        .iter()
+        .any(|request| request.id == archived_request.id));
+}
@@ -3,7 +3,7 @@ use crate::utils::user_test_id;
 use crate::TestEnv;
 use candid::Principal;
 use orbit_essentials::utils::timestamp_to_rfc3339;
-use pocket_ic::{update_candid_as, PocketIc};
+use pocket_ic::{query_candid_as, update_candid_as, PocketIc};
 use station_api::{
     AddUserOperationInput, ApiErrorDTO, CreateRequestInput, CreateRequestResponse,
     ListRequestsInput, ListRequestsResponse, RequestExecutionScheduleDTO, RequestOperationInput,
@@ -17,7 +17,7 @@ fn assert_list_requests_only_one_active_requests_with_dedup_key(
     deduplication_key: &str,
 ) {
     // list the requests, see that there is a request with the same deduplication key
-    let res: (Result<ListRequestsResponse, ApiErrorDTO>,) = update_candid_as(
+    let res: (Result<ListRequestsResponse, ApiErrorDTO>,) = query_candid_as(
         env,
         canister_id,
         WALLET_ADMIN_USER,
@@ -37,6 +37,8 @@ fn assert_list_requests_only_one_active_requests_with_dedup_key(
             only_approvable: false,
             with_evaluation_results: false,
             tags: None,
+            search_term: None,
+            resources: None,
         },),
     )
     .unwrap();
@@ -5,16 +5,17 @@ use crate::utils::{compress_to_gzip, create_file, read_file, NNS_ROOT_CANISTER_I
 use crate::TestEnv;
 use candid::{Encode, Principal};
 use orbit_essentials::api::ApiResult;
-use pocket_ic::{update_candid_as, PocketIc};
+use pocket_ic::{query_candid_as, update_candid_as, PocketIc};
 
-const CURRENT_BASELINE_NR_OF_REQUEST_POLICIES: usize = 24; // can be found in the station core/init.rs
-const CURRENT_BASELINE_NR_PERMISSIONS: usize = 45; // can be found in the station core/init.rs
+const CURRENT_BASELINE_NR_OF_REQUEST_POLICIES: usize = 27; // can be found in the station core/init.rs
+const CURRENT_BASELINE_NR_PERMISSIONS: usize = 46; // can be found in the station core/init.rs
 
 const PREVIOUS_BASELINE_NR_OF_REQUEST_POLICIES: usize = 21; // baseline in the previous memory version core/init.rs
 const PREVIOUS_BASELINE_NR_PERMISSIONS: usize = 40; // baseline in the previous memory version core/init.rs
 
-const POLICIES_ADDED_AT_MIGRATION: usize = 0;
-const PERMISSIONS_ADDED_AT_MIGRATION: usize = 0;
+// the station freeze, unfreeze and release external canister control policies
+const POLICIES_ADDED_AT_MIGRATION: usize = 3;
+const PERMISSIONS_ADDED_AT_MIGRATION: usize = 1; // the release external canister control permission
 
 const USER_GROUPS_NR: usize = 10;
 const USER_NR: usize = 10;
@@ -455,7 +456,7 @@ fn assert_can_list_requests(
     requester: Principal,
     expected: usize,
 ) {
-    let res: (ApiResult<station_api::ListRequestsResponse>,) = update_candid_as(
+    let res: (ApiResult<station_api::ListRequestsResponse>,) = query_candid_as(
         env,
         station_id,
         requester,
@@ -478,6 +479,8 @@ fn assert_can_list_requests(
             }),
             deduplication_keys: None,
             tags: None,
+            search_term: None,
+            resources: None,
         },),
     )
     .unwrap();
@@ -22,6 +22,7 @@ pub fn perform_upgrader_update(
                 module_extra_chunks: None,
                 arg: None,
                 take_backup_snapshot: None,
+                module_registry: None,
             },
         ),
     );
@@ -53,6 +54,7 @@ pub fn perform_station_update(
             module_extra_chunks: Some(module_extra_chunks),
             arg: None,
             take_backup_snapshot: None,
+            module_registry: None,
         });
 
     let request_station_upgrade = submit_request(
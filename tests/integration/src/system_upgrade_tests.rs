@@ -1,19 +1,26 @@
 use crate::setup::{get_canister_wasm, setup_new_env, WALLET_ADMIN_USER};
 use crate::utils::{
     execute_request, execute_request_with_extra_ticks, get_core_canister_health_status,
-    get_request, get_system_info, submit_delayed_request_raw, submit_request, try_get_request,
-    upload_canister_chunks_to_asset_canister, wait_for_request, wait_for_request_with_extra_ticks,
+    get_request, get_system_info, submit_delayed_request_raw, submit_request, submit_request_raw,
+    try_get_request, upload_canister_chunks_to_asset_canister, wait_for_request,
+    wait_for_request_with_extra_ticks,
 };
 use crate::{CanisterIds, TestEnv};
 use candid::{Encode, Principal};
+use control_panel_api::{
+    AddRegistryEntryInput, AddRegistryEntryResponse, RegistryEntryInput, RegistryEntryValueInput,
+    WasmModuleRegistryEntryValueInput,
+};
 use ic_management_canister_types::CanisterIdRecord;
 use orbit_essentials::api::ApiResult;
 use orbit_essentials::utils::rfc3339_to_timestamp;
 use pocket_ic::{update_candid_as, PocketIc};
 use station_api::{
     HealthStatus, ManageSystemInfoOperationInput, NotifyFailedStationUpgradeInput,
-    RequestOperationInput, RequestStatusDTO, SystemInstall, SystemRestoreOperationInput,
-    SystemRestoreTargetDTO, SystemUpgrade, SystemUpgradeOperationInput, SystemUpgradeTargetDTO,
+    RequestOperationDTO, RequestOperationInput, RequestStatusDTO, SystemInstall,
+    SystemRestoreOperationInput, SystemRestoreTargetDTO, SystemUpgrade,
+    SystemUpgradeOperationInput, SystemUpgradeTargetDTO, WasmModuleRegistryReferenceDTO,
+    WasmModuleRegistryVersionDTO,
 };
 use std::time::Duration;
 use upgrader_api::InitArg;
@@ -144,6 +151,7 @@ fn failed_station_upgrade() {
             module_extra_chunks: None,
             arg: None,
             take_backup_snapshot: None,
+            module_registry: None,
         });
 
     do_failed_system_upgrade(
@@ -173,6 +181,7 @@ fn too_many_chunks() {
             module_extra_chunks: Some(module_extra_chunks),
             arg: None,
             take_backup_snapshot: None,
+            module_registry: None,
         });
 
     do_failed_system_upgrade(
@@ -200,6 +209,7 @@ fn too_large_wasm() {
             module_extra_chunks: Some(module_extra_chunks),
             arg: None,
             take_backup_snapshot: None,
+            module_registry: None,
         });
 
     do_failed_system_upgrade(
@@ -250,6 +260,7 @@ fn system_upgrade_from_chunks() {
                 module_extra_chunks: Some(module_extra_chunks.clone()),
                 arg: Some(arg_bytes.clone()),
                 take_backup_snapshot: None,
+                module_registry: None,
             });
 
         // successful upgrade
@@ -272,6 +283,7 @@ fn system_upgrade_from_chunks() {
                 module_extra_chunks: Some(module_extra_chunks.clone()),
                 arg: Some(arg_bytes),
                 take_backup_snapshot: None,
+                module_registry: None,
             });
 
         // failed upgrade
@@ -284,6 +296,119 @@ fn system_upgrade_from_chunks() {
     }
 }
 
+#[test]
+fn system_upgrade_from_registry() {
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = setup_new_env();
+
+    // publish the station module in the registry of the control panel, the base chunk is stored as
+    // an artifact of the control panel and the extra chunks in an asset canister
+    let canister_wasm = get_canister_wasm("station").to_vec();
+    let (base_chunk, module_extra_chunks) =
+        upload_canister_chunks_to_asset_canister(&env, canister_wasm, 500_000);
+    let (result,) = update_candid_as::<_, (ApiResult<AddRegistryEntryResponse>,)>(
+        &env,
+        canister_ids.control_panel,
+        controller,
+        "add_registry_entry",
+        (AddRegistryEntryInput {
+            entry: RegistryEntryInput {
+                name: "@orbit/station".to_string(),
+                description: "The Orbit station".to_string(),
+                tags: vec!["latest".to_string()],
+                categories: vec![],
+                metadata: vec![],
+                value: RegistryEntryValueInput::WasmModule(WasmModuleRegistryEntryValueInput {
+                    wasm_module: base_chunk,
+                    wasm_module_artifact_id: None,
+                    version: "999.0.0".to_string(),
+                    dependencies: vec![],
+                    module_extra_chunks: Some(module_extra_chunks),
+                }),
+            },
+        },),
+    )
+    .unwrap();
+    result.unwrap();
+
+    let station_upgrade_operation =
+        RequestOperationInput::SystemUpgrade(SystemUpgradeOperationInput {
+            target: SystemUpgradeTargetDTO::UpgradeStation,
+            module: vec![],
+            module_extra_chunks: None,
+            arg: Some(Encode!(&SystemInstall::Upgrade(SystemUpgrade { name: None })).unwrap()),
+            take_backup_snapshot: None,
+            module_registry: Some(WasmModuleRegistryReferenceDTO {
+                registry_canister_id: canister_ids.control_panel,
+                name: "@orbit/station".to_string(),
+                version: WasmModuleRegistryVersionDTO::Tag("latest".to_string()),
+            }),
+        });
+
+    // the station in the test environment is installed by the controller, so it does not trust the
+    // control panel of the environment as registry yet
+    let system_info = get_system_info(&env, WALLET_ADMIN_USER, canister_ids.station);
+    assert_eq!(system_info.control_panel_id, Some(controller));
+    let err = submit_request_raw(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        station_upgrade_operation.clone(),
+    )
+    .unwrap()
+    .0
+    .unwrap_err();
+    assert!(err
+        .message
+        .unwrap()
+        .contains("is not the control panel trusted by the station"));
+
+    execute_request(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        RequestOperationInput::ManageSystemInfo(ManageSystemInfoOperationInput {
+            name: None,
+            cycle_obtain_strategy: None,
+            max_station_backup_snapshots: None,
+            max_upgrader_backup_snapshots: None,
+            request_archive: None,
+            control_panel_id: Some(canister_ids.control_panel),
+        }),
+    )
+    .unwrap();
+
+    let last_upgrade_timestamp =
+        get_system_info(&env, WALLET_ADMIN_USER, canister_ids.station).last_upgrade_timestamp;
+
+    let request = execute_request_with_extra_ticks(
+        &env,
+        WALLET_ADMIN_USER,
+        canister_ids.station,
+        station_upgrade_operation,
+        STATION_UPGRADE_EXTRA_TICKS,
+    )
+    .unwrap();
+
+    // the approvers see the version that was resolved in the registry
+    let RequestOperationDTO::SystemUpgrade(operation) = request.operation else {
+        panic!("Unexpected request operation: {:?}", request.operation);
+    };
+    let resolved = operation.module_registry_entry.unwrap();
+    assert_eq!(resolved.registry_canister_id, canister_ids.control_panel);
+    assert_eq!(resolved.version, "999.0.0");
+
+    let health_status =
+        get_core_canister_health_status(&env, WALLET_ADMIN_USER, canister_ids.station);
+    assert_eq!(health_status, HealthStatus::Healthy);
+    let system_info = get_system_info(&env, WALLET_ADMIN_USER, canister_ids.station);
+    assert!(system_info.last_upgrade_timestamp > last_upgrade_timestamp);
+}
+
 #[test]
 fn unauthorized_notify_failed_station_upgrade() {
     let TestEnv {
@@ -363,6 +488,7 @@ fn delayed_system_upgrade() {
             module_extra_chunks: Some(module_extra_chunks),
             arg: Some(upgrader_init_arg_bytes),
             take_backup_snapshot: None,
+            module_registry: None,
         });
 
     let delay = Duration::from_secs(30 * 24 * 60 * 60);
@@ -645,6 +771,7 @@ fn backup_snapshot() {
             module_extra_chunks: Some(module_extra_chunks),
             arg: Some(arg_bytes),
             take_backup_snapshot: Some(true),
+            module_registry: None,
         };
 
         // there should be no snapshots yet
@@ -774,12 +901,16 @@ fn set_max_backup_snapshots() {
                 name: None,
                 max_station_backup_snapshots: Some(max_backup_snapshots),
                 max_upgrader_backup_snapshots: None,
+                request_archive: None,
+                control_panel_id: None,
             },
             SystemUpgradeTargetDTO::UpgradeUpgrader => ManageSystemInfoOperationInput {
                 cycle_obtain_strategy: None,
                 name: None,
                 max_station_backup_snapshots: None,
                 max_upgrader_backup_snapshots: Some(max_backup_snapshots),
+                request_archive: None,
+                control_panel_id: None,
             },
         };
         execute_request(
@@ -817,6 +948,7 @@ fn set_max_backup_snapshots() {
             module_extra_chunks: Some(module_extra_chunks),
             arg: Some(arg_bytes),
             take_backup_snapshot: Some(true),
+            module_registry: None,
         };
 
         // set the maximum number of backup snapshots to `initial_max_backup_snapshots`.
@@ -115,7 +115,7 @@ fn make_transfer_successful() {
         request_id: account_creation_request_dto.id,
         with_full_info: Some(false),
     };
-    let res: (ApiResult<CreateRequestResponse>,) = update_candid_as(
+    let res: (ApiResult<CreateRequestResponse>,) = query_candid_as(
         &env,
         canister_ids.station,
         WALLET_ADMIN_USER,
@@ -218,7 +218,7 @@ fn make_transfer_successful() {
         request_id: request_dto.id.clone(),
         with_full_info: Some(false),
     };
-    let res: (Result<GetRequestResponse, ApiErrorDTO>,) = update_candid_as(
+    let res: (Result<GetRequestResponse, ApiErrorDTO>,) = query_candid_as(
         &env,
         canister_ids.station,
         WALLET_ADMIN_USER,
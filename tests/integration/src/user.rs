@@ -62,6 +62,7 @@ fn cancel_pending_requests() {
         module_extra_chunks: None,
         arg: None,
         take_backup_snapshot: None,
+        module_registry: None,
     });
     let mut alice_request_dtos = vec![];
     for _ in 0..10 {
@@ -133,7 +133,7 @@ pub fn try_get_request(
         request_id: request.id,
         with_full_info: Some(false),
     };
-    update_candid_as::<_, (Result<GetRequestResponse, ApiErrorDTO>,)>(
+    query_candid_as::<_, (Result<GetRequestResponse, ApiErrorDTO>,)>(
         env,
         station_canister_id,
         user_id,
@@ -737,7 +737,7 @@ pub fn create_account(
         request_id: account_creation_request_dto.id,
         with_full_info: Some(false),
     };
-    let res: (ApiResult<CreateRequestResponse>,) = update_candid_as(
+    let res: (ApiResult<CreateRequestResponse>,) = query_candid_as(
         env,
         station_id,
         requester,
@@ -811,7 +811,7 @@ pub fn create_transfer(
         request_id: request_dto.id.clone(),
         with_full_info: Some(false),
     };
-    let res: (Result<GetRequestResponse, ApiErrorDTO>,) = update_candid_as(
+    let res: (Result<GetRequestResponse, ApiErrorDTO>,) = query_candid_as(
         env,
         station_id,
         requester,
@@ -1340,6 +1340,7 @@ pub(crate) fn upgrade_station(
             module_extra_chunks: Some(module_extra_chunks),
             arg: Some(station_init_arg_bytes),
             take_backup_snapshot: None,
+            module_registry: None,
         });
 
     execute_request_with_extra_ticks(
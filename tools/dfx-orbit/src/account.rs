@@ -0,0 +1,312 @@
+//! Inspects the accounts of the station.
+
+mod amount;
+
+pub(crate) use amount::{format_amount, parse_amount};
+
+use crate::{
+    util::{fetch_all_pages, print_as_json},
+    DfxOrbit,
+};
+use anyhow::bail;
+use clap::{Parser, Subcommand};
+use station_api::{
+    AccountBalanceDTO, AccountDTO, AssetDTO, FetchAccountBalancesInput, GetAccountInput,
+    ListAccountsInput, ListAssetsInput,
+};
+use std::{collections::HashMap, fmt::Write};
+use tabled::{
+    settings::{Settings, Style},
+    Table,
+};
+
+/// Inspect the accounts of the station
+#[derive(Debug, Clone, Parser)]
+pub struct AccountArgs {
+    /// Return output as JSON
+    #[clap(short, long)]
+    pub(crate) json: bool,
+
+    #[clap(subcommand)]
+    pub(crate) action: AccountActionArgs,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum AccountActionArgs {
+    /// List the accounts of the station
+    List(AccountListArgs),
+    /// Show the details of an account
+    Show(AccountShowArgs),
+    /// Fetch the current balances of an account
+    Balance(AccountBalanceArgs),
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct AccountListArgs {
+    /// Show only accounts whose name contains this search term
+    #[clap(long)]
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct AccountShowArgs {
+    /// The name or ID of the account
+    pub account: String,
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct AccountBalanceArgs {
+    /// The name or ID of the account
+    pub account: String,
+}
+
+impl AccountArgs {
+    pub(crate) async fn execute(self, dfx_orbit: &DfxOrbit) -> anyhow::Result<()> {
+        match self.action {
+            AccountActionArgs::List(args) => {
+                let accounts = dfx_orbit.list_all_accounts(args.search).await?;
+                if self.json {
+                    print_as_json(&accounts)?;
+                } else {
+                    let assets = dfx_orbit.assets_by_id().await?;
+                    println!("{}", display_account_list(&accounts, &assets));
+                }
+            }
+            AccountActionArgs::Show(args) => {
+                let account = dfx_orbit.resolve_account(&args.account).await?;
+                if self.json {
+                    print_as_json(&account)?;
+                } else {
+                    let assets = dfx_orbit.assets_by_id().await?;
+                    println!("{}", display_account(&account, &assets)?);
+                }
+            }
+            AccountActionArgs::Balance(args) => {
+                let account = dfx_orbit.resolve_account(&args.account).await?;
+                let balances = dfx_orbit
+                    .station
+                    .fetch_account_balances(FetchAccountBalancesInput {
+                        account_ids: vec![account.id.clone()],
+                    })
+                    .await?
+                    .balances
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+                if self.json {
+                    print_as_json(&balances)?;
+                } else {
+                    let assets = dfx_orbit.assets_by_id().await?;
+                    let mut output = String::new();
+                    for balance in balances {
+                        let symbol = assets
+                            .get(&balance.asset_id)
+                            .map(|asset| asset.symbol.as_str())
+                            .unwrap_or(balance.asset_id.as_str());
+                        writeln!(
+                            output,
+                            "{} {} (updated at {})",
+                            format_amount(&balance.balance, balance.decimals),
+                            symbol,
+                            balance.last_update_timestamp
+                        )?;
+                    }
+                    print!("{output}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl DfxOrbit {
+    /// Looks up an account by its exact name or by its ID.
+    pub(crate) async fn resolve_account(&self, name_or_id: &str) -> anyhow::Result<AccountDTO> {
+        let mut accounts = self
+            .list_all_accounts(Some(name_or_id.to_string()))
+            .await?
+            .into_iter()
+            .filter(|account| account.name == name_or_id)
+            .collect::<Vec<_>>();
+        match accounts.len() {
+            0 => (),
+            1 => return Ok(accounts.remove(0)),
+            _ => bail!("There are multiple accounts named \"{name_or_id}\", use the account ID"),
+        }
+
+        match self
+            .station
+            .get_account(GetAccountInput {
+                account_id: name_or_id.to_string(),
+            })
+            .await
+        {
+            Ok(response) => Ok(response.account),
+            Err(_) => bail!("No account with the name or ID \"{name_or_id}\""),
+        }
+    }
+
+    /// Looks up an asset by its symbol or by its ID.
+    ///
+    /// Only the assets held by the account are considered. The asset can be omitted if the account
+    /// holds a single asset.
+    pub(crate) async fn resolve_account_asset(
+        &self,
+        account: &AccountDTO,
+        symbol_or_id: Option<&str>,
+    ) -> anyhow::Result<AssetDTO> {
+        let mut assets = self.assets_by_id().await?;
+        let mut account_assets = account
+            .assets
+            .iter()
+            .filter_map(|account_asset| assets.remove(&account_asset.asset_id))
+            .collect::<Vec<_>>();
+
+        let Some(symbol_or_id) = symbol_or_id else {
+            if account_assets.len() != 1 {
+                bail!(
+                    "The account \"{}\" holds {} assets, specify the asset to use",
+                    account.name,
+                    account_assets.len()
+                );
+            }
+            return Ok(account_assets.remove(0));
+        };
+
+        let mut matching = account_assets
+            .into_iter()
+            .filter(|asset| {
+                asset.id == symbol_or_id || asset.symbol.eq_ignore_ascii_case(symbol_or_id)
+            })
+            .collect::<Vec<_>>();
+        match matching.len() {
+            0 => bail!(
+                "The account \"{}\" does not hold the asset \"{symbol_or_id}\"",
+                account.name
+            ),
+            1 => Ok(matching.remove(0)),
+            _ => bail!(
+                "There are multiple assets with the symbol \"{symbol_or_id}\", use the asset ID"
+            ),
+        }
+    }
+
+    pub(crate) async fn list_all_accounts(
+        &self,
+        search: Option<String>,
+    ) -> anyhow::Result<Vec<AccountDTO>> {
+        fetch_all_pages(|paginate| async {
+            let response = self
+                .station
+                .list_accounts(ListAccountsInput {
+                    search_term: search.clone(),
+                    paginate: Some(paginate),
+                })
+                .await?;
+            anyhow::Ok((response.accounts, response.next_offset))
+        })
+        .await
+    }
+
+    pub(crate) async fn assets_by_id(&self) -> anyhow::Result<HashMap<String, AssetDTO>> {
+        let assets = fetch_all_pages(|paginate| async {
+            let response = self
+                .station
+                .list_assets(ListAssetsInput {
+                    paginate: Some(paginate),
+                })
+                .await?;
+            anyhow::Ok((response.assets, response.next_offset))
+        })
+        .await?;
+
+        Ok(assets
+            .into_iter()
+            .map(|asset| (asset.id.clone(), asset))
+            .collect())
+    }
+}
+
+fn display_account_list(accounts: &[AccountDTO], assets: &HashMap<String, AssetDTO>) -> String {
+    let data_iter = accounts.iter().map(|account| {
+        [
+            account.id.clone(),
+            account.name.clone(),
+            account
+                .assets
+                .iter()
+                .map(|account_asset| {
+                    display_asset_balance(
+                        &account_asset.asset_id,
+                        account_asset.balance.as_ref(),
+                        assets,
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        ]
+    });
+    let titled_iter = std::iter::once([
+        String::from("ID"),
+        String::from("Name"),
+        String::from("Balances"),
+    ])
+    .chain(data_iter);
+
+    let table_config = Settings::default().with(Style::psql());
+    Table::from_iter(titled_iter).with(table_config).to_string()
+}
+
+fn display_account(
+    account: &AccountDTO,
+    assets: &HashMap<String, AssetDTO>,
+) -> anyhow::Result<String> {
+    let mut output = String::new();
+
+    writeln!(output, "Name: {}", account.name)?;
+    writeln!(output, "Id: {}", account.id)?;
+    writeln!(output, "Balances:")?;
+    for account_asset in &account.assets {
+        writeln!(
+            output,
+            "\t{}",
+            display_asset_balance(
+                &account_asset.asset_id,
+                account_asset.balance.as_ref(),
+                assets
+            )
+        )?;
+    }
+    writeln!(output, "Addresses:")?;
+    for address in &account.addresses {
+        writeln!(output, "\t{} ({})", address.address, address.format)?;
+    }
+    writeln!(
+        output,
+        "Last modified: {}",
+        account.last_modification_timestamp
+    )?;
+
+    Ok(output)
+}
+
+/// Displays the cached balance of an account asset, e.g. `1.5 ICP`.
+fn display_asset_balance(
+    asset_id: &str,
+    balance: Option<&AccountBalanceDTO>,
+    assets: &HashMap<String, AssetDTO>,
+) -> String {
+    let symbol = assets
+        .get(asset_id)
+        .map(|asset| asset.symbol.as_str())
+        .unwrap_or(asset_id);
+
+    match balance {
+        Some(balance) => format!(
+            "{} {symbol}",
+            format_amount(&balance.balance, balance.decimals)
+        ),
+        None => format!("- {symbol}"),
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::{bail, Context};
+use candid::Nat;
+use std::str::FromStr;
+
+/// Parses a human readable amount, such as `1.5`, into the smallest unit of an asset.
+pub(crate) fn parse_amount(amount: &str, decimals: u32) -> anyhow::Result<Nat> {
+    let (integer, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if integer.is_empty() && fraction.is_empty() {
+        bail!("The amount \"{amount}\" is empty");
+    }
+    if !integer
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        bail!("The amount \"{amount}\" is not a positive decimal number");
+    }
+    if fraction.len() > decimals as usize {
+        bail!("The amount \"{amount}\" has more than {decimals} decimals");
+    }
+
+    let digits = format!("{integer}{fraction:0<width$}", width = decimals as usize);
+    Nat::from_str(&digits).with_context(|| format!("Invalid amount \"{amount}\""))
+}
+
+/// Formats an amount in the smallest unit of an asset as a human readable amount, such as `1.5`.
+pub(crate) fn format_amount(amount: &Nat, decimals: u32) -> String {
+    let decimals = decimals as usize;
+    let digits = format!(
+        "{:0>width$}",
+        amount.0.to_str_radix(10),
+        width = decimals + 1
+    );
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{integer}.{fraction}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn parses_amounts() {
+        assert_eq!(parse_amount("1.5", 8).unwrap(), Nat::from(150_000_000u64));
+        assert_eq!(parse_amount("0.00000001", 8).unwrap(), Nat::from(1u64));
+        assert_eq!(parse_amount(".5", 2).unwrap(), Nat::from(50u64));
+        assert_eq!(parse_amount("42", 0).unwrap(), Nat::from(42u64));
+
+        assert!(parse_amount("0.000000001", 8).is_err());
+        assert!(parse_amount("-1", 8).is_err());
+        assert!(parse_amount("1.2.3", 8).is_err());
+        assert!(parse_amount(".", 8).is_err());
+    }
+
+    #[test]
+    fn formats_amounts() {
+        assert_eq!(format_amount(&Nat::from(150_000_000u64), 8), "1.5");
+        assert_eq!(format_amount(&Nat::from(1u64), 8), "0.00000001");
+        assert_eq!(format_amount(&Nat::from(0u64), 8), "0");
+        assert_eq!(format_amount(&Nat::from(42u64), 0), "42");
+    }
+}
@@ -1,14 +1,21 @@
 use std::path::PathBuf;
 
 use crate::{
+    account::AccountArgs,
     asset::{RequestAssetArgs, VerifyAssetArgs},
-    canister::{RequestCanisterArgs, VerifyCanisterArgs},
+    canister::{CanisterArgs, RequestCanisterArgs, VerifyCanisterArgs},
+    config::ConfigArgs,
     dfx::OrbitExtensionAgent,
+    group::{GroupArgs, RequestGroupArgs},
     me::MeArgs,
     permission::RequestPermissionArgs,
+    policy::{PolicyArgs, RequestPolicyArgs},
     review::ReviewArgs,
     station::StationArgs,
+    transfer::RequestTransferArgs,
+    user::{RequestUserArgs, UserArgs},
     util::init_logger,
+    wait::{RequestWaitArgs, WaitOptions},
     DfxOrbit,
 };
 use clap::{Parser, Subcommand};
@@ -60,6 +67,22 @@ pub enum DfxOrbitSubcommands {
     Review(ReviewArgs),
     /// Gets the caller's profile on an Orbit station.
     Me(MeArgs),
+    /// Interact with canisters through the station.
+    #[clap(subcommand)]
+    Canister(CanisterArgs),
+    /// Inspect the accounts of the station.
+    Account(AccountArgs),
+    /// Inspect the users of the station.
+    User(UserArgs),
+    /// Inspect the user groups of the station.
+    Group(GroupArgs),
+    /// Inspect the request policies of the station.
+    Policy(PolicyArgs),
+    /// Manage the station configuration declaratively.
+    #[clap(subcommand)]
+    Config(ConfigArgs),
+    /// Wait until a request is completed, failed, rejected or cancelled
+    Wait(RequestWaitArgs),
 }
 
 /// Request canister changes.
@@ -74,6 +97,13 @@ pub struct RequestArgs {
     #[clap(long)]
     pub summary: Option<String>,
 
+    /// Wait until the request is completed, failed, rejected or cancelled
+    #[clap(long)]
+    pub wait: bool,
+
+    #[clap(flatten)]
+    pub wait_options: WaitOptions,
+
     #[clap(subcommand)]
     pub action: RequestArgsActions,
 }
@@ -88,6 +118,17 @@ pub enum RequestArgsActions {
     /// Request permissions
     #[clap(subcommand)]
     Permission(RequestPermissionArgs),
+    /// Request a transfer from an account of the station
+    Transfer(RequestTransferArgs),
+    /// Request changes to the users
+    #[clap(subcommand)]
+    User(RequestUserArgs),
+    /// Request changes to the user groups
+    #[clap(subcommand)]
+    Group(RequestGroupArgs),
+    /// Request changes to the request policies
+    #[clap(subcommand)]
+    Policy(RequestPolicyArgs),
 }
 
 #[derive(Debug, Clone, Subcommand)]
@@ -96,6 +137,8 @@ pub enum VerifyArgsAction {
     Asset(VerifyAssetArgs),
     /// Request canister operations through Orbit
     Canister(VerifyCanisterArgs),
+    /// Request a transfer from an account of the station
+    Transfer(RequestTransferArgs),
 }
 
 impl DfxOrbitArgs {
@@ -136,12 +179,19 @@ impl DfxOrbitArgs {
                 Ok(())
             }
             DfxOrbitSubcommands::Request(request_args) => {
+                let wait_options = request_args.wait.then(|| request_args.wait_options.clone());
                 let request = dfx_orbit
                     .station
                     .request(request_args.into_request(&dfx_orbit).await?)
                     .await?;
                 dfx_orbit.print_create_request_info(&request);
 
+                if let Some(wait_options) = wait_options {
+                    dfx_orbit
+                        .wait_for_request(&request.request.id, &wait_options)
+                        .await?;
+                }
+
                 Ok(())
             }
             DfxOrbitSubcommands::Verify(verify_args) => {
@@ -166,6 +216,17 @@ impl DfxOrbitArgs {
                 Ok(())
             }
             DfxOrbitSubcommands::Review(review_args) => review_args.execute(&dfx_orbit).await,
+            DfxOrbitSubcommands::Canister(canister_args) => canister_args.execute(&dfx_orbit).await,
+            DfxOrbitSubcommands::Account(account_args) => account_args.execute(&dfx_orbit).await,
+            DfxOrbitSubcommands::User(user_args) => user_args.execute(&dfx_orbit).await,
+            DfxOrbitSubcommands::Group(group_args) => group_args.execute(&dfx_orbit).await,
+            DfxOrbitSubcommands::Policy(policy_args) => policy_args.execute(&dfx_orbit).await,
+            DfxOrbitSubcommands::Config(config_args) => config_args.execute(&dfx_orbit).await,
+            DfxOrbitSubcommands::Wait(wait_args) => {
+                dfx_orbit
+                    .wait_for_request(&wait_args.request_id, &wait_args.options)
+                    .await
+            }
             DfxOrbitSubcommands::Station(_) => unreachable!(),
         }
     }
@@ -181,6 +242,12 @@ impl RequestArgs {
             RequestArgsActions::Permission(permission_args) => {
                 permission_args.into_request(dfx_orbit)?
             }
+            RequestArgsActions::Transfer(transfer_args) => {
+                transfer_args.into_request(dfx_orbit).await?
+            }
+            RequestArgsActions::User(user_args) => user_args.into_request(dfx_orbit).await?,
+            RequestArgsActions::Group(group_args) => group_args.into_request(),
+            RequestArgsActions::Policy(policy_args) => policy_args.into_request(dfx_orbit).await?,
         };
 
         Ok(CreateRequestInput {
@@ -224,6 +291,7 @@ impl VerifyArgs {
         match &self.action {
             VerifyArgsAction::Asset(args) => args.verify(dfx_orbit, request).await?,
             VerifyArgsAction::Canister(args) => args.verify(dfx_orbit, request).await?,
+            VerifyArgsAction::Transfer(args) => args.verify(dfx_orbit, request).await?,
         };
 
         Ok(())
@@ -4,16 +4,34 @@ use station_api::{GetRequestResponse, RequestOperationInput};
 
 mod call;
 mod install;
+mod logs;
 mod settings;
 mod util;
 
 pub use call::RequestCanisterCallArgs;
 pub use install::{CanisterInstallModeArgs, RequestCanisterInstallArgs, WasmMemoryPersistenceArgs};
+pub use logs::CanisterLogsArgs;
 pub use settings::RequestCanisterUpdateSettingsArgs;
 
 // TODO: Support Canister create + integration test
 // TODO: Canister get response functionality
 
+/// Interact with canisters through the station
+#[derive(Debug, Clone, Subcommand)]
+#[clap(version, about, long_about = None)]
+pub enum CanisterArgs {
+    /// Fetch the logs of a canister
+    Logs(CanisterLogsArgs),
+}
+
+impl CanisterArgs {
+    pub(crate) async fn execute(self, dfx_orbit: &DfxOrbit) -> anyhow::Result<()> {
+        match self {
+            CanisterArgs::Logs(args) => args.execute(dfx_orbit).await,
+        }
+    }
+}
+
 /// Request canister operations through Orbit
 #[derive(Debug, Clone, Parser)]
 pub struct RequestCanisterArgs {
@@ -51,7 +69,7 @@ impl RequestCanisterActionArgs {
     ) -> anyhow::Result<RequestOperationInput> {
         match self {
             RequestCanisterActionArgs::Install(args) => args.into_request(dfx_orbit).await,
-            RequestCanisterActionArgs::Call(args) => args.into_request(dfx_orbit),
+            RequestCanisterActionArgs::Call(args) => args.into_request(dfx_orbit).await,
             RequestCanisterActionArgs::UpdateSettings(args) => args.into_request(dfx_orbit).await,
         }
     }
@@ -83,7 +101,7 @@ impl VerifyCanisterArgs {
     ) -> anyhow::Result<()> {
         match &self.action {
             VerifyCanisterActionArgs::Install(args) => args.verify(dfx_orbit, request)?,
-            VerifyCanisterActionArgs::Call(args) => args.verify(dfx_orbit, request)?,
+            VerifyCanisterActionArgs::Call(args) => args.verify(dfx_orbit, request).await?,
             VerifyCanisterActionArgs::UpdateSettings(args) => {
                 args.verify(dfx_orbit, request).await?
             }
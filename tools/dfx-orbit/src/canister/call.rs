@@ -1,11 +1,12 @@
 use super::util::parse_arguments;
 use crate::{canister::util::log_hashes, DfxOrbit};
-use anyhow::bail;
+use anyhow::{bail, Context};
+use candid::Principal;
 use clap::Parser;
 use sha2::{Digest, Sha256};
 use station_api::{
     CallExternalCanisterOperationDTO, CallExternalCanisterOperationInput, CanisterMethodDTO,
-    GetRequestResponse, RequestOperationDTO, RequestOperationInput,
+    GetExternalCanisterInput, GetRequestResponse, RequestOperationDTO, RequestOperationInput,
 };
 use std::fmt::Write;
 
@@ -17,6 +18,9 @@ pub struct RequestCanisterCallArgs {
     /// The name of the method to call.
     pub method_name: String,
     /// The candid argument to pass to the method.
+    ///
+    /// If a Candid interface is registered for the canister in the station,
+    /// the argument is type-checked and encoded against it.
     pub argument: Option<String>,
     // TODO: The format of the argument.
     // #[clap(short, long)]
@@ -34,12 +38,12 @@ pub struct RequestCanisterCallArgs {
 
 impl RequestCanisterCallArgs {
     /// Converts the CLI arg stype into the equivalent Orbit API type.
-    pub(crate) fn into_request(
+    pub(crate) async fn into_request(
         self,
         dfx_orbit: &DfxOrbit,
     ) -> anyhow::Result<RequestOperationInput> {
         let canister_id = dfx_orbit.canister_id(&self.canister)?;
-        let arg = parse_arguments(&self.argument, &self.arg_file, &self.raw_arg)?;
+        let arg = self.encode_argument(dfx_orbit, canister_id).await?;
 
         Ok(RequestOperationInput::CallExternalCanister(
             CallExternalCanisterOperationInput {
@@ -54,13 +58,13 @@ impl RequestCanisterCallArgs {
         ))
     }
 
-    pub(crate) fn verify(
+    pub(crate) async fn verify(
         &self,
         dfx_orbit: &DfxOrbit,
         request: &GetRequestResponse,
     ) -> anyhow::Result<()> {
         let canister_id = dfx_orbit.canister_id(&self.canister)?;
-        let arg = parse_arguments(&self.argument, &self.arg_file, &self.raw_arg)?;
+        let arg = self.encode_argument(dfx_orbit, canister_id).await?;
         let arg_checksum = arg.map(|arg| hex::encode(Sha256::digest(arg)));
 
         let RequestOperationDTO::CallExternalCanister(op) = &request.request.operation else {
@@ -93,6 +97,47 @@ impl RequestCanisterCallArgs {
 
         Ok(())
     }
+
+    /// Encodes the argument, with the types of the Candid interface registered for the canister if any.
+    async fn encode_argument(
+        &self,
+        dfx_orbit: &DfxOrbit,
+        canister_id: Principal,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        if self.argument.is_none() && self.arg_file.is_none() {
+            return parse_arguments(&None, &None, &self.raw_arg, None);
+        }
+
+        // Without read access to the canister the argument is encoded untyped,
+        // the station still checks it against the interface.
+        let candid_interface = dfx_orbit
+            .station
+            .get_external_canister(GetExternalCanisterInput { canister_id })
+            .await
+            .ok()
+            .and_then(|response| response.candid_interface);
+        let Some(candid_interface) = candid_interface else {
+            return parse_arguments(&self.argument, &self.arg_file, &self.raw_arg, None);
+        };
+
+        let (env, actor) = candid_parser::utils::CandidSource::Text(&candid_interface)
+            .load()
+            .with_context(|| "Invalid Candid interface registered for the canister".to_string())?;
+        let actor = actor.context("The registered Candid interface does not define a service")?;
+        let method = env.get_method(&actor, &self.method_name).with_context(|| {
+            format!(
+                "The method \"{}\" is not part of the registered Candid interface",
+                self.method_name
+            )
+        })?;
+
+        parse_arguments(
+            &self.argument,
+            &self.arg_file,
+            &self.raw_arg,
+            Some((&env, method.args.as_slice())),
+        )
+    }
 }
 
 impl DfxOrbit {
@@ -122,10 +167,15 @@ impl DfxOrbit {
         if let Some(args) = &op.arg_rendering {
             writeln!(output, "Argument: {args}")?
         }
+        if let Some(args) = &op.arg_candid {
+            writeln!(output, "Argument (Candid): {args}")?
+        }
         if let Some(cycles) = &op.execution_method_cycles {
             writeln!(output, "Execution method cycles: {cycles}")?
         }
-        if let Some(reply) = &op.execution_method_reply {
+        if let Some(reply) = &op.execution_method_reply_candid {
+            writeln!(output, "Execution response: {reply}")?;
+        } else if let Some(reply) = &op.execution_method_reply {
             match candid_parser::IDLArgs::from_bytes(reply) {
                 // TODO: Check if we can get the type information from somewhere to annotate this with types
                 Ok(response) => writeln!(output, "Execution response: {response}"),
@@ -7,8 +7,8 @@ use orbit_essentials::types::WasmModuleExtraChunks;
 use sha2::{Digest, Sha256};
 use station_api::{
     CanisterInstallMode, CanisterUpgradeOptionsInput, ChangeExternalCanisterOperationDTO,
-    ChangeExternalCanisterOperationInput, GetRequestResponse, RequestOperationDTO,
-    RequestOperationInput, WasmMemoryPersistence,
+    ChangeExternalCanisterOperationInput, ExternalCanisterRollbackInput, GetRequestResponse,
+    RequestOperationDTO, RequestOperationInput, WasmMemoryPersistence,
 };
 use std::{collections::HashMap, fmt::Write, path::PathBuf};
 
@@ -43,6 +43,16 @@ pub struct RequestCanisterInstallArgs {
     /// The asset canister name or ID to upload module chunks to.
     #[clap(long)]
     pub asset_canister: Option<String>,
+    /// Take a snapshot right before the install and restore the canister from it
+    /// if the install or the health check fails.
+    #[clap(long)]
+    pub rollback: bool,
+    /// The method called with an empty argument after the install to check the canister health.
+    #[clap(long, requires = "rollback")]
+    pub health_check: Option<String>,
+    /// The snapshot (hex) to replace with the rollback snapshot.
+    #[clap(long, requires = "rollback")]
+    pub replace_snapshot: Option<String>,
 }
 
 #[derive(CandidType)]
@@ -115,6 +125,8 @@ impl RequestCanisterInstallArgs {
             module,
             module_extra_chunks,
             arg,
+            rollback: self.rollback_input(),
+            module_registry: None,
         };
         Ok(RequestOperationInput::ChangeExternalCanister(operation))
     }
@@ -165,15 +177,32 @@ impl RequestCanisterInstallArgs {
             );
             bail!("Argument checksum does not match");
         }
+        let rollback = op
+            .rollback
+            .as_ref()
+            .map(|rollback| (&rollback.health_check_method, &rollback.replace_snapshot));
+        let expected_rollback = self
+            .rollback
+            .then_some((&self.health_check, &self.replace_snapshot));
+        if rollback != expected_rollback {
+            bail!("Rollback settings do not match");
+        }
 
         Ok(())
     }
 
+    fn rollback_input(&self) -> Option<ExternalCanisterRollbackInput> {
+        self.rollback.then(|| ExternalCanisterRollbackInput {
+            replace_snapshot: self.replace_snapshot.clone(),
+            health_check_method: self.health_check.clone(),
+        })
+    }
+
     fn load_module_and_args(&self) -> anyhow::Result<(Vec<u8>, Option<Vec<u8>>)> {
         let module = std::fs::read(&self.wasm)
             .with_context(|| "Could not read Wasm file")?
             .to_vec();
-        let args = parse_arguments(&self.argument, &self.arg_file, &None)?;
+        let args = parse_arguments(&self.argument, &self.arg_file, &None, None)?;
 
         Ok((module, args))
     }
@@ -307,9 +336,33 @@ impl DfxOrbit {
         }
 
         writeln!(output, "Module checksum: {}", &op.module_checksum)?;
+        if let Some(entry) = &op.module_registry_entry {
+            writeln!(
+                output,
+                "Registry module: {} version {} (entry {})",
+                entry.name, entry.version, entry.entry_id
+            )?;
+            match entry.verified_publisher {
+                Some(publisher) => writeln!(output, "Verified publisher: {publisher}")?,
+                None => writeln!(output, "Verified publisher: none (unverified)")?,
+            }
+        }
         if let Some(arg_checksum) = &op.arg_checksum {
             writeln!(output, "Argument checksum: {arg_checksum}")?;
         }
+        if let Some(rollback) = &op.rollback {
+            writeln!(
+                output,
+                "Rollback: enabled, health check: {}",
+                rollback.health_check_method.as_deref().unwrap_or("none")
+            )?;
+            if let Some(replace_snapshot) = &rollback.replace_snapshot {
+                writeln!(output, "Replaced snapshot: {replace_snapshot}")?;
+            }
+        }
+        if let Some(snapshot_id) = &op.rollback_snapshot_id {
+            writeln!(output, "Rollback snapshot: {snapshot_id}")?;
+        }
         Ok(())
     }
 }
@@ -330,6 +383,9 @@ mod tests {
             argument: None,
             arg_file: None,
             asset_canister: None,
+            rollback: false,
+            health_check: None,
+            replace_snapshot: None,
         }
     }
 
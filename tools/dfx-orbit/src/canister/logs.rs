@@ -0,0 +1,176 @@
+use crate::DfxOrbit;
+use anyhow::{bail, Context};
+use clap::Parser;
+use ic_utils::interfaces::{management_canister::CanisterLogRecord, ManagementCanister};
+use orbit_essentials::utils::timestamp_to_rfc3339;
+use serde::Serialize;
+use station_api::GetExternalCanisterInput;
+use std::fmt::Write;
+
+/// Fetches the logs of a canister managed by the station
+///
+/// The management canister only serves logs to non-replicated queries, so they are fetched directly
+/// after checking the permission in the station. The log visibility of the canister needs to include
+/// the caller, e.g. by requesting `canister update-settings` with the caller as allowed viewer.
+#[derive(Debug, Clone, Parser)]
+pub struct CanisterLogsArgs {
+    /// The canister name or `CanisterId`
+    canister: String,
+    /// Only show log records starting from this index (inclusive)
+    #[clap(long)]
+    from_idx: Option<u64>,
+    /// Only show log records up to this index (inclusive)
+    #[clap(long)]
+    to_idx: Option<u64>,
+    /// Only show log records created at or after this time (RFC3339)
+    #[clap(long)]
+    from: Option<String>,
+    /// Only show log records created at or before this time (RFC3339)
+    #[clap(long)]
+    to: Option<String>,
+    /// Return output as JSON
+    #[clap(short, long)]
+    json: bool,
+}
+
+/// A log record of the canister, with the timestamp in RFC3339.
+#[derive(Debug, Clone, Serialize)]
+struct CanisterLogRecordOutput {
+    idx: u64,
+    timestamp: String,
+    #[serde(with = "serde_bytes")]
+    content: Vec<u8>,
+}
+
+/// The filter for the fetched log records, all bounds are inclusive.
+#[derive(Debug, Clone, Default)]
+struct CanisterLogsFilter {
+    from_idx: Option<u64>,
+    to_idx: Option<u64>,
+    from_dt: Option<u64>,
+    to_dt: Option<u64>,
+}
+
+impl CanisterLogsArgs {
+    pub(crate) async fn execute(self, dfx_orbit: &DfxOrbit) -> anyhow::Result<()> {
+        let canister_id = dfx_orbit.canister_id(&self.canister)?;
+        let filter = CanisterLogsFilter {
+            from_idx: self.from_idx,
+            to_idx: self.to_idx,
+            from_dt: self.from.as_deref().map(parse_timestamp).transpose()?,
+            to_dt: self.to.as_deref().map(parse_timestamp).transpose()?,
+        };
+
+        let privileges = dfx_orbit
+            .station
+            .get_external_canister(GetExternalCanisterInput { canister_id })
+            .await?
+            .privileges;
+        if !privileges.can_read_logs {
+            bail!("Not permitted to read the logs of canister {canister_id} in the station");
+        }
+
+        let (response,) = ManagementCanister::create(dfx_orbit.interface.agent())
+            .fetch_canister_logs(&canister_id)
+            .call()
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to fetch the logs of canister {canister_id}, its log visibility might not include the caller"
+                )
+            })?;
+
+        let records = response
+            .canister_log_records
+            .into_iter()
+            .filter(|record| filter.matches(record))
+            .map(|record| CanisterLogRecordOutput {
+                idx: record.idx,
+                timestamp: timestamp_to_rfc3339(&record.timestamp_nanos),
+                content: record.content,
+            })
+            .collect::<Vec<_>>();
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&records)?);
+        } else {
+            print!("{}", display_canister_logs(&records)?);
+        }
+
+        Ok(())
+    }
+}
+
+impl CanisterLogsFilter {
+    fn matches(&self, record: &CanisterLogRecord) -> bool {
+        self.from_idx.is_none_or(|from_idx| record.idx >= from_idx)
+            && self.to_idx.is_none_or(|to_idx| record.idx <= to_idx)
+            && self
+                .from_dt
+                .is_none_or(|from_dt| record.timestamp_nanos >= from_dt)
+            && self
+                .to_dt
+                .is_none_or(|to_dt| record.timestamp_nanos <= to_dt)
+    }
+}
+
+fn parse_timestamp(input: &str) -> anyhow::Result<u64> {
+    dateparser::parse(input)
+        .ok()
+        .and_then(|datetime| datetime.timestamp_nanos_opt())
+        .and_then(|nanos| u64::try_from(nanos).ok())
+        .with_context(|| format!("Invalid time \"{input}\", expected RFC3339"))
+}
+
+fn display_canister_logs(records: &[CanisterLogRecordOutput]) -> anyhow::Result<String> {
+    let mut output = String::new();
+
+    for record in records {
+        writeln!(
+            output,
+            "[{}. {}]: {}",
+            record.idx,
+            record.timestamp,
+            String::from_utf8_lossy(&record.content)
+        )?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn canister_logs_filter_matches_bounds_inclusively() {
+        let record = |idx, timestamp_nanos| CanisterLogRecord {
+            idx,
+            timestamp_nanos,
+            content: b"log".to_vec(),
+        };
+        let filter = CanisterLogsFilter {
+            from_idx: Some(2),
+            to_idx: Some(10),
+            from_dt: None,
+            to_dt: Some(500),
+        };
+
+        assert!(CanisterLogsFilter::default().matches(&record(0, 0)));
+        assert!(filter.matches(&record(2, 100)));
+        assert!(filter.matches(&record(10, 500)));
+        assert!(!filter.matches(&record(1, 100)));
+        assert!(!filter.matches(&record(11, 100)));
+        assert!(!filter.matches(&record(5, 501)));
+    }
+
+    #[test]
+    fn parses_rfc3339_timestamps() {
+        assert_eq!(
+            parse_timestamp("1970-01-01T00:00:01Z").unwrap(),
+            1_000_000_000
+        );
+        assert!(parse_timestamp("yesterday-ish").is_err());
+    }
+}
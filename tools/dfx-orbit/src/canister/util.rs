@@ -1,6 +1,9 @@
 use crate::DfxOrbit;
 use anyhow::Context;
-use candid::Principal;
+use candid::{
+    types::{Type, TypeEnv},
+    Principal,
+};
 use slog::{info, Logger};
 
 impl DfxOrbit {
@@ -14,10 +17,14 @@ impl DfxOrbit {
     }
 }
 
+/// Encodes the argument given on the command line.
+///
+/// If the argument types are known, the Candid values are type-checked and encoded with them.
 pub(super) fn parse_arguments(
     arg_string: &Option<String>,
     arg_path: &Option<String>,
     raw_arg: &Option<String>,
+    types: Option<(&TypeEnv, &[Type])>,
 ) -> anyhow::Result<Option<Vec<u8>>> {
     // TODO: It would be really nice to be able to use `blob_from_arguments(..)` here, as in dfx, to get all the nice things such as help composing the argument.
     // First try to read the argument file, if it was provided
@@ -30,9 +37,16 @@ pub(super) fn parse_arguments(
         .or_else(|| arg_string.clone())
         // Parse the candid
         .map(|arg_string| {
-            candid_parser::parse_idl_args(&arg_string)
-                .with_context(|| "Invalid Candid values".to_string())?
-                .to_bytes()
+            let args = candid_parser::parse_idl_args(&arg_string)
+                .with_context(|| "Invalid Candid values".to_string())?;
+
+            match types {
+                Some((env, types)) => args
+                    .annotate_types(true, env, types)
+                    .with_context(|| "Candid values do not match the method arguments".to_string())?
+                    .to_bytes_with_types(env, types),
+                None => args.to_bytes(),
+            }
         })
         .transpose()?;
 
@@ -0,0 +1,126 @@
+//! Keeps the configuration of the station in a file: exports it, and diffs the file against the
+//! station to make the requests that converge the station to it.
+
+mod model;
+mod plan;
+mod state;
+
+use crate::DfxOrbit;
+use clap::{Parser, Subcommand};
+use model::StationConfigFile;
+use plan::{display_plan, plan};
+use station_api::CreateRequestInput;
+use std::path::PathBuf;
+
+/// Manage the station configuration declaratively
+///
+/// The configuration covers the user groups, named rules, request policies, permissions, accounts
+/// and address book of the station. It is written as TOML, or as JSON if the file name ends with
+/// `.json`.
+#[derive(Debug, Clone, Subcommand)]
+#[clap(version, about, long_about = None)]
+pub enum ConfigArgs {
+    /// Write the current configuration of the station to a file
+    Export(ConfigExportArgs),
+    /// Show the requests needed to make the station match a configuration file
+    Plan(ConfigPlanArgs),
+    /// Make the requests needed to make the station match a configuration file
+    Apply(ConfigApplyArgs),
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct ConfigExportArgs {
+    /// The file to write the configuration to
+    pub file: PathBuf,
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct ConfigPlanArgs {
+    /// The configuration file
+    pub file: PathBuf,
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct ConfigApplyArgs {
+    /// The configuration file
+    pub file: PathBuf,
+
+    /// Tag all requests with this name, so that they can be reviewed together with
+    /// `dfx-orbit review list --tag <BUNDLE>`
+    #[clap(long)]
+    pub bundle: Option<String>,
+
+    /// Make the requests without asking for confirmation
+    #[clap(short, long)]
+    pub yes: bool,
+}
+
+impl ConfigArgs {
+    pub(crate) async fn execute(self, dfx_orbit: &DfxOrbit) -> anyhow::Result<()> {
+        match self {
+            ConfigArgs::Export(args) => {
+                let config = dfx_orbit.fetch_live_station().await?.export();
+                config.save(&args.file)?;
+                println!("Wrote the station configuration to {}", args.file.display());
+            }
+            ConfigArgs::Plan(args) => {
+                let config = StationConfigFile::load(&args.file)?;
+                let live = dfx_orbit.fetch_live_station().await?;
+                print!("{}", display_plan(&plan(&config, &live)?)?);
+            }
+            ConfigArgs::Apply(args) => {
+                let config = StationConfigFile::load(&args.file)?;
+                let live = dfx_orbit.fetch_live_station().await?;
+                let changes = plan(&config, &live)?;
+                print!("{}", display_plan(&changes)?);
+
+                let requests = changes
+                    .iter()
+                    .filter_map(|change| {
+                        let operation = change.operation.clone()?;
+                        Some((change.title(), operation))
+                    })
+                    .collect::<Vec<_>>();
+                let deferred = changes.len() - requests.len();
+                if requests.is_empty() {
+                    return Ok(());
+                }
+                if !args.yes {
+                    dfx_core::cli::ask_for_consent(&format!(
+                        "Make {} requests to apply this plan?",
+                        requests.len()
+                    ))?;
+                }
+
+                for (title, operation) in requests {
+                    let title = match &args.bundle {
+                        Some(bundle) => format!("[{bundle}] {title}"),
+                        None => title,
+                    };
+                    let request = dfx_orbit
+                        .station
+                        .request(CreateRequestInput {
+                            operation,
+                            title: Some(title),
+                            summary: None,
+                            execution_plan: None,
+                            expiration_dt: None,
+                            deduplication_key: None,
+                            tags: args.bundle.clone().map(|bundle| vec![bundle]),
+                        })
+                        .await?;
+                    dfx_orbit.print_create_request_info(&request);
+                }
+
+                if deferred > 0 {
+                    println!(
+                        "{deferred} changes are deferred, run `dfx-orbit config apply` again once \
+                         the requests they depend on are approved."
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,242 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use station_api::{AuthScopeDTO, ResourceDTO};
+use std::path::Path;
+
+/// The declarative configuration of a station.
+///
+/// Every section that is present is managed: applying the configuration adds, changes and removes
+/// the resources of the station to match it. Sections that are left out are not touched.
+///
+/// Users, user groups and named rules are referred to by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StationConfigFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_groups: Option<Vec<UserGroupConfig>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub named_rules: Option<Vec<NamedRuleConfig>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_policies: Option<Vec<RequestPolicyConfig>>,
+    /// The permissions of the station, except the ones of individual accounts.
+    ///
+    /// Permissions can't be removed, so the permissions that are left out are not touched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Vec<PermissionConfig>>,
+    /// The accounts of the station, accounts can't be removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accounts: Option<Vec<AccountConfig>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_book: Option<Vec<AddressBookEntryConfig>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserGroupConfig {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamedRuleConfig {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The rule, e.g. `quorum(group:Admin,2) and not allowlisted`.
+    pub rule: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestPolicyConfig {
+    /// The requests the policy applies to, e.g. `transfer`, `edit_user(<user id>)` or
+    /// `edit_permission({"Permission":"Read"})`.
+    pub specifier: String,
+    /// The rule, e.g. `quorum(group:Admin,2) and not allowlisted`.
+    pub rule: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllowConfig {
+    pub auth_scope: AuthScopeConfig,
+    #[serde(default)]
+    pub users: Vec<String>,
+    #[serde(default)]
+    pub user_groups: Vec<String>,
+}
+
+impl Default for AllowConfig {
+    fn default() -> Self {
+        Self {
+            auth_scope: AuthScopeConfig::Restricted,
+            users: Vec::new(),
+            user_groups: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthScopeConfig {
+    Public,
+    Authenticated,
+    Restricted,
+}
+
+impl From<AuthScopeDTO> for AuthScopeConfig {
+    fn from(auth_scope: AuthScopeDTO) -> Self {
+        match auth_scope {
+            AuthScopeDTO::Public => Self::Public,
+            AuthScopeDTO::Authenticated => Self::Authenticated,
+            AuthScopeDTO::Restricted => Self::Restricted,
+        }
+    }
+}
+
+impl From<AuthScopeConfig> for AuthScopeDTO {
+    fn from(auth_scope: AuthScopeConfig) -> Self {
+        match auth_scope {
+            AuthScopeConfig::Public => Self::Public,
+            AuthScopeConfig::Authenticated => Self::Authenticated,
+            AuthScopeConfig::Restricted => Self::Restricted,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionConfig {
+    #[serde(flatten)]
+    pub allow: AllowConfig,
+    /// The resource in the JSON format of the station API, e.g. `{"Permission":"Read"}`.
+    #[serde(with = "resource_json")]
+    pub resource: ResourceDTO,
+}
+
+impl PartialEq for PermissionConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.allow == other.allow && resource_key(&self.resource) == resource_key(&other.resource)
+    }
+}
+
+impl Eq for PermissionConfig {}
+
+/// A key that identifies a resource, since the API types can't be compared.
+pub(super) fn resource_key(resource: &ResourceDTO) -> String {
+    serde_json::to_string(resource).unwrap_or_else(|_| format!("{resource:?}"))
+}
+
+/// Resources are kept as JSON strings, since TOML can't represent the nested enums of the API.
+mod resource_json {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use station_api::ResourceDTO;
+
+    pub fn serialize<S: Serializer>(
+        resource: &ResourceDTO,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::resource_key(resource))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ResourceDTO, D::Error> {
+        serde_json::from_str(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountConfig {
+    pub name: String,
+    /// The symbols of the assets held by the account.
+    pub assets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transfer_policy: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub configs_policy: Option<String>,
+    #[serde(default)]
+    pub read_permission: AllowConfig,
+    #[serde(default)]
+    pub configs_permission: AllowConfig,
+    #[serde(default)]
+    pub transfer_permission: AllowConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressBookEntryConfig {
+    pub address_owner: String,
+    pub address: String,
+    pub address_format: String,
+    pub blockchain: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+impl StationConfigFile {
+    /// Reads the configuration, from JSON if the file has a `.json` extension and TOML otherwise.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        if is_json(path) {
+            serde_json::from_str(&content)
+                .with_context(|| format!("Invalid configuration in {}", path.display()))
+        } else {
+            toml::from_str(&content)
+                .with_context(|| format!("Invalid configuration in {}", path.display()))
+        }
+    }
+
+    /// Writes the configuration, as JSON if the file has a `.json` extension and TOML otherwise.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let content = if is_json(path) {
+            serde_json::to_string_pretty(self)?
+        } else {
+            // Going through a value orders the tables after the plain values, as TOML requires.
+            toml::to_string_pretty(&toml::Value::try_from(self)?)?
+        };
+
+        std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "json")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use station_api::{PermissionResourceActionDTO, ResourceActionDTO, ResourceIdDTO};
+
+    #[test]
+    fn toml_round_trip() {
+        let config = StationConfigFile {
+            user_groups: Some(vec![UserGroupConfig {
+                name: "Admin".to_string(),
+            }]),
+            request_policies: Some(vec![RequestPolicyConfig {
+                specifier: "add_user".to_string(),
+                rule: "quorum(group:Admin,1)".to_string(),
+            }]),
+            permissions: Some(vec![
+                PermissionConfig {
+                    allow: AllowConfig {
+                        auth_scope: AuthScopeConfig::Authenticated,
+                        users: Vec::new(),
+                        user_groups: vec!["Admin".to_string()],
+                    },
+                    resource: ResourceDTO::Permission(PermissionResourceActionDTO::Read),
+                },
+                PermissionConfig {
+                    allow: AllowConfig::default(),
+                    resource: ResourceDTO::UserGroup(ResourceActionDTO::Read(ResourceIdDTO::Any)),
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let content = toml::to_string_pretty(&toml::Value::try_from(&config).unwrap()).unwrap();
+        let parsed: StationConfigFile = toml::from_str(&content).unwrap();
+
+        assert_eq!(parsed, config);
+    }
+}
@@ -0,0 +1,1039 @@
+use super::{
+    model::{
+        resource_key, AccountConfig, AddressBookEntryConfig, AllowConfig, AuthScopeConfig,
+        NamedRuleConfig, PermissionConfig, RequestPolicyConfig, StationConfigFile, UserGroupConfig,
+    },
+    state::{allow_config, restricted, LiveAccount, LiveStation},
+};
+use crate::policy::{display_rule, display_specifier, parse_rule, parse_specifier, NameLookup};
+use anyhow::{bail, Context};
+use itertools::Itertools;
+use station_api::{
+    AddAccountOperationInput, AddAddressBookEntryOperationInput, AddNamedRuleOperationInput,
+    AddRequestPolicyOperationInput, AddUserGroupOperationInput, AllowDTO, ChangeAssets,
+    EditAccountOperationInput, EditAddressBookEntryOperationInput, EditNamedRuleOperationInput,
+    EditPermissionOperationInput, EditRequestPolicyOperationInput,
+    RemoveAddressBookEntryOperationInput, RemoveNamedRuleOperationInput,
+    RemoveRequestPolicyOperationInput, RemoveUserGroupOperationInput, RequestOperationInput,
+    RequestPolicyRuleDTO, RequestPolicyRuleInput,
+};
+use std::fmt::Write;
+
+/// The IDs of the user groups and named rules that are yet to be created start with this prefix.
+const PENDING_ID_PREFIX: &str = "<pending>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ChangeKind {
+    Add,
+    Change,
+    Remove,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct PlannedChange {
+    pub(super) kind: ChangeKind,
+    /// The changed resource, e.g. `user group "Admin"`.
+    pub(super) resource: String,
+    /// The changed attributes, e.g. `rule: auto_approved -> quorum(any,1)`.
+    pub(super) details: Vec<String>,
+    /// The operation of the request making the change, or `None` if it refers to user groups or
+    /// named rules that are yet to be created, so that it can only be requested once the requests
+    /// creating them are approved.
+    pub(super) operation: Option<RequestOperationInput>,
+}
+
+impl PlannedChange {
+    /// The title of the request making the change.
+    pub(super) fn title(&self) -> String {
+        let verb = match self.kind {
+            ChangeKind::Add => "Add",
+            ChangeKind::Change => "Edit",
+            ChangeKind::Remove => "Remove",
+        };
+        format!("{verb} {}", self.resource)
+    }
+}
+
+/// Computes the changes that make the live station match the configuration.
+///
+/// Additions and edits come first, in the order of the sections, followed by the removals in the
+/// reverse order, so that resources are created before and removed after their uses.
+pub(super) fn plan(
+    config: &StationConfigFile,
+    live: &LiveStation,
+) -> anyhow::Result<Vec<PlannedChange>> {
+    let mut planner = Planner::new(config, live);
+
+    if let Some(user_groups) = &config.user_groups {
+        planner.plan_user_groups(user_groups);
+    }
+    if let Some(named_rules) = &config.named_rules {
+        planner.plan_named_rules(named_rules)?;
+    }
+    if let Some(request_policies) = &config.request_policies {
+        planner.plan_request_policies(request_policies)?;
+    }
+    if let Some(permissions) = &config.permissions {
+        planner.plan_permissions(permissions)?;
+    }
+    if let Some(accounts) = &config.accounts {
+        planner.plan_accounts(accounts)?;
+    }
+    if let Some(address_book) = &config.address_book {
+        planner.plan_address_book(address_book);
+    }
+
+    let mut changes = planner.changes;
+    changes.extend(planner.removals.into_iter().rev());
+    Ok(changes)
+}
+
+/// Displays the changes like `terraform plan`.
+pub(super) fn display_plan(changes: &[PlannedChange]) -> anyhow::Result<String> {
+    let mut output = String::new();
+
+    if changes.is_empty() {
+        writeln!(output, "No changes. The station matches the configuration.")?;
+        return Ok(output);
+    }
+
+    for change in changes {
+        let symbol = match change.kind {
+            ChangeKind::Add => '+',
+            ChangeKind::Change => '~',
+            ChangeKind::Remove => '-',
+        };
+        write!(output, "  {symbol} {}", change.resource)?;
+        if change.operation.is_none() {
+            write!(
+                output,
+                " (deferred until the user groups and named rules it uses exist)"
+            )?;
+        }
+        writeln!(output)?;
+        for detail in &change.details {
+            writeln!(output, "        {detail}")?;
+        }
+    }
+
+    let count = |kind| changes.iter().filter(|change| change.kind == kind).count();
+    writeln!(
+        output,
+        "\nPlan: {} to add, {} to change, {} to remove.",
+        count(ChangeKind::Add),
+        count(ChangeKind::Change),
+        count(ChangeKind::Remove)
+    )?;
+
+    Ok(output)
+}
+
+struct Planner<'a> {
+    live: &'a LiveStation,
+    /// The names of the station, including the user groups and named rules of the configuration
+    /// that are yet to be created, with placeholder IDs.
+    names: NameLookup,
+    changes: Vec<PlannedChange>,
+    removals: Vec<PlannedChange>,
+}
+
+impl<'a> Planner<'a> {
+    fn new(config: &StationConfigFile, live: &'a LiveStation) -> Self {
+        let mut names = live.names.clone();
+        for group in config.user_groups.iter().flatten() {
+            if !names.groups.values().any(|name| *name == group.name) {
+                names.groups.insert(
+                    format!("{PENDING_ID_PREFIX}{}", group.name),
+                    group.name.clone(),
+                );
+            }
+        }
+        for named_rule in config.named_rules.iter().flatten() {
+            if !names
+                .named_rules
+                .values()
+                .any(|name| *name == named_rule.name)
+            {
+                names.named_rules.insert(
+                    format!("{PENDING_ID_PREFIX}{}", named_rule.name),
+                    named_rule.name.clone(),
+                );
+            }
+        }
+
+        Self {
+            live,
+            names,
+            changes: Vec::new(),
+            removals: Vec::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        kind: ChangeKind,
+        resource: String,
+        details: Vec<String>,
+        operation: RequestOperationInput,
+    ) {
+        let change = PlannedChange {
+            kind,
+            resource,
+            details,
+            operation: (!uses_pending_ids(&operation)).then_some(operation),
+        };
+        match kind {
+            ChangeKind::Remove => self.removals.push(change),
+            ChangeKind::Add | ChangeKind::Change => self.changes.push(change),
+        }
+    }
+
+    fn plan_user_groups(&mut self, user_groups: &[UserGroupConfig]) {
+        for group in user_groups {
+            if !self
+                .live
+                .user_groups
+                .iter()
+                .any(|live| live.name == group.name)
+            {
+                self.push(
+                    ChangeKind::Add,
+                    format!("user group \"{}\"", group.name),
+                    Vec::new(),
+                    RequestOperationInput::AddUserGroup(AddUserGroupOperationInput {
+                        name: group.name.clone(),
+                    }),
+                );
+            }
+        }
+
+        for live in &self.live.user_groups {
+            if !user_groups.iter().any(|group| group.name == live.name) {
+                self.push(
+                    ChangeKind::Remove,
+                    format!("user group \"{}\"", live.name),
+                    Vec::new(),
+                    RequestOperationInput::RemoveUserGroup(RemoveUserGroupOperationInput {
+                        user_group_id: live.id.clone(),
+                    }),
+                );
+            }
+        }
+    }
+
+    fn plan_named_rules(&mut self, named_rules: &[NamedRuleConfig]) -> anyhow::Result<()> {
+        for named_rule in named_rules {
+            let resource = format!("named rule \"{}\"", named_rule.name);
+            let rule = self.parse_rule(&named_rule.rule, &resource)?;
+
+            match self
+                .live
+                .named_rules
+                .iter()
+                .find(|live| live.name == named_rule.name)
+            {
+                None => self.push(
+                    ChangeKind::Add,
+                    resource,
+                    vec![format!("rule: {}", self.display_rule(&rule))],
+                    RequestOperationInput::AddNamedRule(AddNamedRuleOperationInput {
+                        name: named_rule.name.clone(),
+                        description: named_rule.description.clone(),
+                        rule,
+                    }),
+                ),
+                Some(live) => {
+                    let mut details = Vec::new();
+                    let description = (live.description != named_rule.description).then(|| {
+                        details.push(format!(
+                            "description: {} -> {}",
+                            display_option(live.description.as_deref()),
+                            display_option(named_rule.description.as_deref())
+                        ));
+                        named_rule.description.clone()
+                    });
+                    let rule = self.changed_rule(&live.rule, rule).map(|(detail, rule)| {
+                        details.push(format!("rule: {detail}"));
+                        rule
+                    });
+
+                    if !details.is_empty() {
+                        self.push(
+                            ChangeKind::Change,
+                            resource,
+                            details,
+                            RequestOperationInput::EditNamedRule(EditNamedRuleOperationInput {
+                                named_rule_id: live.id.clone(),
+                                name: None,
+                                description,
+                                rule,
+                            }),
+                        );
+                    }
+                }
+            }
+        }
+
+        for live in &self.live.named_rules {
+            if !named_rules
+                .iter()
+                .any(|named_rule| named_rule.name == live.name)
+            {
+                self.push(
+                    ChangeKind::Remove,
+                    format!("named rule \"{}\"", live.name),
+                    Vec::new(),
+                    RequestOperationInput::RemoveNamedRule(RemoveNamedRuleOperationInput {
+                        named_rule_id: live.id.clone(),
+                    }),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Policies are identified by their specifier, the policies with the same specifier are paired
+    /// up so that as few policies as possible are added and removed.
+    fn plan_request_policies(&mut self, policies: &[RequestPolicyConfig]) -> anyhow::Result<()> {
+        let mut wanted = Vec::new();
+        for policy in policies {
+            let resource = format!("request policy {}", policy.specifier);
+            let specifier = parse_specifier(&policy.specifier)
+                .with_context(|| format!("Invalid specifier of the {resource}"))?;
+            let rule = self.parse_rule(&policy.rule, &resource)?;
+            wanted.push((display_specifier(&specifier), specifier, rule));
+        }
+
+        let mut unmatched = self.live.request_policies.iter().collect::<Vec<_>>();
+        wanted.retain(|(specifier, _, rule)| {
+            let position = unmatched.iter().position(|live| {
+                display_specifier(&live.specifier) == *specifier
+                    && self.display_rule(&live.rule) == self.display_rule(rule)
+            });
+            match position {
+                Some(position) => {
+                    unmatched.remove(position);
+                    false
+                }
+                None => true,
+            }
+        });
+
+        for (display, specifier, rule) in wanted {
+            let resource = format!("request policy {display}");
+            let position = unmatched
+                .iter()
+                .position(|live| display_specifier(&live.specifier) == display);
+            match position {
+                Some(position) => {
+                    let live = unmatched.remove(position);
+                    self.push(
+                        ChangeKind::Change,
+                        resource,
+                        vec![format!(
+                            "rule: {} -> {}",
+                            self.display_rule(&live.rule),
+                            self.display_rule(&rule)
+                        )],
+                        RequestOperationInput::EditRequestPolicy(EditRequestPolicyOperationInput {
+                            policy_id: live.id.clone(),
+                            specifier: None,
+                            rule: Some(rule),
+                        }),
+                    );
+                }
+                None => self.push(
+                    ChangeKind::Add,
+                    resource,
+                    vec![format!("rule: {}", self.display_rule(&rule))],
+                    RequestOperationInput::AddRequestPolicy(AddRequestPolicyOperationInput {
+                        specifier,
+                        rule,
+                    }),
+                ),
+            }
+        }
+
+        for live in unmatched {
+            self.push(
+                ChangeKind::Remove,
+                format!("request policy {}", display_specifier(&live.specifier)),
+                vec![format!("rule: {}", self.display_rule(&live.rule))],
+                RequestOperationInput::RemoveRequestPolicy(RemoveRequestPolicyOperationInput {
+                    policy_id: live.id.clone(),
+                }),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Permissions can't be removed, the permissions that are not configured are left as they are.
+    fn plan_permissions(&mut self, permissions: &[PermissionConfig]) -> anyhow::Result<()> {
+        for permission in permissions {
+            let key = resource_key(&permission.resource);
+            let resource = format!("permission {key}");
+            let allow = self.resolve_allow(&permission.allow, &resource)?;
+            let live = self
+                .live
+                .permissions
+                .iter()
+                .find(|live| resource_key(&live.resource) == key)
+                .map(|live| live.allow.clone())
+                .unwrap_or_else(restricted);
+
+            if !same_allow(&live, &allow) {
+                self.push(
+                    ChangeKind::Change,
+                    resource,
+                    vec![format!(
+                        "{} -> {}",
+                        self.display_allow(&live),
+                        self.display_allow(&allow)
+                    )],
+                    RequestOperationInput::EditPermission(EditPermissionOperationInput {
+                        resource: permission.resource.clone(),
+                        auth_scope: Some(allow.auth_scope),
+                        users: Some(allow.users),
+                        user_groups: Some(allow.user_groups),
+                    }),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Accounts are identified by their name, accounts can't be removed.
+    fn plan_accounts(&mut self, accounts: &[AccountConfig]) -> anyhow::Result<()> {
+        for account in accounts {
+            let resource = format!("account \"{}\"", account.name);
+            let assets = account
+                .assets
+                .iter()
+                .map(|asset| self.resolve_asset(asset))
+                .collect::<anyhow::Result<Vec<_>>>()
+                .with_context(|| format!("Invalid assets of the {resource}"))?;
+            let transfer_policy = account
+                .transfer_policy
+                .as_deref()
+                .map(|rule| self.parse_rule(rule, &resource))
+                .transpose()?;
+            let configs_policy = account
+                .configs_policy
+                .as_deref()
+                .map(|rule| self.parse_rule(rule, &resource))
+                .transpose()?;
+            let read_permission = self.resolve_allow(&account.read_permission, &resource)?;
+            let configs_permission = self.resolve_allow(&account.configs_permission, &resource)?;
+            let transfer_permission =
+                self.resolve_allow(&account.transfer_permission, &resource)?;
+
+            let Some(live) = self
+                .live
+                .accounts
+                .iter()
+                .find(|live| live.account.name == account.name)
+            else {
+                let mut details = vec![format!("assets: {}", account.assets.join(", "))];
+                if let Some(rule) = &transfer_policy {
+                    details.push(format!("transfer_policy: {}", self.display_rule(rule)));
+                }
+                if let Some(rule) = &configs_policy {
+                    details.push(format!("configs_policy: {}", self.display_rule(rule)));
+                }
+                self.push(
+                    ChangeKind::Add,
+                    resource,
+                    details,
+                    RequestOperationInput::AddAccount(AddAccountOperationInput {
+                        name: account.name.clone(),
+                        assets,
+                        metadata: Vec::new(),
+                        read_permission,
+                        configs_permission,
+                        transfer_permission,
+                        configs_request_policy: configs_policy,
+                        transfer_request_policy: transfer_policy,
+                    }),
+                );
+                continue;
+            };
+
+            let mut details = Vec::new();
+            let change_assets = self.changed_assets(live, &assets, &mut details);
+            let mut changed_allow = |name: &str, live_allow: &AllowDTO, allow: AllowDTO| {
+                (!same_allow(live_allow, &allow)).then(|| {
+                    details.push(format!(
+                        "{name}: {} -> {}",
+                        self.display_allow(live_allow),
+                        self.display_allow(&allow)
+                    ));
+                    allow
+                })
+            };
+            let read_permission =
+                changed_allow("read_permission", &live.read_permission, read_permission);
+            let configs_permission = changed_allow(
+                "configs_permission",
+                &live.configs_permission,
+                configs_permission,
+            );
+            let transfer_permission = changed_allow(
+                "transfer_permission",
+                &live.transfer_permission,
+                transfer_permission,
+            );
+            let transfer_request_policy = self.changed_policy(
+                "transfer_policy",
+                live.account.transfer_request_policy.as_ref(),
+                transfer_policy,
+                &mut details,
+            );
+            let configs_request_policy = self.changed_policy(
+                "configs_policy",
+                live.account.configs_request_policy.as_ref(),
+                configs_policy,
+                &mut details,
+            );
+
+            if !details.is_empty() {
+                self.push(
+                    ChangeKind::Change,
+                    resource,
+                    details,
+                    RequestOperationInput::EditAccount(EditAccountOperationInput {
+                        account_id: live.account.id.clone(),
+                        name: None,
+                        change_assets,
+                        read_permission,
+                        configs_permission,
+                        transfer_permission,
+                        configs_request_policy,
+                        transfer_request_policy,
+                    }),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Entries are identified by their blockchain and address.
+    fn plan_address_book(&mut self, entries: &[AddressBookEntryConfig]) {
+        for entry in entries {
+            let resource = format!(
+                "address book entry \"{}\" ({} {})",
+                entry.address_owner, entry.blockchain, entry.address
+            );
+            let live =
+                self.live.address_book.iter().find(|live| {
+                    live.blockchain == entry.blockchain && live.address == entry.address
+                });
+
+            let Some(live) = live else {
+                self.push(
+                    ChangeKind::Add,
+                    resource,
+                    Vec::new(),
+                    RequestOperationInput::AddAddressBookEntry(AddAddressBookEntryOperationInput {
+                        address_owner: entry.address_owner.clone(),
+                        address: entry.address.clone(),
+                        address_format: entry.address_format.clone(),
+                        blockchain: entry.blockchain.clone(),
+                        metadata: Vec::new(),
+                        labels: entry.labels.clone(),
+                    }),
+                );
+                continue;
+            };
+
+            let mut details = Vec::new();
+            let address_owner = (live.address_owner != entry.address_owner).then(|| {
+                details.push(format!(
+                    "address_owner: {} -> {}",
+                    live.address_owner, entry.address_owner
+                ));
+                entry.address_owner.clone()
+            });
+            let labels = (live.labels != entry.labels).then(|| {
+                details.push(format!(
+                    "labels: [{}] -> [{}]",
+                    live.labels.join(", "),
+                    entry.labels.join(", ")
+                ));
+                entry.labels.clone()
+            });
+
+            if !details.is_empty() {
+                self.push(
+                    ChangeKind::Change,
+                    resource,
+                    details,
+                    RequestOperationInput::EditAddressBookEntry(
+                        EditAddressBookEntryOperationInput {
+                            address_book_entry_id: live.id.clone(),
+                            address_owner,
+                            labels,
+                            change_metadata: None,
+                        },
+                    ),
+                );
+            }
+        }
+
+        for live in &self.live.address_book {
+            let configured = entries
+                .iter()
+                .any(|entry| live.blockchain == entry.blockchain && live.address == entry.address);
+            if !configured {
+                self.push(
+                    ChangeKind::Remove,
+                    format!(
+                        "address book entry \"{}\" ({} {})",
+                        live.address_owner, live.blockchain, live.address
+                    ),
+                    Vec::new(),
+                    RequestOperationInput::RemoveAddressBookEntry(
+                        RemoveAddressBookEntryOperationInput {
+                            address_book_entry_id: live.id.clone(),
+                        },
+                    ),
+                );
+            }
+        }
+    }
+
+    fn parse_rule(&self, rule: &str, resource: &str) -> anyhow::Result<RequestPolicyRuleDTO> {
+        parse_rule(rule, &self.names).with_context(|| format!("Invalid rule of the {resource}"))
+    }
+
+    fn display_rule(&self, rule: &RequestPolicyRuleDTO) -> String {
+        display_rule(rule, &self.names)
+    }
+
+    fn display_allow(&self, allow: &AllowDTO) -> String {
+        let allow = allow_config(allow, &self.names);
+        let auth_scope = match allow.auth_scope {
+            AuthScopeConfig::Public => "public",
+            AuthScopeConfig::Authenticated => "authenticated",
+            AuthScopeConfig::Restricted => "restricted",
+        };
+        format!(
+            "{auth_scope}, users: [{}], user groups: [{}]",
+            allow.users.join(", "),
+            allow.user_groups.join(", ")
+        )
+    }
+
+    /// The new rule, with a description of the change, if it differs from the live rule.
+    fn changed_rule(
+        &self,
+        live: &RequestPolicyRuleDTO,
+        rule: RequestPolicyRuleDTO,
+    ) -> Option<(String, RequestPolicyRuleDTO)> {
+        let (before, after) = (self.display_rule(live), self.display_rule(&rule));
+        (before != after).then(|| (format!("{before} -> {after}"), rule))
+    }
+
+    fn changed_policy(
+        &self,
+        name: &str,
+        live: Option<&RequestPolicyRuleDTO>,
+        rule: Option<RequestPolicyRuleDTO>,
+        details: &mut Vec<String>,
+    ) -> Option<RequestPolicyRuleInput> {
+        let before = live.map(|rule| self.display_rule(rule));
+        let after = rule.as_ref().map(|rule| self.display_rule(rule));
+        if before == after {
+            return None;
+        }
+
+        details.push(format!(
+            "{name}: {} -> {}",
+            display_option(before.as_deref()),
+            display_option(after.as_deref())
+        ));
+        Some(match rule {
+            Some(rule) => RequestPolicyRuleInput::Set(rule),
+            None => RequestPolicyRuleInput::Remove,
+        })
+    }
+
+    fn changed_assets(
+        &self,
+        live: &LiveAccount,
+        assets: &[String],
+        details: &mut Vec<String>,
+    ) -> Option<ChangeAssets> {
+        let live_assets = live
+            .account
+            .assets
+            .iter()
+            .map(|account_asset| account_asset.asset_id.clone())
+            .collect::<Vec<_>>();
+        let add_assets = assets
+            .iter()
+            .filter(|asset| !live_assets.contains(asset))
+            .cloned()
+            .collect::<Vec<_>>();
+        let remove_assets = live_assets
+            .iter()
+            .filter(|asset| !assets.contains(asset))
+            .cloned()
+            .collect::<Vec<_>>();
+        if add_assets.is_empty() && remove_assets.is_empty() {
+            return None;
+        }
+
+        let symbols = |ids: &[String]| ids.iter().map(|id| self.live.asset_symbol(id)).join(", ");
+        details.push(format!(
+            "assets: {} -> {}",
+            symbols(&live_assets),
+            symbols(assets)
+        ));
+        Some(ChangeAssets::Change {
+            add_assets,
+            remove_assets,
+        })
+    }
+
+    fn resolve_allow(&self, allow: &AllowConfig, resource: &str) -> anyhow::Result<AllowDTO> {
+        let resolve = |names, kind, name_or_id: &String| {
+            NameLookup::resolve(names, kind, name_or_id)
+                .with_context(|| format!("Invalid permission of the {resource}"))
+        };
+
+        Ok(AllowDTO {
+            auth_scope: allow.auth_scope.into(),
+            users: allow
+                .users
+                .iter()
+                .map(|user| resolve(&self.names.users, "user", user))
+                .collect::<anyhow::Result<_>>()?,
+            user_groups: allow
+                .user_groups
+                .iter()
+                .map(|group| resolve(&self.names.groups, "user group", group))
+                .collect::<anyhow::Result<_>>()?,
+        })
+    }
+
+    /// Looks up an asset by its symbol or by its ID.
+    fn resolve_asset(&self, symbol_or_id: &str) -> anyhow::Result<String> {
+        let matching = self
+            .live
+            .assets
+            .iter()
+            .filter(|asset| {
+                asset.id == symbol_or_id || asset.symbol.eq_ignore_ascii_case(symbol_or_id)
+            })
+            .collect::<Vec<_>>();
+        match matching.as_slice() {
+            [] => bail!("No asset with the symbol or ID \"{symbol_or_id}\""),
+            [asset] => Ok(asset.id.clone()),
+            _ => bail!(
+                "There are multiple assets with the symbol \"{symbol_or_id}\", use the asset ID"
+            ),
+        }
+    }
+}
+
+fn uses_pending_ids(operation: &RequestOperationInput) -> bool {
+    serde_json::to_string(operation)
+        .map(|json| json.contains(PENDING_ID_PREFIX))
+        .unwrap_or(false)
+}
+
+fn same_allow(a: &AllowDTO, b: &AllowDTO) -> bool {
+    let sorted = |ids: &[String]| ids.iter().sorted().cloned().collect::<Vec<_>>();
+
+    AuthScopeConfig::from(a.auth_scope.clone()) == AuthScopeConfig::from(b.auth_scope.clone())
+        && sorted(&a.users) == sorted(&b.users)
+        && sorted(&a.user_groups) == sorted(&b.user_groups)
+}
+
+fn display_option(value: Option<&str>) -> &str {
+    value.unwrap_or("(none)")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use candid::Principal;
+    use station_api::{
+        AccountAssetDTO, AccountDTO, AddressBookEntryDTO, AssetDTO, AuthScopeDTO,
+        CallExternalCanisterResourceTargetDTO, CanisterMethodDTO, ExecutionMethodResourceTargetDTO,
+        NamedRuleDTO, PermissionDTO, PermissionResourceActionDTO, QuorumDTO, RequestPolicyDTO,
+        RequestSpecifierDTO, ResourceDTO, ResourceSpecifierDTO, UserGroupDTO, UserSpecifierDTO,
+        ValidationMethodResourceTargetDTO,
+    };
+
+    const ADMIN_GROUP_ID: &str = "00000000-0000-4000-8000-000000000001";
+    const OPS_GROUP_ID: &str = "00000000-0000-4000-8000-000000000002";
+    const ICP_ASSET_ID: &str = "00000000-0000-4000-8000-000000000003";
+
+    fn live_station() -> LiveStation {
+        let quorum = |group: &str| {
+            RequestPolicyRuleDTO::Quorum(QuorumDTO {
+                approvers: UserSpecifierDTO::Group(vec![group.to_string()]),
+                min_approved: 1,
+            })
+        };
+        let admins = AllowDTO {
+            auth_scope: AuthScopeDTO::Authenticated,
+            users: Vec::new(),
+            user_groups: vec![ADMIN_GROUP_ID.to_string()],
+        };
+
+        LiveStation {
+            user_groups: vec![
+                UserGroupDTO {
+                    id: ADMIN_GROUP_ID.to_string(),
+                    name: "Admin".to_string(),
+                },
+                UserGroupDTO {
+                    id: OPS_GROUP_ID.to_string(),
+                    name: "Ops".to_string(),
+                },
+            ],
+            named_rules: vec![NamedRuleDTO {
+                id: "rule-1".to_string(),
+                name: "Admins".to_string(),
+                description: None,
+                rule: quorum(ADMIN_GROUP_ID),
+            }],
+            request_policies: vec![
+                RequestPolicyDTO {
+                    id: "policy-1".to_string(),
+                    specifier: RequestSpecifierDTO::AddUser,
+                    rule: quorum(ADMIN_GROUP_ID),
+                },
+                RequestPolicyDTO {
+                    id: "policy-2".to_string(),
+                    specifier: RequestSpecifierDTO::AddUserGroup,
+                    rule: quorum(ADMIN_GROUP_ID),
+                },
+            ],
+            permissions: vec![PermissionDTO {
+                allow: admins.clone(),
+                resource: ResourceDTO::Permission(PermissionResourceActionDTO::Read),
+            }],
+            accounts: vec![LiveAccount {
+                account: AccountDTO {
+                    id: "account-1".to_string(),
+                    name: "Treasury".to_string(),
+                    assets: vec![AccountAssetDTO {
+                        asset_id: ICP_ASSET_ID.to_string(),
+                        balance: None,
+                    }],
+                    addresses: Vec::new(),
+                    metadata: Vec::new(),
+                    transfer_request_policy: Some(quorum(ADMIN_GROUP_ID)),
+                    configs_request_policy: None,
+                    last_modification_timestamp: String::new(),
+                },
+                read_permission: admins.clone(),
+                configs_permission: admins.clone(),
+                transfer_permission: admins,
+            }],
+            assets: vec![AssetDTO {
+                id: ICP_ASSET_ID.to_string(),
+                blockchain: "icp".to_string(),
+                symbol: "ICP".to_string(),
+                decimals: 8,
+                standards: vec!["icp_native".to_string()],
+                name: "Internet Computer".to_string(),
+                metadata: Vec::new(),
+            }],
+            address_book: vec![AddressBookEntryDTO {
+                id: "entry-1".to_string(),
+                address_owner: "Exchange".to_string(),
+                address: "abc".to_string(),
+                address_format: "icp_account_identifier".to_string(),
+                blockchain: "icp".to_string(),
+                labels: Vec::new(),
+                metadata: Vec::new(),
+                last_modification_timestamp: String::new(),
+            }],
+            names: NameLookup {
+                groups: [
+                    (ADMIN_GROUP_ID.to_string(), "Admin".to_string()),
+                    (OPS_GROUP_ID.to_string(), "Ops".to_string()),
+                ]
+                .into(),
+                users: Default::default(),
+                named_rules: [("rule-1".to_string(), "Admins".to_string())].into(),
+            },
+        }
+    }
+
+    fn summary(changes: &[PlannedChange]) -> Vec<(ChangeKind, &str, bool)> {
+        changes
+            .iter()
+            .map(|change| {
+                (
+                    change.kind,
+                    change.resource.as_str(),
+                    change.operation.is_none(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unchanged_configuration_has_no_changes() {
+        let live = live_station();
+        let config = live.export();
+
+        assert!(plan(&config, &live).unwrap().is_empty());
+    }
+
+    #[test]
+    fn exported_call_and_permission_policies_round_trip() {
+        let mut live = live_station();
+        live.request_policies.extend([
+            RequestPolicyDTO {
+                id: "policy-3".to_string(),
+                specifier: RequestSpecifierDTO::CallExternalCanister(
+                    CallExternalCanisterResourceTargetDTO {
+                        validation_method: ValidationMethodResourceTargetDTO::No,
+                        execution_method: ExecutionMethodResourceTargetDTO::ExecutionMethod(
+                            CanisterMethodDTO {
+                                canister_id: Principal::from_slice(&[1; 29]),
+                                method_name: "transfer".to_string(),
+                            },
+                        ),
+                    },
+                ),
+                rule: RequestPolicyRuleDTO::AutoApproved,
+            },
+            RequestPolicyDTO {
+                id: "policy-4".to_string(),
+                specifier: RequestSpecifierDTO::EditPermission(ResourceSpecifierDTO::Resource(
+                    ResourceDTO::Permission(PermissionResourceActionDTO::Update),
+                )),
+                rule: RequestPolicyRuleDTO::AutoApproved,
+            },
+        ]);
+
+        let config = live.export();
+        let content = toml::to_string_pretty(&toml::Value::try_from(&config).unwrap()).unwrap();
+        let config: StationConfigFile = toml::from_str(&content).unwrap();
+
+        assert!(plan(&config, &live).unwrap().is_empty());
+    }
+
+    #[test]
+    fn plans_additions_changes_and_removals() {
+        let live = live_station();
+        let config = StationConfigFile {
+            user_groups: Some(vec![
+                UserGroupConfig {
+                    name: "Admin".to_string(),
+                },
+                UserGroupConfig {
+                    name: "Finance".to_string(),
+                },
+            ]),
+            request_policies: Some(vec![
+                RequestPolicyConfig {
+                    specifier: "add_user".to_string(),
+                    rule: "named_rule(Admins)".to_string(),
+                },
+                RequestPolicyConfig {
+                    specifier: "transfer".to_string(),
+                    rule: "quorum(group:Finance,2)".to_string(),
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let changes = plan(&config, &live).unwrap();
+
+        assert_eq!(
+            summary(&changes),
+            vec![
+                (ChangeKind::Add, "user group \"Finance\"", false),
+                (ChangeKind::Change, "request policy add_user", false),
+                (ChangeKind::Add, "request policy transfer", true),
+                (ChangeKind::Remove, "request policy add_user_group", false),
+                (ChangeKind::Remove, "user group \"Ops\"", false),
+            ]
+        );
+        assert_eq!(
+            changes[1].details,
+            vec!["rule: quorum(group:Admin,1) -> named_rule(Admins)".to_string()]
+        );
+    }
+
+    #[test]
+    fn plans_account_changes() {
+        let live = live_station();
+        let mut config = live.export();
+        let account = &mut config.accounts.as_mut().unwrap()[0];
+        account.transfer_policy = None;
+        account
+            .transfer_permission
+            .user_groups
+            .push("Ops".to_string());
+        config.accounts.as_mut().unwrap().push(AccountConfig {
+            name: "Payroll".to_string(),
+            assets: vec!["icp".to_string()],
+            transfer_policy: Some("quorum(group:Finance,1)".to_string()),
+            configs_policy: None,
+            read_permission: AllowConfig::default(),
+            configs_permission: AllowConfig::default(),
+            transfer_permission: AllowConfig::default(),
+        });
+        config.user_groups.as_mut().unwrap().push(UserGroupConfig {
+            name: "Finance".to_string(),
+        });
+
+        let changes = plan(&config, &live).unwrap();
+
+        assert_eq!(
+            summary(&changes),
+            vec![
+                (ChangeKind::Add, "user group \"Finance\"", false),
+                (ChangeKind::Change, "account \"Treasury\"", false),
+                (ChangeKind::Add, "account \"Payroll\"", true),
+            ]
+        );
+        assert_eq!(
+            changes[1].details,
+            vec![
+                "transfer_permission: authenticated, users: [], user groups: [Admin] -> \
+                 authenticated, users: [], user groups: [Admin, Ops]"
+                    .to_string(),
+                "transfer_policy: quorum(group:Admin,1) -> (none)".to_string(),
+            ]
+        );
+        let Some(RequestOperationInput::EditAccount(edit)) = &changes[1].operation else {
+            panic!("expected an account edit");
+        };
+        assert!(edit.change_assets.is_none());
+        assert!(edit.read_permission.is_none());
+        assert!(matches!(
+            edit.transfer_request_policy,
+            Some(RequestPolicyRuleInput::Remove)
+        ));
+    }
+
+    #[test]
+    fn unknown_names_are_rejected() {
+        let live = live_station();
+        let config = StationConfigFile {
+            request_policies: Some(vec![RequestPolicyConfig {
+                specifier: "transfer".to_string(),
+                rule: "quorum(group:Finance,2)".to_string(),
+            }]),
+            ..Default::default()
+        };
+
+        assert!(plan(&config, &live).is_err());
+    }
+}
@@ -0,0 +1,314 @@
+use super::model::{
+    resource_key, AccountConfig, AddressBookEntryConfig, AllowConfig, NamedRuleConfig,
+    PermissionConfig, RequestPolicyConfig, StationConfigFile, UserGroupConfig,
+};
+use crate::{
+    policy::{display_rule, display_specifier, NameLookup},
+    util::fetch_all_pages,
+    DfxOrbit,
+};
+use station_api::{
+    AccountDTO, AccountResourceActionDTO, AddressBookEntryDTO, AllowDTO, AssetDTO, AuthScopeDTO,
+    ListAddressBookEntriesInputDTO, ListPermissionsInput, NamedRuleDTO, PermissionDTO,
+    RequestPolicyDTO, RequestSpecifierDTO, ResourceDTO, ResourceIdDTO, ResourceIdsDTO,
+    UserGroupDTO,
+};
+use std::collections::HashMap;
+
+/// The configuration of a station, as fetched from the station.
+pub(super) struct LiveStation {
+    pub(super) user_groups: Vec<UserGroupDTO>,
+    pub(super) named_rules: Vec<NamedRuleDTO>,
+    /// The request policies, except the ones managed through the accounts.
+    pub(super) request_policies: Vec<RequestPolicyDTO>,
+    /// The permissions, except the ones managed through the accounts.
+    pub(super) permissions: Vec<PermissionDTO>,
+    pub(super) accounts: Vec<LiveAccount>,
+    pub(super) assets: Vec<AssetDTO>,
+    pub(super) address_book: Vec<AddressBookEntryDTO>,
+    pub(super) names: NameLookup,
+}
+
+pub(super) struct LiveAccount {
+    pub(super) account: AccountDTO,
+    pub(super) read_permission: AllowDTO,
+    pub(super) configs_permission: AllowDTO,
+    pub(super) transfer_permission: AllowDTO,
+}
+
+impl DfxOrbit {
+    pub(super) async fn fetch_live_station(&self) -> anyhow::Result<LiveStation> {
+        let (user_groups, users, named_rules, request_policies) = futures::try_join!(
+            self.list_all_user_groups(None),
+            self.list_all_users(None, None),
+            self.list_all_named_rules(),
+            self.list_all_request_policies()
+        )?;
+        let (permissions, accounts, assets, address_book) = futures::try_join!(
+            self.list_all_permissions(None),
+            self.list_all_accounts(None),
+            self.assets_by_id(),
+            self.list_all_address_book_entries()
+        )?;
+
+        // Permissions that were never set are not listed, so the ones of the accounts are
+        // requested explicitly.
+        let account_resources = accounts
+            .iter()
+            .flat_map(|account| account_permission_resources(&account.id))
+            .collect::<Vec<_>>();
+        let mut account_permissions = if account_resources.is_empty() {
+            HashMap::new()
+        } else {
+            self.list_all_permissions(Some(account_resources))
+                .await?
+                .into_iter()
+                .map(|permission| (resource_key(&permission.resource), permission.allow))
+                .collect()
+        };
+        let mut take_allow = |resource: ResourceDTO| {
+            account_permissions
+                .remove(&resource_key(&resource))
+                .unwrap_or_else(restricted)
+        };
+        let accounts = accounts
+            .into_iter()
+            .map(|account| {
+                let [read, configs, transfer] = account_permission_resources(&account.id);
+                LiveAccount {
+                    read_permission: take_allow(read),
+                    configs_permission: take_allow(configs),
+                    transfer_permission: take_allow(transfer),
+                    account,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let account_ids = accounts
+            .iter()
+            .map(|account| account.account.id.clone())
+            .collect::<Vec<_>>();
+        let account_resource_keys = accounts
+            .iter()
+            .flat_map(|account| account_permission_resources(&account.account.id))
+            .map(|resource| resource_key(&resource))
+            .collect::<Vec<_>>();
+
+        let names = NameLookup {
+            groups: user_groups
+                .iter()
+                .map(|group| (group.id.clone(), group.name.clone()))
+                .collect(),
+            users: users.into_iter().map(|user| (user.id, user.name)).collect(),
+            named_rules: named_rules
+                .iter()
+                .map(|named_rule| (named_rule.id.clone(), named_rule.name.clone()))
+                .collect(),
+        };
+
+        let mut assets = assets.into_values().collect::<Vec<_>>();
+        assets.sort_by(|a, b| a.symbol.cmp(&b.symbol).then_with(|| a.id.cmp(&b.id)));
+
+        Ok(LiveStation {
+            user_groups,
+            named_rules,
+            request_policies: request_policies
+                .into_iter()
+                .filter(|policy| !is_account_policy(&policy.specifier, &account_ids))
+                .collect(),
+            permissions: permissions
+                .into_iter()
+                .filter(|permission| {
+                    !account_resource_keys.contains(&resource_key(&permission.resource))
+                })
+                .collect(),
+            accounts,
+            assets,
+            address_book,
+            names,
+        })
+    }
+
+    async fn list_all_permissions(
+        &self,
+        resources: Option<Vec<ResourceDTO>>,
+    ) -> anyhow::Result<Vec<PermissionDTO>> {
+        fetch_all_pages(|paginate| async {
+            let response = self
+                .station
+                .list_permissions(ListPermissionsInput {
+                    resources: resources.clone(),
+                    paginate: Some(paginate),
+                })
+                .await?;
+            anyhow::Ok((response.permissions, response.next_offset))
+        })
+        .await
+    }
+
+    async fn list_all_address_book_entries(&self) -> anyhow::Result<Vec<AddressBookEntryDTO>> {
+        fetch_all_pages(|paginate| async {
+            let response = self
+                .station
+                .list_address_book_entries(ListAddressBookEntriesInputDTO {
+                    ids: None,
+                    addresses: None,
+                    blockchain: None,
+                    labels: None,
+                    paginate: Some(paginate),
+                    address_formats: None,
+                    search_term: None,
+                })
+                .await?;
+            anyhow::Ok((response.address_book_entries, response.next_offset))
+        })
+        .await
+    }
+}
+
+impl LiveStation {
+    /// Converts the configuration of the station into the file format.
+    pub(super) fn export(&self) -> StationConfigFile {
+        StationConfigFile {
+            user_groups: Some(
+                self.user_groups
+                    .iter()
+                    .map(|group| UserGroupConfig {
+                        name: group.name.clone(),
+                    })
+                    .collect(),
+            ),
+            named_rules: Some(
+                self.named_rules
+                    .iter()
+                    .map(|named_rule| NamedRuleConfig {
+                        name: named_rule.name.clone(),
+                        description: named_rule.description.clone(),
+                        rule: display_rule(&named_rule.rule, &self.names),
+                    })
+                    .collect(),
+            ),
+            request_policies: Some(
+                self.request_policies
+                    .iter()
+                    .map(|policy| RequestPolicyConfig {
+                        specifier: display_specifier(&policy.specifier),
+                        rule: display_rule(&policy.rule, &self.names),
+                    })
+                    .collect(),
+            ),
+            permissions: Some(
+                self.permissions
+                    .iter()
+                    .map(|permission| PermissionConfig {
+                        allow: allow_config(&permission.allow, &self.names),
+                        resource: permission.resource.clone(),
+                    })
+                    .collect(),
+            ),
+            accounts: Some(
+                self.accounts
+                    .iter()
+                    .map(|live| AccountConfig {
+                        name: live.account.name.clone(),
+                        assets: live
+                            .account
+                            .assets
+                            .iter()
+                            .map(|account_asset| self.asset_symbol(&account_asset.asset_id))
+                            .collect(),
+                        transfer_policy: live
+                            .account
+                            .transfer_request_policy
+                            .as_ref()
+                            .map(|rule| display_rule(rule, &self.names)),
+                        configs_policy: live
+                            .account
+                            .configs_request_policy
+                            .as_ref()
+                            .map(|rule| display_rule(rule, &self.names)),
+                        read_permission: allow_config(&live.read_permission, &self.names),
+                        configs_permission: allow_config(&live.configs_permission, &self.names),
+                        transfer_permission: allow_config(&live.transfer_permission, &self.names),
+                    })
+                    .collect(),
+            ),
+            address_book: Some(
+                self.address_book
+                    .iter()
+                    .map(|entry| AddressBookEntryConfig {
+                        address_owner: entry.address_owner.clone(),
+                        address: entry.address.clone(),
+                        address_format: entry.address_format.clone(),
+                        blockchain: entry.blockchain.clone(),
+                        labels: entry.labels.clone(),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// The symbol of an asset, as long as it identifies it unambiguously, otherwise the ID.
+    pub(super) fn asset_symbol(&self, asset_id: &str) -> String {
+        let Some(asset) = self.assets.iter().find(|asset| asset.id == asset_id) else {
+            return asset_id.to_string();
+        };
+        let same_symbol = self
+            .assets
+            .iter()
+            .filter(|other| other.symbol.eq_ignore_ascii_case(&asset.symbol))
+            .count();
+        if same_symbol == 1 {
+            asset.symbol.clone()
+        } else {
+            asset.id.clone()
+        }
+    }
+}
+
+/// Converts a permission into the file format, with names instead of IDs.
+pub(super) fn allow_config(allow: &AllowDTO, names: &NameLookup) -> AllowConfig {
+    AllowConfig {
+        auth_scope: allow.auth_scope.clone().into(),
+        users: allow
+            .users
+            .iter()
+            .map(|id| NameLookup::display(&names.users, id).to_string())
+            .collect(),
+        user_groups: allow
+            .user_groups
+            .iter()
+            .map(|id| NameLookup::display(&names.groups, id).to_string())
+            .collect(),
+    }
+}
+
+/// The resources of the read, configs and transfer permissions of an account.
+pub(super) fn account_permission_resources(account_id: &str) -> [ResourceDTO; 3] {
+    let id = || ResourceIdDTO::Id(account_id.to_string());
+    [
+        ResourceDTO::Account(AccountResourceActionDTO::Read(id())),
+        ResourceDTO::Account(AccountResourceActionDTO::Update(id())),
+        ResourceDTO::Account(AccountResourceActionDTO::Transfer(id())),
+    ]
+}
+
+/// Whether the policy is the transfer or configs policy of an account, these are managed through
+/// the accounts.
+fn is_account_policy(specifier: &RequestSpecifierDTO, account_ids: &[String]) -> bool {
+    match specifier {
+        RequestSpecifierDTO::Transfer(ResourceIdsDTO::Ids(ids))
+        | RequestSpecifierDTO::EditAccount(ResourceIdsDTO::Ids(ids)) => {
+            matches!(ids.as_slice(), [id] if account_ids.contains(id))
+        }
+        _ => false,
+    }
+}
+
+pub(super) fn restricted() -> AllowDTO {
+    AllowDTO {
+        auth_scope: AuthScopeDTO::Restricted,
+        users: Vec::new(),
+        user_groups: Vec::new(),
+    }
+}
@@ -0,0 +1,168 @@
+//! Inspects the user groups of the station and makes `AddUserGroup` requests to Orbit.
+
+use crate::{
+    util::{fetch_all_pages, print_as_json},
+    DfxOrbit,
+};
+use anyhow::bail;
+use clap::{Parser, Subcommand};
+use station_api::{
+    AddUserGroupOperationInput, ListUserGroupsInput, RequestOperationInput, UserGroupDTO, UuidDTO,
+};
+use tabled::{
+    settings::{Settings, Style},
+    Table,
+};
+
+/// Inspect the user groups of the station
+#[derive(Debug, Clone, Parser)]
+pub struct GroupArgs {
+    /// Return output as JSON
+    #[clap(short, long)]
+    pub(crate) json: bool,
+
+    #[clap(subcommand)]
+    pub(crate) action: GroupActionArgs,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum GroupActionArgs {
+    /// List the user groups of the station
+    List(GroupListArgs),
+    /// Show a user group
+    Show(GroupShowArgs),
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct GroupListArgs {
+    /// Show only user groups whose name contains this search term
+    #[clap(long)]
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct GroupShowArgs {
+    /// The name or ID of the user group
+    pub group: String,
+}
+
+/// Request changes to the user groups
+#[derive(Debug, Clone, Subcommand)]
+#[clap(version, about, long_about = None)]
+pub enum RequestGroupArgs {
+    /// Request to add a user group
+    Add(RequestGroupAddArgs),
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct RequestGroupAddArgs {
+    /// The name of the new user group
+    #[clap(long)]
+    pub name: String,
+}
+
+impl GroupArgs {
+    pub(crate) async fn execute(self, dfx_orbit: &DfxOrbit) -> anyhow::Result<()> {
+        match self.action {
+            GroupActionArgs::List(args) => {
+                let groups = dfx_orbit.list_all_user_groups(args.search).await?;
+                if self.json {
+                    print_as_json(&groups)?;
+                } else {
+                    println!("{}", display_group_list(&groups));
+                }
+            }
+            GroupActionArgs::Show(args) => {
+                let group = dfx_orbit.resolve_user_group(&args.group).await?;
+                if self.json {
+                    print_as_json(&group)?;
+                } else {
+                    println!("Name: {}\nId: {}", group.name, group.id);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RequestGroupArgs {
+    /// Converts the CLI arg type into the equivalent Orbit API type.
+    pub(crate) fn into_request(self) -> RequestOperationInput {
+        match self {
+            RequestGroupArgs::Add(args) => {
+                RequestOperationInput::AddUserGroup(AddUserGroupOperationInput { name: args.name })
+            }
+        }
+    }
+}
+
+impl DfxOrbit {
+    /// Looks up a user group by its exact name or by its ID.
+    pub(crate) async fn resolve_user_group(
+        &self,
+        name_or_id: &str,
+    ) -> anyhow::Result<UserGroupDTO> {
+        let groups = self.list_all_user_groups(None).await?;
+
+        find_user_group(&groups, name_or_id).cloned()
+    }
+
+    /// Looks up the IDs of user groups by their exact names or by their IDs.
+    pub(crate) async fn resolve_user_group_ids(
+        &self,
+        names_or_ids: &[String],
+    ) -> anyhow::Result<Vec<UuidDTO>> {
+        if names_or_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let groups = self.list_all_user_groups(None).await?;
+        names_or_ids
+            .iter()
+            .map(|name_or_id| Ok(find_user_group(&groups, name_or_id)?.id.clone()))
+            .collect()
+    }
+
+    pub(crate) async fn list_all_user_groups(
+        &self,
+        search: Option<String>,
+    ) -> anyhow::Result<Vec<UserGroupDTO>> {
+        fetch_all_pages(|paginate| async {
+            let response = self
+                .station
+                .list_user_groups(ListUserGroupsInput {
+                    search_term: search.clone(),
+                    paginate: Some(paginate),
+                })
+                .await?;
+            anyhow::Ok((response.user_groups, response.next_offset))
+        })
+        .await
+    }
+}
+
+fn find_user_group<'a>(
+    groups: &'a [UserGroupDTO],
+    name_or_id: &str,
+) -> anyhow::Result<&'a UserGroupDTO> {
+    let matching = groups
+        .iter()
+        .filter(|group| group.id == name_or_id || group.name == name_or_id)
+        .collect::<Vec<_>>();
+    match matching.as_slice() {
+        [] => bail!("No user group with the name or ID \"{name_or_id}\""),
+        [group] => Ok(*group),
+        _ => bail!("There are multiple user groups named \"{name_or_id}\", use the group ID"),
+    }
+}
+
+fn display_group_list(groups: &[UserGroupDTO]) -> String {
+    let data_iter = groups
+        .iter()
+        .map(|group| [group.id.clone(), group.name.clone()]);
+    let titled_iter = std::iter::once([String::from("ID"), String::from("Name")]).chain(data_iter);
+
+    let table_config = Settings::default().with(Style::psql());
+    Table::from_iter(titled_iter).with(table_config).to_string()
+}
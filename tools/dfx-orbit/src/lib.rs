@@ -4,16 +4,23 @@
 
 //! Library for interacting with Orbit on the Internet Computer.
 
+pub mod account;
 pub mod args;
 pub mod asset;
 pub mod canister;
+pub mod config;
 pub mod dfx;
+pub mod group;
 pub mod local_config;
 mod me;
 pub mod permission;
+pub mod policy;
 pub mod review;
 pub mod station;
+pub mod transfer;
+pub mod user;
 mod util;
+pub mod wait;
 
 use anyhow::{anyhow, bail, Context};
 use candid::Principal;
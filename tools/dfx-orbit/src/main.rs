@@ -3,7 +3,7 @@
 //! Note: This will initially be a standalone executable, but will be converted into a dfx extension once the dfx subcommand extension framework is well defined.
 
 use clap::Parser;
-use dfx_orbit::args::DfxOrbitArgs;
+use dfx_orbit::{args::DfxOrbitArgs, wait::RequestWaitError};
 use tokio::runtime::Builder;
 
 fn main() {
@@ -15,7 +15,10 @@ fn main() {
     runtime.block_on(async {
         if let Err(err) = args.execute().await {
             println!("Failed to execute command: {err}");
-            std::process::exit(1);
+            let exit_code = err
+                .downcast_ref::<RequestWaitError>()
+                .map_or(1, RequestWaitError::exit_code);
+            std::process::exit(exit_code);
         }
     });
 }
@@ -56,7 +56,7 @@ impl DfxOrbit {
     }
 }
 
-fn display_user_status_dto(status: &UserStatusDTO) -> &'static str {
+pub(crate) fn display_user_status_dto(status: &UserStatusDTO) -> &'static str {
     match status {
         UserStatusDTO::Active => "Active",
         UserStatusDTO::Inactive => "Inactive",
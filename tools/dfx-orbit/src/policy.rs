@@ -0,0 +1,199 @@
+//! Inspects the request policies of the station and makes `AddRequestPolicy` and
+//! `EditRequestPolicy` requests to Orbit.
+
+mod syntax;
+
+pub(crate) use syntax::{display_rule, display_specifier, parse_rule, parse_specifier, NameLookup};
+
+use crate::{
+    util::{fetch_all_pages, print_as_json},
+    DfxOrbit,
+};
+use clap::{Parser, Subcommand};
+use station_api::{
+    AddRequestPolicyOperationInput, EditRequestPolicyOperationInput, GetRequestPolicyInput,
+    ListNamedRulesInput, NamedRuleDTO, RequestOperationInput, RequestPolicyDTO,
+};
+use tabled::{
+    settings::{Settings, Style},
+    Table,
+};
+
+/// Inspect the request policies of the station
+///
+/// Rules are displayed and written in a compact syntax, e.g. `quorum(group:Admin,2) and not allowlisted`.
+#[derive(Debug, Clone, Parser)]
+pub struct PolicyArgs {
+    /// Return output as JSON
+    #[clap(short, long)]
+    pub(crate) json: bool,
+
+    #[clap(subcommand)]
+    pub(crate) action: PolicyActionArgs,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum PolicyActionArgs {
+    /// List the request policies of the station
+    List,
+    /// Show a request policy
+    Show(PolicyShowArgs),
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct PolicyShowArgs {
+    /// The ID of the request policy
+    pub policy_id: String,
+}
+
+/// Request changes to the request policies
+#[derive(Debug, Clone, Subcommand)]
+#[clap(version, about, long_about = None)]
+pub enum RequestPolicyArgs {
+    /// Request to add a request policy
+    Add(RequestPolicyAddArgs),
+    /// Request to edit a request policy
+    Edit(RequestPolicyEditArgs),
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct RequestPolicyAddArgs {
+    /// The requests the policy applies to, e.g. `transfer` or `edit_user(<user id>)`
+    #[clap(long)]
+    pub specifier: String,
+    /// The rule of the policy, e.g. `quorum(group:Admin,2) and not allowlisted`
+    #[clap(long)]
+    pub rule: String,
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct RequestPolicyEditArgs {
+    /// The ID of the request policy to edit
+    pub policy_id: String,
+    /// The new requests the policy applies to, e.g. `transfer` or `edit_user(<user id>)`
+    #[clap(long)]
+    pub specifier: Option<String>,
+    /// The new rule of the policy, e.g. `quorum(group:Admin,2) and not allowlisted`
+    #[clap(long)]
+    pub rule: Option<String>,
+}
+
+impl PolicyArgs {
+    pub(crate) async fn execute(self, dfx_orbit: &DfxOrbit) -> anyhow::Result<()> {
+        let policies = match self.action {
+            PolicyActionArgs::List => dfx_orbit.list_all_request_policies().await?,
+            PolicyActionArgs::Show(args) => vec![
+                dfx_orbit
+                    .station
+                    .get_request_policy(GetRequestPolicyInput { id: args.policy_id })
+                    .await?
+                    .policy,
+            ],
+        };
+
+        if self.json {
+            print_as_json(&policies)?;
+        } else {
+            let names = dfx_orbit.name_lookup().await?;
+            println!("{}", display_policy_list(&policies, &names));
+        }
+
+        Ok(())
+    }
+}
+
+impl RequestPolicyArgs {
+    /// Converts the CLI arg type into the equivalent Orbit API type.
+    pub(crate) async fn into_request(
+        self,
+        dfx_orbit: &DfxOrbit,
+    ) -> anyhow::Result<RequestOperationInput> {
+        match self {
+            RequestPolicyArgs::Add(args) => {
+                let names = dfx_orbit.name_lookup().await?;
+                Ok(RequestOperationInput::AddRequestPolicy(
+                    AddRequestPolicyOperationInput {
+                        specifier: parse_specifier(&args.specifier)?,
+                        rule: parse_rule(&args.rule, &names)?,
+                    },
+                ))
+            }
+            RequestPolicyArgs::Edit(args) => {
+                let rule = match args.rule {
+                    Some(rule) => Some(parse_rule(&rule, &dfx_orbit.name_lookup().await?)?),
+                    None => None,
+                };
+                Ok(RequestOperationInput::EditRequestPolicy(
+                    EditRequestPolicyOperationInput {
+                        policy_id: args.policy_id,
+                        specifier: args.specifier.as_deref().map(parse_specifier).transpose()?,
+                        rule,
+                    },
+                ))
+            }
+        }
+    }
+}
+
+impl DfxOrbit {
+    /// Fetches the names of the users, user groups and named rules, to write and display rules.
+    pub(crate) async fn name_lookup(&self) -> anyhow::Result<NameLookup> {
+        let (groups, users, named_rules) = futures::try_join!(
+            self.list_all_user_groups(None),
+            self.list_all_users(None, None),
+            self.list_all_named_rules()
+        )?;
+
+        Ok(NameLookup {
+            groups: groups
+                .into_iter()
+                .map(|group| (group.id, group.name))
+                .collect(),
+            users: users.into_iter().map(|user| (user.id, user.name)).collect(),
+            named_rules: named_rules
+                .into_iter()
+                .map(|named_rule| (named_rule.id, named_rule.name))
+                .collect(),
+        })
+    }
+
+    pub(crate) async fn list_all_named_rules(&self) -> anyhow::Result<Vec<NamedRuleDTO>> {
+        fetch_all_pages(|paginate| async {
+            let response = self
+                .station
+                .list_named_rules(ListNamedRulesInput {
+                    paginate: Some(paginate),
+                })
+                .await?;
+            anyhow::Ok((response.named_rules, response.next_offset))
+        })
+        .await
+    }
+
+    pub(crate) async fn list_all_request_policies(&self) -> anyhow::Result<Vec<RequestPolicyDTO>> {
+        fetch_all_pages(|paginate| async {
+            let response = self.station.list_request_policies(paginate).await?;
+            anyhow::Ok((response.policies, response.next_offset))
+        })
+        .await
+    }
+}
+
+fn display_policy_list(policies: &[RequestPolicyDTO], names: &NameLookup) -> String {
+    let data_iter = policies.iter().map(|policy| {
+        [
+            policy.id.clone(),
+            display_specifier(&policy.specifier),
+            display_rule(&policy.rule, names),
+        ]
+    });
+    let titled_iter = std::iter::once([
+        String::from("ID"),
+        String::from("Specifier"),
+        String::from("Rule"),
+    ])
+    .chain(data_iter);
+
+    let table_config = Settings::default().with(Style::psql());
+    Table::from_iter(titled_iter).with(table_config).to_string()
+}
@@ -0,0 +1,756 @@
+//! A compact textual syntax for request policy rules and specifiers.
+//!
+//! Rules are written as boolean expressions over the rules of the station, e.g.
+//! `quorum(group:Admin,2) and not allowlisted`:
+//!
+//! - `auto_approved`, `allowlisted`, `allowlisted_by_metadata(key,value)` and
+//!   `named_rule(<name or id>)`
+//! - `quorum(approvers,min_approved)` and `quorum_percentage(approvers,min_approved)`, where the
+//!   approvers are either `any`, a list of `group:<name or id>` or a list of `user:<name or id>`,
+//!   `no_group` and `no_user` stand for the empty lists that no approver can satisfy
+//! - `a and b`, `a or b`, `not a` and parentheses, `all_of(..)` and `any_of(..)` for explicit lists
+//!
+//! Specifiers are written as the operation in snake case, with the targeted resources in
+//! parentheses if any, e.g. `add_user`, `transfer` or `edit_user_group(<id>,<id>)`. The targets
+//! of calls and specific permissions are written in the JSON format of the station API, e.g.
+//! `edit_permission({"Permission":"Read"})`.
+
+use anyhow::{bail, Context};
+use candid::Principal;
+use serde::{de::DeserializeOwned, Serialize};
+use station_api::{
+    ExternalCanisterIdDTO, MetadataDTO, QuorumDTO, QuorumPercentageDTO, RequestPolicyRuleDTO,
+    RequestSpecifierDTO, ResourceIdsDTO, ResourceSpecifierDTO, UserSpecifierDTO, UuidDTO,
+};
+use std::collections::BTreeMap;
+
+/// The names of the users, user groups and named rules of a station, to write rules with names
+/// instead of IDs.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NameLookup {
+    /// The names of the user groups, by ID.
+    pub(crate) groups: BTreeMap<UuidDTO, String>,
+    /// The names of the users, by ID.
+    pub(crate) users: BTreeMap<UuidDTO, String>,
+    /// The names of the named rules, by ID.
+    pub(crate) named_rules: BTreeMap<UuidDTO, String>,
+}
+
+impl NameLookup {
+    /// Looks up the ID of a user, group or named rule by its exact name or by its ID.
+    pub(crate) fn resolve(
+        names: &BTreeMap<UuidDTO, String>,
+        kind: &str,
+        name_or_id: &str,
+    ) -> anyhow::Result<UuidDTO> {
+        if names.contains_key(name_or_id) {
+            return Ok(name_or_id.to_string());
+        }
+
+        let ids = names
+            .iter()
+            .filter(|(_, name)| name.as_str() == name_or_id)
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        match ids.as_slice() {
+            [] => bail!("No {kind} with the name or ID \"{name_or_id}\""),
+            [id] => Ok(id.clone()),
+            _ => bail!("There are multiple {kind}s named \"{name_or_id}\", use the ID"),
+        }
+    }
+
+    /// The name of a user, group or named rule if it is unambiguous, otherwise the ID.
+    pub(crate) fn display<'a>(names: &'a BTreeMap<UuidDTO, String>, id: &'a str) -> &'a str {
+        match names.get(id) {
+            Some(name) if names.values().filter(|other| *other == name).count() == 1 => {
+                name.as_str()
+            }
+            _ => id,
+        }
+    }
+}
+
+pub(crate) fn parse_rule(input: &str, names: &NameLookup) -> anyhow::Result<RequestPolicyRuleDTO> {
+    let mut parser = Parser::new(input, names)?;
+    let rule = parser.parse_or()?;
+    parser.expect_end()?;
+
+    Ok(rule)
+}
+
+pub(crate) fn display_rule(rule: &RequestPolicyRuleDTO, names: &NameLookup) -> String {
+    display_rule_with_precedence(rule, names, Precedence::Or)
+}
+
+pub(crate) fn parse_specifier(input: &str) -> anyhow::Result<RequestSpecifierDTO> {
+    if let Some(target) = parse_json_target(input, "call_external_canister")? {
+        return Ok(RequestSpecifierDTO::CallExternalCanister(target));
+    }
+    if let Some(resource) = parse_json_target(input, "edit_permission")? {
+        return Ok(RequestSpecifierDTO::EditPermission(
+            ResourceSpecifierDTO::Resource(resource),
+        ));
+    }
+
+    let (operation, resources) = match input.trim().split_once('(') {
+        Some((operation, resources)) => {
+            let resources = resources
+                .strip_suffix(')')
+                .with_context(|| format!("Missing closing parenthesis in \"{input}\""))?;
+            let resources = resources
+                .split(',')
+                .map(|resource| resource.trim().to_string())
+                .filter(|resource| !resource.is_empty())
+                .collect::<Vec<_>>();
+            (operation.trim(), Some(resources))
+        }
+        None => (input.trim(), None),
+    };
+
+    let ids = || match &resources {
+        Some(ids) => ResourceIdsDTO::Ids(ids.clone()),
+        None => ResourceIdsDTO::Any,
+    };
+    let canister = || -> anyhow::Result<ExternalCanisterIdDTO> {
+        match resources.as_deref() {
+            None => Ok(ExternalCanisterIdDTO::Any),
+            Some([canister_id]) => Ok(ExternalCanisterIdDTO::Canister(
+                Principal::from_text(canister_id)
+                    .with_context(|| format!("Invalid canister id \"{canister_id}\""))?,
+            )),
+            Some(_) => bail!("\"{operation}\" targets a single canister"),
+        }
+    };
+    let no_resources = |specifier: RequestSpecifierDTO| -> anyhow::Result<RequestSpecifierDTO> {
+        if resources.is_some() {
+            bail!("\"{operation}\" does not target specific resources");
+        }
+        Ok(specifier)
+    };
+
+    match operation {
+        "add_account" => no_resources(RequestSpecifierDTO::AddAccount),
+        "edit_account" => Ok(RequestSpecifierDTO::EditAccount(ids())),
+        "transfer" => Ok(RequestSpecifierDTO::Transfer(ids())),
+        "add_user" => no_resources(RequestSpecifierDTO::AddUser),
+        "edit_user" => Ok(RequestSpecifierDTO::EditUser(ids())),
+        "add_user_group" => no_resources(RequestSpecifierDTO::AddUserGroup),
+        "edit_user_group" => Ok(RequestSpecifierDTO::EditUserGroup(ids())),
+        "remove_user_group" => Ok(RequestSpecifierDTO::RemoveUserGroup(ids())),
+        "add_address_book_entry" => no_resources(RequestSpecifierDTO::AddAddressBookEntry),
+        "edit_address_book_entry" => Ok(RequestSpecifierDTO::EditAddressBookEntry(ids())),
+        "remove_address_book_entry" => Ok(RequestSpecifierDTO::RemoveAddressBookEntry(ids())),
+        "add_request_policy" => no_resources(RequestSpecifierDTO::AddRequestPolicy),
+        "edit_request_policy" => Ok(RequestSpecifierDTO::EditRequestPolicy(ids())),
+        "remove_request_policy" => Ok(RequestSpecifierDTO::RemoveRequestPolicy(ids())),
+        "add_asset" => no_resources(RequestSpecifierDTO::AddAsset),
+        "edit_asset" => Ok(RequestSpecifierDTO::EditAsset(ids())),
+        "remove_asset" => Ok(RequestSpecifierDTO::RemoveAsset(ids())),
+        "add_named_rule" => no_resources(RequestSpecifierDTO::AddNamedRule),
+        "edit_named_rule" => Ok(RequestSpecifierDTO::EditNamedRule(ids())),
+        "remove_named_rule" => Ok(RequestSpecifierDTO::RemoveNamedRule(ids())),
+        "edit_permission" => no_resources(RequestSpecifierDTO::EditPermission(
+            ResourceSpecifierDTO::Any,
+        )),
+        "create_external_canister" => no_resources(RequestSpecifierDTO::CreateExternalCanister),
+        "change_external_canister" => Ok(RequestSpecifierDTO::ChangeExternalCanister(canister()?)),
+        "fund_external_canister" => Ok(RequestSpecifierDTO::FundExternalCanister(canister()?)),
+        "release_external_canister_control" => Ok(
+            RequestSpecifierDTO::ReleaseExternalCanisterControl(canister()?),
+        ),
+        "system_upgrade" => no_resources(RequestSpecifierDTO::SystemUpgrade),
+        "set_disaster_recovery" => no_resources(RequestSpecifierDTO::SetDisasterRecovery),
+        "manage_system_info" => no_resources(RequestSpecifierDTO::ManageSystemInfo),
+        "freeze_station" => no_resources(RequestSpecifierDTO::FreezeStation),
+        "unfreeze_station" => no_resources(RequestSpecifierDTO::UnfreezeStation),
+        "call_external_canister" => {
+            bail!("\"{operation}\" requires the call target in the JSON format of the station API")
+        }
+        _ => bail!("Unsupported request specifier \"{operation}\""),
+    }
+}
+
+/// Parses the target of `<operation>(<json>)`, returns `None` if the input is not of this form.
+fn parse_json_target<T: DeserializeOwned>(
+    input: &str,
+    operation: &str,
+) -> anyhow::Result<Option<T>> {
+    let Some(target) = input
+        .trim()
+        .strip_prefix(operation)
+        .and_then(|rest| rest.trim_start().strip_prefix('('))
+        .filter(|target| target.trim_start().starts_with('{'))
+    else {
+        return Ok(None);
+    };
+    let target = target
+        .strip_suffix(')')
+        .with_context(|| format!("Missing closing parenthesis in \"{input}\""))?;
+
+    serde_json::from_str(target)
+        .map(Some)
+        .with_context(|| format!("Invalid target of \"{operation}\": {target}"))
+}
+
+fn display_json_target(operation: &str, target: &(impl Serialize + std::fmt::Debug)) -> String {
+    match serde_json::to_string(target) {
+        Ok(json) => format!("{operation}({json})"),
+        // The API types always serialize to JSON, as for the resources of the configuration.
+        Err(_) => format!("{operation}({target:?})"),
+    }
+}
+
+pub(crate) fn display_specifier(specifier: &RequestSpecifierDTO) -> String {
+    let ids = |operation: &str, ids: &ResourceIdsDTO| match ids {
+        ResourceIdsDTO::Any => operation.to_string(),
+        ResourceIdsDTO::Ids(ids) => format!("{operation}({})", ids.join(",")),
+    };
+    let canister = |operation: &str, canister: &ExternalCanisterIdDTO| match canister {
+        ExternalCanisterIdDTO::Any => operation.to_string(),
+        ExternalCanisterIdDTO::Canister(canister_id) => format!("{operation}({canister_id})"),
+    };
+
+    match specifier {
+        RequestSpecifierDTO::AddAccount => "add_account".to_string(),
+        RequestSpecifierDTO::EditAccount(resources) => ids("edit_account", resources),
+        RequestSpecifierDTO::Transfer(resources) => ids("transfer", resources),
+        RequestSpecifierDTO::AddUser => "add_user".to_string(),
+        RequestSpecifierDTO::EditUser(resources) => ids("edit_user", resources),
+        RequestSpecifierDTO::AddUserGroup => "add_user_group".to_string(),
+        RequestSpecifierDTO::EditUserGroup(resources) => ids("edit_user_group", resources),
+        RequestSpecifierDTO::RemoveUserGroup(resources) => ids("remove_user_group", resources),
+        RequestSpecifierDTO::AddAddressBookEntry => "add_address_book_entry".to_string(),
+        RequestSpecifierDTO::EditAddressBookEntry(resources) => {
+            ids("edit_address_book_entry", resources)
+        }
+        RequestSpecifierDTO::RemoveAddressBookEntry(resources) => {
+            ids("remove_address_book_entry", resources)
+        }
+        RequestSpecifierDTO::AddRequestPolicy => "add_request_policy".to_string(),
+        RequestSpecifierDTO::EditRequestPolicy(resources) => ids("edit_request_policy", resources),
+        RequestSpecifierDTO::RemoveRequestPolicy(resources) => {
+            ids("remove_request_policy", resources)
+        }
+        RequestSpecifierDTO::AddAsset => "add_asset".to_string(),
+        RequestSpecifierDTO::EditAsset(resources) => ids("edit_asset", resources),
+        RequestSpecifierDTO::RemoveAsset(resources) => ids("remove_asset", resources),
+        RequestSpecifierDTO::AddNamedRule => "add_named_rule".to_string(),
+        RequestSpecifierDTO::EditNamedRule(resources) => ids("edit_named_rule", resources),
+        RequestSpecifierDTO::RemoveNamedRule(resources) => ids("remove_named_rule", resources),
+        RequestSpecifierDTO::EditPermission(ResourceSpecifierDTO::Any) => {
+            "edit_permission".to_string()
+        }
+        RequestSpecifierDTO::EditPermission(ResourceSpecifierDTO::Resource(resource)) => {
+            display_json_target("edit_permission", resource)
+        }
+        RequestSpecifierDTO::CreateExternalCanister => "create_external_canister".to_string(),
+        RequestSpecifierDTO::ChangeExternalCanister(target) => {
+            canister("change_external_canister", target)
+        }
+        RequestSpecifierDTO::FundExternalCanister(target) => {
+            canister("fund_external_canister", target)
+        }
+        RequestSpecifierDTO::ReleaseExternalCanisterControl(target) => {
+            canister("release_external_canister_control", target)
+        }
+        RequestSpecifierDTO::CallExternalCanister(target) => {
+            display_json_target("call_external_canister", target)
+        }
+        RequestSpecifierDTO::SystemUpgrade => "system_upgrade".to_string(),
+        RequestSpecifierDTO::SetDisasterRecovery => "set_disaster_recovery".to_string(),
+        RequestSpecifierDTO::ManageSystemInfo => "manage_system_info".to_string(),
+        RequestSpecifierDTO::FreezeStation => "freeze_station".to_string(),
+        RequestSpecifierDTO::UnfreezeStation => "unfreeze_station".to_string(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    LeftParen,
+    RightParen,
+    Comma,
+    Colon,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+fn tokenize(input: &str) -> anyhow::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '(' => tokens.push(Token::LeftParen),
+            ')' => tokens.push(Token::RightParen),
+            ',' => tokens.push(Token::Comma),
+            ':' => tokens.push(Token::Colon),
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => value.push(chars.next().context("Unterminated string")?),
+                        Some(c) => value.push(c),
+                        None => bail!("Unterminated string"),
+                    }
+                }
+                tokens.push(Token::Quoted(value));
+            }
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| is_word_char(*c)) {
+                    word.push(c);
+                }
+                tokens.push(Token::Word(word));
+            }
+            c => bail!("Unexpected character '{c}'"),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    names: &'a NameLookup,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &str, names: &'a NameLookup) -> anyhow::Result<Self> {
+        Ok(Self {
+            tokens: tokenize(input)?,
+            position: 0,
+            names,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn next_is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(word)) if word == keyword)
+    }
+
+    fn expect(&mut self, expected: Token) -> anyhow::Result<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => bail!("Expected {expected:?}, found {token:?}"),
+            None => bail!("Expected {expected:?}, found the end of the rule"),
+        }
+    }
+
+    fn expect_end(&self) -> anyhow::Result<()> {
+        match self.peek() {
+            None => Ok(()),
+            Some(token) => bail!("Unexpected {token:?} after the end of the rule"),
+        }
+    }
+
+    fn expect_value(&mut self) -> anyhow::Result<String> {
+        match self.next() {
+            Some(Token::Word(value)) | Some(Token::Quoted(value)) => Ok(value),
+            Some(token) => bail!("Expected a value, found {token:?}"),
+            None => bail!("Expected a value, found the end of the rule"),
+        }
+    }
+
+    fn parse_or(&mut self) -> anyhow::Result<RequestPolicyRuleDTO> {
+        let mut rules = vec![self.parse_and()?];
+        while self.next_is_keyword("or") {
+            self.next();
+            rules.push(self.parse_and()?);
+        }
+
+        Ok(match rules.len() {
+            1 => rules.remove(0),
+            _ => RequestPolicyRuleDTO::AnyOf(rules),
+        })
+    }
+
+    fn parse_and(&mut self) -> anyhow::Result<RequestPolicyRuleDTO> {
+        let mut rules = vec![self.parse_not()?];
+        while self.next_is_keyword("and") {
+            self.next();
+            rules.push(self.parse_not()?);
+        }
+
+        Ok(match rules.len() {
+            1 => rules.remove(0),
+            _ => RequestPolicyRuleDTO::AllOf(rules),
+        })
+    }
+
+    fn parse_not(&mut self) -> anyhow::Result<RequestPolicyRuleDTO> {
+        if self.next_is_keyword("not") {
+            self.next();
+            return Ok(RequestPolicyRuleDTO::Not(Box::new(self.parse_not()?)));
+        }
+
+        self.parse_atom()
+    }
+
+    fn parse_atom(&mut self) -> anyhow::Result<RequestPolicyRuleDTO> {
+        let keyword = match self.next() {
+            Some(Token::LeftParen) => {
+                let rule = self.parse_or()?;
+                self.expect(Token::RightParen)?;
+                return Ok(rule);
+            }
+            Some(Token::Word(keyword)) => keyword,
+            Some(token) => bail!("Expected a rule, found {token:?}"),
+            None => bail!("Expected a rule, found the end of the rule"),
+        };
+
+        match keyword.as_str() {
+            "auto_approved" => Ok(RequestPolicyRuleDTO::AutoApproved),
+            "allowlisted" => Ok(RequestPolicyRuleDTO::AllowListed),
+            "allowlisted_by_metadata" => {
+                self.expect(Token::LeftParen)?;
+                let key = self.expect_value()?;
+                self.expect(Token::Comma)?;
+                let value = self.expect_value()?;
+                self.expect(Token::RightParen)?;
+                Ok(RequestPolicyRuleDTO::AllowListedByMetadata(MetadataDTO {
+                    key,
+                    value,
+                }))
+            }
+            "named_rule" => {
+                self.expect(Token::LeftParen)?;
+                let name_or_id = self.expect_value()?;
+                self.expect(Token::RightParen)?;
+                Ok(RequestPolicyRuleDTO::NamedRule(NameLookup::resolve(
+                    &self.names.named_rules,
+                    "named rule",
+                    &name_or_id,
+                )?))
+            }
+            "quorum" => {
+                let (approvers, min_approved) = self.parse_quorum()?;
+                Ok(RequestPolicyRuleDTO::Quorum(QuorumDTO {
+                    approvers,
+                    min_approved,
+                }))
+            }
+            "quorum_percentage" => {
+                let (approvers, min_approved) = self.parse_quorum()?;
+                if min_approved > 100 {
+                    bail!("The quorum percentage {min_approved} is above 100");
+                }
+                Ok(RequestPolicyRuleDTO::QuorumPercentage(
+                    QuorumPercentageDTO {
+                        approvers,
+                        min_approved,
+                    },
+                ))
+            }
+            "all_of" => Ok(RequestPolicyRuleDTO::AllOf(self.parse_rule_list()?)),
+            "any_of" => Ok(RequestPolicyRuleDTO::AnyOf(self.parse_rule_list()?)),
+            _ => bail!("Unknown rule \"{keyword}\""),
+        }
+    }
+
+    fn parse_rule_list(&mut self) -> anyhow::Result<Vec<RequestPolicyRuleDTO>> {
+        self.expect(Token::LeftParen)?;
+        let mut rules = Vec::new();
+        if self.peek() != Some(&Token::RightParen) {
+            rules.push(self.parse_or()?);
+            while self.peek() == Some(&Token::Comma) {
+                self.next();
+                rules.push(self.parse_or()?);
+            }
+        }
+        self.expect(Token::RightParen)?;
+
+        Ok(rules)
+    }
+
+    /// Parses the arguments of a quorum, e.g. `(group:Admin,group:Finance,2)`.
+    fn parse_quorum(&mut self) -> anyhow::Result<(UserSpecifierDTO, u16)> {
+        self.expect(Token::LeftParen)?;
+
+        let mut any = false;
+        let mut groups = Vec::new();
+        let mut users = Vec::new();
+        let mut empty = None;
+        let min_approved = loop {
+            let value = self.expect_value()?;
+            if self.peek() == Some(&Token::Colon) {
+                self.next();
+                let name_or_id = self.expect_value()?;
+                match value.as_str() {
+                    "group" => groups.push(NameLookup::resolve(
+                        &self.names.groups,
+                        "user group",
+                        &name_or_id,
+                    )?),
+                    "user" => {
+                        users.push(NameLookup::resolve(&self.names.users, "user", &name_or_id)?)
+                    }
+                    _ => bail!("Unknown approvers \"{value}\", expected `group:` or `user:`"),
+                }
+            } else if value == "any" {
+                any = true;
+            } else if value == "no_group" || value == "no_user" {
+                if empty.is_some() {
+                    bail!("The approvers of a quorum are either `any`, groups or users");
+                }
+                empty = Some(value);
+            } else {
+                let min_approved = value
+                    .parse::<u16>()
+                    .with_context(|| format!("Invalid number of approvals \"{value}\""))?;
+                self.expect(Token::RightParen)?;
+                break min_approved;
+            }
+            self.expect(Token::Comma)?;
+        };
+
+        if let Some(empty) = empty {
+            if any || !groups.is_empty() || !users.is_empty() {
+                bail!("`{empty}` cannot be combined with other approvers");
+            }
+
+            let approvers = match empty.as_str() {
+                "no_group" => UserSpecifierDTO::Group(Vec::new()),
+                _ => UserSpecifierDTO::Id(Vec::new()),
+            };
+            return Ok((approvers, min_approved));
+        }
+
+        let approvers = match (any, groups.is_empty(), users.is_empty()) {
+            (true, true, true) => UserSpecifierDTO::Any,
+            (false, false, true) => UserSpecifierDTO::Group(groups),
+            (false, true, false) => UserSpecifierDTO::Id(users),
+            (false, true, true) => bail!("The quorum has no approvers"),
+            _ => bail!("The approvers of a quorum are either `any`, groups or users"),
+        };
+
+        Ok((approvers, min_approved))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Or,
+    And,
+    Not,
+}
+
+fn display_rule_with_precedence(
+    rule: &RequestPolicyRuleDTO,
+    names: &NameLookup,
+    precedence: Precedence,
+) -> String {
+    let parenthesize = |expression: String, expression_precedence: Precedence| {
+        if expression_precedence < precedence {
+            format!("({expression})")
+        } else {
+            expression
+        }
+    };
+
+    match rule {
+        RequestPolicyRuleDTO::AutoApproved => "auto_approved".to_string(),
+        RequestPolicyRuleDTO::AllowListed => "allowlisted".to_string(),
+        RequestPolicyRuleDTO::AllowListedByMetadata(metadata) => format!(
+            "allowlisted_by_metadata({},{})",
+            display_value(&metadata.key),
+            display_value(&metadata.value)
+        ),
+        RequestPolicyRuleDTO::NamedRule(id) => format!(
+            "named_rule({})",
+            display_value(NameLookup::display(&names.named_rules, id))
+        ),
+        RequestPolicyRuleDTO::Quorum(quorum) => format!(
+            "quorum({},{})",
+            display_user_specifier(&quorum.approvers, names),
+            quorum.min_approved
+        ),
+        RequestPolicyRuleDTO::QuorumPercentage(quorum) => format!(
+            "quorum_percentage({},{})",
+            display_user_specifier(&quorum.approvers, names),
+            quorum.min_approved
+        ),
+        RequestPolicyRuleDTO::AnyOf(rules) if rules.len() > 1 => parenthesize(
+            rules
+                .iter()
+                .map(|rule| display_rule_with_precedence(rule, names, Precedence::And))
+                .collect::<Vec<_>>()
+                .join(" or "),
+            Precedence::Or,
+        ),
+        RequestPolicyRuleDTO::AllOf(rules) if rules.len() > 1 => parenthesize(
+            rules
+                .iter()
+                .map(|rule| display_rule_with_precedence(rule, names, Precedence::Not))
+                .collect::<Vec<_>>()
+                .join(" and "),
+            Precedence::And,
+        ),
+        RequestPolicyRuleDTO::AnyOf(rules) => {
+            format!("any_of({})", display_rule_list(rules, names))
+        }
+        RequestPolicyRuleDTO::AllOf(rules) => {
+            format!("all_of({})", display_rule_list(rules, names))
+        }
+        RequestPolicyRuleDTO::Not(rule) => format!(
+            "not {}",
+            display_rule_with_precedence(rule, names, Precedence::Not)
+        ),
+    }
+}
+
+fn display_rule_list(rules: &[RequestPolicyRuleDTO], names: &NameLookup) -> String {
+    rules
+        .iter()
+        .map(|rule| display_rule(rule, names))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn display_user_specifier(specifier: &UserSpecifierDTO, names: &NameLookup) -> String {
+    match specifier {
+        UserSpecifierDTO::Any => "any".to_string(),
+        UserSpecifierDTO::Group(ids) if ids.is_empty() => "no_group".to_string(),
+        UserSpecifierDTO::Id(ids) if ids.is_empty() => "no_user".to_string(),
+        UserSpecifierDTO::Group(ids) => ids
+            .iter()
+            .map(|id| {
+                format!(
+                    "group:{}",
+                    display_value(NameLookup::display(&names.groups, id))
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+        UserSpecifierDTO::Id(ids) => ids
+            .iter()
+            .map(|id| {
+                format!(
+                    "user:{}",
+                    display_value(NameLookup::display(&names.users, id))
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+    }
+}
+
+/// Quotes a value unless it is a single word.
+fn display_value(value: &str) -> String {
+    if !value.is_empty() && value.chars().all(is_word_char) {
+        value.to_string()
+    } else {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    const ADMIN_GROUP_ID: &str = "00000000-0000-4000-8000-000000000000";
+    const FINANCE_GROUP_ID: &str = "00000000-0000-4000-8000-000000000001";
+    const ALICE_USER_ID: &str = "00000000-0000-4000-8000-000000000002";
+    const TREASURY_RULE_ID: &str = "00000000-0000-4000-8000-000000000003";
+
+    fn names() -> NameLookup {
+        NameLookup {
+            groups: BTreeMap::from([
+                (ADMIN_GROUP_ID.to_string(), "Admin".to_string()),
+                (FINANCE_GROUP_ID.to_string(), "Finance Team".to_string()),
+            ]),
+            users: BTreeMap::from([(ALICE_USER_ID.to_string(), "Alice".to_string())]),
+            named_rules: BTreeMap::from([(TREASURY_RULE_ID.to_string(), "Treasury".to_string())]),
+        }
+    }
+
+    #[test]
+    fn parses_rules() {
+        let rule = parse_rule("quorum(group:Admin,2) and not allowlisted", &names()).unwrap();
+
+        let RequestPolicyRuleDTO::AllOf(rules) = rule else {
+            panic!("Expected all_of, got {rule:?}");
+        };
+        assert!(matches!(
+            &rules[0],
+            RequestPolicyRuleDTO::Quorum(QuorumDTO {
+                approvers: UserSpecifierDTO::Group(groups),
+                min_approved: 2,
+            }) if groups == &vec![ADMIN_GROUP_ID.to_string()]
+        ));
+        assert!(
+            matches!(&rules[1], RequestPolicyRuleDTO::Not(rule) if matches!(**rule, RequestPolicyRuleDTO::AllowListed))
+        );
+    }
+
+    #[test]
+    fn rules_round_trip() {
+        for input in [
+            "auto_approved",
+            "quorum(any,1)",
+            "quorum(no_group,1)",
+            "quorum_percentage(no_user,0)",
+            "quorum_percentage(group:Admin,group:\"Finance Team\",51) or allowlisted",
+            "(quorum(user:Alice,1) or auto_approved) and not (allowlisted and named_rule(Treasury))",
+            "allowlisted_by_metadata(tier,\"gold \\\"plus\\\"\")",
+            "all_of()",
+            "any_of(auto_approved)",
+        ] {
+            let rule = parse_rule(input, &names()).unwrap();
+            assert_eq!(display_rule(&rule, &names()), input);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        for input in [
+            "quorum(group:Unknown,1)",
+            "quorum(group:Admin,user:Alice,1)",
+            "quorum(,1)",
+            "quorum(no_group,group:Admin,1)",
+            "quorum(no_group,no_user,1)",
+            "quorum_percentage(any,101)",
+            "allowlisted and",
+            "allowlisted allowlisted",
+            "unknown",
+        ] {
+            assert!(parse_rule(input, &names()).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn specifiers_round_trip() {
+        for input in [
+            "add_user",
+            "transfer",
+            "edit_user_group(00000000-0000-4000-8000-000000000000)",
+            "change_external_canister(ryjl3-tyaaa-aaaaa-aaaba-cai)",
+            r#"edit_permission({"Permission":"Read"})"#,
+            r#"call_external_canister({"validation_method":"No","execution_method":{"ExecutionMethod":{"canister_id":"ryjl3-tyaaa-aaaaa-aaaba-cai","method_name":"transfer"}}})"#,
+        ] {
+            let specifier = parse_specifier(input).unwrap();
+            assert_eq!(display_specifier(&specifier), input);
+        }
+
+        assert!(parse_specifier("add_user(abc)").is_err());
+        assert!(parse_specifier("call_external_canister").is_err());
+        assert!(parse_specifier(r#"edit_permission({"Unknown":"Read"})"#).is_err());
+    }
+}
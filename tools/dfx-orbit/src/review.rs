@@ -1,13 +1,14 @@
-use crate::DfxOrbit;
+use crate::{util::print_as_json, DfxOrbit};
 use clap::{Parser, Subcommand};
 use slog::{info, warn};
 use station_api::{
     GetNextApprovableRequestInput, GetRequestInput, RequestApprovalStatusDTO, RequestStatusDTO,
     SubmitRequestApprovalInput,
 };
-use util::{external_canister_operations, print_as_json};
+use util::external_canister_operations;
 
 pub use crate::review::list::ReviewListArgs;
+pub(crate) use display::display_request_status;
 
 mod display;
 mod list;
@@ -2,7 +2,7 @@ use crate::DfxOrbit;
 use station_api::{
     EvaluatedRequestPolicyRuleDTO, EvaluationStatusDTO, GetRequestResponse,
     RequestAdditionalInfoDTO, RequestApprovalDTO, RequestApprovalStatusDTO, RequestDTO,
-    RequestOperationDTO, RequestStatusDTO,
+    RequestDiffDTO, RequestOperationDTO, RequestStatusDTO,
 };
 use std::{collections::BTreeMap, fmt::Write};
 
@@ -13,6 +13,7 @@ impl DfxOrbit {
     ) -> anyhow::Result<String> {
         let base_info = request.request;
         let add_info = request.additional_info;
+        let diff = request.diff;
 
         let mut output = String::new();
 
@@ -54,15 +55,49 @@ impl DfxOrbit {
             RequestOperationDTO::CallExternalCanister(op) => {
                 self.display_call_canister_operation(&mut output, op.as_ref())?;
             }
+            RequestOperationDTO::Transfer(op) => {
+                self.display_transfer_operation(&mut output, op.as_ref())?;
+            }
             // TODO: CreateCanister Additional information
             // TODO: ConfigureCanister Additional information
             _ => (),
         };
 
+        if let Some(diff) = diff {
+            display_request_diff(&mut output, &diff)?;
+        }
+
         Ok(output)
     }
 }
 
+fn display_request_diff<W: Write>(writer: &mut W, diff: &RequestDiffDTO) -> anyhow::Result<()> {
+    writeln!(writer, "=== CHANGES ===")?;
+    if diff.is_stale {
+        writeln!(
+            writer,
+            "WARNING: The entity was modified after the request was created ({}), review the current values",
+            diff.snapshot_taken_at
+        )?;
+    }
+
+    let display_value = |value: &Option<String>| value.clone().unwrap_or("<none>".to_string());
+    for field in &diff.fields {
+        writeln!(
+            writer,
+            "{}: {} -> {}",
+            field.field,
+            display_value(&field.before),
+            display_value(&field.after)
+        )?;
+        if let Some(current) = &field.current {
+            writeln!(writer, "  currently: {current}")?;
+        }
+    }
+
+    Ok(())
+}
+
 fn display_approvers_and_rejectors<W: Write>(
     writer: &mut W,
     base_info: &RequestDTO,
@@ -252,10 +287,13 @@ pub(super) fn display_request_operation(op: &RequestOperationDTO) -> &'static st
         RequestOperationDTO::AddNamedRule(_) => "AddNamedRule",
         RequestOperationDTO::EditNamedRule(_) => "EditNamedRule",
         RequestOperationDTO::RemoveNamedRule(_) => "RemoveNamedRule",
+        RequestOperationDTO::FreezeStation(_) => "FreezeStation",
+        RequestOperationDTO::UnfreezeStation(_) => "UnfreezeStation",
+        RequestOperationDTO::UpgradeExternalCanisterFleet(_) => "UpgradeExternalCanisterFleet",
     }
 }
 
-pub(super) fn display_request_status(status: &RequestStatusDTO) -> &'static str {
+pub(crate) fn display_request_status(status: &RequestStatusDTO) -> &'static str {
     match status {
         RequestStatusDTO::Created => "Created",
         RequestStatusDTO::Approved => "Approved",
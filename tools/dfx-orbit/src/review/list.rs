@@ -3,10 +3,12 @@ use super::{
     util::external_canister_operations,
 };
 use crate::DfxOrbit;
+use candid::Principal;
 use clap::Parser;
 use slog::debug;
 use station_api::{
-    ListRequestsInput, ListRequestsResponse, ListRequestsSortBy, PaginationInput, SortDirection,
+    ListRequestsInput, ListRequestsResourceFilterDTO, ListRequestsResponse, ListRequestsSortBy,
+    PaginationInput, SortDirection,
 };
 use std::collections::HashMap;
 use tabled::{
@@ -37,6 +39,68 @@ pub struct ReviewListArgs {
     /// Limit the amount of responses to the specified value
     #[clap(short, long)]
     pub limit: Option<u64>,
+
+    /// Show only requests whose title or summary contain the words of this search term
+    #[clap(long)]
+    pub search: Option<String>,
+
+    /// Show only requests that affect the account with this id (implies `--all`)
+    #[clap(long)]
+    pub account: Vec<String>,
+
+    /// Show only requests that affect the user with this id (implies `--all`)
+    #[clap(long)]
+    pub user: Vec<String>,
+
+    /// Show only requests that affect the external canister with this canister id (implies `--all`)
+    #[clap(long)]
+    pub canister: Vec<Principal>,
+
+    /// Show only requests that affect the asset with this id (implies `--all`)
+    #[clap(long)]
+    pub asset: Vec<String>,
+
+    /// Show only requests that affect the request policy with this id (implies `--all`)
+    #[clap(long)]
+    pub policy: Vec<String>,
+
+    /// Show only requests with this tag, e.g. the bundle of `config apply` (implies `--all`)
+    #[clap(long)]
+    pub tag: Vec<String>,
+}
+
+impl ReviewListArgs {
+    fn resource_filters(&self) -> Vec<ListRequestsResourceFilterDTO> {
+        self.account
+            .iter()
+            .cloned()
+            .map(ListRequestsResourceFilterDTO::Account)
+            .chain(
+                self.user
+                    .iter()
+                    .cloned()
+                    .map(ListRequestsResourceFilterDTO::User),
+            )
+            .chain(
+                self.canister
+                    .iter()
+                    .copied()
+                    .map(ListRequestsResourceFilterDTO::ExternalCanister),
+            )
+            .chain(
+                self.asset
+                    .iter()
+                    .cloned()
+                    .map(ListRequestsResourceFilterDTO::Asset),
+            )
+            .chain(
+                self.policy
+                    .iter()
+                    .cloned()
+                    .map(ListRequestsResourceFilterDTO::RequestPolicy),
+            )
+            .collect()
+    }
 }
 
 impl DfxOrbit {
@@ -83,11 +147,14 @@ impl DfxOrbit {
     }
 
     fn initial_request(&self, args: &ReviewListArgs) -> ListRequestsInput {
+        let resources = args.resource_filters();
+
         ListRequestsInput {
             requester_ids: None,
             approver_ids: None,
             statuses: None,
-            operation_types: (!args.all).then(external_canister_operations),
+            operation_types: (!args.all && resources.is_empty() && args.tag.is_empty())
+                .then(external_canister_operations),
             expiration_from_dt: None,
             expiration_to_dt: None,
             created_from_dt: None,
@@ -100,7 +167,9 @@ impl DfxOrbit {
             only_approvable: args.only_approvable,
             with_evaluation_results: true,
             deduplication_keys: None,
-            tags: None,
+            tags: (!args.tag.is_empty()).then(|| args.tag.clone()),
+            search_term: args.search.clone(),
+            resources: (!resources.is_empty()).then_some(resources),
         }
     }
 
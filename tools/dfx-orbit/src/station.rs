@@ -11,6 +11,7 @@ use clap::{Parser, Subcommand};
 use std::fmt::{self, Display, Formatter};
 
 pub use self::agent::{StationAgent, StationAgentResult, StationConfig};
+pub use self::error::StationAgentError;
 
 /// Station management commands
 #[derive(Debug, Subcommand)]
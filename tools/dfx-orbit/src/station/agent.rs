@@ -2,10 +2,17 @@ pub use crate::station::{config::StationConfig, error::StationAgentResult};
 use candid::CandidType;
 use ic_agent::{agent::UpdateBuilder, Agent};
 use station_api::{
-    ApiErrorDTO, CreateRequestInput, CreateRequestResponse, GetNextApprovableRequestInput,
-    GetNextApprovableRequestResponse, GetRequestInput, GetRequestResponse, ListRequestsInput,
-    ListRequestsResponse, MeResponse, RequestApprovalStatusDTO, SubmitRequestApprovalInput,
-    SubmitRequestApprovalResponse,
+    ApiErrorDTO, CreateRequestInput, CreateRequestResponse, FetchAccountBalancesInput,
+    FetchAccountBalancesResponse, GetAccountInput, GetAccountResponse, GetExternalCanisterInput,
+    GetExternalCanisterResponse, GetNextApprovableRequestInput, GetNextApprovableRequestResponse,
+    GetRequestInput, GetRequestPolicyInput, GetRequestPolicyResponse, GetRequestResponse,
+    GetUserGroupInput, GetUserGroupResponse, GetUserInput, GetUserResponse, ListAccountsInput,
+    ListAccountsResponse, ListAddressBookEntriesInputDTO, ListAddressBookEntriesResponseDTO,
+    ListAssetsInput, ListAssetsResponse, ListNamedRulesInput, ListNamedRulesResponse,
+    ListPermissionsInput, ListPermissionsResponse, ListRequestPoliciesInput,
+    ListRequestPoliciesResponse, ListRequestsInput, ListRequestsResponse, ListUserGroupsInput,
+    ListUserGroupsResponse, ListUsersInput, ListUsersResponse, MeResponse,
+    RequestApprovalStatusDTO, SubmitRequestApprovalInput, SubmitRequestApprovalResponse,
 };
 
 /// A dfx agent for communicating with a specific station.
@@ -68,14 +75,14 @@ impl StationAgent {
     }
 
     pub async fn review_id(&self, args: GetRequestInput) -> StationAgentResult<GetRequestResponse> {
-        self.update_orbit_typed("get_request", args).await
+        self.query_orbit_typed("get_request", args).await
     }
 
     pub async fn review_list(
         &self,
         args: ListRequestsInput,
     ) -> StationAgentResult<ListRequestsResponse> {
-        self.update_orbit_typed("list_requests", args).await
+        self.query_orbit_typed("list_requests", args).await
     }
 
     pub async fn review_next(
@@ -86,6 +93,100 @@ impl StationAgent {
             .await
     }
 
+    pub async fn get_external_canister(
+        &self,
+        args: GetExternalCanisterInput,
+    ) -> StationAgentResult<GetExternalCanisterResponse> {
+        self.query_orbit_typed("get_external_canister", args).await
+    }
+
+    pub async fn list_accounts(
+        &self,
+        args: ListAccountsInput,
+    ) -> StationAgentResult<ListAccountsResponse> {
+        self.query_orbit_typed("list_accounts", args).await
+    }
+
+    pub async fn get_account(
+        &self,
+        args: GetAccountInput,
+    ) -> StationAgentResult<GetAccountResponse> {
+        self.query_orbit_typed("get_account", args).await
+    }
+
+    pub async fn fetch_account_balances(
+        &self,
+        args: FetchAccountBalancesInput,
+    ) -> StationAgentResult<FetchAccountBalancesResponse> {
+        self.update_orbit_typed("fetch_account_balances", args)
+            .await
+    }
+
+    pub async fn list_assets(
+        &self,
+        args: ListAssetsInput,
+    ) -> StationAgentResult<ListAssetsResponse> {
+        self.query_orbit_typed("list_assets", args).await
+    }
+
+    pub async fn list_address_book_entries(
+        &self,
+        args: ListAddressBookEntriesInputDTO,
+    ) -> StationAgentResult<ListAddressBookEntriesResponseDTO> {
+        self.query_orbit_typed("list_address_book_entries", args)
+            .await
+    }
+
+    pub async fn list_users(&self, args: ListUsersInput) -> StationAgentResult<ListUsersResponse> {
+        self.query_orbit_typed("list_users", args).await
+    }
+
+    pub async fn get_user(&self, args: GetUserInput) -> StationAgentResult<GetUserResponse> {
+        self.query_orbit_typed("get_user", args).await
+    }
+
+    pub async fn list_user_groups(
+        &self,
+        args: ListUserGroupsInput,
+    ) -> StationAgentResult<ListUserGroupsResponse> {
+        self.query_orbit_typed("list_user_groups", args).await
+    }
+
+    pub async fn get_user_group(
+        &self,
+        args: GetUserGroupInput,
+    ) -> StationAgentResult<GetUserGroupResponse> {
+        self.query_orbit_typed("get_user_group", args).await
+    }
+
+    pub async fn list_request_policies(
+        &self,
+        args: ListRequestPoliciesInput,
+    ) -> StationAgentResult<ListRequestPoliciesResponse> {
+        self.query_orbit_typed("list_request_policies", args).await
+    }
+
+    pub async fn get_request_policy(
+        &self,
+        args: GetRequestPolicyInput,
+    ) -> StationAgentResult<GetRequestPolicyResponse> {
+        self.query_orbit_typed("get_request_policy", args).await
+    }
+
+    pub async fn list_named_rules(
+        &self,
+        args: ListNamedRulesInput,
+    ) -> StationAgentResult<ListNamedRulesResponse> {
+        self.query_orbit_typed("list_named_rules", args).await
+    }
+
+    pub async fn list_permissions(
+        &self,
+        args: ListPermissionsInput,
+    ) -> StationAgentResult<ListPermissionsResponse> {
+        self.query_orbit_typed("list_permissions", args).await
+    }
+
     async fn update_orbit(&self, method_name: &str) -> UpdateBuilder {
         self.agent.update(&self.config.station_id, method_name)
     }
@@ -115,6 +216,31 @@ impl StationAgent {
         Ok(ans?)
     }
 
+    /// Makes a query call to the station.
+    ///
+    /// This version integrates candid encoding / decoding
+    async fn query_orbit_typed<Req, Res>(
+        &self,
+        method_name: &str,
+        request: Req,
+    ) -> StationAgentResult<Res>
+    where
+        Req: CandidType,
+        Res: CandidType + for<'a> candid::Deserialize<'a>,
+    {
+        let encoded_request = candid::encode_one(request)?;
+
+        let response_bytes = self
+            .agent
+            .query(&self.config.station_id, method_name)
+            .with_arg(encoded_request)
+            .call()
+            .await?;
+        let ans: Result<Res, ApiErrorDTO> = candid::decode_one(&response_bytes)?;
+
+        Ok(ans?)
+    }
+
     /// The URL for a request in the Orbit UI.
     pub fn request_url(&self, request_id: &str) -> String {
         format!(
@@ -14,6 +14,20 @@ pub enum StationAgentError {
     Agent(#[from] AgentError),
 }
 
+impl StationAgentError {
+    /// Whether the station refused the call because the caller lacks the permission to make it.
+    pub fn is_unauthorized(&self) -> bool {
+        match self {
+            Self::Api(error) => error.code == "UNAUTHORIZED",
+            // The authorization guard of the station traps, which rejects the call.
+            Self::Agent(
+                AgentError::CertifiedReject(reject) | AgentError::UncertifiedReject(reject),
+            ) => reject.reject_message.contains("Unauthorized access"),
+            _ => false,
+        }
+    }
+}
+
 impl From<ApiErrorDTO> for StationAgentError {
     fn from(value: ApiErrorDTO) -> Self {
         Self::Api(value)
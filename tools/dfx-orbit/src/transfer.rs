@@ -0,0 +1,226 @@
+//! Makes `Transfer` requests to Orbit.
+
+use crate::{
+    account::{format_amount, parse_amount},
+    station::StationAgentError,
+    util::fetch_all_pages,
+    DfxOrbit,
+};
+use anyhow::bail;
+use clap::Parser;
+use station_api::{
+    AssetDTO, GetRequestResponse, ListAddressBookEntriesInputDTO, MetadataDTO, RequestOperationDTO,
+    RequestOperationInput, TransferOperationDTO, TransferOperationInput,
+};
+use std::fmt::Write;
+
+/// The metadata key under which the station expects the memo of a transfer.
+const METADATA_MEMO_KEY: &str = "memo";
+
+/// Requests a transfer of funds from an account of the station.
+#[derive(Debug, Clone, Parser)]
+pub struct RequestTransferArgs {
+    /// The name or ID of the account to transfer from
+    #[clap(long)]
+    pub from_account: String,
+    /// The destination address, or the owner of an address in the address book
+    #[clap(long)]
+    pub to: String,
+    /// The amount to transfer, in units of the asset (e.g. `1.5`)
+    #[clap(long)]
+    pub amount: String,
+    /// The symbol or ID of the asset to transfer, can be omitted if the account holds a single asset
+    #[clap(long)]
+    pub asset: Option<String>,
+    /// The memo to attach to the transfer
+    #[clap(long)]
+    pub memo: Option<u64>,
+    /// The token standard to transfer with, defaults to the first standard of the asset
+    #[clap(long)]
+    pub standard: Option<String>,
+}
+
+impl RequestTransferArgs {
+    /// Converts the CLI arg type into the equivalent Orbit API type.
+    pub(crate) async fn into_request(
+        self,
+        dfx_orbit: &DfxOrbit,
+    ) -> anyhow::Result<RequestOperationInput> {
+        Ok(RequestOperationInput::Transfer(
+            self.transfer_input(dfx_orbit).await?,
+        ))
+    }
+
+    pub(crate) async fn verify(
+        &self,
+        dfx_orbit: &DfxOrbit,
+        request: &GetRequestResponse,
+    ) -> anyhow::Result<()> {
+        let expected = self.transfer_input(dfx_orbit).await?;
+
+        let RequestOperationDTO::Transfer(op) = &request.request.operation else {
+            bail!("This request is not a transfer");
+        };
+        let input = &op.input;
+        if input.from_account_id != expected.from_account_id {
+            bail!(
+                "The request transfers from another account: \"{}\"",
+                input.from_account_id
+            );
+        }
+        if input.from_asset_id != expected.from_asset_id {
+            bail!(
+                "The request transfers another asset: \"{}\"",
+                op.from_asset.symbol
+            );
+        }
+        if input.with_standard != expected.with_standard {
+            bail!(
+                "The request transfers with another standard: \"{}\"",
+                input.with_standard
+            );
+        }
+        if input.to != expected.to {
+            bail!("The request transfers to another address: \"{}\"", input.to);
+        }
+        if input.amount != expected.amount {
+            bail!(
+                "The request transfers another amount: {} {}",
+                format_amount(&input.amount, op.from_asset.decimals),
+                op.from_asset.symbol
+            );
+        }
+        if memo(&input.metadata) != memo(&expected.metadata) {
+            bail!("The memo of the request does not match");
+        }
+
+        Ok(())
+    }
+
+    async fn transfer_input(&self, dfx_orbit: &DfxOrbit) -> anyhow::Result<TransferOperationInput> {
+        let account = dfx_orbit.resolve_account(&self.from_account).await?;
+        let asset = dfx_orbit
+            .resolve_account_asset(&account, self.asset.as_deref())
+            .await?;
+        let with_standard = match &self.standard {
+            Some(standard) => standard.clone(),
+            None => match asset.standards.first() {
+                Some(standard) => standard.clone(),
+                None => bail!("The asset \"{}\" has no token standard", asset.symbol),
+            },
+        };
+        let to = dfx_orbit.resolve_destination(&self.to, &asset).await?;
+        let amount = parse_amount(&self.amount, asset.decimals)?;
+        let metadata = self
+            .memo
+            .map(|memo| MetadataDTO {
+                key: METADATA_MEMO_KEY.to_string(),
+                value: memo.to_string(),
+            })
+            .into_iter()
+            .collect();
+
+        Ok(TransferOperationInput {
+            from_account_id: account.id,
+            from_asset_id: asset.id,
+            with_standard,
+            to,
+            amount,
+            fee: None,
+            metadata,
+            network: None,
+        })
+    }
+}
+
+impl DfxOrbit {
+    /// Resolves the owner of an address book entry to its address.
+    ///
+    /// If no entry of the blockchain of the asset is owned by `to`, it is used as the address.
+    async fn resolve_destination(&self, to: &str, asset: &AssetDTO) -> anyhow::Result<String> {
+        let entries = fetch_all_pages(|paginate| async {
+            let response = self
+                .station
+                .list_address_book_entries(ListAddressBookEntriesInputDTO {
+                    ids: None,
+                    addresses: None,
+                    blockchain: Some(asset.blockchain.clone()),
+                    labels: None,
+                    paginate: Some(paginate),
+                    address_formats: None,
+                    search_term: Some(to.to_string()),
+                })
+                .await?;
+            anyhow::Ok((response.address_book_entries, response.next_offset))
+        })
+        .await;
+
+        // Without read access to the address book only addresses can be used,
+        // the station validates them when the request is created.
+        let entries = match entries {
+            Ok(entries) => entries,
+            Err(err)
+                if err
+                    .downcast_ref::<StationAgentError>()
+                    .is_some_and(StationAgentError::is_unauthorized) =>
+            {
+                return Ok(to.to_string());
+            }
+            Err(err) => return Err(err),
+        };
+
+        let entries = entries
+            .into_iter()
+            .filter(|entry| entry.address_owner == to)
+            .collect::<Vec<_>>();
+        match entries.as_slice() {
+            [] => Ok(to.to_string()),
+            [entry] => Ok(entry.address.clone()),
+            _ => bail!("There are multiple addresses owned by \"{to}\", use the address instead"),
+        }
+    }
+
+    pub(crate) fn display_transfer_operation(
+        &self,
+        output: &mut String,
+        op: &TransferOperationDTO,
+    ) -> anyhow::Result<()> {
+        writeln!(output, "=== Transfer ===")?;
+        match &op.from_account {
+            Some(account) => writeln!(output, "From account: {} ({})", account.name, account.id)?,
+            None => writeln!(output, "From account: {}", op.input.from_account_id)?,
+        }
+        writeln!(
+            output,
+            "Asset: {} ({}, {})",
+            op.from_asset.symbol, op.from_asset.id, op.input.with_standard
+        )?;
+        writeln!(output, "To: {}", op.input.to)?;
+        writeln!(
+            output,
+            "Amount: {} {}",
+            format_amount(&op.input.amount, op.from_asset.decimals),
+            op.from_asset.symbol
+        )?;
+        if let Some(fee) = op.fee.as_ref().or(op.input.fee.as_ref()) {
+            writeln!(
+                output,
+                "Fee: {} {}",
+                format_amount(fee, op.from_asset.decimals),
+                op.from_asset.symbol
+            )?;
+        }
+        if let Some(memo) = memo(&op.input.metadata) {
+            writeln!(output, "Memo: {memo}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn memo(metadata: &[MetadataDTO]) -> Option<&str> {
+    metadata
+        .iter()
+        .find(|metadata| metadata.key == METADATA_MEMO_KEY)
+        .map(|metadata| metadata.value.as_str())
+}
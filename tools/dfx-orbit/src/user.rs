@@ -0,0 +1,270 @@
+//! Inspects the users of the station and makes `AddUser` and `EditUser` requests to Orbit.
+
+use crate::{
+    me::display_user_status_dto,
+    util::{fetch_all_pages, print_as_json},
+    DfxOrbit,
+};
+use anyhow::bail;
+use candid::Principal;
+use clap::{Parser, Subcommand, ValueEnum};
+use itertools::Itertools;
+use station_api::{
+    AddUserOperationInput, EditUserOperationInput, ListUsersInput, RequestOperationInput, UserDTO,
+    UserStatusDTO,
+};
+use std::fmt::Write;
+use tabled::{
+    settings::{Settings, Style},
+    Table,
+};
+
+/// Inspect the users of the station
+#[derive(Debug, Clone, Parser)]
+pub struct UserArgs {
+    /// Return output as JSON
+    #[clap(short, long)]
+    pub(crate) json: bool,
+
+    #[clap(subcommand)]
+    pub(crate) action: UserActionArgs,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum UserActionArgs {
+    /// List the users of the station
+    List(UserListArgs),
+    /// Show a user
+    Show(UserShowArgs),
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct UserListArgs {
+    /// Show only users whose name contains this search term
+    #[clap(long)]
+    pub search: Option<String>,
+
+    /// Show only the members of this user group (name or ID)
+    #[clap(long)]
+    pub group: Vec<String>,
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct UserShowArgs {
+    /// The name or ID of the user
+    pub user: String,
+}
+
+/// Request changes to the users
+#[derive(Debug, Clone, Subcommand)]
+#[clap(version, about, long_about = None)]
+pub enum RequestUserArgs {
+    /// Request to add a user
+    Add(RequestUserAddArgs),
+    /// Request to edit a user
+    Edit(RequestUserEditArgs),
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct RequestUserAddArgs {
+    /// The name of the new user
+    #[clap(long)]
+    pub name: String,
+    /// An identity of the new user
+    #[clap(long)]
+    pub identity: Vec<Principal>,
+    /// A user group (name or ID) the new user is a member of
+    #[clap(long)]
+    pub group: Vec<String>,
+    /// The status of the new user
+    #[clap(long, value_enum, default_value = "active")]
+    pub status: UserStatusArgs,
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct RequestUserEditArgs {
+    /// The name or ID of the user to edit
+    pub user: String,
+    /// The new name of the user
+    #[clap(long)]
+    pub name: Option<String>,
+    /// An identity of the user.  WARNING: Any identity that is not listed will be removed from the user.
+    #[clap(long)]
+    pub identity: Vec<Principal>,
+    /// A user group (name or ID) of the user.  WARNING: The user is removed from any group that is not listed.
+    #[clap(long)]
+    pub group: Vec<String>,
+    /// Remove the user from all its user groups
+    #[clap(long, conflicts_with = "group")]
+    pub clear_groups: bool,
+    /// The new status of the user
+    #[clap(long, value_enum)]
+    pub status: Option<UserStatusArgs>,
+    /// Cancel the pending requests of the user
+    #[clap(long)]
+    pub cancel_pending_requests: bool,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, ValueEnum)]
+pub enum UserStatusArgs {
+    Active,
+    Inactive,
+}
+
+impl From<UserStatusArgs> for UserStatusDTO {
+    fn from(status: UserStatusArgs) -> Self {
+        match status {
+            UserStatusArgs::Active => Self::Active,
+            UserStatusArgs::Inactive => Self::Inactive,
+        }
+    }
+}
+
+impl UserArgs {
+    pub(crate) async fn execute(self, dfx_orbit: &DfxOrbit) -> anyhow::Result<()> {
+        match self.action {
+            UserActionArgs::List(args) => {
+                let groups = dfx_orbit.resolve_user_group_ids(&args.group).await?;
+                let users = dfx_orbit
+                    .list_all_users(args.search, (!groups.is_empty()).then_some(groups))
+                    .await?;
+                if self.json {
+                    print_as_json(&users)?;
+                } else {
+                    println!("{}", display_user_list(&users));
+                }
+            }
+            UserActionArgs::Show(args) => {
+                let user = dfx_orbit.resolve_user(&args.user).await?;
+                if self.json {
+                    print_as_json(&user)?;
+                } else {
+                    println!("{}", display_user(&user)?);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RequestUserArgs {
+    /// Converts the CLI arg type into the equivalent Orbit API type.
+    pub(crate) async fn into_request(
+        self,
+        dfx_orbit: &DfxOrbit,
+    ) -> anyhow::Result<RequestOperationInput> {
+        match self {
+            RequestUserArgs::Add(args) => {
+                Ok(RequestOperationInput::AddUser(AddUserOperationInput {
+                    name: args.name,
+                    identities: args.identity,
+                    groups: dfx_orbit.resolve_user_group_ids(&args.group).await?,
+                    status: args.status.into(),
+                }))
+            }
+            RequestUserArgs::Edit(args) => {
+                let user = dfx_orbit.resolve_user(&args.user).await?;
+                let groups = dfx_orbit.resolve_user_group_ids(&args.group).await?;
+                // Without groups the groups of the user are left unchanged, unless they are
+                // explicitly cleared.
+                let groups = (args.clear_groups || !groups.is_empty()).then_some(groups);
+
+                Ok(RequestOperationInput::EditUser(EditUserOperationInput {
+                    id: user.id,
+                    name: args.name,
+                    identities: (!args.identity.is_empty()).then_some(args.identity),
+                    groups,
+                    status: args.status.map(Into::into),
+                    cancel_pending_requests: args.cancel_pending_requests.then_some(true),
+                }))
+            }
+        }
+    }
+}
+
+impl DfxOrbit {
+    /// Looks up a user by its exact name or by its ID.
+    pub(crate) async fn resolve_user(&self, name_or_id: &str) -> anyhow::Result<UserDTO> {
+        let mut users = self
+            .list_all_users(None, None)
+            .await?
+            .into_iter()
+            .filter(|user| user.id == name_or_id || user.name == name_or_id)
+            .collect::<Vec<_>>();
+        match users.len() {
+            0 => bail!("No user with the name or ID \"{name_or_id}\""),
+            1 => Ok(users.remove(0)),
+            _ => bail!("There are multiple users named \"{name_or_id}\", use the user ID"),
+        }
+    }
+
+    pub(crate) async fn list_all_users(
+        &self,
+        search: Option<String>,
+        groups: Option<Vec<String>>,
+    ) -> anyhow::Result<Vec<UserDTO>> {
+        fetch_all_pages(|paginate| async {
+            let response = self
+                .station
+                .list_users(ListUsersInput {
+                    search_term: search.clone(),
+                    statuses: None,
+                    groups: groups.clone(),
+                    paginate: Some(paginate),
+                })
+                .await?;
+            anyhow::Ok((response.users, response.next_offset))
+        })
+        .await
+    }
+}
+
+fn display_user_list(users: &[UserDTO]) -> String {
+    let data_iter = users.iter().map(|user| {
+        [
+            user.id.clone(),
+            user.name.clone(),
+            display_user_status_dto(&user.status).to_string(),
+            user.groups.iter().map(|group| &group.name).join(", "),
+        ]
+    });
+    let titled_iter = std::iter::once([
+        String::from("ID"),
+        String::from("Name"),
+        String::from("Status"),
+        String::from("Groups"),
+    ])
+    .chain(data_iter);
+
+    let table_config = Settings::default().with(Style::psql());
+    Table::from_iter(titled_iter).with(table_config).to_string()
+}
+
+fn display_user(user: &UserDTO) -> anyhow::Result<String> {
+    let mut output = String::new();
+
+    writeln!(output, "Name: {}", user.name)?;
+    writeln!(output, "Id: {}", user.id)?;
+    writeln!(output, "Status: {}", display_user_status_dto(&user.status))?;
+    writeln!(
+        output,
+        "Identities: {}",
+        user.identities.iter().map(|p| format!("\n\t{p}")).join("")
+    )?;
+    writeln!(
+        output,
+        "Groups: {}",
+        user.groups
+            .iter()
+            .map(|group| format!("\n\t{} ({})", group.name, group.id))
+            .join("")
+    )?;
+    writeln!(
+        output,
+        "Last modified: {}",
+        user.last_modification_timestamp
+    )?;
+
+    Ok(output)
+}
@@ -1,7 +1,12 @@
 use crate::DfxOrbit;
 use anyhow::{bail, Context};
 use dfx_core::config::model::dfinity::CanisterTypeProperties;
-use std::path::{Path, PathBuf};
+use serde::Serialize;
+use station_api::PaginationInput;
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+};
 
 impl DfxOrbit {
     pub(super) fn as_path_bufs(
@@ -46,3 +51,34 @@ pub(super) fn init_logger(verbose: u8, quiet: u8) -> anyhow::Result<slog::Logger
     let drain = slog_async::Async::new(drain).build().fuse();
     Ok(slog::Logger::root(drain, slog::o!()))
 }
+
+pub(crate) fn print_as_json<D>(data: D) -> anyhow::Result<()>
+where
+    D: Serialize,
+{
+    println!("{}", serde_json::to_string_pretty(&data)?);
+    Ok(())
+}
+
+/// Fetches all the pages of a paginated list, one page after the other.
+///
+/// `fetch_page` returns the items of the requested page and the offset of the next page, if any.
+pub(crate) async fn fetch_all_pages<T, F, Fut>(fetch_page: F) -> anyhow::Result<Vec<T>>
+where
+    F: Fn(PaginationInput) -> Fut,
+    Fut: Future<Output = anyhow::Result<(Vec<T>, Option<u64>)>>,
+{
+    let mut items = Vec::new();
+    let mut offset = Some(0);
+    while let Some(current_offset) = offset {
+        let (page, next_offset) = fetch_page(PaginationInput {
+            offset: Some(current_offset),
+            limit: None,
+        })
+        .await?;
+        items.extend(page);
+        offset = next_offset;
+    }
+
+    Ok(items)
+}
@@ -0,0 +1,223 @@
+//! Waits for requests to be completed, for scripts and CI pipelines.
+
+use crate::{review::display_request_status, station::StationAgentError, DfxOrbit};
+use clap::Parser;
+use slog::warn;
+use station_api::{
+    GetRequestInput, GetRequestResponse, RequestApprovalStatusDTO, RequestStatusDTO,
+};
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+/// Wait until a request is completed, failed, rejected or cancelled
+///
+/// Exits with 0 if the request is completed, 2 if it is rejected, 3 if it is cancelled, 4 if it
+/// failed and 5 if the timeout expires first.
+#[derive(Debug, Clone, Parser)]
+pub struct RequestWaitArgs {
+    /// The ID of the request to wait for
+    pub request_id: String,
+
+    #[clap(flatten)]
+    pub options: WaitOptions,
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct WaitOptions {
+    /// Stop waiting after this many seconds
+    #[clap(long, default_value = "3600")]
+    pub timeout: u64,
+
+    /// Check the status of the request every this many seconds
+    #[clap(long, default_value = "5")]
+    pub poll_interval: u64,
+}
+
+/// The outcomes of waiting for a request other than its completion, each with its own exit code.
+#[derive(Error, Debug)]
+pub enum RequestWaitError {
+    #[error("The request was rejected")]
+    Rejected,
+    #[error("The request was cancelled{}", display_reason(.0))]
+    Cancelled(Option<String>),
+    #[error("The request failed{}", display_reason(.0))]
+    Failed(Option<String>),
+    #[error("Timed out after {0} seconds waiting for the request")]
+    TimedOut(u64),
+}
+
+impl RequestWaitError {
+    /// The exit code of the process, distinct from the code 1 of any other error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            RequestWaitError::Rejected => 2,
+            RequestWaitError::Cancelled(_) => 3,
+            RequestWaitError::Failed(_) => 4,
+            RequestWaitError::TimedOut(_) => 5,
+        }
+    }
+}
+
+fn display_reason(reason: &Option<String>) -> String {
+    reason
+        .as_ref()
+        .map(|reason| format!(": {reason}"))
+        .unwrap_or_default()
+}
+
+/// What has been reported so far, to only print the changes.
+#[derive(Debug, Default)]
+struct WaitProgress {
+    status: Option<&'static str>,
+    approvers: HashSet<String>,
+}
+
+impl DfxOrbit {
+    /// Polls a request until it reaches a terminal status, printing its status changes and new
+    /// approvals along the way. Polls that fail in the agent, e.g. due to network issues, are
+    /// retried until the timeout expires, while errors returned by the station are not.
+    pub(crate) async fn wait_for_request(
+        &self,
+        request_id: &str,
+        options: &WaitOptions,
+    ) -> anyhow::Result<()> {
+        let deadline = Instant::now() + Duration::from_secs(options.timeout);
+        let mut progress = WaitProgress::default();
+
+        loop {
+            let response = self
+                .station
+                .review_id(GetRequestInput {
+                    request_id: request_id.to_string(),
+                    with_full_info: Some(false),
+                })
+                .await;
+            match response {
+                Ok(response) => {
+                    for line in progress.update(&response) {
+                        println!("{line}");
+                    }
+
+                    if let Some(outcome) = request_outcome(&response.request.status) {
+                        return outcome.map_err(Into::into);
+                    }
+                }
+                // transient errors, e.g. network issues, should not abort a long wait
+                Err(StationAgentError::Agent(err)) => warn!(
+                    self.logger,
+                    "Failed to check the status of the request, retrying: {err}"
+                ),
+                Err(err) => return Err(err.into()),
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(RequestWaitError::TimedOut(options.timeout).into());
+            }
+            tokio::time::sleep(remaining.min(Duration::from_secs(options.poll_interval))).await;
+        }
+    }
+}
+
+impl WaitProgress {
+    fn update(&mut self, response: &GetRequestResponse) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        for approval in &response.request.approvals {
+            if !self.approvers.insert(approval.approver_id.clone()) {
+                continue;
+            }
+            let approver = response
+                .additional_info
+                .approvers
+                .iter()
+                .find(|approver| approver.id == approval.approver_id)
+                .map(|approver| approver.name.as_str())
+                .unwrap_or(approval.approver_id.as_str());
+            let decision = match approval.status {
+                RequestApprovalStatusDTO::Approved => "approved",
+                RequestApprovalStatusDTO::Rejected => "rejected",
+            };
+            lines.push(format!(
+                "{approver} {decision} the request{}",
+                display_reason(&approval.status_reason)
+            ));
+        }
+
+        let status = display_request_status(&response.request.status);
+        if self.status != Some(status) {
+            self.status = Some(status);
+            lines.push(format!("Status: {status}"));
+        }
+
+        lines
+    }
+}
+
+/// The outcome of a request in a terminal status, `None` while it can still change.
+fn request_outcome(status: &RequestStatusDTO) -> Option<Result<(), RequestWaitError>> {
+    match status {
+        RequestStatusDTO::Completed { .. } => Some(Ok(())),
+        RequestStatusDTO::Rejected => Some(Err(RequestWaitError::Rejected)),
+        RequestStatusDTO::Cancelled { reason } => {
+            Some(Err(RequestWaitError::Cancelled(reason.clone())))
+        }
+        RequestStatusDTO::Failed { reason } => Some(Err(RequestWaitError::Failed(reason.clone()))),
+        RequestStatusDTO::Created
+        | RequestStatusDTO::Approved
+        | RequestStatusDTO::Scheduled { .. }
+        | RequestStatusDTO::Processing { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::DfxOrbitArgs;
+
+    #[test]
+    fn waiting_is_not_a_request() {
+        assert!(DfxOrbitArgs::try_parse_from(["dfx-orbit", "wait", "request-id"]).is_ok());
+        assert!(DfxOrbitArgs::try_parse_from([
+            "dfx-orbit",
+            "request",
+            "--title",
+            "X",
+            "--wait",
+            "wait",
+            "request-id"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn only_terminal_statuses_have_an_outcome() {
+        assert!(request_outcome(&RequestStatusDTO::Approved).is_none());
+        assert!(request_outcome(&RequestStatusDTO::Processing {
+            started_at: "2024-01-01T00:00:00Z".to_string()
+        })
+        .is_none());
+        assert!(matches!(
+            request_outcome(&RequestStatusDTO::Completed {
+                completed_at: "2024-01-01T00:00:00Z".to_string()
+            }),
+            Some(Ok(()))
+        ));
+
+        let exit_code = |status| match request_outcome(&status) {
+            Some(Err(err)) => err.exit_code(),
+            _ => 0,
+        };
+        assert_eq!(exit_code(RequestStatusDTO::Rejected), 2);
+        assert_eq!(exit_code(RequestStatusDTO::Cancelled { reason: None }), 3);
+        assert_eq!(
+            exit_code(RequestStatusDTO::Failed {
+                reason: Some("out of cycles".to_string())
+            }),
+            4
+        );
+    }
+}